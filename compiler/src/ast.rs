@@ -0,0 +1,212 @@
+//! Abstract syntax tree of ASN.1 modules
+//!
+//! Only the subset of X.680 needed to generate types is represented. Constraints are parsed but
+//! not kept, except where noted.
+
+/// Default tagging mode of a module (X.680: 13.1)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagDefault {
+    Explicit,
+    Implicit,
+    Automatic,
+}
+
+/// An ASN.1 module definition
+#[derive(Clone, Debug, PartialEq)]
+pub struct Module {
+    pub name: String,
+    pub tag_default: TagDefault,
+    pub extensibility_implied: bool,
+    pub imports: Vec<Import>,
+    pub assignments: Vec<Assignment>,
+}
+
+impl Module {
+    /// Returns the type assignment with the given name
+    pub fn get_type(&self, name: &str) -> Option<&TypeAssignment> {
+        self.assignments.iter().find_map(|a| match a {
+            Assignment::Type(t) if t.name == name => Some(t),
+            _ => None,
+        })
+    }
+
+    /// Returns the value assignment with the given name
+    pub fn get_value(&self, name: &str) -> Option<&ValueAssignment> {
+        self.assignments.iter().find_map(|a| match a {
+            Assignment::Value(v) if v.name == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns the name of the module from which `symbol` is imported, if any
+    pub fn import_source(&self, symbol: &str) -> Option<&str> {
+        self.imports
+            .iter()
+            .find(|i| i.symbols.iter().any(|s| s == symbol))
+            .map(|i| i.module.as_str())
+    }
+}
+
+/// Symbols imported from another module
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Import {
+    pub symbols: Vec<String>,
+    pub module: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Assignment {
+    Type(TypeAssignment),
+    Value(ValueAssignment),
+}
+
+/// `Name ::= Type`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeAssignment {
+    pub name: String,
+    pub ty: Type,
+    /// Source text of the assignment
+    pub source: String,
+}
+
+/// `name Type ::= Value`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValueAssignment {
+    pub name: String,
+    pub ty: Type,
+    pub value: Value,
+    /// Source text of the assignment
+    pub source: String,
+}
+
+/// Class of a tag (X.680: 31.2)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagClass {
+    Universal,
+    Application,
+    ContextSpecific,
+    Private,
+}
+
+/// Tagging mode specified in a tagged type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagMode {
+    Explicit,
+    Implicit,
+}
+
+/// Restricted and unrestricted character string types
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StringKind {
+    Bmp,
+    General,
+    Graphic,
+    Ia5,
+    Numeric,
+    ObjectDescriptor,
+    Printable,
+    Teletex,
+    Universal,
+    Utf8,
+    Videotex,
+    Visible,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Type {
+    Any,
+    BitString(Vec<NamedNumber>),
+    Boolean,
+    Choice(Vec<Component>, bool),
+    Enumerated(Vec<EnumItem>, bool),
+    GeneralizedTime,
+    Integer(Vec<NamedNumber>),
+    Null,
+    ObjectIdentifier,
+    OctetString,
+    Real,
+    /// Type reference, optionally qualified by a module name
+    Reference(Option<String>, String),
+    RelativeOid,
+    /// Components, and `true` if the type is extensible
+    Sequence(Vec<Component>, bool),
+    SequenceOf(Box<Type>),
+    Set(Vec<Component>, bool),
+    SetOf(Box<Type>),
+    String(StringKind),
+    Tagged(Tag, Box<Type>),
+    UtcTime,
+}
+
+impl Type {
+    /// Returns `true` if the type is a SEQUENCE, SET, CHOICE or ENUMERATED type with a body
+    ///
+    /// These types must be declared as named Rust types.
+    pub fn is_structured(&self) -> bool {
+        matches!(
+            self,
+            Type::Choice(..) | Type::Enumerated(..) | Type::Sequence(..) | Type::Set(..)
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tag {
+    pub class: TagClass,
+    pub number: u32,
+    /// Tagging mode, if specified in the type
+    pub mode: Option<TagMode>,
+}
+
+/// `name(number)`, with the number given directly or as a value reference
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NamedNumber {
+    pub name: String,
+    pub value: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnumItem {
+    pub name: String,
+    pub value: Option<i64>,
+    /// `true` if the item is an extension addition
+    pub extension: bool,
+}
+
+/// Component of a SEQUENCE or SET, or alternative of a CHOICE
+#[derive(Clone, Debug, PartialEq)]
+pub struct Component {
+    pub name: String,
+    pub ty: Type,
+    pub optional: bool,
+    pub default: Option<Value>,
+    /// `true` if the component is an extension addition
+    pub extension: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    Boolean(bool),
+    Integer(i64),
+    Null,
+    CString(String),
+    BString(String),
+    HString(String),
+    /// Identifier, or value reference
+    Reference(String),
+    /// Components of an OBJECT IDENTIFIER value
+    ObjectIdentifier(Vec<OidComponent>),
+    /// `{}`
+    Empty,
+    /// Any other value (for ex. SEQUENCE values)
+    Other,
+}
+
+/// Component of an OBJECT IDENTIFIER value (X.680: 32.3)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OidComponent {
+    /// `name(n)` or `n`
+    Number(Option<String>, u64),
+    /// Value reference (or arc name, like `iso`)
+    Name(String),
+}
@@ -0,0 +1,618 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::ast::*;
+use crate::error::{Error, Result};
+use crate::resolve::{enumerated_values, Resolver};
+
+/// Rust code generator for a set of ASN.1 modules
+pub(crate) struct CodeGen<'a> {
+    modules: &'a [Module],
+    resolver: Resolver<'a>,
+    extern_types: &'a BTreeMap<String, String>,
+}
+
+impl<'a> CodeGen<'a> {
+    pub(crate) fn new(modules: &'a [Module], extern_types: &'a BTreeMap<String, String>) -> Self {
+        CodeGen {
+            modules,
+            resolver: Resolver::new(modules),
+            extern_types,
+        }
+    }
+
+    pub(crate) fn generate(&self) -> Result<String> {
+        let mut out = String::from("// Generated by asn1-rs-compiler. Do not edit.\n");
+        for (idx, module) in self.modules.iter().enumerate() {
+            let names = self.modules[..idx].iter().map(|m| m.name.as_str());
+            if names.clone().any(|n| n == module.name) {
+                return Err(Error::Duplicate(module.name.clone(), module.name.clone()));
+            }
+            let gen = ModuleGen {
+                gen: self,
+                idx,
+                module,
+                type_names: BTreeSet::new(),
+                const_names: BTreeSet::new(),
+                pending: Vec::new(),
+                items: Vec::new(),
+            };
+            out.push('\n');
+            out.push_str(&gen.generate()?);
+        }
+        Ok(out)
+    }
+
+    /// Returns `true` if the Rust type for `ty` has a lifetime parameter
+    fn needs_lifetime(&self, idx: usize, ty: &Type, visiting: &mut Vec<(usize, String)>) -> bool {
+        match ty {
+            Type::Any
+            | Type::Integer(_)
+            | Type::ObjectIdentifier
+            | Type::OctetString
+            | Type::RelativeOid
+            | Type::String(_) => true,
+            Type::BitString(_)
+            | Type::Boolean
+            | Type::Enumerated(..)
+            | Type::GeneralizedTime
+            | Type::Null
+            | Type::Real
+            | Type::UtcTime => false,
+            Type::Choice(components, _)
+            | Type::Sequence(components, _)
+            | Type::Set(components, _) => components
+                .iter()
+                .any(|c| self.needs_lifetime(idx, &c.ty, visiting)),
+            Type::SequenceOf(t) | Type::SetOf(t) | Type::Tagged(_, t) => {
+                self.needs_lifetime(idx, t, visiting)
+            }
+            Type::Reference(m, n) => match self.resolver.resolve_type(idx, m.as_deref(), n) {
+                Some((i, t)) => {
+                    let key = (i, t.name.clone());
+                    // recursive type: the lifetime depends on the other components
+                    if visiting.contains(&key) {
+                        return false;
+                    }
+                    visiting.push(key);
+                    let result = self.needs_lifetime(i, &t.ty, visiting);
+                    visiting.pop();
+                    result
+                }
+                None => matches!(self.extern_types.get(n), Some(s) if s.contains("'a")),
+            },
+        }
+    }
+}
+
+/// Code generator for one module
+struct ModuleGen<'g, 'a> {
+    gen: &'g CodeGen<'a>,
+    idx: usize,
+    module: &'a Module,
+    type_names: BTreeSet<String>,
+    const_names: BTreeSet<String>,
+    /// Anonymous types to declare: Rust name, ASN.1 path, type
+    pending: Vec<(String, String, Type)>,
+    items: Vec<String>,
+}
+
+impl ModuleGen<'_, '_> {
+    fn generate(mut self) -> Result<String> {
+        let module = self.module;
+        for assignment in &module.assignments {
+            if let Assignment::Type(t) = assignment {
+                self.declare_type(type_name(&t.name))?;
+            }
+        }
+        for assignment in &module.assignments {
+            match assignment {
+                Assignment::Type(t) => {
+                    self.gen_type_assignment(t)?;
+                    while !self.pending.is_empty() {
+                        let (name, path, ty) = self.pending.remove(0);
+                        let doc = format!("/// Anonymous type of `{}`\n", path);
+                        self.gen_structured(&name, &path, &ty, &doc)?;
+                    }
+                }
+                Assignment::Value(v) => self.gen_value_assignment(v)?,
+            }
+        }
+
+        let mut out = format!(
+            "/// Types and values of the ASN.1 module `{}`\npub mod {} {{\n    #![allow(clippy::upper_case_acronyms, missing_docs)]\n",
+            module.name,
+            snake_case(&module.name)
+        );
+        for item in &self.items {
+            out.push('\n');
+            for line in item.lines() {
+                if !line.is_empty() {
+                    out.push_str("    ");
+                    out.push_str(line);
+                }
+                out.push('\n');
+            }
+        }
+        out.push_str("}\n");
+        Ok(out)
+    }
+
+    fn declare_type(&mut self, name: String) -> Result<()> {
+        if !self.type_names.insert(name.clone()) {
+            return Err(Error::Duplicate(self.module.name.clone(), name));
+        }
+        Ok(())
+    }
+
+    fn lifetime(&self, ty: &Type) -> &'static str {
+        if self.gen.needs_lifetime(self.idx, ty, &mut Vec::new()) {
+            "<'a>"
+        } else {
+            ""
+        }
+    }
+
+    fn gen_type_assignment(&mut self, t: &TypeAssignment) -> Result<()> {
+        let name = type_name(&t.name);
+        let doc = source_doc(&t.source);
+        if t.ty.is_structured() {
+            return self.gen_structured(&name, &t.name, &t.ty, &doc);
+        }
+        // name of the anonymous type, if any
+        let mut inner = &t.ty;
+        while let Type::Tagged(_, ty) = inner {
+            inner = ty;
+        }
+        let hint = if inner.is_structured() {
+            format!("{}Content", name)
+        } else {
+            name.clone()
+        };
+        let target = self.rust_type(&t.ty, &hint, &t.name)?;
+        let lifetime = self.lifetime(&t.ty);
+        self.items.push(format!(
+            "{}pub type {}{} = {};\n",
+            doc, name, lifetime, target
+        ));
+        Ok(())
+    }
+
+    /// Declare a Rust type for a SEQUENCE, SET, CHOICE or ENUMERATED type
+    fn gen_structured(&mut self, name: &str, path: &str, ty: &Type, doc: &str) -> Result<()> {
+        let lifetime = self.lifetime(ty);
+        let item = match ty {
+            Type::Sequence(components, _) => {
+                self.gen_struct(name, path, components, "Sequence", lifetime)?
+            }
+            Type::Set(components, _) => self.gen_struct(name, path, components, "Set", lifetime)?,
+            Type::Choice(alternatives, extensible) => {
+                self.gen_choice(name, path, alternatives, *extensible, lifetime)?
+            }
+            Type::Enumerated(items, _) => gen_enumerated(name, items),
+            _ => return Ok(()),
+        };
+        self.items.push(format!("{}{}", doc, item));
+        Ok(())
+    }
+
+    fn tag_attribute(&self, tag: &Tag, inner: &Type) -> String {
+        let kind = if self.gen.resolver.is_explicit(self.idx, tag, inner) {
+            "tag_explicit"
+        } else {
+            "tag_implicit"
+        };
+        let class = match tag.class {
+            TagClass::Universal => "UNIVERSAL ",
+            TagClass::Application => "APPLICATION ",
+            TagClass::ContextSpecific => "",
+            TagClass::Private => "PRIVATE ",
+        };
+        format!("#[{}({}{})]", kind, class, tag.number)
+    }
+
+    fn gen_struct(
+        &mut self,
+        name: &str,
+        path: &str,
+        components: &[Component],
+        derive: &str,
+        lifetime: &str,
+    ) -> Result<String> {
+        let mut s = format!(
+            "#[derive(Debug, PartialEq, asn1_rs::{})]\npub struct {}{} {{\n",
+            derive, name, lifetime
+        );
+        for c in self.gen.resolver.auto_tag(self.idx, components).iter() {
+            let (tag_attribute, inner) = match &c.ty {
+                Type::Tagged(tag, inner) => (Some(self.tag_attribute(tag, inner)), inner.as_ref()),
+                ty => (None, ty),
+            };
+            let hint = format!("{}{}", name, type_name(&c.name));
+            let c_path = format!("{}.{}", path, c.name);
+            let mut rust = self.rust_type(inner, &hint, &c_path)?;
+            if let Some(attr) = tag_attribute {
+                s += &format!("    {}\n", attr);
+            }
+            if let Some(value) = &c.default {
+                let expr = self
+                    .default_expr(inner, &rust, value)
+                    .ok_or_else(|| Error::InvalidDefault(path.to_string(), c.name.clone()))?;
+                s += &format!("    #[default({})]\n", expr);
+            } else if c.optional || c.extension {
+                s += "    #[optional]\n";
+                rust = format!("Option<{}>", rust);
+            }
+            s += &format!("    pub {}: {},\n", field_name(&c.name), rust);
+        }
+        s += "}\n";
+        Ok(s)
+    }
+
+    fn gen_choice(
+        &mut self,
+        name: &str,
+        path: &str,
+        alternatives: &[Component],
+        extensible: bool,
+        lifetime: &str,
+    ) -> Result<String> {
+        let alternatives = self.gen.resolver.auto_tag(self.idx, alternatives);
+        // use the tagged variants of the derive if all alternatives have a context-specific tag,
+        // with the same tagging mode
+        let modes: Vec<_> = alternatives
+            .iter()
+            .map(|a| match &a.ty {
+                Type::Tagged(tag, inner) if tag.class == TagClass::ContextSpecific => {
+                    Some(self.gen.resolver.is_explicit(self.idx, tag, inner))
+                }
+                _ => None,
+            })
+            .collect();
+        let mode = match modes.first() {
+            Some(Some(explicit)) if modes.iter().all(|m| *m == Some(*explicit)) => Some(*explicit),
+            _ => None,
+        };
+
+        let mut s = String::from("#[derive(Debug, PartialEq, asn1_rs::Choice)]\n");
+        match mode {
+            Some(true) => s += "#[tagged_explicit]\n",
+            Some(false) => s += "#[tagged_implicit]\n",
+            None => (),
+        }
+        if extensible {
+            s += "#[asn1(extensible)]\n";
+        }
+        s += &format!("pub enum {}{} {{\n", name, lifetime);
+        for a in alternatives.iter() {
+            let hint = format!("{}{}", name, type_name(&a.name));
+            let a_path = format!("{}.{}", path, a.name);
+            let rust = match (&a.ty, mode) {
+                (Type::Tagged(tag, inner), Some(_)) => {
+                    s += &format!("    #[tag({})]\n", tag.number);
+                    self.rust_type(inner, &hint, &a_path)?
+                }
+                (ty, _) => self.rust_type(ty, &hint, &a_path)?,
+            };
+            s += &format!("    {}({}),\n", variant_name(&a.name), rust);
+        }
+        s += "}\n";
+        Ok(s)
+    }
+
+    /// Returns the Rust type for `ty`
+    ///
+    /// Anonymous SEQUENCE, SET, CHOICE and ENUMERATED types are declared using the `hint` name.
+    fn rust_type(&mut self, ty: &Type, hint: &str, path: &str) -> Result<String> {
+        let s = match ty {
+            Type::Any => "asn1_rs::Any<'a>".to_string(),
+            Type::BitString(_) => "asn1_rs::BitString".to_string(),
+            Type::Boolean => "bool".to_string(),
+            Type::Choice(..) | Type::Enumerated(..) | Type::Sequence(..) | Type::Set(..) => {
+                self.declare_type(hint.to_string())?;
+                self.pending
+                    .push((hint.to_string(), path.to_string(), ty.clone()));
+                format!("{}{}", hint, self.lifetime(ty))
+            }
+            Type::GeneralizedTime => "asn1_rs::GeneralizedTime".to_string(),
+            Type::Integer(_) => "asn1_rs::Integer<'a>".to_string(),
+            Type::Null => "asn1_rs::Null".to_string(),
+            Type::ObjectIdentifier | Type::RelativeOid => "asn1_rs::Oid<'a>".to_string(),
+            Type::OctetString => "asn1_rs::OctetString<'a>".to_string(),
+            Type::Real => "asn1_rs::Real".to_string(),
+            Type::Reference(m, n) => self.rust_reference(m.as_deref(), n)?,
+            Type::SequenceOf(t) => {
+                format!(
+                    "Vec<{}>",
+                    self.rust_type(t, &format!("{}Item", hint), path)?
+                )
+            }
+            Type::SetOf(t) => format!(
+                "asn1_rs::SetOf<{}>",
+                self.rust_type(t, &format!("{}Item", hint), path)?
+            ),
+            Type::String(kind) => format!("asn1_rs::{}<'a>", string_type(*kind)),
+            Type::Tagged(tag, inner) => {
+                let inner_rust = self.rust_type(inner, hint, path)?;
+                let kind = if self.gen.resolver.is_explicit(self.idx, tag, inner) {
+                    "Explicit"
+                } else {
+                    "Implicit"
+                };
+                match tag.class {
+                    TagClass::Universal => format!(
+                        "asn1_rs::TaggedValue<{}, asn1_rs::Error, asn1_rs::{}, {{ asn1_rs::Class::UNIVERSAL }}, {}>",
+                        inner_rust, kind, tag.number
+                    ),
+                    TagClass::Application => format!(
+                        "asn1_rs::Application{}<{}, asn1_rs::Error, {}>",
+                        kind, inner_rust, tag.number
+                    ),
+                    TagClass::ContextSpecific => format!(
+                        "asn1_rs::Tagged{}<{}, asn1_rs::Error, {}>",
+                        kind, inner_rust, tag.number
+                    ),
+                    TagClass::Private => format!(
+                        "asn1_rs::Private{}<{}, asn1_rs::Error, {}>",
+                        kind, inner_rust, tag.number
+                    ),
+                }
+            }
+            Type::UtcTime => "asn1_rs::UtcTime".to_string(),
+        };
+        Ok(s)
+    }
+
+    fn rust_reference(&self, module: Option<&str>, name: &str) -> Result<String> {
+        if let Some((i, t)) = self.gen.resolver.resolve_type(self.idx, module, name) {
+            let lifetime = if self.gen.needs_lifetime(i, &t.ty, &mut Vec::new()) {
+                "<'a>"
+            } else {
+                ""
+            };
+            let path = if i == self.idx {
+                String::new()
+            } else {
+                format!("super::{}::", snake_case(&self.gen.modules[i].name))
+            };
+            return Ok(format!("{}{}{}", path, type_name(&t.name), lifetime));
+        }
+        if let Some(s) = self.gen.extern_types.get(name) {
+            return Ok(s.clone());
+        }
+        match module.or_else(|| self.module.import_source(name)) {
+            Some(m) if self.gen.resolver.module_index(m).is_none() => {
+                Err(Error::UnresolvedImport(m.to_string(), name.to_string()))
+            }
+            _ => Err(Error::UnknownType(
+                self.module.name.clone(),
+                name.to_string(),
+            )),
+        }
+    }
+
+    /// Returns a Rust expression for the DEFAULT `value` of a component of type `ty`
+    fn default_expr(&self, ty: &Type, rust: &str, value: &Value) -> Option<String> {
+        let (idx, base) = self.gen.resolver.base_type(self.idx, ty)?;
+        match (base, value) {
+            (Type::Boolean, Value::Boolean(b)) => Some(b.to_string()),
+            (Type::Integer(named), value) => {
+                let n = self.gen.resolver.named_integer_value(idx, named, value)?;
+                Some(format!("asn1_rs::Integer::from({}_i64)", n))
+            }
+            (Type::Enumerated(items, _), Value::Reference(r))
+                if items.iter().any(|item| item.name == *r) =>
+            {
+                Some(format!("{}::{}", rust, variant_name(r)))
+            }
+            (Type::SequenceOf(_), Value::Empty) => Some("Vec::new()".to_string()),
+            (Type::SetOf(_), Value::Empty) => Some("asn1_rs::SetOf::new(Vec::new())".to_string()),
+            _ => None,
+        }
+    }
+
+    fn gen_value_assignment(&mut self, v: &ValueAssignment) -> Result<()> {
+        // values of information object classes, or of extern types, are ignored
+        let Some((idx, base)) = self.gen.resolver.base_type(self.idx, &v.ty) else {
+            return Ok(());
+        };
+        let name = const_name(&v.name);
+        let doc = source_doc(&v.source);
+        let item = match (base, &v.value) {
+            (Type::ObjectIdentifier, value) => match self.gen.resolver.oid_arcs(self.idx, value, 0)
+            {
+                Some(arcs) if arcs.len() >= 2 => {
+                    let arcs: Vec<_> = arcs.iter().map(|n| n.to_string()).collect();
+                    format!(
+                        "{}pub const {}: asn1_rs::Oid<'static> = asn1_rs::oid!({});\n",
+                        doc,
+                        name,
+                        arcs.join(".")
+                    )
+                }
+                _ => format!(
+                    "// `{}` is not generated: the value could not be resolved\n",
+                    v.name
+                ),
+            },
+            (Type::Integer(named), value) => {
+                match self.gen.resolver.named_integer_value(idx, named, value) {
+                    Some(n) => format!("{}pub const {}: i64 = {};\n", doc, name, n),
+                    None => format!(
+                        "// `{}` is not generated: the value could not be resolved\n",
+                        v.name
+                    ),
+                }
+            }
+            (Type::Boolean, Value::Boolean(b)) => {
+                format!("{}pub const {}: bool = {};\n", doc, name, b)
+            }
+            _ => return Ok(()),
+        };
+        if !self.const_names.insert(name.clone()) {
+            return Err(Error::Duplicate(self.module.name.clone(), name));
+        }
+        self.items.push(item);
+        Ok(())
+    }
+}
+
+fn gen_enumerated(name: &str, items: &[EnumItem]) -> String {
+    let mut s = format!(
+        "#[derive(Debug, Clone, Copy, PartialEq, Eq, asn1_rs::Enumerated)]\npub enum {} {{\n",
+        name
+    );
+    for (item, value) in items.iter().zip(enumerated_values(items)) {
+        s += &format!("    {} = {},\n", variant_name(&item.name), value);
+    }
+    s += "}\n";
+    s
+}
+
+fn string_type(kind: StringKind) -> &'static str {
+    match kind {
+        StringKind::Bmp => "BmpString",
+        StringKind::General => "GeneralString",
+        StringKind::Graphic => "GraphicString",
+        StringKind::Ia5 => "Ia5String",
+        StringKind::Numeric => "NumericString",
+        StringKind::ObjectDescriptor => "ObjectDescriptor",
+        StringKind::Printable => "PrintableString",
+        StringKind::Teletex => "TeletexString",
+        StringKind::Universal => "UniversalString",
+        StringKind::Utf8 => "Utf8String",
+        StringKind::Videotex => "VideotexString",
+        StringKind::Visible => "VisibleString",
+    }
+}
+
+/// Format the source of an assignment as a doc comment
+fn source_doc(source: &str) -> String {
+    let mut s = String::from("/// ```text\n");
+    for line in source.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            s += "///\n";
+        } else {
+            s += &format!("/// {}\n", line);
+        }
+    }
+    s += "/// ```\n";
+    s
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+/// Names of the prelude that are used in generated code
+const RESERVED_TYPE_NAMES: &[&str] = &["Option", "Self", "Vec"];
+
+/// Convert an ASN.1 type reference to a Rust type name (`Kerberos-Flags` -> `KerberosFlags`)
+pub(crate) fn type_name(s: &str) -> String {
+    let name: String = s
+        .split('-')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(c) => c.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    if RESERVED_TYPE_NAMES.contains(&name.as_str()) {
+        name + "_"
+    } else {
+        name
+    }
+}
+
+/// Convert an ASN.1 identifier to a Rust variant name (`dNSName` -> `DNSName`)
+pub(crate) fn variant_name(s: &str) -> String {
+    type_name(s)
+}
+
+/// Convert a name to snake case (`subjectPublicKeyInfo` -> `subject_public_key_info`)
+pub(crate) fn snake_case(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '-' {
+            out.push('_');
+            continue;
+        }
+        if c.is_ascii_uppercase() && i > 0 && !out.ends_with('_') {
+            let prev = chars[i - 1];
+            let next_lower = matches!(chars.get(i + 1), Some(n) if n.is_ascii_lowercase());
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower)
+            {
+                out.push('_');
+            }
+        }
+        out.push(c.to_ascii_lowercase());
+    }
+    out
+}
+
+/// Convert an ASN.1 identifier to a Rust field name
+pub(crate) fn field_name(s: &str) -> String {
+    let name = snake_case(s);
+    match name.as_str() {
+        "crate" | "self" | "super" => name + "_",
+        _ if RUST_KEYWORDS.contains(&name.as_str()) => format!("r#{}", name),
+        _ => name,
+    }
+}
+
+/// Convert an ASN.1 value reference to a Rust constant name (`id-pkix` -> `ID_PKIX`)
+pub(crate) fn const_name(s: &str) -> String {
+    snake_case(s).to_ascii_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn naming() {
+        assert_eq!(type_name("TBSCertificate"), "TBSCertificate");
+        assert_eq!(type_name("Kerberos-Flags"), "KerberosFlags");
+        assert_eq!(type_name("Option"), "Option_");
+        assert_eq!(variant_name("dNSName"), "DNSName");
+        assert_eq!(variant_name("rfc822Name"), "Rfc822Name");
+        assert_eq!(
+            field_name("subjectPublicKeyInfo"),
+            "subject_public_key_info"
+        );
+        assert_eq!(field_name("issuerUniqueID"), "issuer_unique_id");
+        assert_eq!(field_name("x400Address"), "x400_address");
+        assert_eq!(field_name("tkt-vno"), "tkt_vno");
+        assert_eq!(field_name("type"), "r#type");
+        assert_eq!(field_name("self"), "self_");
+        assert_eq!(snake_case("PKIX1Explicit88"), "pkix1_explicit88");
+        assert_eq!(const_name("id-pe"), "ID_PE");
+    }
+
+    #[test]
+    fn enumerated_numbering() {
+        let item = |name: &str, value, extension| EnumItem {
+            name: name.into(),
+            value,
+            extension,
+        };
+        let items = [
+            item("a", None, false),
+            item("b", Some(0), false),
+            item("c", None, false),
+            item("d", Some(5), true),
+            item("e", None, true),
+        ];
+        let s = gen_enumerated("E", &items);
+        assert!(s.contains("    A = 1,\n    B = 0,\n    C = 2,\n    D = 5,\n    E = 6,\n"));
+    }
+}
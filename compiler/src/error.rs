@@ -0,0 +1,53 @@
+use displaydoc::Display;
+use thiserror::Error;
+
+/// The error type for the ASN.1 compiler
+#[derive(Clone, Debug, Display, PartialEq, Eq, Error)]
+pub enum Error {
+    /// Syntax error at line {0}: {1}
+    Syntax(usize, String),
+    /// Unsupported ASN.1 construct at line {0}: {1}
+    Unsupported(usize, String),
+    /// Unexpected end of input
+    UnexpectedEnd,
+    /// Unknown type `{1}` in module `{0}`
+    UnknownType(String, String),
+    /// Symbol `{1}` imported from unknown module `{0}`
+    UnresolvedImport(String, String),
+    /// Duplicate definition of `{1}` in module `{0}`
+    Duplicate(String, String),
+    /// Invalid or unsupported DEFAULT value for component `{1}` of `{0}`
+    InvalidDefault(String, String),
+    /// I/O error on `{0}`: {1}
+    Io(String, String),
+}
+
+/// A specialized `Result` type for the ASN.1 compiler
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// The error type for decoding values using a [`Schema`](crate::schema::Schema)
+///
+/// The first field of each variant is the path of the value in the decoded object (for ex.
+/// `Certificate.tbsCertificate.extensions[1]`).
+#[derive(Clone, Debug, Display, PartialEq, Eq, Error)]
+pub enum DecodeError {
+    /// Unknown module `{0}`
+    UnknownModule(String),
+    /// Unknown type `{1}` in module `{0}`
+    UnknownType(String, String),
+    /// Unexpected object with class {1} and tag {2:?} at `{0}`
+    UnexpectedTag(String, asn1_rs::Class, asn1_rs::Tag),
+    /// Missing component `{1}` in `{0}`
+    MissingComponent(String, String),
+    /// Unexpected component in `{0}`
+    UnexpectedComponent(String),
+    /// Unknown value {1} at `{0}`
+    UnknownValue(String, i64),
+    /// Maximum nesting depth reached at `{0}`
+    MaxDepth(String),
+    /// Parsing error at `{0}`: {1}
+    Ber(String, asn1_rs::Error),
+}
+
+/// A specialized `Result` type for decoding values using a [`Schema`](crate::schema::Schema)
+pub type DecodeResult<T> = core::result::Result<T, DecodeError>;
@@ -0,0 +1,220 @@
+use crate::error::{Error, Result};
+
+/// Lexical item of an ASN.1 module (X.680: 12)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Token {
+    /// Reference or identifier (including keywords)
+    Word(String),
+    /// Unsigned number
+    Number(String),
+    /// Character string, with `""` escapes replaced
+    CString(String),
+    /// Binary string, without the quotes and the `B` suffix
+    BString(String),
+    /// Hexadecimal string, without the quotes and the `H` suffix
+    HString(String),
+    /// Any other lexical item
+    Symbol(&'static str),
+}
+
+/// A token, with its position in the source text
+#[derive(Clone, Debug)]
+pub(crate) struct Spanned {
+    pub token: Token,
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Multi-character symbols must be listed before their prefixes
+const SYMBOLS: &[&str] = &[
+    "::=", "...", "..", "{", "}", "(", ")", "[", "]", ",", ";", ".", "|", "^", "@", "!", "<", ">",
+    ":", "&", "-",
+];
+
+pub(crate) fn tokenize(s: &str) -> Result<Vec<Spanned>> {
+    let bytes = s.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    let mut line = 1;
+    while pos < bytes.len() {
+        let b = bytes[pos];
+        // whitespace
+        if b.is_ascii_whitespace() {
+            if b == b'\n' {
+                line += 1;
+            }
+            pos += 1;
+            continue;
+        }
+        // comments: `--` until end of line or next `--`, and (nested) `/* */`
+        if bytes[pos..].starts_with(b"--") {
+            pos += 2;
+            while pos < bytes.len() && bytes[pos] != b'\n' {
+                if bytes[pos..].starts_with(b"--") {
+                    pos += 2;
+                    break;
+                }
+                pos += 1;
+            }
+            continue;
+        }
+        if bytes[pos..].starts_with(b"/*") {
+            let mut depth = 0;
+            loop {
+                if pos >= bytes.len() {
+                    return Err(Error::UnexpectedEnd);
+                }
+                if bytes[pos..].starts_with(b"/*") {
+                    depth += 1;
+                    pos += 2;
+                } else if bytes[pos..].starts_with(b"*/") {
+                    depth -= 1;
+                    pos += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    if bytes[pos] == b'\n' {
+                        line += 1;
+                    }
+                    pos += 1;
+                }
+            }
+            continue;
+        }
+        let start = pos;
+        let start_line = line;
+        let token = if b.is_ascii_alphabetic() {
+            // a hyphen cannot be the last character, nor be followed by another hyphen
+            while pos < bytes.len() {
+                let c = bytes[pos];
+                if c.is_ascii_alphanumeric()
+                    || (c == b'-'
+                        && matches!(bytes.get(pos + 1), Some(n) if n.is_ascii_alphanumeric()))
+                {
+                    pos += 1;
+                } else {
+                    break;
+                }
+            }
+            Token::Word(s[start..pos].to_string())
+        } else if b.is_ascii_digit() {
+            while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+                pos += 1;
+            }
+            Token::Number(s[start..pos].to_string())
+        } else if b == b'"' {
+            let mut value = String::new();
+            pos += 1;
+            loop {
+                let Some(c) = s[pos..].chars().next() else {
+                    return Err(Error::UnexpectedEnd);
+                };
+                pos += c.len_utf8();
+                if c == '"' {
+                    if bytes.get(pos) == Some(&b'"') {
+                        pos += 1;
+                    } else {
+                        break;
+                    }
+                }
+                if c == '\n' {
+                    line += 1;
+                }
+                value.push(c);
+            }
+            Token::CString(value)
+        } else if b == b'\'' {
+            let mut value = String::new();
+            pos += 1;
+            loop {
+                match bytes.get(pos) {
+                    None => return Err(Error::UnexpectedEnd),
+                    Some(b'\'') => break,
+                    Some(c) if c.is_ascii_whitespace() => {
+                        if *c == b'\n' {
+                            line += 1;
+                        }
+                    }
+                    Some(c) if c.is_ascii_hexdigit() => value.push(char::from(*c)),
+                    Some(_) => {
+                        return Err(Error::Syntax(line, "invalid character in string".into()))
+                    }
+                }
+                pos += 1;
+            }
+            pos += 1;
+            match bytes.get(pos) {
+                Some(b'B') if value.bytes().all(|c| c == b'0' || c == b'1') => {
+                    pos += 1;
+                    Token::BString(value)
+                }
+                Some(b'H') => {
+                    pos += 1;
+                    Token::HString(value.to_ascii_uppercase())
+                }
+                _ => return Err(Error::Syntax(line, "invalid bstring or hstring".into())),
+            }
+        } else if let Some(sym) = SYMBOLS
+            .iter()
+            .find(|sym| bytes[pos..].starts_with(sym.as_bytes()))
+        {
+            pos += sym.len();
+            Token::Symbol(sym)
+        } else {
+            return Err(Error::Syntax(
+                line,
+                format!("unexpected character {:?}", &s[pos..].chars().next()),
+            ));
+        };
+        tokens.push(Spanned {
+            token,
+            line: start_line,
+            start,
+            end: pos,
+        });
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenize, Token};
+
+    #[test]
+    fn tokenize_module() {
+        let s = r#"A-B ::= { c-d 12, -- comment -- "x""y" '0101'B '0a 0B'H /* a /* b */ */ ... }"#;
+        let tokens: Vec<_> = tokenize(s).unwrap().into_iter().map(|t| t.token).collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Word("A-B".into()),
+                Token::Symbol("::="),
+                Token::Symbol("{"),
+                Token::Word("c-d".into()),
+                Token::Number("12".into()),
+                Token::Symbol(","),
+                Token::CString("x\"y".into()),
+                Token::BString("0101".into()),
+                Token::HString("0A0B".into()),
+                Token::Symbol("..."),
+                Token::Symbol("}"),
+            ]
+        );
+        // comment until end of line, and a trailing hyphen is not part of a word
+        let tokens = tokenize("a-- comment\nb- 1..2").unwrap();
+        assert_eq!(tokens.len(), 6);
+        assert_eq!(tokens[1].line, 2);
+        assert_eq!(tokens[2].token, Token::Symbol("-"));
+        assert_eq!(tokens[4].token, Token::Symbol(".."));
+    }
+
+    #[test]
+    fn tokenize_errors() {
+        assert!(tokenize("\"abc").is_err());
+        assert!(tokenize("'012'B").is_err());
+        assert!(tokenize("/* abc").is_err());
+        assert!(tokenize("a # b").is_err());
+    }
+}
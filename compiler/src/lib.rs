@@ -0,0 +1,183 @@
+//! # ASN.1 module compiler for `asn1-rs`
+//!
+//! This crate reads ASN.1 module definitions ([[X.680]]), and generates Rust types using the
+//! custom derive attributes of [asn1-rs](https://docs.rs/asn1-rs): `Sequence`, `Set`, `Choice`,
+//! `Enumerated`, and the `tag_explicit`, `tag_implicit`, `optional` and `default` field attributes.
+//!
+//! It is designed to be called from a build script, to avoid transcribing modules (for ex. from
+//! RFCs) by hand.
+//!
+//! ## Example
+//!
+//! In the `main` function of `build.rs`:
+//!
+//! ```rust,no_run
+//! asn1_rs_compiler::Generator::new()
+//!     .file("asn1/PKIX1Explicit88.asn1")
+//!     .file("asn1/PKIX1Implicit88.asn1")
+//!     .write_to_out_dir("pkix.rs")
+//!     .expect("ASN.1 compilation failed");
+//! ```
+//!
+//! Then, include the generated file in the crate (the crate must depend on `asn1-rs`):
+//!
+//! ```rust,ignore
+//! include!(concat!(env!("OUT_DIR"), "/pkix.rs"));
+//!
+//! use pkix1_explicit88::Certificate;
+//! ```
+//!
+//! ## Generated code
+//!
+//! Each ASN.1 module is generated as a Rust module, named after the module reference in snake
+//! case (for ex. `pkix1_explicit88`). Type references to other modules are resolved if the modules
+//! are compiled together.
+//!
+//! - SEQUENCE and SET types are generated as `struct`s, with fields for the components. OPTIONAL
+//!   components are `Option<T>`, and components with a DEFAULT value use the `default` attribute.
+//! - CHOICE types are generated as `enum`s. If all alternatives have context-specific tags with the
+//!   same mode, the `tagged_explicit` or `tagged_implicit` attributes are used, otherwise tagged
+//!   alternatives use types like `TaggedExplicit`.
+//! - ENUMERATED types are generated as `enum`s with explicit discriminants.
+//! - Other type assignments are generated as type aliases, using the `asn1-rs` types (for ex.
+//!   `Integer<'a>`, `OctetString<'a>` or `Any<'a>` for open types). `SEQUENCE OF` is a `Vec`, and
+//!   `SET OF` is a `SetOf`.
+//! - Anonymous SEQUENCE, SET, CHOICE and ENUMERATED types are declared as types named after the
+//!   containing type and component (for ex. `TBSCertListRevokedCertificatesItem`).
+//! - OBJECT IDENTIFIER, INTEGER and BOOLEAN value assignments are generated as constants.
+//!
+//! The tagging mode of the module (EXPLICIT, IMPLICIT or AUTOMATIC) is applied to tagged types.
+//! Constraints are ignored. Extension additions are generated as OPTIONAL components.
+//!
+//! Like other types using the `asn1-rs` custom derive attributes, the encoders (`ToBer` and `ToDer`)
+//! are always generated, and do not require the `std` feature.
+//!
+//! ## Limitations
+//!
+//! Information object classes and object sets are ignored, and their fields are generated as
+//! `Any<'a>`. Parameterized types, `COMPONENTS OF`, and some types (for ex. `EMBEDDED PDV`) are
+//! not supported.
+//!
+//! Types from modules that are not compiled can be mapped to Rust types using
+//! [`Generator::extern_type`]. Such types are assumed not to be untagged CHOICE types.
+//!
+//! ## Runtime decoding
+//!
+//! When modules are only known at runtime, the [`schema`] module can be used instead of generated
+//! code: a [`schema::Schema`] decodes `Any` objects into trees of values, annotated with the names
+//! of the types and components.
+//!
+//! [X.680]: http://www.itu.int/rec/T-REC-X.680/en "Abstract Syntax Notation One (ASN.1):
+//!   Specification of basic notation."
+#![deny(unstable_features, unused_import_braces, unused_qualifications)]
+#![forbid(unsafe_code)]
+#![warn(missing_debug_implementations)]
+
+pub mod ast;
+mod codegen;
+mod error;
+mod lexer;
+mod parser;
+mod resolve;
+pub mod schema;
+
+pub use error::*;
+pub use parser::parse_modules;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use codegen::CodeGen;
+
+#[derive(Clone, Debug)]
+enum Source {
+    File(PathBuf),
+    Text(String),
+}
+
+/// Generator of Rust code from ASN.1 modules
+///
+/// All modules are compiled together, so types imported from other modules of the same
+/// generator are resolved.
+#[derive(Clone, Debug, Default)]
+pub struct Generator {
+    sources: Vec<Source>,
+    extern_types: BTreeMap<String, String>,
+}
+
+impl Generator {
+    /// Creates a new generator, without modules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file containing ASN.1 module definitions
+    pub fn file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.sources.push(Source::File(path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Add ASN.1 module definitions, as text
+    pub fn source<S: Into<String>>(mut self, text: S) -> Self {
+        self.sources.push(Source::Text(text.into()));
+        self
+    }
+
+    /// Map the ASN.1 type `asn1_name` to the Rust type `rust_type`
+    ///
+    /// This is used for types imported from modules that are not compiled by this generator.
+    /// `rust_type` is used as is, and must contain the lifetime `'a` if required (for ex.
+    /// `x509_parser::x509::AlgorithmIdentifier<'a>`).
+    pub fn extern_type<S1: Into<String>, S2: Into<String>>(
+        mut self,
+        asn1_name: S1,
+        rust_type: S2,
+    ) -> Self {
+        self.extern_types.insert(asn1_name.into(), rust_type.into());
+        self
+    }
+
+    /// Parse all modules, and return the generated Rust code
+    pub fn generate(&self) -> Result<String> {
+        let mut modules = Vec::new();
+        for source in &self.sources {
+            let text = match source {
+                Source::File(path) => fs::read_to_string(path)
+                    .map_err(|e| Error::Io(path.display().to_string(), e.to_string()))?,
+                Source::Text(text) => text.clone(),
+            };
+            modules.extend(parse_modules(&text)?);
+        }
+        CodeGen::new(&modules, &self.extern_types).generate()
+    }
+
+    /// Generate Rust code, and write it to `path`
+    ///
+    /// The file is not modified if the content did not change.
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let code = self.generate()?;
+        if fs::read_to_string(path).ok().as_deref() == Some(code.as_str()) {
+            return Ok(());
+        }
+        fs::write(path, code).map_err(|e| Error::Io(path.display().to_string(), e.to_string()))
+    }
+
+    /// Generate Rust code in the file `name` of the `OUT_DIR` directory, and return its path
+    ///
+    /// This function is meant to be called from build scripts: it also instructs `cargo` to run
+    /// the build script again if one of the source files changes.
+    pub fn write_to_out_dir(&self, name: &str) -> Result<PathBuf> {
+        let out_dir = std::env::var_os("OUT_DIR")
+            .ok_or_else(|| Error::Io("OUT_DIR".into(), "variable not set".into()))?;
+        for source in &self.sources {
+            if let Source::File(path) = source {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+        let path = Path::new(&out_dir).join(name);
+        self.write(&path)?;
+        Ok(path)
+    }
+}
@@ -0,0 +1,766 @@
+use core::convert::TryFrom;
+
+use crate::ast::*;
+use crate::error::{Error, Result};
+use crate::lexer::{tokenize, Spanned, Token};
+
+/// Parse all the ASN.1 module definitions contained in `s`
+///
+/// Assignments of information object classes, value sets and object sets are accepted, but
+/// ignored. Parameterized assignments and `COMPONENTS OF` are not supported.
+pub fn parse_modules(s: &str) -> Result<Vec<Module>> {
+    let tokens = tokenize(s)?;
+    let mut parser = Parser {
+        src: s,
+        tokens,
+        pos: 0,
+    };
+    let mut modules = Vec::new();
+    while parser.peek().is_some() {
+        modules.push(parser.parse_module()?);
+    }
+    Ok(modules)
+}
+
+fn is_upper(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    tokens: Vec<Spanned>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, n: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + n).map(|t| &t.token)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map_or(0, |t| t.line)
+    }
+
+    fn error<S: Into<String>>(&self, msg: S) -> Error {
+        Error::Syntax(self.line(), msg.into())
+    }
+
+    fn unsupported<S: Into<String>>(&self, msg: S) -> Error {
+        Error::Unsupported(self.line(), msg.into())
+    }
+
+    fn next(&mut self) -> Result<Token> {
+        let token = self.peek().cloned().ok_or(Error::UnexpectedEnd)?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    /// Returns the source text from the token at index `start` to the last consumed token
+    fn source_from(&self, start: usize) -> String {
+        match (
+            self.tokens.get(start),
+            self.tokens.get(self.pos.wrapping_sub(1)),
+        ) {
+            (Some(first), Some(last)) if start < self.pos => {
+                self.src[first.start..last.end].to_string()
+            }
+            _ => String::new(),
+        }
+    }
+
+    fn is_word(&self, w: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(s)) if s == w)
+    }
+
+    fn is_symbol(&self, sym: &str) -> bool {
+        matches!(self.peek(), Some(Token::Symbol(s)) if *s == sym)
+    }
+
+    fn eat_word(&mut self, w: &str) -> bool {
+        let found = self.is_word(w);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn eat_symbol(&mut self, sym: &str) -> bool {
+        let found = self.is_symbol(sym);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_word(&mut self, w: &str) -> Result<()> {
+        if !self.eat_word(w) {
+            return Err(self.error(format!("expected `{}`", w)));
+        }
+        Ok(())
+    }
+
+    fn expect_symbol(&mut self, sym: &str) -> Result<()> {
+        if !self.eat_symbol(sym) {
+            return Err(self.error(format!("expected `{}`", sym)));
+        }
+        Ok(())
+    }
+
+    fn word(&mut self) -> Result<String> {
+        match self.next()? {
+            Token::Word(s) => Ok(s),
+            _ => {
+                self.pos -= 1;
+                Err(self.error("expected an identifier or a reference"))
+            }
+        }
+    }
+
+    fn unsigned(&mut self) -> Result<u64> {
+        match self.next()? {
+            Token::Number(n) => n.parse().map_err(|_| self.error("number too large")),
+            _ => {
+                self.pos -= 1;
+                Err(self.error("expected a number"))
+            }
+        }
+    }
+
+    fn signed(&mut self) -> Result<i64> {
+        let negative = self.eat_symbol("-");
+        let n = i64::try_from(self.unsigned()?).map_err(|_| self.error("number too large"))?;
+        Ok(if negative { -n } else { n })
+    }
+
+    /// Skip tokens until the matching `close` symbol (the current token must be `open`)
+    fn skip_balanced(&mut self, open: &str, close: &str) -> Result<()> {
+        self.expect_symbol(open)?;
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next()? {
+                Token::Symbol(s) if s == open => depth += 1,
+                Token::Symbol(s) if s == close => depth -= 1,
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// Skip the constraints following a type
+    fn skip_constraints(&mut self) -> Result<()> {
+        while self.is_symbol("(") {
+            self.skip_balanced("(", ")")?;
+        }
+        Ok(())
+    }
+
+    /// Skip an exception specification (`! value`), until the next `,` or `}`
+    fn skip_exception(&mut self) -> Result<()> {
+        if self.eat_symbol("!") {
+            while !self.is_symbol(",") && !self.is_symbol("}") && !self.is_symbol("]") {
+                match self.peek() {
+                    Some(Token::Symbol("{")) => self.skip_balanced("{", "}")?,
+                    Some(Token::Symbol("(")) => self.skip_balanced("(", ")")?,
+                    _ => {
+                        self.next()?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_module(&mut self) -> Result<Module> {
+        let name = self.word()?;
+        if !is_upper(&name) {
+            return Err(self.error("expected a module reference"));
+        }
+        if self.is_symbol("{") {
+            self.skip_balanced("{", "}")?;
+        }
+        self.expect_word("DEFINITIONS")?;
+        let tag_default = if self.eat_word("EXPLICIT") {
+            Some(TagDefault::Explicit)
+        } else if self.eat_word("IMPLICIT") {
+            Some(TagDefault::Implicit)
+        } else if self.eat_word("AUTOMATIC") {
+            Some(TagDefault::Automatic)
+        } else {
+            None
+        };
+        if tag_default.is_some() {
+            self.expect_word("TAGS")?;
+        }
+        let tag_default = tag_default.unwrap_or(TagDefault::Explicit);
+        let extensibility_implied = self.eat_word("EXTENSIBILITY");
+        if extensibility_implied {
+            self.expect_word("IMPLIED")?;
+        }
+        self.expect_symbol("::=")?;
+        self.expect_word("BEGIN")?;
+
+        if self.eat_word("EXPORTS") {
+            while !self.eat_symbol(";") {
+                self.next()?;
+            }
+        }
+        let mut imports = Vec::new();
+        if self.eat_word("IMPORTS") {
+            while !self.eat_symbol(";") {
+                let mut symbols = Vec::new();
+                loop {
+                    symbols.push(self.word()?);
+                    // parameterized reference
+                    if self.is_symbol("{") {
+                        self.skip_balanced("{", "}")?;
+                    }
+                    if !self.eat_symbol(",") {
+                        break;
+                    }
+                }
+                self.expect_word("FROM")?;
+                let module = self.word()?;
+                if self.is_symbol("{") {
+                    self.skip_balanced("{", "}")?;
+                }
+                imports.push(Import { symbols, module });
+            }
+        }
+
+        let mut assignments = Vec::new();
+        while !self.eat_word("END") {
+            if let Some(assignment) = self.parse_assignment()? {
+                assignments.push(assignment);
+            }
+        }
+        Ok(Module {
+            name,
+            tag_default,
+            extensibility_implied,
+            imports,
+            assignments,
+        })
+    }
+
+    fn parse_assignment(&mut self) -> Result<Option<Assignment>> {
+        let start = self.pos;
+        let name = self.word()?;
+        if self.is_symbol("{") {
+            return Err(self.unsupported(format!("parameterized assignment `{}`", name)));
+        }
+        if is_upper(&name) && self.eat_symbol("::=") {
+            // information object class
+            if self.eat_word("CLASS") {
+                self.skip_balanced("{", "}")?;
+                if self.eat_word("WITH") {
+                    self.expect_word("SYNTAX")?;
+                    self.skip_balanced("{", "}")?;
+                }
+                return Ok(None);
+            }
+            let ty = self.parse_type()?;
+            let source = self.source_from(start);
+            return Ok(Some(Assignment::Type(TypeAssignment { name, ty, source })));
+        }
+        let ty = self.parse_type()?;
+        self.expect_symbol("::=")?;
+        if is_upper(&name) {
+            // value set, or information object set
+            self.skip_balanced("{", "}")?;
+            return Ok(None);
+        }
+        let value = self.parse_value()?;
+        let source = self.source_from(start);
+        Ok(Some(Assignment::Value(ValueAssignment {
+            name,
+            ty,
+            value,
+            source,
+        })))
+    }
+
+    fn parse_type(&mut self) -> Result<Type> {
+        let ty = if self.eat_symbol("[") {
+            let class = if self.eat_word("UNIVERSAL") {
+                TagClass::Universal
+            } else if self.eat_word("APPLICATION") {
+                TagClass::Application
+            } else if self.eat_word("PRIVATE") {
+                TagClass::Private
+            } else {
+                TagClass::ContextSpecific
+            };
+            let number =
+                u32::try_from(self.unsigned()?).map_err(|_| self.error("tag number too large"))?;
+            self.expect_symbol("]")?;
+            let mode = if self.eat_word("EXPLICIT") {
+                Some(TagMode::Explicit)
+            } else if self.eat_word("IMPLICIT") {
+                Some(TagMode::Implicit)
+            } else {
+                None
+            };
+            let tag = Tag {
+                class,
+                number,
+                mode,
+            };
+            Type::Tagged(tag, Box::new(self.parse_type()?))
+        } else {
+            self.parse_untagged_type()?
+        };
+        self.skip_constraints()?;
+        Ok(ty)
+    }
+
+    fn parse_untagged_type(&mut self) -> Result<Type> {
+        let word = self.word()?;
+        let ty = match word.as_str() {
+            "ANY" => {
+                if self.eat_word("DEFINED") {
+                    self.expect_word("BY")?;
+                    self.word()?;
+                }
+                Type::Any
+            }
+            "BIT" => {
+                self.expect_word("STRING")?;
+                Type::BitString(self.parse_named_numbers()?)
+            }
+            "BOOLEAN" => Type::Boolean,
+            "CHOICE" => {
+                let (components, extensible) = self.parse_components(true)?;
+                if components.is_empty() {
+                    return Err(self.error("CHOICE without alternatives"));
+                }
+                Type::Choice(components, extensible)
+            }
+            "ENUMERATED" => {
+                let (items, extensible) = self.parse_enumerated_items()?;
+                Type::Enumerated(items, extensible)
+            }
+            "GeneralizedTime" => Type::GeneralizedTime,
+            "INTEGER" => Type::Integer(self.parse_named_numbers()?),
+            "NULL" => Type::Null,
+            "OBJECT" => {
+                self.expect_word("IDENTIFIER")?;
+                Type::ObjectIdentifier
+            }
+            "OCTET" => {
+                self.expect_word("STRING")?;
+                Type::OctetString
+            }
+            "REAL" => Type::Real,
+            "RELATIVE-OID" => Type::RelativeOid,
+            "SEQUENCE" | "SET" => {
+                let is_sequence = word == "SEQUENCE";
+                if self.is_symbol("{") {
+                    let (components, extensible) = self.parse_components(false)?;
+                    if is_sequence {
+                        Type::Sequence(components, extensible)
+                    } else {
+                        Type::Set(components, extensible)
+                    }
+                } else {
+                    // size constraint, either `SIZE (...)` or `(SIZE (...))`
+                    if self.eat_word("SIZE") {
+                        self.skip_balanced("(", ")")?;
+                    }
+                    self.skip_constraints()?;
+                    self.expect_word("OF")?;
+                    // optional identifier of the element
+                    if matches!(self.peek(), Some(Token::Word(w)) if !is_upper(w)) {
+                        self.next()?;
+                    }
+                    let element = Box::new(self.parse_type()?);
+                    if is_sequence {
+                        Type::SequenceOf(element)
+                    } else {
+                        Type::SetOf(element)
+                    }
+                }
+            }
+            "UTCTime" => Type::UtcTime,
+            "BMPString" => Type::String(StringKind::Bmp),
+            "GeneralString" => Type::String(StringKind::General),
+            "GraphicString" => Type::String(StringKind::Graphic),
+            "IA5String" => Type::String(StringKind::Ia5),
+            "ISO646String" | "VisibleString" => Type::String(StringKind::Visible),
+            "NumericString" => Type::String(StringKind::Numeric),
+            "ObjectDescriptor" => Type::String(StringKind::ObjectDescriptor),
+            "PrintableString" => Type::String(StringKind::Printable),
+            "T61String" | "TeletexString" => Type::String(StringKind::Teletex),
+            "UniversalString" => Type::String(StringKind::Universal),
+            "UTF8String" => Type::String(StringKind::Utf8),
+            "VideotexString" => Type::String(StringKind::Videotex),
+            "CHARACTER" | "DATE" | "DATE-TIME" | "DURATION" | "EMBEDDED" | "EXTERNAL"
+            | "INSTANCE" | "TIME" | "TIME-OF-DAY" => {
+                return Err(self.unsupported(format!("type `{}`", word)));
+            }
+            _ if is_upper(&word) => {
+                if self.eat_symbol(".") {
+                    if self.eat_symbol("&") {
+                        // open type (X.681: 14.1), for ex. `TYPE-IDENTIFIER.&Type`
+                        self.word()?;
+                        while self.eat_symbol(".") {
+                            self.expect_symbol("&")?;
+                            self.word()?;
+                        }
+                        Type::Any
+                    } else {
+                        Type::Reference(Some(word), self.word()?)
+                    }
+                } else if self.is_symbol("{") {
+                    return Err(self.unsupported(format!("parameterized type `{}`", word)));
+                } else {
+                    Type::Reference(None, word)
+                }
+            }
+            _ => {
+                self.pos -= 1;
+                return Err(self.error("expected a type"));
+            }
+        };
+        Ok(ty)
+    }
+
+    /// Parse the optional list of named numbers of INTEGER or named bits of BIT STRING
+    fn parse_named_numbers(&mut self) -> Result<Vec<NamedNumber>> {
+        let mut numbers = Vec::new();
+        if !self.eat_symbol("{") {
+            return Ok(numbers);
+        }
+        loop {
+            let name = self.word()?;
+            self.expect_symbol("(")?;
+            let value = match self.peek() {
+                Some(Token::Word(_)) => Value::Reference(self.word()?),
+                _ => Value::Integer(self.signed()?),
+            };
+            self.expect_symbol(")")?;
+            numbers.push(NamedNumber { name, value });
+            if !self.eat_symbol(",") {
+                break;
+            }
+        }
+        self.expect_symbol("}")?;
+        Ok(numbers)
+    }
+
+    fn parse_enumerated_items(&mut self) -> Result<(Vec<EnumItem>, bool)> {
+        self.expect_symbol("{")?;
+        let mut items = Vec::new();
+        let mut extensible = false;
+        loop {
+            if self.eat_symbol("...") {
+                extensible = true;
+                self.skip_exception()?;
+            } else {
+                let name = self.word()?;
+                let value = if self.eat_symbol("(") {
+                    let n = self.signed()?;
+                    self.expect_symbol(")")?;
+                    Some(n)
+                } else {
+                    None
+                };
+                items.push(EnumItem {
+                    name,
+                    value,
+                    extension: extensible,
+                });
+            }
+            if !self.eat_symbol(",") {
+                break;
+            }
+        }
+        self.expect_symbol("}")?;
+        Ok((items, extensible))
+    }
+
+    /// Parse the components of a SEQUENCE or SET, or the alternatives of a CHOICE
+    fn parse_components(&mut self, choice: bool) -> Result<(Vec<Component>, bool)> {
+        self.expect_symbol("{")?;
+        let mut components = Vec::new();
+        let mut extensible = false;
+        let mut in_extension = false;
+        if self.eat_symbol("}") {
+            return Ok((components, extensible));
+        }
+        loop {
+            if self.eat_symbol("...") {
+                // a second ellipsis ends the extension additions
+                extensible = true;
+                in_extension = !in_extension;
+                self.skip_exception()?;
+            } else if self.is_symbol("[") && self.peek_at(1) == Some(&Token::Symbol("[")) {
+                // extension addition group
+                self.pos += 2;
+                if matches!(self.peek(), Some(Token::Number(_))) {
+                    self.next()?;
+                    self.expect_symbol(":")?;
+                }
+                loop {
+                    components.push(self.parse_component(choice, true)?);
+                    if !self.eat_symbol(",") {
+                        break;
+                    }
+                }
+                self.expect_symbol("]")?;
+                self.expect_symbol("]")?;
+            } else if self.is_word("COMPONENTS") {
+                return Err(self.unsupported("COMPONENTS OF"));
+            } else {
+                components.push(self.parse_component(choice, in_extension)?);
+            }
+            if !self.eat_symbol(",") {
+                break;
+            }
+        }
+        self.expect_symbol("}")?;
+        Ok((components, extensible))
+    }
+
+    fn parse_component(&mut self, choice: bool, extension: bool) -> Result<Component> {
+        let name = self.word()?;
+        if is_upper(&name) {
+            return Err(self.error("expected an identifier"));
+        }
+        let ty = self.parse_type()?;
+        let mut optional = false;
+        let mut default = None;
+        if !choice {
+            if self.eat_word("OPTIONAL") {
+                optional = true;
+            } else if self.eat_word("DEFAULT") {
+                default = Some(self.parse_value()?);
+            }
+        }
+        Ok(Component {
+            name,
+            ty,
+            optional,
+            default,
+            extension,
+        })
+    }
+
+    fn parse_value(&mut self) -> Result<Value> {
+        let value = match self.next()? {
+            Token::Word(w) => match w.as_str() {
+                "TRUE" => Value::Boolean(true),
+                "FALSE" => Value::Boolean(false),
+                "NULL" => Value::Null,
+                _ => {
+                    // CHOICE value, or open type value
+                    if self.eat_symbol(":") {
+                        self.parse_value()?;
+                        Value::Other
+                    } else if is_upper(&w) {
+                        Value::Other
+                    } else {
+                        Value::Reference(w)
+                    }
+                }
+            },
+            Token::Number(_) | Token::Symbol("-") => {
+                self.pos -= 1;
+                let n = self.signed()?;
+                // REAL value
+                if self.is_symbol(".") && matches!(self.peek_at(1), Some(Token::Number(_))) {
+                    self.pos += 2;
+                    Value::Other
+                } else {
+                    Value::Integer(n)
+                }
+            }
+            Token::CString(s) => Value::CString(s),
+            Token::BString(s) => Value::BString(s),
+            Token::HString(s) => Value::HString(s),
+            Token::Symbol("{") => {
+                self.pos -= 1;
+                self.parse_braced_value()?
+            }
+            _ => {
+                self.pos -= 1;
+                return Err(self.error("expected a value"));
+            }
+        };
+        Ok(value)
+    }
+
+    /// Parse a value in braces: an OBJECT IDENTIFIER value, an empty value, or any other value
+    fn parse_braced_value(&mut self) -> Result<Value> {
+        let start = self.pos;
+        self.expect_symbol("{")?;
+        if self.eat_symbol("}") {
+            return Ok(Value::Empty);
+        }
+        let mut components = Vec::new();
+        loop {
+            match self.peek() {
+                Some(Token::Symbol("}")) => {
+                    self.pos += 1;
+                    return Ok(Value::ObjectIdentifier(components));
+                }
+                Some(Token::Number(_)) => {
+                    components.push(OidComponent::Number(None, self.unsigned()?))
+                }
+                Some(Token::Word(w)) if !is_upper(w) => {
+                    let name = self.word()?;
+                    if self.is_symbol("(") && matches!(self.peek_at(1), Some(Token::Number(_))) {
+                        self.pos += 1;
+                        let n = self.unsigned()?;
+                        if !self.eat_symbol(")") {
+                            break;
+                        }
+                        components.push(OidComponent::Number(Some(name), n));
+                    } else {
+                        components.push(OidComponent::Name(name));
+                    }
+                }
+                _ => break,
+            }
+        }
+        // not an OBJECT IDENTIFIER value
+        self.pos = start;
+        self.skip_balanced("{", "}")?;
+        Ok(Value::Other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_modules;
+    use crate::ast::*;
+    use crate::error::Error;
+
+    #[test]
+    fn parse_module_header() {
+        let s = r#"
+        Test-Module { iso(1) 2 } DEFINITIONS IMPLICIT TAGS ::=
+        BEGIN
+        EXPORTS ALL;
+        IMPORTS A, b FROM Other { 1 2 3 }
+                C FROM Third;
+        T ::= INTEGER
+        END
+        M2 DEFINITIONS AUTOMATIC TAGS EXTENSIBILITY IMPLIED ::= BEGIN END
+        "#;
+        let modules = parse_modules(s).unwrap();
+        assert_eq!(modules.len(), 2);
+        let m = &modules[0];
+        assert_eq!(m.name, "Test-Module");
+        assert_eq!(m.tag_default, TagDefault::Implicit);
+        assert_eq!(m.imports.len(), 2);
+        assert_eq!(m.imports[0].symbols, vec!["A", "b"]);
+        assert_eq!(m.import_source("C"), Some("Third"));
+        assert_eq!(m.get_type("T").unwrap().ty, Type::Integer(vec![]));
+        assert_eq!(m.get_type("T").unwrap().source, "T ::= INTEGER");
+        assert_eq!(modules[1].tag_default, TagDefault::Automatic);
+        assert!(modules[1].extensibility_implied);
+    }
+
+    #[test]
+    fn parse_types() {
+        let s = r#"
+        M DEFINITIONS ::= BEGIN
+        S ::= SEQUENCE {
+            version  [0] EXPLICIT Version DEFAULT v1,
+            a        INTEGER (0..MAX) OPTIONAL,
+            b        SEQUENCE SIZE (1..MAX) OF SET OF B,
+            c        [APPLICATION 2] IMPLICIT OCTET STRING,
+            ...,
+            [[ d BOOLEAN ]],
+            e        M.T,
+            ...,
+            f        NULL
+        }
+        Version ::= INTEGER { v1(0), v2(1) }
+        E ::= ENUMERATED { a, b(3), ..., c }
+        C ::= CHOICE { x TYPE-IDENTIFIER.&Type, y ANY DEFINED BY a }
+        ATTRIBUTE ::= CLASS { &id OBJECT IDENTIFIER UNIQUE } WITH SYNTAX { ID &id }
+        Attrs ATTRIBUTE ::= { a | b }
+        id-x OBJECT IDENTIFIER ::= { iso(1) member-body(2) 840 }
+        ub INTEGER ::= -1
+        END
+        "#;
+        let modules = parse_modules(s).unwrap();
+        let m = &modules[0];
+        let Type::Sequence(components, true) = &m.get_type("S").unwrap().ty else {
+            panic!("not an extensible SEQUENCE");
+        };
+        assert_eq!(components.len(), 7);
+        assert_eq!(
+            components[0].ty,
+            Type::Tagged(
+                Tag {
+                    class: TagClass::ContextSpecific,
+                    number: 0,
+                    mode: Some(TagMode::Explicit)
+                },
+                Box::new(Type::Reference(None, "Version".into()))
+            )
+        );
+        assert_eq!(components[0].default, Some(Value::Reference("v1".into())));
+        assert!(components[1].optional);
+        assert_eq!(
+            components[2].ty,
+            Type::SequenceOf(Box::new(Type::SetOf(Box::new(Type::Reference(
+                None,
+                "B".into()
+            )))))
+        );
+        assert!(!components[3].extension);
+        assert!(components[4].extension);
+        assert_eq!(components[4].name, "d");
+        assert_eq!(
+            components[5].ty,
+            Type::Reference(Some("M".into()), "T".into())
+        );
+        assert!(components[5].extension);
+        assert!(!components[6].extension);
+
+        let Type::Enumerated(items, true) = &m.get_type("E").unwrap().ty else {
+            panic!("not an extensible ENUMERATED");
+        };
+        assert_eq!(items[1].value, Some(3));
+        assert!(items[2].extension);
+
+        let Type::Choice(alternatives, false) = &m.get_type("C").unwrap().ty else {
+            panic!("not a CHOICE");
+        };
+        assert_eq!(alternatives[0].ty, Type::Any);
+        assert_eq!(alternatives[1].ty, Type::Any);
+
+        assert!(m.get_type("ATTRIBUTE").is_none());
+        assert_eq!(
+            m.get_value("id-x").unwrap().value,
+            Value::ObjectIdentifier(vec![
+                OidComponent::Number(Some("iso".into()), 1),
+                OidComponent::Number(Some("member-body".into()), 2),
+                OidComponent::Number(None, 840),
+            ])
+        );
+        assert_eq!(m.get_value("ub").unwrap().value, Value::Integer(-1));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let r = parse_modules("M DEFINITIONS ::= BEGIN T ::= SEQUENCE { a } END");
+        assert!(matches!(r, Err(Error::Syntax(1, _))));
+        let r = parse_modules("M DEFINITIONS ::= BEGIN T{X} ::= SEQUENCE { a X } END");
+        assert!(matches!(r, Err(Error::Unsupported(1, _))));
+        let r = parse_modules("M DEFINITIONS ::= BEGIN T ::= SEQUENCE { COMPONENTS OF U } END");
+        assert!(matches!(r, Err(Error::Unsupported(1, _))));
+        let r = parse_modules("M DEFINITIONS ::= BEGIN T ::= INTEGER");
+        assert_eq!(r, Err(Error::UnexpectedEnd));
+    }
+}
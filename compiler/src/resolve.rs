@@ -0,0 +1,266 @@
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+
+use crate::ast::*;
+
+/// Maximum number of references followed when resolving a type or a value
+pub(crate) const MAX_DEPTH: usize = 64;
+
+/// Resolution of type and value references in a set of ASN.1 modules
+#[derive(Clone, Copy)]
+pub(crate) struct Resolver<'a> {
+    pub(crate) modules: &'a [Module],
+}
+
+impl<'a> Resolver<'a> {
+    pub(crate) fn new(modules: &'a [Module]) -> Self {
+        Resolver { modules }
+    }
+
+    pub(crate) fn module_index(&self, name: &str) -> Option<usize> {
+        self.modules.iter().position(|m| m.name == name)
+    }
+
+    /// Resolve a type reference used in module `idx`
+    pub(crate) fn resolve_type(
+        &self,
+        idx: usize,
+        module: Option<&str>,
+        name: &str,
+    ) -> Option<(usize, &'a TypeAssignment)> {
+        let idx = match module {
+            Some(m) => self.module_index(m)?,
+            None => idx,
+        };
+        let m = &self.modules[idx];
+        if let Some(t) = m.get_type(name) {
+            return Some((idx, t));
+        }
+        let source = self.module_index(m.import_source(name)?)?;
+        self.modules[source].get_type(name).map(|t| (source, t))
+    }
+
+    /// Resolve a value reference used in module `idx`
+    pub(crate) fn resolve_value(
+        &self,
+        idx: usize,
+        name: &str,
+    ) -> Option<(usize, &'a ValueAssignment)> {
+        let m = &self.modules[idx];
+        if let Some(v) = m.get_value(name) {
+            return Some((idx, v));
+        }
+        let source = self.module_index(m.import_source(name)?)?;
+        self.modules[source].get_value(name).map(|v| (source, v))
+    }
+
+    /// Follow type references and tags, and return the underlying type
+    ///
+    /// Returns `None` if a reference cannot be resolved (for ex. for extern types).
+    pub(crate) fn base_type<'t>(
+        &self,
+        mut idx: usize,
+        mut ty: &'t Type,
+    ) -> Option<(usize, &'t Type)>
+    where
+        'a: 't,
+    {
+        for _ in 0..MAX_DEPTH {
+            match ty {
+                Type::Reference(m, n) => {
+                    let (i, t) = self.resolve_type(idx, m.as_deref(), n)?;
+                    idx = i;
+                    ty = &t.ty;
+                }
+                Type::Tagged(_, inner) => ty = inner,
+                _ => return Some((idx, ty)),
+            }
+        }
+        None
+    }
+
+    /// Returns `true` if `ty` is an untagged CHOICE, or an open type
+    ///
+    /// These types are always tagged explicitly (X.680: 31.2.7).
+    pub(crate) fn requires_explicit_tag(&self, idx: usize, ty: &Type) -> bool {
+        let mut current = (idx, ty);
+        for _ in 0..MAX_DEPTH {
+            match current.1 {
+                Type::Any | Type::Choice(..) => return true,
+                Type::Reference(m, n) => match self.resolve_type(current.0, m.as_deref(), n) {
+                    Some((i, t)) => current = (i, &t.ty),
+                    None => return false,
+                },
+                _ => return false,
+            }
+        }
+        false
+    }
+
+    /// Returns `true` if the tag of a tagged type in module `idx` is explicit
+    pub(crate) fn is_explicit(&self, idx: usize, tag: &Tag, inner: &Type) -> bool {
+        match tag.mode {
+            Some(TagMode::Explicit) => true,
+            Some(TagMode::Implicit) => false,
+            None => {
+                self.modules[idx].tag_default == TagDefault::Explicit
+                    || self.requires_explicit_tag(idx, inner)
+            }
+        }
+    }
+
+    /// Returns the value of an INTEGER value reference
+    pub(crate) fn integer_value(&self, idx: usize, name: &str, depth: usize) -> Option<i64> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let (i, v) = self.resolve_value(idx, name)?;
+        match (self.base_type(i, &v.ty)?.1, &v.value) {
+            (Type::Integer(_), Value::Integer(n)) => Some(*n),
+            (Type::Integer(_), Value::Reference(r)) => self.integer_value(i, r, depth + 1),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of an INTEGER, possibly given as a named number of the type
+    pub(crate) fn named_integer_value(
+        &self,
+        idx: usize,
+        named: &[NamedNumber],
+        value: &Value,
+    ) -> Option<i64> {
+        match value {
+            Value::Integer(n) => Some(*n),
+            Value::Reference(r) => match named.iter().find(|n| n.name == *r) {
+                Some(NamedNumber {
+                    value: Value::Integer(n),
+                    ..
+                }) => Some(*n),
+                Some(NamedNumber {
+                    value: Value::Reference(r),
+                    ..
+                }) => self.integer_value(idx, r, 0),
+                Some(_) => None,
+                None => self.integer_value(idx, r, 0),
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the arcs of an OBJECT IDENTIFIER value
+    pub(crate) fn oid_arcs(&self, idx: usize, value: &Value, depth: usize) -> Option<Vec<u64>> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let components = match value {
+            Value::ObjectIdentifier(components) => components,
+            Value::Reference(r) => {
+                let (i, v) = self.resolve_value(idx, r)?;
+                return self.oid_arcs(i, &v.value, depth + 1);
+            }
+            _ => return None,
+        };
+        let mut arcs = Vec::new();
+        for (pos, component) in components.iter().enumerate() {
+            match component {
+                OidComponent::Number(_, n) => arcs.push(*n),
+                OidComponent::Name(name) if pos == 0 => match oid_root_arc(name) {
+                    Some(n) => arcs.push(n),
+                    None => {
+                        let (i, v) = self.resolve_value(idx, name)?;
+                        arcs.extend(self.oid_arcs(i, &v.value, depth + 1)?);
+                    }
+                },
+                OidComponent::Name(name) if pos == 1 => {
+                    arcs.push(oid_second_arc(arcs[0], name)?);
+                }
+                OidComponent::Name(_) => return None,
+            }
+        }
+        Some(arcs)
+    }
+
+    /// Apply automatic tagging (X.680: 25.3) to components, if required
+    pub(crate) fn auto_tag<'c>(
+        &self,
+        idx: usize,
+        components: &'c [Component],
+    ) -> Cow<'c, [Component]> {
+        if self.modules[idx].tag_default != TagDefault::Automatic
+            || components.iter().any(|c| matches!(c.ty, Type::Tagged(..)))
+        {
+            return Cow::Borrowed(components);
+        }
+        let tagged = components
+            .iter()
+            .enumerate()
+            .map(|(i, c)| Component {
+                ty: Type::Tagged(
+                    Tag {
+                        class: TagClass::ContextSpecific,
+                        number: i as u32,
+                        mode: None,
+                    },
+                    Box::new(c.ty.clone()),
+                ),
+                ..c.clone()
+            })
+            .collect();
+        Cow::Owned(tagged)
+    }
+}
+
+/// Arcs of the root of the OBJECT IDENTIFIER tree (X.660: A.2)
+fn oid_root_arc(name: &str) -> Option<u64> {
+    match name {
+        "itu-t" | "ccitt" => Some(0),
+        "iso" => Some(1),
+        "joint-iso-itu-t" | "joint-iso-ccitt" => Some(2),
+        _ => None,
+    }
+}
+
+/// Arcs below `itu-t` and `iso` that can be identified by name only (X.660: A.3, A.4)
+fn oid_second_arc(root: u64, name: &str) -> Option<u64> {
+    match (root, name) {
+        (0, "recommendation") => Some(0),
+        (0, "question") => Some(1),
+        (0, "administration") => Some(2),
+        (0, "network-operator") => Some(3),
+        (0, "identified-organization") => Some(4),
+        (1, "standard") => Some(0),
+        (1, "member-body") => Some(2),
+        (1, "identified-organization") => Some(3),
+        _ => None,
+    }
+}
+
+/// Returns the values of the items of an ENUMERATED type
+///
+/// Items without value are numbered as specified in X.680: 20.3 and 20.4.
+pub(crate) fn enumerated_values(items: &[EnumItem]) -> Vec<i64> {
+    let used: BTreeSet<i64> = items
+        .iter()
+        .filter(|item| !item.extension)
+        .filter_map(|item| item.value)
+        .collect();
+    let mut next_root = 0;
+    let mut last = -1;
+    let mut values = Vec::with_capacity(items.len());
+    for item in items {
+        let value = match item.value {
+            Some(v) => v,
+            None if !item.extension => {
+                while used.contains(&next_root) {
+                    next_root += 1;
+                }
+                next_root += 1;
+                next_root - 1
+            }
+            None => last + 1,
+        };
+        last = last.max(value);
+        values.push(value);
+    }
+    values
+}
@@ -0,0 +1,650 @@
+//! Decoding of values using ASN.1 modules loaded at runtime
+//!
+//! A [`Schema`] is a set of ASN.1 modules, parsed at runtime (for ex. from a file provided by the
+//! user). It is used to decode BER objects into [`AnnotatedValue`] trees, where the components of
+//! SEQUENCE and SET values are named, the alternatives of CHOICE values are resolved, and the
+//! values of ENUMERATED types are named.
+//!
+//! ```rust
+//! use asn1_rs_compiler::schema::{DecodedValue, Schema};
+//!
+//! let schema = Schema::parse(
+//!     "Example DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+//!      Point ::= SEQUENCE { x INTEGER, y INTEGER OPTIONAL, color Color }
+//!      Color ::= ENUMERATED { red, green, blue }
+//!      END",
+//! )
+//! .expect("invalid module");
+//!
+//! let bytes = &[0x30, 0x06, 0x80, 0x01, 0x07, 0x82, 0x01, 0x02];
+//! let (_, value) = schema.decode_ber("Example", "Point", bytes).expect("decoding failed");
+//! assert_eq!(value.type_name.as_deref(), Some("Point"));
+//!
+//! let color = value.get("color").expect("missing component");
+//! assert!(matches!(&color.value, DecodedValue::Enumerated { name: Some(name), .. } if name == "blue"));
+//! ```
+//!
+//! Values are decoded using BER rules. Components with a DEFAULT value are not added to the tree
+//! if they are absent, and the values of open types (`ANY`, or fields of information object
+//! classes) are not decoded.
+
+use std::borrow::Cow;
+
+use asn1_rs::{
+    ASN1DateTime, Any, AnyIterator, BerMode, BerParser, BitString, BmpString, Class, GeneralString,
+    GeneralizedTime, GraphicString, Header, Ia5String, Input, Integer, Null, NumericString,
+    ObjectDescriptor, Oid, PrintableString, Tag, TeletexString, UniversalString, UtcTime,
+    Utf8String, VideotexString, VisibleString,
+};
+
+use crate::ast::*;
+use crate::error::{DecodeError, DecodeResult, Error, Result};
+use crate::parser::parse_modules;
+use crate::resolve::{enumerated_values, Resolver, MAX_DEPTH};
+
+/// Maximum nesting depth of decoded values (including type references and tags)
+const MAX_NESTING: usize = 256;
+
+/// A set of ASN.1 modules, used to decode values at runtime
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+    modules: Vec<Module>,
+}
+
+/// A value decoded using a [`Schema`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnnotatedValue<'a> {
+    /// Name of the type of the value, if the value has a named type
+    pub type_name: Option<String>,
+    /// The encoded object (including tags, if the type is tagged)
+    pub any: Any<'a>,
+    /// The decoded value
+    pub value: DecodedValue<'a>,
+}
+
+/// Named component of a SEQUENCE or SET value
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field<'a> {
+    pub name: String,
+    pub value: AnnotatedValue<'a>,
+}
+
+/// Content of a value decoded using a [`Schema`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DecodedValue<'a> {
+    /// Value of an open type, or unknown alternative of an extensible CHOICE
+    ///
+    /// The value is not decoded, and can be read from the `any` field of [`AnnotatedValue`].
+    Any,
+    /// BIT STRING value, and names of the named bits that are set
+    BitString {
+        value: BitString,
+        named: Vec<String>,
+    },
+    Boolean(bool),
+    /// Name and value of the alternative of a CHOICE
+    Choice {
+        alternative: String,
+        value: Box<AnnotatedValue<'a>>,
+    },
+    /// ENUMERATED value, and its name (if known)
+    Enumerated {
+        value: i64,
+        name: Option<String>,
+    },
+    GeneralizedTime(ASN1DateTime),
+    /// INTEGER value, and its name if it is a named number of the type
+    Integer {
+        value: Integer<'a>,
+        name: Option<String>,
+    },
+    Null,
+    ObjectIdentifier(Oid<'a>),
+    OctetString(&'a [u8]),
+    Real(f64),
+    RelativeOid(Oid<'a>),
+    /// Components of a SEQUENCE, and unknown extension additions
+    Sequence {
+        fields: Vec<Field<'a>>,
+        extensions: Vec<Any<'a>>,
+    },
+    SequenceOf(Vec<AnnotatedValue<'a>>),
+    /// Components of a SET, and unknown extension additions
+    Set {
+        fields: Vec<Field<'a>>,
+        extensions: Vec<Any<'a>>,
+    },
+    SetOf(Vec<AnnotatedValue<'a>>),
+    String(StringKind, String),
+    UtcTime(ASN1DateTime),
+}
+
+impl<'a> AnnotatedValue<'a> {
+    /// Returns the component named `name`, if the value is a SEQUENCE or SET
+    ///
+    /// If the value is a CHOICE, the component is searched in the chosen alternative.
+    pub fn get(&self, name: &str) -> Option<&AnnotatedValue<'a>> {
+        match &self.value {
+            DecodedValue::Sequence { fields, .. } | DecodedValue::Set { fields, .. } => {
+                fields.iter().find(|f| f.name == name).map(|f| &f.value)
+            }
+            DecodedValue::Choice { value, .. } => value.get(name),
+            _ => None,
+        }
+    }
+}
+
+impl Schema {
+    /// Creates a new schema, without modules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse ASN.1 module definitions, and return a schema containing these modules
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut schema = Self::new();
+        schema.add_source(text)?;
+        Ok(schema)
+    }
+
+    /// Parse ASN.1 module definitions, and add these modules to the schema
+    pub fn add_source(&mut self, text: &str) -> Result<()> {
+        for module in parse_modules(text)? {
+            self.add_module(module)?;
+        }
+        Ok(())
+    }
+
+    /// Add a module to the schema
+    ///
+    /// Types imported from other modules are resolved when decoding, so modules can be added
+    /// in any order.
+    pub fn add_module(&mut self, module: Module) -> Result<()> {
+        if self.modules.iter().any(|m| m.name == module.name) {
+            return Err(Error::Duplicate(module.name.clone(), module.name));
+        }
+        self.modules.push(module);
+        Ok(())
+    }
+
+    /// Returns the modules of the schema
+    pub fn modules(&self) -> &[Module] {
+        &self.modules
+    }
+
+    /// Decode `any` as a value of the type `type_name`, defined (or imported) in `module`
+    pub fn decode<'a>(
+        &self,
+        module: &str,
+        type_name: &str,
+        any: Any<'a>,
+    ) -> DecodeResult<AnnotatedValue<'a>> {
+        let decoder = Decoder {
+            resolver: Resolver::new(&self.modules),
+        };
+        let idx = decoder
+            .resolver
+            .module_index(module)
+            .ok_or_else(|| DecodeError::UnknownModule(module.to_string()))?;
+        let ty = Type::Reference(None, type_name.to_string());
+        decoder.decode(idx, &ty, any, type_name, false, 0)
+    }
+
+    /// Parse a BER object from `bytes`, and decode it as a value of the type `type_name`
+    ///
+    /// Returns the remaining bytes and the decoded value.
+    pub fn decode_ber<'a>(
+        &self,
+        module: &str,
+        type_name: &str,
+        bytes: &'a [u8],
+    ) -> DecodeResult<(&'a [u8], AnnotatedValue<'a>)> {
+        let (rem, any) = Any::parse_ber(Input::from(bytes)).map_err(ber_error(type_name))?;
+        let value = self.decode(module, type_name, any)?;
+        Ok((rem.into_bytes(), value))
+    }
+}
+
+fn ber_error<'p, 'i>(
+    path: &'p str,
+) -> impl Fn(asn1_rs::nom::Err<asn1_rs::BerError<Input<'i>>>) -> DecodeError + 'p {
+    move |e| DecodeError::Ber(path.to_string(), asn1_rs::Error::from_nom_berr(e))
+}
+
+fn tag_class(class: TagClass) -> Class {
+    match class {
+        TagClass::Universal => Class::Universal,
+        TagClass::Application => Class::Application,
+        TagClass::ContextSpecific => Class::ContextSpecific,
+        TagClass::Private => Class::Private,
+    }
+}
+
+/// Returns the tag of a type of the UNIVERSAL class
+fn universal_tag(ty: &Type) -> Option<Tag> {
+    let tag = match ty {
+        Type::BitString(_) => Tag::BitString,
+        Type::Boolean => Tag::Boolean,
+        Type::Enumerated(..) => Tag::Enumerated,
+        Type::GeneralizedTime => Tag::GeneralizedTime,
+        Type::Integer(_) => Tag::Integer,
+        Type::Null => Tag::Null,
+        Type::ObjectIdentifier => Tag::Oid,
+        Type::OctetString => Tag::OctetString,
+        Type::Real => Tag::RealType,
+        Type::RelativeOid => Tag::RelativeOid,
+        Type::Sequence(..) | Type::SequenceOf(_) => Tag::Sequence,
+        Type::Set(..) | Type::SetOf(_) => Tag::Set,
+        Type::String(kind) => match kind {
+            StringKind::Bmp => Tag::BmpString,
+            StringKind::General => Tag::GeneralString,
+            StringKind::Graphic => Tag::GraphicString,
+            StringKind::Ia5 => Tag::Ia5String,
+            StringKind::Numeric => Tag::NumericString,
+            StringKind::ObjectDescriptor => Tag::ObjectDescriptor,
+            StringKind::Printable => Tag::PrintableString,
+            StringKind::Teletex => Tag::TeletexString,
+            StringKind::Universal => Tag::UniversalString,
+            StringKind::Utf8 => Tag::Utf8String,
+            StringKind::Videotex => Tag::VideotexString,
+            StringKind::Visible => Tag::VisibleString,
+        },
+        Type::UtcTime => Tag::UtcTime,
+        Type::Any | Type::Choice(..) | Type::Reference(..) | Type::Tagged(..) => return None,
+    };
+    Some(tag)
+}
+
+/// Parse the content of `any` using the `BerParser` implementation of `T`
+///
+/// The tag is not checked, so this can be used for implicitly tagged values.
+fn parse_content<'a, T>(any: &Any<'a>, path: &str) -> DecodeResult<T>
+where
+    T: BerParser<'a, Error = asn1_rs::BerError<Input<'a>>>,
+{
+    T::from_ber_content(&any.header, any.data.clone())
+        .map(|(_, value)| value)
+        .map_err(ber_error(path))
+}
+
+fn decode_string<'a>(kind: StringKind, any: &Any<'a>, path: &str) -> DecodeResult<String> {
+    let s = match kind {
+        StringKind::Bmp => parse_content::<BmpString>(any, path)?.string(),
+        StringKind::General => parse_content::<GeneralString>(any, path)?.string(),
+        StringKind::Graphic => parse_content::<GraphicString>(any, path)?.string(),
+        StringKind::Ia5 => parse_content::<Ia5String>(any, path)?.string(),
+        StringKind::Numeric => parse_content::<NumericString>(any, path)?.string(),
+        StringKind::ObjectDescriptor => parse_content::<ObjectDescriptor>(any, path)?.string(),
+        StringKind::Printable => parse_content::<PrintableString>(any, path)?.string(),
+        StringKind::Teletex => parse_content::<TeletexString>(any, path)?.string(),
+        StringKind::Universal => parse_content::<UniversalString>(any, path)?.string(),
+        StringKind::Utf8 => parse_content::<Utf8String>(any, path)?.string(),
+        StringKind::Videotex => parse_content::<VideotexString>(any, path)?.string(),
+        StringKind::Visible => parse_content::<VisibleString>(any, path)?.string(),
+    };
+    Ok(s)
+}
+
+fn is_optional(c: &Component) -> bool {
+    c.optional || c.default.is_some() || c.extension
+}
+
+struct Decoder<'s> {
+    resolver: Resolver<'s>,
+}
+
+impl Decoder<'_> {
+    /// Returns `true` if an object with header `header` can be a value of type `ty`
+    fn accepts(&self, idx: usize, ty: &Type, header: &Header, depth: usize) -> bool {
+        if depth > MAX_DEPTH {
+            return false;
+        }
+        match ty {
+            Type::Any => true,
+            Type::Choice(alternatives, _) => self
+                .resolver
+                .auto_tag(idx, alternatives)
+                .iter()
+                .any(|c| self.accepts(idx, &c.ty, header, depth + 1)),
+            Type::Reference(m, n) => match self.resolver.resolve_type(idx, m.as_deref(), n) {
+                Some((i, t)) => self.accepts(i, &t.ty, header, depth + 1),
+                None => false,
+            },
+            Type::Tagged(tag, _) => {
+                header.class() == tag_class(tag.class) && header.tag() == Tag(tag.number)
+            }
+            _ => header.class() == Class::Universal && Some(header.tag()) == universal_tag(ty),
+        }
+    }
+
+    /// Decode `any` as a value of type `ty`, used in module `idx`
+    ///
+    /// If `implicit` is `true`, the tag of `any` was already checked by an enclosing
+    /// IMPLICIT tag.
+    fn decode<'a>(
+        &self,
+        idx: usize,
+        ty: &Type,
+        any: Any<'a>,
+        path: &str,
+        implicit: bool,
+        depth: usize,
+    ) -> DecodeResult<AnnotatedValue<'a>> {
+        if depth > MAX_NESTING {
+            return Err(DecodeError::MaxDepth(path.to_string()));
+        }
+        let value = match ty {
+            Type::Reference(m, n) => {
+                let (i, t) = self
+                    .resolver
+                    .resolve_type(idx, m.as_deref(), n)
+                    .ok_or_else(|| {
+                        let module = m.as_ref().unwrap_or(&self.resolver.modules[idx].name);
+                        DecodeError::UnknownType(module.clone(), n.clone())
+                    })?;
+                let mut value = self.decode(i, &t.ty, any, path, implicit, depth + 1)?;
+                value.type_name = Some(t.name.clone());
+                return Ok(value);
+            }
+            Type::Tagged(tag, inner) => {
+                let (class, number) = (tag_class(tag.class), Tag(tag.number));
+                if !implicit && (any.header.class() != class || any.header.tag() != number) {
+                    return Err(DecodeError::UnexpectedTag(
+                        path.to_string(),
+                        any.header.class(),
+                        any.header.tag(),
+                    ));
+                }
+                if !self.resolver.is_explicit(idx, tag, inner) {
+                    return self.decode(idx, inner, any, path, true, depth + 1);
+                }
+                if !any.header.is_constructed() {
+                    let e = asn1_rs::Error::ConstructExpected;
+                    return Err(DecodeError::Ber(path.to_string(), e));
+                }
+                let (rem, inner_any) = Any::parse_ber(any.data.clone()).map_err(ber_error(path))?;
+                if !rem.is_empty() {
+                    let e = asn1_rs::Error::InvalidLength;
+                    return Err(DecodeError::Ber(path.to_string(), e));
+                }
+                let mut value = self.decode(idx, inner, inner_any, path, false, depth + 1)?;
+                value.any = any;
+                return Ok(value);
+            }
+            _ => {
+                // the alternatives of a CHOICE are checked when decoding the value
+                let is_choice = matches!(ty, Type::Choice(..));
+                if !implicit && !is_choice && !self.accepts(idx, ty, &any.header, 0) {
+                    return Err(DecodeError::UnexpectedTag(
+                        path.to_string(),
+                        any.header.class(),
+                        any.header.tag(),
+                    ));
+                }
+                self.decode_content(idx, ty, &any, path, depth)?
+            }
+        };
+        Ok(AnnotatedValue {
+            type_name: None,
+            any,
+            value,
+        })
+    }
+
+    /// Decode the content of `any`, as a value of type `ty`
+    fn decode_content<'a>(
+        &self,
+        idx: usize,
+        ty: &Type,
+        any: &Any<'a>,
+        path: &str,
+        depth: usize,
+    ) -> DecodeResult<DecodedValue<'a>> {
+        let value = match ty {
+            Type::Any => DecodedValue::Any,
+            Type::BitString(named_bits) => {
+                let value = parse_content::<BitString>(any, path)?;
+                let named = named_bits
+                    .iter()
+                    .filter(
+                        |n| match self.resolver.named_integer_value(idx, &[], &n.value) {
+                            Some(bit) => bit >= 0 && value.is_set(bit as usize),
+                            None => false,
+                        },
+                    )
+                    .map(|n| n.name.clone())
+                    .collect();
+                DecodedValue::BitString { value, named }
+            }
+            Type::Boolean => DecodedValue::Boolean(parse_content::<bool>(any, path)?),
+            Type::Enumerated(items, extensible) => {
+                // ENUMERATED values are encoded like INTEGER values (X.690: 8.4)
+                let value = parse_content::<Integer>(any, path)?
+                    .as_i64()
+                    .map_err(|e| DecodeError::Ber(path.to_string(), e))?;
+                let name = items
+                    .iter()
+                    .zip(enumerated_values(items))
+                    .find(|(_, v)| *v == value)
+                    .map(|(item, _)| item.name.clone());
+                if name.is_none()
+                    && !*extensible
+                    && !self.resolver.modules[idx].extensibility_implied
+                {
+                    return Err(DecodeError::UnknownValue(path.to_string(), value));
+                }
+                DecodedValue::Enumerated { value, name }
+            }
+            Type::GeneralizedTime => {
+                DecodedValue::GeneralizedTime(parse_content::<GeneralizedTime>(any, path)?.0)
+            }
+            Type::Integer(named_numbers) => {
+                let value = parse_content::<Integer>(any, path)?;
+                let name = match value.as_i64() {
+                    Ok(v) => named_numbers
+                        .iter()
+                        .find(|n| self.resolver.named_integer_value(idx, &[], &n.value) == Some(v))
+                        .map(|n| n.name.clone()),
+                    Err(_) => None,
+                };
+                DecodedValue::Integer { value, name }
+            }
+            Type::Null => {
+                parse_content::<Null>(any, path)?;
+                DecodedValue::Null
+            }
+            Type::ObjectIdentifier => {
+                DecodedValue::ObjectIdentifier(parse_content::<Oid>(any, path)?)
+            }
+            Type::OctetString => DecodedValue::OctetString(parse_content::<&[u8]>(any, path)?),
+            Type::Real => DecodedValue::Real(parse_content::<f64>(any, path)?),
+            Type::RelativeOid => {
+                // the encoding is the same as for OBJECT IDENTIFIER, only the tag differs
+                parse_content::<Oid>(any, path)?;
+                let bytes = any.data.as_bytes2();
+                DecodedValue::RelativeOid(Oid::new_relative(Cow::Borrowed(bytes)))
+            }
+            Type::Sequence(components, extensible) => {
+                let (fields, extensions) =
+                    self.decode_sequence(idx, components, *extensible, any, path, depth)?;
+                DecodedValue::Sequence { fields, extensions }
+            }
+            Type::Set(components, extensible) => {
+                let (fields, extensions) =
+                    self.decode_set(idx, components, *extensible, any, path, depth)?;
+                DecodedValue::Set { fields, extensions }
+            }
+            Type::SequenceOf(element) => {
+                DecodedValue::SequenceOf(self.decode_elements(idx, element, any, path, depth)?)
+            }
+            Type::SetOf(element) => {
+                DecodedValue::SetOf(self.decode_elements(idx, element, any, path, depth)?)
+            }
+            Type::String(kind) => DecodedValue::String(*kind, decode_string(*kind, any, path)?),
+            Type::UtcTime => DecodedValue::UtcTime(parse_content::<UtcTime>(any, path)?.0),
+            Type::Choice(alternatives, extensible) => {
+                self.decode_choice(idx, alternatives, *extensible, any, path, depth)?
+            }
+            Type::Reference(..) | Type::Tagged(..) => {
+                let value = self.decode(idx, ty, any.clone(), path, true, depth + 1)?;
+                return Ok(value.value);
+            }
+        };
+        Ok(value)
+    }
+
+    /// Returns the elements contained in the constructed object `any`
+    fn children<'a>(&self, any: &Any<'a>, path: &str) -> DecodeResult<Vec<Any<'a>>> {
+        if !any.header.is_constructed() {
+            let e = asn1_rs::Error::ConstructExpected;
+            return Err(DecodeError::Ber(path.to_string(), e));
+        }
+        AnyIterator::<BerMode>::new(any.data.clone())
+            .map(|r| r.map(|(_, obj)| obj))
+            .collect::<core::result::Result<Vec<_>, _>>()
+            .map_err(|e| ber_error(path)(asn1_rs::nom::Err::Error(e)))
+    }
+
+    fn decode_choice<'a>(
+        &self,
+        idx: usize,
+        alternatives: &[Component],
+        extensible: bool,
+        any: &Any<'a>,
+        path: &str,
+        depth: usize,
+    ) -> DecodeResult<DecodedValue<'a>> {
+        let alternatives = self.resolver.auto_tag(idx, alternatives);
+        let alternative = alternatives
+            .iter()
+            .find(|c| self.accepts(idx, &c.ty, &any.header, 0));
+        let Some(alternative) = alternative else {
+            if extensible || self.resolver.modules[idx].extensibility_implied {
+                return Ok(DecodedValue::Any);
+            }
+            return Err(DecodeError::UnexpectedTag(
+                path.to_string(),
+                any.header.class(),
+                any.header.tag(),
+            ));
+        };
+        let path = format!("{}.{}", path, alternative.name);
+        let value = self.decode(idx, &alternative.ty, any.clone(), &path, false, depth + 1)?;
+        Ok(DecodedValue::Choice {
+            alternative: alternative.name.clone(),
+            value: Box::new(value),
+        })
+    }
+
+    fn decode_sequence<'a>(
+        &self,
+        idx: usize,
+        components: &[Component],
+        extensible: bool,
+        any: &Any<'a>,
+        path: &str,
+        depth: usize,
+    ) -> DecodeResult<(Vec<Field<'a>>, Vec<Any<'a>>)> {
+        let components = self.resolver.auto_tag(idx, components);
+        let mut children = self.children(any, path)?.into_iter().peekable();
+        let mut fields = Vec::new();
+        for c in components.iter() {
+            // a mandatory component is always decoded, to report errors on its value
+            let present = match children.peek() {
+                Some(child) => !is_optional(c) || self.accepts(idx, &c.ty, &child.header, 0),
+                None => false,
+            };
+            match children.next_if(|_| present) {
+                Some(child) => {
+                    let path = format!("{}.{}", path, c.name);
+                    let value = self.decode(idx, &c.ty, child, &path, false, depth + 1)?;
+                    fields.push(Field {
+                        name: c.name.clone(),
+                        value,
+                    });
+                }
+                None if !is_optional(c) => {
+                    return Err(DecodeError::MissingComponent(
+                        path.to_string(),
+                        c.name.clone(),
+                    ));
+                }
+                None => (),
+            }
+        }
+        let extensions: Vec<_> = children.collect();
+        if !extensions.is_empty()
+            && !extensible
+            && !self.resolver.modules[idx].extensibility_implied
+        {
+            return Err(DecodeError::UnexpectedComponent(path.to_string()));
+        }
+        Ok((fields, extensions))
+    }
+
+    fn decode_set<'a>(
+        &self,
+        idx: usize,
+        components: &[Component],
+        extensible: bool,
+        any: &Any<'a>,
+        path: &str,
+        depth: usize,
+    ) -> DecodeResult<(Vec<Field<'a>>, Vec<Any<'a>>)> {
+        let components = self.resolver.auto_tag(idx, components);
+        // values of components, in the order of the type definition
+        let mut values: Vec<Option<AnnotatedValue<'a>>> = vec![None; components.len()];
+        let mut extensions = Vec::new();
+        for child in self.children(any, path)? {
+            let position = components.iter().enumerate().position(|(i, c)| {
+                values[i].is_none() && self.accepts(idx, &c.ty, &child.header, 0)
+            });
+            match position {
+                Some(i) => {
+                    let c = &components[i];
+                    let path = format!("{}.{}", path, c.name);
+                    values[i] = Some(self.decode(idx, &c.ty, child, &path, false, depth + 1)?);
+                }
+                None if extensible || self.resolver.modules[idx].extensibility_implied => {
+                    extensions.push(child);
+                }
+                None => return Err(DecodeError::UnexpectedComponent(path.to_string())),
+            }
+        }
+        let mut fields = Vec::new();
+        for (c, value) in components.iter().zip(values) {
+            match value {
+                Some(value) => fields.push(Field {
+                    name: c.name.clone(),
+                    value,
+                }),
+                None if !is_optional(c) => {
+                    return Err(DecodeError::MissingComponent(
+                        path.to_string(),
+                        c.name.clone(),
+                    ));
+                }
+                None => (),
+            }
+        }
+        Ok((fields, extensions))
+    }
+
+    fn decode_elements<'a>(
+        &self,
+        idx: usize,
+        element: &Type,
+        any: &Any<'a>,
+        path: &str,
+        depth: usize,
+    ) -> DecodeResult<Vec<AnnotatedValue<'a>>> {
+        self.children(any, path)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, child)| {
+                let path = format!("{}[{}]", path, i);
+                self.decode(idx, element, child, &path, false, depth + 1)
+            })
+            .collect()
+    }
+}
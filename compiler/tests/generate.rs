@@ -0,0 +1,203 @@
+use asn1_rs_compiler::{Error, Generator};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn asset(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/assets")
+        .join(name)
+}
+
+fn pkix_generator() -> Generator {
+    Generator::new()
+        .file(asset("PKIX1Explicit88.asn1"))
+        .file(asset("PKIX1Implicit88.asn1"))
+        .extern_type("ORAddress", "asn1_rs::Sequence<'a>")
+}
+
+const AUTOMATIC_MODULE: &str = r#"
+Messages DEFINITIONS AUTOMATIC TAGS ::=
+BEGIN
+Message ::= SEQUENCE {
+    id      INTEGER,
+    body    CHOICE {
+        text    UTF8String,
+        data    OCTET STRING
+    },
+    kind    ENUMERATED { request, response, ... } DEFAULT request,
+    flags   SEQUENCE OF BOOLEAN OPTIONAL,
+    ...
+}
+Envelope ::= [APPLICATION 1] Message
+END
+"#;
+
+/// Write `code` and `main` to a file, and check it compiles and runs
+fn run_pass(name: &str, code: &str, main: &str) {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("{}.rs", name));
+    fs::write(&path, format!("{}\n{}", code, main)).expect("could not write test file");
+    let t = trybuild::TestCases::new();
+    t.pass(&path);
+}
+
+#[test]
+fn generate_pkix() {
+    let code = pkix_generator().generate().expect("generation failed");
+
+    // tagging modes, DEFAULT and OPTIONAL
+    assert!(code.contains(
+        "        #[tag_explicit(0)]
+        #[default(asn1_rs::Integer::from(0_i64))]
+        pub version: Version<'a>,"
+    ));
+    assert!(code.contains(
+        "        #[tag_implicit(1)]
+        #[optional]
+        pub issuer_unique_id: Option<UniqueIdentifier>,"
+    ));
+    assert!(code.contains(
+        "        #[default(false)]
+        pub critical: bool,"
+    ));
+    // anonymous type
+    assert!(code.contains(
+        "pub revoked_certificates: Option<Vec<TBSCertListRevokedCertificatesItem<'a>>>,"
+    ));
+    assert!(code.contains("pub struct TBSCertListRevokedCertificatesItem<'a> {"));
+    // CHOICE with mixed tagging modes, and types from other modules
+    assert!(code.contains(
+        "    pub enum GeneralName<'a> {
+        OtherName(asn1_rs::TaggedImplicit<AnotherName<'a>, asn1_rs::Error, 0>),"
+    ));
+    assert!(code.contains(
+        "DirectoryName(asn1_rs::TaggedExplicit<super::pkix1_explicit88::Name<'a>, asn1_rs::Error, 4>),"
+    ));
+    assert!(code.contains(
+        "X400Address(asn1_rs::TaggedImplicit<asn1_rs::Sequence<'a>, asn1_rs::Error, 3>),"
+    ));
+    // values
+    assert!(code.contains(
+        "pub const ID_KP_SERVER_AUTH: asn1_rs::Oid<'static> = asn1_rs::oid!(1.3.6.1.5.5.7.3.1);"
+    ));
+    assert!(code.contains("pub const UB_COMMON_NAME: i64 = 64;"));
+
+    let main = format!(
+        r#"
+use asn1_rs::{{DerParser, Input}};
+use pkix1_explicit88::*;
+use pkix1_implicit88::*;
+
+fn main() {{
+    let bytes = include_bytes!({:?});
+    let (rem, cert) = Certificate::parse_der(Input::from(&bytes[..])).expect("parsing failed");
+    assert!(rem.is_empty());
+    let tbs = &cert.tbs_certificate;
+    assert_eq!(tbs.version.as_u32(), Ok(2));
+    assert_eq!(tbs.serial_number.as_u64(), Ok(0x12_3456_7890));
+    let Name::RdnSequence(rdns) = &tbs.subject;
+    assert_eq!(rdns.len(), 3);
+    assert!(matches!(tbs.validity.not_before, Time::UtcTime(_)));
+
+    let extensions = tbs.extensions.as_ref().expect("no extensions");
+    for ext in extensions {{
+        let value = ext.extn_value.as_ref();
+        if ext.extn_id == ID_CE_BASIC_CONSTRAINTS {{
+            let (_, bc) = BasicConstraints::parse_der(Input::from(value)).expect("invalid extension");
+            assert!(bc.c_a);
+            assert_eq!(bc.path_len_constraint.map(|n| n.as_u32()), Some(Ok(1)));
+        }} else if ext.extn_id == ID_CE_EXT_KEY_USAGE {{
+            let (_, eku) = ExtKeyUsageSyntax::parse_der(Input::from(value)).expect("invalid extension");
+            assert_eq!(eku, vec![ID_KP_SERVER_AUTH]);
+        }} else if ext.extn_id == ID_CE_SUBJECT_ALT_NAME {{
+            let (_, names) = SubjectAltName::parse_der(Input::from(value)).expect("invalid extension");
+            assert_eq!(names.len(), 4);
+            assert!(matches!(&names[0], GeneralName::DNSName(s) if s.as_ref().as_ref() == "test.example.com"));
+            assert!(matches!(&names[1], GeneralName::IPAddress(ip) if ip.as_ref().as_ref() == [127, 0, 0, 1]));
+            assert!(matches!(&names[3], GeneralName::DirectoryName(_)));
+        }}
+    }}
+}}
+"#,
+        asset("certificate.der")
+    );
+    run_pass("pkix", &code, &main);
+}
+
+#[test]
+fn generate_automatic_tags() {
+    let code = Generator::new()
+        .source(AUTOMATIC_MODULE)
+        .generate()
+        .expect("generation failed");
+
+    assert!(code.contains(
+        "        #[tag_explicit(1)]
+        pub body: MessageBody<'a>,
+        #[tag_implicit(2)]
+        #[default(MessageKind::Request)]
+        pub kind: MessageKind,"
+    ));
+    assert!(code.contains(
+        "    #[tagged_implicit]
+    pub enum MessageBody<'a> {
+        #[tag(0)]
+        Text(asn1_rs::Utf8String<'a>),"
+    ));
+    assert!(code.contains(
+        "pub type Envelope<'a> = asn1_rs::ApplicationImplicit<Message<'a>, asn1_rs::Error, 1>;"
+    ));
+
+    let main = r#"
+use asn1_rs::{DerParser, Input, Integer, OctetString, Utf8String};
+use messages::*;
+
+fn main() {
+    let bytes = hex_literal::hex!("61 15 800103 a105 8103010203 820101 a306 0101ff 010100");
+    let (rem, envelope) = Envelope::parse_der(Input::from(&bytes[..])).expect("parsing failed");
+    assert!(rem.is_empty());
+    let message = Message {
+        id: Integer::from(3_u8),
+        body: MessageBody::Data(OctetString::new(&[1, 2, 3])),
+        kind: MessageKind::Response,
+        flags: Some(vec![true, false]),
+    };
+    assert_eq!(envelope.into_inner(), message);
+
+    // DEFAULT value
+    let bytes = hex_literal::hex!("61 09 800103 a104 80026869");
+    let (_, envelope) = Envelope::parse_der(Input::from(&bytes[..])).expect("parsing failed");
+    let message = envelope.into_inner();
+    assert_eq!(message.body, MessageBody::Text(Utf8String::new("hi")));
+    assert_eq!(message.kind, MessageKind::Request);
+    assert_eq!(message.flags, None);
+}
+"#;
+    run_pass("automatic", &code, main);
+}
+
+#[test]
+fn generate_errors() {
+    let r = Generator::new()
+        .file(asset("PKIX1Implicit88.asn1"))
+        .generate();
+    assert_eq!(
+        r,
+        Err(Error::UnresolvedImport(
+            "PKIX1Explicit88".into(),
+            "CertificateSerialNumber".into()
+        ))
+    );
+
+    let r = Generator::new()
+        .source("M DEFINITIONS ::= BEGIN T ::= SEQUENCE { a U } END")
+        .generate();
+    assert_eq!(r, Err(Error::UnknownType("M".into(), "U".into())));
+
+    let r = Generator::new()
+        .source("M DEFINITIONS ::= BEGIN T ::= SEQUENCE { a BIT STRING DEFAULT '0'B } END")
+        .generate();
+    assert_eq!(r, Err(Error::InvalidDefault("T".into(), "a".into())));
+
+    let r = Generator::new().file(asset("missing.asn1")).generate();
+    assert!(matches!(r, Err(Error::Io(..))));
+}
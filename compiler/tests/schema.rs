@@ -0,0 +1,274 @@
+use asn1_rs::{Class, Tag};
+use asn1_rs_compiler::ast::StringKind;
+use asn1_rs_compiler::schema::{AnnotatedValue, DecodedValue, Schema};
+use asn1_rs_compiler::DecodeError;
+use hex_literal::hex;
+use std::fs;
+use std::path::Path;
+
+const TEST_MODULE: &str = r#"
+Test DEFINITIONS AUTOMATIC TAGS ::=
+BEGIN
+Record ::= SET {
+    id      INTEGER,
+    name    UTF8String OPTIONAL,
+    tags    SEQUENCE OF VisibleString
+}
+Message ::= SEQUENCE {
+    kind    ENUMERATED { request(1), response(2) },
+    body    CHOICE {
+        text    UTF8String,
+        data    OCTET STRING,
+        ...
+    },
+    ...
+}
+END
+"#;
+
+fn read_asset(name: &str) -> Vec<u8> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/assets")
+        .join(name);
+    fs::read(path).expect("could not read asset")
+}
+
+fn pkix_schema() -> Schema {
+    let mut schema = Schema::new();
+    for name in ["PKIX1Implicit88.asn1", "PKIX1Explicit88.asn1"] {
+        let text = String::from_utf8(read_asset(name)).expect("invalid module");
+        schema.add_source(&text).expect("invalid module");
+    }
+    schema
+}
+
+fn elements<'v, 'a>(value: &'v AnnotatedValue<'a>) -> &'v [AnnotatedValue<'a>] {
+    match &value.value {
+        DecodedValue::SequenceOf(v) | DecodedValue::SetOf(v) => v,
+        _ => panic!("not a SEQUENCE OF or SET OF"),
+    }
+}
+
+fn alternative<'v, 'a>(value: &'v AnnotatedValue<'a>) -> (&'v str, &'v AnnotatedValue<'a>) {
+    match &value.value {
+        DecodedValue::Choice { alternative, value } => (alternative, value),
+        _ => panic!("not a CHOICE"),
+    }
+}
+
+#[test]
+fn schema_certificate() {
+    let schema = pkix_schema();
+    let bytes = read_asset("certificate.der");
+    let (rem, cert) = schema
+        .decode_ber("PKIX1Explicit88", "Certificate", &bytes)
+        .expect("decoding failed");
+    assert!(rem.is_empty());
+    assert_eq!(cert.type_name.as_deref(), Some("Certificate"));
+    assert_eq!(cert.any.header.length().definite(), Ok(bytes.len() - 4));
+
+    let tbs = cert.get("tbsCertificate").expect("no tbsCertificate");
+    // explicit tag, and named number
+    let version = tbs.get("version").expect("no version");
+    assert_eq!(version.type_name.as_deref(), Some("Version"));
+    assert_eq!(version.any.header.tag(), Tag(0));
+    assert!(matches!(&version.value, DecodedValue::Integer { name: Some(n), .. } if n == "v3"));
+    let serial = tbs.get("serialNumber").expect("no serialNumber");
+    assert!(
+        matches!(&serial.value, DecodedValue::Integer { value, .. } if value.as_u64() == Ok(0x12_3456_7890))
+    );
+    assert!(tbs.get("issuerUniqueID").is_none());
+
+    let (name, rdns) = alternative(tbs.get("subject").expect("no subject"));
+    assert_eq!(name, "rdnSequence");
+    assert_eq!(rdns.type_name.as_deref(), Some("RDNSequence"));
+    assert_eq!(elements(rdns).len(), 3);
+    let attribute = &elements(&elements(rdns)[2])[0];
+    assert!(matches!(
+        &attribute.get("type").expect("no type").value,
+        DecodedValue::ObjectIdentifier(oid) if oid.to_id_string() == "2.5.4.3"
+    ));
+    // open type
+    let value = attribute.get("value").expect("no value");
+    assert_eq!(value.value, DecodedValue::Any);
+    assert_eq!(value.any.as_str(), Ok("test.example.com"));
+
+    let (name, _) = alternative(
+        tbs.get("validity")
+            .and_then(|v| v.get("notBefore"))
+            .unwrap(),
+    );
+    assert_eq!(name, "utcTime");
+
+    // decode the content of extensions
+    let extensions = tbs.get("extensions").expect("no extensions");
+    let mut decoded = 0;
+    for ext in elements(extensions) {
+        let Some(DecodedValue::ObjectIdentifier(oid)) = ext.get("extnID").map(|v| &v.value) else {
+            panic!("no extnID");
+        };
+        let Some(DecodedValue::OctetString(data)) = ext.get("extnValue").map(|v| &v.value) else {
+            panic!("no extnValue");
+        };
+        let type_name = match oid.to_id_string().as_str() {
+            "2.5.29.15" => "KeyUsage",
+            "2.5.29.17" => "SubjectAltName",
+            "2.5.29.19" => "BasicConstraints",
+            _ => continue,
+        };
+        let (_, value) = schema
+            .decode_ber("PKIX1Implicit88", type_name, data)
+            .expect("decoding extension failed");
+        match type_name {
+            "KeyUsage" => {
+                let DecodedValue::BitString { named, .. } = &value.value else {
+                    panic!("not a BIT STRING");
+                };
+                assert_eq!(named, &["digitalSignature", "keyCertSign"]);
+            }
+            "SubjectAltName" => {
+                let names: Vec<_> = elements(&value).iter().map(alternative).collect();
+                assert_eq!(names.len(), 4);
+                assert_eq!(names[0].0, "dNSName");
+                assert_eq!(
+                    names[0].1.value,
+                    DecodedValue::String(StringKind::Ia5, "test.example.com".into())
+                );
+                assert_eq!(names[1].0, "iPAddress");
+                assert_eq!(names[1].1.value, DecodedValue::OctetString(&[127, 0, 0, 1]));
+                assert_eq!(names[2].0, "rfc822Name");
+                // explicit tag, and type from another module
+                assert_eq!(names[3].0, "directoryName");
+                assert_eq!(names[3].1.type_name.as_deref(), Some("Name"));
+                assert_eq!(names[3].1.any.header.tag(), Tag(4));
+            }
+            _ => {
+                assert_eq!(
+                    value.get("cA").map(|v| &v.value),
+                    Some(&DecodedValue::Boolean(true))
+                );
+                assert!(value.get("pathLenConstraint").is_some());
+            }
+        }
+        decoded += 1;
+    }
+    assert_eq!(decoded, 3);
+}
+
+#[test]
+fn schema_automatic_tags() {
+    let schema = Schema::parse(TEST_MODULE).expect("invalid module");
+
+    // SET, with components in any order
+    let (_, record) = schema
+        .decode_ber("Test", "Record", &hex!("31 0a a2 05 1a 03 616263 80 01 05"))
+        .expect("decoding failed");
+    let DecodedValue::Set { fields, extensions } = &record.value else {
+        panic!("not a SET");
+    };
+    assert!(extensions.is_empty());
+    let names: Vec<_> = fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["id", "tags"]);
+    let tags = elements(&fields[1].value);
+    assert_eq!(
+        tags[0].value,
+        DecodedValue::String(StringKind::Visible, "abc".into())
+    );
+
+    // extension additions
+    let (_, message) = schema
+        .decode_ber(
+            "Test",
+            "Message",
+            &hex!("30 0c 80 01 02 a1 04 80 02 6869 82 01 00"),
+        )
+        .expect("decoding failed");
+    let kind = message.get("kind").expect("no kind");
+    assert_eq!(
+        kind.value,
+        DecodedValue::Enumerated {
+            value: 2,
+            name: Some("response".into())
+        }
+    );
+    let (name, text) = alternative(message.get("body").expect("no body"));
+    assert_eq!(name, "text");
+    assert_eq!(
+        text.value,
+        DecodedValue::String(StringKind::Utf8, "hi".into())
+    );
+    assert!(
+        matches!(&message.value, DecodedValue::Sequence { extensions, .. } if extensions.len() == 1)
+    );
+
+    // unknown alternative of an extensible CHOICE
+    let (_, message) = schema
+        .decode_ber("Test", "Message", &hex!("30 08 80 01 01 a1 03 85 01 00"))
+        .expect("decoding failed");
+    assert_eq!(
+        message.get("body").map(|v| &v.value),
+        Some(&DecodedValue::Any)
+    );
+}
+
+#[test]
+fn schema_errors() {
+    let schema = Schema::parse(TEST_MODULE).expect("invalid module");
+
+    let r = schema.decode_ber("Test", "Message", &hex!("30 08 80 01 03 a1 03 80 01 68"));
+    assert_eq!(r, Err(DecodeError::UnknownValue("Message.kind".into(), 3)));
+
+    let r = schema.decode_ber("Test", "Message", &hex!("30 03 80 01 01"));
+    assert_eq!(
+        r,
+        Err(DecodeError::MissingComponent(
+            "Message".into(),
+            "body".into()
+        ))
+    );
+
+    let r = schema.decode_ber("Test", "Record", &hex!("31 05 a2 03 02 01 05"));
+    assert_eq!(
+        r,
+        Err(DecodeError::UnexpectedTag(
+            "Record.tags[0]".into(),
+            Class::Universal,
+            Tag::Integer
+        ))
+    );
+
+    let r = schema.decode_ber("Test", "Message", &hex!("31 00"));
+    assert_eq!(
+        r,
+        Err(DecodeError::UnexpectedTag(
+            "Message".into(),
+            Class::Universal,
+            Tag::Set
+        ))
+    );
+
+    let r = schema.decode_ber("Test", "Unknown", &hex!("05 00"));
+    assert_eq!(
+        r,
+        Err(DecodeError::UnknownType("Test".into(), "Unknown".into()))
+    );
+
+    let r = schema.decode_ber("Other", "Message", &hex!("05 00"));
+    assert_eq!(r, Err(DecodeError::UnknownModule("Other".into())));
+
+    // types are checked lazily: missing imported modules are reported when decoding
+    let schema = Schema::parse(&String::from_utf8(read_asset("PKIX1Implicit88.asn1")).unwrap())
+        .expect("invalid module");
+    let r = schema.decode_ber(
+        "PKIX1Implicit88",
+        "AuthorityKeyIdentifier",
+        &hex!("30 03 82 01 01"),
+    );
+    assert_eq!(
+        r,
+        Err(DecodeError::UnknownType(
+            "PKIX1Implicit88".into(),
+            "CertificateSerialNumber".into()
+        ))
+    );
+}
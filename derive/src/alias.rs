@@ -4,6 +4,7 @@ use crate::container::*;
 use crate::options::Options;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
+use syn::ext::IdentExt;
 use syn::{
     parse_quote, Attribute, Data, DataStruct, Error, Fields, Ident, Lifetime, Result,
     WherePredicate,
@@ -90,16 +91,40 @@ impl<'s> DeriveAlias<'s> {
     fn to_tokens(&self) -> TokenStream {
         let dyntagged = self.derive_alias_dyntagged();
         let berparser = self.derive_alias_parser(Asn1Type::Ber);
+        let cerparser = self.derive_alias_parser(Asn1Type::Cer);
         let derparser = self.derive_alias_parser(Asn1Type::Der);
         let berencode = self.derive_alias_encode(Asn1Type::Ber);
+        let cerencode = self.derive_alias_encode(Asn1Type::Cer);
         let derencode = self.derive_alias_encode(Asn1Type::Der);
+        let perparser = self.derive_alias_per_parser();
+        let perencode = self.derive_alias_per_encode();
+        let oerparser = self.derive_alias_oer_parser();
+        let oerencode = self.derive_alias_oer_encode();
+        let jerparser = self.derive_alias_jer_parser();
+        let jerencode = self.derive_alias_jer_encode();
+        let xmltypename = self.derive_alias_xmltypename();
+        let xerparser = self.derive_alias_xer_parser();
+        let xerencode = self.derive_alias_xer_encode();
+        let valuenotation = self.derive_alias_value_notation();
 
         let ts = quote! {
             #dyntagged
             #berparser
+            #cerparser
             #derparser
             #berencode
+            #cerencode
             #derencode
+            #perparser
+            #perencode
+            #oerparser
+            #oerencode
+            #jerparser
+            #jerencode
+            #xmltypename
+            #xerparser
+            #xerencode
+            #valuenotation
         };
 
         if self.options.debug {
@@ -228,13 +253,42 @@ impl<'s> DeriveAlias<'s> {
         let ber_content_len = asn1_type.compose("_content_len");
         let ber_tag_info = asn1_type.compose("_tag_info");
         let ber_write_content = asn1_type.compose("_write_content");
+        // DER: two-pass and reverse encoding, forwarded to the aliased type
+        let toder_cached = if asn1_type == Asn1Type::Der {
+            let der_encode = needs_length_cache(core::iter::once(&self.target.ast().ty)).then(|| {
+                quote! {
+                    fn der_encode<W: asn1_rs::Writer>(&self, target: &mut W) -> asn1_rs::SerializeResult<usize> {
+                        use asn1_rs::ToDer;
+                        self.der_encode_with_cache(target)
+                    }
+                }
+            });
+            quote! {
+                fn der_content_len_cached(&self, cache: &mut asn1_rs::LengthCache) -> asn1_rs::Length {
+                    use asn1_rs::ToDer;
+                    self.0.der_content_len_cached(cache)
+                }
+
+                fn der_write_content_cached<W: asn1_rs::Writer>(&self, cache: &mut asn1_rs::LengthCache, target: &mut W) -> asn1_rs::SerializeResult<usize> {
+                    use asn1_rs::ToDer;
+                    self.0.der_write_content_cached(cache, target)
+                }
+
+                #der_encode
+
+                fn der_write_content_reverse(&self, target: &mut asn1_rs::ReverseWriter) -> asn1_rs::SerializeResult<usize> {
+                    use asn1_rs::ToDer;
+                    self.0.der_write_content_reverse(target)
+                }
+            }
+        } else {
+            quote! {}
+        };
 
         // note: `gen impl` in synstructure takes care of appending extra where clauses if any, and removing
         // the `where` statement if there are none.
         let ts = self.synstruct.gen_impl(quote! {
             extern crate asn1_rs;
-
-            #[cfg(feature = "std")]
             gen impl asn1_rs::#tober for @Self where #(#wh)+* {
                 type Encoder = asn1_rs::BerGenericEncoder;
 
@@ -248,14 +302,226 @@ impl<'s> DeriveAlias<'s> {
                     self.0.#ber_tag_info()
                 }
 
-                fn #ber_write_content<W: std::io::Write>(&self, target: &mut W) -> asn1_rs::SerializeResult<usize> {
+                fn #ber_write_content<W: asn1_rs::Writer>(&self, target: &mut W) -> asn1_rs::SerializeResult<usize> {
                     use asn1_rs::#tober;
                     self.0.#ber_write_content(target)
                 }
+
+                #toder_cached
             }
         });
         ts
     }
+
+    fn derive_alias_per_parser(&self) -> TokenStream {
+        if !self.options.per_parser {
+            return quote! {};
+        }
+
+        let lft = Lifetime::new("'ber", Span::call_site());
+        let wh = &self.where_predicates;
+
+        // constraints are those of the aliased type, and are provided by the caller
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl<#lft> asn1_rs::per::PerParser<#lft> for @Self where #(#wh)+* {
+                fn per_decode(reader: &mut asn1_rs::per::PerReader<#lft>, constraints: &asn1_rs::Constraints) -> asn1_rs::PerResult<Self> {
+                    asn1_rs::per::PerParser::per_decode(reader, constraints).map(Self)
+                }
+            }
+        })
+    }
+
+    fn derive_alias_per_encode(&self) -> TokenStream {
+        if !self.options.per_encoder {
+            return quote! {};
+        }
+
+        // we must filter out the 'ber lifetime (added for parsers, but not used here)
+        let wh = self
+            .where_predicates
+            .iter()
+            .filter(|predicate| match predicate {
+                WherePredicate::Lifetime(lft) => lft.lifetime.ident != "ber",
+                _ => true,
+            });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::per::ToPer for @Self where #(#wh)+* {
+                fn per_encode(&self, writer: &mut asn1_rs::per::PerWriter, constraints: &asn1_rs::Constraints) -> asn1_rs::PerResult<()> {
+                    asn1_rs::per::ToPer::per_encode(&self.0, writer, constraints)
+                }
+            }
+        })
+    }
+
+    fn derive_alias_oer_parser(&self) -> TokenStream {
+        if !self.options.oer_parser {
+            return quote! {};
+        }
+
+        let lft = Lifetime::new("'ber", Span::call_site());
+        let wh = &self.where_predicates;
+
+        // constraints are those of the aliased type, and are provided by the caller
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl<#lft> asn1_rs::oer::OerParser<#lft> for @Self where #(#wh)+* {
+                fn oer_decode(reader: &mut asn1_rs::oer::OerReader<#lft>, constraints: &asn1_rs::Constraints) -> asn1_rs::OerResult<Self> {
+                    asn1_rs::oer::OerParser::oer_decode(reader, constraints).map(Self)
+                }
+            }
+        })
+    }
+
+    fn derive_alias_oer_encode(&self) -> TokenStream {
+        if !self.options.oer_encoder {
+            return quote! {};
+        }
+
+        // we must filter out the 'ber lifetime (added for parsers, but not used here)
+        let wh = self
+            .where_predicates
+            .iter()
+            .filter(|predicate| match predicate {
+                WherePredicate::Lifetime(lft) => lft.lifetime.ident != "ber",
+                _ => true,
+            });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::oer::ToOer for @Self where #(#wh)+* {
+                fn oer_encode(&self, writer: &mut asn1_rs::oer::OerWriter, constraints: &asn1_rs::Constraints) -> asn1_rs::OerResult<()> {
+                    asn1_rs::oer::ToOer::oer_encode(&self.0, writer, constraints)
+                }
+            }
+        })
+    }
+
+    /// Returns the where predicates, without the `'ber` lifetime (added for BER parsers)
+    fn where_predicates_without_ber(&self) -> impl Iterator<Item = &WherePredicate> {
+        self.where_predicates
+            .iter()
+            .filter(|predicate| match predicate {
+                WherePredicate::Lifetime(lft) => lft.lifetime.ident != "ber",
+                _ => true,
+            })
+    }
+
+    fn derive_alias_jer_parser(&self) -> TokenStream {
+        if !self.options.jer_parser {
+            return quote! {};
+        }
+
+        let wh = self.where_predicates_without_ber();
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::jer::JerParser for @Self where #(#wh)+* {
+                fn from_jer(value: &asn1_rs::jer::JsonValue) -> asn1_rs::JerResult<Self> {
+                    asn1_rs::jer::JerParser::from_jer(value).map(Self)
+                }
+            }
+        })
+    }
+
+    fn derive_alias_jer_encode(&self) -> TokenStream {
+        if !self.options.jer_encoder {
+            return quote! {};
+        }
+
+        let wh = self.where_predicates_without_ber();
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::jer::ToJer for @Self where #(#wh)+* {
+                fn to_jer(&self) -> asn1_rs::JerResult<asn1_rs::jer::JsonValue> {
+                    asn1_rs::jer::ToJer::to_jer(&self.0)
+                }
+            }
+        })
+    }
+
+    fn derive_alias_xmltypename(&self) -> TokenStream {
+        if !(self.options.xer_parser || self.options.xer_encoder) {
+            return quote! {};
+        }
+
+        let wh = self.where_predicates_without_ber();
+        let name = self.ident.unraw().to_string();
+        let ty = &self.target.ast().ty;
+
+        // values are encoded like the aliased type, but the type name is the name of the alias
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::xer::XmlTypeName for @Self where #(#wh)+* {
+                const XML_TYPE_NAME: &'static str = #name;
+                const XML_VALUE_LIST: bool = <#ty as asn1_rs::xer::XmlTypeName>::XML_VALUE_LIST;
+            }
+        })
+    }
+
+    fn derive_alias_xer_parser(&self) -> TokenStream {
+        if !self.options.xer_parser {
+            return quote! {};
+        }
+
+        let wh = self.where_predicates_without_ber();
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::xer::XerParser for @Self where #(#wh)+* {
+                fn from_xer(content: &[asn1_rs::xer::XmlNode]) -> asn1_rs::XerResult<Self> {
+                    asn1_rs::xer::XerParser::from_xer(content).map(Self)
+                }
+            }
+        })
+    }
+
+    fn derive_alias_xer_encode(&self) -> TokenStream {
+        if !self.options.xer_encoder {
+            return quote! {};
+        }
+
+        let wh = self.where_predicates_without_ber();
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::xer::ToXer for @Self where #(#wh)+* {
+                fn xer_encode(&self, writer: &mut asn1_rs::xer::XerWriter) -> asn1_rs::XerResult<()> {
+                    asn1_rs::xer::ToXer::xer_encode(&self.0, writer)
+                }
+            }
+        })
+    }
+
+    fn derive_alias_value_notation(&self) -> TokenStream {
+        if !self.options.value_notation {
+            return quote! {};
+        }
+
+        let wh = self.where_predicates_without_ber();
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::value_notation::ToValueNotation for @Self where #(#wh)+* {
+                fn write_value(&self, f: &mut dyn core::fmt::Write) -> core::fmt::Result {
+                    asn1_rs::value_notation::ToValueNotation::write_value(&self.0, f)
+                }
+            }
+        })
+    }
 }
 
 pub fn derive_ber_alias(s: synstructure::Structure) -> TokenStream {
@@ -338,7 +604,7 @@ pub fn derive_der_alias(s: synstructure::Structure) -> TokenStream {
     let impl_tryfrom = container.gen_tryfrom();
     let impl_tagged = container.gen_tagged();
     let impl_checkconstraints = container.gen_checkconstraints();
-    let impl_fromder = container.gen_fromder();
+    let impl_fromder = container.gen_fromder(false);
     let ts = s.gen_impl(quote! {
         extern crate asn1_rs;
 
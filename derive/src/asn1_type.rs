@@ -7,6 +7,7 @@ use syn::{Error, Ident, LitStr, Token};
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Asn1Type {
     Ber,
+    Cer,
     Der,
 }
 
@@ -14,6 +15,7 @@ impl Asn1Type {
     pub(crate) fn tober(&self) -> TokenStream {
         match *self {
             Asn1Type::Ber => quote!(ToBer),
+            Asn1Type::Cer => quote!(ToCer),
             Asn1Type::Der => quote!(ToDer),
         }
     }
@@ -21,6 +23,7 @@ impl Asn1Type {
     pub(crate) fn compose(&self, suffix: &str) -> TokenStream {
         let prefix = match *self {
             Asn1Type::Ber => "ber",
+            Asn1Type::Cer => "cer",
             Asn1Type::Der => "der",
         };
         let s = format!("{prefix}{suffix}");
@@ -31,6 +34,7 @@ impl Asn1Type {
     pub(crate) fn total_len_tokens(&self) -> TokenStream {
         match *self {
             Asn1Type::Ber => quote!(ber_total_len),
+            Asn1Type::Cer => quote!(cer_total_len),
             Asn1Type::Der => quote!(der_total_len),
         }
     }
@@ -38,6 +42,7 @@ impl Asn1Type {
     pub(crate) fn content_len_tokens(&self) -> TokenStream {
         match *self {
             Asn1Type::Ber => quote!(ber_content_len),
+            Asn1Type::Cer => quote!(cer_content_len),
             Asn1Type::Der => quote!(der_content_len),
         }
     }
@@ -45,6 +50,7 @@ impl Asn1Type {
     pub(crate) fn tag_info_tokens(&self) -> TokenStream {
         match *self {
             Asn1Type::Ber => quote!(ber_tag_info),
+            Asn1Type::Cer => quote!(cer_tag_info),
             Asn1Type::Der => quote!(der_tag_info),
         }
     }
@@ -52,6 +58,7 @@ impl Asn1Type {
     pub(crate) fn encode_tokens(&self) -> TokenStream {
         match *self {
             Asn1Type::Ber => quote!(ber_encode),
+            Asn1Type::Cer => quote!(cer_encode),
             Asn1Type::Der => quote!(der_encode),
         }
     }
@@ -59,6 +66,7 @@ impl Asn1Type {
     pub(crate) fn write_content_tokens(&self) -> TokenStream {
         match *self {
             Asn1Type::Ber => quote!(ber_write_content),
+            Asn1Type::Cer => quote!(cer_write_content),
             Asn1Type::Der => quote!(der_write_content),
         }
     }
@@ -66,6 +74,7 @@ impl Asn1Type {
     pub(crate) fn parser(&self) -> TokenStream {
         match *self {
             Asn1Type::Ber => quote!(BerParser),
+            Asn1Type::Cer => quote!(CerParser),
             Asn1Type::Der => quote!(DerParser),
         }
     }
@@ -73,6 +82,7 @@ impl Asn1Type {
     pub(crate) fn parse_ber(&self) -> TokenStream {
         match *self {
             Asn1Type::Ber => quote!(parse_ber),
+            Asn1Type::Cer => quote!(parse_cer),
             Asn1Type::Der => quote!(parse_der),
         }
     }
@@ -80,14 +90,10 @@ impl Asn1Type {
     pub(crate) fn from_ber_content(&self) -> TokenStream {
         match *self {
             Asn1Type::Ber => quote!(from_ber_content),
+            Asn1Type::Cer => quote!(from_cer_content),
             Asn1Type::Der => quote!(from_der_content),
         }
     }
-
-    pub fn parse_multi(input: ParseStream<'_>) -> syn::Result<impl IntoIterator<Item = Self>> {
-        let lit_s: LitStr = input.parse()?;
-        lit_s.parse_with(Punctuated::<Self, Token![,]>::parse_terminated)
-    }
 }
 
 impl Parse for Asn1Type {
@@ -95,13 +101,81 @@ impl Parse for Asn1Type {
         let ident: Ident = input.parse()?;
         if ident == "BER" {
             Ok(Asn1Type::Ber)
+        } else if ident == "CER" {
+            Ok(Asn1Type::Cer)
         } else if ident == "DER" {
             Ok(Asn1Type::Der)
         } else {
             Err(Error::new(
                 ident.span(),
-                "Invalid ASN.1 type (possible values: BER, DER)",
+                "Invalid ASN.1 type (possible values: BER, CER, DER, JER, OER, PER, XER)",
             ))
         }
     }
 }
+
+/// Encoding rules accepted in the `parse` and `encode` items of the `asn1` attribute
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EncodingRules {
+    /// Rules based on Tag-Length-Value encodings (BER, CER and DER)
+    Tlv(Asn1Type),
+    /// Packed Encoding Rules (aligned and unaligned variants)
+    Per,
+    /// Octet Encoding Rules (basic and canonical variants)
+    Oer,
+    /// JSON Encoding Rules
+    Jer,
+    /// XML Encoding Rules (basic and canonical variants)
+    Xer,
+}
+
+impl EncodingRules {
+    pub fn parse_multi(input: ParseStream<'_>) -> syn::Result<impl IntoIterator<Item = Self>> {
+        let lit_s: LitStr = input.parse()?;
+        lit_s.parse_with(Punctuated::<Self, Token![,]>::parse_terminated)
+    }
+}
+
+impl Parse for EncodingRules {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let fork = input.fork();
+        let ident: Ident = fork.parse()?;
+        if ident == "PER" {
+            input.parse::<Ident>()?;
+            require_feature(&ident, cfg!(feature = "per"), "per")?;
+            Ok(EncodingRules::Per)
+        } else if ident == "OER" {
+            input.parse::<Ident>()?;
+            require_feature(&ident, cfg!(feature = "oer"), "oer")?;
+            Ok(EncodingRules::Oer)
+        } else if ident == "JER" {
+            input.parse::<Ident>()?;
+            require_feature(&ident, cfg!(feature = "jer"), "jer")?;
+            Ok(EncodingRules::Jer)
+        } else if ident == "XER" {
+            input.parse::<Ident>()?;
+            require_feature(&ident, cfg!(feature = "xer"), "xer")?;
+            Ok(EncodingRules::Xer)
+        } else {
+            input.parse().map(EncodingRules::Tlv)
+        }
+    }
+}
+
+/// Reject encoding rules whose support was not enabled in `asn1-rs`
+///
+/// The feature flags of this crate are enabled by the matching features of `asn1-rs`, so the
+/// generated code never refers to a module which was not compiled.
+fn require_feature(ident: &Ident, enabled: bool, feature: &str) -> syn::Result<()> {
+    if enabled {
+        Ok(())
+    } else {
+        Err(Error::new(
+            ident.span(),
+            format!(
+                "{} support requires the `{}` feature of asn1-rs",
+                ident, feature
+            ),
+        ))
+    }
+}
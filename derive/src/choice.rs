@@ -1,9 +1,11 @@
 use crate::asn1_type::Asn1Type;
 use crate::check_derive::check_lastderive_fromber;
+use crate::constraints::FieldConstraints;
 use crate::container::*;
 use crate::options::Options;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
+use syn::ext::IdentExt;
 use syn::{parse_quote, Attribute, Data, Error, Ident, Lifetime, LitInt, Result, WherePredicate};
 use synstructure::VariantInfo;
 
@@ -61,16 +63,40 @@ impl<'s> DeriveChoice<'s> {
     fn to_tokens(&self) -> TokenStream {
         let dyntagged = self.derive_choice_dyntagged();
         let berparser = self.derive_choice_parser(Asn1Type::Ber);
+        let cerparser = self.derive_choice_parser(Asn1Type::Cer);
         let derparser = self.derive_choice_parser(Asn1Type::Der);
         let berencode = self.derive_choice_encode(Asn1Type::Ber);
+        let cerencode = self.derive_choice_encode(Asn1Type::Cer);
         let derencode = self.derive_choice_encode(Asn1Type::Der);
+        let perparser = self.derive_choice_per_parser();
+        let perencode = self.derive_choice_per_encode();
+        let oerparser = self.derive_choice_oer_parser();
+        let oerencode = self.derive_choice_oer_encode();
+        let jerparser = self.derive_choice_jer_parser();
+        let jerencode = self.derive_choice_jer_encode();
+        let xmltypename = self.derive_choice_xmltypename();
+        let xerparser = self.derive_choice_xer_parser();
+        let xerencode = self.derive_choice_xer_encode();
+        let valuenotation = self.derive_choice_value_notation();
 
         let ts = quote! {
             #dyntagged
             #berparser
+            #cerparser
             #derparser
             #berencode
+            #cerencode
             #derencode
+            #perparser
+            #perencode
+            #oerparser
+            #oerencode
+            #jerparser
+            #jerencode
+            #xmltypename
+            #xerparser
+            #xerencode
+            #valuenotation
         };
 
         if self.options.debug {
@@ -116,10 +142,12 @@ impl<'s> DeriveChoice<'s> {
             Some(_) => quote! { asn1_rs::Class::ContextSpecific },
             None => {
                 // more complex answer: depends on variant/binding
+                // note: call `DynTagged` methods explicitly, since `TaggedValue` has an inherent
+                // `class()` method returning the class as `u8`
                 let class_branches = self.variants.iter().map(|v| {
                     let pat = v.vi.pat();
                     let bi = &v.vi.bindings()[0];
-                    quote! { #pat => #bi.class(),  }
+                    quote! { #pat => asn1_rs::DynTagged::class(#bi),  }
                 });
                 quote! {
                     match self {
@@ -135,7 +163,7 @@ impl<'s> DeriveChoice<'s> {
                 let constructed_branches = self.variants.iter().map(|v| {
                     let pat = v.vi.pat();
                     let bi = &v.vi.bindings()[0];
-                    quote! { #pat => #bi.constructed(),  }
+                    quote! { #pat => asn1_rs::DynTagged::constructed(#bi),  }
                 });
                 quote! {
                     match self {
@@ -154,7 +182,7 @@ impl<'s> DeriveChoice<'s> {
                     }
                     None => {
                         let bi = &v.vi.bindings()[0];
-                        quote! { #pat => #bi.tag(),  }
+                        quote! { #pat => asn1_rs::DynTagged::tag(#bi),  }
                     }
                 }
             });
@@ -362,25 +390,106 @@ impl<'s> DeriveChoice<'s> {
         let impl_tober_content_len = self.choice_gen_tober_content_len(asn1_type);
         let impl_tober_tag_info = self.choice_gen_tober_tag_info(asn1_type);
         let impl_tober_write_content = self.choice_gen_tober_write_content(asn1_type);
+        let impl_toder_cached = self.choice_gen_toder_cached(asn1_type);
 
         self.synstruct.gen_impl(quote! {
             extern crate asn1_rs;
-
-            #[cfg(feature = "std")]
             gen impl asn1_rs::#tober for @Self {
                 type Encoder = asn1_rs::BerGenericEncoder;
 
                 #impl_tober_content_len
                 #impl_tober_tag_info
                 #impl_tober_write_content
+                #impl_toder_cached
             }
         })
     }
 
+    /// DER: generate methods for two-pass encoding, using a `LengthCache`, and for single-pass
+    /// encoding to a `ReverseWriter`
+    fn choice_gen_toder_cached(&self, asn1_type: Asn1Type) -> TokenStream {
+        if asn1_type != Asn1Type::Der {
+            return quote! {};
+        }
+
+        let content_len_branches = self.synstruct.fold(quote! {}, |acc, bi| {
+            let instrs = match self.options.tag_kind {
+                Some(Asn1TagKind::Explicit) => quote! { #bi.der_total_len_cached(cache) },
+                Some(Asn1TagKind::Implicit) | None => quote! { #bi.der_content_len_cached(cache) },
+            };
+            quote! { #acc #instrs }
+        });
+        let write_branches = self.variants.iter().map(|v| {
+            let pat = v.vi.pat();
+            let bi = &v.vi.bindings()[0];
+            match self.options.tag_kind {
+                Some(Asn1TagKind::Explicit) => quote! {
+                    #pat => #bi.der_encode_cached(cache, writer),
+                },
+                Some(Asn1TagKind::Implicit) | None => quote! {
+                    #pat => #bi.der_write_content_cached(cache, writer),
+                },
+            }
+        });
+
+        let variant_types = self.variants.iter().map(|v| &v.vi.bindings()[0].ast().ty);
+        let der_encode = needs_length_cache(variant_types).then(|| {
+            quote! {
+                fn der_encode<W: asn1_rs::Writer>(&self, writer: &mut W) -> asn1_rs::SerializeResult<usize> {
+                    self.der_encode_with_cache(writer)
+                }
+            }
+        });
+
+        let write_reverse_branches = self.variants.iter().map(|v| {
+            let pat = v.vi.pat();
+            let bi = &v.vi.bindings()[0];
+            match self.options.tag_kind {
+                Some(Asn1TagKind::Explicit) => quote! {
+                    #pat => #bi.der_encode_reverse(writer),
+                },
+                Some(Asn1TagKind::Implicit) | None => quote! {
+                    #pat => #bi.der_write_content_reverse(writer),
+                },
+            }
+        });
+
+        quote! {
+            fn der_content_len_cached(&self, cache: &mut asn1_rs::LengthCache) -> asn1_rs::Length {
+                match self {
+                    #content_len_branches
+                }
+            }
+
+            fn der_write_content_cached<W: asn1_rs::Writer>(&self, cache: &mut asn1_rs::LengthCache, writer: &mut W) -> asn1_rs::SerializeResult<usize> {
+                match self {
+                    #(#write_branches)*
+                }
+            }
+
+            #der_encode
+
+            fn der_write_content_reverse(&self, writer: &mut asn1_rs::ReverseWriter) -> asn1_rs::SerializeResult<usize> {
+                match self {
+                    #(#write_reverse_branches)*
+                }
+            }
+        }
+    }
+
     fn choice_gen_tober_content_len(&self, asn1_type: Asn1Type) -> TokenStream {
         let content_len = asn1_type.content_len_tokens();
         let total_len = asn1_type.total_len_tokens();
 
+        if asn1_type == Asn1Type::Cer && self.options.tag_kind == Some(Asn1TagKind::Explicit) {
+            // CER: tagged explicit is constructed, and uses the indefinite length form (X.690: 9.1)
+            return quote! {
+                fn #content_len(&self) -> asn1_rs::Length {
+                    asn1_rs::Length::Indefinite
+                }
+            };
+        }
+
         // NOTE: fold() only works on variants with bindings, but we know that it is the case
         let content_len_branches = self.synstruct.fold(quote! {}, |acc, bi| {
             let instrs = match self.options.tag_kind {
@@ -414,6 +523,12 @@ impl<'s> DeriveChoice<'s> {
     fn choice_gen_tober_write_content(&self, asn1_type: Asn1Type) -> TokenStream {
         let encode = asn1_type.compose("_encode");
         let write_content = asn1_type.compose("_write_content");
+        // CER: content of tagged explicit is terminated by End-Of-Content
+        let write_eoc = if asn1_type == Asn1Type::Cer {
+            Some(quote! { .and_then(|sz| Ok(sz + asn1_rs::cer_write_eoc(writer)?)) })
+        } else {
+            None
+        };
 
         let write_branches = self.variants.iter().map(|v| {
             let bindings = v.vi.bindings();
@@ -426,7 +541,7 @@ impl<'s> DeriveChoice<'s> {
                 Some(Asn1TagKind::Explicit) => quote! {
                     #pat => {
                         // encode as tagged explicit (write full object)
-                        #bi.#encode(writer)
+                        #bi.#encode(writer)#write_eoc
                     }
                 },
                 Some(Asn1TagKind::Implicit) | None => quote! {
@@ -439,7 +554,7 @@ impl<'s> DeriveChoice<'s> {
         });
 
         let impl_tober_write_content = quote! {
-            fn #write_content<W: std::io::Write>(&self, writer: &mut W) -> asn1_rs::SerializeResult<usize> {
+            fn #write_content<W: asn1_rs::Writer>(&self, writer: &mut W) -> asn1_rs::SerializeResult<usize> {
                     match self {
                         #(#write_branches)*
                     }
@@ -447,6 +562,369 @@ impl<'s> DeriveChoice<'s> {
         };
         impl_tober_write_content
     }
+
+    /// Returns the variants in the order used for PER indexes: canonical order of tags if the
+    /// choice is tagged, or declaration order otherwise
+    fn per_sorted_variants(&self) -> Vec<&TagVariant<'s, 's>> {
+        let mut variants: Vec<_> = self.variants.iter().collect();
+        if self.options.tag_kind.is_some() {
+            variants.sort_by_key(|v| v.tag);
+        }
+        variants
+    }
+
+    fn derive_choice_per_parser(&self) -> TokenStream {
+        if !self.options.per_parser {
+            return quote! {};
+        }
+
+        let lft = Lifetime::new("'ber", Span::call_site());
+        let variants = self.per_sorted_variants();
+        let root_count = variants.len();
+        let extensible = self.options.extensible;
+
+        let parse_branches = variants.iter().enumerate().map(|(idx, v)| {
+            let bi = &v.vi.bindings()[0];
+            let construct = v.vi.construct(|_, _i| bi);
+            let constraints = v.constraints.to_constraints_tokens();
+            quote! {
+                #idx => {
+                    let #bi = asn1_rs::per::PerParser::per_decode(reader, &#constraints)?;
+                    Ok(#construct)
+                }
+            }
+        });
+
+        // input slice must outlive all lifetimes from Self
+        let lfts: Vec<_> = self.synstruct.ast().generics.lifetimes().collect();
+        let mut where_predicates = Vec::new();
+        if !lfts.is_empty() {
+            let wh: WherePredicate = parse_quote! { #lft: #(#lfts)+* };
+            where_predicates.push(wh);
+        };
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl<#lft> asn1_rs::per::PerParser<#lft> for @Self where #(#where_predicates)+* {
+                fn per_decode(reader: &mut asn1_rs::per::PerReader<#lft>, _constraints: &asn1_rs::Constraints) -> asn1_rs::PerResult<Self> {
+                    match reader.read_choice_index(#root_count, #extensible)? {
+                        #(#parse_branches)*
+                        _ => Err(asn1_rs::PerError::InvalidEncoding),
+                    }
+                }
+            }
+        })
+    }
+
+    fn derive_choice_per_encode(&self) -> TokenStream {
+        if !self.options.per_encoder {
+            return quote! {};
+        }
+
+        let variants = self.per_sorted_variants();
+        let root_count = variants.len();
+        let extensible = self.options.extensible;
+
+        let write_branches = variants.iter().enumerate().map(|(idx, v)| {
+            let pat = v.vi.pat();
+            let bi = &v.vi.bindings()[0];
+            let constraints = v.constraints.to_constraints_tokens();
+            quote! {
+                #pat => {
+                    writer.write_choice_index(#idx, #root_count, #extensible)?;
+                    asn1_rs::per::ToPer::per_encode(#bi, writer, &#constraints)
+                }
+            }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::per::ToPer for @Self {
+                fn per_encode(&self, writer: &mut asn1_rs::per::PerWriter, _constraints: &asn1_rs::Constraints) -> asn1_rs::PerResult<()> {
+                    match self {
+                        #(#write_branches)*
+                    }
+                }
+            }
+        })
+    }
+
+    fn derive_choice_oer_parser(&self) -> TokenStream {
+        if !self.options.oer_parser {
+            return quote! {};
+        }
+
+        let lft = Lifetime::new("'ber", Span::call_site());
+
+        // the tag of the chosen alternative is encoded (X.696: 20): context-specific tags of the
+        // variants if the choice is tagged, or the tags of the variant types otherwise
+        let tagged = self.options.tag_kind.is_some();
+        let parse_branches_if_else = self.variants.iter().map(|v| {
+            let bi = &v.vi.bindings()[0];
+            let construct = v.vi.construct(|_, _i| bi);
+            let ty = &bi.ast().ty;
+            let constraints = v.constraints.to_constraints_tokens();
+            let accept = if tagged {
+                let tag = v.tag;
+                quote! { class == asn1_rs::Class::ContextSpecific && tag.0 == #tag }
+            } else {
+                quote! { <#ty>::accept_tag(tag) }
+            };
+            quote! {
+                if #accept {
+                    let #bi = asn1_rs::oer::OerParser::oer_decode(reader, &#constraints)?;
+                    Ok(#construct)
+                } else
+            }
+        });
+        let unknown_alternative = if self.options.extensible {
+            quote! {
+                // extension additions are encoded as open types
+                reader.read_open_type()?;
+                Err(asn1_rs::OerError::UnknownExtension(u64::from(tag.0)))
+            }
+        } else {
+            quote! { Err(asn1_rs::OerError::InvalidEncoding) }
+        };
+
+        // input slice must outlive all lifetimes from Self
+        let lfts: Vec<_> = self.synstruct.ast().generics.lifetimes().collect();
+        let mut where_predicates = Vec::new();
+        if !lfts.is_empty() {
+            let wh: WherePredicate = parse_quote! { #lft: #(#lfts)+* };
+            where_predicates.push(wh);
+        };
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl<#lft> asn1_rs::oer::OerParser<#lft> for @Self where #(#where_predicates)+* {
+                fn oer_decode(reader: &mut asn1_rs::oer::OerReader<#lft>, _constraints: &asn1_rs::Constraints) -> asn1_rs::OerResult<Self> {
+                    use asn1_rs::DynTagged;
+                    let (class, tag) = reader.read_tag()?;
+                    #(#parse_branches_if_else)*
+                    {
+                        #unknown_alternative
+                    }
+                }
+            }
+        })
+    }
+
+    fn derive_choice_oer_encode(&self) -> TokenStream {
+        if !self.options.oer_encoder {
+            return quote! {};
+        }
+
+        let tagged = self.options.tag_kind.is_some();
+        let write_branches = self.variants.iter().map(|v| {
+            let pat = v.vi.pat();
+            let bi = &v.vi.bindings()[0];
+            let constraints = v.constraints.to_constraints_tokens();
+            let write_tag = if tagged {
+                let tag = v.tag;
+                quote! { writer.write_tag(asn1_rs::Class::ContextSpecific, asn1_rs::Tag(#tag)); }
+            } else {
+                quote! { writer.write_tag(asn1_rs::DynTagged::class(#bi), asn1_rs::DynTagged::tag(#bi)); }
+            };
+            quote! {
+                #pat => {
+                    #write_tag
+                    asn1_rs::oer::ToOer::oer_encode(#bi, writer, &#constraints)
+                }
+            }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::oer::ToOer for @Self {
+                fn oer_encode(&self, writer: &mut asn1_rs::oer::OerWriter, _constraints: &asn1_rs::Constraints) -> asn1_rs::OerResult<()> {
+                    use asn1_rs::DynTagged;
+                    match self {
+                        #(#write_branches)*
+                    }
+                }
+            }
+        })
+    }
+
+    fn derive_choice_jer_parser(&self) -> TokenStream {
+        if !self.options.jer_parser {
+            return quote! {};
+        }
+
+        // the chosen alternative is encoded as an object with a single member, named after the
+        // variant (X.697: 30)
+        let parse_branches = self.variants.iter().map(|v| {
+            let bi = &v.vi.bindings()[0];
+            let construct = v.vi.construct(|_, _i| bi);
+            let member = v.vi.ast().ident.unraw().to_string();
+            quote! {
+                #member => {
+                    let #bi = asn1_rs::jer::JerParser::from_jer(__value)?;
+                    Ok(#construct)
+                }
+            }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::jer::JerParser for @Self {
+                fn from_jer(value: &asn1_rs::jer::JsonValue) -> asn1_rs::JerResult<Self> {
+                    let __object = value.as_object().ok_or(asn1_rs::JerError::UnexpectedType)?;
+                    let (__name, __value) = match __object.iter().next() {
+                        Some(member) if __object.len() == 1 => member,
+                        _ => return Err(asn1_rs::JerError::InvalidValue),
+                    };
+                    match __name {
+                        #(#parse_branches)*
+                        _ => Err(asn1_rs::JerError::UnknownAlternative(__name.into())),
+                    }
+                }
+            }
+        })
+    }
+
+    fn derive_choice_jer_encode(&self) -> TokenStream {
+        if !self.options.jer_encoder {
+            return quote! {};
+        }
+
+        let write_branches = self.variants.iter().map(|v| {
+            let pat = v.vi.pat();
+            let bi = &v.vi.bindings()[0];
+            let member = v.vi.ast().ident.unraw().to_string();
+            quote! {
+                #pat => __object.insert(#member, asn1_rs::jer::ToJer::to_jer(#bi)?),
+            }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::jer::ToJer for @Self {
+                fn to_jer(&self) -> asn1_rs::JerResult<asn1_rs::jer::JsonValue> {
+                    let mut __object = asn1_rs::jer::JsonObject::new();
+                    match self {
+                        #(#write_branches)*
+                    }
+                    Ok(asn1_rs::jer::JsonValue::Object(__object))
+                }
+            }
+        })
+    }
+
+    fn derive_choice_xmltypename(&self) -> TokenStream {
+        if !(self.options.xer_parser || self.options.xer_encoder) {
+            return quote! {};
+        }
+
+        let name = self.ident.unraw().to_string();
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::xer::XmlTypeName for @Self {
+                const XML_TYPE_NAME: &'static str = #name;
+                const XML_VALUE_LIST: bool = true;
+            }
+        })
+    }
+
+    fn derive_choice_xer_parser(&self) -> TokenStream {
+        if !self.options.xer_parser {
+            return quote! {};
+        }
+
+        // the chosen alternative is encoded in an element named after the variant
+        let parse_branches = self.variants.iter().map(|v| {
+            let bi = &v.vi.bindings()[0];
+            let construct = v.vi.construct(|_, _i| bi);
+            let element = v.vi.ast().ident.unraw().to_string();
+            quote! {
+                #element => {
+                    let #bi = asn1_rs::xer::XerParser::from_xer(&__element.content)?;
+                    Ok(#construct)
+                }
+            }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::xer::XerParser for @Self {
+                fn from_xer(content: &[asn1_rs::xer::XmlNode]) -> asn1_rs::XerResult<Self> {
+                    let __element = asn1_rs::xer::single_element(content)?;
+                    match __element.name.as_str() {
+                        #(#parse_branches)*
+                        _ => Err(asn1_rs::XerError::UnknownAlternative(__element.name.clone())),
+                    }
+                }
+            }
+        })
+    }
+
+    fn derive_choice_xer_encode(&self) -> TokenStream {
+        if !self.options.xer_encoder {
+            return quote! {};
+        }
+
+        let write_branches = self.variants.iter().map(|v| {
+            let pat = v.vi.pat();
+            let bi = &v.vi.bindings()[0];
+            let element = v.vi.ast().ident.unraw().to_string();
+            quote! {
+                #pat => asn1_rs::xer::ToXer::xer_encode_element(#bi, writer, #element),
+            }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::xer::ToXer for @Self {
+                fn xer_encode(&self, writer: &mut asn1_rs::xer::XerWriter) -> asn1_rs::XerResult<()> {
+                    match self {
+                        #(#write_branches)*
+                    }
+                }
+            }
+        })
+    }
+
+    fn derive_choice_value_notation(&self) -> TokenStream {
+        if !self.options.value_notation {
+            return quote! {};
+        }
+
+        // values are written as `identifier : value`
+        let write_branches = self.variants.iter().map(|v| {
+            let pat = v.vi.pat();
+            let bi = &v.vi.bindings()[0];
+            let identifier = format!("{} : ", v.vi.ast().ident.unraw());
+            quote! {
+                #pat => {
+                    f.write_str(#identifier)?;
+                    asn1_rs::value_notation::ToValueNotation::write_value(#bi, f)
+                }
+            }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::value_notation::ToValueNotation for @Self {
+                fn write_value(&self, f: &mut dyn core::fmt::Write) -> core::fmt::Result {
+                    match self {
+                        #(#write_branches)*
+                    }
+                }
+            }
+        })
+    }
 }
 
 //--- old-style derive
@@ -489,6 +967,7 @@ pub fn derive_berparser_choice_container(
 struct TagVariant<'a, 'r> {
     tag: u32,
     vi: &'r VariantInfo<'a>,
+    constraints: FieldConstraints,
 }
 
 fn parse_tag_variants<'a, 'r>(
@@ -520,7 +999,12 @@ fn parse_tag_variants<'a, 'r>(
                 }
                 None => current_tag,
             };
-            let tag_variant = TagVariant { tag, vi };
+            let constraints = get_variant_constraints(vi)?;
+            let tag_variant = TagVariant {
+                tag,
+                vi,
+                constraints,
+            };
             // before inserting, check for tags uniqueness
             if acc.iter().any(|tv: &TagVariant<'_, '_>| tv.tag == tag) {
                 return Err(Error::new_spanned(
@@ -551,3 +1035,20 @@ fn get_variant_tag(vi: &VariantInfo<'_>) -> Result<Option<u32>> {
 
     Ok(None)
 }
+
+/// Check `asn1` attributes for PER-visible constraints
+fn get_variant_constraints(vi: &VariantInfo<'_>) -> Result<FieldConstraints> {
+    let mut constraints = FieldConstraints::default();
+    for attr in vi.ast().attrs {
+        if attr.meta.path().is_ident("asn1") {
+            attr.parse_nested_meta(|meta| {
+                if constraints.parse_meta(&meta)? {
+                    Ok(())
+                } else {
+                    Err(meta.error("Invalid or unknown attribute"))
+                }
+            })?;
+        }
+    }
+    Ok(constraints)
+}
@@ -0,0 +1,112 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::{parenthesized, Expr, ExprRange, Ident, RangeLimits, Result, Token};
+
+/// Bounds of a constraint, as parsed from `value(...)` or `size(...)` attribute items
+#[derive(Debug)]
+pub struct ConstraintBounds {
+    pub lower: Option<TokenStream>,
+    pub upper: Option<TokenStream>,
+    pub extensible: bool,
+}
+
+/// PER-visible constraints of a field or variant
+#[derive(Debug, Default)]
+pub struct FieldConstraints {
+    pub value: Option<ConstraintBounds>,
+    pub size: Option<ConstraintBounds>,
+}
+
+impl FieldConstraints {
+    /// Try to parse a `value(...)` or `size(...)` item from the `asn1` attribute
+    ///
+    /// Returns `false` if the item is not a constraint.
+    pub fn parse_meta(&mut self, meta: &ParseNestedMeta) -> Result<bool> {
+        let target = if meta.path.is_ident("value") {
+            &mut self.value
+        } else if meta.path.is_ident("size") {
+            &mut self.size
+        } else {
+            return Ok(false);
+        };
+        if target.is_some() {
+            return Err(meta.error("constraint cannot be set twice"));
+        }
+        *target = Some(parse_constraint_bounds(meta)?);
+        Ok(true)
+    }
+
+    /// Returns an expression building the `asn1_rs::Constraints` object
+    pub fn to_constraints_tokens(&self) -> TokenStream {
+        let value = self.value.as_ref().map(|b| {
+            let lower = b
+                .lower
+                .as_ref()
+                .map_or_else(|| quote! { None }, |lb| quote! { Some((#lb) as i128) });
+            let upper = b
+                .upper
+                .as_ref()
+                .map_or_else(|| quote! { None }, |ub| quote! { Some((#ub) as i128) });
+            let extensible = b.extensible;
+            quote! { .with_value(#lower, #upper, #extensible) }
+        });
+        let size = self.size.as_ref().map(|b| {
+            let lower = b
+                .lower
+                .as_ref()
+                .map_or_else(|| quote! { 0 }, |lb| quote! { (#lb) as usize });
+            let upper = b
+                .upper
+                .as_ref()
+                .map_or_else(|| quote! { None }, |ub| quote! { Some((#ub) as usize) });
+            let extensible = b.extensible;
+            quote! { .with_size(#lower, #upper, #extensible) }
+        });
+        quote! { asn1_rs::Constraints::NONE #value #size }
+    }
+}
+
+/// Parse constraint bounds: `(range)` or `(value)`, optionally followed by `, extensible`
+///
+/// Supported ranges are `a..=b`, `a..b` and `a..`.
+fn parse_constraint_bounds(meta: &ParseNestedMeta) -> Result<ConstraintBounds> {
+    let content;
+    parenthesized!(content in meta.input);
+    let expr: Expr = content.parse()?;
+    let (lower, upper) = match expr {
+        Expr::Range(ExprRange {
+            start, limits, end, ..
+        }) => {
+            let lower = start.map(|e| quote! { #e });
+            let upper = match (limits, end) {
+                (RangeLimits::Closed(_), Some(e)) => Some(quote! { #e }),
+                (RangeLimits::HalfOpen(_), Some(e)) => Some(quote! { (#e) - 1 }),
+                (_, None) => None,
+            };
+            (lower, upper)
+        }
+        // single value
+        e => (Some(quote! { #e }), Some(quote! { #e })),
+    };
+    let mut extensible = false;
+    if content.peek(Token![,]) {
+        content.parse::<Token![,]>()?;
+        let ident: Ident = content.parse()?;
+        if ident != "extensible" {
+            return Err(syn::Error::new(
+                ident.span(),
+                "Invalid constraint item (expected 'extensible')",
+            ));
+        }
+        extensible = true;
+    }
+    if !content.is_empty() {
+        return Err(content.error("Unexpected tokens in constraint"));
+    }
+    Ok(ConstraintBounds {
+        lower,
+        upper,
+        extensible,
+    })
+}
@@ -1,7 +1,8 @@
 use std::convert::TryFrom;
 
 use proc_macro2::{Literal, Span, TokenStream};
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::ext::IdentExt;
 use syn::parse::ParseStream;
 use syn::spanned::Spanned;
 use syn::{
@@ -10,6 +11,7 @@ use syn::{
 };
 
 use crate::asn1_type::Asn1Type;
+use crate::constraints::FieldConstraints;
 use crate::options::Options;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -52,6 +54,17 @@ impl ToTokens for Asn1TagKind {
     }
 }
 
+/// Target of generated `write_content` methods
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum WriteMode {
+    /// Write to a `Writer`, computing lengths of fields when writing headers
+    Direct,
+    /// Write to a `Writer`, reading lengths of fields from a `LengthCache` (DER only)
+    Cached,
+    /// Write fields back-to-front to a `ReverseWriter` (DER only)
+    Reverse,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Asn1TagClass {
     Universal,
@@ -159,10 +172,18 @@ impl Container {
         Ok(container)
     }
 
+    /// Generate the parsing of fields, for the `FromBer`/`FromDer` implementations
+    fn gen_ber_content(&self, asn1_type: Asn1Type, extensible: bool) -> TokenStream {
+        if self.container_type == ContainerType::Set {
+            derive_ber_set_content(&self.fields, asn1_type, self.error.is_some(), extensible)
+        } else {
+            derive_ber_sequence_content(&self.fields, asn1_type, self.error.is_some())
+        }
+    }
+
     pub fn gen_tryfrom(&self) -> TokenStream {
         let field_names = &self.fields.iter().map(|f| &f.name).collect::<Vec<_>>();
-        let parse_content =
-            derive_ber_sequence_content(&self.fields, Asn1Type::Ber, self.error.is_some());
+        let parse_content = self.gen_ber_content(Asn1Type::Ber, false);
         let lifetime = Lifetime::new("'ber", Span::call_site());
         let wh = &self.where_predicates;
         let error = if let Some(attr) = &self.error {
@@ -287,7 +308,22 @@ impl Container {
 
         let field_names = &self.fields.iter().map(|f| &f.name).collect::<Vec<_>>();
 
-        let parse_content = derive_berparser_sequence_content(&self.fields, asn1_type);
+        // errors are annotated with the field path, unless a custom error type is used
+        let type_name = if self.error.is_none() {
+            Some(s.ast().ident.unraw().to_string())
+        } else {
+            None
+        };
+        let parse_content = if self.container_type == ContainerType::Set {
+            derive_berparser_set_content(
+                &self.fields,
+                asn1_type,
+                type_name.as_deref(),
+                options.extensible,
+            )
+        } else {
+            derive_berparser_sequence_content(&self.fields, asn1_type, type_name.as_deref())
+        };
 
         // Note: if Self has lifetime bounds, then a new bound must be added to the implementation
         // For ex: `pub struct AA<'a>` will require a bound `impl[..] DerParser[..] where 'i: 'a`
@@ -395,7 +431,7 @@ impl Container {
             quote!(
                 // Tagged Explicit must be constructed (X.690 8.14.2)
                 if !header.constructed() {
-                    return Err(nom::Err::Error(
+                    return Err(asn1_rs::nom::Err::Error(
                         asn1_rs::BerError::new(rem, asn1_rs::InnerError::ConstructExpected).into(),
                     ));
                 }
@@ -403,6 +439,18 @@ impl Container {
         }
     }
 
+    /// Generate a check of the order of components of a `SET` with content `data`, which must be
+    /// sorted in canonical tag order in DER (X.690: 10.3)
+    ///
+    /// Returns `None` if `Self` is not a `SET`
+    fn gen_check_set_order(&self, data: TokenStream) -> Option<TokenStream> {
+        if self.container_type == ContainerType::Set {
+            Some(quote! { asn1_rs::check_set_components_sorted(#data) })
+        } else {
+            None
+        }
+    }
+
     pub fn gen_checkconstraints(&self) -> TokenStream {
         let lifetime = Lifetime::new("'ber", Span::call_site());
         let wh = &self.where_predicates;
@@ -430,8 +478,12 @@ impl Container {
                     }
                 })
                 .collect();
+            let check_order = self
+                .gen_check_set_order(quote! { any.data.as_bytes2() })
+                .map(|check| quote! { #check?; });
             quote! {
                 any.tag().assert_eq(Self::TAG)?;
+                #check_order
                 let rem = any.data.as_bytes2();
                 #(#check_fields)*
                 Ok(())
@@ -450,12 +502,11 @@ impl Container {
         }
     }
 
-    pub fn gen_fromder(&self) -> TokenStream {
+    pub fn gen_fromder(&self, extensible: bool) -> TokenStream {
         let lifetime = Lifetime::new("'ber", Span::call_site());
         let wh = &self.where_predicates;
         let field_names = &self.fields.iter().map(|f| &f.name).collect::<Vec<_>>();
-        let parse_content =
-            derive_ber_sequence_content(&self.fields, Asn1Type::Der, self.error.is_some());
+        let parse_content = self.gen_ber_content(Asn1Type::Der, extensible);
         let error = if let Some(attr) = &self.error {
             get_attribute_meta(attr).expect("Invalid error attribute format")
         } else {
@@ -478,10 +529,14 @@ impl Container {
                 }
             }
         } else {
+            let check_order = self
+                .gen_check_set_order(quote! { i })
+                .map(|check| quote! { #check.map_err(|e| asn1_rs::nom::Err::Error(e.into()))?; });
             quote! {
                 let (rem, any) = asn1_rs::Any::from_der(bytes).map_err(asn1_rs::nom::Err::convert)?;
                 any.header.assert_tag(Self::TAG).map_err(|e| asn1_rs::nom::Err::Error(e.into()))?;
                 let i = any.data.as_bytes2();
+                #check_order
                 //
                 #parse_content
                 //
@@ -507,6 +562,36 @@ impl Container {
         asn1_type: Asn1Type,
         s: &synstructure::Structure,
     ) -> TokenStream {
+        let content_len = asn1_type.content_len_tokens();
+        if asn1_type == Asn1Type::Cer && self.container_type != ContainerType::Alias {
+            // CER: constructed types shall use the indefinite length form (X.690: 9.1)
+            return quote! {
+                fn #content_len(&self) -> asn1_rs::Length {
+                    asn1_rs::Length::Indefinite
+                }
+            };
+        }
+        let body = self.gen_content_len_body(asn1_type, s, false);
+        quote! {
+            fn #content_len(&self) -> asn1_rs::Length {
+                match *self {
+                    #body
+                }
+            }
+        }
+    }
+
+    /// Return the sum of lengths of fields
+    ///
+    /// If `cached` is true, lengths of fields are recorded in a `LengthCache` named `cache` (DER
+    /// only).
+    fn gen_content_len_body(
+        &self,
+        asn1_type: Asn1Type,
+        s: &synstructure::Structure,
+        cached: bool,
+    ) -> TokenStream {
+        let content_len = asn1_type.content_len_tokens();
         let total_len = if self.container_type == ContainerType::Alias {
             // alias: content length only
             asn1_type.content_len_tokens()
@@ -514,48 +599,74 @@ impl Container {
             // this is a structured type, full object length
             asn1_type.total_len_tokens()
         };
-        let content_len = asn1_type.content_len_tokens();
 
-        let body = s.fold(quote! {asn1_rs::Length::Definite(0)}, |acc, bi| {
+        s.fold(quote! {asn1_rs::Length::Definite(0)}, |acc, bi| {
+            let field = get_field(&self.fields, bi.ast().ident.as_ref());
             // check if binding has a 'tag_explicit' or 'tag_implicit' attribute
-            let tag_kind = get_field(&self.fields, bi.ast().ident.as_ref())
-                .map(|f| f.tag)
-                .flatten();
+            let tag_kind = field.and_then(|f| f.tag);
 
-            match tag_kind {
-                Some((Asn1TagKind::Explicit, _class, tag)) => {
+            let len = match (tag_kind, cached) {
+                (Some((Asn1TagKind::Explicit, _class, tag)), false) => {
                     // TAGGED EXPLICIT: add length required to encode tag header
+                    // An absent OPTIONAL value (total length 0) is not encoded
                     let tag = u32::from(tag);
                     quote! {
-                        #acc
-                            + asn1_rs::ber_header_length(asn1_rs::Tag(#tag), #bi.#total_len()).unwrap_or_default()
-                            + #bi.#total_len()
+                        match #bi.#total_len() {
+                            asn1_rs::Length::Definite(0) => asn1_rs::Length::Definite(0),
+                            l => asn1_rs::ber_total_length(asn1_rs::Tag(#tag), l),
+                        }
                     }
                 }
-                Some((Asn1TagKind::Implicit, _class, tag)) => {
+                (Some((Asn1TagKind::Implicit, _class, tag)), false) => {
                     // TAGGED IMPLICIT: add length required to encode tag header
                     // This could be different from `#bi.#total_len()` in the specific case one of
                     // (implicit tag, object tag) is long and the other is not
                     let tag = u32::from(tag);
                     quote! {
-                        #acc
-                            + asn1_rs::ber_header_length(asn1_rs::Tag(#tag), #bi.#total_len()).unwrap_or_default()
-                            + #bi.#content_len()
+                        match #bi.#total_len() {
+                            asn1_rs::Length::Definite(0) => asn1_rs::Length::Definite(0),
+                            _ => asn1_rs::ber_total_length(asn1_rs::Tag(#tag), #bi.#content_len()),
+                        }
                     }
                 }
-                None => quote! {
-                    #acc + #bi.#total_len()
-                },
-            }
-        });
-
-        quote! {
-            fn #content_len(&self) -> asn1_rs::Length {
-                match *self {
-                    #body
+                (None, false) => quote! { #bi.#total_len() },
+                (Some((Asn1TagKind::Explicit, _class, tag)), true) => {
+                    let tag = u32::from(tag);
+                    quote! { #bi.der_total_len_tagged_explicit_cached(#tag, cache) }
+                }
+                (Some((Asn1TagKind::Implicit, _class, tag)), true) => {
+                    let tag = u32::from(tag);
+                    quote! { #bi.der_total_len_tagged_implicit_cached(#tag, cache) }
                 }
+                (None, true) if self.container_type == ContainerType::Alias => {
+                    quote! { #bi.der_content_len_cached(cache) }
+                }
+                (None, true) => quote! { #bi.der_total_len_cached(cache) },
+            };
+            match self.omitted_default(asn1_type, field) {
+                Some(f) => {
+                    let is_default = f.gen_is_default(quote! { #bi });
+                    quote! {
+                        #acc + if #is_default { asn1_rs::Length::Definite(0) } else { #len }
+                    }
+                }
+                None => quote! { #acc + #len },
             }
+        })
+    }
+
+    /// Return `field`, if it has a default value and must be omitted when equal to this value
+    ///
+    /// DER and CER: a value equal to its DEFAULT value shall not be encoded (X.690: 11.5)
+    fn omitted_default<'a>(
+        &self,
+        asn1_type: Asn1Type,
+        field: Option<&'a FieldInfo>,
+    ) -> Option<&'a FieldInfo> {
+        if asn1_type == Asn1Type::Ber || self.container_type == ContainerType::Alias {
+            return None;
         }
+        field.filter(|f| f.default.is_some())
     }
 
     pub fn gen_tober_tag_info(&self, asn1_type: Asn1Type) -> TokenStream {
@@ -594,6 +705,27 @@ impl Container {
         asn1_type: Asn1Type,
         s: &synstructure::Structure,
     ) -> TokenStream {
+        let write_content = asn1_type.write_content_tokens();
+        let body = self.gen_write_content_body(asn1_type, s, WriteMode::Direct);
+        quote! {
+            fn #write_content<W: asn1_rs::Writer>(&self, writer: &mut W) -> asn1_rs::SerializeResult<usize> {
+                match *self {
+                    #(#body)*
+                }
+            }
+        }
+    }
+
+    /// Return the match branches encoding fields, for each variant
+    ///
+    /// With `WriteMode::Cached`, lengths are read from a `LengthCache` named `cache`. With
+    /// `WriteMode::Reverse`, `writer` is a `ReverseWriter` and fields are written in reverse order.
+    fn gen_write_content_body(
+        &self,
+        asn1_type: Asn1Type,
+        s: &synstructure::Structure,
+        mode: WriteMode,
+    ) -> Vec<TokenStream> {
         let encode = if self.container_type == ContainerType::Alias {
             // alias: only write content
             asn1_type.write_content_tokens()
@@ -601,50 +733,174 @@ impl Container {
             // this is a structured type, encode full object
             asn1_type.encode_tokens()
         };
-        let write_content = asn1_type.write_content_tokens();
         let encode_explicit = asn1_type.compose("_encode_tagged_explicit");
         let encode_implicit = asn1_type.compose("_encode_tagged_implicit");
+        // CER: content of constructed types is terminated by End-Of-Content
+        let write_eoc = if asn1_type == Asn1Type::Cer && self.container_type != ContainerType::Alias
+        {
+            Some(quote! { num_bytes += asn1_rs::cer_write_eoc(writer)?; })
+        } else {
+            None
+        };
+
+        // DER/CER: components of SET shall be sorted by tag (X.690: 9.3 and 10.3), so they are
+        // encoded separately before being written
+        let sorted = self.container_type == ContainerType::Set && asn1_type != Asn1Type::Ber;
 
         // we can't just use `s.fold()` because we need to add a footer `Ok(num_bytes)`
-        let body = s.variants().iter().map(|vi| {
-            let encode = vi.bindings().iter().map(|bi| {
-                // check if binding has a 'tag_explicit' or 'tag_implicit' attribute
-                let tag_kind = get_field(&self.fields, bi.ast().ident.as_ref())
-                    .map(|f| f.tag)
-                    .flatten();
-
-                match tag_kind {
-                    Some((Asn1TagKind::Explicit, class, tag)) => {
-                        let tk_class = class.class_tokens();
-                        let tag = u32::from(tag);
-                        quote! {
-                            num_bytes += #bi.#encode_explicit(#tk_class, #tag, writer)?;
+        s.variants()
+            .iter()
+            .map(|vi| {
+                let mut bindings: Vec<_> = vi.bindings().iter().collect();
+                if mode == WriteMode::Reverse && !sorted {
+                    bindings.reverse();
+                }
+                let encode = bindings.into_iter().map(|bi| {
+                    let field = get_field(&self.fields, bi.ast().ident.as_ref());
+                    // check if binding has a 'tag_explicit' or 'tag_implicit' attribute
+                    let tag_kind = field.and_then(|f| f.tag);
+                    let writer = if sorted {
+                        quote! { &mut v }
+                    } else {
+                        quote! { writer }
+                    };
+
+                    let encode = match (tag_kind, mode) {
+                        (Some((Asn1TagKind::Explicit, class, tag)), WriteMode::Direct) => {
+                            let tk_class = class.class_tokens();
+                            let tag = u32::from(tag);
+                            quote! { #bi.#encode_explicit(#tk_class, #tag, #writer)? }
+                        }
+                        (Some((Asn1TagKind::Implicit, class, tag)), WriteMode::Direct) => {
+                            let tk_class = class.class_tokens();
+                            let tag = u32::from(tag);
+                            quote! { #bi.#encode_implicit(#tk_class, #tag, #writer)? }
+                        }
+                        (None, WriteMode::Direct) => quote! { #bi.#encode(#writer)? },
+                        (Some((Asn1TagKind::Explicit, class, tag)), WriteMode::Cached) => {
+                            let tk_class = class.class_tokens();
+                            let tag = u32::from(tag);
+                            quote! { #bi.der_encode_tagged_explicit_cached(#tk_class, #tag, cache, #writer)? }
                         }
+                        (Some((Asn1TagKind::Implicit, class, tag)), WriteMode::Cached) => {
+                            let tk_class = class.class_tokens();
+                            let tag = u32::from(tag);
+                            quote! { #bi.der_encode_tagged_implicit_cached(#tk_class, #tag, cache, #writer)? }
+                        }
+                        (None, WriteMode::Cached) if self.container_type == ContainerType::Alias => {
+                            quote! { #bi.der_write_content_cached(cache, #writer)? }
+                        }
+                        (None, WriteMode::Cached) => quote! { #bi.der_encode_cached(cache, #writer)? },
+                        (Some((Asn1TagKind::Explicit, class, tag)), WriteMode::Reverse) => {
+                            let tk_class = class.class_tokens();
+                            let tag = u32::from(tag);
+                            quote! { #bi.der_encode_tagged_explicit_reverse(#tk_class, #tag, #writer)? }
+                        }
+                        (Some((Asn1TagKind::Implicit, class, tag)), WriteMode::Reverse) => {
+                            let tk_class = class.class_tokens();
+                            let tag = u32::from(tag);
+                            quote! { #bi.der_encode_tagged_implicit_reverse(#tk_class, #tag, #writer)? }
+                        }
+                        (None, WriteMode::Reverse) if self.container_type == ContainerType::Alias => {
+                            quote! { #bi.der_write_content_reverse(#writer)? }
+                        }
+                        (None, WriteMode::Reverse) => quote! { #bi.der_encode_reverse(#writer)? },
+                    };
+                    let encode = match (sorted, mode) {
+                        (true, WriteMode::Reverse) => quote! {
+                            let mut v = asn1_rs::ReverseWriter::new();
+                            #encode;
+                            encodings.push(v.into_vec());
+                        },
+                        (true, _) => quote! {
+                            let mut v = asn1_rs::exports::vec::Vec::new();
+                            #encode;
+                            encodings.push(v);
+                        },
+                        (false, _) => quote! { num_bytes += #encode; },
+                    };
+                    match self.omitted_default(asn1_type, field) {
+                        Some(f) => {
+                            let is_default = f.gen_is_default(quote! { #bi });
+                            quote! {
+                                if !#is_default {
+                                    #encode
+                                }
+                            }
+                        }
+                        None => encode,
                     }
-                    Some((Asn1TagKind::Implicit, class, tag)) => {
-                        let tk_class = class.class_tokens();
-                        let tag = u32::from(tag);
+                });
+                let encode = if sorted {
+                    let write_sorted = if mode == WriteMode::Reverse {
                         quote! {
-                            num_bytes += #bi.#encode_implicit(#tk_class, #tag, writer)?;
+                            writer.prepend_with(|v| asn1_rs::write_sorted_set_components(encodings, v))?
                         }
+                    } else {
+                        quote! { asn1_rs::write_sorted_set_components(encodings, writer)? }
+                    };
+                    quote! {
+                        let mut encodings = asn1_rs::exports::vec::Vec::new();
+                        #(#encode)*
+                        num_bytes += #write_sorted;
+                    }
+                } else {
+                    quote! { #(#encode)* }
+                };
+                let pat = vi.pat();
+                quote! {
+                    #pat => {
+                        let mut num_bytes = 0;
+                        #encode
+                        #write_eoc
+                        Ok(num_bytes)
                     }
-                    None => quote! { num_bytes += #bi.#encode(writer)?; },
                 }
-            });
-            let pat = vi.pat();
+            })
+            .collect()
+    }
+
+    /// DER: generate methods for two-pass encoding, using a `LengthCache`, and for single-pass
+    /// encoding to a `ReverseWriter`
+    ///
+    /// `der_encode` is implemented using the cache, so lengths of fields are computed only once.
+    pub fn gen_toder_cached(
+        &self,
+        asn1_type: Asn1Type,
+        s: &synstructure::Structure,
+    ) -> TokenStream {
+        if asn1_type != Asn1Type::Der {
+            return quote! {};
+        }
+        let content_len_body = self.gen_content_len_body(asn1_type, s, true);
+        let write_content_body = self.gen_write_content_body(asn1_type, s, WriteMode::Cached);
+        let write_content_reverse_body =
+            self.gen_write_content_body(asn1_type, s, WriteMode::Reverse);
+        let der_encode = needs_length_cache(self.fields.iter().map(|f| &f.type_)).then(|| {
             quote! {
-                #pat => {
-                    let mut num_bytes = 0;
-                    #(#encode)*
-                    Ok(num_bytes)
+                fn der_encode<W: asn1_rs::Writer>(&self, writer: &mut W) -> asn1_rs::SerializeResult<usize> {
+                    self.der_encode_with_cache(writer)
                 }
             }
         });
-
         quote! {
-            fn #write_content<W: std::io::Write>(&self, writer: &mut W) -> asn1_rs::SerializeResult<usize> {
+            fn der_content_len_cached(&self, cache: &mut asn1_rs::LengthCache) -> asn1_rs::Length {
                 match *self {
-                    #(#body)*
+                    #content_len_body
+                }
+            }
+
+            fn der_write_content_cached<W: asn1_rs::Writer>(&self, cache: &mut asn1_rs::LengthCache, writer: &mut W) -> asn1_rs::SerializeResult<usize> {
+                match *self {
+                    #(#write_content_body)*
+                }
+            }
+
+            #der_encode
+
+            fn der_write_content_reverse(&self, writer: &mut asn1_rs::ReverseWriter) -> asn1_rs::SerializeResult<usize> {
+                match *self {
+                    #(#write_content_reverse_body)*
                 }
             }
         }
@@ -673,26 +929,560 @@ impl Container {
         let impl_tober_content_len = self.gen_tober_content_len(asn1_type, &s);
         let impl_tober_tag_info = self.gen_tober_tag_info(asn1_type);
         let impl_tober_write_content = self.gen_tober_write_content(asn1_type, &s);
+        let impl_toder_cached = self.gen_toder_cached(asn1_type, s);
         let tober = asn1_type.tober();
 
         // note: `gen impl` in synstructure takes care of appending extra where clauses if any, and removing
         // the `where` statement if there are none.
         let ts = s.gen_impl(quote! {
             extern crate asn1_rs;
-
-            #[cfg(feature = "std")]
             gen impl asn1_rs::#tober for @Self where #(#wh)+* {
                 type Encoder = asn1_rs::BerGenericEncoder;
 
                 #impl_tober_content_len
                 #impl_tober_tag_info
                 #impl_tober_write_content
+                #impl_toder_cached
             }
         });
         ts
     }
 }
 
+impl Container {
+    pub fn gen_toper(&self, options: &Options) -> TokenStream {
+        if !options.per_encoder {
+            return quote! {};
+        }
+        if self.container_type != ContainerType::Sequence {
+            return syn::Error::new(
+                Span::call_site(),
+                "PER is only supported for SEQUENCE types",
+            )
+            .to_compile_error();
+        }
+
+        // we must filter out the 'ber lifetime (added for parsers, but not used here)
+        let wh = self
+            .where_predicates
+            .iter()
+            .filter(|predicate| match predicate {
+                WherePredicate::Lifetime(lft) => lft.lifetime.ident != "ber",
+                _ => true,
+            });
+
+        // extension additions are never encoded
+        let extension_bit = options
+            .extensible
+            .then(|| quote! { writer.write_bit(false); });
+        // preamble: one bit for each OPTIONAL or DEFAULT field (X.691: 19.2)
+        let presence_bits = self.fields.iter().filter(|f| f.optional).map(|f| {
+            let name = &f.name;
+            if f.default.is_some() {
+                // DEFAULT values are always encoded
+                quote! { writer.write_bit(true); }
+            } else {
+                quote! { writer.write_bit(self.#name.is_some()); }
+            }
+        });
+        let field_encoders = self.fields.iter().map(|f| {
+            let name = &f.name;
+            let constraints = f.constraints.to_constraints_tokens();
+            quote! { asn1_rs::per::ToPer::per_encode(&self.#name, writer, &#constraints)?; }
+        });
+
+        quote! {
+            gen impl asn1_rs::per::ToPer for @Self where #(#wh)+* {
+                fn per_encode(&self, writer: &mut asn1_rs::per::PerWriter, _constraints: &asn1_rs::Constraints) -> asn1_rs::PerResult<()> {
+                    #extension_bit
+                    #(#presence_bits)*
+                    #(#field_encoders)*
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    pub fn gen_perparser(&self, options: &Options) -> TokenStream {
+        if !options.per_parser {
+            return quote! {};
+        }
+        if self.container_type != ContainerType::Sequence {
+            return syn::Error::new(
+                Span::call_site(),
+                "PER is only supported for SEQUENCE types",
+            )
+            .to_compile_error();
+        }
+
+        let lft = Lifetime::new("'ber", Span::call_site());
+        let wh = &self.where_predicates;
+
+        let read_extension_bit = options
+            .extensible
+            .then(|| quote! { let __per_extended = reader.read_bit()?; });
+        let skip_extensions = options.extensible.then(|| {
+            quote! {
+                if __per_extended {
+                    reader.skip_sequence_extensions()?;
+                }
+            }
+        });
+        let presence_ident = |f: &FieldInfo| format_ident!("__per_present_{}", f.name.unraw());
+        let read_presence_bits = self.fields.iter().filter(|f| f.optional).map(|f| {
+            let present = presence_ident(f);
+            quote! { let #present = reader.read_bit()?; }
+        });
+        let field_decoders = self.fields.iter().map(|f| {
+            let name = &f.name;
+            let ty = &f.type_;
+            let constraints = f.constraints.to_constraints_tokens();
+            if f.optional {
+                let present = presence_ident(f);
+                let absent = f.default.clone().unwrap_or_else(|| quote! { None });
+                quote! {
+                    let #name: #ty = if #present {
+                        asn1_rs::per::PerParser::per_decode(reader, &#constraints)?
+                    } else {
+                        #absent
+                    };
+                }
+            } else {
+                quote! {
+                    let #name: #ty = asn1_rs::per::PerParser::per_decode(reader, &#constraints)?;
+                }
+            }
+        });
+        let field_names = self.fields.iter().map(|f| &f.name);
+
+        quote! {
+            gen impl<#lft> asn1_rs::per::PerParser<#lft> for @Self where #(#wh)+* {
+                fn per_decode(reader: &mut asn1_rs::per::PerReader<#lft>, _constraints: &asn1_rs::Constraints) -> asn1_rs::PerResult<Self> {
+                    #read_extension_bit
+                    #(#read_presence_bits)*
+                    #(#field_decoders)*
+                    #skip_extensions
+                    Ok(Self {
+                        #(#field_names),*
+                    })
+                }
+            }
+        }
+    }
+
+    pub fn gen_tooer(&self, options: &Options) -> TokenStream {
+        if !options.oer_encoder {
+            return quote! {};
+        }
+        if self.container_type != ContainerType::Sequence {
+            return syn::Error::new(
+                Span::call_site(),
+                "OER is only supported for SEQUENCE types",
+            )
+            .to_compile_error();
+        }
+
+        // we must filter out the 'ber lifetime (added for parsers, but not used here)
+        let wh = self.where_predicates_without_ber();
+
+        // preamble: extension bit, then one bit for each OPTIONAL or DEFAULT field (X.696: 16.2)
+        // extension additions are never encoded
+        let extension_bit = options.extensible.then(|| quote! { false, });
+        let presence_bits = self.fields.iter().filter(|f| f.optional).map(|f| {
+            let name = &f.name;
+            if f.default.is_some() {
+                // canonical encodings omit DEFAULT values
+                let is_default = f.gen_is_default(quote! { &self.#name });
+                quote! { !#is_default }
+            } else {
+                quote! { self.#name.is_some() }
+            }
+        });
+        let field_encoders = self.fields.iter().map(|f| {
+            let name = &f.name;
+            let constraints = f.constraints.to_constraints_tokens();
+            if f.default.is_some() {
+                let is_default = f.gen_is_default(quote! { &self.#name });
+                quote! {
+                    if !#is_default {
+                        asn1_rs::oer::ToOer::oer_encode(&self.#name, writer, &#constraints)?;
+                    }
+                }
+            } else {
+                quote! { asn1_rs::oer::ToOer::oer_encode(&self.#name, writer, &#constraints)?; }
+            }
+        });
+
+        quote! {
+            gen impl asn1_rs::oer::ToOer for @Self where #(#wh)+* {
+                fn oer_encode(&self, writer: &mut asn1_rs::oer::OerWriter, _constraints: &asn1_rs::Constraints) -> asn1_rs::OerResult<()> {
+                    writer.write_preamble(&[#extension_bit #(#presence_bits),*]);
+                    #(#field_encoders)*
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    pub fn gen_oerparser(&self, options: &Options) -> TokenStream {
+        if !options.oer_parser {
+            return quote! {};
+        }
+        if self.container_type != ContainerType::Sequence {
+            return syn::Error::new(
+                Span::call_site(),
+                "OER is only supported for SEQUENCE types",
+            )
+            .to_compile_error();
+        }
+
+        let lft = Lifetime::new("'ber", Span::call_site());
+        let wh = &self.where_predicates;
+
+        let extension_bit = usize::from(options.extensible);
+        let noptional = self.fields.iter().filter(|f| f.optional).count();
+        let nbits = extension_bit + noptional;
+        let read_preamble = (nbits > 0).then(|| {
+            quote! { let __oer_preamble = reader.read_preamble(#nbits)?; }
+        });
+        let skip_extensions = options.extensible.then(|| {
+            quote! {
+                if __oer_preamble[0] {
+                    reader.skip_sequence_extensions()?;
+                }
+            }
+        });
+        let field_decoders = self.fields.iter().scan(extension_bit, |bit_index, f| {
+            let name = &f.name;
+            let ty = &f.type_;
+            let constraints = f.constraints.to_constraints_tokens();
+            let decoder = if f.optional {
+                let idx = *bit_index;
+                *bit_index += 1;
+                let (absent, check_default) = match &f.default {
+                    Some(default) => {
+                        // canonical encodings omit DEFAULT values
+                        let is_default = f.gen_is_default(quote! { &#name });
+                        (
+                            default.clone(),
+                            quote! { reader.check_canonical(!#is_default)?; },
+                        )
+                    }
+                    None => (quote! { None }, quote! {}),
+                };
+                quote! {
+                    let #name: #ty = if __oer_preamble[#idx] {
+                        let #name: #ty = asn1_rs::oer::OerParser::oer_decode(reader, &#constraints)?;
+                        #check_default
+                        #name
+                    } else {
+                        #absent
+                    };
+                }
+            } else {
+                quote! {
+                    let #name: #ty = asn1_rs::oer::OerParser::oer_decode(reader, &#constraints)?;
+                }
+            };
+            Some(decoder)
+        });
+        let field_names = self.fields.iter().map(|f| &f.name);
+
+        quote! {
+            gen impl<#lft> asn1_rs::oer::OerParser<#lft> for @Self where #(#wh)+* {
+                fn oer_decode(reader: &mut asn1_rs::oer::OerReader<#lft>, _constraints: &asn1_rs::Constraints) -> asn1_rs::OerResult<Self> {
+                    #read_preamble
+                    #(#field_decoders)*
+                    #skip_extensions
+                    Ok(Self {
+                        #(#field_names),*
+                    })
+                }
+            }
+        }
+    }
+
+    /// Returns the where predicates, without the `'ber` lifetime (added for BER parsers)
+    fn where_predicates_without_ber(&self) -> impl Iterator<Item = &WherePredicate> {
+        self.where_predicates
+            .iter()
+            .filter(|predicate| match predicate {
+                WherePredicate::Lifetime(lft) => lft.lifetime.ident != "ber",
+                _ => true,
+            })
+    }
+
+    pub fn gen_tojer(&self, options: &Options) -> TokenStream {
+        if !options.jer_encoder {
+            return quote! {};
+        }
+        if !matches!(
+            self.container_type,
+            ContainerType::Sequence | ContainerType::Set
+        ) {
+            return syn::Error::new(
+                Span::call_site(),
+                "JER is only supported for SEQUENCE and SET types",
+            )
+            .to_compile_error();
+        }
+
+        let wh = self.where_predicates_without_ber();
+
+        // members are named after fields (X.697: 27.3). Absent OPTIONAL fields are omitted.
+        let field_encoders = self.fields.iter().map(|f| {
+            let name = &f.name;
+            let member = f.name.unraw().to_string();
+            let insert = quote! {
+                __object.insert(#member, asn1_rs::jer::ToJer::to_jer(&self.#name)?);
+            };
+            if f.optional && f.default.is_none() {
+                quote! {
+                    if self.#name.is_some() {
+                        #insert
+                    }
+                }
+            } else {
+                insert
+            }
+        });
+
+        quote! {
+            gen impl asn1_rs::jer::ToJer for @Self where #(#wh)+* {
+                fn to_jer(&self) -> asn1_rs::JerResult<asn1_rs::jer::JsonValue> {
+                    let mut __object = asn1_rs::jer::JsonObject::new();
+                    #(#field_encoders)*
+                    Ok(asn1_rs::jer::JsonValue::Object(__object))
+                }
+            }
+        }
+    }
+
+    pub fn gen_jerparser(&self, options: &Options) -> TokenStream {
+        if !options.jer_parser {
+            return quote! {};
+        }
+        if !matches!(
+            self.container_type,
+            ContainerType::Sequence | ContainerType::Set
+        ) {
+            return syn::Error::new(
+                Span::call_site(),
+                "JER is only supported for SEQUENCE and SET types",
+            )
+            .to_compile_error();
+        }
+
+        let wh = self.where_predicates_without_ber();
+
+        // unknown members are ignored
+        let field_decoders = self.fields.iter().map(|f| {
+            let name = &f.name;
+            let ty = &f.type_;
+            let member = f.name.unraw().to_string();
+            if f.optional {
+                let absent = match &f.default {
+                    Some(default) => default.clone(),
+                    None => quote! { None },
+                };
+                quote! {
+                    let #name: #ty = match __object.get(#member) {
+                        Some(v) => asn1_rs::jer::JerParser::from_jer(v)?,
+                        None => #absent,
+                    };
+                }
+            } else {
+                quote! {
+                    let #name: #ty = asn1_rs::jer::JerParser::from_jer(__object.member(#member)?)?;
+                }
+            }
+        });
+        let field_names = self.fields.iter().map(|f| &f.name);
+
+        quote! {
+            gen impl asn1_rs::jer::JerParser for @Self where #(#wh)+* {
+                fn from_jer(value: &asn1_rs::jer::JsonValue) -> asn1_rs::JerResult<Self> {
+                    let __object = value.as_object().ok_or(asn1_rs::JerError::UnexpectedType)?;
+                    #(#field_decoders)*
+                    Ok(Self {
+                        #(#field_names),*
+                    })
+                }
+            }
+        }
+    }
+
+    pub fn gen_xmltypename(&self, options: &Options, ident: &Ident) -> TokenStream {
+        if !(options.xer_parser || options.xer_encoder) {
+            return quote! {};
+        }
+        if !matches!(
+            self.container_type,
+            ContainerType::Sequence | ContainerType::Set
+        ) {
+            return syn::Error::new(
+                Span::call_site(),
+                "XER is only supported for SEQUENCE and SET types",
+            )
+            .to_compile_error();
+        }
+
+        let wh = self.where_predicates_without_ber();
+        let name = ident.unraw().to_string();
+
+        quote! {
+            gen impl asn1_rs::xer::XmlTypeName for @Self where #(#wh)+* {
+                const XML_TYPE_NAME: &'static str = #name;
+            }
+        }
+    }
+
+    pub fn gen_toxer(&self, options: &Options) -> TokenStream {
+        if !options.xer_encoder
+            || !matches!(
+                self.container_type,
+                ContainerType::Sequence | ContainerType::Set
+            )
+        {
+            return quote! {};
+        }
+
+        let wh = self.where_predicates_without_ber();
+
+        // components are encoded in elements named after fields. Absent OPTIONAL fields and
+        // DEFAULT values are omitted (this is required by CANONICAL-XER).
+        let field_encoders = self.fields.iter().map(|f| {
+            let name = &f.name;
+            let element = f.name.unraw().to_string();
+            match &f.default {
+                Some(_) => {
+                    let is_default = f.gen_is_default(quote! { &self.#name });
+                    quote! {
+                        if !#is_default {
+                            asn1_rs::xer::ToXer::xer_encode_element(&self.#name, writer, #element)?;
+                        }
+                    }
+                }
+                None if f.optional => quote! {
+                    if let Some(v) = &self.#name {
+                        asn1_rs::xer::ToXer::xer_encode_element(v, writer, #element)?;
+                    }
+                },
+                None => quote! {
+                    asn1_rs::xer::ToXer::xer_encode_element(&self.#name, writer, #element)?;
+                },
+            }
+        });
+
+        quote! {
+            gen impl asn1_rs::xer::ToXer for @Self where #(#wh)+* {
+                fn xer_encode(&self, writer: &mut asn1_rs::xer::XerWriter) -> asn1_rs::XerResult<()> {
+                    #(#field_encoders)*
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    pub fn gen_xerparser(&self, options: &Options) -> TokenStream {
+        if !options.xer_parser
+            || !matches!(
+                self.container_type,
+                ContainerType::Sequence | ContainerType::Set
+            )
+        {
+            return quote! {};
+        }
+
+        let wh = self.where_predicates_without_ber();
+
+        // unknown elements are ignored
+        let field_decoders = self.fields.iter().map(|f| {
+            let name = &f.name;
+            let ty = &f.type_;
+            let element = f.name.unraw().to_string();
+            match &f.default {
+                Some(default) => quote! {
+                    let #name: #ty = match asn1_rs::xer::find_element(content, #element) {
+                        Some(e) => asn1_rs::xer::XerParser::from_xer(&e.content)?,
+                        None => #default,
+                    };
+                },
+                None if f.optional => quote! {
+                    let #name: #ty = match asn1_rs::xer::find_element(content, #element) {
+                        Some(e) => Some(asn1_rs::xer::XerParser::from_xer(&e.content)?),
+                        None => None,
+                    };
+                },
+                None => quote! {
+                    let #name: #ty = asn1_rs::xer::XerParser::from_xer(
+                        &asn1_rs::xer::get_element(content, #element)?.content
+                    )?;
+                },
+            }
+        });
+        let field_names = self.fields.iter().map(|f| &f.name);
+
+        quote! {
+            gen impl asn1_rs::xer::XerParser for @Self where #(#wh)+* {
+                fn from_xer(content: &[asn1_rs::xer::XmlNode]) -> asn1_rs::XerResult<Self> {
+                    // only elements are allowed
+                    asn1_rs::xer::child_elements(content)?;
+                    #(#field_decoders)*
+                    Ok(Self {
+                        #(#field_names),*
+                    })
+                }
+            }
+        }
+    }
+
+    pub fn gen_tovaluenotation(&self, options: &Options) -> TokenStream {
+        if !options.value_notation {
+            return quote! {};
+        }
+        if !matches!(
+            self.container_type,
+            ContainerType::Sequence | ContainerType::Set
+        ) {
+            return syn::Error::new(
+                Span::call_site(),
+                "Value notation is only supported for SEQUENCE and SET types",
+            )
+            .to_compile_error();
+        }
+
+        let wh = self.where_predicates_without_ber();
+
+        // components are identified by field names. Absent OPTIONAL fields are omitted.
+        let field_writers = self.fields.iter().map(|f| {
+            let name = &f.name;
+            let identifier = f.name.unraw().to_string();
+            if f.optional && f.default.is_none() {
+                quote! {
+                    if let Some(v) = &self.#name {
+                        writer.component(Some(#identifier), v)?;
+                    }
+                }
+            } else {
+                quote! {
+                    writer.component(Some(#identifier), &self.#name)?;
+                }
+            }
+        });
+
+        quote! {
+            gen impl asn1_rs::value_notation::ToValueNotation for @Self where #(#wh)+* {
+                fn write_value(&self, f: &mut dyn core::fmt::Write) -> core::fmt::Result {
+                    let mut writer = asn1_rs::value_notation::ComponentsWriter::new(f);
+                    #(#field_writers)*
+                    writer.finish()
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct FieldInfo {
     pub name: Ident,
@@ -705,6 +1495,21 @@ pub struct FieldInfo {
     // TODO: implement this
     #[allow(unused)]
     pub encode: Option<Expr>,
+    pub constraints: FieldConstraints,
+}
+
+impl FieldInfo {
+    /// Generate the test of `value` (a reference to the field type) against the default value
+    ///
+    /// This requires the field type to implement `PartialEq`. The generated code is spanned on the
+    /// field type, so a missing implementation is reported on the field.
+    fn gen_is_default(&self, value: TokenStream) -> TokenStream {
+        let ty = &self.type_;
+        let default = &self.default;
+        quote_spanned! { ty.span()=>
+            asn1_rs::is_default_value::<#ty>(#value, &#default)
+        }
+    }
 }
 
 impl TryFrom<&Field> for FieldInfo {
@@ -722,6 +1527,7 @@ impl TryFrom<&Field> for FieldInfo {
             .map_or_else(|| Ident::new("_", Span::call_site()), |s| s.clone());
         let mut parse = None;
         let mut encode = None;
+        let mut constraints = FieldConstraints::default();
         for attr in &field.attrs {
             let ident = match attr.meta.path().get_ident() {
                 Some(ident) => ident.to_string(),
@@ -766,6 +1572,8 @@ impl TryFrom<&Field> for FieldInfo {
                             let e: Expr = lit.parse()?;
                             encode = Some(e);
                             return Err(meta.error("Attribute 'encode' is not yet supported"));
+                        } else if constraints.parse_meta(&meta)? {
+                            // PER-visible constraint
                         } else {
                             return Err(meta.error("Invalid or unknown attribute"));
                         }
@@ -786,6 +1594,7 @@ impl TryFrom<&Field> for FieldInfo {
             map_err,
             parse,
             encode,
+            constraints,
         };
         Ok(f)
     }
@@ -840,14 +1649,27 @@ fn get_field_parser(f: &FieldInfo, asn1_type: Asn1Type, custom_errors: bool) ->
 
     // else, derive parser
     let from = match asn1_type {
-        Asn1Type::Ber => quote! {FromBer::from_ber},
+        // there is no legacy CER parser, CER is a restricted form of BER
+        Asn1Type::Ber | Asn1Type::Cer => quote! {FromBer::from_ber},
         Asn1Type::Der => quote! {FromDer::from_der},
     };
-    let default = f
-        .default
-        .as_ref()
+    let default = f.default.as_ref().map(|x| {
+        // DER: a value equal to its DEFAULT value shall not be encoded (X.690: 11.5)
+        let check = if asn1_type == Asn1Type::Der {
+            let is_default = f.gen_is_default(quote! { v });
+            Some(quote! {
+                if #name.as_ref().map_or(false, |v| #is_default) {
+                    return Err(asn1_rs::nom::Err::Error(
+                        asn1_rs::Error::DerConstraintFailed(asn1_rs::DerConstraint::DefaultValueEncoded).into(),
+                    ));
+                }
+            })
+        } else {
+            None
+        };
         // use a type hint, otherwise compiler will not know what type provides .unwrap_or
-        .map(|x| quote! {let #name: Option<_> = #name; let #name = #name.unwrap_or(#x);});
+        quote! {let #name: Option<_> = #name; #check let #name = #name.unwrap_or(#x);}
+    });
     let map_err = if let Some(tt) = f.map_err.as_ref() {
         if asn1_type == Asn1Type::Ber {
             Some(quote! {
@@ -864,7 +1686,7 @@ fn get_field_parser(f: &FieldInfo, asn1_type: Asn1Type, custom_errors: bool) ->
             if asn1_type == Asn1Type::Ber {
                 Some(quote! { .map_err(asn1_rs::from_nom_error::<_, Self::Error>) })
             } else {
-                Some(quote! { .map_err(nom::Err::convert) })
+                Some(quote! { .map_err(asn1_rs::nom::Err::convert) })
             }
         } else {
             None
@@ -909,10 +1731,14 @@ fn get_field_parser(f: &FieldInfo, asn1_type: Asn1Type, custom_errors: bool) ->
     }
 }
 
-fn derive_berparser_sequence_content(fields: &[FieldInfo], asn1_type: Asn1Type) -> TokenStream {
+fn derive_berparser_sequence_content(
+    fields: &[FieldInfo],
+    asn1_type: Asn1Type,
+    type_name: Option<&str>,
+) -> TokenStream {
     let field_parsers: Vec<_> = fields
         .iter()
-        .map(|f| get_field_berparser(f, asn1_type))
+        .map(|f| get_field_berparser(f, asn1_type, type_name))
         .collect();
 
     quote! {
@@ -920,6 +1746,217 @@ fn derive_berparser_sequence_content(fields: &[FieldInfo], asn1_type: Asn1Type)
     }
 }
 
+// Parse the components of a `SET`, in any order
+//
+// Each component is assigned to the first missing field with a matching tag (tagged fields are
+// tested first). Other components are rejected using `unknown` (an expression returning an
+// error), or ignored if `unknown` is `None`. Missing fields are then parsed from the remaining
+// (empty) content, which returns the default value of OPTIONAL and DEFAULT fields, or an error
+// for other fields.
+//
+// `input` is the variable holding the content, and `next` an expression returning a copy of
+// it. `parse_header` reads the next component, setting `obj_header` and `skip` (the content
+// after this component), and `field_parser` generates the parser of a field (declaring a
+// variable with the field name).
+//
+// A component matching a field which is already set is a duplicate, and `duplicate` is returned
+// as error (X.680: 27.3, the tags of the components of a SET shall be distinct).
+fn gen_set_content(
+    fields: &[FieldInfo],
+    input: &Ident,
+    next: TokenStream,
+    parse_header: TokenStream,
+    unknown: Option<TokenStream>,
+    duplicate: TokenStream,
+    field_parser: impl Fn(&FieldInfo) -> TokenStream,
+) -> TokenStream {
+    let slot = |f: &FieldInfo| format_ident!("__slot_{}", f.name.unraw());
+    let slots = fields.iter().map(slot);
+    let unknown = match unknown {
+        Some(e) => quote! { return Err(#e); },
+        None => quote! { #input = skip; },
+    };
+
+    // tagged fields first: untagged fields only test the tag number
+    let (tagged, untagged): (Vec<_>, Vec<_>) = fields.iter().partition(|f| f.tag.is_some());
+    let matches = |f: &FieldInfo| {
+        if let Some((_, class, n)) = f.tag {
+            let class = class.class_tokens();
+            let tag = Literal::u16_unsuffixed(n);
+            quote! { obj_header.class() == #class && obj_header.tag().0 == #tag }
+        } else {
+            let ty = &f.type_;
+            quote! { <#ty as asn1_rs::DynTagged>::accept_tag(obj_header.tag()) }
+        }
+    };
+    let branches = tagged.iter().chain(untagged.iter()).map(|f| {
+        let name = &f.name;
+        let slot = slot(f);
+        let matches = matches(f);
+        let field_parser = field_parser(f);
+        quote! {
+            if #slot.is_none() && #matches {
+                let (next, value) = {
+                    let #input = #next;
+                    #field_parser
+                    (#input, #name)
+                };
+                #slot = Some(value);
+                #input = next;
+                continue;
+            }
+        }
+    });
+    let duplicates = fields.iter().map(|f| {
+        let slot = slot(f);
+        let matches = matches(f);
+        quote! {
+            if #slot.is_some() && #matches {
+                return Err(#duplicate);
+            }
+        }
+    });
+
+    let finals = fields.iter().map(|f| {
+        let name = &f.name;
+        let slot = slot(f);
+        let field_parser = field_parser(f);
+        quote! {
+            #[allow(unused_variables)]
+            let #name = match #slot {
+                Some(value) => value,
+                None => {
+                    let #input = #next;
+                    #field_parser
+                    #name
+                }
+            };
+        }
+    });
+
+    quote! {
+        #(let mut #slots = None;)*
+        let mut #input = #input;
+        while !#input.is_empty() {
+            #parse_header
+            #(#branches)*
+            #(#duplicates)*
+            // no field matches: component is unknown
+            #unknown
+        }
+        #(#finals)*
+    }
+}
+
+fn derive_ber_set_content(
+    fields: &[FieldInfo],
+    asn1_type: Asn1Type,
+    custom_errors: bool,
+    extensible: bool,
+) -> TokenStream {
+    let from = match asn1_type {
+        Asn1Type::Ber | Asn1Type::Cer => quote! {FromBer::from_ber},
+        Asn1Type::Der => quote! {FromDer::from_der},
+    };
+    let map_err = match (custom_errors, asn1_type) {
+        (false, _) => None,
+        (true, Asn1Type::Ber) => {
+            Some(quote! { .map_err(asn1_rs::from_nom_error::<_, Self::Error>) })
+        }
+        (true, _) => Some(quote! { .map_err(asn1_rs::nom::Err::convert) }),
+    };
+    // Note: in DER, the order of components is checked before parsing (see `gen_fromder`)
+    let parse_header = quote! {
+        let (skip, obj): (_, asn1_rs::Any) = #from(i)#map_err?;
+        let obj_header = &obj.header;
+    };
+    // DER: unknown components are rejected, unless the type is extensible
+    let unknown = (asn1_type == Asn1Type::Der && !extensible).then(|| {
+        quote! {
+            asn1_rs::nom::Err::Error(
+                asn1_rs::Error::UnexpectedTag { expected: None, actual: obj_header.tag() }.into(),
+            )
+        }
+    });
+    let duplicate = quote! {
+        asn1_rs::Error::invalid_value(obj_header.tag(), "Duplicate SET component".into()).into()
+    };
+    // note: `TryFrom<Any>` (BER) returns the error directly
+    let duplicate = match asn1_type {
+        Asn1Type::Ber => duplicate,
+        _ => quote! { asn1_rs::nom::Err::Error(#duplicate) },
+    };
+    gen_set_content(
+        fields,
+        &Ident::new("i", Span::call_site()),
+        quote! { i },
+        parse_header,
+        unknown,
+        duplicate,
+        |f| get_field_parser(f, asn1_type, custom_errors),
+    )
+}
+
+fn derive_berparser_set_content(
+    fields: &[FieldInfo],
+    asn1_type: Asn1Type,
+    type_name: Option<&str>,
+    extensible: bool,
+) -> TokenStream {
+    let from = match asn1_type {
+        Asn1Type::Ber => quote! {BerParser::parse_ber},
+        Asn1Type::Cer => quote! {CerParser::parse_cer},
+        Asn1Type::Der => quote! {DerParser::parse_der},
+    };
+    // DER/CER: components shall be sorted by tag (X.690: 9.3 and 10.3)
+    let (decl_previous, check_order) = match asn1_type {
+        Asn1Type::Ber => (None, None),
+        Asn1Type::Cer | Asn1Type::Der => (
+            Some(quote! { let mut previous_tag = None; }),
+            Some(quote! {
+                asn1_rs::check_set_component_order(&mut previous_tag, obj_header, &rem)
+                    .map_err(|e| asn1_rs::nom::Err::Error(e.into()))?;
+            }),
+        ),
+    };
+    let parse_header = quote! {
+        let (skip, obj): (_, asn1_rs::Any) = #from(rem.clone()).map_err(asn1_rs::nom::Err::convert)?;
+        let obj_header = &obj.header;
+        #check_order
+    };
+    // DER/CER: unknown components are rejected, unless the type is extensible
+    let unknown = (asn1_type != Asn1Type::Ber && !extensible).then(|| {
+        quote! {
+            asn1_rs::nom::Err::Error(
+                asn1_rs::BerError::unexpected_tag(rem.clone(), None, obj_header.tag()).into(),
+            )
+        }
+    });
+    let duplicate = quote! {
+        asn1_rs::nom::Err::Error(
+            asn1_rs::BerError::invalid_value(
+                rem.clone(),
+                obj_header.tag(),
+                "Duplicate SET component".into(),
+            )
+            .into(),
+        )
+    };
+    let content = gen_set_content(
+        fields,
+        &Ident::new("rem", Span::call_site()),
+        quote! { rem.clone() },
+        parse_header,
+        unknown,
+        duplicate,
+        |f| get_field_berparser(f, asn1_type, type_name),
+    );
+    quote! {
+        #decl_previous
+        #content
+    }
+}
+
 fn get_field<'a>(fields: &'a [FieldInfo], ident: Option<&Ident>) -> Option<&'a FieldInfo> {
     let ident = if let Some(ident) = ident {
         ident
@@ -930,15 +1967,100 @@ fn get_field<'a>(fields: &'a [FieldInfo], ident: Option<&Ident>) -> Option<&'a F
     fields.iter().find(|&f| f.name == *ident)
 }
 
+/// Names of types encoded as a single primitive (or raw) object
+const PRIMITIVE_TYPES: &[&str] = &[
+    "bool",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "f32",
+    "f64",
+    "str",
+    "String",
+    "Any",
+    "BitString",
+    "BmpString",
+    "Boolean",
+    "Enumerated",
+    "GeneralString",
+    "GeneralizedTime",
+    "GraphicString",
+    "Ia5String",
+    "Integer",
+    "Null",
+    "NumericString",
+    "ObjectDescriptor",
+    "OctetString",
+    "Oid",
+    "PrintableString",
+    "Real",
+    "TeletexString",
+    "UniversalString",
+    "UtcTime",
+    "Utf8String",
+    "VideotexString",
+    "VisibleString",
+];
+
+/// Test if values of type `ty` are encoded as primitive objects (or are absent, for `Option`)
+///
+/// This is a syntactic check on the last segment of the type path: unknown types are assumed to
+/// contain nested constructed objects.
+fn is_primitive_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(p) => match p.path.segments.last() {
+            Some(seg) if seg.ident == "Option" => match &seg.arguments {
+                syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+                    Some(syn::GenericArgument::Type(ty)) => is_primitive_type(ty),
+                    _ => false,
+                },
+                _ => false,
+            },
+            Some(seg) => PRIMITIVE_TYPES.iter().any(|name| seg.ident == name),
+            None => false,
+        },
+        // `&[u8]` is encoded as an OCTET STRING
+        Type::Reference(r) => match &*r.elem {
+            Type::Slice(slice) => is_primitive_type(&slice.elem),
+            ty => is_primitive_type(ty),
+        },
+        Type::Group(g) => is_primitive_type(&g.elem),
+        Type::Paren(p) => is_primitive_type(&p.elem),
+        _ => false,
+    }
+}
+
+/// Test if encoding DER values with fields of types `types` benefits from two-pass encoding
+///
+/// Lengths are computed several times only for nested constructed objects, so `der_encode`
+/// uses a `LengthCache` (see `ToDer::der_encode_with_cache`) only if a field is not primitive.
+pub(crate) fn needs_length_cache<'a>(mut types: impl Iterator<Item = &'a Type>) -> bool {
+    types.any(|ty| !is_primitive_type(ty))
+}
+
 // This is an adapted version of `get_field_parser` to use types related to `BerParser`
-fn get_field_berparser(f: &FieldInfo, asn1_type: Asn1Type) -> TokenStream {
+//
+// If `type_name` is set, errors are annotated with the type and field names (see `ErrorPath`)
+fn get_field_berparser(f: &FieldInfo, asn1_type: Asn1Type, type_name: Option<&str>) -> TokenStream {
     let name = &f.name;
 
+    let context = type_name.map(|type_name| {
+        let field = name.unraw().to_string();
+        quote! { .map_err(asn1_rs::err_in_field::<Self::Error>(#type_name, #field)) }
+    });
+
     // if a 'parse' attribute was specified, use it
     if let Some(e) = &f.parse {
         return quote! {
             let parse = #e;
-            let (rem, #name) = parse(rem)?;
+            let (rem, #name) = parse(rem)#context?;
         };
     }
 
@@ -946,20 +2068,44 @@ fn get_field_berparser(f: &FieldInfo, asn1_type: Asn1Type) -> TokenStream {
     let parser = asn1_type.parser();
     let from = match asn1_type {
         Asn1Type::Ber => quote! {BerParser::parse_ber},
+        Asn1Type::Cer => quote! {CerParser::parse_cer},
         Asn1Type::Der => quote! {DerParser::parse_der},
     };
 
-    let default = f
-        .default
-        .as_ref()
+    // DER and CER: a value equal to its DEFAULT value shall not be encoded (X.690: 11.5)
+    let check_default = asn1_type != Asn1Type::Ber && f.default.is_some();
+    let field_input = if check_default {
+        Some(quote! { let field_input = rem.clone(); })
+    } else {
+        None
+    };
+    let default = f.default.as_ref().map(|x| {
+        let check = if check_default {
+            let is_default = f.gen_is_default(quote! { v });
+            Some(quote! {
+                if #name.as_ref().map_or(false, |v| #is_default) {
+                    let e: asn1_rs::nom::Err<Self::Error> = asn1_rs::nom::Err::Error(
+                        asn1_rs::BerError::new(
+                            field_input,
+                            asn1_rs::InnerError::DerConstraintFailed(asn1_rs::DerConstraint::DefaultValueEncoded),
+                        )
+                        .into(),
+                    );
+                    return Err(e)#context;
+                }
+            })
+        } else {
+            None
+        };
         // use a type hint, otherwise compiler will not know what type provides .unwrap_or
-        .map(|x| quote! {let #name: Option<_> = #name; let #name = #name.unwrap_or(#x);});
+        quote! {let #name: Option<_> = #name; #check let #name = #name.unwrap_or(#x);}
+    });
 
     // no need to check for custom errors, this should be transparent using `.into()`
     let map_err = if let Some(tt) = f.map_err.as_ref() {
-        quote! { .map_err(|err| err.map(#tt)) }
+        quote! { .map_err(|err| err.map(#tt))#context }
     } else {
-        quote! { .map_err(nom::Err::convert) }
+        quote! { .map_err(asn1_rs::nom::Err::convert)#context }
     };
 
     if let Some((tag_kind, class, n)) = f.tag {
@@ -970,6 +2116,7 @@ fn get_field_berparser(f: &FieldInfo, asn1_type: Asn1Type) -> TokenStream {
             // Tagged + optional
             let f_ty = &f.type_;
             quote! {
+                #field_input
                 let (rem, #name) = {
                     if rem.is_empty() {
                         (rem, None)
@@ -997,6 +2144,7 @@ fn get_field_berparser(f: &FieldInfo, asn1_type: Asn1Type) -> TokenStream {
             // tagged, but not Optional
             let f_ty = &f.type_;
             quote! {
+                #field_input
                 let (rem, #name) = {
                     let (rem, t): (_, asn1_rs::TaggedValue::<
                         _,
@@ -1013,6 +2161,7 @@ fn get_field_berparser(f: &FieldInfo, asn1_type: Asn1Type) -> TokenStream {
     } else {
         // not tagged
         quote! {
+            #field_input
             let (rem, #name) = #from(rem)#map_err?;
             #default
         }
@@ -3,6 +3,7 @@ use crate::container::*;
 use crate::options::Options;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
+use syn::ext::IdentExt;
 use syn::{Attribute, Data, Error, Expr, ExprLit, Ident, Lifetime, Lit, Result};
 
 pub fn derive_enumerated(s: synstructure::Structure) -> TokenStream {
@@ -67,16 +68,40 @@ impl<'s> DeriveEnumerated<'s> {
     fn to_tokens(&self) -> TokenStream {
         let dyntagged = self.derive_enumerated_dyntagged();
         let berparser = self.derive_enumerated_parser(Asn1Type::Ber);
+        let cerparser = self.derive_enumerated_parser(Asn1Type::Cer);
         let derparser = self.derive_enumerated_parser(Asn1Type::Der);
         let berencode = self.derive_enumerated_encode(Asn1Type::Ber);
+        let cerencode = self.derive_enumerated_encode(Asn1Type::Cer);
         let derencode = self.derive_enumerated_encode(Asn1Type::Der);
+        let perparser = self.derive_enumerated_per_parser();
+        let perencode = self.derive_enumerated_per_encode();
+        let oerparser = self.derive_enumerated_oer_parser();
+        let oerencode = self.derive_enumerated_oer_encode();
+        let jerparser = self.derive_enumerated_jer_parser();
+        let jerencode = self.derive_enumerated_jer_encode();
+        let xmltypename = self.derive_enumerated_xmltypename();
+        let xerparser = self.derive_enumerated_xer_parser();
+        let xerencode = self.derive_enumerated_xer_encode();
+        let valuenotation = self.derive_enumerated_value_notation();
 
         let ts = quote! {
             #dyntagged
             #berparser
+            #cerparser
             #derparser
             #berencode
+            #cerencode
             #derencode
+            #perparser
+            #perencode
+            #oerparser
+            #oerencode
+            #jerparser
+            #jerencode
+            #xmltypename
+            #xerparser
+            #xerencode
+            #valuenotation
         };
 
         if self.options.debug {
@@ -175,8 +200,6 @@ impl<'s> DeriveEnumerated<'s> {
 
         self.synstruct.gen_impl(quote! {
             extern crate asn1_rs;
-
-            #[cfg(feature = "std")]
             gen impl asn1_rs::#tober for @Self {
                 type Encoder = asn1_rs::BerGenericEncoder;
 
@@ -215,13 +238,288 @@ impl<'s> DeriveEnumerated<'s> {
         let write_content = asn1_type.compose("_write_content");
 
         let impl_tober_write_content = quote! {
-            fn #write_content<W: std::io::Write>(&self, writer: &mut W) -> asn1_rs::SerializeResult<usize> {
+            fn #write_content<W: asn1_rs::Writer>(&self, writer: &mut W) -> asn1_rs::SerializeResult<usize> {
                 let e = asn1_rs::Enumerated::new(*self as u32);
                 e.#write_content(writer)
             }
         };
         impl_tober_write_content
     }
+
+    /// Returns the variants in the order used for PER indexes (sorted by value)
+    fn per_sorted_variants(&self) -> Vec<&EnumVariant> {
+        let mut variants: Vec<_> = self.variants.iter().collect();
+        variants.sort_by_key(|v| v.discriminant);
+        variants
+    }
+
+    fn derive_enumerated_per_parser(&self) -> TokenStream {
+        if !self.options.per_parser {
+            return quote! {};
+        }
+
+        let lft = Lifetime::new("'ber", Span::call_site());
+        let variants = self.per_sorted_variants();
+        let root_count = variants.len();
+        let extensible = self.options.extensible;
+
+        let match_branches = variants.iter().enumerate().map(|(idx, v)| {
+            let ident = &v.ident;
+            quote! { #idx => Ok(Self::#ident), }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl<#lft> asn1_rs::per::PerParser<#lft> for @Self {
+                fn per_decode(reader: &mut asn1_rs::per::PerReader<#lft>, _constraints: &asn1_rs::Constraints) -> asn1_rs::PerResult<Self> {
+                    match reader.read_enumerated(#root_count, #extensible)? {
+                        #(#match_branches)*
+                        n => Err(asn1_rs::PerError::UnknownExtension((n - #root_count) as u64)),
+                    }
+                }
+            }
+        })
+    }
+
+    fn derive_enumerated_per_encode(&self) -> TokenStream {
+        if !self.options.per_encoder {
+            return quote! {};
+        }
+
+        let variants = self.per_sorted_variants();
+        let root_count = variants.len();
+        let extensible = self.options.extensible;
+
+        let match_branches = variants.iter().enumerate().map(|(idx, v)| {
+            let ident = &v.ident;
+            quote! { Self::#ident => #idx, }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::per::ToPer for @Self {
+                fn per_encode(&self, writer: &mut asn1_rs::per::PerWriter, _constraints: &asn1_rs::Constraints) -> asn1_rs::PerResult<()> {
+                    let index = match self {
+                        #(#match_branches)*
+                    };
+                    writer.write_enumerated(index, #root_count, #extensible)
+                }
+            }
+        })
+    }
+
+    fn derive_enumerated_oer_parser(&self) -> TokenStream {
+        if !self.options.oer_parser {
+            return quote! {};
+        }
+
+        let lft = Lifetime::new("'ber", Span::call_site());
+
+        // values are encoded directly (X.696: 11)
+        let match_branches = self.variants.iter().map(|v| {
+            let ident = &v.ident;
+            let value = i128::from(v.discriminant);
+            quote! { #value => Ok(Self::#ident), }
+        });
+        let unknown_value = if self.options.extensible {
+            quote! {
+                n => Err(<u64 as core::convert::TryFrom<i128>>::try_from(n)
+                    .map_or(asn1_rs::OerError::InvalidEncoding, asn1_rs::OerError::UnknownExtension)),
+            }
+        } else {
+            quote! { _ => Err(asn1_rs::OerError::InvalidEncoding), }
+        };
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl<#lft> asn1_rs::oer::OerParser<#lft> for @Self {
+                fn oer_decode(reader: &mut asn1_rs::oer::OerReader<#lft>, _constraints: &asn1_rs::Constraints) -> asn1_rs::OerResult<Self> {
+                    match reader.read_enumerated()? {
+                        #(#match_branches)*
+                        #unknown_value
+                    }
+                }
+            }
+        })
+    }
+
+    fn derive_enumerated_oer_encode(&self) -> TokenStream {
+        if !self.options.oer_encoder {
+            return quote! {};
+        }
+
+        let match_branches = self.variants.iter().map(|v| {
+            let ident = &v.ident;
+            let value = i128::from(v.discriminant);
+            quote! { Self::#ident => #value, }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::oer::ToOer for @Self {
+                fn oer_encode(&self, writer: &mut asn1_rs::oer::OerWriter, _constraints: &asn1_rs::Constraints) -> asn1_rs::OerResult<()> {
+                    let value = match self {
+                        #(#match_branches)*
+                    };
+                    writer.write_enumerated(value);
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    fn derive_enumerated_jer_parser(&self) -> TokenStream {
+        if !self.options.jer_parser {
+            return quote! {};
+        }
+
+        // values are encoded using the identifiers of the variants (X.697: 21)
+        let match_branches = self.variants.iter().map(|v| {
+            let ident = &v.ident;
+            let name = ident.unraw().to_string();
+            quote! { #name => Ok(Self::#ident), }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::jer::JerParser for @Self {
+                fn from_jer(value: &asn1_rs::jer::JsonValue) -> asn1_rs::JerResult<Self> {
+                    let name = value.as_str().ok_or(asn1_rs::JerError::UnexpectedType)?;
+                    match name {
+                        #(#match_branches)*
+                        _ => Err(asn1_rs::JerError::InvalidValue),
+                    }
+                }
+            }
+        })
+    }
+
+    fn derive_enumerated_jer_encode(&self) -> TokenStream {
+        if !self.options.jer_encoder {
+            return quote! {};
+        }
+
+        let match_branches = self.variants.iter().map(|v| {
+            let ident = &v.ident;
+            let name = ident.unraw().to_string();
+            quote! { Self::#ident => #name, }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::jer::ToJer for @Self {
+                fn to_jer(&self) -> asn1_rs::JerResult<asn1_rs::jer::JsonValue> {
+                    let name = match self {
+                        #(#match_branches)*
+                    };
+                    Ok(asn1_rs::jer::JsonValue::from(name))
+                }
+            }
+        })
+    }
+
+    fn derive_enumerated_xmltypename(&self) -> TokenStream {
+        if !(self.options.xer_parser || self.options.xer_encoder) {
+            return quote! {};
+        }
+
+        let name = self.ident.unraw().to_string();
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::xer::XmlTypeName for @Self {
+                const XML_TYPE_NAME: &'static str = #name;
+                const XML_VALUE_LIST: bool = true;
+            }
+        })
+    }
+
+    fn derive_enumerated_xer_parser(&self) -> TokenStream {
+        if !self.options.xer_parser {
+            return quote! {};
+        }
+
+        // values are encoded as empty elements named after the variants
+        let match_branches = self.variants.iter().map(|v| {
+            let ident = &v.ident;
+            let name = ident.unraw().to_string();
+            quote! { #name => Ok(Self::#ident), }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::xer::XerParser for @Self {
+                fn from_xer(content: &[asn1_rs::xer::XmlNode]) -> asn1_rs::XerResult<Self> {
+                    let element = asn1_rs::xer::single_element(content)?;
+                    if !element.content.is_empty() {
+                        return Err(asn1_rs::XerError::UnexpectedContent);
+                    }
+                    match element.name.as_str() {
+                        #(#match_branches)*
+                        _ => Err(asn1_rs::XerError::UnexpectedElement(element.name.clone())),
+                    }
+                }
+            }
+        })
+    }
+
+    fn derive_enumerated_xer_encode(&self) -> TokenStream {
+        if !self.options.xer_encoder {
+            return quote! {};
+        }
+
+        let match_branches = self.variants.iter().map(|v| {
+            let ident = &v.ident;
+            let name = ident.unraw().to_string();
+            quote! { Self::#ident => #name, }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::xer::ToXer for @Self {
+                fn xer_encode(&self, writer: &mut asn1_rs::xer::XerWriter) -> asn1_rs::XerResult<()> {
+                    let name = match self {
+                        #(#match_branches)*
+                    };
+                    writer.write_empty_element(name);
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    fn derive_enumerated_value_notation(&self) -> TokenStream {
+        if !self.options.value_notation {
+            return quote! {};
+        }
+
+        let match_branches = self.variants.iter().map(|v| {
+            let ident = &v.ident;
+            let name = ident.unraw().to_string();
+            quote! { Self::#ident => #name, }
+        });
+
+        self.synstruct.gen_impl(quote! {
+            extern crate asn1_rs;
+
+            gen impl asn1_rs::value_notation::ToValueNotation for @Self {
+                fn write_value(&self, f: &mut dyn core::fmt::Write) -> core::fmt::Result {
+                    f.write_str(match self {
+                        #(#match_branches)*
+                    })
+                }
+            }
+        })
+    }
 }
 
 fn parse_enum_variants(s: &synstructure::Structure<'_>) -> Result<Vec<EnumVariant>> {
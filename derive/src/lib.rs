@@ -2,6 +2,7 @@ mod alias;
 mod asn1_type;
 mod check_derive;
 mod choice;
+mod constraints;
 mod container;
 mod enumerated;
 mod options;
@@ -61,7 +62,8 @@ synstructure::decl_derive!([BerSet, attributes(
     tag_explicit,
     tag_implicit,
     error,
-    map_err
+    map_err,
+    asn1
 )] => derive_ber_set);
 synstructure::decl_derive!([DerSet, attributes(
     debug_derive,
@@ -70,7 +72,8 @@ synstructure::decl_derive!([DerSet, attributes(
     tag_explicit,
     tag_implicit,
     error,
-    map_err
+    map_err,
+    asn1
 )] => derive_der_set);
 
 synstructure::decl_derive!([ToStatic, attributes(
@@ -122,7 +125,8 @@ synstructure::decl_derive!([BerParserSet, attributes(
     tag_explicit,
     tag_implicit,
     error,
-    map_err
+    map_err,
+    asn1
 )] => derive_berparser_set);
 
 synstructure::decl_derive!([DerParserSet, attributes(
@@ -132,7 +136,8 @@ synstructure::decl_derive!([DerParserSet, attributes(
     tag_explicit,
     tag_implicit,
     error,
-    map_err
+    map_err,
+    asn1
 )] => derive_derparser_set);
 
 synstructure::decl_derive!([BerParserChoice, attributes(
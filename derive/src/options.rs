@@ -1,4 +1,5 @@
-use crate::{asn1_type::Asn1Type, container::*};
+use crate::asn1_type::{Asn1Type, EncodingRules};
+use crate::container::*;
 use syn::{Attribute, Result};
 
 #[derive(Debug, Default)]
@@ -10,6 +11,19 @@ pub struct Options {
 
     pub parsers: Vec<Asn1Type>,
     pub encoders: Vec<Asn1Type>,
+    pub per_parser: bool,
+    pub per_encoder: bool,
+    pub oer_parser: bool,
+    pub oer_encoder: bool,
+    pub jer_parser: bool,
+    pub jer_encoder: bool,
+    pub xer_parser: bool,
+    pub xer_encoder: bool,
+
+    /// Type has an extension marker (used by PER and OER)
+    pub extensible: bool,
+    /// Generate an implementation of `ToValueNotation`
+    pub value_notation: bool,
 }
 
 impl Options {
@@ -36,14 +50,35 @@ impl Options {
                 attr.parse_nested_meta(|meta| {
                     if meta.path.is_ident("parse") {
                         let value = meta.value()?;
-                        let asn1_types = Asn1Type::parse_multi(value)?;
-                        options.parsers = asn1_types.into_iter().collect();
+                        let rules = EncodingRules::parse_multi(value)?;
+                        (
+                            options.parsers,
+                            options.per_parser,
+                            options.oer_parser,
+                            options.jer_parser,
+                            options.xer_parser,
+                        ) = split_encoding_rules(rules);
                     } else if meta.path.is_ident("encode") {
                         let value = meta.value()?;
-                        let asn1_types = Asn1Type::parse_multi(value)?;
-                        options.encoders = asn1_types.into_iter().collect();
+                        let rules = EncodingRules::parse_multi(value)?;
+                        (
+                            options.encoders,
+                            options.per_encoder,
+                            options.oer_encoder,
+                            options.jer_encoder,
+                            options.xer_encoder,
+                        ) = split_encoding_rules(rules);
                     } else if meta.path.is_ident("orig_input") {
                         options.orig_input = true;
+                    } else if meta.path.is_ident("extensible") {
+                        options.extensible = true;
+                    } else if meta.path.is_ident("value_notation") {
+                        if !cfg!(feature = "value-notation") {
+                            return Err(meta.error(
+                                "value notation support requires the `value-notation` feature of asn1-rs",
+                            ));
+                        }
+                        options.value_notation = true;
                     } else {
                         return Err(meta.error("Invalid or unknown attribute"));
                     }
@@ -55,3 +90,24 @@ impl Options {
         Ok(options)
     }
 }
+
+/// Split encoding rules into the list of TLV-based rules, and flags for PER, OER, JER and XER
+fn split_encoding_rules(
+    rules: impl IntoIterator<Item = EncodingRules>,
+) -> (Vec<Asn1Type>, bool, bool, bool, bool) {
+    let mut asn1_types = Vec::new();
+    let mut per = false;
+    let mut oer = false;
+    let mut jer = false;
+    let mut xer = false;
+    for r in rules {
+        match r {
+            EncodingRules::Tlv(asn1_type) => asn1_types.push(asn1_type),
+            EncodingRules::Per => per = true,
+            EncodingRules::Oer => oer = true,
+            EncodingRules::Jer => jer = true,
+            EncodingRules::Xer => xer = true,
+        }
+    }
+    (asn1_types, per, oer, jer, xer)
+}
@@ -57,19 +57,45 @@ impl<'s> DeriveSequence<'s> {
         let impl_berparser = self
             .container
             .gen_berparser(Asn1Type::Ber, options, synstruct);
+        let impl_cerparser = self
+            .container
+            .gen_berparser(Asn1Type::Cer, options, synstruct);
         let impl_derparser = self
             .container
             .gen_berparser(Asn1Type::Der, options, synstruct);
         let impl_tober = self.container.gen_tober(Asn1Type::Ber, options, synstruct);
+        let impl_tocer = self.container.gen_tober(Asn1Type::Cer, options, synstruct);
         let impl_toder = self.container.gen_tober(Asn1Type::Der, options, synstruct);
+        let impl_perparser = self.container.gen_perparser(options);
+        let impl_toper = self.container.gen_toper(options);
+        let impl_oerparser = self.container.gen_oerparser(options);
+        let impl_tooer = self.container.gen_tooer(options);
+        let impl_jerparser = self.container.gen_jerparser(options);
+        let impl_tojer = self.container.gen_tojer(options);
+        let impl_xmltypename = self.container.gen_xmltypename(options, &self.ident);
+        let impl_xerparser = self.container.gen_xerparser(options);
+        let impl_toxer = self.container.gen_toxer(options);
+        let impl_tovaluenotation = self.container.gen_tovaluenotation(options);
         let ts = self.synstruct.gen_impl(quote! {
             extern crate asn1_rs;
 
             #impl_tagged
             #impl_berparser
+            #impl_cerparser
             #impl_derparser
             #impl_tober
+            #impl_tocer
             #impl_toder
+            #impl_perparser
+            #impl_toper
+            #impl_oerparser
+            #impl_tooer
+            #impl_jerparser
+            #impl_tojer
+            #impl_xmltypename
+            #impl_xerparser
+            #impl_toxer
+            #impl_tovaluenotation
         });
         if self.options.debug {
             eprintln!("// SEQUENCE for {}", self.ident);
@@ -159,6 +185,10 @@ pub fn derive_der_container(
             .is_ident(&Ident::new("debug_derive", Span::call_site()))
     });
     let last_berderive = check_lastderive_fromber(ast);
+    let options = match Options::from_struct(&s) {
+        Ok(options) => options,
+        Err(e) => return e.to_compile_error(),
+    };
 
     let impl_tagged = if last_berderive {
         container.gen_tagged()
@@ -167,7 +197,7 @@ pub fn derive_der_container(
     };
     let impl_tryfrom = container.gen_tryfrom();
     let impl_checkconstraints = container.gen_checkconstraints();
-    let impl_fromder = container.gen_fromder();
+    let impl_fromder = container.gen_fromder(options.extensible);
     let ts = s.gen_impl(quote! {
         extern crate asn1_rs;
 
@@ -0,0 +1,53 @@
+use asn1_rs::{lint_der, Input};
+use colored::*;
+use std::error::Error;
+use std::{env, fs, process};
+
+/// Check DER files (or PEM files), and print all DER violations
+///
+/// Exit status is 1 if any file is not valid DER.
+fn main() -> std::result::Result<(), Box<dyn Error>> {
+    let mut all_valid = true;
+    for filename in env::args().skip(1) {
+        eprintln!("File: {filename}");
+        let content = fs::read(&filename)?;
+        // check for PEM file
+        if filename.ends_with(".pem") || content.starts_with(b"----") {
+            let pems = pem::parse_many(&content).expect("Parsing PEM failed");
+            if pems.is_empty() {
+                eprintln!("{}", "No PEM section decoded".bright_red());
+                all_valid = false;
+                continue;
+            }
+            for (idx, pem) in pems.iter().enumerate() {
+                eprintln!("Pem entry {} [{}]", idx, pem.tag().bright_blue());
+                all_valid &= lint(pem.contents());
+            }
+        } else {
+            all_valid &= lint(&content);
+        }
+    }
+
+    if !all_valid {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn lint(data: &[u8]) -> bool {
+    let report = lint_der(Input::from(data));
+    for violation in report.violations() {
+        println!("{}", violation.to_string().bright_red());
+    }
+    if let Some((offset, e)) = report.error() {
+        let msg = format!("offset {offset}: parsing stopped: {e}");
+        println!("{}", msg.bright_red());
+    }
+    if report.is_valid() {
+        println!("{}", "OK: valid DER".green());
+    } else {
+        let msg = format!("{} DER violation(s)", report.violations().len());
+        println!("{}", msg.yellow());
+    }
+    report.is_valid()
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "value-notation")]
+use asn1_rs::value_notation::ToValueNotation;
 use asn1_rs::{Any, AnyIterator, Class, DerMode, DerParser, Header, Input, Length, Tag};
 use colored::*;
 // use oid_registry::{format_oid, Oid as DerOid, OidRegistry};
@@ -19,6 +21,9 @@ struct Context<'a> {
     /// Dump object hex data
     dump_hex_data: bool,
 
+    /// Print objects using the ASN.1 value notation (requires the `value-notation` feature)
+    value_notation: bool,
+
     t: PhantomData<&'a ()>,
 }
 
@@ -31,6 +36,7 @@ impl Default for Context<'_> {
             off_width: 0,
             dump_header: false,
             dump_hex_data: false,
+            value_notation: false,
             t: PhantomData,
         }
     }
@@ -124,6 +130,11 @@ fn main() -> std::result::Result<(), Box<dyn Error>> {
                 ctx.dump_hex_data = true;
                 continue;
             }
+            #[cfg(feature = "value-notation")]
+            "-v" => {
+                ctx.value_notation = true;
+                continue;
+            }
             _ => (),
         }
         let filename = arg;
@@ -155,7 +166,12 @@ fn print_der(i: &[u8], depth: usize, ctx: &Context) {
     let start = input.start();
     match Any::parse_der(input) {
         Ok((rem, any)) => {
-            print_der_any(start, any, depth, ctx);
+            if ctx.value_notation {
+                #[cfg(feature = "value-notation")]
+                println!("{}", any.to_value_notation());
+            } else {
+                print_der_any(start, any, depth, ctx);
+            }
             if !rem.is_empty() {
                 let warning = format!("WARNING: {} extra bytes after object", rem.len());
                 indent_println!(depth, "{}", warning.bright_red());
@@ -200,7 +200,11 @@ macro_rules! impl_tryfrom_any {
                 use $crate::BerParser;
 
                 if !<Self as $crate::DynTagged>::accept_tag(any.tag()) {
-                    Err($crate::Error::unexpected_tag(None, any.tag()).into())
+                    Err($crate::Error::unexpected_tag(
+                        <Self as $crate::DynTagged>::expected_tag(),
+                        any.tag(),
+                    )
+                    .into())
                 } else {
                     let (_, obj) = Self::from_ber_content(&any.header, any.data)
                         .map_err($crate::Error::from_nom_berr)?;
@@ -216,7 +220,11 @@ macro_rules! impl_tryfrom_any {
                 use $crate::BerParser;
 
                 if !<Self as $crate::DynTagged>::accept_tag(any.tag()) {
-                    Err($crate::Error::unexpected_tag(None, any.tag()).into())
+                    Err($crate::Error::unexpected_tag(
+                        <Self as $crate::DynTagged>::expected_tag(),
+                        any.tag(),
+                    )
+                    .into())
                 } else {
                     let (_, obj) = Self::from_ber_content(&any.header, any.data.clone())
                         .map_err($crate::Error::from_nom_berr)?;
@@ -540,6 +548,28 @@ impl<'i> DerParser<'i> for Any<'i> {
     }
 }
 
+impl<'i> CerParser<'i> for Any<'i> {
+    type Error = BerError<Input<'i>>;
+
+    fn from_cer_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        let (rem, data) = match header.length {
+            Length::Definite(l) => take(l)(input)?,
+            Length::Indefinite => {
+                // assume entire input was parsed (End-Of-Content is not included)
+                take(input.len())(input)?
+            }
+        };
+        let any = Any {
+            header: header.clone(),
+            data,
+        };
+        Ok((rem, any))
+    }
+}
+
 impl CheckDerConstraints for Any<'_> {
     fn check_constraints(any: &Any) -> Result<()> {
         any.header.length().assert_definite()?;
@@ -580,10 +610,7 @@ impl DynTagged for Any<'_> {
 //     }
 // }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for Any<'_> {
         type Encoder = BerGenericEncoder;
 
@@ -591,7 +618,7 @@ const _: () = {
             Length::Definite(self.data.len())
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             target.write_all(self.data.as_bytes2())?;
             Ok(self.data.len())
         }
@@ -602,6 +629,32 @@ const _: () = {
     }
 
     impl_toder_from_tober!(LFT 'a, Any<'a>);
+
+    impl ToCer for Any<'_> {
+        type Encoder = BerGenericEncoder;
+
+        fn cer_content_len(&self) -> Length {
+            // constructed objects shall use the indefinite length form (X.690: 9.1)
+            if self.constructed() {
+                Length::Indefinite
+            } else {
+                Length::Definite(self.data.len())
+            }
+        }
+
+        fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            target.write_all(self.data.as_bytes2())?;
+            let mut sz = self.data.len();
+            if self.constructed() {
+                sz += cer_write_eoc(target)?;
+            }
+            Ok(sz)
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            (self.class(), self.constructed(), self.tag())
+        }
+    }
 };
 
 #[cfg(test)]
@@ -140,6 +140,18 @@ impl<'i> DerParser<'i> for BitString {
     }
 }
 
+impl<'i> CerParser<'i> for BitString {
+    type Error = BerError<Input<'i>>;
+
+    fn from_cer_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        // Encoding shall be primitive, or constructed with segments of 1000 octets (X.690: 9.2)
+        parse_cer_segmented(header, input)
+    }
+}
+
 impl CheckDerConstraints for BitString {
     fn check_constraints(any: &Any) -> Result<()> {
         // X.690 section 10.2
@@ -181,10 +193,7 @@ impl Appendable for BitString {
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for BitString {
         type Encoder = Primitive<{ Tag::BitString.0 }>;
 
@@ -193,7 +202,7 @@ const _: () = {
             Length::Definite(len)
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             let data = self.as_raw_slice();
             // ignored bits
             let ignored = (8 * data.len()) - self.len();
@@ -210,6 +219,47 @@ const _: () = {
     }
 
     impl_toder_from_tober!(TY BitString);
+
+    impl ToCer for BitString {
+        type Encoder = BerGenericEncoder;
+
+        fn cer_content_len(&self) -> Length {
+            // the unused bits octet is counted in each segment
+            let len = 1 + ((self.len() + 7) / 8);
+            cer_string_content_len(len)
+        }
+
+        fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            let data = self.as_raw_slice();
+            if !cer_string_is_segmented(1 + data.len()) {
+                return self.ber_write_content(target);
+            }
+            // ignored bits (only in last segment)
+            let ignored = (8 * data.len()) - self.len();
+            let mut sz = 0;
+            let mut chunks = data.chunks(CER_SEGMENT_SIZE - 1).peekable();
+            while let Some(chunk) = chunks.next() {
+                let unused_bits = if chunks.peek().is_none() { ignored } else { 0 };
+                let header = Header::new(
+                    Class::Universal,
+                    false,
+                    Tag::BitString,
+                    Length::Definite(1 + chunk.len()),
+                );
+                sz += header.cer_write_header(target)?;
+                target.write_all(&[unused_bits as u8])?;
+                target.write_all(chunk)?;
+                sz += 1 + chunk.len();
+            }
+            sz += cer_write_eoc(target)?;
+            Ok(sz)
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            let len = 1 + ((self.len() + 7) / 8);
+            (Self::CLASS, cer_string_is_segmented(len), Self::TAG)
+        }
+    }
 };
 
 #[cfg(test)]
@@ -72,6 +72,8 @@ impl<'i> DerParser<'i> for Boolean {
     }
 }
 
+impl_cerparser_from_derparser!(TY Boolean);
+
 impl CheckDerConstraints for Boolean {
     fn check_constraints(any: &Any) -> Result<()> {
         let c = any.data.as_bytes()[0];
@@ -89,10 +91,7 @@ impl Tagged for Boolean {
     const TAG: Tag = Tag::Boolean;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for Boolean {
         type Encoder = Primitive<{ Tag::Boolean.0 }>;
 
@@ -100,7 +99,7 @@ const _: () = {
             Length::Definite(1)
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             target.write_all(&[self.value])?;
             Ok(1)
         }
@@ -117,7 +116,7 @@ const _: () = {
             Length::Definite(1)
         }
 
-        fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             let value = if self.value != 0 { 0xff } else { 0x00 };
             target.write_all(&[value])?;
             Ok(1)
@@ -127,6 +126,8 @@ const _: () = {
             (self.class(), self.constructed(), self.tag())
         }
     }
+
+    impl_tocer_from_toder!(TY Boolean);
 };
 
 //---- bool
@@ -166,16 +167,15 @@ impl<'i> DerParser<'i> for bool {
     }
 }
 
+impl_cerparser_from_derparser!(TY bool);
+
 impl DerAutoDerive for bool {}
 
 impl Tagged for bool {
     const TAG: Tag = Tag::Boolean;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for bool {
         type Encoder = Primitive<{ Tag::Boolean.0 }>;
 
@@ -183,7 +183,7 @@ const _: () = {
             Length::Definite(1)
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             let value = if *self { 0xff } else { 0x00 };
             target.write_all(&[value])?;
             Ok(1)
@@ -201,7 +201,7 @@ const _: () = {
             Length::Definite(1)
         }
 
-        fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             let value = if *self { 0xff } else { 0x00 };
             target.write_all(&[value])?;
             Ok(1)
@@ -211,6 +211,8 @@ const _: () = {
             (self.class(), self.constructed(), self.tag())
         }
     }
+
+    impl_tocer_from_toder!(TY bool);
 };
 
 #[cfg(test)]
@@ -46,6 +46,8 @@ impl<'i> DerParser<'i> for Enumerated {
     }
 }
 
+impl_cerparser_from_derparser!(TY Enumerated);
+
 impl CheckDerConstraints for Enumerated {
     fn check_constraints(any: &Any) -> Result<()> {
         any.header.length.assert_definite()?;
@@ -59,10 +61,7 @@ impl Tagged for Enumerated {
     const TAG: Tag = Tag::Enumerated;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for Enumerated {
         type Encoder = Primitive<{ Tag::Enumerated.0 }>;
 
@@ -71,7 +70,7 @@ const _: () = {
             Length::Definite(i.data.len())
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             let i = Integer::from(self.0);
             target.write_all(&i.data)?;
             Ok(i.data.len())
@@ -83,6 +82,7 @@ const _: () = {
     }
 
     impl_toder_from_tober!(TY Enumerated);
+    impl_tocer_from_toder!(TY Enumerated);
 };
 
 #[cfg(test)]
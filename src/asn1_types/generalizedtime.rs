@@ -1,7 +1,7 @@
 use crate::*;
 use alloc::format;
 #[cfg(not(feature = "std"))]
-use alloc::string::String;
+use alloc::string::{String, ToString};
 use core::fmt;
 use nom::{AsBytes, Input as _};
 #[cfg(feature = "datetime")]
@@ -15,6 +15,32 @@ impl GeneralizedTime {
         GeneralizedTime(datetime)
     }
 
+    /// Returns the characters of the BER encoding of this value (for ex. `"20021213142923.5Z"`)
+    ///
+    /// Trailing zeroes of the fractional part are removed.
+    #[cfg(any(feature = "jer", feature = "xer", feature = "value-notation"))]
+    pub(crate) fn to_value_string(&self) -> String {
+        let dt = &self.0;
+        let fractional = match dt.millisecond {
+            Some(v) if v > 0 => {
+                let s = format!(".{v:03}");
+                s.trim_end_matches('0').into()
+            }
+            _ => String::new(),
+        };
+        format!(
+            "{:04}{:02}{:02}{:02}{:02}{:02}{}{}",
+            dt.year,
+            dt.month,
+            dt.day,
+            dt.hour,
+            dt.minute,
+            dt.second,
+            fractional,
+            format_time_zone(dt.tz)
+        )
+    }
+
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
         // X.680 section 42 defines a GeneralizedTime as a VisibleString restricted to:
         //
@@ -248,6 +274,8 @@ impl<'i> DerParser<'i> for GeneralizedTime {
     }
 }
 
+impl_cerparser_from_derparser!(TY GeneralizedTime);
+
 impl fmt::Display for GeneralizedTime {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let dt = &self.0;
@@ -300,10 +328,7 @@ impl Tagged for GeneralizedTime {
     const TAG: Tag = Tag::GeneralizedTime;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for GeneralizedTime {
         type Encoder = Primitive<{ Tag::GeneralizedTime.0 }>;
 
@@ -322,7 +347,7 @@ const _: () = {
             Length::Definite(15 + num_digits)
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             let fractional = match self.0.millisecond {
                 None => "".to_string(),
                 Some(v) => format!(".{v}"),
@@ -349,6 +374,7 @@ const _: () = {
     }
 
     impl_toder_from_tober!(TY GeneralizedTime);
+    impl_tocer_from_toder!(TY GeneralizedTime);
 };
 
 #[cfg(test)]
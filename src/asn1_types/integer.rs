@@ -130,6 +130,8 @@ macro_rules! impl_int {
             }
         }
 
+        impl_cerparser_from_derparser!(TY $int);
+
         impl CheckDerConstraints for $int {
             fn check_constraints(any: &Any) -> Result<()> {
                 check_der_int_constraints(any)
@@ -142,10 +144,7 @@ macro_rules! impl_int {
             const TAG: Tag = Tag::Integer;
         }
 
-        #[cfg(feature = "std")]
         const _: () = {
-            use std::io::Write;
-
             impl ToBer for $int {
                 type Encoder = Primitive<{ Tag::Integer.0 }>;
 
@@ -154,7 +153,7 @@ macro_rules! impl_int {
                     Length::Definite(int.data.len())
                 }
 
-                fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+                fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
                     let int = Integer::from(*self);
                     target.write_all(&int.data)?;
                     Ok(int.data.len())
@@ -174,7 +173,7 @@ macro_rules! impl_int {
                     Length::Definite(int.data.len())
                 }
 
-                fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+                fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
                     let int = Integer::from(*self);
                     target.write_all(&int.data)?;
                     Ok(int.data.len())
@@ -185,6 +184,8 @@ macro_rules! impl_int {
                     (Self::CLASS, false, Self::TAG)
                 }
             }
+
+            impl_tocer_from_toder!(TY $int);
         };
     };
 }
@@ -228,6 +229,8 @@ macro_rules! impl_uint {
             }
         }
 
+        impl_cerparser_from_derparser!(TY $ty);
+
         impl CheckDerConstraints for $ty {
             fn check_constraints(any: &Any) -> Result<()> {
                 check_der_int_constraints(any)
@@ -240,10 +243,7 @@ macro_rules! impl_uint {
             const TAG: Tag = Tag::Integer;
         }
 
-        #[cfg(feature = "std")]
         const _: () = {
-            use std::io::Write;
-
             impl ToBer for $ty {
                 type Encoder = Primitive<{ Tag::Integer.0 }>;
 
@@ -252,7 +252,7 @@ macro_rules! impl_uint {
                     Length::Definite(int.data.len())
                 }
 
-                fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+                fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
                     let int = Integer::from(*self);
                     target.write_all(&int.data)?;
                     Ok(int.data.len())
@@ -272,7 +272,7 @@ macro_rules! impl_uint {
                     Length::Definite(int.data.len())
                 }
 
-                fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+                fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
                     let int = Integer::from(*self);
                     target.write_all(&int.data)?;
                     Ok(int.data.len())
@@ -283,6 +283,8 @@ macro_rules! impl_uint {
                     (Self::CLASS, false, Self::TAG)
                 }
             }
+
+            impl_tocer_from_toder!(TY $ty);
         };
     };
 }
@@ -597,6 +599,8 @@ impl<'i> DerParser<'i> for Integer<'i> {
     }
 }
 
+impl_cerparser_from_derparser!(LFT 'a, Integer<'a>);
+
 impl CheckDerConstraints for Integer<'_> {
     fn check_constraints(any: &Any) -> Result<()> {
         check_der_int_constraints(any)
@@ -621,7 +625,7 @@ fn check_der_int_constraints(any: &Any) -> Result<()> {
     }
 }
 
-fn check_der_int_constraints_input(input: &Input) -> Result<(), DerConstraint> {
+pub(crate) fn check_der_int_constraints_input(input: &Input) -> Result<(), DerConstraint> {
     match input.as_bytes2() {
         [] => Err(DerConstraint::IntegerEmpty),
         [0] => Ok(()),
@@ -639,10 +643,7 @@ impl Tagged for Integer<'_> {
     const TAG: Tag = Tag::Integer;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for Integer<'_> {
         type Encoder = Primitive<{ Tag::Integer.0 }>;
 
@@ -650,7 +651,7 @@ const _: () = {
             Length::Definite(self.data.len())
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             target.write_all(&self.data)?;
             Ok(self.data.len())
         }
@@ -661,6 +662,7 @@ const _: () = {
     }
 
     impl_toder_from_tober!(LFT 'a, Integer<'a>);
+    impl_tocer_from_toder!(LFT 'a, Integer<'a>);
 };
 
 /// Helper macro to declare integers at compile-time
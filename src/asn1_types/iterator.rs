@@ -3,7 +3,10 @@ use core::marker::PhantomData;
 
 use nom::{Err, IResult, Input as _};
 
-use crate::{ASN1Mode, BerError, BerMode, BerParser, DerMode, DerParser, InnerError, Input};
+use crate::{
+    err_at_index, ASN1Mode, BerError, BerMode, BerParser, CerMode, CerParser, DerMode, DerParser,
+    ErrorContext, InnerError, Input,
+};
 
 use super::Any;
 
@@ -23,6 +26,9 @@ use super::Any;
 ///
 /// Note: to avoid infinite loops, calling `.next()` after an error happened will
 /// return `None` (ending iteration).
+///
+/// The number of sub-objects is bounded by the `max_elements` [`Limits`](crate::Limits) of
+/// the input: if exceeded, a [`BerMaxElements`](InnerError::BerMaxElements) error is returned.
 #[derive(Debug)]
 pub struct AnyIterator<'a, Mode>
 where
@@ -30,6 +36,7 @@ where
 {
     input: Input<'a>,
     has_error: bool,
+    count: usize,
     _mode: PhantomData<*const Mode>,
 }
 
@@ -41,9 +48,23 @@ where
         Self {
             input,
             has_error: false,
+            count: 0,
             _mode: PhantomData,
         }
     }
+
+    /// Count the next sub-object, and return an error if the maximum number of elements is reached
+    fn check_max_elements(&mut self) -> Result<(), BerError<Input<'a>>> {
+        if self.count >= self.input.limits().max_elements() {
+            self.has_error = true;
+            return Err(BerError::new(
+                self.input.clone(),
+                InnerError::BerMaxElements,
+            ));
+        }
+        self.count += 1;
+        Ok(())
+    }
 }
 
 impl<'a> AnyIterator<'a, BerMode> {
@@ -90,21 +111,29 @@ impl<'a> AnyIterator<'a, BerMode> {
     where
         B: FromIterator<T>,
         T: BerParser<'a>,
-        <T as BerParser<'a>>::Error: From<BerError<Input<'a>>>,
+        <T as BerParser<'a>>::Error: From<BerError<Input<'a>>> + ErrorContext,
     {
-        let b = <Result<B, Err<T::Error, T::Error>>>::from_iter(self.map(|r| match r {
-            Ok((_, obj)) => {
-                if !T::accept_tag(obj.tag()) {
-                    Err(Err::Error(
-                        BerError::unexpected_tag(obj.data.clone(), None, obj.tag()).into(),
-                    ))
-                } else {
-                    let (_, obj) = T::from_ber_content(&obj.header, obj.data)?;
-                    Ok(obj)
-                }
-            }
-            Err(e) => Err(Err::Error(e.into())),
-        }));
+        let b =
+            <Result<B, Err<T::Error, T::Error>>>::from_iter(self.enumerate().map(|(index, r)| {
+                let r = match r {
+                    Ok((_, obj)) => {
+                        if !T::accept_tag(obj.tag()) {
+                            Err(Err::Error(
+                                BerError::unexpected_tag(
+                                    obj.data.clone(),
+                                    T::expected_tag(),
+                                    obj.tag(),
+                                )
+                                .into(),
+                            ))
+                        } else {
+                            T::from_ber_content(&obj.header, obj.data).map(|(_, obj)| obj)
+                        }
+                    }
+                    Err(e) => Err(Err::Error(e.into())),
+                };
+                r.map_err(err_at_index(index))
+            }));
         // after iteration, self.input points at end of last object content
         b.map(|obj| (self.input.clone(), obj))
     }
@@ -154,24 +183,104 @@ impl<'a> AnyIterator<'a, DerMode> {
     where
         B: FromIterator<T>,
         T: DerParser<'a>,
-        <T as DerParser<'a>>::Error: From<BerError<Input<'a>>>,
+        <T as DerParser<'a>>::Error: From<BerError<Input<'a>>> + ErrorContext,
     {
-        let b = <Result<B, Err<T::Error, T::Error>>>::from_iter(self.map(|r| match r {
-            Ok((_, obj)) => {
-                if !T::accept_tag(obj.tag()) {
-                    Err(Err::Error(
-                        BerError::unexpected_tag(obj.data.clone(), None, obj.tag()).into(),
-                    ))
-                } else {
-                    let (_, obj) = T::from_der_content(&obj.header, obj.data)?;
-                    Ok(obj)
-                }
-            }
-            Err(e) => Err(Err::Error(e.into())),
+        let b =
+            <Result<B, Err<T::Error, T::Error>>>::from_iter(self.enumerate().map(|(index, r)| {
+                let r = match r {
+                    Ok((_, obj)) => {
+                        if !T::accept_tag(obj.tag()) {
+                            Err(Err::Error(
+                                BerError::unexpected_tag(
+                                    obj.data.clone(),
+                                    T::expected_tag(),
+                                    obj.tag(),
+                                )
+                                .into(),
+                            ))
+                        } else {
+                            T::from_der_content(&obj.header, obj.data).map(|(_, obj)| obj)
+                        }
+                    }
+                    Err(e) => Err(Err::Error(e.into())),
+                };
+                r.map_err(err_at_index(index))
+            }));
+        // after iteration, self.input points at end of last object content
+        b.map(|obj| (self.input.clone(), obj))
+    }
+}
+
+impl<'a> AnyIterator<'a, CerMode> {
+    /// Iterate on sub-objects, returning a collection `B` of elements with type `Any`
+    ///
+    /// This is a simplifier (and faster) version of [`AnyIterator::try_parse_collect`] when `T = Any`.
+    pub fn try_collect<B>(&mut self) -> IResult<Input<'a>, B, BerError<Input<'a>>>
+    where
+        B: FromIterator<Any<'a>>,
+    {
+        let b = <Result<B, _>>::from_iter(self.map(|r| match r {
+            Ok((_, obj)) => Ok(obj),
+            Err(e) => Err(Err::Error(e)),
         }));
         // after iteration, self.input points at end of last object content
         b.map(|obj| (self.input.clone(), obj))
     }
+
+    /// Try to iterate on sub-objects, returning a collection `B` of elements with type `T`
+    ///
+    /// Similarly to [`Iterator::collect`], this function requires type annotations.
+    ///
+    /// Since it also has to infer the error type, it is often not possible to use the `?`
+    /// operator directly: `.try_parse_collect()?`will cause an error because it cannot infer
+    /// result type. To avoid this, use an itermediate value to store result (or use the
+    /// turbofish `::<>` operator).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use asn1_rs::{AnyIterator, CerMode, Input};
+    /// use hex_literal::hex;
+    ///
+    /// let input = Input::from_slice(&hex!("0203010001 0203010001"));
+    /// let mut iter = AnyIterator::<CerMode>::new(input);
+    ///
+    /// let r: Result<(Input, Vec<u32>), _> = iter.try_parse_collect();
+    /// let _ = r.expect("parsing failed");
+    ///
+    /// let r = iter.try_parse_collect::<Vec<u32>, _>();
+    /// let _ = r.expect("parsing failed");
+    /// ```
+    pub fn try_parse_collect<B, T>(&mut self) -> IResult<Input<'a>, B, <T as CerParser<'a>>::Error>
+    where
+        B: FromIterator<T>,
+        T: CerParser<'a>,
+        <T as CerParser<'a>>::Error: From<BerError<Input<'a>>> + ErrorContext,
+    {
+        let b =
+            <Result<B, Err<T::Error, T::Error>>>::from_iter(self.enumerate().map(|(index, r)| {
+                let r = match r {
+                    Ok((_, obj)) => {
+                        if !T::accept_tag(obj.tag()) {
+                            Err(Err::Error(
+                                BerError::unexpected_tag(
+                                    obj.data.clone(),
+                                    T::expected_tag(),
+                                    obj.tag(),
+                                )
+                                .into(),
+                            ))
+                        } else {
+                            T::from_cer_content(&obj.header, obj.data).map(|(_, obj)| obj)
+                        }
+                    }
+                    Err(e) => Err(Err::Error(e.into())),
+                };
+                r.map_err(err_at_index(index))
+            }));
+        // after iteration, self.input points at end of last object content
+        b.map(|obj| (self.input.clone(), obj))
+    }
 }
 
 impl<'a> Iterator for AnyIterator<'a, BerMode> {
@@ -181,6 +290,9 @@ impl<'a> Iterator for AnyIterator<'a, BerMode> {
         if self.has_error || self.input.is_empty() {
             return None;
         }
+        if let Err(e) = self.check_max_elements() {
+            return Some(Err(e));
+        }
         let input = self.input.clone();
         match Any::parse_ber(input) {
             Ok((rem, obj)) => {
@@ -208,6 +320,9 @@ impl<'a> Iterator for AnyIterator<'a, DerMode> {
         if self.has_error || self.input.is_empty() {
             return None;
         }
+        if let Err(e) = self.check_max_elements() {
+            return Some(Err(e));
+        }
         let input = self.input.clone();
         match Any::parse_der(input) {
             Ok((rem, obj)) => {
@@ -228,6 +343,36 @@ impl<'a> Iterator for AnyIterator<'a, DerMode> {
     }
 }
 
+impl<'a> Iterator for AnyIterator<'a, CerMode> {
+    type Item = Result<(Input<'a>, Any<'a>), BerError<Input<'a>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.has_error || self.input.is_empty() {
+            return None;
+        }
+        if let Err(e) = self.check_max_elements() {
+            return Some(Err(e));
+        }
+        let input = self.input.clone();
+        match Any::parse_cer(input) {
+            Ok((rem, obj)) => {
+                let data = self.input.take(rem.span().start - self.input.span().start);
+                self.input = rem;
+                Some(Ok((data, obj)))
+            }
+            Err(Err::Error(e)) | Err(Err::Failure(e)) => {
+                self.has_error = true;
+                Some(Err(e))
+            }
+            Err(Err::Incomplete(n)) => {
+                self.has_error = true;
+                let e = BerError::new(self.input.clone(), InnerError::Incomplete(n));
+                Some(Err(e))
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::vec::Vec;
@@ -49,6 +49,8 @@ impl<'i> DerParser<'i> for Null {
     }
 }
 
+impl_cerparser_from_derparser!(TY Null);
+
 impl CheckDerConstraints for Null {
     fn check_constraints(_any: &Any) -> Result<()> {
         Ok(())
@@ -61,10 +63,7 @@ impl Tagged for Null {
     const TAG: Tag = Tag::Null;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for Null {
         type Encoder = Primitive<{ Tag::Null.0 }>;
 
@@ -72,7 +71,7 @@ const _: () = {
             Length::Definite(0)
         }
 
-        fn ber_write_content<W: Write>(&self, _: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, _: &mut W) -> SerializeResult<usize> {
             Ok(0)
         }
 
@@ -82,6 +81,7 @@ const _: () = {
     }
 
     impl_toder_from_tober!(TY Null);
+    impl_tocer_from_toder!(TY Null);
 };
 
 //---- ()
@@ -110,6 +110,8 @@ impl<'i> DerParser<'i> for () {
     }
 }
 
+impl_cerparser_from_derparser!(TY());
+
 impl CheckDerConstraints for () {
     fn check_constraints(_any: &Any) -> Result<()> {
         Ok(())
@@ -122,10 +124,7 @@ impl Tagged for () {
     const TAG: Tag = Tag::Null;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for () {
         type Encoder = Primitive<{ Tag::Null.0 }>;
 
@@ -133,7 +132,7 @@ const _: () = {
             Length::Definite(0)
         }
 
-        fn ber_write_content<W: Write>(&self, _: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, _: &mut W) -> SerializeResult<usize> {
             Ok(0)
         }
 
@@ -143,6 +142,7 @@ const _: () = {
     }
 
     impl_toder_from_tober!(TY());
+    impl_tocer_from_toder!(TY());
 };
 
 #[cfg(test)]
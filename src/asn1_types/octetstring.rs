@@ -52,6 +52,12 @@ impl<'a> From<&'a [u8]> for OctetString<'a> {
     }
 }
 
+impl<'a> From<Cow<'a, [u8]>> for OctetString<'a> {
+    fn from(data: Cow<'a, [u8]>) -> Self {
+        OctetString { data }
+    }
+}
+
 impl_tryfrom_any!('i @ OctetString<'i>);
 
 impl<'i> BerParser<'i> for OctetString<'i> {
@@ -90,6 +96,18 @@ impl<'i> DerParser<'i> for OctetString<'i> {
     }
 }
 
+impl<'i> CerParser<'i> for OctetString<'i> {
+    type Error = BerError<Input<'i>>;
+
+    fn from_cer_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        // Encoding shall be primitive, or constructed with segments of 1000 octets (X.690: 9.2)
+        parse_cer_segmented(header, input)
+    }
+}
+
 impl CheckDerConstraints for OctetString<'_> {
     fn check_constraints(any: &Any) -> Result<()> {
         // X.690 section 10.2
@@ -117,10 +135,7 @@ impl Appendable for OctetString<'_> {
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for OctetString<'_> {
         type Encoder = Primitive<{ Tag::OctetString.0 }>;
 
@@ -128,7 +143,7 @@ const _: () = {
             Length::Definite(self.data.len())
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             target.write_all(&self.data)?;
             Ok(self.data.len())
         }
@@ -139,6 +154,26 @@ const _: () = {
     }
 
     impl_toder_from_tober!(LFT 'a, OctetString<'a>);
+
+    impl ToCer for OctetString<'_> {
+        type Encoder = BerGenericEncoder;
+
+        fn cer_content_len(&self) -> Length {
+            cer_string_content_len(self.data.len())
+        }
+
+        fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            cer_write_string_content(&self.data, target)
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            (
+                Self::CLASS,
+                cer_string_is_segmented(self.data.len()),
+                Self::TAG,
+            )
+        }
+    }
 };
 
 //---- &[u8]
@@ -177,6 +212,25 @@ impl<'i> DerParser<'i> for &'i [u8] {
     }
 }
 
+impl<'i> CerParser<'i> for &'i [u8] {
+    type Error = BerError<Input<'i>>;
+
+    fn from_cer_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        // Encoding shall be primitive if length is at most 1000 octets (X.690: 9.2)
+        // Longer strings use the constructed form, which is not supported for shared slices.
+        if input.len() > CER_SEGMENT_SIZE {
+            return Err(BerError::nom_err_input(
+                &input,
+                InnerError::DerConstraintFailed(DerConstraint::SegmentSize),
+            ));
+        }
+        Self::from_der_content(header, input)
+    }
+}
+
 impl CheckDerConstraints for &'_ [u8] {
     fn check_constraints(any: &Any) -> Result<()> {
         // X.690 section 10.2
@@ -191,10 +245,7 @@ impl Tagged for &'_ [u8] {
     const TAG: Tag = Tag::OctetString;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for &'_ [u8] {
         type Encoder = Primitive<{ Tag::OctetString.0 }>;
 
@@ -202,7 +253,7 @@ const _: () = {
             Length::Definite(self.len())
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             target.write_all(self)?;
             Ok(self.len())
         }
@@ -213,6 +264,22 @@ const _: () = {
     }
 
     impl_toder_from_tober!(LFT 'a, &'a [u8]);
+
+    impl ToCer for &'_ [u8] {
+        type Encoder = BerGenericEncoder;
+
+        fn cer_content_len(&self) -> Length {
+            cer_string_content_len(self.len())
+        }
+
+        fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            cer_write_string_content(self, target)
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            (Self::CLASS, cer_string_is_segmented(self.len()), Self::TAG)
+        }
+    }
 };
 
 #[cfg(test)]
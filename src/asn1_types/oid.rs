@@ -75,6 +75,8 @@ impl<'i> DerParser<'i> for Oid<'i> {
     }
 }
 
+impl_cerparser_from_derparser!(LFT 'a, Oid<'a>);
+
 impl CheckDerConstraints for Oid<'_> {
     fn check_constraints(any: &Any) -> Result<()> {
         any.header.assert_primitive()?;
@@ -99,10 +101,7 @@ impl DynTagged for Oid<'_> {
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for Oid<'_> {
         type Encoder = BerGenericEncoder;
 
@@ -110,7 +109,7 @@ const _: () = {
             Length::Definite(self.asn1.len())
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             target.write_all(&self.asn1)?;
             Ok(self.asn1.len())
         }
@@ -121,6 +120,7 @@ const _: () = {
     }
 
     impl_toder_from_tober!(LFT 'a, Oid<'a>);
+    impl_tocer_from_toder!(LFT 'a, Oid<'a>);
 };
 
 fn encode_relative(ids: &'_ [u64]) -> impl Iterator<Item = u8> + '_ {
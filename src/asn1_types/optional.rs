@@ -121,6 +121,49 @@ where
     }
 }
 
+impl<'a, T, E> CerParser<'a> for Option<T>
+where
+    T: CerParser<'a, Error = E>,
+    E: Display + ParseError<Input<'a>> + From<BerError<Input<'a>>>,
+{
+    type Error = E;
+
+    fn parse_cer(input: Input<'a>) -> IResult<Input<'a>, Self, Self::Error> {
+        trace_input("Option<T>::parse_cer", |input| {
+            if input.is_empty() {
+                return Ok((input, None));
+            }
+            // FIXME: call default trait impl?
+            // FIXME: default trait impl does not work: bytes are consumed even
+            // if tag does not match
+
+            let (rem, header) = Header::parse_cer(input.clone()).map_err(Err::convert)?;
+            // NOTE: we add this to default trait impl
+            if !T::accept_tag(header.tag) {
+                return Ok((input, None));
+            }
+            // NOTE: end
+            let (rem, data) =
+                CerMode::get_object_content(&header, rem, MAX_RECURSION).map_err(Err::convert)?;
+            let (_, obj) = Self::from_cer_content(&header, data).map_err(Err::convert)?;
+            Ok((rem, obj))
+        })(input)
+    }
+
+    fn from_cer_content(
+        header: &'_ Header<'a>,
+        input: Input<'a>,
+    ) -> IResult<Input<'a>, Self, Self::Error> {
+        // do not map empty input to None: in this method, we have parsed a header
+        if !T::accept_tag(header.tag) {
+            return Ok((input, None));
+        }
+        // input is the object content (End-Of-Content is not included if length is indefinite)
+        let (rem, obj) = T::from_cer_content(header, input)?;
+        Ok((rem, Some(obj)))
+    }
+}
+
 impl<'a, T> FromDer<'a> for Option<T>
 where
     T: FromDer<'a>,
@@ -156,11 +199,24 @@ impl<T> DynTagged for Option<T>
 where
     T: DynTagged,
 {
+    fn class(&self) -> Class {
+        match self {
+            Some(t) => t.class(),
+            None => Class::Universal,
+        }
+    }
+
+    fn constructed(&self) -> bool {
+        match self {
+            Some(t) => t.constructed(),
+            None => false,
+        }
+    }
+
     fn tag(&self) -> Tag {
-        if self.is_some() {
-            self.tag()
-        } else {
-            Tag(0)
+        match self {
+            Some(t) => t.tag(),
+            None => Tag(0),
         }
     }
 
@@ -169,10 +225,7 @@ where
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl<T> ToBer for Option<T>
     where
         T: ToBer + DynTagged,
@@ -186,7 +239,14 @@ const _: () = {
             }
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_total_len(&self) -> Length {
+            match self {
+                Some(t) => t.ber_total_len(),
+                None => Length::Definite(0),
+            }
+        }
+
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             match self {
                 Some(t) => t.ber_write_content(target),
                 None => Ok(0),
@@ -197,12 +257,36 @@ const _: () = {
             (self.class(), self.constructed(), self.tag())
         }
 
-        fn ber_encode<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_encode<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             match self {
                 Some(t) => t.ber_encode(target),
                 None => Ok(0),
             }
         }
+
+        fn ber_encode_tagged_explicit<W: Writer>(
+            &self,
+            class: Class,
+            tag_number: u32,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.ber_encode_tagged_explicit(class, tag_number, target),
+                None => Ok(0),
+            }
+        }
+
+        fn ber_encode_tagged_implicit<W: Writer>(
+            &self,
+            class: Class,
+            tag_number: u32,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.ber_encode_tagged_implicit(class, tag_number, target),
+                None => Ok(0),
+            }
+        }
     }
 
     impl<T> ToDer for Option<T>
@@ -218,7 +302,14 @@ const _: () = {
             }
         }
 
-        fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn der_total_len(&self) -> Length {
+            match self {
+                Some(t) => t.der_total_len(),
+                None => Length::Definite(0),
+            }
+        }
+
+        fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             match self {
                 Some(t) => t.der_write_content(target),
                 None => Ok(0),
@@ -229,12 +320,221 @@ const _: () = {
             (self.class(), self.constructed(), self.tag())
         }
 
-        fn der_encode<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn der_encode<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             match self {
                 Some(t) => t.der_encode(target),
                 None => Ok(0),
             }
         }
+
+        fn der_encode_tagged_explicit<W: Writer>(
+            &self,
+            class: Class,
+            tag_number: u32,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.der_encode_tagged_explicit(class, tag_number, target),
+                None => Ok(0),
+            }
+        }
+
+        fn der_encode_tagged_implicit<W: Writer>(
+            &self,
+            class: Class,
+            tag_number: u32,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.der_encode_tagged_implicit(class, tag_number, target),
+                None => Ok(0),
+            }
+        }
+
+        fn der_total_len_cached(&self, cache: &mut LengthCache) -> Length {
+            match self {
+                Some(t) => t.der_total_len_cached(cache),
+                None => Length::Definite(0),
+            }
+        }
+
+        fn der_total_len_tagged_explicit_cached(
+            &self,
+            tag_number: u32,
+            cache: &mut LengthCache,
+        ) -> Length {
+            match self {
+                Some(t) => t.der_total_len_tagged_explicit_cached(tag_number, cache),
+                None => Length::Definite(0),
+            }
+        }
+
+        fn der_total_len_tagged_implicit_cached(
+            &self,
+            tag_number: u32,
+            cache: &mut LengthCache,
+        ) -> Length {
+            match self {
+                Some(t) => t.der_total_len_tagged_implicit_cached(tag_number, cache),
+                None => Length::Definite(0),
+            }
+        }
+
+        fn der_content_len_cached(&self, cache: &mut LengthCache) -> Length {
+            match self {
+                Some(t) => t.der_content_len_cached(cache),
+                None => Length::Definite(0),
+            }
+        }
+
+        fn der_write_content_cached<W: Writer>(
+            &self,
+            cache: &mut LengthCache,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.der_write_content_cached(cache, target),
+                None => Ok(0),
+            }
+        }
+
+        fn der_encode_cached<W: Writer>(
+            &self,
+            cache: &mut LengthCache,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.der_encode_cached(cache, target),
+                None => Ok(0),
+            }
+        }
+
+        fn der_encode_tagged_explicit_cached<W: Writer>(
+            &self,
+            class: Class,
+            tag_number: u32,
+            cache: &mut LengthCache,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.der_encode_tagged_explicit_cached(class, tag_number, cache, target),
+                None => Ok(0),
+            }
+        }
+
+        fn der_encode_tagged_implicit_cached<W: Writer>(
+            &self,
+            class: Class,
+            tag_number: u32,
+            cache: &mut LengthCache,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.der_encode_tagged_implicit_cached(class, tag_number, cache, target),
+                None => Ok(0),
+            }
+        }
+
+        fn der_write_content_reverse(&self, target: &mut ReverseWriter) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.der_write_content_reverse(target),
+                None => Ok(0),
+            }
+        }
+
+        fn der_encode_reverse(&self, target: &mut ReverseWriter) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.der_encode_reverse(target),
+                None => Ok(0),
+            }
+        }
+
+        fn der_encode_tagged_explicit_reverse(
+            &self,
+            class: Class,
+            tag_number: u32,
+            target: &mut ReverseWriter,
+        ) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.der_encode_tagged_explicit_reverse(class, tag_number, target),
+                None => Ok(0),
+            }
+        }
+
+        fn der_encode_tagged_implicit_reverse(
+            &self,
+            class: Class,
+            tag_number: u32,
+            target: &mut ReverseWriter,
+        ) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.der_encode_tagged_implicit_reverse(class, tag_number, target),
+                None => Ok(0),
+            }
+        }
+    }
+
+    impl<T> ToCer for Option<T>
+    where
+        T: ToCer + DynTagged,
+    {
+        type Encoder = BerGenericEncoder;
+
+        fn cer_content_len(&self) -> Length {
+            match self {
+                Some(t) => t.cer_content_len(),
+                None => Length::Definite(0),
+            }
+        }
+
+        fn cer_total_len(&self) -> Length {
+            match self {
+                Some(t) => t.cer_total_len(),
+                None => Length::Definite(0),
+            }
+        }
+
+        fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.cer_write_content(target),
+                None => Ok(0),
+            }
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            (self.class(), self.constructed(), self.tag())
+        }
+
+        fn cer_encode<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.cer_encode(target),
+                None => Ok(0),
+            }
+        }
+
+        fn cer_encode_tagged_explicit<W: Writer>(
+            &self,
+            class: Class,
+            tag_number: u32,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.cer_encode_tagged_explicit(class, tag_number, target),
+                None => Ok(0),
+            }
+        }
+
+        fn cer_encode_tagged_implicit<W: Writer>(
+            &self,
+            class: Class,
+            tag_number: u32,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            match self {
+                Some(t) => t.cer_encode_tagged_implicit(class, tag_number, target),
+                None => Ok(0),
+            }
+        }
     }
 };
 
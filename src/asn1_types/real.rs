@@ -186,6 +186,8 @@ impl<'i> DerParser<'i> for Real {
     }
 }
 
+impl_cerparser_from_derparser!(TY Real);
+
 impl CheckDerConstraints for Real {
     fn check_constraints(any: &Any) -> Result<()> {
         any.header.assert_primitive()?;
@@ -333,9 +335,6 @@ fn decode_real(header: &Header, bytes: &[u8]) -> Result<Real, InnerError> {
 
 #[cfg(feature = "std")]
 const _: () = {
-    use std::io;
-    use std::io::Write;
-
     impl ToBer for Real {
         type Encoder = Primitive<{ Tag::RealType.0 }>;
 
@@ -344,14 +343,14 @@ const _: () = {
                 Real::Zero => Length::Definite(0),
                 Real::Infinity | Real::NegInfinity | Real::NaN => Length::Definite(1),
                 Real::Binary { .. } => {
-                    let mut sink = io::sink();
+                    let mut sink = IoWriter::new(std::io::sink());
                     let n = self.ber_write_content(&mut sink).unwrap_or(0);
                     Length::Definite(n)
                 }
             }
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             match self {
                 Real::Zero => Ok(0),
                 Real::Infinity => {
@@ -471,6 +470,7 @@ const _: () = {
     }
 
     impl_toder_from_tober!(TY Real);
+    impl_tocer_from_toder!(TY Real);
 };
 
 impl From<f32> for Real {
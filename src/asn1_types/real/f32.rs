@@ -1,8 +1,8 @@
 use nom::IResult;
 
 use crate::{
-    impl_tryfrom_any, Any, BerError, BerParser, CheckDerConstraints, DerAutoDerive, DerParser,
-    Header, Input, Real, Result, Tag, Tagged,
+    impl_cerparser_from_derparser, impl_tryfrom_any, Any, BerError, BerParser, CheckDerConstraints,
+    DerAutoDerive, DerParser, Header, Input, Real, Result, Tag, Tagged,
 };
 
 impl_tryfrom_any!(f32);
@@ -33,6 +33,8 @@ impl<'i> DerParser<'i> for f32 {
     }
 }
 
+impl_cerparser_from_derparser!(TY f32);
+
 impl CheckDerConstraints for f32 {
     fn check_constraints(any: &Any) -> Result<()> {
         any.header.assert_primitive()?;
@@ -49,9 +51,10 @@ impl Tagged for f32 {
 
 #[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
-    use crate::{impl_toder_from_tober, Class, Length, Primitive, SerializeResult, ToBer};
+    use crate::{
+        impl_tocer_from_toder, impl_toder_from_tober, Class, Length, Primitive, SerializeResult,
+        ToBer, Writer,
+    };
 
     impl ToBer for f32 {
         type Encoder = Primitive<{ Tag::RealType.0 }>;
@@ -61,7 +64,7 @@ const _: () = {
             r.ber_content_len()
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             let r = Real::from(*self);
             r.ber_write_content(target)
         }
@@ -72,4 +75,5 @@ const _: () = {
     }
 
     impl_toder_from_tober!(TY f32);
+    impl_tocer_from_toder!(TY f32);
 };
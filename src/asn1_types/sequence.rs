@@ -459,10 +459,7 @@ impl Tagged for Sequence<'_> {
     const TAG: Tag = Tag::Sequence;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for Sequence<'_> {
         type Encoder = Constructed;
 
@@ -470,7 +467,7 @@ const _: () = {
             Length::Definite(self.content.len())
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             target.write_all(&self.content)?;
             Ok(self.content.len())
         }
@@ -483,7 +480,6 @@ const _: () = {
     impl_toder_from_tober!(LFT 'a, Sequence<'a>);
 };
 
-#[cfg(feature = "std")]
 impl Sequence<'_> {
     /// Attempt to create a `Sequence` from an iterator over serializable objects (to DER)
     ///
@@ -91,13 +91,10 @@ impl<'a> DerParser<'a> for AnySequence<'a> {
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     use crate::{
         ber_length_constructed_items, der_length_constructed_items, Class, Constructed, Length,
-        SerializeResult, ToBer, ToDer,
+        SerializeResult, ToBer, ToDer, Writer,
     };
 
     impl ToBer for AnySequence<'_> {
@@ -107,7 +104,7 @@ const _: () = {
             ber_length_constructed_items(self.iter())
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.iter().try_fold(0, |acc, t| {
                 let sz = t.ber_encode(target)?;
                 Ok(acc + sz)
@@ -126,7 +123,7 @@ const _: () = {
             der_length_constructed_items(self.iter())
         }
 
-        fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.iter().try_fold(0, |acc, t| {
                 let sz = t.der_encode(target)?;
                 Ok(acc + sz)
@@ -2,7 +2,7 @@ use alloc::vec::Vec;
 use nom::error::ParseError;
 use nom::Err;
 
-use crate::{BerError, BerParser, DerParser, InnerError, Input, Tag, Tagged};
+use crate::{BerError, BerParser, DerParser, ErrorContext, InnerError, Input, Tag, Tagged};
 
 use core::convert::TryFrom;
 use core::fmt::Display;
@@ -15,7 +15,7 @@ impl<T, const N: usize> Tagged for [T; N] {
 impl<'i, T, E, const N: usize> BerParser<'i> for [T; N]
 where
     T: BerParser<'i, Error = E>,
-    E: Display + ParseError<Input<'i>> + From<BerError<Input<'i>>>,
+    E: Display + ParseError<Input<'i>> + From<BerError<Input<'i>>> + ErrorContext,
 {
     type Error = E;
 
@@ -33,7 +33,7 @@ where
 impl<'i, T, E, const N: usize> DerParser<'i> for [T; N]
 where
     T: DerParser<'i, Error = E>,
-    E: Display + ParseError<Input<'i>> + From<BerError<Input<'i>>>,
+    E: Display + ParseError<Input<'i>> + From<BerError<Input<'i>>> + ErrorContext,
 {
     type Error = E;
 
@@ -48,13 +48,10 @@ where
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     use crate::{
         ber_length_constructed_items, der_length_constructed_items, Class, Constructed, Length,
-        SerializeResult, ToBer, ToDer,
+        LengthCache, ReverseWriter, SerializeResult, ToBer, ToDer, Writer,
     };
 
     impl<T, const N: usize> ToBer for [T; N]
@@ -67,7 +64,7 @@ const _: () = {
             ber_length_constructed_items(self.iter())
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.iter().try_fold(0, |acc, t| {
                 let sz = t.ber_encode(target)?;
                 Ok(acc + sz)
@@ -89,13 +86,36 @@ const _: () = {
             der_length_constructed_items(self.iter())
         }
 
-        fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.iter().try_fold(0, |acc, t| {
                 let sz = t.der_encode(target)?;
                 Ok(acc + sz)
             })
         }
 
+        fn der_content_len_cached(&self, cache: &mut LengthCache) -> Length {
+            self.iter().map(|t| t.der_total_len_cached(cache)).sum()
+        }
+
+        fn der_write_content_cached<W: Writer>(
+            &self,
+            cache: &mut LengthCache,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            self.iter().try_fold(0, |acc, t| {
+                let sz = t.der_encode_cached(cache, target)?;
+                Ok(acc + sz)
+            })
+        }
+
+        fn der_write_content_reverse(&self, target: &mut ReverseWriter) -> SerializeResult<usize> {
+            // items are written back-to-front
+            self.iter().rev().try_fold(0, |acc, t| {
+                let sz = t.der_encode_reverse(target)?;
+                Ok(acc + sz)
+            })
+        }
+
         fn der_tag_info(&self) -> (Class, bool, Tag) {
             (Self::CLASS, true, Self::TAG)
         }
@@ -117,7 +117,7 @@ where
 impl<'i, T> BerParser<'i> for SequenceOf<T>
 where
     T: BerParser<'i>,
-    <T as BerParser<'i>>::Error: From<BerError<Input<'i>>>,
+    <T as BerParser<'i>>::Error: From<BerError<Input<'i>>> + ErrorContext,
 {
     type Error = <T as BerParser<'i>>::Error;
 
@@ -135,7 +135,7 @@ where
 impl<'i, T> DerParser<'i> for SequenceOf<T>
 where
     T: DerParser<'i>,
-    <T as DerParser<'i>>::Error: From<BerError<Input<'i>>>,
+    <T as DerParser<'i>>::Error: From<BerError<Input<'i>>> + ErrorContext,
 {
     type Error = <T as DerParser<'i>>::Error;
 
@@ -150,6 +150,23 @@ where
     }
 }
 
+impl<'i, T> CerParser<'i> for SequenceOf<T>
+where
+    T: CerParser<'i>,
+    <T as CerParser<'i>>::Error: From<BerError<Input<'i>>> + ErrorContext,
+{
+    type Error = <T as CerParser<'i>>::Error;
+
+    fn from_cer_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        let (rem, items) = <Vec<T>>::from_cer_content(header, input)?;
+
+        Ok((rem, SequenceOf::new(items)))
+    }
+}
+
 impl<T> CheckDerConstraints for SequenceOf<T>
 where
     T: CheckDerConstraints,
@@ -200,10 +217,7 @@ impl<T> Tagged for SequenceOf<T> {
     const TAG: Tag = Tag::Sequence;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl<T> ToBer for SequenceOf<T>
     where
         T: ToBer + DynTagged,
@@ -214,7 +228,7 @@ const _: () = {
             self.items.ber_content_len()
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.items.ber_write_content(target)
         }
 
@@ -233,14 +247,49 @@ const _: () = {
             self.items.der_content_len()
         }
 
-        fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.items.der_write_content(target)
         }
 
+        fn der_content_len_cached(&self, cache: &mut LengthCache) -> Length {
+            self.items.der_content_len_cached(cache)
+        }
+
+        fn der_write_content_cached<W: Writer>(
+            &self,
+            cache: &mut LengthCache,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            self.items.der_write_content_cached(cache, target)
+        }
+
+        fn der_write_content_reverse(&self, target: &mut ReverseWriter) -> SerializeResult<usize> {
+            self.items.der_write_content_reverse(target)
+        }
+
         fn der_tag_info(&self) -> (Class, bool, Tag) {
             (Self::CLASS, true, Self::TAG)
         }
     }
+
+    impl<T> ToCer for SequenceOf<T>
+    where
+        T: ToCer + DynTagged,
+    {
+        type Encoder = ConstructedIndefinite;
+
+        fn cer_content_len(&self) -> Length {
+            self.items.cer_content_len()
+        }
+
+        fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            self.items.cer_write_content(target)
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            (Self::CLASS, true, Self::TAG)
+        }
+    }
 };
 
 #[cfg(test)]
@@ -63,7 +63,7 @@ where
 impl<'i, T> BerParser<'i> for Vec<T>
 where
     T: BerParser<'i>,
-    <T as BerParser<'i>>::Error: From<BerError<Input<'i>>>,
+    <T as BerParser<'i>>::Error: From<BerError<Input<'i>>> + ErrorContext,
 {
     type Error = <T as BerParser<'i>>::Error;
 
@@ -90,7 +90,7 @@ where
 impl<'i, T> DerParser<'i> for Vec<T>
 where
     T: DerParser<'i>,
-    <T as DerParser<'i>>::Error: From<BerError<Input<'i>>>,
+    <T as DerParser<'i>>::Error: From<BerError<Input<'i>>> + ErrorContext,
 {
     type Error = <T as DerParser<'i>>::Error;
 
@@ -114,6 +114,30 @@ where
     }
 }
 
+impl<'i, T> CerParser<'i> for Vec<T>
+where
+    T: CerParser<'i>,
+    <T as CerParser<'i>>::Error: From<BerError<Input<'i>>> + ErrorContext,
+{
+    type Error = <T as CerParser<'i>>::Error;
+
+    fn from_cer_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        // Encoding shall be constructed (X.690: 8.10.1)
+        header
+            .assert_constructed_input(&input)
+            .map_err(|e| Err::Error(e.into()))?;
+
+        if input.is_empty() {
+            return Ok((input, Vec::new()));
+        }
+
+        AnyIterator::<CerMode>::new(input).try_parse_collect::<Vec<_>, T>()
+    }
+}
+
 impl<T> CheckDerConstraints for Vec<T>
 where
     T: CheckDerConstraints,
@@ -176,10 +200,7 @@ where
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     // NOTE: we need T::DynTagged (T can be a CHOICE)
     impl<T> ToBer for Vec<T>
     where
@@ -193,7 +214,7 @@ const _: () = {
             ber_length_constructed_items(self.iter())
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.iter().try_fold(0, |acc, t| {
                 let sz = t.ber_encode(target)?;
                 Ok(acc + sz)
@@ -217,15 +238,62 @@ const _: () = {
             der_length_constructed_items(self.iter())
         }
 
-        fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.iter().try_fold(0, |acc, t| {
                 let sz = t.der_encode(target)?;
                 Ok(acc + sz)
             })
         }
 
+        fn der_content_len_cached(&self, cache: &mut LengthCache) -> Length {
+            self.iter().map(|t| t.der_total_len_cached(cache)).sum()
+        }
+
+        fn der_write_content_cached<W: Writer>(
+            &self,
+            cache: &mut LengthCache,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            self.iter().try_fold(0, |acc, t| {
+                let sz = t.der_encode_cached(cache, target)?;
+                Ok(acc + sz)
+            })
+        }
+
+        fn der_write_content_reverse(&self, target: &mut ReverseWriter) -> SerializeResult<usize> {
+            // items are written back-to-front
+            self.iter().rev().try_fold(0, |acc, t| {
+                let sz = t.der_encode_reverse(target)?;
+                Ok(acc + sz)
+            })
+        }
+
         fn der_tag_info(&self) -> (Class, bool, Tag) {
             (Self::CLASS, true, Self::TAG)
         }
     }
+
+    impl<T> ToCer for Vec<T>
+    where
+        T: ToCer,
+        T: DynTagged,
+    {
+        type Encoder = ConstructedIndefinite;
+
+        fn cer_content_len(&self) -> Length {
+            Length::Indefinite
+        }
+
+        fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            let sz = self.iter().try_fold(0, |acc, t| {
+                let sz = t.cer_encode(target)?;
+                Ok::<_, SerializeError>(acc + sz)
+            })?;
+            Ok(sz + cer_write_eoc(target)?)
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            (Self::CLASS, true, Self::TAG)
+        }
+    }
 };
@@ -7,12 +7,14 @@ use nom::{Input as _, Parser};
 
 mod any_set;
 mod btreeset;
+mod components;
 mod hashset;
 mod iterator;
 mod set_of;
 
 #[cfg(feature = "std")]
 pub use any_set::*;
+pub use components::*;
 pub use iterator::*;
 pub use set_of::*;
 
@@ -440,10 +442,7 @@ impl Tagged for Set<'_> {
     const TAG: Tag = Tag::Set;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for Set<'_> {
         type Encoder = Constructed;
 
@@ -451,7 +450,7 @@ const _: () = {
             Length::Definite(self.content.len())
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             target.write_all(&self.content)?;
             Ok(self.content.len())
         }
@@ -464,7 +463,6 @@ const _: () = {
     impl_toder_from_tober!(LFT 'a, Set<'a>);
 };
 
-#[cfg(feature = "std")]
 impl Set<'_> {
     /// Attempt to create a `Set` from an iterator over serializable objects (to DER)
     ///
@@ -96,11 +96,8 @@ impl<'a, S: BuildHasher + Default> DerParser<'a> for AnySet<'a, S> {
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
-    use crate::{Class, Constructed, Length, SerializeResult, ToBer, ToDer};
+    use crate::{Class, Constructed, Length, SerializeResult, ToBer, ToDer, Writer};
 
     impl<S: BuildHasher> ToBer for AnySet<'_, S> {
         type Encoder = Constructed;
@@ -109,7 +106,7 @@ const _: () = {
             self.items.ber_content_len()
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.iter().try_fold(0, |acc, t| {
                 let sz = t.ber_encode(target)?;
                 Ok(acc + sz)
@@ -128,7 +125,7 @@ const _: () = {
             self.items.der_content_len()
         }
 
-        fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.iter().try_fold(0, |acc, t| {
                 let sz = t.der_encode(target)?;
                 Ok(acc + sz)
@@ -1,5 +1,7 @@
 use crate::*;
 use alloc::collections::BTreeSet;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 use core::{convert::TryFrom, fmt::Debug};
 
 use self::debug::{trace, trace_generic};
@@ -23,8 +25,8 @@ where
             |any| {
                 any.tag().assert_eq(Self::TAG)?;
                 any.header.assert_constructed()?;
-                let items = SetIterator::<T, BerMode>::new(any.data.as_bytes2())
-                    .collect::<Result<BTreeSet<T>>>()?;
+                let items =
+                    parse_set_of_items::<BerMode, _, Error, BTreeSet<T>>(any.data, T::from_ber)?;
                 Ok(items)
             },
             any,
@@ -35,6 +37,7 @@ where
 impl<'a, T> BerParser<'a> for BTreeSet<T>
 where
     T: BerParser<'a>,
+    <T as BerParser<'a>>::Error: ErrorContext,
     T: Ord,
 {
     type Error = <T as BerParser<'a>>::Error;
@@ -55,6 +58,7 @@ where
 impl<'a, T> DerParser<'a> for BTreeSet<T>
 where
     T: DerParser<'a>,
+    <T as DerParser<'a>>::Error: ErrorContext,
     T: Ord,
 {
     type Error = <T as DerParser<'a>>::Error;
@@ -68,7 +72,10 @@ where
             .assert_constructed_input(&input)
             .map_err(|e| Err::Error(e.into()))?;
 
-        AnyIterator::<DerMode>::new(input).try_parse_collect::<Self, T>()
+        // Components shall be sorted (X.690: 11.6)
+        let (rem, items) = parse_sorted_set_of::<DerMode, _, _>(input, T::from_der_content)?;
+
+        Ok((rem, items.into_iter().collect()))
     }
 }
 
@@ -79,11 +86,9 @@ where
     fn check_constraints(any: &Any) -> Result<()> {
         any.tag().assert_eq(Self::TAG)?;
         any.header.assert_constructed()?;
-        for item in SetIterator::<Any, DerMode>::new(any.data.as_bytes2()) {
-            let item = item?;
-            T::check_constraints(&item)?;
-        }
-        Ok(())
+        check_set_of_items::<T>(any.data.clone())?;
+        // Components shall be sorted (X.690: 11.6)
+        check_set_of_sorted(any.data.clone())
     }
 }
 
@@ -107,8 +112,8 @@ where
                 any.header
                     .assert_constructed()
                     .map_err(|e| Err::Error(e.into()))?;
-                let items = SetIterator::<T, DerMode, E>::new(any.data.as_bytes2())
-                    .collect::<Result<BTreeSet<T>, E>>()
+                check_set_of_sorted(any.data.clone()).map_err(|e| Err::Error(e.into()))?;
+                let items = parse_set_of_items::<DerMode, _, E, BTreeSet<T>>(any.data, T::from_der)
                     .map_err(Err::Error)?;
                 Ok((rem, items))
             },
@@ -117,10 +122,7 @@ where
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl<T> ToBer for BTreeSet<T>
     where
         T: ToBer + DynTagged,
@@ -131,7 +133,7 @@ const _: () = {
             ber_length_constructed_items(self.iter())
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.iter().try_fold(0, |acc, t| {
                 let sz = t.ber_encode(target)?;
                 Ok(acc + sz)
@@ -153,11 +155,26 @@ const _: () = {
             der_length_constructed_items(self.iter())
         }
 
-        fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
-            self.iter().try_fold(0, |acc, t| {
-                let sz = t.der_encode(target)?;
-                Ok(acc + sz)
-            })
+        fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            // Components shall be sorted (X.690: 11.6)
+            let encodings = self
+                .iter()
+                .map(|t| t.to_der_vec())
+                .collect::<SerializeResult<Vec<_>>>()?;
+            write_sorted_set_of(encodings, target)
+        }
+
+        fn der_content_len_cached(&self, cache: &mut LengthCache) -> Length {
+            self.iter().map(|t| t.der_total_len_cached(cache)).sum()
+        }
+
+        fn der_write_content_cached<W: Writer>(
+            &self,
+            cache: &mut LengthCache,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            // Components shall be sorted (X.690: 11.6)
+            write_sorted_set_of_cached(self.iter(), cache, target)
         }
 
         fn der_tag_info(&self) -> (Class, bool, Tag) {
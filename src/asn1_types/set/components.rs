@@ -0,0 +1,101 @@
+use crate::*;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Return the canonical order of a tag (X.680: 8.6)
+///
+/// Tags are ordered by class (universal, application, context-specific, private), then by number.
+fn canonical_tag_order(class: Class, tag: Tag) -> (u8, u32) {
+    (class as u8, tag.0)
+}
+
+/// Check that the component with `header` is not before the `previous` component of a `SET`,
+/// in canonical tag order (X.690: 9.3 and 10.3)
+///
+/// `previous` is updated with the tag of the current component. `input` is used to build the
+/// error, if any (`DerConstraint::SetUnsorted`).
+///
+/// Note: used in custom derive
+pub fn check_set_component_order<'a>(
+    previous: &mut Option<(Class, Tag)>,
+    header: &Header,
+    input: &Input<'a>,
+) -> Result<(), BerError<Input<'a>>> {
+    let current = (header.class(), header.tag());
+    if let Some((class, tag)) = *previous {
+        if canonical_tag_order(class, tag) > canonical_tag_order(current.0, current.1) {
+            return Err(BerError::new(
+                input.clone(),
+                InnerError::DerConstraintFailed(DerConstraint::SetUnsorted),
+            ));
+        }
+    }
+    *previous = Some(current);
+    Ok(())
+}
+
+/// Check that the components of a `SET` with content `data` are sorted in canonical tag order
+/// (X.690: 10.3)
+///
+/// Note: used in custom derive
+pub fn check_set_components_sorted(data: &[u8]) -> Result<()> {
+    let mut previous = None;
+    let mut rem = data;
+    while !rem.is_empty() {
+        let (i, any) = Any::from_der(rem)?;
+        check_set_component_order(&mut previous, &any.header, &Input::from(rem))?;
+        rem = i;
+    }
+    Ok(())
+}
+
+/// Write encodings of `SET` components, sorted in canonical tag order (X.690: 9.3 and 10.3)
+///
+/// Empty encodings (absent `OPTIONAL` components) are skipped. Components with the same tag keep
+/// their relative order.
+///
+/// Returns the number of bytes written
+///
+/// Note: used in custom derive
+pub fn write_sorted_set_components<W: Writer>(
+    encodings: Vec<Vec<u8>>,
+    target: &mut W,
+) -> SerializeResult<usize> {
+    let mut components = Vec::with_capacity(encodings.len());
+    for v in encodings.into_iter().filter(|v| !v.is_empty()) {
+        let (_, header) = Header::from_ber(&v).map_err(Error::from)?;
+        components.push((canonical_tag_order(header.class(), header.tag()), v));
+    }
+    components.sort_by_key(|(order, _)| *order);
+    components.iter().try_fold(0, |acc, (_, v)| {
+        target.write_all(v)?;
+        Ok(acc + v.len())
+    })
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn set_components_sorted() {
+        // context-specific [1], universal INTEGER, context-specific [0], absent component
+        let encodings = vec![
+            hex!("a1 03 020101").to_vec(),
+            hex!("02 01 02").to_vec(),
+            hex!("80 01 03").to_vec(),
+            Vec::new(),
+        ];
+        let mut v = Vec::new();
+        let sz = write_sorted_set_components(encodings, &mut v).expect("serialization failed");
+        assert_eq!(sz, 11);
+        assert_eq!(&v, &hex!("020102 800103 a103020101"));
+        check_set_components_sorted(&v).expect("components are sorted");
+
+        let e = check_set_components_sorted(&hex!("800103 020102")).expect_err("not sorted");
+        assert_eq!(e, Error::DerConstraintFailed(DerConstraint::SetUnsorted));
+        // same tags are accepted (order of values is not checked)
+        check_set_components_sorted(&hex!("020102 020101")).expect("same tags");
+    }
+}
@@ -1,13 +1,11 @@
 #![cfg(feature = "std")]
+use self::debug::{trace, trace_generic};
 use crate::*;
 use core::fmt::Debug;
 use core::hash::BuildHasher;
 use std::collections::HashSet;
 use std::convert::TryFrom;
 use std::hash::Hash;
-use std::io::Write;
-
-use self::debug::{trace, trace_generic};
 
 impl<T, S> Tagged for HashSet<T, S> {
     const CONSTRUCTED: bool = true;
@@ -25,8 +23,7 @@ where
     fn try_from(any: Any<'a>) -> Result<Self> {
         any.tag().assert_eq(Self::TAG)?;
         any.header.assert_constructed()?;
-        let items = SetIterator::<T, BerMode>::new(any.data.as_bytes2())
-            .collect::<Result<HashSet<T, S>>>()?;
+        let items = parse_set_of_items::<BerMode, _, Error, HashSet<T, S>>(any.data, T::from_ber)?;
         Ok(items)
     }
 }
@@ -34,6 +31,7 @@ where
 impl<'a, T, S> BerParser<'a> for HashSet<T, S>
 where
     T: BerParser<'a>,
+    <T as BerParser<'a>>::Error: ErrorContext,
     T: Hash + Eq,
     S: BuildHasher + Default,
 {
@@ -55,6 +53,7 @@ where
 impl<'a, T, S> DerParser<'a> for HashSet<T, S>
 where
     T: DerParser<'a>,
+    <T as DerParser<'a>>::Error: ErrorContext,
     T: Hash + Eq,
     S: BuildHasher + Default,
 {
@@ -69,7 +68,10 @@ where
             .assert_constructed_input(&input)
             .map_err(|e| Err::Error(e.into()))?;
 
-        AnyIterator::<DerMode>::new(input).try_parse_collect::<Self, T>()
+        // Components shall be sorted (X.690: 11.6)
+        let (rem, items) = parse_sorted_set_of::<DerMode, _, _>(input, T::from_der_content)?;
+
+        Ok((rem, items.into_iter().collect()))
     }
 }
 
@@ -80,11 +82,9 @@ where
     fn check_constraints(any: &Any) -> Result<()> {
         any.tag().assert_eq(Self::TAG)?;
         any.header.assert_constructed()?;
-        for item in SetIterator::<Any, DerMode>::new(any.data.as_bytes2()) {
-            let item = item?;
-            T::check_constraints(&item)?;
-        }
-        Ok(())
+        check_set_of_items::<T>(any.data.clone())?;
+        // Components shall be sorted (X.690: 11.6)
+        check_set_of_sorted(any.data.clone())
     }
 }
 
@@ -109,9 +109,10 @@ where
                 any.header
                     .assert_constructed()
                     .map_err(|e| Err::Error(e.into()))?;
-                let items = SetIterator::<T, DerMode, E>::new(any.data.as_bytes2())
-                    .collect::<Result<HashSet<T, S>, E>>()
-                    .map_err(Err::Error)?;
+                check_set_of_sorted(any.data.clone()).map_err(|e| Err::Error(e.into()))?;
+                let items =
+                    parse_set_of_items::<DerMode, _, E, HashSet<T, S>>(any.data, T::from_der)
+                        .map_err(Err::Error)?;
                 Ok((rem, items))
             },
             bytes,
@@ -129,7 +130,7 @@ where
         ber_length_constructed_items(self.iter())
     }
 
-    fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+    fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
         self.iter().try_fold(0, |acc, t| {
             let sz = t.ber_encode(target)?;
             Ok(acc + sz)
@@ -151,11 +152,26 @@ where
         der_length_constructed_items(self.iter())
     }
 
-    fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
-        self.iter().try_fold(0, |acc, t| {
-            let sz = t.der_encode(target)?;
-            Ok(acc + sz)
-        })
+    fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+        // Components shall be sorted (X.690: 11.6)
+        let encodings = self
+            .iter()
+            .map(|t| t.to_der_vec())
+            .collect::<SerializeResult<Vec<_>>>()?;
+        write_sorted_set_of(encodings, target)
+    }
+
+    fn der_content_len_cached(&self, cache: &mut LengthCache) -> Length {
+        self.iter().map(|t| t.der_total_len_cached(cache)).sum()
+    }
+
+    fn der_write_content_cached<W: Writer>(
+        &self,
+        cache: &mut LengthCache,
+        target: &mut W,
+    ) -> SerializeResult<usize> {
+        // Components shall be sorted (X.690: 11.6)
+        write_sorted_set_of_cached(self.iter(), cache, target)
     }
 
     fn der_tag_info(&self) -> (Class, bool, Tag) {
@@ -192,6 +208,7 @@ mod tests {
         assert_eq!(h.der_content_len(), Length::Definite(6));
         let v = h.to_der_vec().expect("could not serialize");
         let (_, h2) = SetOf::<u32>::from_der(&v).unwrap();
-        assert!(h.iter().eq(h2.iter()));
+        // components are sorted when encoding, so iteration order may differ
+        assert_eq!(h, h2.iter().copied().collect());
     }
 }
@@ -1,10 +1,12 @@
 use crate::*;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
+use core::cmp::Ordering;
 use core::convert::TryFrom;
 use core::fmt::{Debug, Display};
 use core::iter::FromIterator;
 use core::ops::{Deref, DerefMut};
+use nom::Input as _;
 
 use self::debug::{trace, trace_generic};
 
@@ -108,8 +110,7 @@ where
         if !any.header.is_constructed() {
             return Err(Error::ConstructExpected);
         }
-        let items =
-            SetIterator::<T, BerMode>::new(any.data.as_bytes2()).collect::<Result<Vec<T>>>()?;
+        let items = parse_set_of_items::<BerMode, _, Error, Vec<T>>(any.data, T::from_ber)?;
         Ok(SetOf::new(items))
     }
 }
@@ -117,6 +118,7 @@ where
 impl<'a, T> BerParser<'a> for SetOf<T>
 where
     T: BerParser<'a>,
+    <T as BerParser<'a>>::Error: ErrorContext,
 {
     type Error = <T as BerParser<'a>>::Error;
 
@@ -138,6 +140,7 @@ where
 impl<'a, T> DerParser<'a> for SetOf<T>
 where
     T: DerParser<'a>,
+    <T as DerParser<'a>>::Error: ErrorContext,
 {
     type Error = <T as DerParser<'a>>::Error;
 
@@ -150,7 +153,31 @@ where
             .assert_constructed_input(&input)
             .map_err(|e| Err::Error(e.into()))?;
 
-        let (rem, items) = AnyIterator::<DerMode>::new(input).try_parse_collect::<Vec<_>, T>()?;
+        // Components shall be sorted (X.690: 11.6)
+        let (rem, items) = parse_sorted_set_of::<DerMode, _, _>(input, T::from_der_content)?;
+
+        Ok((rem, SetOf::new(items)))
+    }
+}
+
+impl<'a, T> CerParser<'a> for SetOf<T>
+where
+    T: CerParser<'a>,
+    <T as CerParser<'a>>::Error: ErrorContext,
+{
+    type Error = <T as CerParser<'a>>::Error;
+
+    fn from_cer_content(
+        header: &'_ Header<'a>,
+        input: Input<'a>,
+    ) -> IResult<Input<'a>, Self, Self::Error> {
+        // Encoding shall be constructed (X.690: 8.12.1)
+        header
+            .assert_constructed_input(&input)
+            .map_err(|e| Err::Error(e.into()))?;
+
+        // Components shall be sorted (X.690: 11.6)
+        let (rem, items) = parse_sorted_set_of::<CerMode, _, _>(input, T::from_cer_content)?;
 
         Ok((rem, SetOf::new(items)))
     }
@@ -163,11 +190,9 @@ where
     fn check_constraints(any: &Any) -> Result<()> {
         any.tag().assert_eq(Self::TAG)?;
         any.header.assert_constructed()?;
-        for item in SetIterator::<Any, DerMode>::new(any.data.as_bytes2()) {
-            let item = item?;
-            T::check_constraints(&item)?;
-        }
-        Ok(())
+        check_set_of_items::<T>(any.data.clone())?;
+        // Components shall be sorted (X.690: 11.6)
+        check_set_of_sorted(any.data.clone())
     }
 }
 
@@ -187,8 +212,8 @@ where
                 any.header
                     .assert_tag(Self::TAG)
                     .map_err(|e| Err::Error(e.into()))?;
-                let items = SetIterator::<T, DerMode, E>::new(any.data.as_bytes2())
-                    .collect::<Result<Vec<T>, E>>()
+                check_set_of_sorted(any.data.clone()).map_err(|e| Err::Error(e.into()))?;
+                let items = parse_set_of_items::<DerMode, _, E, Vec<T>>(any.data, T::from_der)
                     .map_err(Err::Error)?;
                 Ok((rem, SetOf::new(items)))
             },
@@ -201,10 +226,171 @@ impl<T> Tagged for SetOf<T> {
     const TAG: Tag = Tag::Set;
 }
 
-#[cfg(feature = "std")]
-const _: () = {
-    use std::io::Write;
+/// Compare encodings of `SET OF` components, as specified in X.690 section 11.6
+///
+/// Encodings are compared as octet strings, with the shorter components being padded at
+/// their trailing end with 0-octets.
+pub(crate) fn cmp_set_of_encodings(a: &[u8], b: &[u8]) -> Ordering {
+    let common = a.len().min(b.len());
+    match a[..common].cmp(&b[..common]) {
+        Ordering::Equal => (),
+        o => return o,
+    }
+    // compare remaining bytes to padding
+    if a[common..].iter().any(|&x| x != 0) {
+        Ordering::Greater
+    } else if b[common..].iter().any(|&x| x != 0) {
+        Ordering::Less
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// Parse components of a `SET OF`, checking that encodings are sorted (X.690: 11.6)
+///
+/// `f` is used to parse the content of each component.
+pub(crate) fn parse_sorted_set_of<'a, Mode, T, E>(
+    input: Input<'a>,
+    mut f: impl FnMut(&'_ Header<'a>, Input<'a>) -> IResult<Input<'a>, T, E>,
+) -> IResult<Input<'a>, Vec<T>, E>
+where
+    Mode: ASN1Mode,
+    AnyIterator<'a, Mode>:
+        Iterator<Item = core::result::Result<(Input<'a>, Any<'a>), BerError<Input<'a>>>>,
+    T: DynTagged,
+    E: From<BerError<Input<'a>>> + ErrorContext,
+{
+    let mut items = Vec::new();
+    let mut previous: Option<Input<'a>> = None;
+    for (index, res) in AnyIterator::<Mode>::new(input.clone()).enumerate() {
+        let parse_item = || {
+            let (span, obj) = res.map_err(|e| Err::Error(e.into()))?;
+            if let Some(prev) = &previous {
+                if cmp_set_of_encodings(prev.as_bytes2(), span.as_bytes2()) == Ordering::Greater {
+                    return Err(Err::Error(
+                        BerError::new(
+                            span,
+                            InnerError::DerConstraintFailed(DerConstraint::SetOfUnsorted),
+                        )
+                        .into(),
+                    ));
+                }
+            }
+            if !T::accept_tag(obj.tag()) {
+                return Err(Err::Error(
+                    BerError::unexpected_tag(obj.data.clone(), T::expected_tag(), obj.tag()).into(),
+                ));
+            }
+            let (_, item) = f(&obj.header, obj.data)?;
+            Ok((span, item))
+        };
+        let (span, item) = parse_item().map_err(err_at_index(index))?;
+        items.push(item);
+        previous = Some(span);
+    }
+    let (rem, _) = input.take_split(input.len());
+    Ok((rem, items))
+}
+
+/// Check that encodings of `SET OF` components in `input` are sorted (X.690: 11.6)
+///
+/// The [`Limits`] of the input are applied.
+pub(crate) fn check_set_of_sorted(input: Input) -> Result<()> {
+    let mut previous: Option<Input> = None;
+    for res in AnyIterator::<DerMode>::new(input) {
+        let (span, _) = res?;
+        if let Some(prev) = &previous {
+            if cmp_set_of_encodings(prev.as_bytes2(), span.as_bytes2()) == Ordering::Greater {
+                return Err(Error::DerConstraintFailed(DerConstraint::SetOfUnsorted));
+            }
+        }
+        previous = Some(span);
+    }
+    Ok(())
+}
+
+/// Parse the components of a `SET OF` in `input` using `parse` (for ex. `FromBer::from_ber`)
+///
+/// The [`Limits`] of the input are applied to the components: each component is read using
+/// [`AnyIterator`], then parsed from its encoding.
+pub(crate) fn parse_set_of_items<'a, Mode, T, E, B>(
+    input: Input<'a>,
+    parse: impl Fn(&'a [u8]) -> ParseResult<'a, T, E>,
+) -> core::result::Result<B, E>
+where
+    Mode: ASN1Mode,
+    AnyIterator<'a, Mode>:
+        Iterator<Item = core::result::Result<(Input<'a>, Any<'a>), BerError<Input<'a>>>>,
+    E: From<Error>,
+    B: FromIterator<T>,
+{
+    AnyIterator::<Mode>::new(input)
+        .map(|res| {
+            let (span, _) = res.map_err(|e| E::from(e.into()))?;
+            match parse(span.as_bytes2()) {
+                Ok((_, item)) => Ok(item),
+                Err(Err::Error(e)) | Err(Err::Failure(e)) => Err(e),
+                Err(Err::Incomplete(n)) => Err(Error::Incomplete(n).into()),
+            }
+        })
+        .collect()
+}
+
+/// Check the DER constraints of the components of a `SET OF` in `input`
+///
+/// The [`Limits`] of the input are applied.
+pub(crate) fn check_set_of_items<T>(input: Input) -> Result<()>
+where
+    T: CheckDerConstraints,
+{
+    for res in AnyIterator::<DerMode>::new(input) {
+        let (_, item) = res?;
+        T::check_constraints(&item)?;
+    }
+    Ok(())
+}
 
+/// Write encodings of `SET OF` components, sorted as specified in X.690 section 11.6
+///
+/// Returns the number of bytes written
+pub(crate) fn write_sorted_set_of<W: Writer>(
+    mut encodings: Vec<Vec<u8>>,
+    target: &mut W,
+) -> SerializeResult<usize> {
+    encodings.sort_by(|a, b| cmp_set_of_encodings(a, b));
+    encodings.iter().try_fold(0, |acc, v| {
+        target.write_all(v)?;
+        Ok(acc + v.len())
+    })
+}
+
+/// Encode `SET OF` components using the lengths recorded in `cache`, and write them sorted as
+/// specified in X.690 section 11.6
+///
+/// Components must be iterated in the same order as when recording lengths (see
+/// [`ToDer::der_content_len_cached`]).
+///
+/// Returns the number of bytes written
+pub(crate) fn write_sorted_set_of_cached<'a, T, W>(
+    items: impl Iterator<Item = &'a T>,
+    cache: &mut LengthCache,
+    target: &mut W,
+) -> SerializeResult<usize>
+where
+    T: ToDer + 'a,
+    W: Writer,
+{
+    let encodings = items
+        .map(|t| {
+            let mut v = Vec::new();
+            t.der_encode_cached(cache, &mut v)?;
+            Ok(v)
+        })
+        .collect::<SerializeResult<Vec<_>>>()?;
+    write_sorted_set_of(encodings, target)
+}
+
+const _: () = {
     impl<T> ToBer for SetOf<T>
     where
         T: ToBer + DynTagged,
@@ -215,7 +401,7 @@ const _: () = {
             self.items.ber_content_len()
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.items.ber_write_content(target)
         }
 
@@ -234,12 +420,57 @@ const _: () = {
             self.items.der_content_len()
         }
 
-        fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
-            self.items.der_write_content(target)
+        fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            // Components shall be sorted (X.690: 11.6)
+            let encodings = self
+                .items
+                .iter()
+                .map(|t| t.to_der_vec())
+                .collect::<SerializeResult<Vec<_>>>()?;
+            write_sorted_set_of(encodings, target)
+        }
+
+        fn der_content_len_cached(&self, cache: &mut LengthCache) -> Length {
+            self.items.der_content_len_cached(cache)
+        }
+
+        fn der_write_content_cached<W: Writer>(
+            &self,
+            cache: &mut LengthCache,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            // Components shall be sorted (X.690: 11.6)
+            write_sorted_set_of_cached(self.items.iter(), cache, target)
         }
 
         fn der_tag_info(&self) -> (Class, bool, Tag) {
             (Self::CLASS, true, Self::TAG)
         }
     }
+
+    impl<T> ToCer for SetOf<T>
+    where
+        T: ToCer + DynTagged,
+    {
+        type Encoder = ConstructedIndefinite;
+
+        fn cer_content_len(&self) -> Length {
+            Length::Indefinite
+        }
+
+        fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            // Components shall be sorted (X.690: 11.6)
+            let encodings = self
+                .items
+                .iter()
+                .map(|t| t.to_cer_vec())
+                .collect::<SerializeResult<Vec<_>>>()?;
+            let sz = write_sorted_set_of(encodings, target)?;
+            Ok(sz + cer_write_eoc(target)?)
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            (Self::CLASS, true, Self::TAG)
+        }
+    }
 };
@@ -160,6 +160,34 @@ macro_rules! asn1_string {
             }
         }
 
+        impl<'i> $crate::CerParser<'i> for $name<'i> {
+            type Error = $crate::BerError<$crate::Input<'i>>;
+
+            fn from_cer_content(header: &'_ $crate::Header<'i>, input: $crate::Input<'i>) -> $crate::nom::IResult<$crate::Input<'i>, Self, Self::Error> {
+                use alloc::borrow::Cow;
+                // Encoding shall be primitive, or constructed with segments of 1000 octets (X.690: 9.2)
+                let (rem, s) = $crate::parse_cer_segmented::<$crate::OctetString>(header, input)?;
+                let data = s.into_cow();
+
+                <$name>::test_valid_charset(data.as_ref()).map_err(|e|
+                    $crate::BerError::nom_err_input(&rem, e.into()))?;
+
+                let data = match data {
+                    Cow::Borrowed(b) => {
+                        let s = alloc::str::from_utf8(b).map_err(|e|
+                            $crate::BerError::nom_err_input(&rem, e.into()))?;
+                        Cow::Borrowed(s)
+                    }
+                    Cow::Owned(v) => {
+                        let s = alloc::string::String::from_utf8(v).map_err(|e|
+                            $crate::BerError::nom_err_input(&rem, e.into()))?;
+                        Cow::Owned(s)
+                    }
+                };
+                Ok((rem, $name { data }))
+            }
+        }
+
         impl<'a> $crate::CheckDerConstraints for $name<'a> {
             fn check_constraints(any: &$crate::Any) -> $crate::Result<()> {
                 any.header.assert_primitive()?;
@@ -173,10 +201,7 @@ macro_rules! asn1_string {
             const TAG: $crate::Tag = $crate::Tag::$name;
         }
 
-        #[cfg(feature = "std")]
         const _: () = {
-            use std::io::Write;
-
             impl $crate::ToBer for $name<'_> {
                 type Encoder = $crate::Primitive< { $crate::Tag::$name.0 }>;
 
@@ -184,7 +209,7 @@ macro_rules! asn1_string {
                     $crate::Length::Definite(self.data.len())
                 }
 
-                fn ber_write_content<W: Write>(&self, target: &mut W) -> $crate::SerializeResult<usize> {
+                fn ber_write_content<W: $crate::Writer>(&self, target: &mut W) -> $crate::SerializeResult<usize> {
                     target.write_all(self.data.as_bytes())?;
                     Ok(self.data.len())
                 }
@@ -202,7 +227,7 @@ macro_rules! asn1_string {
                     $crate::Length::Definite(self.data.len())
                 }
 
-                fn der_write_content<W: Write>(&self, target: &mut W) -> $crate::SerializeResult<usize> {
+                fn der_write_content<W: $crate::Writer>(&self, target: &mut W) -> $crate::SerializeResult<usize> {
                     target.write_all(self.data.as_bytes())?;
                     Ok(self.data.len())
                 }
@@ -212,6 +237,23 @@ macro_rules! asn1_string {
                     (Self::CLASS, false, Self::TAG)
                 }
             }
+
+            impl $crate::ToCer for $name<'_> {
+                type Encoder = $crate::BerGenericEncoder;
+
+                fn cer_content_len(&self) -> $crate::Length {
+                    $crate::cer_string_content_len(self.data.len())
+                }
+
+                fn cer_write_content<W: $crate::Writer>(&self, target: &mut W) -> $crate::SerializeResult<usize> {
+                    $crate::cer_write_string_content(self.data.as_bytes(), target)
+                }
+
+                fn cer_tag_info(&self) -> ($crate::Class, bool, $crate::Tag) {
+                    use $crate::Tagged;
+                    (Self::CLASS, $crate::cer_string_is_segmented(self.data.len()), Self::TAG)
+                }
+            }
         };
     };
     ($name:ident) => {
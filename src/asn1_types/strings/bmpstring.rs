@@ -70,25 +70,28 @@ impl<'i> BerParser<'i> for BmpString<'i> {
             (rem, s)
         };
 
-        // read slice as big-endian UTF-16 string
-        let v = data
-            .as_ref()
-            .chunks(2)
-            .map(|s| match s {
-                [a, b] => ((*a as u16) << 8) | (*b as u16),
-                [a] => *a as u16,
-                _ => unreachable!(),
-            })
-            .collect::<Vec<_>>();
-
-        let s = String::from_utf16(&v)
-            .map_err(|_| BerError::nom_err_input(&rem, InnerError::StringInvalidCharset))?;
+        let s = decode_utf16_be(&data)
+            .ok_or_else(|| BerError::nom_err_input(&rem, InnerError::StringInvalidCharset))?;
         let data = Cow::Owned(s);
 
         Ok((rem, BmpString { data }))
     }
 }
 
+/// Read slice as big-endian UTF-16 string
+fn decode_utf16_be(data: &[u8]) -> Option<String> {
+    let v = data
+        .chunks(2)
+        .map(|s| match s {
+            [a, b] => ((*a as u16) << 8) | (*b as u16),
+            [a] => *a as u16,
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+
+    String::from_utf16(&v).ok()
+}
+
 impl<'i> DerParser<'i> for BmpString<'i> {
     type Error = BerError<Input<'i>>;
 
@@ -103,6 +106,24 @@ impl<'i> DerParser<'i> for BmpString<'i> {
     }
 }
 
+impl<'i> CerParser<'i> for BmpString<'i> {
+    type Error = BerError<Input<'i>>;
+
+    fn from_cer_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        // Encoding shall be primitive, or constructed with segments of 1000 octets (X.690: 9.2)
+        let (rem, s) = parse_cer_segmented::<OctetString>(header, input)?;
+
+        let s = decode_utf16_be(s.as_ref())
+            .ok_or_else(|| BerError::nom_err_input(&rem, InnerError::StringInvalidCharset))?;
+        let data = Cow::Owned(s);
+
+        Ok((rem, BmpString { data }))
+    }
+}
+
 impl CheckDerConstraints for BmpString<'_> {
     fn check_constraints(any: &Any) -> Result<()> {
         any.header.assert_primitive()?;
@@ -131,10 +152,7 @@ impl TestValidCharset for BmpString<'_> {
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for BmpString<'_> {
         type Encoder = Primitive<{ Tag::BmpString.0 }>;
 
@@ -144,7 +162,7 @@ const _: () = {
             Length::Definite(sz)
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             let mut v = Vec::new();
             for u in self.data.encode_utf16() {
                 v.push((u >> 8) as u8);
@@ -160,6 +178,29 @@ const _: () = {
     }
 
     impl_toder_from_tober!(LFT 'a, BmpString<'a>);
+
+    impl ToCer for BmpString<'_> {
+        type Encoder = BerGenericEncoder;
+
+        fn cer_content_len(&self) -> Length {
+            let sz = self.data.encode_utf16().count() * 2;
+            cer_string_content_len(sz)
+        }
+
+        fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            let mut v = Vec::new();
+            for u in self.data.encode_utf16() {
+                v.push((u >> 8) as u8);
+                v.push((u & 0xff) as u8);
+            }
+            cer_write_string_content(&v, target)
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            let sz = self.data.encode_utf16().count() * 2;
+            (Self::CLASS, cer_string_is_segmented(sz), Self::TAG)
+        }
+    }
 };
 
 #[cfg(test)]
@@ -59,16 +59,32 @@ impl<'i> DerParser<'i> for &'i str {
     }
 }
 
+impl<'i> CerParser<'i> for &'i str {
+    type Error = BerError<Input<'i>>;
+
+    fn from_cer_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        // Encoding shall be primitive if length is at most 1000 octets (X.690: 9.2)
+        // Longer strings use the constructed form, which is not supported for shared slices.
+        if input.len() > CER_SEGMENT_SIZE {
+            return Err(BerError::nom_err_input(
+                &input,
+                InnerError::DerConstraintFailed(DerConstraint::SegmentSize),
+            ));
+        }
+        Self::from_der_content(header, input)
+    }
+}
+
 impl DerAutoDerive for &'_ str {}
 
 impl Tagged for &'_ str {
     const TAG: Tag = Tag::Utf8String;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for &'_ str {
         type Encoder = Primitive<{ Tag::Utf8String.0 }>;
 
@@ -76,7 +92,7 @@ const _: () = {
             Length::Definite(self.len())
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             target.write_all(self.as_bytes())?;
             Ok(self.len())
         }
@@ -87,6 +103,22 @@ const _: () = {
     }
 
     impl_toder_from_tober!(LFT 'a, &'a str);
+
+    impl ToCer for &'_ str {
+        type Encoder = BerGenericEncoder;
+
+        fn cer_content_len(&self) -> Length {
+            cer_string_content_len(self.len())
+        }
+
+        fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            cer_write_string_content(self.as_bytes(), target)
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            (Self::CLASS, cer_string_is_segmented(self.len()), Self::TAG)
+        }
+    }
 };
 
 #[cfg(test)]
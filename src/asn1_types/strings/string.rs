@@ -49,16 +49,27 @@ impl<'i> DerParser<'i> for String {
     }
 }
 
+impl<'i> CerParser<'i> for String {
+    type Error = BerError<Input<'i>>;
+
+    fn from_cer_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        let (rem, obj) = Utf8String::from_cer_content(header, input)?;
+
+        let s = obj.data.into_owned();
+        Ok((rem, s))
+    }
+}
+
 impl DerAutoDerive for String {}
 
 impl Tagged for String {
     const TAG: Tag = Tag::Utf8String;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for String {
         type Encoder = Primitive<{ Tag::Utf8String.0 }>;
 
@@ -66,7 +77,7 @@ const _: () = {
             Length::Definite(self.len())
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             target.write_all(self.as_bytes())?;
             Ok(self.len())
         }
@@ -77,6 +88,22 @@ const _: () = {
     }
 
     impl_toder_from_tober!(TY String);
+
+    impl ToCer for String {
+        type Encoder = BerGenericEncoder;
+
+        fn cer_content_len(&self) -> Length {
+            cer_string_content_len(self.len())
+        }
+
+        fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            cer_write_string_content(self.as_bytes(), target)
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            (Self::CLASS, cer_string_is_segmented(self.len()), Self::TAG)
+        }
+    }
 };
 
 #[cfg(test)]
@@ -7,7 +7,6 @@ use alloc::borrow::Cow;
 use alloc::string::{String, ToString};
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
-use core::iter::FromIterator;
 use nom::Input as _;
 
 /// ASN.1 `UniversalString` type
@@ -70,37 +69,32 @@ impl<'i> BerParser<'i> for UniversalString<'i> {
             (rem, s)
         };
 
-        if data.len() % 4 != 0 {
-            return Err(BerError::nom_err_input(
-                &rem,
-                InnerError::StringInvalidCharset,
-            ));
-        }
-
-        // read slice as big-endian UCS-4 string
-        let v = data
-            .as_ref()
-            .chunks(4)
-            .map(|s| match s {
-                [a, b, c, d] => {
-                    let u32_val = ((*a as u32) << 24)
-                        | ((*b as u32) << 16)
-                        | ((*c as u32) << 8)
-                        | (*d as u32);
-                    char::from_u32(u32_val)
-                }
-                _ => unreachable!(),
-            })
-            .collect::<Option<Vec<_>>>()
+        let s = decode_ucs4_be(&data)
             .ok_or_else(|| BerError::nom_err_input(&rem, InnerError::StringInvalidCharset))?;
-
-        let s = String::from_iter(v);
         let data = Cow::Owned(s);
 
         Ok((rem, UniversalString { data }))
     }
 }
 
+/// Read slice as big-endian UCS-4 string
+fn decode_ucs4_be(data: &[u8]) -> Option<String> {
+    if data.len() % 4 != 0 {
+        return None;
+    }
+
+    data.chunks(4)
+        .map(|s| match s {
+            [a, b, c, d] => {
+                let u32_val =
+                    ((*a as u32) << 24) | ((*b as u32) << 16) | ((*c as u32) << 8) | (*d as u32);
+                char::from_u32(u32_val)
+            }
+            _ => unreachable!(),
+        })
+        .collect::<Option<String>>()
+}
+
 impl<'i> DerParser<'i> for UniversalString<'i> {
     type Error = BerError<Input<'i>>;
 
@@ -115,6 +109,24 @@ impl<'i> DerParser<'i> for UniversalString<'i> {
     }
 }
 
+impl<'i> CerParser<'i> for UniversalString<'i> {
+    type Error = BerError<Input<'i>>;
+
+    fn from_cer_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        // Encoding shall be primitive, or constructed with segments of 1000 octets (X.690: 9.2)
+        let (rem, s) = parse_cer_segmented::<OctetString>(header, input)?;
+
+        let s = decode_ucs4_be(s.as_ref())
+            .ok_or_else(|| BerError::nom_err_input(&rem, InnerError::StringInvalidCharset))?;
+        let data = Cow::Owned(s);
+
+        Ok((rem, UniversalString { data }))
+    }
+}
+
 impl CheckDerConstraints for UniversalString<'_> {
     fn check_constraints(any: &Any) -> Result<()> {
         any.header.assert_primitive()?;
@@ -128,10 +140,7 @@ impl Tagged for UniversalString<'_> {
     const TAG: Tag = Tag::UniversalString;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for UniversalString<'_> {
         type Encoder = Primitive<{ Tag::UniversalString.0 }>;
 
@@ -141,7 +150,7 @@ const _: () = {
             Length::Definite(sz)
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.data
                 .chars()
                 .try_for_each(|c| target.write_all(&(c as u32).to_be_bytes()[..]))?;
@@ -154,6 +163,30 @@ const _: () = {
     }
 
     impl_toder_from_tober!(LFT 'a, UniversalString<'a>);
+
+    impl ToCer for UniversalString<'_> {
+        type Encoder = BerGenericEncoder;
+
+        fn cer_content_len(&self) -> Length {
+            // UCS-4: 4 bytes per character
+            let sz = self.data.chars().count() * 4;
+            cer_string_content_len(sz)
+        }
+
+        fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            let v = self
+                .data
+                .chars()
+                .flat_map(|c| (c as u32).to_be_bytes())
+                .collect::<Vec<_>>();
+            cer_write_string_content(&v, target)
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            let sz = self.data.chars().count() * 4;
+            (Self::CLASS, cer_string_is_segmented(sz), Self::TAG)
+        }
+    }
 };
 
 #[cfg(test)]
@@ -131,6 +131,49 @@ where
     }
 }
 
+impl<'i, T, E, const CLASS: u8, const TAG: u32> CerParser<'i>
+    for TaggedValue<T, E, Explicit, CLASS, TAG>
+where
+    // T: Tagged,
+    T: CerParser<'i>,
+    // E: ParseError<Input<'a>> + From<BerError<Input<'a>>>,
+{
+    type Error = T::Error;
+
+    fn from_cer_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        // Tagged Explicit must be constructed (X.690 8.14.2)
+        header
+            .assert_constructed_input(&input)
+            .map_err(|e| Err::Error(e.into()))?;
+
+        // assert class
+        if header.class as u8 != CLASS {
+            // Safety: CLASS < 4
+            let class = Class::try_from(CLASS).unwrap_or(Class::Private);
+            return Err(Err::Error(
+                BerError::unexpected_class(input, Some(class), header.class).into(),
+            ));
+        }
+
+        // note: we check tag here, because the only way to have a different tag
+        // would be to be IMPLICIT, and we already know we are EXPLICIT
+        // This is an exception!
+        if !Self::accept_tag(header.tag) {
+            return Err(Err::Error(
+                BerError::unexpected_tag(input, Some(TAG.into()), header.tag).into(),
+            ));
+        }
+        // calling `parse_cer` will read a new header and parse object `T``
+        // this will also check that T::TAG is expected
+        let (rem, t) = T::parse_cer(input)?;
+        let tagged = TaggedValue::explicit(t);
+        Ok((rem, tagged))
+    }
+}
+
 impl<'a, T, E, const CLASS: u8, const TAG: u32> FromDer<'a, E>
     for TaggedValue<T, E, Explicit, CLASS, TAG>
 where
@@ -169,7 +212,6 @@ where
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
     impl<T, E, const CLASS: u8, const TAG: u32> ToBer for TaggedValue<T, E, Explicit, CLASS, TAG>
     where
@@ -184,7 +226,7 @@ const _: () = {
             header_len + content_len
         }
 
-        fn ber_write_content<W: std::io::Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.inner.ber_encode(target)
         }
 
@@ -206,13 +248,51 @@ const _: () = {
             header_len + content_len
         }
 
-        fn der_write_content<W: std::io::Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.inner.der_encode(target)
         }
 
         fn der_tag_info(&self) -> (Class, bool, Tag) {
             (Self::CLASS, true, Self::TAG)
         }
+
+        fn der_content_len_cached(&self, cache: &mut LengthCache) -> Length {
+            self.inner.der_total_len_cached(cache)
+        }
+
+        fn der_write_content_cached<W: Writer>(
+            &self,
+            cache: &mut LengthCache,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            self.inner.der_encode_cached(cache, target)
+        }
+
+        fn der_write_content_reverse(&self, target: &mut ReverseWriter) -> SerializeResult<usize> {
+            self.inner.der_encode_reverse(target)
+        }
+    }
+
+    impl<T, E, const CLASS: u8, const TAG: u32> ToCer for TaggedValue<T, E, Explicit, CLASS, TAG>
+    where
+        T: ToCer,
+        T: DynTagged,
+    {
+        type Encoder = BerGenericEncoder;
+
+        fn cer_content_len(&self) -> Length {
+            // Tagged Explicit is constructed, so it uses the indefinite form (X.690: 9.1)
+            Length::Indefinite
+        }
+
+        fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            let sz = self.inner.cer_encode(target)?;
+            Ok(sz + cer_write_eoc(target)?)
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            (Self::CLASS, true, Self::TAG)
+        }
     }
 };
 
@@ -342,7 +422,6 @@ where
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
     impl<T, E> ToBer for TaggedParser<'_, Explicit, T, E>
     where
@@ -354,7 +433,7 @@ const _: () = {
             self.inner.ber_total_len()
         }
 
-        fn ber_write_content<W: std::io::Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.inner.ber_encode(target)
         }
 
@@ -373,13 +452,29 @@ const _: () = {
             self.inner.der_total_len()
         }
 
-        fn der_write_content<W: std::io::Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.inner.der_encode(target)
         }
 
         fn der_tag_info(&self) -> (Class, bool, Tag) {
             (self.class(), true, self.tag())
         }
+
+        fn der_content_len_cached(&self, cache: &mut LengthCache) -> Length {
+            self.inner.der_total_len_cached(cache)
+        }
+
+        fn der_write_content_cached<W: Writer>(
+            &self,
+            cache: &mut LengthCache,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            self.inner.der_encode_cached(cache, target)
+        }
+
+        fn der_write_content_reverse(&self, target: &mut ReverseWriter) -> SerializeResult<usize> {
+            self.inner.der_encode_reverse(target)
+        }
     }
 };
 
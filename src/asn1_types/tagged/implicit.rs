@@ -132,6 +132,37 @@ where
     }
 }
 
+impl<'i, T, E, const CLASS: u8, const TAG: u32> CerParser<'i>
+    for TaggedValue<T, E, Implicit, CLASS, TAG>
+where
+    T: CerParser<'i>,
+    // E: ParseError<Input<'a>> + From<BerError<Input<'a>>>,
+{
+    type Error = T::Error;
+
+    fn from_cer_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        // assert class
+        if header.class as u8 != CLASS {
+            // Safety: CLASS < 4
+            let class = Class::try_from(CLASS).unwrap_or(Class::Private);
+            return Err(Err::Error(
+                BerError::unexpected_class(input, Some(class), header.class).into(),
+            ));
+        }
+
+        // pass the same header to parse inner content
+        // note: we *know* that header.tag is most probably different from t::tag,
+        // so the tag is not checked here
+
+        let (rem, t) = T::from_cer_content(header, input)?;
+        let tagged = TaggedValue::implicit(t);
+        Ok((rem, tagged))
+    }
+}
+
 impl<'a, T, E, const CLASS: u8, const TAG: u32> FromDer<'a, E>
     for TaggedValue<T, E, Implicit, CLASS, TAG>
 where
@@ -179,7 +210,6 @@ where
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
     impl<T, E, const CLASS: u8, const TAG: u32> ToBer for TaggedValue<T, E, Implicit, CLASS, TAG>
     where
@@ -192,7 +222,7 @@ const _: () = {
             self.inner.ber_content_len()
         }
 
-        fn ber_write_content<W: std::io::Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.inner.ber_write_content(target)
         }
 
@@ -212,13 +242,51 @@ const _: () = {
             self.inner.der_content_len()
         }
 
-        fn der_write_content<W: std::io::Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.inner.der_write_content(target)
         }
 
         fn der_tag_info(&self) -> (Class, bool, Tag) {
             (DynTagged::class(self), self.constructed(), self.tag())
         }
+
+        fn der_content_len_cached(&self, cache: &mut LengthCache) -> Length {
+            self.inner.der_content_len_cached(cache)
+        }
+
+        fn der_write_content_cached<W: Writer>(
+            &self,
+            cache: &mut LengthCache,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            self.inner.der_write_content_cached(cache, target)
+        }
+
+        fn der_write_content_reverse(&self, target: &mut ReverseWriter) -> SerializeResult<usize> {
+            self.inner.der_write_content_reverse(target)
+        }
+    }
+
+    impl<T, E, const CLASS: u8, const TAG: u32> ToCer for TaggedValue<T, E, Implicit, CLASS, TAG>
+    where
+        T: ToCer,
+        T: DynTagged,
+    {
+        type Encoder = BerGenericEncoder;
+
+        fn cer_content_len(&self) -> Length {
+            self.inner.cer_content_len()
+        }
+
+        fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+            self.inner.cer_write_content(target)
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            // inner encoding can be constructed (for ex. long strings)
+            let (_, constructed, _) = self.inner.cer_tag_info();
+            (DynTagged::class(self), constructed, self.tag())
+        }
     }
 };
 
@@ -340,7 +408,6 @@ where
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
     impl<T, E> ToBer for TaggedParser<'_, Implicit, T, E>
     where
@@ -352,7 +419,7 @@ const _: () = {
             self.inner.ber_content_len()
         }
 
-        fn ber_write_content<W: std::io::Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.inner.ber_write_content(target)
         }
 
@@ -371,13 +438,29 @@ const _: () = {
             self.inner.der_content_len()
         }
 
-        fn der_write_content<W: std::io::Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             self.inner.der_write_content(target)
         }
 
         fn der_tag_info(&self) -> (Class, bool, Tag) {
             (self.class(), self.constructed(), self.tag())
         }
+
+        fn der_content_len_cached(&self, cache: &mut LengthCache) -> Length {
+            self.inner.der_content_len_cached(cache)
+        }
+
+        fn der_write_content_cached<W: Writer>(
+            &self,
+            cache: &mut LengthCache,
+            target: &mut W,
+        ) -> SerializeResult<usize> {
+            self.inner.der_write_content_cached(cache, target)
+        }
+
+        fn der_write_content_reverse(&self, target: &mut ReverseWriter) -> SerializeResult<usize> {
+            self.inner.der_write_content_reverse(target)
+        }
     }
 };
 
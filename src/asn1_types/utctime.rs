@@ -1,4 +1,9 @@
 use crate::*;
+#[cfg(all(
+    not(feature = "std"),
+    any(feature = "jer", feature = "xer", feature = "value-notation")
+))]
+use alloc::string::String;
 use core::fmt;
 use nom::Input as _;
 #[cfg(feature = "datetime")]
@@ -12,6 +17,29 @@ impl UtcTime {
         UtcTime(datetime)
     }
 
+    /// Returns the characters of the BER encoding of this value (for ex. `"021213142923Z"`)
+    #[cfg(any(feature = "jer", feature = "xer", feature = "value-notation"))]
+    pub(crate) fn to_value_string(&self) -> String {
+        use alloc::format;
+
+        let dt = &self.0;
+        // UTCTime always has a time zone
+        let tz = match dt.tz {
+            ASN1TimeZone::Undefined => ASN1TimeZone::Z,
+            tz => tz,
+        };
+        format!(
+            "{:02}{:02}{:02}{:02}{:02}{:02}{}",
+            dt.year % 100,
+            dt.month,
+            dt.day,
+            dt.hour,
+            dt.minute,
+            dt.second,
+            format_time_zone(tz)
+        )
+    }
+
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
         // X.680 section 43 defines a UniversalTime as a VisibleString restricted to:
         //
@@ -190,6 +218,8 @@ impl<'i> DerParser<'i> for UtcTime {
     }
 }
 
+impl_cerparser_from_derparser!(TY UtcTime);
+
 impl fmt::Display for UtcTime {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let dt = &self.0;
@@ -223,10 +253,7 @@ impl Tagged for UtcTime {
     const TAG: Tag = Tag::UtcTime;
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use std::io::Write;
-
     impl ToBer for UtcTime {
         type Encoder = Primitive<{ Tag::UtcTime.0 }>;
 
@@ -239,7 +266,7 @@ const _: () = {
             Length::Definite(13)
         }
 
-        fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
             write!(
                 target,
                 "{:02}{:02}{:02}{:02}{:02}{:02}Z",
@@ -255,6 +282,7 @@ const _: () = {
     }
 
     impl_toder_from_tober!(TY UtcTime);
+    impl_tocer_from_toder!(TY UtcTime);
 };
 
 #[cfg(test)]
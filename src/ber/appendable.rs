@@ -1,7 +1,8 @@
 use nom::{Err, IResult, Input as _};
 
 use crate::{
-    Any, AnyIterator, BerError, BerMode, BerParser, DynTagged, Header, InnerError, Input, Tag,
+    Any, AnyIterator, BerError, BerMode, BerParser, DerConstraint, DerParser, DynTagged, Header,
+    InnerError, Input, Tag, CER_SEGMENT_SIZE,
 };
 
 use super::ber_get_content;
@@ -15,11 +16,27 @@ pub trait Appendable {
 /// Notes:
 /// - header of recursion entrypoint must be constructed
 /// - `T` must be able to parse a primitive object using `BerParser`
+/// - the `max_alloc` [`Limits`](crate::Limits) of the input are applied, using the allocation
+///   budget of its [`LimitsState`](crate::LimitsState). If limits were set on the input, their
+///   `max_depth` is used instead of `recursion_limit`
 pub(crate) fn parse_ber_segmented<'i, T>(
     header: &'_ Header<'i>,
     input: Input<'i>,
     recursion_limit: usize,
 ) -> IResult<Input<'i>, T, BerError<Input<'i>>>
+where
+    T: Appendable + Default,
+    T: BerParser<'i, Error = BerError<Input<'i>>>,
+{
+    let recursion_limit = input.max_depth_or(recursion_limit);
+    parse_ber_segmented_inner(header, input, recursion_limit)
+}
+
+fn parse_ber_segmented_inner<'i, T>(
+    header: &'_ Header<'i>,
+    input: Input<'i>,
+    recursion_limit: usize,
+) -> IResult<Input<'i>, T, BerError<Input<'i>>>
 where
     T: Appendable + Default,
     T: BerParser<'i, Error = BerError<Input<'i>>>,
@@ -52,9 +69,13 @@ where
             if !<T as DynTagged>::accept_tag(h2.tag()) {
                 return Err(BerError::nom_err_input(&data2, InnerError::InvalidTag));
             }
+            // segments are copied when appended
+            if !h2.constructed() && !try_alloc(&data2) {
+                return Err(BerError::nom_err_input(&data2, InnerError::BerMaxAlloc));
+            }
             // Empty segments are sometimes allowed (for ex: OctetStrings). Just skip recursion if empty
             if !data2.is_empty() {
-                let (_, mut part_v) = parse_ber_segmented(&h2, data2, recursion_limit - 1)?;
+                let (_, mut part_v) = parse_ber_segmented_inner(&h2, data2, recursion_limit - 1)?;
                 v.append(&mut part_v);
             }
         }
@@ -65,3 +86,69 @@ where
         Ok((rem, t))
     }
 }
+
+/// Parse object content for segmented objects, enforcing CER constraints (X.690: 9.2)
+///
+/// Notes:
+/// - if `header` is primitive, content must not be longer than [`CER_SEGMENT_SIZE`] octets
+/// - if `header` is constructed, content must be a list of primitive segments of
+///   [`CER_SEGMENT_SIZE`] octets (except the last one, which can be shorter), and total length
+///   must be greater than [`CER_SEGMENT_SIZE`]
+/// - `T` must be able to parse a primitive object using `DerParser`
+pub(crate) fn parse_cer_segmented<'i, T>(
+    header: &'_ Header<'i>,
+    input: Input<'i>,
+) -> IResult<Input<'i>, T, BerError<Input<'i>>>
+where
+    T: Appendable + Default,
+    T: DerParser<'i, Error = BerError<Input<'i>>>,
+{
+    const SEGMENT_SIZE_ERROR: InnerError =
+        InnerError::DerConstraintFailed(DerConstraint::SegmentSize);
+
+    if !header.constructed() {
+        if input.len() > CER_SEGMENT_SIZE {
+            return Err(BerError::nom_err_input(&input, SEGMENT_SIZE_ERROR));
+        }
+        return T::from_der_content(header, input);
+    }
+
+    let mut v = T::default();
+    let mut total_len = 0;
+    let mut previous_len = CER_SEGMENT_SIZE;
+    for res in AnyIterator::<BerMode>::new(input.clone()) {
+        let (_, obj) = res.map_err(Err::Error)?;
+        let Any {
+            header: h2,
+            data: data2,
+        } = obj;
+        if !<T as DynTagged>::accept_tag(h2.tag()) {
+            return Err(BerError::nom_err_input(&data2, InnerError::InvalidTag));
+        }
+        // segments are primitive, and only the last one can be shorter
+        h2.assert_primitive_input(&data2).map_err(Err::Error)?;
+        if previous_len != CER_SEGMENT_SIZE || data2.is_empty() || data2.len() > CER_SEGMENT_SIZE {
+            return Err(BerError::nom_err_input(&data2, SEGMENT_SIZE_ERROR));
+        }
+        previous_len = data2.len();
+        total_len += data2.len();
+        // segments are copied when appended
+        if !try_alloc(&data2) {
+            return Err(BerError::nom_err_input(&data2, InnerError::BerMaxAlloc));
+        }
+        let (_, mut part_v) = T::from_der_content(&h2, data2)?;
+        v.append(&mut part_v);
+    }
+    // short strings must use the primitive form
+    if total_len <= CER_SEGMENT_SIZE {
+        return Err(BerError::nom_err_input(&input, SEGMENT_SIZE_ERROR));
+    }
+    let (rem, _) = input.take_split(input.len());
+    Ok((rem, v))
+}
+
+/// Reserve the length of `data` in the allocation budget of the input limits, if any
+fn try_alloc(data: &Input) -> bool {
+    data.limits_state()
+        .map_or(true, |state| state.try_alloc(data.len()))
+}
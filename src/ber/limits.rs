@@ -0,0 +1,429 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use super::MAX_RECURSION;
+
+/// Limits applied when parsing BER/DER/CER objects
+///
+/// When parsing untrusted input, limits bound the resources used by parsers. For each message, a
+/// [`LimitsState`] is created from the limits and attached to the parser input using
+/// [`Input::with_limits`](crate::Input::with_limits). Limits are propagated to all sub-objects,
+/// so they apply to all parsers (including [`BerParser`], [`DerParser`], [`AnyIterator`],
+/// collections like [`SequenceOf`] or [`SetOf`], and segmented strings).
+///
+/// Exceeding a limit returns a specific error:
+///
+/// | Limit | Default | Error |
+/// |-------|---------|-------|
+/// | [`max_depth`](Limits::with_max_depth) | [`MAX_RECURSION`] | [`BerMaxDepth`](crate::InnerError::BerMaxDepth) |
+/// | [`max_length`](Limits::with_max_length) | no limit | [`BerMaxLength`](crate::InnerError::BerMaxLength) |
+/// | [`max_elements`](Limits::with_max_elements) | no limit | [`BerMaxElements`](crate::InnerError::BerMaxElements) |
+/// | [`max_alloc`](Limits::with_max_alloc) | no limit | [`BerMaxAlloc`](crate::InnerError::BerMaxAlloc) |
+///
+/// If no limits are set on the input, [`Limits::DEFAULT`] is used, except for the maximum depth:
+/// parsers then use their own default (for ex. segmented strings are limited to 5 levels).
+///
+/// `Limits` only holds configuration, so the same value can be used for all messages (for ex. in
+/// a `static`). The allocation budget consumed while parsing a message is kept in the
+/// [`LimitsState`].
+///
+/// [`BerParser`]: crate::BerParser
+/// [`DerParser`]: crate::DerParser
+/// [`AnyIterator`]: crate::AnyIterator
+/// [`SequenceOf`]: crate::SequenceOf
+/// [`SetOf`]: crate::SetOf
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{BerParser, Input, InnerError, Limits, LimitsState};
+/// use hex_literal::hex;
+///
+/// static LIMITS: Limits = Limits::new().with_max_length(1024).with_max_elements(2);
+///
+/// // SEQUENCE OF INTEGER, with 3 elements
+/// let data = hex!("30 09 02 01 01 02 01 02 02 01 03");
+/// let state = LimitsState::new(LIMITS);
+/// let input = Input::from(&data).with_limits(&state);
+///
+/// let e = <Vec<u32>>::parse_ber(input).expect_err("limit not applied");
+/// assert!(matches!(e, nom::Err::Error(e) if *e.inner() == InnerError::BerMaxElements));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Limits {
+    max_depth: usize,
+    max_length: usize,
+    max_elements: usize,
+    max_alloc: usize,
+}
+
+impl Limits {
+    /// Default limits: maximum depth is [`MAX_RECURSION`], other values are not limited
+    pub const DEFAULT: Limits = Limits {
+        max_depth: MAX_RECURSION,
+        max_length: usize::MAX,
+        max_elements: usize::MAX,
+        max_alloc: usize::MAX,
+    };
+
+    /// Build new limits, with default values (see [`Limits::DEFAULT`])
+    #[inline]
+    pub const fn new() -> Self {
+        Self::DEFAULT
+    }
+
+    /// Set the maximum nesting depth of objects
+    ///
+    /// This applies to objects with indefinite length (which must be parsed recursively to find
+    /// their end), and to segmented strings. It replaces the default depth of parsers, so it can
+    /// be used to raise or lower the limit.
+    #[inline]
+    pub const fn with_max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    /// Set the maximum length of the content of an object, in octets
+    ///
+    /// For definite lengths, this is checked after reading the header, so the content does not
+    /// need to be received.
+    #[inline]
+    pub const fn with_max_length(self, max_length: usize) -> Self {
+        Self { max_length, ..self }
+    }
+
+    /// Set the maximum number of elements of a constructed object
+    ///
+    /// This applies to all objects iterating on sub-objects, for ex. `SEQUENCE OF` and `SET OF`.
+    #[inline]
+    pub const fn with_max_elements(self, max_elements: usize) -> Self {
+        Self {
+            max_elements,
+            ..self
+        }
+    }
+
+    /// Set the maximum number of octets allocated to reassemble segmented strings
+    ///
+    /// Segmented strings are constructed (BER/CER) strings, for ex. `OCTET STRING` or
+    /// `BIT STRING`, with content split in several segments. The total size of the segments of all
+    /// strings parsed using the same [`LimitsState`] (usually, a whole message) is checked while
+    /// parsing.
+    #[inline]
+    pub const fn with_max_alloc(self, max_alloc: usize) -> Self {
+        Self { max_alloc, ..self }
+    }
+
+    /// Return the maximum nesting depth of objects
+    #[inline]
+    pub const fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Return the maximum length of the content of an object
+    #[inline]
+    pub const fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Return the maximum number of elements of a constructed object
+    #[inline]
+    pub const fn max_elements(&self) -> usize {
+        self.max_elements
+    }
+
+    /// Return the maximum number of octets allocated to reassemble segmented strings
+    #[inline]
+    pub const fn max_alloc(&self) -> usize {
+        self.max_alloc
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// State of the parsing of a message using [`Limits`]
+///
+/// The state holds the limits, and the allocation budget consumed while parsing (see
+/// [`Limits::with_max_alloc`]). Create a new state for each message, and attach it to the input
+/// using [`Input::with_limits`](crate::Input::with_limits). The state is shared by all
+/// sub-inputs, so the `max_alloc` limit applies to all segmented strings of the message.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{BerParser, Input, Limits, LimitsState, OctetString};
+/// use hex_literal::hex;
+///
+/// static LIMITS: Limits = Limits::new().with_max_alloc(4);
+///
+/// // SEQUENCE { OCTET STRING (segmented), OCTET STRING (segmented) }
+/// let data = hex!("30 10 24 80 04 02 aa bb 00 00 24 80 04 02 cc dd 00 00");
+///
+/// let state = LimitsState::new(LIMITS);
+/// let input = Input::from(&data).with_limits(&state);
+/// let (_, v) = <Vec<OctetString>>::parse_ber(input).expect("parsing failed");
+/// assert_eq!(state.allocated(), 4);
+/// ```
+#[derive(Debug, Default)]
+pub struct LimitsState {
+    limits: Limits,
+    /// Number of octets allocated by parsers using this state
+    allocated: AtomicUsize,
+}
+
+impl LimitsState {
+    /// Build a new state for the given limits, with no octets allocated
+    #[inline]
+    pub const fn new(limits: Limits) -> Self {
+        Self {
+            limits,
+            allocated: AtomicUsize::new(0),
+        }
+    }
+
+    /// Return the limits
+    #[inline]
+    pub const fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Return the number of octets allocated so far to reassemble segmented strings
+    #[inline]
+    pub fn allocated(&self) -> usize {
+        self.allocated.load(Ordering::Relaxed)
+    }
+
+    /// Reserve `len` octets in the allocation budget
+    ///
+    /// Return `false` if the budget is exceeded (in this case, nothing is reserved).
+    pub(crate) fn try_alloc(&self, len: usize) -> bool {
+        let max_alloc = self.limits.max_alloc;
+        if max_alloc == usize::MAX {
+            return true;
+        }
+        self.allocated
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |allocated| {
+                allocated
+                    .checked_add(len)
+                    .filter(|&total| total <= max_alloc)
+            })
+            .is_ok()
+    }
+}
+
+impl From<Limits> for LimitsState {
+    fn from(limits: Limits) -> Self {
+        Self::new(limits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+    use alloc::vec::Vec;
+    use core::convert::TryFrom;
+    use hex_literal::hex;
+    use nom::Input as _;
+
+    use crate::{
+        Any, BerMode, BerParser, CheckDerConstraints, DerParser, Error, InnerError, Input, Limits,
+        LimitsState, OctetString, SequenceOf, SetOf,
+    };
+
+    fn inner_error<T>(r: nom::IResult<Input, T, crate::BerError<Input>>) -> Option<InnerError> {
+        match r {
+            Err(nom::Err::Error(e)) => Some(e.inner().clone()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn limits_input() {
+        let data = hex!("30 03 02 01 01");
+        let limits = LimitsState::new(Limits::new().with_max_elements(3));
+        let input = Input::from(&data).with_limits(&limits);
+        assert_eq!(input.limits(), limits.limits());
+        // limits are propagated to sub-inputs, and not compared
+        assert_eq!(input.take_from(2).limits().max_elements(), 3);
+        assert_eq!(input, Input::from(&data));
+        assert_eq!(Input::from(&data).limits(), &Limits::new());
+        let (_, any) = Any::parse_ber(input).expect("parsing failed");
+        assert_eq!(any.data.limits(), limits.limits());
+    }
+
+    #[test]
+    fn limits_depth() {
+        let data = hex!("30 80 30 80 30 80 00 00 00 00 00 00");
+        assert!(Any::parse_ber(Input::from(&data)).is_ok());
+        let limits = LimitsState::new(Limits::new().with_max_depth(2));
+        let r = Any::parse_ber(Input::from(&data).with_limits(&limits));
+        assert_eq!(inner_error(r), Some(InnerError::BerMaxDepth));
+
+        // segmented strings
+        let data = hex!("24 80 24 80 04 01 aa 00 00 00 00");
+        let (_, s) = OctetString::parse_ber(Input::from(&data)).expect("parsing failed");
+        assert_eq!(s.as_ref(), &[0xaa]);
+        let limits = LimitsState::new(Limits::new().with_max_depth(1));
+        let r = OctetString::parse_ber(Input::from(&data).with_limits(&limits));
+        assert_eq!(inner_error(r), Some(InnerError::BerMaxDepth));
+    }
+
+    #[test]
+    fn limits_depth_raise() {
+        // 60 nested SEQUENCE with indefinite length
+        let mut data = Vec::new();
+        data.extend([0x30, 0x80].repeat(60));
+        data.extend([0x00, 0x00].repeat(60));
+        let r = Any::parse_ber(Input::from(&data));
+        assert_eq!(inner_error(r), Some(InnerError::BerMaxDepth));
+        let limits = LimitsState::new(Limits::new().with_max_depth(64));
+        let (rem, _) =
+            Any::parse_ber(Input::from(&data).with_limits(&limits)).expect("parsing failed");
+        assert!(rem.is_empty());
+
+        // segmented strings, with 7 levels
+        let mut data = Vec::new();
+        data.extend([0x24, 0x80].repeat(6));
+        data.extend([0x04, 0x01, 0xaa]);
+        data.extend([0x00, 0x00].repeat(6));
+        let r = OctetString::parse_ber(Input::from(&data));
+        assert_eq!(inner_error(r), Some(InnerError::BerMaxDepth));
+        let limits = LimitsState::new(Limits::new().with_max_depth(8));
+        let (_, s) = OctetString::parse_ber(Input::from(&data).with_limits(&limits))
+            .expect("parsing failed");
+        assert_eq!(s.as_ref(), &[0xaa]);
+    }
+
+    #[test]
+    fn limits_length() {
+        let limits = LimitsState::new(Limits::new().with_max_length(4));
+        // checked before content is received
+        let r = Any::parse_ber(Input::from(&hex!("04 82 10 00 00")).with_limits(&limits));
+        assert_eq!(inner_error(r), Some(InnerError::BerMaxLength));
+        let r = Any::parse_der(Input::from(&hex!("04 05 00")).with_limits(&limits));
+        assert_eq!(inner_error(r), Some(InnerError::BerMaxLength));
+        // indefinite length
+        let data = hex!("30 80 04 03 01 02 03 00 00");
+        let r = Any::parse_ber(Input::from(&data).with_limits(&limits));
+        assert_eq!(inner_error(r), Some(InnerError::BerMaxLength));
+        let limits = LimitsState::new(Limits::new().with_max_length(5));
+        assert!(Any::parse_ber(Input::from(&data).with_limits(&limits)).is_ok());
+    }
+
+    #[test]
+    fn limits_elements() {
+        let data = hex!("30 09 02 01 01 02 01 02 02 01 03");
+        let limits = LimitsState::new(Limits::new().with_max_elements(3));
+        let (_, v) =
+            <Vec<u32>>::parse_ber(Input::from(&data).with_limits(&limits)).expect("parsing failed");
+        assert_eq!(v, [1, 2, 3]);
+
+        let limits = LimitsState::new(Limits::new().with_max_elements(2));
+        let input = Input::from(&data).with_limits(&limits);
+        let r = <Vec<u32>>::parse_ber(input.clone());
+        assert_eq!(inner_error(r), Some(InnerError::BerMaxElements));
+        let r = <SequenceOf<u32>>::parse_der(input.clone());
+        assert_eq!(inner_error(r), Some(InnerError::BerMaxElements));
+        let (_, any) = Any::parse_ber(input).expect("parsing failed");
+        let mut iter = any.iter_elements::<BerMode>();
+        assert!(iter.next().is_some_and(|r| r.is_ok()));
+        assert!(iter.next().is_some_and(|r| r.is_ok()));
+        assert!(iter.next().is_some_and(|r| r.is_err()));
+        assert!(iter.next().is_none());
+
+        let data = hex!("31 09 02 01 01 02 01 02 02 01 03");
+        let input = Input::from(&data).with_limits(&limits);
+        let r = <SetOf<u32>>::parse_ber(input.clone());
+        assert_eq!(inner_error(r), Some(InnerError::BerMaxElements));
+        let r = <BTreeSet<u32>>::parse_der(input.clone());
+        assert_eq!(inner_error(r), Some(InnerError::BerMaxElements));
+
+        // conversion to Error
+        let data = hex!("30 09 02 01 01 02 01 02 02 01 03");
+        let input = Input::from(&data).with_limits(&limits);
+        let e = <Vec<u32>>::parse_ber(input).expect_err("limit not applied");
+        assert_eq!(Error::from_nom_berr(e), Error::BerMaxElements);
+    }
+
+    #[test]
+    fn limits_set_of_any() {
+        // limits are kept in `Any`, and applied when converting to a SET OF
+        let data = hex!("31 09 02 01 03 02 01 01 02 01 02");
+        let limits = LimitsState::new(Limits::new().with_max_elements(2));
+        let input = Input::from(&data).with_limits(&limits);
+        let (_, any) = Any::parse_ber(input).expect("parsing failed");
+        let r = <SetOf<u32>>::try_from(any.clone());
+        assert_eq!(r, Err(Error::BerMaxElements));
+        let r = <BTreeSet<u32>>::try_from(any.clone());
+        assert_eq!(r, Err(Error::BerMaxElements));
+        let r = <BTreeSet<u32>>::check_constraints(&any);
+        assert_eq!(r, Err(Error::BerMaxElements));
+
+        let data = hex!("31 09 02 01 01 02 01 02 02 01 03");
+        let input = Input::from(&data).with_limits(&limits);
+        let (_, any) = Any::parse_der(input).expect("parsing failed");
+        let r = <SetOf<u32>>::check_constraints(&any);
+        assert_eq!(r, Err(Error::BerMaxElements));
+
+        // nested indefinite-length components
+        let data = hex!("31 0c 30 80 30 80 30 80 00 00 00 00 00 00");
+        let (_, any) = Any::parse_ber(Input::from(&data)).expect("parsing failed");
+        let v = <SetOf<Any>>::try_from(any).expect("parsing failed");
+        assert_eq!(v.len(), 1);
+
+        let limits = LimitsState::new(Limits::new().with_max_depth(2));
+        let input = Input::from(&data).with_limits(&limits);
+        let (_, any) = Any::parse_ber(input).expect("parsing failed");
+        let r = <SetOf<Any>>::try_from(any);
+        assert_eq!(r, Err(Error::BerMaxDepth));
+    }
+
+    #[test]
+    fn limits_alloc() {
+        let data = hex!("24 80 04 02 aa bb 24 80 04 02 cc dd 00 00 00 00");
+        let limits = LimitsState::new(Limits::new().with_max_alloc(4));
+        let (_, s) = OctetString::parse_ber(Input::from(&data).with_limits(&limits))
+            .expect("parsing failed");
+        assert_eq!(s.as_ref(), &hex!("aa bb cc dd"));
+
+        let limits = LimitsState::new(Limits::new().with_max_alloc(3));
+        let r = OctetString::parse_ber(Input::from(&data).with_limits(&limits));
+        assert_eq!(inner_error(r), Some(InnerError::BerMaxAlloc));
+
+        // primitive strings are not copied
+        let data = hex!("04 04 aa bb cc dd");
+        assert!(OctetString::parse_ber(Input::from(&data).with_limits(&limits)).is_ok());
+    }
+
+    #[test]
+    fn limits_alloc_shared() {
+        // SEQUENCE { OCTET STRING (segmented, 2 bytes), OCTET STRING (segmented, 2 bytes) }
+        let data = hex!("30 10 24 80 04 02 aa bb 00 00 24 80 04 02 cc dd 00 00");
+        static LIMITS: Limits = Limits::new().with_max_alloc(4);
+
+        // the budget applies to all strings of the message
+        let state = LimitsState::new(LIMITS);
+        let (_, v) = <Vec<OctetString>>::parse_ber(Input::from(&data).with_limits(&state))
+            .expect("parsing failed");
+        assert_eq!(v.len(), 2);
+        assert_eq!(state.allocated(), 4);
+        let state = LimitsState::new(LIMITS.with_max_alloc(3));
+        let r = <Vec<OctetString>>::parse_ber(Input::from(&data).with_limits(&state));
+        assert_eq!(inner_error(r), Some(InnerError::BerMaxAlloc));
+
+        // the same limits can be used for several messages, each with a new state
+        for _ in 0..2 {
+            let state = LimitsState::new(LIMITS);
+            assert!(<Vec<OctetString>>::parse_ber(Input::from(&data).with_limits(&state)).is_ok());
+            assert_eq!(state.allocated(), 4);
+        }
+
+        // a state is consumed while parsing
+        let state = LimitsState::new(LIMITS);
+        assert!(<Vec<OctetString>>::parse_ber(Input::from(&data).with_limits(&state)).is_ok());
+        let r = <Vec<OctetString>>::parse_ber(Input::from(&data).with_limits(&state));
+        assert_eq!(inner_error(r), Some(InnerError::BerMaxAlloc));
+    }
+}
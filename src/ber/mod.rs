@@ -1,5 +1,7 @@
 mod appendable;
+mod limits;
 mod parser;
 
 pub use appendable::*;
+pub use limits::*;
 pub use parser::*;
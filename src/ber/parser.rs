@@ -1,6 +1,6 @@
 use crate::error::*;
 use crate::header::*;
-use crate::{BerMode, BerParser, DerMode, Input, Length, Tag};
+use crate::{BerMode, BerParser, CerMode, DerMode, Input, Length, Tag};
 use nom::bytes::streaming::take;
 use nom::{Err, IResult, Input as _};
 
@@ -14,6 +14,10 @@ pub trait GetObjectContent {
     /// Return the raw content (bytes) of the next ASN.1 encoded object
     ///
     /// Note: if using BER and length is indefinite, terminating End-Of-Content is NOT included
+    ///
+    /// The [`Limits`](crate::Limits) of the input are applied: if limits were set on the input,
+    /// their `max_depth` is used instead of the `max_depth` argument, and content longer than
+    /// `max_length` is rejected.
     fn get_object_content<'a>(
         hdr: &'_ Header,
         i: Input<'a>,
@@ -27,6 +31,10 @@ impl GetObjectContent for BerMode {
         i: Input<'a>,
         max_depth: usize,
     ) -> IResult<Input<'a>, Input<'a>, BerError<Input<'a>>> {
+        let max_depth = i.max_depth_or(max_depth);
+        if let Length::Definite(l) = hdr.length {
+            check_max_length(&i, l)?;
+        }
         let start_i = i.clone();
         let (i, _) = ber_skip_object_content(i, hdr, max_depth)?;
         let len = i.span().start - start_i.span().start;
@@ -34,6 +42,7 @@ impl GetObjectContent for BerMode {
             Ok(start_i.take_split(len))
         } else {
             assert!(len >= 2);
+            check_max_length(&start_i, len - 2)?;
             // take content (minus EndOfContent) and return i (after EndOfContent)
             let content = start_i.take(len - 2);
             Ok((i, content))
@@ -62,7 +71,10 @@ impl GetObjectContent for DerMode {
         _max_depth: usize,
     ) -> IResult<Input<'a>, Input<'a>, BerError<Input<'a>>> {
         match hdr.length {
-            Length::Definite(l) => take(l)(i),
+            Length::Definite(l) => {
+                check_max_length(&i, l)?;
+                take(l)(i)
+            }
             Length::Indefinite => Err(Err::Error(BerError::new(
                 i,
                 InnerError::DerConstraintFailed(DerConstraint::IndefiniteLength),
@@ -82,6 +94,38 @@ pub fn der_get_content<'i>(
     DerMode::get_object_content(header, input, MAX_RECURSION)
 }
 
+impl GetObjectContent for CerMode {
+    /// Skip object content, accepting both definite and indefinite lengths
+    ///
+    /// CER constraints on length form are checked when parsing header (see
+    /// [`CerParser`](crate::CerParser)), so content is read the same way as BER.
+    fn get_object_content<'a>(
+        hdr: &'_ Header,
+        i: Input<'a>,
+        max_depth: usize,
+    ) -> IResult<Input<'a>, Input<'a>, BerError<Input<'a>>> {
+        BerMode::get_object_content(hdr, i, max_depth)
+    }
+}
+
+/// Read the content bytes matching length defined in `header` (CER)
+///
+/// This function is an alias to [`CerMode::get_object_content`],
+/// with default parameters (including recursing limit)
+pub fn cer_get_content<'i>(
+    header: &Header,
+    input: Input<'i>,
+) -> IResult<Input<'i>, Input<'i>, BerError<Input<'i>>> {
+    CerMode::get_object_content(header, input, MAX_RECURSION)
+}
+
+fn check_max_length<'a>(i: &Input<'a>, len: usize) -> Result<(), Err<BerError<Input<'a>>>> {
+    if len > i.limits().max_length() {
+        return Err(BerError::nom_err_input(i, InnerError::BerMaxLength));
+    }
+    Ok(())
+}
+
 /// Skip object content, and return true if object was End-Of-Content
 fn ber_skip_object_content<'a>(
     i: Input<'a>,
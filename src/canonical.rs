@@ -0,0 +1,985 @@
+use core::convert::TryFrom;
+use core::fmt;
+
+use displaydoc::Display;
+use nom::{Err, IResult};
+
+use crate::ber::GetObjectContent;
+use crate::{
+    cmp_set_of_encodings, BerError, BerMode, BerParser, Class, Header, InnerError, Input, Length,
+    Tag, ToDer,
+};
+
+/// Issue found when canonicalizing an object (see [`ber_to_der`])
+///
+/// A constructed value with a non-universal tag and several components could be an implicitly
+/// tagged `SET`, `SET OF` or string: its components are canonicalized, but not sorted or
+/// flattened.
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq)]
+pub enum CanonicalIssueKind {
+    /// Implicitly tagged primitive value, copied without normalization
+    ImplicitPrimitive,
+    /// Implicitly tagged constructed value, components kept in order
+    ImplicitConstructed,
+    /// Time value which cannot be normalized (for ex. local time), copied without normalization
+    UnnormalizedTime,
+    /// Real value which cannot be normalized, copied without normalization
+    UnnormalizedReal,
+}
+
+/// Object which could not be fully canonicalized by [`ber_to_der`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CanonicalIssue {
+    offset: usize,
+    class: Class,
+    tag: Tag,
+    kind: CanonicalIssueKind,
+}
+
+impl CanonicalIssue {
+    /// Return the absolute offset of the object in the BER input
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Return the class of the object
+    #[inline]
+    pub const fn class(&self) -> Class {
+        self.class
+    }
+
+    /// Return the tag of the object
+    #[inline]
+    pub const fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Return the kind of issue
+    #[inline]
+    pub const fn kind(&self) -> CanonicalIssueKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for CanonicalIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "offset {}: [{} {}]: {}",
+            self.offset, self.class, self.tag.0, self.kind
+        )
+    }
+}
+
+/// DER encoding of an object, produced by [`ber_to_der`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CanonicalDer {
+    data: Vec<u8>,
+    issues: Vec<CanonicalIssue>,
+}
+
+impl CanonicalDer {
+    /// Return the DER encoding
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Consume the object and return the DER encoding
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Return the objects which could not be fully canonicalized, ordered by offset
+    #[inline]
+    pub fn issues(&self) -> &[CanonicalIssue] {
+        &self.issues
+    }
+
+    /// Return `true` if all objects were canonicalized
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Parse the next BER object, and re-encode it as DER without knowing its type
+///
+/// The following transformations are applied (X.690 sections 10 and 11):
+///
+/// - lengths are encoded in definite form, and tags and lengths use the minimum number of octets
+/// - constructed strings (`BIT STRING`, `OCTET STRING` and character strings) are flattened
+/// - `BOOLEAN` values are `0x00` or `0xff`, `INTEGER` and `ENUMERATED` leading octets are
+///   removed, `BIT STRING` unused bits are set to zero
+/// - `UTCTime` and `GeneralizedTime` values are converted to UTC (`Z`), with seconds, and without
+///   trailing zeros in fractions of seconds
+/// - `REAL` values use base 2 with an odd mantissa (binary encoding), or the NR3 form (decimal
+///   encoding)
+/// - `SET` components are sorted: by encoding if all components have the same tag (`SET OF`),
+///   by tag otherwise
+///
+/// Only objects with universal tags are normalized: implicitly tagged values cannot be
+/// interpreted without a schema. They are copied (primitive values), or their components are
+/// canonicalized in order (constructed values), and reported in
+/// [`issues`](CanonicalDer::issues). Constructed values with a non-universal tag and a single
+/// component are assumed to be explicitly tagged, and are not reported.
+///
+/// Time and real values which cannot be normalized (for ex. local times, which cannot be converted
+/// to UTC) are copied, and also reported in [`issues`](CanonicalDer::issues).
+///
+/// The maximum depth is defined by the input [`Limits`](crate::Limits).
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{ber_to_der, Input};
+/// use hex_literal::hex;
+///
+/// // SET (indefinite length) { BOOLEAN 0x01, OCTET STRING (constructed) { "ab", "c" } }
+/// let data = hex!("31 80 24 80 04 02 61 62 04 01 63 00 00 01 01 01 00 00");
+/// let (rem, der) = ber_to_der(Input::from(&data)).expect("invalid BER");
+/// assert!(rem.is_empty());
+/// assert!(der.is_complete());
+/// assert_eq!(der.as_bytes(), &hex!("31 08 01 01 ff 04 03 61 62 63"));
+/// ```
+pub fn ber_to_der(input: Input<'_>) -> IResult<Input<'_>, CanonicalDer, BerError<Input<'_>>> {
+    let max_depth = input.limits().max_depth();
+    let mut der = CanonicalDer::default();
+    let (rem, _) = canonicalize(input, max_depth, &mut der.data, &mut der.issues)?;
+    Ok((rem, der))
+}
+
+/// Canonicalize the next object, and append its DER encoding to `out`
+///
+/// Return the class and tag of the object.
+fn canonicalize<'i>(
+    input: Input<'i>,
+    max_depth: usize,
+    out: &mut Vec<u8>,
+    issues: &mut Vec<CanonicalIssue>,
+) -> IResult<Input<'i>, (Class, Tag), BerError<Input<'i>>> {
+    if max_depth == 0 {
+        return Err(BerError::nom_err(input, InnerError::BerMaxDepth));
+    }
+    let offset = input.start();
+    let (rem, header) = Header::parse_ber(input)?;
+    let (rem, content) = BerMode::get_object_content(&header, rem, max_depth)?;
+    let (class, tag) = (header.class(), header.tag());
+
+    let mut buf = Vec::new();
+    let mut constructed = header.is_constructed();
+    if class == Class::Universal {
+        match tag {
+            Tag::Boolean => {
+                let [b] = content.as_bytes2() else {
+                    return Err(BerError::nom_err_input(&content, InnerError::InvalidLength));
+                };
+                buf.push(if *b == 0 { 0x00 } else { 0xff });
+            }
+            Tag::Integer | Tag::Enumerated => {
+                let bytes = content.as_bytes2();
+                if bytes.is_empty() {
+                    return Err(BerError::nom_err_input(&content, InnerError::InvalidLength));
+                }
+                buf.extend_from_slice(trim_integer(bytes));
+            }
+            Tag::BitString => {
+                if constructed {
+                    flatten_bitstring(content.clone(), max_depth - 1, &mut buf)?;
+                } else {
+                    buf.extend_from_slice(content.as_bytes2());
+                }
+                normalize_bitstring(&content, &mut buf)?;
+                constructed = false;
+            }
+            tag if is_string(tag) => {
+                if constructed {
+                    let mut segments = Vec::new();
+                    collect_segments(content, max_depth - 1, &mut segments)?;
+                    segments
+                        .iter()
+                        .for_each(|s| buf.extend_from_slice(s.as_bytes2()));
+                } else {
+                    buf.extend_from_slice(content.as_bytes2());
+                }
+                if matches!(tag, Tag::UtcTime | Tag::GeneralizedTime) {
+                    match normalize_time(&buf, tag == Tag::GeneralizedTime) {
+                        Some(time) => buf = time,
+                        None => {
+                            let kind = CanonicalIssueKind::UnnormalizedTime;
+                            issues.push(issue(offset, class, tag, kind));
+                        }
+                    }
+                }
+                constructed = false;
+            }
+            Tag::RealType if !constructed => match normalize_real(content.as_bytes2()) {
+                Some(real) => buf = real,
+                None => {
+                    let kind = CanonicalIssueKind::UnnormalizedReal;
+                    issues.push(issue(offset, class, tag, kind));
+                    buf.extend_from_slice(content.as_bytes2());
+                }
+            },
+            Tag::Set if constructed => {
+                let mut components = canonicalize_components(content, max_depth, issues)?;
+                let same_tag = components.windows(2).all(|w| w[0].0 == w[1].0);
+                if same_tag {
+                    components.sort_by(|a, b| cmp_set_of_encodings(&a.1, &b.1));
+                } else {
+                    // sort by canonical tag order (class, then number), then by encoding
+                    components.sort_by(|a, b| {
+                        tag_order(a.0)
+                            .cmp(&tag_order(b.0))
+                            .then_with(|| cmp_set_of_encodings(&a.1, &b.1))
+                    });
+                }
+                components
+                    .iter()
+                    .for_each(|(_, c)| buf.extend_from_slice(c));
+            }
+            _ if constructed => {
+                let components = canonicalize_components(content, max_depth, issues)?;
+                components
+                    .iter()
+                    .for_each(|(_, c)| buf.extend_from_slice(c));
+            }
+            _ => buf.extend_from_slice(content.as_bytes2()),
+        }
+    } else if constructed {
+        let index = issues.len();
+        let components = canonicalize_components(content, max_depth, issues)?;
+        if components.len() > 1 {
+            let kind = CanonicalIssueKind::ImplicitConstructed;
+            issues.insert(index, issue(offset, class, tag, kind));
+        }
+        components
+            .iter()
+            .for_each(|(_, c)| buf.extend_from_slice(c));
+    } else {
+        issues.push(issue(
+            offset,
+            class,
+            tag,
+            CanonicalIssueKind::ImplicitPrimitive,
+        ));
+        buf.extend_from_slice(content.as_bytes2());
+    }
+
+    let header = Header::new(class, constructed, tag, Length::Definite(buf.len()));
+    header
+        .der_encode(out)
+        .map_err(|_| BerError::nom_err(rem.clone(), InnerError::InvalidLength))?;
+    out.extend_from_slice(&buf);
+    Ok((rem, (class, tag)))
+}
+
+/// Canonicalize all components of a constructed object, returning their tag and encoding
+#[allow(clippy::type_complexity)]
+fn canonicalize_components<'i>(
+    content: Input<'i>,
+    max_depth: usize,
+    issues: &mut Vec<CanonicalIssue>,
+) -> Result<Vec<((Class, Tag), Vec<u8>)>, Err<BerError<Input<'i>>>> {
+    let mut components = Vec::new();
+    let mut i = content;
+    while !i.is_empty() {
+        let mut encoding = Vec::new();
+        let (rem, class_tag) = canonicalize(i, max_depth - 1, &mut encoding, issues)?;
+        components.push((class_tag, encoding));
+        i = rem;
+    }
+    Ok(components)
+}
+
+/// Collect the primitive segments of a constructed string (segments can be constructed)
+fn collect_segments<'i>(
+    content: Input<'i>,
+    max_depth: usize,
+    segments: &mut Vec<Input<'i>>,
+) -> Result<(), Err<BerError<Input<'i>>>> {
+    if max_depth == 0 {
+        return Err(BerError::nom_err(content, InnerError::BerMaxDepth));
+    }
+    let mut i = content;
+    while !i.is_empty() {
+        let (rem, header) = Header::parse_ber(i)?;
+        let (rem, segment) = BerMode::get_object_content(&header, rem, max_depth)?;
+        if header.is_constructed() {
+            collect_segments(segment, max_depth - 1, segments)?;
+        } else {
+            segments.push(segment);
+        }
+        i = rem;
+    }
+    Ok(())
+}
+
+/// Append the content of a constructed `BIT STRING` to `buf`
+///
+/// All segments except the last must have no unused bits (X.690: 8.6.4).
+fn flatten_bitstring<'i>(
+    content: Input<'i>,
+    max_depth: usize,
+    buf: &mut Vec<u8>,
+) -> Result<(), Err<BerError<Input<'i>>>> {
+    let mut segments = Vec::new();
+    collect_segments(content, max_depth, &mut segments)?;
+    let mut unused_bits = 0;
+    let mut bits = Vec::new();
+    for segment in &segments {
+        let Some((first, data)) = segment.as_bytes2().split_first() else {
+            return Err(BerError::nom_err_input(segment, InnerError::InvalidLength));
+        };
+        if unused_bits != 0 {
+            return Err(BerError::nom_err_input(
+                segment,
+                InnerError::invalid_value(Tag::BitString, "Invalid unused bits"),
+            ));
+        }
+        unused_bits = *first;
+        bits.extend_from_slice(data);
+    }
+    buf.push(unused_bits);
+    buf.extend_from_slice(&bits);
+    Ok(())
+}
+
+/// Set unused bits of a `BIT STRING` content to zero (X.690: 11.2.1)
+fn normalize_bitstring<'i>(
+    content: &Input<'i>,
+    buf: &mut [u8],
+) -> Result<(), Err<BerError<Input<'i>>>> {
+    match buf {
+        [] => Err(BerError::nom_err_input(content, InnerError::InvalidLength)),
+        [8..=255, ..] => Err(BerError::nom_err_input(
+            content,
+            InnerError::invalid_value(Tag::BitString, "Invalid unused bits"),
+        )),
+        // X.690 section 11.2.2 Note 2: empty bit string
+        [unused_bits] => {
+            *unused_bits = 0;
+            Ok(())
+        }
+        [unused_bits, .., last] => {
+            *last &= 0xff << *unused_bits;
+            Ok(())
+        }
+    }
+}
+
+/// Remove redundant leading octets of an `INTEGER` encoding (X.690: 8.3.2)
+fn trim_integer(mut bytes: &[u8]) -> &[u8] {
+    while let [b0, b1, ..] = bytes {
+        let redundant = (*b0 == 0x00 && *b1 < 0x80) || (*b0 == 0xff && *b1 >= 0x80);
+        if !redundant {
+            break;
+        }
+        bytes = &bytes[1..];
+    }
+    bytes
+}
+
+/// Normalize the content of a `UTCTime` (X.690: 11.8) or `GeneralizedTime` (X.690: 11.7)
+///
+/// The time is converted to UTC, and seconds are added if missing. Trailing zeros of the
+/// fraction of seconds are removed, as well as the decimal point if the fraction is zero.
+///
+/// Return `None` if the time cannot be normalized: invalid value, local time (without time
+/// zone), or fraction of hours or minutes.
+fn normalize_time(bytes: &[u8], generalized: bool) -> Option<Vec<u8>> {
+    let mut i = bytes;
+    let (year, month, day, hour) = if generalized {
+        let year = i64::from(take_digits(&mut i, 4)?);
+        (
+            year,
+            take_digits(&mut i, 2)?,
+            take_digits(&mut i, 2)?,
+            take_digits(&mut i, 2)?,
+        )
+    } else {
+        // two-digit years are interpreted in 1950..2049 (for leap years)
+        let year = i64::from(take_digits(&mut i, 2)?);
+        let year = if year < 50 { 2000 + year } else { 1900 + year };
+        (
+            year,
+            take_digits(&mut i, 2)?,
+            take_digits(&mut i, 2)?,
+            take_digits(&mut i, 2)?,
+        )
+    };
+    // minutes are optional in GeneralizedTime, seconds are optional in both types
+    let minute = match take_digits(&mut i, 2) {
+        Some(minute) => minute,
+        None if generalized && !i.first().is_some_and(u8::is_ascii_digit) => 0,
+        None => return None,
+    };
+    let second = match take_digits(&mut i, 2) {
+        Some(second) => second,
+        None if !i.first().is_some_and(u8::is_ascii_digit) => 0,
+        None => return None,
+    };
+    let fraction = match i {
+        [b'.' | b',', rem @ ..] if generalized => {
+            let len = rem.iter().take_while(|b| b.is_ascii_digit()).count();
+            // fraction must follow seconds
+            if len == 0 || bytes.len() - i.len() != 14 {
+                return None;
+            }
+            let (fraction, rem) = rem.split_at(len);
+            i = rem;
+            fraction
+        }
+        _ => &[],
+    };
+    let offset = match i {
+        [b'Z'] => 0,
+        [sign @ (b'+' | b'-'), rem @ ..] => {
+            let mut rem = rem;
+            let hours = take_digits(&mut rem, 2)?;
+            // minutes of the offset are optional in GeneralizedTime
+            let minutes = match rem {
+                [] if generalized => 0,
+                _ => take_digits(&mut rem, 2)?,
+            };
+            if !rem.is_empty() || hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = i64::from(hours * 60 + minutes);
+            if *sign == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        // local time cannot be converted to UTC
+        _ => return None,
+    };
+    // end of day (24:00:00) is allowed, and converted to midnight of the next day
+    let end_of_day =
+        hour == 24 && minute == 0 && second == 0 && fraction.iter().all(|&b| b == b'0');
+    if !(1..=12).contains(&month) || (hour > 23 && !end_of_day) || minute > 59 || second > 59 {
+        return None;
+    }
+    let days = days_from_civil(year, month, day);
+    if civil_from_days(days) != (year, month, day) {
+        // invalid day of month
+        return None;
+    }
+    let minutes = days * 1440 + i64::from(hour * 60 + minute) - offset;
+    let (year, month, day) = civil_from_days(minutes.div_euclid(1440));
+    let minutes = minutes.rem_euclid(1440);
+    let (hour, minute) = (minutes / 60, minutes % 60);
+
+    let year = if generalized {
+        if !(0..=9999).contains(&year) {
+            return None;
+        }
+        format!("{:04}", year)
+    } else {
+        format!("{:02}", year % 100)
+    };
+    let mut time = format!(
+        "{}{:02}{:02}{:02}{:02}{:02}",
+        year, month, day, hour, minute, second
+    )
+    .into_bytes();
+    let fraction = match fraction.iter().rposition(|&b| b != b'0') {
+        Some(last) if !end_of_day => &fraction[..=last],
+        _ => &[],
+    };
+    if !fraction.is_empty() {
+        time.push(b'.');
+        time.extend_from_slice(fraction);
+    }
+    time.push(b'Z');
+    Some(time)
+}
+
+/// Read a number of `n` decimal digits, and advance `input`
+fn take_digits(input: &mut &[u8], n: usize) -> Option<u32> {
+    let digits = input.get(..n)?;
+    if !digits.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    *input = &input[n..];
+    Some(
+        digits
+            .iter()
+            .fold(0, |acc, d| acc * 10 + u32::from(d - b'0')),
+    )
+}
+
+/// Return the number of days since 1970-01-01 of a date in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let (month, day) = (i64::from(month), i64::from(day));
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Return the date `(year, month, day)` of a number of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = ((mp + 2) % 12 + 1) as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Normalize the content of a `REAL` (X.690: 11.3)
+///
+/// Binary encodings are converted to base 2, with a scaling factor of 0 and an odd mantissa.
+/// Decimal encodings are converted to the NR3 form. Zero is encoded without content
+/// (X.690: 8.5.2).
+///
+/// Return `None` if the value cannot be normalized (invalid or unsupported encoding).
+fn normalize_real(bytes: &[u8]) -> Option<Vec<u8>> {
+    let Some((&first, rem)) = bytes.split_first() else {
+        return Some(Vec::new());
+    };
+    match first >> 6 {
+        // binary encoding (X.690: 8.5.7)
+        0b10 | 0b11 => {
+            let sign = first & 0x40;
+            let base_bits = match (first >> 4) & 0b11 {
+                0b00 => 1,
+                0b01 => 3,
+                0b10 => 4,
+                _ => return None,
+            };
+            let scale = i64::from((first >> 2) & 0b11);
+            let (exponent, mantissa) = match first & 0b11 {
+                0b11 => {
+                    let (&len, rem) = rem.split_first()?;
+                    (rem.get(..len as usize)?, rem.get(len as usize..)?)
+                }
+                len => (rem.get(..=len as usize)?, rem.get(len as usize + 1..)?),
+            };
+            if exponent.is_empty() || mantissa.is_empty() {
+                return None;
+            }
+            let exponent = trim_integer(exponent);
+            if exponent.len() > 7 {
+                return None;
+            }
+            let exponent = exponent[1..]
+                .iter()
+                .fold(i64::from(exponent[0] as i8), |acc, b| {
+                    (acc << 8) | i64::from(*b)
+                });
+            let Some(start) = mantissa.iter().position(|&b| b != 0) else {
+                return Some(Vec::new());
+            };
+            let mut mantissa = mantissa[start..].to_vec();
+            // remove trailing zero octets, then trailing zero bits
+            let mut shift = 0;
+            while let Some(0) = mantissa.last() {
+                mantissa.pop();
+                shift += 8;
+            }
+            let bits = mantissa.last().map_or(0, |b| b.trailing_zeros());
+            if bits > 0 {
+                for i in (0..mantissa.len()).rev() {
+                    let high = if i > 0 {
+                        mantissa[i - 1] << (8 - bits)
+                    } else {
+                        0
+                    };
+                    mantissa[i] = (mantissa[i] >> bits) | high;
+                }
+                if mantissa[0] == 0 {
+                    mantissa.remove(0);
+                }
+            }
+            let exponent = exponent
+                .checked_mul(base_bits)?
+                .checked_add(scale + shift + i64::from(bits))?;
+            let exponent = exponent.to_be_bytes();
+            let exponent = trim_integer(&exponent);
+            let mut real = Vec::with_capacity(exponent.len() + mantissa.len() + 2);
+            match exponent.len() {
+                len @ 1..=3 => real.push(0x80 | sign | (len as u8 - 1)),
+                len => real.extend_from_slice(&[0x80 | sign | 0b11, len as u8]),
+            }
+            real.extend_from_slice(exponent);
+            real.extend_from_slice(&mantissa);
+            Some(real)
+        }
+        // special real values (X.690: 8.5.9)
+        0b01 => match bytes {
+            [0x40..=0x43] => Some(bytes.to_vec()),
+            _ => None,
+        },
+        // decimal encoding (X.690: 8.5.8)
+        _ => {
+            if !(1..=3).contains(&first) {
+                return None;
+            }
+            normalize_decimal_real(rem)
+        }
+    }
+}
+
+/// Convert a decimal `REAL` (ISO 6093 NR1, NR2 or NR3 form) to the DER NR3 form (X.690: 11.3.2)
+fn normalize_decimal_real(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut i = bytes;
+    while let [b' ', rem @ ..] = i {
+        i = rem;
+    }
+    let negative = match i {
+        [b'-', rem @ ..] => {
+            i = rem;
+            true
+        }
+        [b'+', rem @ ..] => {
+            i = rem;
+            false
+        }
+        _ => false,
+    };
+    fn take_digits<'a>(i: &mut &'a [u8]) -> &'a [u8] {
+        let len = i.iter().take_while(|b| b.is_ascii_digit()).count();
+        let (digits, rem) = i.split_at(len);
+        *i = rem;
+        digits
+    }
+    let mut digits = take_digits(&mut i).to_vec();
+    let mut exponent: i64 = 0;
+    if let [b'.' | b',', rem @ ..] = i {
+        i = rem;
+        let fraction = take_digits(&mut i);
+        digits.extend_from_slice(fraction);
+        exponent = -i64::try_from(fraction.len()).ok()?;
+    }
+    if digits.is_empty() {
+        return None;
+    }
+    if let [b'E' | b'e', rem @ ..] = i {
+        i = rem;
+        let negative = match i {
+            [b'-', rem @ ..] => {
+                i = rem;
+                true
+            }
+            [b'+', rem @ ..] => {
+                i = rem;
+                false
+            }
+            _ => false,
+        };
+        let e = take_digits(&mut i);
+        if e.is_empty() {
+            return None;
+        }
+        let e = e.iter().try_fold(0i64, |acc, d| {
+            acc.checked_mul(10)?.checked_add(i64::from(d - b'0'))
+        })?;
+        exponent = if negative {
+            exponent.checked_sub(e)?
+        } else {
+            exponent.checked_add(e)?
+        };
+    }
+    if !i.is_empty() {
+        return None;
+    }
+    // zero is encoded without content
+    let Some(start) = digits.iter().position(|&d| d != b'0') else {
+        return Some(Vec::new());
+    };
+    let end = digits.iter().rposition(|&d| d != b'0')? + 1;
+    exponent = exponent.checked_add(i64::try_from(digits.len() - end).ok()?)?;
+    let mut real = vec![0x03];
+    if negative {
+        real.push(b'-');
+    }
+    real.extend_from_slice(&digits[start..end]);
+    real.extend_from_slice(b".E");
+    if exponent == 0 {
+        real.extend_from_slice(b"+0");
+    } else {
+        real.extend_from_slice(exponent.to_string().as_bytes());
+    }
+    Some(real)
+}
+
+/// Return `true` if universal `tag` is a string type, which can be constructed in BER
+/// (X.690: 8.7 and 8.23)
+fn is_string(tag: Tag) -> bool {
+    matches!(
+        tag,
+        Tag::OctetString
+            | Tag::ObjectDescriptor
+            | Tag::Utf8String
+            | Tag::NumericString
+            | Tag::PrintableString
+            | Tag::TeletexString
+            | Tag::VideotexString
+            | Tag::Ia5String
+            | Tag::UtcTime
+            | Tag::GeneralizedTime
+            | Tag::GraphicString
+            | Tag::VisibleString
+            | Tag::GeneralString
+            | Tag::UniversalString
+            | Tag::BmpString
+    )
+}
+
+/// Canonical order of tags (X.690: 8.6): class first, then number
+fn tag_order((class, tag): (Class, Tag)) -> (u8, u32) {
+    (class as u8, tag.0)
+}
+
+fn issue(offset: usize, class: Class, tag: Tag, kind: CanonicalIssueKind) -> CanonicalIssue {
+    CanonicalIssue {
+        offset,
+        class,
+        tag,
+        kind,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::{ber_to_der, lint_der, CanonicalIssueKind, Class, InnerError, Input, Tag};
+
+    fn to_der(data: &[u8]) -> Vec<u8> {
+        let (rem, der) = ber_to_der(Input::from(data)).expect("canonicalization failed");
+        assert!(rem.is_empty());
+        assert!(der.is_complete(), "issues: {:?}", der.issues());
+        let der = der.into_vec();
+        assert!(lint_der(Input::from(&der)).is_valid());
+        der
+    }
+
+    #[test]
+    fn canonical_headers() {
+        // long form tag and length, indefinite length
+        assert_eq!(to_der(&hex!("1f 05 81 00")), hex!("05 00"));
+        assert_eq!(
+            to_der(&hex!("30 80 02 82 00 01 01 00 00")),
+            hex!("30 03 02 01 01")
+        );
+        // already DER
+        let data = hex!("30 09 02 01 01 bf 81 00 02 05 00");
+        let (_, der) = ber_to_der(Input::from(&data)).expect("canonicalization failed");
+        assert_eq!(der.as_bytes(), &data);
+        // long tag number is kept
+        assert_eq!(der.issues().len(), 0);
+    }
+
+    #[test]
+    fn canonical_values() {
+        // BOOLEAN, INTEGER, ENUMERATED
+        assert_eq!(to_der(&hex!("01 01 05")), hex!("01 01 ff"));
+        assert_eq!(to_der(&hex!("02 03 00 00 7f")), hex!("02 01 7f"));
+        assert_eq!(to_der(&hex!("02 03 00 00 80")), hex!("02 02 00 80"));
+        assert_eq!(to_der(&hex!("0a 03 ff ff 80")), hex!("0a 01 80"));
+        // BIT STRING unused bits
+        assert_eq!(to_der(&hex!("03 02 04 ff")), hex!("03 02 04 f0"));
+        assert_eq!(to_der(&hex!("03 01 03")), hex!("03 01 00"));
+        // errors
+        let r = ber_to_der(Input::from(&hex!("01 02 00 00")));
+        assert!(matches!(r, Err(nom::Err::Error(e)) if *e.inner() == InnerError::InvalidLength));
+        assert!(ber_to_der(Input::from(&hex!("03 02 08 00"))).is_err());
+    }
+
+    #[test]
+    fn canonical_strings() {
+        // nested constructed OCTET STRING
+        assert_eq!(
+            to_der(&hex!("24 80 04 01 61 24 06 04 01 62 04 01 63 00 00")),
+            hex!("04 03 61 62 63")
+        );
+        // constructed UTF8String
+        assert_eq!(
+            to_der(&hex!("2c 06 04 01 61 04 01 62")),
+            hex!("0c 02 61 62")
+        );
+        // constructed BIT STRING
+        assert_eq!(
+            to_der(&hex!("23 80 03 02 00 aa 03 02 04 bf 00 00")),
+            hex!("03 03 04 aa b0")
+        );
+        // unused bits in a segment which is not the last
+        assert!(ber_to_der(Input::from(&hex!("23 08 03 02 04 a0 03 02 00 aa"))).is_err());
+    }
+
+    /// Encode a primitive object with a universal `tag` and ASCII content
+    fn encode(tag: u8, content: &str) -> Vec<u8> {
+        let mut v = vec![tag, content.len() as u8];
+        v.extend_from_slice(content.as_bytes());
+        v
+    }
+
+    #[test]
+    fn canonical_times() {
+        const UTC: u8 = 0x17;
+        const GEN: u8 = 0x18;
+        let cases = [
+            // UTCTime: seconds, time zone
+            (UTC, "991231235959Z", "991231235959Z"),
+            (UTC, "2001021230Z", "200102123000Z"),
+            (UTC, "991231230000-0100", "000101000000Z"),
+            (UTC, "000101003000+0100", "991231233000Z"),
+            (UTC, "2402282330-0100", "240229003000Z"),
+            // GeneralizedTime: fractions of seconds
+            (GEN, "20240102030405Z", "20240102030405Z"),
+            (GEN, "20240102030405.500Z", "20240102030405.5Z"),
+            (GEN, "20240102030405.000Z", "20240102030405Z"),
+            (GEN, "20240102030405,25Z", "20240102030405.25Z"),
+            // GeneralizedTime: minutes and seconds, time zone, end of day
+            (GEN, "2024010203Z", "20240102030000Z"),
+            (GEN, "202401020304Z", "20240102030400Z"),
+            (GEN, "20240102030405,25+0130", "20240102013405.25Z"),
+            (GEN, "20240228235959-01", "20240229005959Z"),
+            (GEN, "20230228235959-0100", "20230301005959Z"),
+            (GEN, "20241231240000Z", "20250101000000Z"),
+        ];
+        for (tag, ber, der) in cases {
+            assert_eq!(to_der(&encode(tag, ber)), encode(tag, der), "{}", ber);
+        }
+
+        // constructed GeneralizedTime
+        let mut data = hex!("38 80 04 08").to_vec();
+        data.extend_from_slice(b"20240102");
+        data.extend_from_slice(&hex!("04 09"));
+        data.extend_from_slice(b"030405.0Z");
+        data.extend_from_slice(&hex!("00 00"));
+        assert_eq!(to_der(&data), encode(GEN, "20240102030405Z"));
+
+        // local time, fraction of minutes and invalid values are copied and reported
+        for (tag, ber) in [
+            (GEN, "20240102030405"),
+            (GEN, "202401020304.5Z"),
+            (GEN, "20240230030405Z"),
+            (GEN, "20240102030460Z"),
+            (UTC, "2401020304"),
+            (UTC, "240102030405.5Z"),
+        ] {
+            let data = encode(tag, ber);
+            let (rem, der) = ber_to_der(Input::from(&data)).expect("canonicalization failed");
+            assert!(rem.is_empty());
+            assert_eq!(der.as_bytes(), &data, "{}", ber);
+            assert!(!der.is_complete());
+            assert_eq!(der.issues()[0].kind(), CanonicalIssueKind::UnnormalizedTime);
+        }
+        // local time is not valid DER
+        let (_, der) = ber_to_der(Input::from(&encode(GEN, "20240102030405")))
+            .expect("canonicalization failed");
+        assert!(!der.is_complete());
+        assert!(!lint_der(Input::from(der.as_bytes())).is_valid());
+    }
+
+    #[test]
+    fn canonical_reals() {
+        let cases: &[(&[u8], &[u8])] = &[
+            // binary: odd mantissa, base 2, no scaling factor
+            (&hex!("09 03 80 02 01"), &hex!("09 03 80 02 01")),
+            (&hex!("09 03 80 00 04"), &hex!("09 03 80 02 01")),
+            (&hex!("09 04 80 00 01 00"), &hex!("09 03 80 08 01")),
+            (&hex!("09 04 80 00 01 02"), &hex!("09 03 80 01 81")),
+            (&hex!("09 03 a0 01 01"), &hex!("09 03 80 04 01")),
+            (&hex!("09 03 90 ff 03"), &hex!("09 03 80 fd 03")),
+            (&hex!("09 03 84 00 03"), &hex!("09 03 80 01 03")),
+            (&hex!("09 03 c0 00 06"), &hex!("09 03 c0 01 03")),
+            // binary: minimal exponent and mantissa
+            (&hex!("09 05 81 00 01 00 05"), &hex!("09 03 80 01 05")),
+            (&hex!("09 05 83 01 ff 00 05"), &hex!("09 03 80 ff 05")),
+            (&hex!("09 03 80 00 00"), &hex!("09 00")),
+            // decimal: NR3 form
+            (b"\x09\x04\x01120", b"\x09\x06\x0312.E1"),
+            (b"\x09\x06\x02-0,50", b"\x09\x07\x03-5.E-1"),
+            (b"\x09\x06\x03 1E+0", b"\x09\x06\x031.E+0"),
+            (b"\x09\x05\x030.E5", &hex!("09 00")),
+            // special values
+            (&hex!("09 01 40"), &hex!("09 01 40")),
+            (&hex!("09 00"), &hex!("09 00")),
+        ];
+        for (ber, der) in cases {
+            assert_eq!(to_der(ber), *der, "{:02x?}", ber);
+        }
+
+        // unsupported encodings are copied and reported
+        for data in [
+            &hex!("09 02 b0 00")[..],
+            &hex!("09 01 44"),
+            b"\x09\x03\x011a",
+        ] {
+            let (_, der) = ber_to_der(Input::from(data)).expect("canonicalization failed");
+            assert_eq!(der.as_bytes(), data);
+            assert_eq!(der.issues()[0].kind(), CanonicalIssueKind::UnnormalizedReal);
+        }
+    }
+
+    #[test]
+    fn canonical_sets() {
+        // SET OF, sorted by encoding
+        assert_eq!(
+            to_der(&hex!("31 09 02 01 03 02 01 01 02 01 02")),
+            hex!("31 09 02 01 01 02 01 02 02 01 03")
+        );
+        // SET OF, canonicalized before sorting
+        assert_eq!(
+            to_der(&hex!("31 80 02 02 00 01 02 01 00 00 00")),
+            hex!("31 06 02 01 00 02 01 01")
+        );
+        // SET, sorted by tag (class, then number)
+        let data = hex!("31 0a a1 03 02 01 01 80 00 01 01 00");
+        let (_, der) = ber_to_der(Input::from(&data)).expect("canonicalization failed");
+        assert_eq!(der.as_bytes(), &hex!("31 0a 01 01 00 80 00 a1 03 02 01 01"));
+    }
+
+    #[test]
+    fn canonical_issues() {
+        // [0] IMPLICIT value, and [1] with two components
+        let data = hex!("30 0b 80 01 05 a1 06 01 01 01 02 01 01");
+        let (_, der) = ber_to_der(Input::from(&data)).expect("canonicalization failed");
+        assert_eq!(
+            der.as_bytes(),
+            &hex!("30 0b 80 01 05 a1 06 01 01 ff 02 01 01")
+        );
+        let issues: Vec<_> = der
+            .issues()
+            .iter()
+            .map(|i| (i.offset(), i.class(), i.tag(), i.kind()))
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                (
+                    2,
+                    Class::ContextSpecific,
+                    Tag(0),
+                    CanonicalIssueKind::ImplicitPrimitive
+                ),
+                (
+                    5,
+                    Class::ContextSpecific,
+                    Tag(1),
+                    CanonicalIssueKind::ImplicitConstructed
+                ),
+            ]
+        );
+        // explicit tag is not reported
+        let (_, der) = ber_to_der(Input::from(&hex!("a0 80 01 01 01 00 00")))
+            .expect("canonicalization failed");
+        assert_eq!(der.as_bytes(), &hex!("a0 03 01 01 ff"));
+        assert!(der.is_complete());
+    }
+}
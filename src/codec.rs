@@ -0,0 +1,255 @@
+use core::marker::PhantomData;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::ber::MAX_RECURSION;
+use crate::header::raw_header_len;
+use crate::{
+    ASN1Mode, BerMode, BerParser, DerMode, DerParser, Error, Header, Input, IoWriter, Length,
+    ReadError, SerializeError, ToBer, ToDer,
+};
+
+/// Default maximum size of a PDU (8 MiB)
+pub const DEFAULT_MAX_PDU_SIZE: usize = 8 * 1024 * 1024;
+
+/// Codec framing complete BER/DER objects (PDUs) on byte streams
+///
+/// This type implements the `tokio-util` [`Decoder`] and [`Encoder`] traits, and can be used
+/// with `Framed`, `FramedRead` or `FramedWrite` to read or write PDUs on async streams, for
+/// ex. for protocols like LDAP, SNMP over TCP or Kerberos over TCP.
+///
+/// The decoder returns the complete encoding (header and content) of each PDU as [`Bytes`],
+/// which can then be parsed using [`BerParser`] or [`DerParser`]. Only headers are parsed to
+/// find the end of a PDU, so the decoder only waits until the full PDU is received. For BER,
+/// PDUs with an indefinite length are supported: headers of sub-objects are parsed to find the
+/// End-Of-Content terminating the PDU.
+///
+/// PDUs larger than the maximum size (default: [`DEFAULT_MAX_PDU_SIZE`]) are rejected with
+/// [`ReadError::ObjectTooLarge`].
+///
+/// The encoder writes objects implementing [`ToBer`] (for [`BerCodec`]) or [`ToDer`] (for
+/// [`DerCodec`]).
+///
+/// This type is usually used through the [`BerCodec`] and [`DerCodec`] aliases. The DER codec
+/// rejects indefinite lengths.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{BerCodec, Input, Integer, BerParser};
+/// use bytes::BytesMut;
+/// use tokio_util::codec::Decoder;
+///
+/// let mut codec = BerCodec::new();
+/// let mut buf = BytesMut::new();
+///
+/// // partial PDU
+/// buf.extend_from_slice(&[0x30, 0x80, 0x02, 0x01]);
+/// assert!(codec.decode(&mut buf).expect("decoding failed").is_none());
+///
+/// buf.extend_from_slice(&[0x01, 0x00, 0x00, 0x02, 0x01]);
+/// let pdu = codec.decode(&mut buf).expect("decoding failed").expect("no PDU");
+/// assert_eq!(&pdu[..], &[0x30, 0x80, 0x02, 0x01, 0x01, 0x00, 0x00]);
+/// assert_eq!(&buf[..], &[0x02, 0x01]);
+///
+/// let (_, seq) = <(Integer,)>::parse_ber(Input::from(&pdu[..])).expect("parsing failed");
+/// assert_eq!(seq.0.as_u32(), Ok(1));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "codec")))]
+#[derive(Debug, Clone)]
+pub struct AnyCodec<Mode>
+where
+    Mode: ASN1Mode,
+{
+    der: bool,
+    max_pdu_size: usize,
+    max_depth: usize,
+    /// Length of the current PDU known so far
+    scanned: usize,
+    /// Number of objects with indefinite length not terminated
+    depth: usize,
+    started: bool,
+    _mode: PhantomData<fn() -> Mode>,
+}
+
+/// Codec framing BER objects (see [`AnyCodec`])
+#[cfg_attr(docsrs, doc(cfg(feature = "codec")))]
+pub type BerCodec = AnyCodec<BerMode>;
+
+/// Codec framing DER objects (see [`AnyCodec`])
+#[cfg_attr(docsrs, doc(cfg(feature = "codec")))]
+pub type DerCodec = AnyCodec<DerMode>;
+
+impl AnyCodec<BerMode> {
+    /// Build a new codec for BER objects
+    pub const fn new() -> Self {
+        Self::with_rules(false)
+    }
+}
+
+impl Default for AnyCodec<BerMode> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnyCodec<DerMode> {
+    /// Build a new codec for DER objects
+    pub const fn new() -> Self {
+        Self::with_rules(true)
+    }
+}
+
+impl Default for AnyCodec<DerMode> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Mode> AnyCodec<Mode>
+where
+    Mode: ASN1Mode,
+{
+    const fn with_rules(der: bool) -> Self {
+        AnyCodec {
+            der,
+            max_pdu_size: DEFAULT_MAX_PDU_SIZE,
+            max_depth: MAX_RECURSION,
+            scanned: 0,
+            depth: 0,
+            started: false,
+            _mode: PhantomData,
+        }
+    }
+
+    /// Set the maximum size of a PDU, including header (default: [`DEFAULT_MAX_PDU_SIZE`])
+    pub const fn with_max_pdu_size(self, max_pdu_size: usize) -> Self {
+        Self {
+            max_pdu_size,
+            ..self
+        }
+    }
+
+    /// Set the maximum nesting depth of objects with indefinite length (default:
+    /// [`MAX_RECURSION`])
+    pub const fn with_max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    /// Return the maximum size of a PDU
+    #[inline]
+    pub const fn max_pdu_size(&self) -> usize {
+        self.max_pdu_size
+    }
+
+    fn reset(&mut self) {
+        self.scanned = 0;
+        self.depth = 0;
+        self.started = false;
+    }
+
+    /// Find the end of the current PDU, and return its length if complete
+    ///
+    /// Progress is kept between calls, so headers are only parsed once.
+    fn scan(&mut self, src: &mut BytesMut) -> Result<Option<usize>, ReadError> {
+        loop {
+            if self.scanned > self.max_pdu_size {
+                return Err(ReadError::ObjectTooLarge);
+            }
+            if self.started && self.depth == 0 {
+                if src.len() < self.scanned {
+                    src.reserve(self.scanned - src.len());
+                    return Ok(None);
+                }
+                return Ok(Some(self.scanned));
+            }
+            let bytes = src.get(self.scanned..).unwrap_or_default();
+            let Some(header_len) = raw_header_len(bytes) else {
+                src.reserve(1);
+                return Ok(None);
+            };
+            let raw = &bytes[..header_len];
+            // End-Of-Content of an object with indefinite length
+            if self.depth > 0 && raw == [0, 0] {
+                self.scanned += header_len;
+                self.depth -= 1;
+                continue;
+            }
+            let header = self.parse_header(raw)?;
+            self.started = true;
+            self.scanned += header_len;
+            match header.length() {
+                Length::Definite(l) => {
+                    self.scanned = self
+                        .scanned
+                        .checked_add(l)
+                        .ok_or(ReadError::ObjectTooLarge)?;
+                }
+                Length::Indefinite => {
+                    if self.depth >= self.max_depth {
+                        return Err(Error::BerMaxDepth.into());
+                    }
+                    self.depth += 1;
+                }
+            }
+        }
+    }
+
+    fn parse_header<'a>(&self, raw: &'a [u8]) -> Result<Header<'a>, ReadError> {
+        let input = Input::from_slice(raw);
+        let r = if self.der {
+            Header::parse_der(input)
+        } else {
+            Header::parse_ber(input)
+        };
+        let (_, header) = r.map_err(Error::from_nom_berr)?;
+        Ok(header)
+    }
+}
+
+impl<Mode> Decoder for AnyCodec<Mode>
+where
+    Mode: ASN1Mode,
+{
+    type Item = Bytes;
+    type Error = ReadError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.scan(src) {
+            Ok(Some(len)) => {
+                self.reset();
+                Ok(Some(src.split_to(len).freeze()))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => {
+                self.reset();
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<T: ToBer> Encoder<T> for AnyCodec<BerMode> {
+    type Error = SerializeError;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        if let Length::Definite(len) = item.ber_total_len() {
+            dst.reserve(len);
+        }
+        item.write_ber(&mut IoWriter::new(dst.writer()))?;
+        Ok(())
+    }
+}
+
+impl<T: ToDer> Encoder<T> for AnyCodec<DerMode> {
+    type Error = SerializeError;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        if let Length::Definite(len) = item.der_total_len() {
+            dst.reserve(len);
+        }
+        item.write_der(&mut IoWriter::new(dst.writer()))?;
+        Ok(())
+    }
+}
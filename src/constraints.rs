@@ -0,0 +1,150 @@
+/// Value range constraint (X.680: 51.4)
+///
+/// This constraint applies to `INTEGER` values. It is also used to give the number of root
+/// items of an [`Enumerated`](struct@crate::Enumerated) value.
+///
+/// Bounds are inclusive. A missing bound means the value is not constrained in this direction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ValueConstraint {
+    /// Lower bound (inclusive)
+    pub lower: Option<i128>,
+    /// Upper bound (inclusive)
+    pub upper: Option<i128>,
+    /// `true` if the constraint has an extension marker (`...`)
+    pub extensible: bool,
+}
+
+impl ValueConstraint {
+    /// Unconstrained value
+    pub const NONE: ValueConstraint = ValueConstraint::new(None, None, false);
+
+    /// Build a new value constraint
+    #[inline]
+    pub const fn new(lower: Option<i128>, upper: Option<i128>, extensible: bool) -> Self {
+        ValueConstraint {
+            lower,
+            upper,
+            extensible,
+        }
+    }
+
+    /// Test if `value` is in the root of the constraint
+    pub const fn contains(&self, value: i128) -> bool {
+        if let Some(lower) = self.lower {
+            if value < lower {
+                return false;
+            }
+        }
+        if let Some(upper) = self.upper {
+            if value > upper {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Size constraint (X.680: 51.5)
+///
+/// This constraint applies to the number of items of strings, `SEQUENCE OF` and `SET OF` values.
+///
+/// Bounds are inclusive. A missing upper bound means the size is not bounded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SizeConstraint {
+    /// Lower bound (inclusive)
+    pub lower: usize,
+    /// Upper bound (inclusive)
+    pub upper: Option<usize>,
+    /// `true` if the constraint has an extension marker (`...`)
+    pub extensible: bool,
+}
+
+impl SizeConstraint {
+    /// Unconstrained size
+    pub const NONE: SizeConstraint = SizeConstraint::new(0, None, false);
+
+    /// Build a new size constraint
+    #[inline]
+    pub const fn new(lower: usize, upper: Option<usize>, extensible: bool) -> Self {
+        SizeConstraint {
+            lower,
+            upper,
+            extensible,
+        }
+    }
+
+    /// Test if `size` is in the root of the constraint
+    pub const fn contains(&self, size: usize) -> bool {
+        if size < self.lower {
+            return false;
+        }
+        match self.upper {
+            Some(upper) => size <= upper,
+            None => true,
+        }
+    }
+
+    /// Return the size if the constraint allows only one value
+    pub const fn fixed(&self) -> Option<usize> {
+        match self.upper {
+            Some(upper) if upper == self.lower => Some(upper),
+            _ => None,
+        }
+    }
+}
+
+/// Constraints visible to encoding rules that depend on them (PER and OER)
+///
+/// TLV encodings (BER/DER) do not depend on constraints, and ignore this type.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::Constraints;
+///
+/// // INTEGER (0..255)
+/// let c = Constraints::NONE.with_value(Some(0), Some(255), false);
+/// assert!(c.value.contains(42));
+///
+/// // OCTET STRING (SIZE(1..16, ...))
+/// let c = Constraints::NONE.with_size(1, Some(16), true);
+/// assert!(!c.size.contains(0));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Constraints {
+    /// Value range constraint
+    pub value: ValueConstraint,
+    /// Size constraint
+    pub size: SizeConstraint,
+}
+
+impl Constraints {
+    /// No constraints
+    pub const NONE: Constraints = Constraints {
+        value: ValueConstraint::NONE,
+        size: SizeConstraint::NONE,
+    };
+
+    /// Return a copy of `self` with the provided value constraint
+    #[inline]
+    pub const fn with_value(
+        self,
+        lower: Option<i128>,
+        upper: Option<i128>,
+        extensible: bool,
+    ) -> Self {
+        Constraints {
+            value: ValueConstraint::new(lower, upper, extensible),
+            size: self.size,
+        }
+    }
+
+    /// Return a copy of `self` with the provided size constraint
+    #[inline]
+    pub const fn with_size(self, lower: usize, upper: Option<usize>, extensible: bool) -> Self {
+        Constraints {
+            value: self.value,
+            size: SizeConstraint::new(lower, upper, extensible),
+        }
+    }
+}
@@ -1,5 +1,10 @@
 use crate::{Result, Tag};
 use alloc::format;
+#[cfg(all(
+    not(feature = "std"),
+    any(feature = "jer", feature = "xer", feature = "value-notation")
+))]
+use alloc::string::String;
 #[cfg(not(feature = "std"))]
 use alloc::string::ToString;
 use core::fmt;
@@ -99,6 +104,19 @@ impl fmt::Display for ASN1DateTime {
     }
 }
 
+/// Format the time zone of a time value, as in its BER encoding
+#[cfg(any(feature = "jer", feature = "xer", feature = "value-notation"))]
+pub(crate) fn format_time_zone(tz: ASN1TimeZone) -> String {
+    match tz {
+        ASN1TimeZone::Undefined => String::new(),
+        ASN1TimeZone::Z => "Z".into(),
+        ASN1TimeZone::Offset(hh, mm) => {
+            let sign = if hh < 0 || mm < 0 { '-' } else { '+' };
+            format!("{sign}{:02}{:02}", hh.unsigned_abs(), mm.unsigned_abs())
+        }
+    }
+}
+
 /// Decode 2-digit decimal value
 pub(crate) fn decode_decimal(tag: Tag, hi: u8, lo: u8) -> Result<u8> {
     if hi.is_ascii_digit() && lo.is_ascii_digit() {
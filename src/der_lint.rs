@@ -0,0 +1,520 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use nom::Input as _;
+
+use crate::ber::GetObjectContent;
+use crate::{
+    check_der_int_constraints_input, cmp_set_of_encodings, BerError, BerMode, BerParser, Class,
+    DerConstraint, Header, InnerError, Input, Length, Tag,
+};
+
+/// A DER constraint violation, found by [`lint_der`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DerViolation {
+    offset: usize,
+    class: Class,
+    tag: Tag,
+    constraint: DerConstraint,
+}
+
+impl DerViolation {
+    /// Return the absolute offset of the object violating the constraint
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Return the class of the object violating the constraint
+    #[inline]
+    pub const fn class(&self) -> Class {
+        self.class
+    }
+
+    /// Return the tag of the object violating the constraint
+    #[inline]
+    pub const fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Return the violated constraint
+    #[inline]
+    pub const fn constraint(&self) -> DerConstraint {
+        self.constraint
+    }
+}
+
+impl fmt::Display for DerViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "offset {}: [{} {}]: {}",
+            self.offset, self.class, self.tag.0, self.constraint
+        )
+    }
+}
+
+/// Result of [`lint_der`]: all DER violations found in the input
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DerLintReport {
+    violations: Vec<DerViolation>,
+    error: Option<(usize, InnerError)>,
+}
+
+impl DerLintReport {
+    /// Return the DER violations, ordered by offset of objects
+    #[inline]
+    pub fn violations(&self) -> &[DerViolation] {
+        &self.violations
+    }
+
+    /// Return the error (and its absolute offset) which stopped the walk, if any
+    ///
+    /// Errors are raised when the input cannot be parsed as BER (for ex. if it is truncated).
+    /// Violations found before the error are still reported.
+    #[inline]
+    pub fn error(&self) -> Option<(usize, &InnerError)> {
+        self.error.as_ref().map(|(offset, e)| (*offset, e))
+    }
+
+    /// Return `true` if the input is valid DER (no violation, and no error)
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty() && self.error.is_none()
+    }
+
+    fn push(&mut self, header: &Header, offset: usize, constraint: DerConstraint) {
+        self.violations.push(DerViolation {
+            offset,
+            class: header.class(),
+            tag: header.tag(),
+            constraint,
+        });
+    }
+}
+
+/// Walk all objects of `input`, and collect every DER constraint violation
+///
+/// Unlike DER parsers (or [`CheckDerConstraints`](crate::CheckDerConstraints)), which stop on
+/// the first error, this function parses input as BER and reports all violations found in
+/// objects and sub-objects. Schema is not known, so only the following rules (X.690 section 10
+/// and 11) are checked:
+///
+/// - all objects: minimal encoding of tags and lengths, definite lengths
+/// - universal types:
+///   - constructed encoding of strings, and primitive encoding of `SEQUENCE` and `SET`
+///   - `BOOLEAN` values, `INTEGER` and `ENUMERATED` leading octets, `BIT STRING` unused bits
+///   - `UTCTime` and `GeneralizedTime` formatting
+///   - ordering of `SET` (if components have the same tag, as `SET OF`, using their encodings)
+///
+/// Content of objects with non-universal tags is walked only if constructed (implicitly tagged
+/// values are not checked).
+///
+/// If the input cannot be parsed as BER, the walk stops and the error is returned in the report.
+/// The maximum depth is defined by the input [`Limits`](crate::Limits).
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{lint_der, DerConstraint, Input};
+/// use hex_literal::hex;
+///
+/// // SEQUENCE (length in long form) { BOOLEAN 0x01, INTEGER with leading zero }
+/// let data = hex!("30 81 07 01 01 01 02 02 00 01");
+/// let report = lint_der(Input::from(&data));
+/// let violations: Vec<_> = report
+///     .violations()
+///     .iter()
+///     .map(|v| (v.offset(), v.constraint()))
+///     .collect();
+/// assert_eq!(
+///     violations,
+///     [
+///         (0, DerConstraint::NonMinimalLength),
+///         (3, DerConstraint::InvalidBoolean),
+///         (6, DerConstraint::IntegerLeadingZeroes),
+///     ]
+/// );
+/// assert!(report.error().is_none());
+/// ```
+pub fn lint_der(input: Input<'_>) -> DerLintReport {
+    let mut report = DerLintReport::default();
+    let max_depth = input.limits().max_depth();
+    let mut input = input;
+    while !input.is_empty() {
+        match lint_object(input, max_depth, &mut report) {
+            Ok((rem, _)) => input = rem,
+            Err(e) => {
+                report.error = Some((e.input().start(), e.inner().clone()));
+                break;
+            }
+        }
+    }
+    report
+}
+
+fn lint_object<'i>(
+    input: Input<'i>,
+    max_depth: usize,
+    report: &mut DerLintReport,
+) -> Result<(Input<'i>, Header<'i>), BerError<Input<'i>>> {
+    if max_depth == 0 {
+        return Err(BerError::new(input, InnerError::BerMaxDepth));
+    }
+    let offset = input.start();
+    let (rem, header) = Header::parse_ber(input.clone()).map_err(flatten_err(&input))?;
+    lint_header(&header, offset, report);
+    let (rem, content) = BerMode::get_object_content(&header, rem.clone(), max_depth)
+        .map_err(flatten_err(&input))?;
+
+    if !header.is_constructed() {
+        if header.class() == Class::Universal {
+            if let Some(constraint) = lint_primitive(header.tag(), &content) {
+                report.push(&header, offset, constraint);
+            }
+            if matches!(header.tag(), Tag::Sequence | Tag::Set) {
+                report.push(&header, offset, DerConstraint::NotConstructed);
+            }
+        }
+        return Ok((rem, header));
+    }
+
+    if header.class() == Class::Universal && is_primitive_type(header.tag()) {
+        report.push(&header, offset, DerConstraint::Constructed);
+    }
+    // components, with their encoding
+    let mut components = Vec::new();
+    let mut i = content;
+    while !i.is_empty() {
+        let start = i.clone();
+        let (r, component) = lint_object(i, max_depth - 1, report)?;
+        let encoding = start.take(r.start() - start.start());
+        components.push((component, encoding));
+        i = r;
+    }
+    if header.class() == Class::Universal && header.tag() == Tag::Set {
+        if let Some(constraint) = lint_set_order(&components) {
+            report.push(&header, offset, constraint);
+        }
+    }
+    Ok((rem, header))
+}
+
+/// Check the encoding of tag and length (X.690: 8.1.2 and 10.1)
+fn lint_header(header: &Header, offset: usize, report: &mut DerLintReport) {
+    if let Some(raw_tag) = header.raw_tag() {
+        // long form is only allowed for tags >= 31, without leading 0x80 octets
+        let non_minimal = match raw_tag {
+            [_, 0x80, ..] => true,
+            [_, _, ..] => header.tag().0 < 31,
+            _ => false,
+        };
+        if non_minimal {
+            report.push(header, offset, DerConstraint::NonMinimalTag);
+        }
+    }
+    match header.length() {
+        Length::Indefinite => report.push(header, offset, DerConstraint::IndefiniteLength),
+        Length::Definite(l) => {
+            let raw_header = header
+                .raw_header()
+                .map(|h| h.as_bytes2())
+                .unwrap_or_default();
+            let tag_len = header.raw_tag().map(|t| t.len()).unwrap_or_default();
+            let raw_length = raw_header.get(tag_len..).unwrap_or_default();
+            // long form is only allowed for lengths >= 128, without leading zero
+            let non_minimal = match raw_length {
+                [_, 0, ..] => true,
+                [_, _, ..] => l < 128,
+                _ => false,
+            };
+            if non_minimal {
+                report.push(header, offset, DerConstraint::NonMinimalLength);
+            }
+        }
+    }
+}
+
+/// Check the content of a primitive object with a universal tag
+fn lint_primitive(tag: Tag, content: &Input) -> Option<DerConstraint> {
+    let bytes = content.as_bytes2();
+    match tag {
+        // X.690: 11.1
+        Tag::Boolean => match bytes {
+            [0x00] | [0xff] => None,
+            _ => Some(DerConstraint::InvalidBoolean),
+        },
+        // X.690: 8.3.2
+        Tag::Integer | Tag::Enumerated => check_der_int_constraints_input(content).err(),
+        // X.690: 8.6.2.2 and 11.2.1
+        Tag::BitString => match bytes {
+            [unused_bits, ..] if *unused_bits > 7 => Some(DerConstraint::InvalidUnusedBits),
+            [0] => None,
+            [_] => Some(DerConstraint::UnusedBitsNotZero),
+            [unused_bits, .., last] if last.trailing_zeros() < u32::from(*unused_bits) => {
+                Some(DerConstraint::UnusedBitsNotZero)
+            }
+            _ => None,
+        },
+        Tag::UtcTime => lint_time(bytes, 12, false),
+        Tag::GeneralizedTime => lint_time(bytes, 14, true),
+        _ => None,
+    }
+}
+
+/// Check the formatting of `UTCTime` (X.690: 11.8) and `GeneralizedTime` (X.690: 11.7)
+///
+/// `digits` is the number of digits up to (and including) the seconds.
+fn lint_time(bytes: &[u8], digits: usize, fraction: bool) -> Option<DerConstraint> {
+    let Some((b'Z', value)) = bytes.split_last() else {
+        return Some(DerConstraint::MissingTimeZone);
+    };
+    let num_digits = value.iter().take_while(|b| b.is_ascii_digit()).count();
+    if num_digits != digits {
+        return Some(DerConstraint::MissingSeconds);
+    }
+    match &value[num_digits..] {
+        [] => None,
+        [b'.', f @ ..]
+            if fraction
+                && !f.is_empty()
+                && f.iter().all(u8::is_ascii_digit)
+                && f.last() != Some(&b'0') =>
+        {
+            None
+        }
+        _ => Some(DerConstraint::InvalidFractionalSeconds),
+    }
+}
+
+/// Check the order of `SET` components (X.690: 10.3 and 11.6)
+///
+/// Without schema, a `SET` and a `SET OF` (for ex. of a `CHOICE`) cannot be distinguished, so
+/// components are accepted if they are sorted either by encoding, or by tag. If not, the `SET` is
+/// assumed to be a `SET OF` if two components have the same tag.
+fn lint_set_order(components: &[(Header, Input)]) -> Option<DerConstraint> {
+    // SET OF: encodings are compared, including identifier octets (X.690: 11.6)
+    let sorted_by_encoding = components
+        .windows(2)
+        .all(|w| cmp_set_of_encodings(w[0].1.as_bytes2(), w[1].1.as_bytes2()).is_le());
+    if sorted_by_encoding {
+        return None;
+    }
+    // SET: tags are distinct, and compared ignoring the constructed bit (X.690: 10.3)
+    let tag_order = |h: &Header| (h.class() as u8, h.tag().0);
+    let sorted_by_tag = components
+        .windows(2)
+        .all(|w| tag_order(&w[0].0) < tag_order(&w[1].0));
+    if sorted_by_tag {
+        return None;
+    }
+    let mut tags: Vec<_> = components.iter().map(|(h, _)| tag_order(h)).collect();
+    tags.sort_unstable();
+    if tags.windows(2).any(|w| w[0] == w[1]) {
+        Some(DerConstraint::SetOfUnsorted)
+    } else {
+        Some(DerConstraint::SetUnsorted)
+    }
+}
+
+/// Return `true` if universal `tag` must use the primitive encoding in DER (X.690: 10.2)
+///
+/// This includes strings, which can use the constructed encoding in BER (X.690: 8.21).
+fn is_primitive_type(tag: Tag) -> bool {
+    matches!(
+        tag,
+        Tag::Boolean
+            | Tag::Integer
+            | Tag::BitString
+            | Tag::OctetString
+            | Tag::Null
+            | Tag::Oid
+            | Tag::ObjectDescriptor
+            | Tag::RealType
+            | Tag::Enumerated
+            | Tag::Utf8String
+            | Tag::RelativeOid
+            | Tag::NumericString
+            | Tag::PrintableString
+            | Tag::TeletexString
+            | Tag::VideotexString
+            | Tag::Ia5String
+            | Tag::UtcTime
+            | Tag::GeneralizedTime
+            | Tag::GraphicString
+            | Tag::VisibleString
+            | Tag::GeneralString
+            | Tag::UniversalString
+            | Tag::BmpString
+    )
+}
+
+/// Convert a `nom` error to a `BerError` (`Incomplete` errors are located at `input`)
+fn flatten_err<'i>(
+    input: &Input<'i>,
+) -> impl Fn(nom::Err<BerError<Input<'i>>>) -> BerError<Input<'i>> {
+    let input = input.clone();
+    move |e| match e {
+        nom::Err::Error(e) | nom::Err::Failure(e) => e,
+        nom::Err::Incomplete(needed) => BerError::incomplete(input.clone(), needed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use hex_literal::hex;
+
+    use crate::{lint_der, DerConstraint, InnerError, Input, Limits, LimitsState, Tag};
+
+    fn violations(data: &[u8]) -> Vec<(usize, DerConstraint)> {
+        let report = lint_der(Input::from(data));
+        assert!(
+            report.error().is_none(),
+            "unexpected error: {:?}",
+            report.error()
+        );
+        report
+            .violations()
+            .iter()
+            .map(|v| (v.offset(), v.constraint()))
+            .collect()
+    }
+
+    #[test]
+    fn lint_valid() {
+        let data = hex!(
+            "30 24 01 01 ff 02 02 00 80 03 02 06 40 31 06 02 01 01 02 01 02
+             18 0f 32 30 32 34 30 31 30 32 30 33 30 34 30 35 5a"
+        );
+        let report = lint_der(Input::from(&data));
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn lint_headers() {
+        // long form tag for tag < 31, leading 0x80 in tag, non-minimal lengths
+        assert_eq!(
+            violations(&hex!("1f 02 01 00 9f 80 21 00 04 81 01 00 04 82 00 01 00")),
+            [
+                (0, DerConstraint::NonMinimalTag),
+                (4, DerConstraint::NonMinimalTag),
+                (8, DerConstraint::NonMinimalLength),
+                (12, DerConstraint::NonMinimalLength),
+            ]
+        );
+        // indefinite length, and constructed string
+        assert_eq!(
+            violations(&hex!("30 80 24 80 04 01 aa 00 00 00 00")),
+            [
+                (0, DerConstraint::IndefiniteLength),
+                (2, DerConstraint::IndefiniteLength),
+                (2, DerConstraint::Constructed),
+            ]
+        );
+        // primitive SEQUENCE
+        assert_eq!(
+            violations(&hex!("10 00")),
+            [(0, DerConstraint::NotConstructed)]
+        );
+        // long tags are accepted
+        assert!(violations(&hex!("9f 21 00 bf 81 00 00")).is_empty());
+    }
+
+    #[test]
+    fn lint_values() {
+        // all violations are reported, in order
+        let data = hex!(
+            "30 1d 01 01 01 02 02 ff ff 02 00 03 02 07 81 03 01 07
+             17 0b 39 39 30 31 30 32 30 33 30 34 5a"
+        );
+        assert_eq!(
+            violations(&data),
+            [
+                (2, DerConstraint::InvalidBoolean),
+                (5, DerConstraint::IntegerLeadingFF),
+                (9, DerConstraint::IntegerEmpty),
+                (11, DerConstraint::UnusedBitsNotZero),
+                (15, DerConstraint::UnusedBitsNotZero),
+                (18, DerConstraint::MissingSeconds),
+            ]
+        );
+        // BIT STRING: unused bits greater than 7 (invalid in BER too)
+        assert_eq!(
+            violations(&hex!("03 02 08 00 03 01 09")),
+            [
+                (0, DerConstraint::InvalidUnusedBits),
+                (4, DerConstraint::InvalidUnusedBits),
+            ]
+        );
+        // GeneralizedTime
+        for (value, constraint) in [
+            (&b"20240102030405"[..], Some(DerConstraint::MissingTimeZone)),
+            (b"202401020304Z", Some(DerConstraint::MissingSeconds)),
+            (
+                b"20240102030405,5Z",
+                Some(DerConstraint::InvalidFractionalSeconds),
+            ),
+            (
+                b"20240102030405.50Z",
+                Some(DerConstraint::InvalidFractionalSeconds),
+            ),
+            (
+                b"20240102030405.Z",
+                Some(DerConstraint::InvalidFractionalSeconds),
+            ),
+            (b"20240102030405.05Z", None),
+        ] {
+            let mut data = Vec::from([0x18, value.len() as u8]);
+            data.extend_from_slice(value);
+            let expected: Vec<_> = constraint.into_iter().map(|c| (0, c)).collect();
+            assert_eq!(violations(&data), expected, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn lint_sets() {
+        // SET OF, sorted by encoding
+        assert!(violations(&hex!("31 06 02 01 01 02 01 02")).is_empty());
+        assert_eq!(
+            violations(&hex!("31 06 02 01 02 02 01 01")),
+            [(0, DerConstraint::SetOfUnsorted)]
+        );
+        // SET, sorted by tag
+        assert!(violations(&hex!("31 06 01 01 ff 02 01 01")).is_empty());
+        assert_eq!(
+            violations(&hex!("31 06 02 01 01 01 01 ff")),
+            [(0, DerConstraint::SetUnsorted)]
+        );
+        assert_eq!(
+            violations(&hex!("31 06 80 01 01 02 01 01")),
+            [(0, DerConstraint::SetUnsorted)]
+        );
+        // SET OF CHOICE, sorted by encoding (including the constructed bit)
+        assert!(violations(&hex!("31 07 81 01 01 a0 02 05 00")).is_empty());
+        assert_eq!(
+            violations(&hex!("31 0a 81 01 01 a0 02 05 00 81 01 02")),
+            [(0, DerConstraint::SetOfUnsorted)]
+        );
+        // SET, sorted by tag (ignoring the constructed bit)
+        assert!(violations(&hex!("31 07 a0 02 05 00 81 01 01")).is_empty());
+    }
+
+    #[test]
+    fn lint_errors() {
+        // violations before the error are reported
+        let report = lint_der(Input::from(&hex!("30 06 01 01 01 02 03 01")));
+        assert_eq!(report.violations().len(), 1);
+        assert_eq!(report.violations()[0].tag(), Tag::Boolean);
+        assert!(matches!(
+            report.error(),
+            Some((5, InnerError::Incomplete(_)))
+        ));
+        assert!(!report.is_valid());
+
+        let data = hex!("30 80 30 80 30 80 00 00 00 00 00 00");
+        let limits = LimitsState::new(Limits::new().with_max_depth(2));
+        let report = lint_der(Input::from(&data).with_limits(&limits));
+        assert!(matches!(report.error(), Some((_, InnerError::BerMaxDepth))));
+    }
+}
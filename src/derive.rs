@@ -946,3 +946,15 @@ pub use asn1_rs_derive::Set;
 ///
 /// See [Alias types](crate::doc::derive#type-alias) section in 'derive' documentation.
 pub use asn1_rs_derive::Alias;
+
+/// Test if `value` is equal to the DEFAULT value of a field
+///
+/// Fields with a `#[default(...)]` attribute are compared to their default value (for ex. DER and
+/// CER omit DEFAULT values when encoding), so their type must implement `PartialEq`.
+///
+/// Note: used in custom derive
+#[doc(hidden)]
+#[inline]
+pub fn is_default_value<T: PartialEq>(value: &T, default: &T) -> bool {
+    value == default
+}
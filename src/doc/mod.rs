@@ -5,6 +5,17 @@ pub mod recipes {}
 
 #[cfg(feature = "std")]
 #[doc = include_str!("../../doc/DERIVE.md")]
+// links to optional modules (encoding rules, value notation) only resolve when their features are enabled
+#[cfg_attr(
+    not(all(
+        feature = "per",
+        feature = "oer",
+        feature = "jer",
+        feature = "xer",
+        feature = "value-notation"
+    )),
+    allow(rustdoc::broken_intra_doc_links)
+)]
 pub mod derive {}
 
 #[doc = include_str!("../../doc/DEBUG.md")]
@@ -0,0 +1,462 @@
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+use nom::{Err, IResult, Input as _};
+
+use crate::ber::GetObjectContent;
+use crate::{Any, BerError, Class, DerMode, DerParser, Header, InnerError, Input};
+
+/// Entry of the node table of a [`DerDocument`]
+#[derive(Clone, Debug)]
+struct Node<'a> {
+    header: Header<'a>,
+    /// Complete encoding (header and content)
+    raw: Input<'a>,
+    /// Content
+    data: Input<'a>,
+    depth: usize,
+    parent: Option<usize>,
+    first_child: Option<usize>,
+    next_sibling: Option<usize>,
+    num_children: usize,
+}
+
+/// Indexed DER document, for random access to objects
+///
+/// The document is parsed once, and all objects (including sub-objects of constructed objects)
+/// are stored in a flat node table. Nodes can then be accessed by index, by child index (see
+/// [`DerNode::child`]) or by path (see [`DerDocument::get`]), and return [`Any`] views on the
+/// input without parsing again.
+///
+/// Only headers are parsed: content of primitive objects is not checked. Primitive objects
+/// are not parsed, even if they contain encapsulated objects (for ex. an `OCTET STRING`
+/// containing a DER object): use another `DerDocument` to index them.
+///
+/// Nodes are stored in breadth-first order, so the children of a node are contiguous: access
+/// to a child by index is done in constant time.
+///
+/// # Paths
+///
+/// A path is a list of segments separated by `/`, each one selecting a child of the current
+/// node (starting from the root object):
+/// - a number selects the child at this index (starting at 0)
+/// - a tag in brackets selects the first child with this tag: `[ctx 3]` (or `[3]`) for a
+///   context-specific tag, `[app 1]`, `[priv 2]`, or `[univ 16]`.
+///
+/// For ex, in a X.509 certificate, the path `/0/[ctx 3]` selects the extensions of the
+/// `tbsCertificate` object. The empty path (or `/`) selects the root object.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{DerDocument, Input, Tag};
+/// use hex_literal::hex;
+///
+/// // SEQUENCE { INTEGER 1, [3] { SEQUENCE { BOOLEAN TRUE } }, NULL }
+/// let data = hex!("30 0c 02 01 01 a3 05 30 03 01 01 ff 05 00");
+/// let (_, doc) = DerDocument::parse_der(Input::from(&data)).expect("parsing failed");
+///
+/// let node = doc.get("/[ctx 3]/0/0").expect("path not found");
+/// assert_eq!(node.any().as_bool(), Ok(true));
+/// assert_eq!(node.parent().map(|n| n.header().tag()), Some(Tag::Sequence));
+///
+/// let root = doc.root();
+/// assert_eq!(root.num_children(), 3);
+/// assert_eq!(root.child(2).map(|n| n.header().tag()), Some(Tag::Null));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DerDocument<'a> {
+    nodes: Vec<Node<'a>>,
+}
+
+impl<'a> DerDocument<'a> {
+    /// Parse a DER object and all its sub-objects, and build the node table
+    ///
+    /// Return the remaining input (after the root object) and the document.
+    ///
+    /// The [`Limits`](crate::Limits) of the input are applied to all sub-objects.
+    pub fn parse_der(input: Input<'a>) -> IResult<Input<'a>, Self, BerError<Input<'a>>> {
+        let limits = input.limits();
+        let (max_depth, max_elements) = (limits.max_depth(), limits.max_elements());
+        let (rem, root) = parse_node(input, None, 0)?;
+        let mut nodes = alloc::vec![root];
+        let mut index = 0;
+        while index < nodes.len() {
+            let node = &nodes[index];
+            if node.header.is_constructed() {
+                if node.depth >= max_depth {
+                    return Err(Err::Error(BerError::new(
+                        node.raw.clone(),
+                        InnerError::BerMaxDepth,
+                    )));
+                }
+                let depth = node.depth + 1;
+                let first = nodes.len();
+                let mut content = node.data.clone();
+                while !content.is_empty() {
+                    if nodes.len() - first >= max_elements {
+                        return Err(BerError::nom_err_input(
+                            &nodes[index].raw,
+                            InnerError::BerMaxElements,
+                        ));
+                    }
+                    let (i, child) =
+                        parse_node(content, Some(index), depth).map_err(|e| match e {
+                            // content is complete: sub-object is larger than its parent
+                            Err::Incomplete(_) => BerError::nom_err_input(
+                                &nodes[index].raw,
+                                InnerError::InvalidLength,
+                            ),
+                            e => e,
+                        })?;
+                    let child_index = nodes.len();
+                    if child_index > first {
+                        nodes[child_index - 1].next_sibling = Some(child_index);
+                    }
+                    nodes.push(child);
+                    content = i;
+                }
+                if nodes.len() > first {
+                    nodes[index].first_child = Some(first);
+                    nodes[index].num_children = nodes.len() - first;
+                }
+            }
+            index += 1;
+        }
+        Ok((rem, DerDocument { nodes }))
+    }
+
+    /// Return the number of nodes (objects and sub-objects) in the document
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return `true` if the document contains no node
+    ///
+    /// This is never the case for a parsed document, which contains at least the root object.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Return the root object
+    pub fn root(&self) -> DerNode<'_, 'a> {
+        DerNode {
+            doc: self,
+            index: 0,
+        }
+    }
+
+    /// Return the node with index `index` in the node table
+    pub fn node(&self, index: usize) -> Option<DerNode<'_, 'a>> {
+        if index < self.nodes.len() {
+            Some(DerNode { doc: self, index })
+        } else {
+            None
+        }
+    }
+
+    /// Return an iterator over all nodes (in breadth-first order)
+    pub fn nodes(&self) -> impl Iterator<Item = DerNode<'_, 'a>> {
+        (0..self.nodes.len()).map(move |index| DerNode { doc: self, index })
+    }
+
+    /// Return the node at `path`, starting from the root object
+    ///
+    /// See [`DerDocument`] for the syntax of paths. Return `None` if the path is invalid, or if
+    /// no node matches.
+    pub fn get(&self, path: &str) -> Option<DerNode<'_, 'a>> {
+        self.root().get(path)
+    }
+}
+
+fn parse_node(
+    input: Input<'_>,
+    parent: Option<usize>,
+    depth: usize,
+) -> IResult<Input<'_>, Node<'_>, BerError<Input<'_>>> {
+    let start = input.clone();
+    let (rem, header) = Header::parse_der(input)?;
+    let (rem, data) = DerMode::get_object_content(&header, rem, 0)?;
+    let raw = start.take(rem.start() - start.start());
+    let node = Node {
+        header,
+        raw,
+        data,
+        depth,
+        parent,
+        first_child: None,
+        next_sibling: None,
+        num_children: 0,
+    };
+    Ok((rem, node))
+}
+
+/// Reference to a node of a [`DerDocument`]
+#[derive(Clone, Copy, Debug)]
+pub struct DerNode<'d, 'a> {
+    doc: &'d DerDocument<'a>,
+    index: usize,
+}
+
+impl<'d, 'a> DerNode<'d, 'a> {
+    #[inline]
+    fn node(&self) -> &'d Node<'a> {
+        &self.doc.nodes[self.index]
+    }
+
+    fn to(&self, index: Option<usize>) -> Option<Self> {
+        index.map(|index| DerNode {
+            doc: self.doc,
+            index,
+        })
+    }
+
+    /// Return the index of this node in the node table
+    #[inline]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Return the offset of this object (start of header) in the input
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.node().raw.start()
+    }
+
+    /// Return the nesting depth of this object (`0` for the root object)
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.node().depth
+    }
+
+    /// Return the header of this object
+    #[inline]
+    pub fn header(&self) -> &'d Header<'a> {
+        &self.node().header
+    }
+
+    /// Return the content of this object
+    #[inline]
+    pub fn data(&self) -> Input<'a> {
+        self.node().data.clone()
+    }
+
+    /// Return the complete encoding (header and content) of this object
+    ///
+    /// This is useful for ex. to verify signatures on encoded data.
+    #[inline]
+    pub fn raw(&self) -> &'a [u8] {
+        self.node().raw.as_bytes2()
+    }
+
+    /// Return an `Any` view on this object
+    pub fn any(&self) -> Any<'a> {
+        let node = self.node();
+        Any::new(node.header.clone(), node.data.clone())
+    }
+
+    /// Return the parent of this node, or `None` for the root object
+    pub fn parent(&self) -> Option<Self> {
+        self.to(self.node().parent)
+    }
+
+    /// Return the next sibling of this node
+    pub fn next_sibling(&self) -> Option<Self> {
+        self.to(self.node().next_sibling)
+    }
+
+    /// Return the number of sub-objects
+    #[inline]
+    pub fn num_children(&self) -> usize {
+        self.node().num_children
+    }
+
+    /// Return the sub-object at index `n`
+    pub fn child(&self, n: usize) -> Option<Self> {
+        let node = self.node();
+        if n >= node.num_children {
+            return None;
+        }
+        self.to(node.first_child.map(|first| first + n))
+    }
+
+    /// Return an iterator over the sub-objects
+    pub fn children(&self) -> DerNodeChildren<'d, 'a> {
+        DerNodeChildren {
+            next: self.child(0),
+        }
+    }
+
+    /// Return the node at `path`, starting from this node
+    ///
+    /// See [`DerDocument`] for the syntax of paths. Return `None` if the path is invalid, or if
+    /// no node matches.
+    pub fn get(&self, path: &str) -> Option<Self> {
+        let mut node = *self;
+        for segment in path.split('/').filter(|s| !s.is_empty()) {
+            node = match segment.strip_prefix('[') {
+                Some(tag) => {
+                    let (class, tag) = parse_tag_segment(tag.strip_suffix(']')?)?;
+                    node.children()
+                        .find(|n| n.header().class() == class && n.header().tag().0 == tag)?
+                }
+                None => node.child(segment.parse().ok()?)?,
+            };
+        }
+        Some(node)
+    }
+}
+
+/// Parse the content of a tag segment of a path (for ex. `ctx 3`)
+fn parse_tag_segment(s: &str) -> Option<(Class, u32)> {
+    let mut words = s.split_whitespace();
+    let (class, tag) = match (words.next()?, words.next()) {
+        (tag, None) => (Class::ContextSpecific, tag),
+        (class, Some(tag)) => {
+            let class = match class {
+                "univ" | "universal" => Class::Universal,
+                "app" | "application" => Class::Application,
+                "ctx" | "context" => Class::ContextSpecific,
+                "priv" | "private" => Class::Private,
+                _ => return None,
+            };
+            (class, tag)
+        }
+    };
+    if words.next().is_some() {
+        return None;
+    }
+    Some((class, tag.parse().ok()?))
+}
+
+/// Iterator over the sub-objects of a [`DerNode`]
+#[derive(Clone, Debug)]
+pub struct DerNodeChildren<'d, 'a> {
+    next: Option<DerNode<'d, 'a>>,
+}
+
+impl<'d, 'a> Iterator for DerNodeChildren<'d, 'a> {
+    type Item = DerNode<'d, 'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next.take()?;
+        self.next = node.next_sibling();
+        Some(node)
+    }
+}
+
+impl FusedIterator for DerNodeChildren<'_, '_> {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use hex_literal::hex;
+
+    use crate::{DerDocument, InnerError, Input, Tag};
+
+    // Certificate-like structure:
+    // SEQUENCE {
+    //   SEQUENCE {
+    //     [0] { INTEGER 2 }
+    //     INTEGER 1234
+    //     SEQUENCE { OID 1.2.840.113549.1.1.11, NULL }
+    //     [3] { SEQUENCE { SEQUENCE { OID 2.5.29.19, OCTET STRING { 30 03 01 01 ff } } } }
+    //   }
+    //   SEQUENCE { OID 1.2.840.113549.1.1.11, NULL }
+    //   BIT STRING 00 aa
+    // }
+    const CERT: &[u8] = &hex!(
+        "30 3f
+            30 2a
+                a0 03 02 01 02
+                02 02 04 d2
+                30 0d 06 09 2a 86 48 86 f7 0d 01 01 0b 05 00
+                a3 10 30 0e 30 0c 06 03 55 1d 13 04 05 30 03 01 01 ff
+            30 0d 06 09 2a 86 48 86 f7 0d 01 01 0b 05 00
+            03 02 00 aa"
+    );
+
+    #[test]
+    fn document_nodes() {
+        let (rem, doc) = DerDocument::parse_der(Input::from(CERT)).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(doc.len(), 17);
+        assert_eq!(doc.nodes().count(), 17);
+
+        let root = doc.root();
+        assert_eq!(root.depth(), 0);
+        assert!(root.parent().is_none());
+        let tags: Vec<_> = root.children().map(|n| n.header().tag()).collect();
+        assert_eq!(tags, [Tag::Sequence, Tag::Sequence, Tag::BitString]);
+
+        let tbs = root.child(0).expect("no tbs");
+        assert_eq!(tbs.index(), 1);
+        assert_eq!(tbs.offset(), 2);
+        assert_eq!(tbs.raw(), &CERT[2..46]);
+        assert_eq!(tbs.data().as_bytes2(), &CERT[4..46]);
+        assert_eq!(tbs.num_children(), 4);
+        assert!(tbs.child(4).is_none());
+        let serial = tbs.child(1).expect("no serial");
+        assert_eq!(serial.any().as_u32(), Ok(1234));
+        assert_eq!(serial.next_sibling().map(|n| n.index()), Some(6));
+        assert_eq!(serial.parent().map(|n| n.index()), Some(1));
+        assert!(tbs.child(3).and_then(|n| n.next_sibling()).is_none());
+
+        // primitive objects are not parsed
+        let node = doc.node(3).expect("no node");
+        assert_eq!(node.header().tag(), Tag::BitString);
+        assert_eq!(node.num_children(), 0);
+        assert!(doc.node(17).is_none());
+    }
+
+    #[test]
+    fn document_paths() {
+        let (_, doc) = DerDocument::parse_der(Input::from(CERT)).expect("parsing failed");
+        assert_eq!(doc.get("").map(|n| n.index()), Some(0));
+        assert_eq!(doc.get("/").map(|n| n.index()), Some(0));
+        let version = doc.get("/0/[0]/0").expect("no version");
+        assert_eq!(version.any().as_u32(), Ok(2));
+        assert_eq!(version.depth(), 3);
+        let ext = doc.get("/0/[ctx 3]/0/0").expect("no extension");
+        assert_eq!(
+            ext.get("1").map(|n| n.data().as_bytes2()),
+            Some(&hex!("30 03 01 01 ff")[..])
+        );
+        assert_eq!(ext.get("[univ 6]").map(|n| n.index()), Some(15));
+        assert_eq!(
+            doc.get("/2").map(|n| n.header().tag()),
+            Some(Tag::BitString)
+        );
+
+        // no match, or invalid paths
+        assert!(doc.get("/3").is_none());
+        assert!(doc.get("/0/[ctx 1]").is_none());
+        assert!(doc.get("/0/[app 3]").is_none());
+        assert!(doc.get("/0/[foo 3]").is_none());
+        assert!(doc.get("/0/[ctx 3").is_none());
+        assert!(doc.get("/0/[ctx 3 4]").is_none());
+        assert!(doc.get("/a").is_none());
+    }
+
+    #[test]
+    fn document_errors() {
+        // sub-object larger than parent
+        let r = DerDocument::parse_der(Input::from(&hex!("30 03 04 02 aa bb")));
+        assert!(matches!(r, Err(nom::Err::Error(e)) if *e.inner() == InnerError::InvalidLength));
+        // indefinite length
+        let r = DerDocument::parse_der(Input::from(&hex!("30 80 00 00")));
+        assert!(matches!(r, Err(nom::Err::Error(_))));
+        // incomplete root object
+        let r = DerDocument::parse_der(Input::from(&hex!("30 05 02 01")));
+        assert!(matches!(r, Err(nom::Err::Incomplete(_))));
+        // maximum depth
+        let mut data = Vec::new();
+        for i in 0..60 {
+            data.extend_from_slice(&[0x30, 2 * (59 - i)]);
+        }
+        let r = DerDocument::parse_der(Input::from(&data));
+        assert!(matches!(r, Err(nom::Err::Error(e)) if *e.inner() == InnerError::BerMaxDepth));
+    }
+}
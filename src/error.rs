@@ -6,6 +6,7 @@ use alloc::str;
 use alloc::string;
 #[cfg(not(feature = "std"))]
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::fmt;
 use displaydoc::Display;
 use nom::error::{ErrorKind, FromExternalError, ParseError};
@@ -37,6 +38,24 @@ pub enum DerConstraint {
     IntegerLeadingZeroes,
     /// Leading 0xff in negative Integer encoding
     IntegerLeadingFF,
+    /// Definite length not allowed for constructed object (CER)
+    DefiniteLength,
+    /// Invalid size for string segment (CER)
+    SegmentSize,
+    /// SET OF components are not sorted
+    SetOfUnsorted,
+    /// SET components are not sorted by tag
+    SetUnsorted,
+    /// Length is not encoded with the minimum number of octets
+    NonMinimalLength,
+    /// Tag is not encoded with the minimum number of octets
+    NonMinimalTag,
+    /// Fractional seconds must use a decimal point, and must not end with zero
+    InvalidFractionalSeconds,
+    /// Value equal to the DEFAULT value of a component must not be encoded
+    DefaultValueEncoded,
+    /// Bitstring unused bits must be in range 0..=7
+    InvalidUnusedBits,
 }
 
 /// The error type for operations of the [`FromBer`](crate::FromBer),
@@ -77,6 +96,12 @@ pub enum Error {
     IntegerNegative,
     /// BER recursive parsing reached maximum depth
     BerMaxDepth,
+    /// BER object length exceeds maximum length
+    BerMaxLength,
+    /// BER constructed object exceeds maximum number of elements
+    BerMaxElements,
+    /// BER segmented object exceeds maximum allocation size
+    BerMaxAlloc,
 
     /// Invalid encoding or forbidden characters in string
     StringInvalidCharset,
@@ -98,12 +123,163 @@ pub enum Error {
     NomError(ErrorKind),
 }
 
-#[derive(Debug, Error, PartialEq, Eq)]
+/// Segment of an [`ErrorPath`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorPathSegment {
+    /// Field of a structure
+    Field(&'static str),
+    /// Index of an element in a collection
+    Index(usize),
+}
+
+/// Location of an error in a structure
+///
+/// The path is built while the error is propagated: parsers generated by custom derive record
+/// the name of the field being parsed, and collections (for ex. `Vec` or `SetOf`) record the
+/// index of the element. It is displayed starting from the outermost derived type, for ex.
+/// `Certificate.tbs_certificate.extensions[3].critical`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ErrorPath {
+    type_name: Option<&'static str>,
+    /// Segments, innermost first
+    segments: Vec<ErrorPathSegment>,
+}
+
+impl ErrorPath {
+    /// Build a new, empty path
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            type_name: None,
+            segments: Vec::new(),
+        }
+    }
+
+    /// Return `true` if the path is empty
+    pub fn is_empty(&self) -> bool {
+        self.type_name.is_none() && self.segments.is_empty()
+    }
+
+    /// Return the name of the outermost derived type, if any
+    #[inline]
+    pub const fn type_name(&self) -> Option<&'static str> {
+        self.type_name
+    }
+
+    /// Return an iterator over the path segments, starting from the outermost
+    pub fn segments(&self) -> impl Iterator<Item = &ErrorPathSegment> {
+        self.segments.iter().rev()
+    }
+
+    /// Record that the error happened when parsing `field` of type `type_name`
+    pub fn push_field(&mut self, type_name: &'static str, field: &'static str) {
+        self.type_name = Some(type_name);
+        self.segments.push(ErrorPathSegment::Field(field));
+    }
+
+    /// Record that the error happened when parsing element `index` of a collection
+    pub fn push_index(&mut self, index: usize) {
+        // the type name only applies to the outermost field
+        self.type_name = None;
+        self.segments.push(ErrorPathSegment::Index(index));
+    }
+}
+
+impl fmt::Display for ErrorPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        if let Some(type_name) = self.type_name {
+            f.write_str(type_name)?;
+            first = false;
+        }
+        for segment in self.segments() {
+            match segment {
+                ErrorPathSegment::Field(field) if first => f.write_str(field)?,
+                ErrorPathSegment::Field(field) => write!(f, ".{}", field)?,
+                ErrorPathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+/// Errors which can record their location in a structure
+///
+/// This trait is used by parsers generated by custom derive, and by collections, to build the
+/// [`ErrorPath`] of [`BerError`].
+///
+/// The error types of parsers of collections (for ex. `Vec<T>`, [`SequenceOf`](crate::SequenceOf) or
+/// [`SetOf`](crate::SetOf)) must implement this trait. The default methods ignore the location, so
+/// custom error types can implement it with an empty block.
+pub trait ErrorContext {
+    /// Record that the error happened when parsing `field` of type `type_name`
+    fn push_field(&mut self, _type_name: &'static str, _field: &'static str) {}
+
+    /// Record that the error happened when parsing element `index` of a collection
+    fn push_index(&mut self, _index: usize) {}
+}
+
+impl ErrorContext for Error {}
+
+/// Return a function recording, in a `nom::Err` error, that it happened when parsing `field` of
+/// type `type_name`
+///
+/// This is intended to be used with `map_err`.
+///
+/// Note: used in custom derive
+pub fn err_in_field<E: ErrorContext>(
+    type_name: &'static str,
+    field: &'static str,
+) -> impl Fn(nom::Err<E>) -> nom::Err<E> {
+    move |e| {
+        e.map(|mut e| {
+            e.push_field(type_name, field);
+            e
+        })
+    }
+}
+
+/// Return a function recording, in a `nom::Err` error, that it happened when parsing element
+/// `index` of a collection
+///
+/// This is intended to be used with `map_err`.
+pub fn err_at_index<E: ErrorContext>(index: usize) -> impl Fn(nom::Err<E>) -> nom::Err<E> {
+    move |e| {
+        e.map(|mut e| {
+            e.push_index(index);
+            e
+        })
+    }
+}
+
+#[derive(Debug, Error)]
 pub struct BerError<I: Input> {
     /// Input location where error happened
     input: I,
     /// Error kind
     inner_error: InnerError,
+    /// Location of the error in the structure
+    path: ErrorPath,
+}
+
+// the path is context, and is not compared
+impl<I: Input + PartialEq> PartialEq for BerError<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.input == other.input && self.inner_error == other.inner_error
+    }
+}
+
+impl<I: Input + Eq> Eq for BerError<I> {}
+
+impl<I: Input> ErrorContext for BerError<I> {
+    fn push_field(&mut self, type_name: &'static str, field: &'static str) {
+        self.path.push_field(type_name, field);
+    }
+
+    fn push_index(&mut self, index: usize) {
+        self.path.push_index(index);
+    }
 }
 
 impl<I: Input> BerError<I> {
@@ -113,6 +289,7 @@ impl<I: Input> BerError<I> {
         Self {
             input,
             inner_error: inner,
+            path: ErrorPath::new(),
         }
     }
 
@@ -126,9 +303,16 @@ impl<I: Input> BerError<I> {
         &self.inner_error
     }
 
+    /// Return the location of the error in the parsed structure
+    ///
+    /// The path is empty if the error did not happen in a derived type or a collection.
+    pub const fn path(&self) -> &ErrorPath {
+        &self.path
+    }
+
     #[inline]
     pub const fn nom_err(input: I, inner_error: InnerError) -> nom::Err<Self> {
-        nom::Err::Error(Self { input, inner_error })
+        nom::Err::Error(Self::new(input, inner_error))
     }
 
     /// convert an `InnerError` to a `nom::Err::Error(input, inner)`
@@ -181,10 +365,7 @@ impl<I: Input> BerError<I> {
     /// Build an `Incomplete` error from the provided parameters
     #[inline]
     pub const fn incomplete(input: I, n: nom::Needed) -> Self {
-        Self {
-            input,
-            inner_error: InnerError::Incomplete(n),
-        }
+        Self::new(input, InnerError::Incomplete(n))
     }
 }
 
@@ -210,16 +391,17 @@ impl<'i> BerError<crate::Input<'i>> {
 
 impl<I: Input + fmt::Display> fmt::Display for BerError<I> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "BerError: input={} error={}", self.input(), self.inner())
+        write!(f, "BerError: input={} error={}", self.input(), self.inner())?;
+        if !self.path.is_empty() {
+            write!(f, " path={}", self.path)?;
+        }
+        Ok(())
     }
 }
 
 impl<I: Input> ParseError<I> for BerError<I> {
     fn from_error_kind(input: I, kind: ErrorKind) -> Self {
-        Self {
-            input,
-            inner_error: InnerError::Nom(kind),
-        }
+        Self::new(input, InnerError::Nom(kind))
     }
 
     fn append(_: I, _z: ErrorKind, other: Self) -> Self {
@@ -240,10 +422,7 @@ where
     'b: 'a,
 {
     fn from(value: (crate::Input<'b>, Error)) -> Self {
-        Self {
-            input: value.0,
-            inner_error: InnerError::from(value.1),
-        }
+        Self::new(value.0, InnerError::from(value.1))
     }
 }
 
@@ -300,6 +479,12 @@ pub enum InnerError {
     IntegerNegative,
     /// BER recursive parsing reached maximum depth
     BerMaxDepth,
+    /// BER object length exceeds maximum length
+    BerMaxLength,
+    /// BER constructed object exceeds maximum number of elements
+    BerMaxElements,
+    /// BER segmented object exceeds maximum allocation size
+    BerMaxAlloc,
 
     /// Invalid encoding or forbidden characters in string
     StringInvalidCharset,
@@ -367,6 +552,9 @@ impl From<Error> for InnerError {
             Error::IntegerTooLarge => Self::IntegerTooLarge,
             Error::IntegerNegative => Self::IntegerNegative,
             Error::BerMaxDepth => Self::BerMaxDepth,
+            Error::BerMaxLength => Self::BerMaxLength,
+            Error::BerMaxElements => Self::BerMaxElements,
+            Error::BerMaxAlloc => Self::BerMaxAlloc,
             Error::StringInvalidCharset => Self::StringInvalidCharset,
             Error::InvalidDateTime => Self::InvalidDateTime,
             Error::DerConstraintFailed(der_constraint) => Self::DerConstraintFailed(der_constraint),
@@ -475,6 +663,9 @@ impl From<InnerError> for Error {
             InnerError::IntegerTooLarge => Self::IntegerTooLarge,
             InnerError::IntegerNegative => Self::IntegerNegative,
             InnerError::BerMaxDepth => Self::BerMaxDepth,
+            InnerError::BerMaxLength => Self::BerMaxLength,
+            InnerError::BerMaxElements => Self::BerMaxElements,
+            InnerError::BerMaxAlloc => Self::BerMaxAlloc,
             InnerError::StringInvalidCharset => Self::StringInvalidCharset,
             InnerError::InvalidDateTime => Self::InvalidDateTime,
             InnerError::DerConstraintFailed(der_constraint) => {
@@ -525,7 +716,6 @@ pub type ParseResult<'a, T, E = Error> = IResult<&'a [u8], T, E>;
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
 /// The error type for serialization operations of the [`ToDer`](crate::ToDer) trait.
-#[cfg(feature = "std")]
 #[derive(Debug, Error)]
 pub enum SerializeError {
     #[error("ASN.1 error: {0:?}")]
@@ -537,10 +727,173 @@ pub enum SerializeError {
     #[error("Invalid Length")]
     InvalidLength,
 
+    #[error("Buffer too small for encoded data")]
+    BufferOverflow,
+
+    #[error("Formatting error")]
+    FormatError,
+
+    #[cfg(feature = "std")]
     #[error("I/O error: {0:?}")]
     IOError(#[from] io::Error),
 }
 
-#[cfg(feature = "std")]
 /// Holds the result of BER/DER encoding functions
-pub type SerializeResult<T> = std::result::Result<T, SerializeError>;
+pub type SerializeResult<T> = core::result::Result<T, SerializeError>;
+
+/// The error type for stream readers ([`BerReader`](crate::BerReader) and
+/// [`DerReader`](crate::DerReader))
+#[cfg(feature = "std")]
+#[derive(Debug, Error)]
+pub enum ReadError {
+    #[error("ASN.1 error: {0:?}")]
+    ASN1Error(#[from] Error),
+
+    #[error("Object larger than the maximum object size")]
+    ObjectTooLarge,
+
+    #[error("I/O error: {0:?}")]
+    IOError(#[from] io::Error),
+}
+
+#[cfg(feature = "std")]
+impl From<ReadError> for io::Error {
+    fn from(value: ReadError) -> Self {
+        match value {
+            ReadError::IOError(e) => e,
+            e => io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+/// The error type for PER encoding and decoding operations (see [`per`](crate::per))
+#[cfg(feature = "per")]
+#[cfg_attr(docsrs, doc(cfg(feature = "per")))]
+#[derive(Clone, Debug, Display, PartialEq, Eq, Error)]
+pub enum PerError {
+    /// Value does not satisfy its PER-visible constraints
+    ConstraintViolation,
+    /// A constraint is required to encode or decode this type
+    MissingConstraint,
+    /// Invalid PER encoding
+    InvalidEncoding,
+    /// Integer too large to fit requested type
+    IntegerTooLarge,
+    /// Invalid encoding or forbidden characters in string
+    StringInvalidCharset,
+    /// Unknown extension addition (index {0})
+    UnknownExtension(u64),
+
+    /// incomplete data, missing: {0:?}
+    Incomplete(nom::Needed),
+}
+
+/// Holds the result of PER encoding and decoding functions
+#[cfg(feature = "per")]
+#[cfg_attr(docsrs, doc(cfg(feature = "per")))]
+pub type PerResult<T> = core::result::Result<T, PerError>;
+
+/// The error type for OER encoding and decoding operations (see [`oer`](crate::oer))
+#[cfg(feature = "oer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "oer")))]
+#[derive(Clone, Debug, Display, PartialEq, Eq, Error)]
+pub enum OerError {
+    /// Value does not satisfy its OER-visible constraints
+    ConstraintViolation,
+    /// Invalid OER encoding
+    InvalidEncoding,
+    /// Encoding is valid but not canonical (COER)
+    NonCanonical,
+    /// Integer too large to fit requested type
+    IntegerTooLarge,
+    /// Invalid encoding or forbidden characters in string
+    StringInvalidCharset,
+    /// Unknown extension addition (value {0})
+    UnknownExtension(u64),
+
+    /// incomplete data, missing: {0:?}
+    Incomplete(nom::Needed),
+}
+
+/// Holds the result of OER encoding and decoding functions
+#[cfg(feature = "oer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "oer")))]
+pub type OerResult<T> = core::result::Result<T, OerError>;
+
+/// The error type for JER encoding and decoding operations (see [`jer`](crate::jer))
+#[cfg(feature = "jer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jer")))]
+#[derive(Clone, Debug, Display, PartialEq, Eq, Error)]
+pub enum JerError {
+    /// Invalid JSON text (at offset {0})
+    InvalidJson(usize),
+    /// Unexpected JSON value type
+    UnexpectedType,
+    /// Invalid value
+    InvalidValue,
+    /// Missing member '{0}'
+    MissingMember(String),
+    /// Unknown alternative '{0}'
+    UnknownAlternative(String),
+    /// Integer too large to fit requested type
+    IntegerTooLarge,
+    /// Invalid encoding or forbidden characters in string
+    StringInvalidCharset,
+}
+
+/// Holds the result of JER encoding and decoding functions
+#[cfg(feature = "jer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jer")))]
+pub type JerResult<T> = core::result::Result<T, JerError>;
+
+/// The error type for XER encoding and decoding operations (see [`xer`](crate::xer))
+#[cfg(feature = "xer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "xer")))]
+#[derive(Clone, Debug, Display, PartialEq, Eq, Error)]
+pub enum XerError {
+    /// Invalid XML document (at offset {0})
+    InvalidXml(usize),
+    /// Unexpected element '{0}'
+    UnexpectedElement(String),
+    /// Missing element '{0}'
+    MissingElement(String),
+    /// Unexpected text or element content
+    UnexpectedContent,
+    /// Invalid value
+    InvalidValue,
+    /// Unknown alternative '{0}'
+    UnknownAlternative(String),
+    /// Integer too large to fit requested type
+    IntegerTooLarge,
+    /// Invalid encoding or forbidden characters in string
+    StringInvalidCharset,
+}
+
+/// Holds the result of XER encoding and decoding functions
+#[cfg(feature = "xer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "xer")))]
+pub type XerResult<T> = core::result::Result<T, XerError>;
+
+/// The error type for parsing the ASN.1 value notation (see [`value_notation`](crate::value_notation))
+#[cfg(feature = "value-notation")]
+#[cfg_attr(docsrs, doc(cfg(feature = "value-notation")))]
+#[derive(Clone, Debug, Display, PartialEq, Eq, Error)]
+pub enum ValueNotationError {
+    /// Unexpected end of input
+    UnexpectedEnd,
+    /// Syntax error (at offset {0})
+    Syntax(usize),
+    /// Invalid value (at offset {0})
+    InvalidValue(usize),
+    /// Unknown type '{0}'
+    UnknownType(String),
+    /// Nesting level too deep
+    NestingTooDeep,
+    /// Invalid encoding or forbidden characters in string (at offset {0})
+    StringInvalidCharset(usize),
+}
+
+/// Holds the result of value notation parsing functions
+#[cfg(feature = "value-notation")]
+#[cfg_attr(docsrs, doc(cfg(feature = "value-notation")))]
+pub type ValueNotationResult<T> = core::result::Result<T, ValueNotationError>;
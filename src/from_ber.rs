@@ -100,8 +100,7 @@ where
             let (rem, header) = Header::parse_ber(input.clone()).map_err(Err::convert)?;
             if !Self::accept_tag(header.tag) {
                 return Err(Err::Error(
-                    // FIXME: expected Tag is `None`, so the error will not be helpful
-                    BerError::unexpected_tag(input, None, header.tag).into(),
+                    BerError::unexpected_tag(input, Self::expected_tag(), header.tag).into(),
                 ));
             }
             let (rem, data) =
@@ -148,8 +147,7 @@ where
             let (rem, header) = Header::parse_ber(input.clone()).map_err(Err::convert)?;
             if !Self::accept_tag(header.tag) {
                 return Err(Err::Error(
-                    // FIXME: expected Tag is `None`, so the error will not be helpful
-                    BerError::unexpected_tag(input, None, header.tag).into(),
+                    BerError::unexpected_tag(input, Self::expected_tag(), header.tag).into(),
                 ));
             }
             let (rem, data) =
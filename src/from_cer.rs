@@ -0,0 +1,158 @@
+use core::fmt::Display;
+
+use nom::error::ParseError;
+use nom::{Err, IResult, Input as _};
+
+use crate::debug::trace_input;
+use crate::{BerError, CerMode, DynTagged, GetObjectContent, Header, Input, MAX_RECURSION};
+
+/// Maximum length of a primitive string encoding in CER, and size of segments
+/// when using the constructed form (X.690: 9.2)
+pub const CER_SEGMENT_SIZE: usize = 1000;
+
+/// Base trait for CER object parsers
+///
+/// Canonical Encoding Rules (CER) are a restricted form of BER. Compared to DER, the main
+/// differences are:
+/// - constructed objects shall use the indefinite length form (X.690: 9.1)
+/// - string types shall be encoded as primitive if their content is at most 1000 octets,
+///   and as constructed (with segments of 1000 octets) otherwise (X.690: 9.2)
+/// - components of `SET OF` shall be sorted (X.690: 11.6)
+///
+/// Implementers should provide a definition for the following:
+/// - method [`from_cer_content`](CerParser::from_cer_content): Parse CER content, given a header and data
+/// - trait [`DynTagged`]
+///
+/// This trait can be automatically derived using the `#[asn1(parse = "CER")]` attribute of the
+/// [`Sequence`](crate::derive::Sequence), [`Set`](crate::derive::Set), [`Choice`](crate::derive::Choice),
+/// [`Enumerated`](crate::derive::Enumerated) and [`Alias`](crate::derive::Alias) custom derive attributes.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{CerParser, Input};
+/// use hex_literal::hex;
+///
+/// // constructed objects use indefinite length
+/// let input = Input::from(&hex!("30 80 0203010001 020101 0000"));
+/// let (rem, v) = <Vec<u32>>::parse_cer(input).expect("parsing failed");
+/// assert!(rem.is_empty());
+/// assert_eq!(&v, &[65537, 1]);
+///
+/// // definite length is rejected for constructed objects
+/// let input = Input::from(&hex!("30 08 0203010001 020101"));
+/// let _ = <Vec<u32>>::parse_cer(input).expect_err("definite length");
+/// ```
+pub trait CerParser<'i>
+where
+    Self: Sized,
+    Self: DynTagged,
+{
+    /// The Error type for parsing errors.
+    type Error: Display + ParseError<Input<'i>> + From<BerError<Input<'i>>>;
+
+    /// Attempt to parse a new CER object from data.
+    ///
+    /// Header tag must match expected tag, and length form must match the constructed bit
+    /// (indefinite for constructed objects, definite for primitive objects).
+    fn parse_cer(input: Input<'i>) -> IResult<Input<'i>, Self, Self::Error> {
+        trace_input("CerParser::parse_cer", |input| {
+            let (rem, header) = Header::parse_cer(input.clone()).map_err(Err::convert)?;
+            if !Self::accept_tag(header.tag) {
+                return Err(Err::Error(
+                    BerError::unexpected_tag(input, Self::expected_tag(), header.tag).into(),
+                ));
+            }
+            let (rem, data) =
+                CerMode::get_object_content(&header, rem, MAX_RECURSION).map_err(Err::convert)?;
+            let (_, obj) = trace_input("CerParser::from_cer_content", |i| {
+                // wrap from_cer_content function to display better errors, if any
+                Self::from_cer_content(&header, i)
+            })(data)
+            .map_err(Err::convert)?;
+            Ok((rem, obj))
+        })(input)
+    }
+
+    /// Parse a new CER object from header and data.
+    ///
+    /// `input` is the content of the object. If length is indefinite, the terminating
+    /// End-Of-Content is not included.
+    ///
+    /// This function also checks CER-related constraints.
+    /// Relevant sections in specifications:
+    /// - Canonical encoding rules (X.690: 9)
+    /// - Restrictions on BER employed by both CER and DER (X.690: 11)
+    ///
+    /// Note: in this method, implementers should *not* check header tag (which can be
+    /// different from the usual object tag when using IMPLICIT tagging, for ex.).
+    fn from_cer_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error>;
+
+    fn parse_cer_optional(input: Input<'i>) -> IResult<Input<'i>, Option<Self>, Self::Error> {
+        if input.input_len() == 0 {
+            return Ok((input, None));
+        }
+        let (rem, header) = Header::parse_cer(input.clone()).map_err(Err::convert)?;
+        if !Self::accept_tag(header.tag) {
+            return Ok((input, None));
+        }
+        let (rem, data) =
+            CerMode::get_object_content(&header, rem, MAX_RECURSION).map_err(Err::convert)?;
+        let (_, obj) = Self::from_cer_content(&header, data).map_err(Err::convert)?;
+        Ok((rem, Some(obj)))
+    }
+
+    /// Parse object header (validating tag) and return header and content as `Input`
+    fn parse_cer_as_input(
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, (Header<'i>, Input<'i>), Self::Error> {
+        trace_input("CerParser::parse_cer_as_input", |input| {
+            let (rem, header) = Header::parse_cer(input.clone()).map_err(Err::convert)?;
+            if !Self::accept_tag(header.tag) {
+                return Err(Err::Error(
+                    BerError::unexpected_tag(input, Self::expected_tag(), header.tag).into(),
+                ));
+            }
+            let (rem, data) =
+                CerMode::get_object_content(&header, rem, MAX_RECURSION).map_err(Err::convert)?;
+
+            Ok((rem, (header, data)))
+        })(input)
+    }
+}
+
+//--- Macros
+
+/// Helper macro to implement [`CerParser`] for types where implementation is the same as [`DerParser`](crate::DerParser)
+///
+/// This is the case for most types using a primitive encoding (except strings).
+#[macro_export]
+macro_rules! impl_cerparser_from_derparser {
+    (TY $ty:ty) => {
+        impl<'i> $crate::CerParser<'i> for $ty {
+            type Error = <$ty as $crate::DerParser<'i>>::Error;
+
+            fn from_cer_content(
+                header: &'_ $crate::Header<'i>,
+                input: $crate::Input<'i>,
+            ) -> $crate::nom::IResult<$crate::Input<'i>, Self, Self::Error> {
+                <$ty as $crate::DerParser<'i>>::from_der_content(header, input)
+            }
+        }
+    };
+    (LFT $lft:lifetime, $ty:ty) => {
+        impl<$lft> $crate::CerParser<$lft> for $ty {
+            type Error = <$ty as $crate::DerParser<$lft>>::Error;
+
+            fn from_cer_content(
+                header: &'_ $crate::Header<$lft>,
+                input: $crate::Input<$lft>,
+            ) -> $crate::nom::IResult<$crate::Input<$lft>, Self, Self::Error> {
+                <$ty as $crate::DerParser<$lft>>::from_der_content(header, input)
+            }
+        }
+    };
+}
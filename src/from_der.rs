@@ -1,7 +1,6 @@
 use core::convert::{TryFrom, TryInto};
 use core::fmt::{Debug, Display};
 
-use nom::bytes::streaming::take;
 use nom::error::ParseError;
 use nom::{Err, IResult, Input as _};
 
@@ -141,17 +140,17 @@ where
         trace_input("DerParser::parse_der", |input| {
             let (rem, header) = Header::parse_der(input.clone()).map_err(Err::convert)?;
             // get length, rejecting indefinite (invalid for DER)
-            let length = header
+            header
                 .length
-                .definite_inner()
+                .assert_definite_inner()
                 .map_err(BerError::convert_into(input.clone()))?;
             if !Self::accept_tag(header.tag) {
                 return Err(Err::Error(
-                    // TODO: expected Tag is `None`, so the error will not be helpful
-                    BerError::unexpected_tag(input, None, header.tag).into(),
+                    BerError::unexpected_tag(input, Self::expected_tag(), header.tag).into(),
                 ));
             }
-            let (rem, data) = take(length)(rem)?;
+            let (rem, data) =
+                DerMode::get_object_content(&header, rem, MAX_RECURSION).map_err(Err::convert)?;
             let (_, obj) = trace_input("DerParser::from_der_content", |i| {
                 // wrap from_der_content function to display better errors, if any
                 Self::from_der_content(&header, i)
@@ -187,11 +186,12 @@ where
             return Ok((input, None));
         }
         // get length, rejecting indefinite (invalid for DER)
-        let length = header
+        header
             .length
-            .definite_inner()
+            .assert_definite_inner()
             .map_err(BerError::convert_into(input.clone()))?;
-        let (rem, data) = take(length)(rem)?;
+        let (rem, data) =
+            DerMode::get_object_content(&header, rem, MAX_RECURSION).map_err(Err::convert)?;
         let (_, obj) = Self::from_der_content(&header, data).map_err(Err::convert)?;
         Ok((rem, Some(obj)))
     }
@@ -204,8 +204,7 @@ where
             let (rem, header) = Header::parse_der(input.clone()).map_err(Err::convert)?;
             if !Self::accept_tag(header.tag) {
                 return Err(Err::Error(
-                    // FIXME: expected Tag is `None`, so the error will not be helpful
-                    BerError::unexpected_tag(input, None, header.tag).into(),
+                    BerError::unexpected_tag(input, Self::expected_tag(), header.tag).into(),
                 ));
             }
             let (rem, data) =
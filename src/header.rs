@@ -2,11 +2,10 @@ use crate::ber::*;
 use crate::debug::trace_input;
 use crate::error::*;
 use crate::wrap_ber_parser;
-use crate::DerParser;
-#[cfg(feature = "std")]
-use crate::ToDer;
 use crate::{BerMode, Class, DerMode, DynTagged, FromBer, FromDer, Length, Tag, ToStatic};
 use crate::{BerParser, Input};
+use crate::{CerMode, CerParser, DerParser};
+use crate::{ToCer, ToDer};
 use alloc::borrow::Cow;
 use core::convert::TryFrom;
 use core::hash::Hash;
@@ -270,6 +269,23 @@ impl<'a> Header<'a> {
         }
     }
 
+    /// Return error if object length form is not valid for CER
+    ///
+    /// Constructed objects must use the indefinite form, and primitive objects
+    /// the definite form (X.690: 9.1)
+    #[inline]
+    pub const fn assert_cer_length_inner(&self) -> Result<(), InnerError> {
+        match (self.constructed, self.length.is_definite()) {
+            (true, true) => Err(InnerError::DerConstraintFailed(
+                DerConstraint::DefiniteLength,
+            )),
+            (false, false) => Err(InnerError::DerConstraintFailed(
+                DerConstraint::IndefiniteLength,
+            )),
+            _ => Ok(()),
+        }
+    }
+
     /// Get the content following a BER header
     #[inline]
     pub fn parse_ber_content<'i>(
@@ -291,6 +307,17 @@ impl<'a> Header<'a> {
             .map_err(BerError::convert(i.clone()))?;
         DerMode::get_object_content(self, i, 8)
     }
+
+    /// Get the content following a CER header
+    #[inline]
+    pub fn parse_cer_content<'i>(
+        &'_ self,
+        i: Input<'i>,
+    ) -> IResult<Input<'i>, Input<'i>, BerError<Input<'i>>> {
+        self.assert_cer_length_inner()
+            .map_err(BerError::convert(i.clone()))?;
+        CerMode::get_object_content(self, i, 8)
+    }
 }
 
 impl From<Tag> for Header<'_> {
@@ -375,6 +402,29 @@ impl<'i> DerParser<'i> for Header<'i> {
     }
 }
 
+impl<'i> CerParser<'i> for Header<'i> {
+    type Error = BerError<Input<'i>>;
+
+    fn parse_cer(input: Input<'i>) -> IResult<Input<'i>, Self, Self::Error> {
+        trace_input("Header::parse_cer", |input| {
+            let (rem, header) = parse_header(input.clone())?;
+            // CER parser: length form depends on the constructed bit
+            header
+                .assert_cer_length_inner()
+                .map_err(BerError::convert(input))?;
+            Ok((rem, header))
+        })(input)
+    }
+
+    /// <div class="warning">This method is usually not called (and will create a useless clone)</div>
+    fn from_cer_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        Ok((input, header.clone()))
+    }
+}
+
 impl<'a> FromDer<'a> for Header<'a> {
     fn from_der(bytes: &'a [u8]) -> ParseResult<'a, Self> {
         let (rem, header) = wrap_ber_parser(parse_header)(bytes)?;
@@ -406,9 +456,8 @@ impl DynTagged for Header<'_> {
     }
 }
 
-#[cfg(feature = "std")]
 const _: () = {
-    use crate::{BerGenericEncoder, ToBer};
+    use crate::{BerGenericEncoder, ToBer, Writer};
 
     impl ToBer for Header<'_> {
         type Encoder = BerGenericEncoder;
@@ -417,7 +466,7 @@ const _: () = {
             self.length()
         }
 
-        fn ber_write_content<W: std::io::Write>(&self, _: &mut W) -> SerializeResult<usize> {
+        fn ber_write_content<W: Writer>(&self, _: &mut W) -> SerializeResult<usize> {
             Ok(0)
         }
 
@@ -433,7 +482,7 @@ const _: () = {
             self.length()
         }
 
-        fn der_write_content<W: std::io::Write>(&self, _: &mut W) -> SerializeResult<usize> {
+        fn der_write_content<W: Writer>(&self, _: &mut W) -> SerializeResult<usize> {
             Ok(0)
         }
 
@@ -441,6 +490,22 @@ const _: () = {
             (self.class(), self.constructed(), self.tag())
         }
     }
+
+    impl ToCer for Header<'_> {
+        type Encoder = BerGenericEncoder;
+
+        fn cer_content_len(&self) -> Length {
+            self.length()
+        }
+
+        fn cer_write_content<W: Writer>(&self, _: &mut W) -> SerializeResult<usize> {
+            Ok(0)
+        }
+
+        fn cer_tag_info(&self) -> (Class, bool, Tag) {
+            (self.class(), self.constructed(), self.tag())
+        }
+    }
 };
 
 /// Compare two BER headers. `len` fields are compared only if both objects have it set (same for `raw_tag`)
@@ -479,6 +544,33 @@ impl Hash for Header<'_> {
     }
 }
 
+/// Return the length of the header at the start of `bytes`, or `None` if incomplete
+pub(crate) fn raw_header_len(bytes: &[u8]) -> Option<usize> {
+    let b0 = *bytes.first()?;
+    let mut len = 1;
+    // high tag number form (X.690: 8.1.2.4)
+    if b0 & 0x1f == 0x1f {
+        loop {
+            let b = *bytes.get(len)?;
+            len += 1;
+            // longer tags are rejected by the parser
+            if b & 0x80 == 0 || len > 6 {
+                break;
+            }
+        }
+    }
+    // long form of length (X.690: 8.1.3.5)
+    let len_b0 = *bytes.get(len)?;
+    len += 1;
+    if len_b0 > 0x80 && len_b0 != 0xff {
+        len += usize::from(len_b0 & 0x7f);
+    }
+    if bytes.len() < len {
+        return None;
+    }
+    Some(len)
+}
+
 pub(crate) fn parse_header(
     input: Input<'_>,
 ) -> IResult<Input<'_>, Header<'_>, BerError<Input<'_>>> {
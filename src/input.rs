@@ -2,24 +2,35 @@
 use alloc::string::String;
 use alloc::vec::Vec;
 use core::fmt;
+use core::hash::{Hash, Hasher};
 use core::iter::{Cloned, Enumerate};
 use core::ops::Range;
 use core::slice::Iter;
 
 use nom::{AsBytes, Needed};
 
+use crate::{Limits, LimitsState};
+
 /// BER/DER parser input type
-#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+///
+/// The input can carry parser [`Limits`] (see [`Input::with_limits`]), which are propagated to
+/// all sub-inputs. Limits are not compared when comparing inputs.
+#[derive(Debug, Default, Clone)]
 pub struct Input<'a> {
     data: &'a [u8],
     span: Range<usize>,
+    limits: Option<&'a LimitsState>,
 }
 
 impl<'a> Input<'a> {
     /// Build a new `Input``
     #[inline]
     pub const fn new(data: &'a [u8], span: Range<usize>) -> Self {
-        Self { data, span }
+        Self {
+            data,
+            span,
+            limits: None,
+        }
     }
 
     #[inline]
@@ -28,7 +39,11 @@ impl<'a> Input<'a> {
             start: 0,
             end: data.len(),
         };
-        Self { data, span }
+        Self {
+            data,
+            span,
+            limits: None,
+        }
     }
 
     #[inline]
@@ -39,6 +54,45 @@ impl<'a> Input<'a> {
                 start: self.span.start,
                 end: self.span.end,
             },
+            limits: self.limits,
+        }
+    }
+
+    /// Set the limits used when parsing this input (and all sub-inputs)
+    ///
+    /// The state holds the limits, and the allocation budget of the message. See [`Limits`] and
+    /// [`LimitsState`].
+    #[inline]
+    pub const fn with_limits(self, limits: &'a LimitsState) -> Self {
+        Self {
+            limits: Some(limits),
+            ..self
+        }
+    }
+
+    /// Return the limits used when parsing this input
+    ///
+    /// If no limits were set, this returns [`Limits::DEFAULT`].
+    #[inline]
+    pub fn limits(&self) -> &'a Limits {
+        match self.limits {
+            Some(state) => state.limits(),
+            None => &Limits::DEFAULT,
+        }
+    }
+
+    /// Return the limits state used when parsing this input, if limits were set
+    #[inline]
+    pub const fn limits_state(&self) -> Option<&'a LimitsState> {
+        self.limits
+    }
+
+    /// Return the maximum depth of the limits of this input, or `default` if no limits were set
+    #[inline]
+    pub(crate) fn max_depth_or(&self, default: usize) -> usize {
+        match self.limits {
+            Some(state) => state.limits().max_depth(),
+            None => default,
         }
     }
 
@@ -99,6 +153,7 @@ impl<'a> From<&'a [u8]> for Input<'a> {
                 start: 0,
                 end: data.len(),
             },
+            limits: None,
         }
     }
 }
@@ -118,10 +173,26 @@ impl<'a> From<&'a Vec<u8>> for Input<'a> {
                 start: 0,
                 end: data.len(),
             },
+            limits: None,
         }
     }
 }
 
+impl PartialEq for Input<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data && self.span == other.span
+    }
+}
+
+impl Eq for Input<'_> {}
+
+impl Hash for Input<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+        self.span.hash(state);
+    }
+}
+
 impl fmt::Display for Input<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Input(start={} end={})", self.start(), self.end())
@@ -145,7 +216,11 @@ impl<'a> nom::Input for Input<'a> {
             start: self.span.start,
             end: self.span.start + index,
         };
-        Self::new(fragment, span)
+        Self {
+            data: fragment,
+            span,
+            limits: self.limits,
+        }
     }
 
     fn take_from(&self, index: usize) -> Self {
@@ -154,7 +229,11 @@ impl<'a> nom::Input for Input<'a> {
             start: self.span.start + index,
             end: self.span.end,
         };
-        Self::new(fragment, span)
+        Self {
+            data: fragment,
+            span,
+            limits: self.limits,
+        }
     }
 
     fn take_split(&self, index: usize) -> (Self, Self) {
@@ -0,0 +1,87 @@
+//! JSON Encoding Rules (JER) encoders and decoders
+//!
+//! JER is defined in [[X.697]]. It gives a standard JSON representation of ASN.1 values, for
+//! example to exchange or display objects parsed from BER or DER in web tools.
+//!
+//! Values are converted to and from a [`JsonValue`], which can be printed as compact JSON text
+//! (using `Display`) or parsed from JSON text (using [`JsonValue::parse`]). No external JSON
+//! library is required.
+//!
+//! Types built with the `Sequence`, `Set`, `Choice`, `Enumerated` and `Alias` custom derive
+//! attributes can implement these traits by adding `JER` to the `parse` and `encode` items of
+//! the `asn1` attribute. Field names are used as member names. See
+//! [doc::derive](crate::doc::derive) for details.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use asn1_rs::jer::{JerParser, ToJer};
+//! use asn1_rs::{BitString, Real};
+//!
+//! let s = 1234u32.to_jer_string().expect("serialization failed");
+//! assert_eq!(s, "1234");
+//!
+//! // BIT STRING is encoded as an object
+//! let b = BitString::new(4, &[0xa5, 0xf0]);
+//! let s = b.to_jer_string().expect("serialization failed");
+//! assert_eq!(s, r#"{"value":"a5f0","length":12}"#);
+//! assert_eq!(BitString::parse_jer(&s).expect("parsing failed"), b);
+//!
+//! // special REAL values are encoded as strings
+//! let s = Real::Infinity.to_jer_string().expect("serialization failed");
+//! assert_eq!(s, r#""INF""#);
+//! ```
+//!
+//! # Limitations
+//!
+//! - constraints are not checked, and encoding instructions are not supported
+//! - `Any` can be encoded (without schema, see [`ToJer`] implementation for details), but not
+//!   decoded
+//! - `RELATIVE-OID` values are decoded as [`Oid`](crate::Oid) objects, which are not relative
+//! - decoded values always own their data
+//!
+//! [X.697]: http://www.itu.int/rec/T-REC-X.697/en "Recommendation ITU-T X.697"
+
+mod strings;
+mod traits;
+mod types;
+mod value;
+
+pub use traits::*;
+pub use value::*;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{JerError, JerResult};
+
+/// Encode octets as a hexadecimal string, using lowercase digits
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut s = String::with_capacity(2 * bytes.len());
+    for b in bytes {
+        s.push(DIGITS[usize::from(b >> 4)] as char);
+        s.push(DIGITS[usize::from(b & 0xf)] as char);
+    }
+    s
+}
+
+/// Decode a hexadecimal string (digits can be lowercase or uppercase)
+pub(crate) fn decode_hex(s: &str) -> JerResult<Vec<u8>> {
+    fn digit(c: u8) -> JerResult<u8> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => Err(JerError::InvalidValue),
+        }
+    }
+    let bytes = s.as_bytes();
+    if bytes.len() % 2 != 0 {
+        return Err(JerError::InvalidValue);
+    }
+    bytes
+        .chunks_exact(2)
+        .map(|c| Ok((digit(c[0])? << 4) | digit(c[1])?))
+        .collect()
+}
@@ -0,0 +1,134 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+use crate::{
+    BmpString, GeneralString, GraphicString, Ia5String, JerError, JerResult, NumericString,
+    PrintableString, TeletexString, TestValidCharset, UniversalString, Utf8String, VideotexString,
+    VisibleString,
+};
+
+use super::{JerParser, JsonValue, ToJer};
+
+/// Character strings are encoded as JSON strings (X.697: 25)
+impl ToJer for str {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        Ok(JsonValue::from(self))
+    }
+}
+
+impl ToJer for String {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        self.as_str().to_jer()
+    }
+}
+
+impl JerParser for String {
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        value
+            .as_str()
+            .map(String::from)
+            .ok_or(JerError::UnexpectedType)
+    }
+}
+
+/// Strings with a restricted character set, checked when encoding and decoding
+macro_rules! impl_jer_restricted_string {
+    ($($name:ident)*) => {
+        $(
+            impl ToJer for $name<'_> {
+                fn to_jer(&self) -> JerResult<JsonValue> {
+                    <$name>::test_valid_charset(self.data.as_bytes())
+                        .map_err(|_| JerError::StringInvalidCharset)?;
+                    self.data.to_jer()
+                }
+            }
+
+            impl JerParser for $name<'_> {
+                fn from_jer(value: &JsonValue) -> JerResult<Self> {
+                    let s = String::from_jer(value)?;
+                    <$name>::test_valid_charset(s.as_bytes())
+                        .map_err(|_| JerError::StringInvalidCharset)?;
+                    Ok($name { data: Cow::Owned(s) })
+                }
+            }
+        )*
+    };
+}
+
+impl_jer_restricted_string!(Ia5String PrintableString VisibleString NumericString);
+
+/// Strings where any Unicode character is valid
+macro_rules! impl_jer_string {
+    ($($name:ident)*) => {
+        $(
+            impl ToJer for $name<'_> {
+                fn to_jer(&self) -> JerResult<JsonValue> {
+                    self.data.to_jer()
+                }
+            }
+
+            impl JerParser for $name<'_> {
+                fn from_jer(value: &JsonValue) -> JerResult<Self> {
+                    let s = String::from_jer(value)?;
+                    Ok($name { data: Cow::Owned(s) })
+                }
+            }
+        )*
+    };
+}
+
+impl_jer_string!(Utf8String GeneralString GraphicString TeletexString VideotexString UniversalString);
+
+/// Returns an error if the string contains characters outside the Basic Multilingual Plane
+fn check_bmp(s: &str) -> JerResult<()> {
+    if s.chars().any(|c| c as u32 > 0xffff) {
+        return Err(JerError::StringInvalidCharset);
+    }
+    Ok(())
+}
+
+impl ToJer for BmpString<'_> {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        check_bmp(&self.data)?;
+        self.data.to_jer()
+    }
+}
+
+impl JerParser for BmpString<'_> {
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        let s = String::from_jer(value)?;
+        check_bmp(&s)?;
+        Ok(BmpString::from(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jer::{JerParser, ToJer};
+    use crate::{BmpString, Ia5String, JerError, NumericString, Utf8String};
+
+    #[test]
+    fn jer_strings() {
+        let s = Utf8String::from("caf\u{e9} \"1\"");
+        let v = s.to_jer_string().unwrap();
+        assert_eq!(v, r#""café \"1\"""#);
+        assert_eq!(Utf8String::parse_jer(&v).unwrap(), s);
+
+        assert_eq!(
+            Ia5String::parse_jer(r#""abc""#).unwrap(),
+            Ia5String::new("abc")
+        );
+        assert_eq!(
+            Ia5String::parse_jer(r#""café""#),
+            Err(JerError::StringInvalidCharset)
+        );
+        assert_eq!(
+            NumericString::new("12a").to_jer(),
+            Err(JerError::StringInvalidCharset)
+        );
+        assert_eq!(Ia5String::parse_jer("1"), Err(JerError::UnexpectedType));
+
+        BmpString::parse_jer(r#""😀""#).unwrap_err();
+        BmpString::parse_jer(r#""€""#).unwrap();
+    }
+}
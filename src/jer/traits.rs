@@ -0,0 +1,60 @@
+use alloc::string::{String, ToString};
+
+use crate::JerResult;
+
+use super::JsonValue;
+
+/// Common trait for JER encoding functions
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::jer::ToJer;
+/// use asn1_rs::oid;
+///
+/// let oid = oid!(1.2.840 .113549);
+/// let s = oid.to_jer_string().expect("serialization failed");
+/// assert_eq!(s, r#""1.2.840.113549""#);
+/// ```
+pub trait ToJer {
+    /// Encode the value as a JSON value
+    fn to_jer(&self) -> JerResult<JsonValue>;
+
+    /// Encode the value as compact JSON text
+    fn to_jer_string(&self) -> JerResult<String> {
+        self.to_jer().map(|v| v.to_string())
+    }
+}
+
+impl<T> ToJer for &'_ T
+where
+    T: ToJer + ?Sized,
+{
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        (*self).to_jer()
+    }
+}
+
+/// Base trait for JER decoding functions
+///
+/// Decoded values own their data (they do not borrow from the JSON value).
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::jer::JerParser;
+/// use asn1_rs::OctetString;
+///
+/// let s = OctetString::parse_jer(r#""01a2""#).expect("parsing failed");
+/// assert_eq!(s.as_ref(), &[0x01, 0xa2]);
+/// ```
+pub trait JerParser: Sized {
+    /// Decode a value from a JSON value
+    fn from_jer(value: &JsonValue) -> JerResult<Self>;
+
+    /// Decode a value from JSON text
+    fn parse_jer(s: &str) -> JerResult<Self> {
+        let value = JsonValue::parse(s)?;
+        Self::from_jer(&value)
+    }
+}
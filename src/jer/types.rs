@@ -0,0 +1,564 @@
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use crate::{
+    Any, AnyIterator, BerMode, BitString, Boolean, Class, Enumerated, GeneralizedTime, Integer,
+    JerError, JerResult, Null, OctetString, Oid, Real, SequenceOf, SetOf, Tag, UtcTime,
+};
+
+use super::{decode_hex, encode_hex, JerParser, JsonObject, JsonValue, ToJer};
+
+//---- BOOLEAN (X.697: 18)
+
+impl ToJer for bool {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        Ok(JsonValue::Bool(*self))
+    }
+}
+
+impl JerParser for bool {
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        value.as_bool().ok_or(JerError::UnexpectedType)
+    }
+}
+
+impl ToJer for Boolean {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        self.bool().to_jer()
+    }
+}
+
+impl JerParser for Boolean {
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        let b = bool::from_jer(value)?;
+        Ok(if b { Boolean::TRUE } else { Boolean::FALSE })
+    }
+}
+
+//---- NULL (X.697: 24)
+
+impl ToJer for Null {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        Ok(JsonValue::Null)
+    }
+}
+
+impl JerParser for Null {
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        if !value.is_null() {
+            return Err(JerError::UnexpectedType);
+        }
+        Ok(Null::new())
+    }
+}
+
+impl ToJer for () {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        Ok(JsonValue::Null)
+    }
+}
+
+impl JerParser for () {
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        Null::from_jer(value).map(|_| ())
+    }
+}
+
+//---- INTEGER (X.697: 19)
+
+/// Returns the text of a JSON number, if it is an integer (no fraction or exponent)
+fn integer_text(value: &JsonValue) -> JerResult<&str> {
+    let n = value.as_number().ok_or(JerError::UnexpectedType)?;
+    if n.contains(['.', 'e', 'E']) {
+        return Err(JerError::InvalidValue);
+    }
+    Ok(n)
+}
+
+macro_rules! impl_jer_int {
+    ($($ty:ty)*) => {
+        $(
+            impl ToJer for $ty {
+                fn to_jer(&self) -> JerResult<JsonValue> {
+                    Ok(JsonValue::Number(self.to_string()))
+                }
+            }
+
+            impl JerParser for $ty {
+                fn from_jer(value: &JsonValue) -> JerResult<Self> {
+                    let n = integer_text(value)?;
+                    n.parse::<$ty>().map_err(|_| JerError::IntegerTooLarge)
+                }
+            }
+        )*
+    };
+}
+
+impl_jer_int!(i8 i16 i32 i64 i128 u8 u16 u32 u64 u128);
+
+impl ToJer for Integer<'_> {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        if let Ok(i) = self.as_i128() {
+            return Ok(JsonValue::from(i));
+        }
+        #[cfg(feature = "bigint")]
+        {
+            Ok(JsonValue::Number(self.as_bigint().to_string()))
+        }
+        #[cfg(not(feature = "bigint"))]
+        {
+            Err(JerError::IntegerTooLarge)
+        }
+    }
+}
+
+impl JerParser for Integer<'_> {
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        let n = integer_text(value)?;
+        if let Ok(i) = n.parse::<i128>() {
+            return Ok(Integer::from_i128(i));
+        }
+        #[cfg(feature = "bigint")]
+        {
+            let i = n
+                .parse::<crate::BigInt>()
+                .map_err(|_| JerError::InvalidValue)?;
+            Ok(Integer {
+                data: Cow::Owned(i.to_signed_bytes_be()),
+            })
+        }
+        #[cfg(not(feature = "bigint"))]
+        {
+            Err(JerError::IntegerTooLarge)
+        }
+    }
+}
+
+/// `ENUMERATED` values of derived types are encoded using identifiers. Since this type has no
+/// identifiers, the numerical value is used.
+impl ToJer for Enumerated {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        self.0.to_jer()
+    }
+}
+
+impl JerParser for Enumerated {
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        u32::from_jer(value).map(Enumerated)
+    }
+}
+
+//---- REAL (X.697: 20)
+
+/// Special values are encoded as strings: `"INF"`, `"-INF"` and `"NaN"`
+impl ToJer for Real {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        let v = match self {
+            Real::Zero => JsonValue::Number("0".into()),
+            Real::Infinity => JsonValue::from("INF"),
+            Real::NegInfinity => JsonValue::from("-INF"),
+            Real::NaN => JsonValue::from("NaN"),
+            Real::Binary { .. } => JsonValue::from_f64(self.f64()).ok_or(JerError::InvalidValue)?,
+        };
+        Ok(v)
+    }
+}
+
+impl JerParser for Real {
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        match value {
+            JsonValue::Number(n) => {
+                let f = n.parse::<f64>().map_err(|_| JerError::InvalidValue)?;
+                Ok(Real::new(f))
+            }
+            JsonValue::String(s) => match s.as_str() {
+                "INF" => Ok(Real::Infinity),
+                "-INF" => Ok(Real::NegInfinity),
+                "NaN" => Ok(Real::NaN),
+                "0" | "-0" => Ok(Real::Zero),
+                _ => Err(JerError::InvalidValue),
+            },
+            _ => Err(JerError::UnexpectedType),
+        }
+    }
+}
+
+//---- BIT STRING (X.697: 22)
+
+/// Bit strings are encoded as an object with members `value` (hexadecimal string) and `length`
+/// (number of bits)
+impl ToJer for BitString {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        let nbits = self.len();
+        let mut bytes = self.as_raw_slice()[..(nbits + 7) / 8].to_vec();
+        // unused bits are set to zero
+        if let Some(last) = bytes.last_mut() {
+            *last &= 0xff << (8 * ((nbits + 7) / 8) - nbits);
+        }
+        let mut o = JsonObject::new();
+        o.insert("value", JsonValue::String(encode_hex(&bytes)));
+        o.insert("length", JsonValue::Number(nbits.to_string()));
+        Ok(JsonValue::Object(o))
+    }
+}
+
+impl JerParser for BitString {
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        let o = value.as_object().ok_or(JerError::UnexpectedType)?;
+        let s = o
+            .member("value")?
+            .as_str()
+            .ok_or(JerError::UnexpectedType)?;
+        let bytes = decode_hex(s)?;
+        let nbits = bitstring_length(o.member("length")?)?;
+        if bytes.len() != (nbits + 7) / 8 {
+            return Err(JerError::InvalidValue);
+        }
+        if nbits == 0 {
+            return Ok(BitString::default());
+        }
+        Ok(BitString::new((8 * bytes.len() - nbits) as u8, &bytes))
+    }
+}
+
+/// Returns the number of bits of a bit string (`length` member)
+fn bitstring_length(value: &JsonValue) -> JerResult<usize> {
+    let n = integer_text(value)?;
+    n.parse().map_err(|_| JerError::IntegerTooLarge)
+}
+
+//---- OCTET STRING (X.697: 23)
+
+/// Octet strings are encoded as hexadecimal strings
+impl ToJer for OctetString<'_> {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        Ok(JsonValue::String(encode_hex(self.as_ref())))
+    }
+}
+
+impl JerParser for OctetString<'_> {
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        let s = value.as_str().ok_or(JerError::UnexpectedType)?;
+        let bytes = decode_hex(s)?;
+        Ok(OctetString::from(Cow::Owned(bytes)))
+    }
+}
+
+//---- OBJECT IDENTIFIER (X.697: 26) and RELATIVE-OID (X.697: 29)
+
+/// Object identifiers are encoded as strings, using the dotted notation
+impl ToJer for Oid<'_> {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        Ok(JsonValue::String(self.to_id_string()))
+    }
+}
+
+/// Relative object identifiers cannot be distinguished from absolute ones, so this function always
+/// returns an absolute object identifier
+impl JerParser for Oid<'_> {
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        let s = value.as_str().ok_or(JerError::UnexpectedType)?;
+        let arcs = s
+            .split('.')
+            .map(|arc| arc.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| JerError::InvalidValue)?;
+        Oid::from(&arcs).map_err(|_| JerError::InvalidValue)
+    }
+}
+
+//---- UTCTime and GeneralizedTime (X.697: 33)
+
+/// Time values are encoded as strings, containing the characters of their BER encoding
+impl ToJer for UtcTime {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        Ok(JsonValue::String(self.to_value_string()))
+    }
+}
+
+impl JerParser for UtcTime {
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        let s = value.as_str().ok_or(JerError::UnexpectedType)?;
+        UtcTime::from_bytes(s.as_bytes()).map_err(|_| JerError::InvalidValue)
+    }
+}
+
+impl ToJer for GeneralizedTime {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        Ok(JsonValue::String(self.to_value_string()))
+    }
+}
+
+impl JerParser for GeneralizedTime {
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        let s = value.as_str().ok_or(JerError::UnexpectedType)?;
+        GeneralizedTime::from_bytes(s.as_bytes()).map_err(|_| JerError::InvalidValue)
+    }
+}
+
+//---- OPTIONAL
+
+/// Absent optional values are omitted by the enclosing type (for ex. in a `SEQUENCE`). When
+/// encoded alone, `None` is encoded as `null`.
+impl<T> ToJer for Option<T>
+where
+    T: ToJer,
+{
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        match self {
+            Some(t) => t.to_jer(),
+            None => Ok(JsonValue::Null),
+        }
+    }
+}
+
+/// `null` is decoded as `None`
+impl<T> JerParser for Option<T>
+where
+    T: JerParser,
+{
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        if value.is_null() {
+            return Ok(None);
+        }
+        T::from_jer(value).map(Some)
+    }
+}
+
+//---- SEQUENCE OF (X.697: 28) and SET OF (X.697: 28)
+
+impl<T> ToJer for [T]
+where
+    T: ToJer,
+{
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        let items = self.iter().map(T::to_jer).collect::<JerResult<_>>()?;
+        Ok(JsonValue::Array(items))
+    }
+}
+
+impl<T> ToJer for Vec<T>
+where
+    T: ToJer,
+{
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        self.as_slice().to_jer()
+    }
+}
+
+impl<T> JerParser for Vec<T>
+where
+    T: JerParser,
+{
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        let items = value.as_array().ok_or(JerError::UnexpectedType)?;
+        items.iter().map(T::from_jer).collect()
+    }
+}
+
+impl<T> ToJer for SequenceOf<T>
+where
+    T: ToJer,
+{
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        self.as_ref().to_jer()
+    }
+}
+
+impl<T> JerParser for SequenceOf<T>
+where
+    T: JerParser,
+{
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        Vec::from_jer(value).map(SequenceOf::new)
+    }
+}
+
+impl<T> ToJer for SetOf<T>
+where
+    T: ToJer,
+{
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        self.as_ref().to_jer()
+    }
+}
+
+impl<T> JerParser for SetOf<T>
+where
+    T: JerParser,
+{
+    fn from_jer(value: &JsonValue) -> JerResult<Self> {
+        Vec::from_jer(value).map(SetOf::new)
+    }
+}
+
+//---- Any
+
+/// Encode an `Any` object, without schema information
+///
+/// The type of the object is guessed from its tag:
+/// - universal primitive types are encoded as described in X.697
+/// - constructed objects (for ex. `SEQUENCE`, `SET` or tagged values) are encoded as an array of
+///   their sub-objects (member names are not known)
+/// - other objects are encoded as a hexadecimal string of their content
+///
+/// This encoding cannot be decoded back to an `Any` object.
+impl ToJer for Any<'_> {
+    fn to_jer(&self) -> JerResult<JsonValue> {
+        fn convert<T: ToJer, E>(r: Result<T, E>) -> JerResult<JsonValue> {
+            r.map_err(|_| JerError::InvalidValue)?.to_jer()
+        }
+
+        if self.header.is_constructed() {
+            let items = AnyIterator::<BerMode>::new(self.data.clone())
+                .map(|r| match r {
+                    Ok((_, any)) => any.to_jer(),
+                    Err(_) => Err(JerError::InvalidValue),
+                })
+                .collect::<JerResult<_>>()?;
+            return Ok(JsonValue::Array(items));
+        }
+        if self.class() != Class::Universal {
+            return Ok(JsonValue::String(encode_hex(self.as_bytes())));
+        }
+        let any = self.clone();
+        match self.tag() {
+            Tag::Boolean => convert(any.bool()),
+            Tag::Integer => convert(any.integer()),
+            Tag::BitString => convert(any.bitstring()),
+            Tag::OctetString => convert(any.octetstring()),
+            Tag::Null => Ok(JsonValue::Null),
+            Tag::Oid => convert(any.oid()),
+            Tag::RelativeOid => convert(any.relative_oid()),
+            Tag::RealType => convert(any.real()),
+            Tag::Enumerated => convert(any.enumerated()),
+            Tag::UtcTime => convert(any.utctime()),
+            Tag::GeneralizedTime => convert(any.generalizedtime()),
+            Tag::BmpString => convert(any.bmpstring()),
+            Tag::UniversalString => convert(any.universalstring()),
+            Tag::Utf8String
+            | Tag::NumericString
+            | Tag::PrintableString
+            | Tag::T61String
+            | Tag::VideotexString
+            | Tag::Ia5String
+            | Tag::GraphicString
+            | Tag::VisibleString
+            | Tag::GeneralString
+            | Tag::ObjectDescriptor => convert(self.as_any_str()),
+            _ => Ok(JsonValue::String(encode_hex(self.as_bytes()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use hex_literal::hex;
+
+    use crate::jer::{JerParser, JsonValue, ToJer};
+    use crate::{
+        oid, Any, BitString, DerParser, GeneralizedTime, Input, Integer, JerError, Null,
+        OctetString, Oid, Real, SequenceOf, UtcTime,
+    };
+
+    #[test]
+    fn jer_primitive_types() {
+        assert_eq!(true.to_jer_string().unwrap(), "true");
+        assert!(bool::parse_jer("true").unwrap());
+        assert_eq!(Null::new().to_jer_string().unwrap(), "null");
+        bool::parse_jer("1").unwrap_err();
+
+        assert_eq!((-129i16).to_jer_string().unwrap(), "-129");
+        assert_eq!(i16::parse_jer("-129").unwrap(), -129);
+        assert_eq!(u8::parse_jer("256"), Err(JerError::IntegerTooLarge));
+        assert_eq!(u8::parse_jer("1.5"), Err(JerError::InvalidValue));
+
+        let i = Integer::from_u64(0x8000_0000_0000_0000);
+        let s = i.to_jer_string().unwrap();
+        assert_eq!(s, "9223372036854775808");
+        assert_eq!(Integer::parse_jer(&s).unwrap(), i);
+    }
+
+    #[test]
+    fn jer_real() {
+        assert_eq!(Real::new(1.5).to_jer_string().unwrap(), "1.5");
+        assert_eq!(Real::Infinity.to_jer_string().unwrap(), "\"INF\"");
+        assert_eq!(Real::NaN.to_jer_string().unwrap(), "\"NaN\"");
+        assert_eq!(Real::parse_jer("\"-INF\"").unwrap(), Real::NegInfinity);
+        assert_eq!(Real::parse_jer("-2.5e1").unwrap().f64(), -25.0);
+        assert_eq!(Real::parse_jer("0").unwrap(), Real::Zero);
+    }
+
+    #[test]
+    fn jer_strings_of_bits_and_octets() {
+        let b = BitString::new(4, &hex!("a5 f0"));
+        let s = b.to_jer_string().unwrap();
+        assert_eq!(s, r#"{"value":"a5f0","length":12}"#);
+        assert_eq!(BitString::parse_jer(&s).unwrap(), b);
+        BitString::parse_jer(r#"{"value":"a5f0","length":17}"#).unwrap_err();
+
+        let s = OctetString::new(&hex!("01 ff")).to_jer_string().unwrap();
+        assert_eq!(s, r#""01ff""#);
+        assert_eq!(
+            OctetString::parse_jer(r#""01FF""#).unwrap().as_ref(),
+            &hex!("01 ff")
+        );
+        OctetString::parse_jer(r#""1ff""#).unwrap_err();
+    }
+
+    #[test]
+    fn jer_oid_and_times() {
+        let oid = oid!(1.2.840 .113549);
+        let s = oid.to_jer_string().unwrap();
+        assert_eq!(s, r#""1.2.840.113549""#);
+        assert_eq!(Oid::parse_jer(&s).unwrap(), oid);
+        Oid::parse_jer(r#""1.2.x""#).unwrap_err();
+
+        let t = UtcTime::from_bytes(b"021213142923Z").unwrap();
+        let s = t.to_jer_string().unwrap();
+        assert_eq!(s, r#""021213142923Z""#);
+        assert_eq!(UtcTime::parse_jer(&s).unwrap(), t);
+
+        let t = GeneralizedTime::from_bytes(b"20021213142923+0100").unwrap();
+        let s = t.to_jer_string().unwrap();
+        assert_eq!(s, r#""20021213142923+0100""#);
+        assert_eq!(GeneralizedTime::parse_jer(&s).unwrap(), t);
+
+        // fractional seconds: leading zeroes are kept, trailing zeroes are removed
+        for (input, expected) in [
+            ("20021213142923.005Z", r#""20021213142923.005Z""#),
+            ("20021213142923.05Z", r#""20021213142923.05Z""#),
+            ("20021213142923.5Z", r#""20021213142923.5Z""#),
+        ] {
+            let t = GeneralizedTime::from_bytes(input.as_bytes()).unwrap();
+            let s = t.to_jer_string().unwrap();
+            assert_eq!(s, expected);
+            assert_eq!(GeneralizedTime::parse_jer(&s).unwrap(), t);
+        }
+    }
+
+    #[test]
+    fn jer_sequence_of_and_options() {
+        let v = SequenceOf::new(vec![1u8, 2, 3]);
+        let s = v.to_jer_string().unwrap();
+        assert_eq!(s, "[1,2,3]");
+        assert_eq!(SequenceOf::<u8>::parse_jer(&s).unwrap(), v);
+
+        assert_eq!(Option::<bool>::parse_jer("null").unwrap(), None);
+        assert_eq!(Option::<bool>::parse_jer("false").unwrap(), Some(false));
+    }
+
+    #[test]
+    fn jer_any() {
+        // SEQUENCE { INTEGER 2, [0] { OCTET STRING 01 }, UTF8String "a", [1] 02 }
+        let input = &hex!("30 0e 02 01 02 a0 03 04 01 01 0c 01 61 81 01 02");
+        let (_, any) = Any::parse_der(Input::from(input)).unwrap();
+        let v = any.to_jer().unwrap();
+        assert_eq!(v.to_string(), r#"[2,["01"],"a","02"]"#);
+        assert!(matches!(v, JsonValue::Array(_)));
+    }
+}
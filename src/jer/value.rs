@@ -0,0 +1,510 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{JerError, JerResult};
+
+/// Maximum nesting level of arrays and objects accepted by [`JsonValue::parse`]
+const MAX_DEPTH: usize = 128;
+
+/// A JSON value
+///
+/// This is a minimal representation of JSON values (RFC 8259), used as the target of JER
+/// encodings. Numbers are stored as text, so integers of any size can be represented without loss.
+///
+/// The [`Display`](fmt::Display) implementation produces compact JSON text, and
+/// [`JsonValue::parse`] reads JSON text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    /// Number, as JSON text (for ex. `-12` or `1.5e3`)
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(JsonObject),
+}
+
+impl JsonValue {
+    /// Parse a complete JSON text
+    ///
+    /// Leading and trailing whitespace is allowed. Any other trailing data is an error.
+    pub fn parse(s: &str) -> JerResult<Self> {
+        let mut parser = JsonParser {
+            input: s.as_bytes(),
+            pos: 0,
+        };
+        let value = parser.parse_value(0)?;
+        parser.skip_whitespace();
+        if parser.pos != parser.input.len() {
+            return Err(JerError::InvalidJson(parser.pos));
+        }
+        Ok(value)
+    }
+
+    /// Build a number from an `f64` value
+    ///
+    /// Returns `None` if the value is not finite.
+    pub fn from_f64(f: f64) -> Option<Self> {
+        if f.is_finite() {
+            Some(JsonValue::Number(f.to_string()))
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the value is `null`
+    #[inline]
+    pub const fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+
+    /// Returns the value if it is a boolean
+    pub const fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the text of the number, if the value is a number
+    pub fn as_number(&self) -> Option<&str> {
+        match self {
+            JsonValue::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the items if the value is an array
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            JsonValue::Array(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the members if the value is an object
+    pub const fn as_object(&self) -> Option<&JsonObject> {
+        match self {
+            JsonValue::Object(o) => Some(o),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(b: bool) -> Self {
+        JsonValue::Bool(b)
+    }
+}
+
+impl From<i128> for JsonValue {
+    fn from(i: i128) -> Self {
+        JsonValue::Number(i.to_string())
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(s: &str) -> Self {
+        JsonValue::String(s.to_string())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(s: String) -> Self {
+        JsonValue::String(s)
+    }
+}
+
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonValue::Null => f.write_str("null"),
+            JsonValue::Bool(b) => write!(f, "{b}"),
+            JsonValue::Number(n) => f.write_str(n),
+            JsonValue::String(s) => write_json_string(f, s),
+            JsonValue::Array(items) => {
+                f.write_str("[")?;
+                for (idx, item) in items.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_str("]")
+            }
+            JsonValue::Object(o) => write!(f, "{o}"),
+        }
+    }
+}
+
+/// A JSON object
+///
+/// Members are kept in insertion order. Member names should be unique: [`JsonObject::get`]
+/// returns the first member with the given name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JsonObject {
+    members: Vec<(String, JsonValue)>,
+}
+
+impl JsonObject {
+    /// Build a new, empty object
+    pub const fn new() -> Self {
+        JsonObject {
+            members: Vec::new(),
+        }
+    }
+
+    /// Returns the number of members
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns `true` if the object has no members
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Append a member
+    pub fn insert(&mut self, name: &str, value: JsonValue) {
+        self.members.push((name.to_string(), value));
+    }
+
+    /// Returns the value of member `name`, if present
+    pub fn get(&self, name: &str) -> Option<&JsonValue> {
+        self.members
+            .iter()
+            .find_map(|(n, v)| (n == name).then_some(v))
+    }
+
+    /// Returns the value of member `name`, or [`JerError::MissingMember`] if absent
+    pub fn member(&self, name: &str) -> JerResult<&JsonValue> {
+        self.get(name)
+            .ok_or_else(|| JerError::MissingMember(name.to_string()))
+    }
+
+    /// Return an iterator over members (name and value)
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &JsonValue)> {
+        self.members.iter().map(|(n, v)| (n.as_str(), v))
+    }
+}
+
+impl fmt::Display for JsonObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("{")?;
+        for (idx, (name, value)) in self.members.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(",")?;
+            }
+            write_json_string(f, name)?;
+            write!(f, ":{value}")?;
+        }
+        f.write_str("}")
+    }
+}
+
+fn write_json_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    f.write_str("\"")
+}
+
+struct JsonParser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl JsonParser<'_> {
+    fn error(&self) -> JerError {
+        JerError::InvalidJson(self.pos)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, b: u8) -> JerResult<()> {
+        if self.peek() != Some(b) {
+            return Err(self.error());
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn expect_keyword(&mut self, keyword: &[u8]) -> JerResult<()> {
+        if !self.input[self.pos..].starts_with(keyword) {
+            return Err(self.error());
+        }
+        self.pos += keyword.len();
+        Ok(())
+    }
+
+    fn parse_value(&mut self, depth: usize) -> JerResult<JsonValue> {
+        if depth > MAX_DEPTH {
+            return Err(self.error());
+        }
+        self.skip_whitespace();
+        match self.peek().ok_or_else(|| self.error())? {
+            b'n' => self.expect_keyword(b"null").map(|_| JsonValue::Null),
+            b't' => self.expect_keyword(b"true").map(|_| JsonValue::Bool(true)),
+            b'f' => self
+                .expect_keyword(b"false")
+                .map(|_| JsonValue::Bool(false)),
+            b'"' => self.parse_string().map(JsonValue::String),
+            b'[' => self.parse_array(depth),
+            b'{' => self.parse_object(depth),
+            b'-' | b'0'..=b'9' => self.parse_number(),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn parse_array(&mut self, depth: usize) -> JerResult<JsonValue> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(JsonValue::Array(items));
+        }
+        loop {
+            items.push(self.parse_value(depth + 1)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    fn parse_object(&mut self, depth: usize) -> JerResult<JsonValue> {
+        self.expect(b'{')?;
+        let mut object = JsonObject::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(object));
+        }
+        loop {
+            self.skip_whitespace();
+            let name = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+            let value = self.parse_value(depth + 1)?;
+            object.members.push((name, value));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(object));
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> JerResult<JsonValue> {
+        let start = self.pos;
+        let digits = |p: &mut Self| {
+            let start = p.pos;
+            while let Some(b'0'..=b'9') = p.peek() {
+                p.pos += 1;
+            }
+            p.pos - start
+        };
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        // no leading zeroes
+        if self.peek() == Some(b'0') {
+            self.pos += 1;
+        } else if digits(self) == 0 {
+            return Err(self.error());
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if digits(self) == 0 {
+                return Err(self.error());
+            }
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            if digits(self) == 0 {
+                return Err(self.error());
+            }
+        }
+        // input is a str, and the number contains only ASCII characters
+        let s = core::str::from_utf8(&self.input[start..self.pos]).map_err(|_| self.error())?;
+        Ok(JsonValue::Number(s.to_string()))
+    }
+
+    fn parse_hex4(&mut self) -> JerResult<u32> {
+        let hex = self
+            .input
+            .get(self.pos..self.pos + 4)
+            .ok_or_else(|| self.error())?;
+        let s = core::str::from_utf8(hex).map_err(|_| self.error())?;
+        let v = u32::from_str_radix(s, 16).map_err(|_| self.error())?;
+        self.pos += 4;
+        Ok(v)
+    }
+
+    fn parse_string(&mut self) -> JerResult<String> {
+        self.expect(b'"')?;
+        let mut s = String::new();
+        loop {
+            // copy unescaped characters
+            let start = self.pos;
+            while let Some(b) = self.peek() {
+                if b == b'"' || b == b'\\' || b < 0x20 {
+                    break;
+                }
+                self.pos += 1;
+            }
+            // input is a str, and the slice boundaries are ASCII characters
+            let chunk =
+                core::str::from_utf8(&self.input[start..self.pos]).map_err(|_| self.error())?;
+            s.push_str(chunk);
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(s);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let b = self.peek().ok_or_else(|| self.error())?;
+                    self.pos += 1;
+                    let c = match b {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{08}',
+                        b'f' => '\u{0c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let hi = self.parse_hex4()?;
+                            let v = if (0xd800..0xdc00).contains(&hi) {
+                                // surrogate pair
+                                self.expect_keyword(b"\\u")?;
+                                let lo = self.parse_hex4()?;
+                                if !(0xdc00..0xe000).contains(&lo) {
+                                    return Err(self.error());
+                                }
+                                0x10000 + ((hi - 0xd800) << 10) + (lo - 0xdc00)
+                            } else {
+                                hi
+                            };
+                            char::from_u32(v).ok_or_else(|| self.error())?
+                        }
+                        _ => return Err(self.error()),
+                    };
+                    s.push(c);
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use crate::jer::{JsonObject, JsonValue};
+    use crate::JerError;
+
+    #[test]
+    fn json_display() {
+        let mut o = JsonObject::new();
+        o.insert("a", JsonValue::from(-12));
+        o.insert("b", JsonValue::from("x\"\u{1}"));
+        o.insert(
+            "c",
+            JsonValue::Array(vec![JsonValue::Null, JsonValue::Bool(true)]),
+        );
+        let v = JsonValue::Object(o);
+        assert_eq!(
+            v.to_string(),
+            r#"{"a":-12,"b":"x\"\u0001","c":[null,true]}"#
+        );
+        assert_eq!(
+            JsonValue::from_f64(1.5),
+            Some(JsonValue::Number("1.5".into()))
+        );
+        assert_eq!(JsonValue::from_f64(f64::NAN), None);
+    }
+
+    #[test]
+    fn json_parse() {
+        let v = JsonValue::parse(r#" { "a" : [1, -0.5e+3, "\u00e9\ud83d\ude00\n"], "b": {} } "#)
+            .expect("parsing failed");
+        let o = v.as_object().unwrap();
+        let a = o.get("a").and_then(JsonValue::as_array).unwrap();
+        assert_eq!(a[0].as_number(), Some("1"));
+        assert_eq!(a[1].as_number(), Some("-0.5e+3"));
+        assert_eq!(a[2].as_str(), Some("é\u{1f600}\n"));
+        assert!(o
+            .get("b")
+            .and_then(JsonValue::as_object)
+            .unwrap()
+            .is_empty());
+        assert_eq!(o.member("c"), Err(JerError::MissingMember("c".into())));
+
+        // round-trip
+        assert_eq!(JsonValue::parse(&v.to_string()).unwrap(), v);
+
+        // invalid texts
+        for s in [
+            "",
+            "[1,]",
+            "01",
+            "1.",
+            "\"a",
+            "{\"a\" 1}",
+            "nul",
+            "[] x",
+            "\"\\ud800\"",
+        ] {
+            JsonValue::parse(s).expect_err(s);
+        }
+        // nesting limit
+        let s = "[".repeat(200) + &"]".repeat(200);
+        JsonValue::parse(&s).expect_err("too deep");
+    }
+}
@@ -91,12 +91,35 @@
 //! If the parsing succeeds, but the integer cannot fit into the expected type, the method will return
 //! an `IntegerTooLarge` error.
 //!
+//! When parsing untrusted input, resources used by parsers (nesting depth, object length,
+//! number of elements and size of segmented strings) can be bounded by attaching [`Limits`] to
+//! the input using [`Input::with_limits`] (with a new [`LimitsState`] for each message).
+//!
+//! Parsers require the complete input in memory. To read objects from large streams (implementing
+//! `std::io::Read`), use the [`BerReader`] and [`DerReader`] stream readers.
+//! On async streams, the `codec` feature provides `BerCodec` and `DerCodec`, implementing the
+//! `tokio-util` `Decoder` and `Encoder` traits to frame complete objects.
+//! For event-driven parsing of data received in chunks, without I/O, see [`PushParser`].
+//! To walk objects without building `Any` objects or collections (for ex. to extract a few
+//! fields from large structures), implement the [`Visitor`] trait and use [`visit_ber`] or
+//! [`visit_der`].
+//! For random access to the objects of a DER document (for ex. using paths like `/0/[ctx 3]`),
+//! index it once using [`DerDocument`].
+//!
+//! To locate parsing errors in the input, build an [`ErrorReport`] (with offsets, enclosing
+//! objects and an annotated hex dump) using [`BerError::report`].
+//! To check that input is valid DER and collect all violations (instead of failing on the first
+//! one), use [`lint_der`] (see also the `der-lint` example).
+//! To convert BER input to DER without knowing its schema, use `ber_to_der`.
+//!
 //! # BER/DER encoders
 //!
 //! BER/DER encoding is symmetrical to decoding, using the traits
 //! [`ToBer`](crate::to_ber::ToBer) and [`ToDer`](crate::to_der::ToDer) traits.
-//! These traits provide methods to write encoded content to objects with the `io::Write` trait,
+//! These traits provide methods to write encoded content to objects with the [`Writer`] trait
+//! (for ex. `Vec<u8>`, or a fixed `&mut [u8]` buffer, which does not require `std` nor allocation),
 //! or return an allocated `Vec<u8>` with the encoded data.
+//! To write to objects with the `io::Write` trait, wrap them in an `IoWriter`.
 //! If the serialization fails, an error is returned.
 //!
 //! ## Examples
@@ -160,6 +183,9 @@
 //!
 //! See [doc::derive] for documentation and examples.
 //!
+//! To generate these types from ASN.1 module definitions (for ex. in a build script), see the
+//! `asn1-rs-compiler` crate.
+//!
 #![doc = include_str!("../doc/ASN1.md")]
 //!
 //! ## Changes
@@ -211,36 +237,79 @@ pub use input::*;
 
 mod asn1_types;
 mod ber;
+#[cfg(feature = "std")]
+mod canonical;
 mod class;
+#[cfg(feature = "codec")]
+mod codec;
+mod constraints;
 mod datetime;
 mod debug;
+mod der_lint;
 mod derive;
+mod document;
 mod error;
 mod from_ber;
+mod from_cer;
 mod from_der;
 mod header;
+#[cfg(feature = "jer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jer")))]
+pub mod jer;
 mod length;
+#[cfg(feature = "oer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "oer")))]
+pub mod oer;
+#[cfg(feature = "per")]
+#[cfg_attr(docsrs, doc(cfg(feature = "per")))]
+pub mod per;
+mod push_parser;
+#[cfg(feature = "std")]
+mod reader;
+mod report;
 mod tag;
 mod to_ber;
+mod to_cer;
 mod to_der;
 mod tostatic;
 mod traits;
+#[cfg(feature = "value-notation")]
+#[cfg_attr(docsrs, doc(cfg(feature = "value-notation")))]
+pub mod value_notation;
+mod visitor;
+mod writer;
+#[cfg(feature = "xer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "xer")))]
+pub mod xer;
 
 pub use asn1_types::*;
 pub use ber::*;
 pub use class::*;
+#[cfg(feature = "codec")]
+pub use codec::*;
+pub use constraints::*;
 pub use datetime::*;
+pub use der_lint::*;
 pub use derive::*;
+pub use document::*;
 pub use error::*;
 pub use from_ber::*;
+pub use from_cer::*;
 pub use from_der::*;
 pub use header::*;
 pub use length::*;
+pub use push_parser::*;
+pub use report::*;
 pub use tag::*;
+pub use to_ber::*;
+pub use to_cer::*;
+pub use to_der::*;
 pub use traits::*;
+pub use visitor::*;
+pub use writer::*;
 
 #[cfg(feature = "std")]
-pub use {to_ber::*, to_der::*};
+pub use {canonical::*, reader::*};
 
 pub use nom;
 pub use nom::{Err, IResult, Needed};
@@ -253,6 +322,7 @@ pub use num_bigint;
 #[doc(hidden)]
 pub mod exports {
     pub use alloc::borrow;
+    pub use alloc::vec;
     pub use asn1_rs_impl;
 }
 
@@ -0,0 +1,274 @@
+use alloc::borrow::Cow;
+use core::convert::TryFrom;
+
+use crate::{Constraints, Enumerated, Integer, OerError, OerResult, ValueConstraint};
+
+use super::{min_signed_octets, OerParser, OerReader, OerWriter, ToOer};
+
+/// Returns `true` if the value constraint is not OER-visible
+///
+/// Extensible constraints are not OER-visible (X.696: 8.2.2).
+fn is_unconstrained(vc: &ValueConstraint) -> bool {
+    vc.extensible || (vc.lower.is_none() && vc.upper.is_none())
+}
+
+/// Encoding of an `INTEGER` value, selected from its value constraint (X.696: 10.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IntegerFormat {
+    /// Non-negative binary integer, using a fixed number of octets
+    Unsigned(usize),
+    /// 2's-complement binary integer, using a fixed number of octets
+    Signed(usize),
+    /// Non-negative binary integer, prefixed with a length determinant
+    UnsignedWithLength,
+    /// 2's-complement binary integer, prefixed with a length determinant
+    SignedWithLength,
+}
+
+impl IntegerFormat {
+    fn new(vc: &ValueConstraint) -> Self {
+        if is_unconstrained(vc) {
+            return IntegerFormat::SignedWithLength;
+        }
+        match (vc.lower, vc.upper) {
+            (Some(lower), Some(upper)) if lower >= 0 => match upper {
+                0..=0xff => IntegerFormat::Unsigned(1),
+                0x100..=0xffff => IntegerFormat::Unsigned(2),
+                0x1_0000..=0xffff_ffff => IntegerFormat::Unsigned(4),
+                0x1_0000_0000..=0xffff_ffff_ffff_ffff => IntegerFormat::Unsigned(8),
+                _ => IntegerFormat::UnsignedWithLength,
+            },
+            (Some(lower), Some(upper)) => {
+                let n = min_signed_octets(lower).max(min_signed_octets(upper));
+                match n {
+                    1 | 2 | 4 | 8 => IntegerFormat::Signed(n),
+                    3 => IntegerFormat::Signed(4),
+                    5..=7 => IntegerFormat::Signed(8),
+                    _ => IntegerFormat::SignedWithLength,
+                }
+            }
+            (Some(lower), None) if lower >= 0 => IntegerFormat::UnsignedWithLength,
+            _ => IntegerFormat::SignedWithLength,
+        }
+    }
+}
+
+/// Returns an error if the constraint is OER-visible and does not contain `value`
+fn check_integer_constraint(vc: &ValueConstraint, value: i128) -> OerResult<()> {
+    if !is_unconstrained(vc) && !vc.contains(value) {
+        return Err(OerError::ConstraintViolation);
+    }
+    Ok(())
+}
+
+/// Encode an `INTEGER` value (X.696: 10)
+fn oer_encode_integer(
+    writer: &mut OerWriter,
+    value: i128,
+    constraints: &Constraints,
+) -> OerResult<()> {
+    let vc = &constraints.value;
+    check_integer_constraint(vc, value)?;
+    match IntegerFormat::new(vc) {
+        IntegerFormat::Unsigned(n) => writer.write_unsigned(value as u128, n),
+        IntegerFormat::Signed(n) => writer.write_signed(value, n),
+        IntegerFormat::UnsignedWithLength => {
+            writer.write_unsigned_with_length(value as u128);
+            Ok(())
+        }
+        IntegerFormat::SignedWithLength => {
+            writer.write_signed_with_length(value);
+            Ok(())
+        }
+    }
+}
+
+/// Decode an `INTEGER` value (X.696: 10)
+fn oer_decode_integer(reader: &mut OerReader, constraints: &Constraints) -> OerResult<i128> {
+    let vc = &constraints.value;
+    let value = match IntegerFormat::new(vc) {
+        IntegerFormat::Unsigned(n) => {
+            i128::try_from(reader.read_unsigned(n)?).map_err(|_| OerError::IntegerTooLarge)?
+        }
+        IntegerFormat::Signed(n) => reader.read_signed(n)?,
+        IntegerFormat::UnsignedWithLength => i128::try_from(reader.read_unsigned_with_length()?)
+            .map_err(|_| OerError::IntegerTooLarge)?,
+        IntegerFormat::SignedWithLength => reader.read_signed_with_length()?,
+    };
+    check_integer_constraint(vc, value)?;
+    Ok(value)
+}
+
+macro_rules! impl_oer_int {
+    ($($ty:ty)*) => {
+        $(
+            impl ToOer for $ty {
+                fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+                    oer_encode_integer(writer, i128::from(*self), constraints)
+                }
+            }
+
+            impl OerParser<'_> for $ty {
+                fn oer_decode(reader: &mut OerReader, constraints: &Constraints) -> OerResult<Self> {
+                    let value = oer_decode_integer(reader, constraints)?;
+                    <$ty>::try_from(value).map_err(|_| OerError::IntegerTooLarge)
+                }
+            }
+        )*
+    };
+}
+
+impl_oer_int!(i8 i16 i32 i64 i128 u8 u16 u32 u64);
+
+impl ToOer for Integer<'_> {
+    fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+        if is_unconstrained(&constraints.value) {
+            // unconstrained: content is already a 2's-complement binary integer
+            writer.write_octets_with_length(&self.data);
+            return Ok(());
+        }
+        let value = self.as_i128().map_err(|_| OerError::IntegerTooLarge)?;
+        oer_encode_integer(writer, value, constraints)
+    }
+}
+
+impl<'a> OerParser<'a> for Integer<'a> {
+    fn oer_decode(reader: &mut OerReader<'a>, constraints: &Constraints) -> OerResult<Self> {
+        if is_unconstrained(&constraints.value) {
+            let data = reader.read_octets_with_length()?;
+            match data {
+                [] => return Err(OerError::InvalidEncoding),
+                [0x00, b, ..] | [0xff, b, ..] => {
+                    // leading octet is redundant if it is equal to the sign of the next one
+                    let redundant = (data[0] ^ b) & 0x80 == 0;
+                    reader.check_canonical(!redundant)?;
+                }
+                _ => (),
+            }
+            return Ok(Integer {
+                data: Cow::Borrowed(data),
+            });
+        }
+        let value = oer_decode_integer(reader, constraints)?;
+        Ok(Integer::from_i128(value))
+    }
+}
+
+/// `ENUMERATED` values are encoded using their numerical value (X.696: 11). Constraints are ignored.
+impl ToOer for Enumerated {
+    fn oer_encode(&self, writer: &mut OerWriter, _constraints: &Constraints) -> OerResult<()> {
+        writer.write_enumerated(i128::from(self.0));
+        Ok(())
+    }
+}
+
+impl OerParser<'_> for Enumerated {
+    fn oer_decode(reader: &mut OerReader, _constraints: &Constraints) -> OerResult<Self> {
+        let value = reader.read_enumerated()?;
+        let value = u32::try_from(value).map_err(|_| OerError::IntegerTooLarge)?;
+        Ok(Enumerated(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use hex_literal::hex;
+
+    use crate::oer::{OerParser, OerReader, OerWriter, ToOer};
+    use crate::{Constraints, Enumerated, Integer, OerError};
+
+    fn oer<T: ToOer>(value: T, c: &Constraints) -> Vec<u8> {
+        let mut w = OerWriter::new();
+        value.oer_encode(&mut w, c).unwrap();
+        w.into_bytes()
+    }
+
+    #[test]
+    fn oer_integer_constrained() {
+        // fixed-size unsigned
+        let c = Constraints::NONE.with_value(Some(0), Some(255), false);
+        assert_eq!(&oer(200u8, &c), &hex!("c8"));
+        let c = Constraints::NONE.with_value(Some(1), Some(256), false);
+        assert_eq!(&oer(200u16, &c), &hex!("00 c8"));
+        let c = Constraints::NONE.with_value(Some(0), Some(0x1_0000), false);
+        let v = oer(200u32, &c);
+        assert_eq!(&v, &hex!("00 00 00 c8"));
+        let mut r = OerReader::coer(&v);
+        assert_eq!(u32::oer_decode(&mut r, &c).unwrap(), 200);
+
+        // fixed-size signed
+        let c = Constraints::NONE.with_value(Some(-100), Some(100), false);
+        let v = oer(-100i32, &c);
+        assert_eq!(&v, &hex!("9c"));
+        let mut r = OerReader::coer(&v);
+        assert_eq!(i32::oer_decode(&mut r, &c).unwrap(), -100);
+        let c = Constraints::NONE.with_value(Some(-1), Some(0x80_0000), false);
+        assert_eq!(&oer(-1i32, &c), &hex!("ff ff ff ff"));
+
+        // value outside constraint
+        let c = Constraints::NONE.with_value(Some(3), Some(6), false);
+        let mut w = OerWriter::new();
+        7u8.oer_encode(&mut w, &c).unwrap_err();
+        let mut r = OerReader::oer(&hex!("07"));
+        assert_eq!(
+            u8::oer_decode(&mut r, &c),
+            Err(OerError::ConstraintViolation)
+        );
+
+        // extensible constraints are not OER-visible
+        let c = Constraints::NONE.with_value(Some(3), Some(6), true);
+        assert_eq!(&oer(7u8, &c), &hex!("01 07"));
+    }
+
+    #[test]
+    fn oer_integer_semi_constrained() {
+        let c = Constraints::NONE.with_value(Some(0), None, false);
+        let v = oer(255i64, &c);
+        assert_eq!(&v, &hex!("01 ff"));
+        let mut r = OerReader::coer(&v);
+        assert_eq!(i64::oer_decode(&mut r, &c).unwrap(), 255);
+
+        // negative lower bound: signed
+        let c = Constraints::NONE.with_value(Some(-1), None, false);
+        assert_eq!(&oer(255i64, &c), &hex!("02 00 ff"));
+    }
+
+    #[test]
+    fn oer_integer_unconstrained() {
+        let v = (-129i16).to_oer_vec().unwrap();
+        assert_eq!(&v, &hex!("02 ff 7f"));
+        assert_eq!(i16::parse_coer(&v).unwrap(), -129);
+
+        // too large for requested type
+        let v = 256u16.to_oer_vec().unwrap();
+        u8::parse_oer(&v).unwrap_err();
+
+        let i = Integer::from_u64(0x8000_0000_0000_0000);
+        let v = i.to_oer_vec().unwrap();
+        assert_eq!(&v, &hex!("09 00 80 00 00 00 00 00 00 00"));
+        assert_eq!(Integer::parse_coer(&v).unwrap(), i);
+
+        // redundant leading octet
+        let input = &hex!("02 ff 80");
+        assert_eq!(Integer::parse_oer(input).unwrap().as_i32(), Ok(-128));
+        assert_eq!(Integer::parse_coer(input), Err(OerError::NonCanonical));
+    }
+
+    #[test]
+    fn oer_enumerated() {
+        let v = Enumerated(2).to_oer_vec().unwrap();
+        assert_eq!(&v, &hex!("02"));
+        assert_eq!(Enumerated::parse_coer(&v).unwrap(), Enumerated(2));
+
+        let v = Enumerated(1000).to_oer_vec().unwrap();
+        assert_eq!(&v, &hex!("82 03 e8"));
+        assert_eq!(Enumerated::parse_coer(&v).unwrap(), Enumerated(1000));
+
+        // long form for a value in 0..=127
+        let input = &hex!("81 05");
+        assert_eq!(Enumerated::parse_oer(input).unwrap(), Enumerated(5));
+        assert_eq!(Enumerated::parse_coer(input), Err(OerError::NonCanonical));
+    }
+}
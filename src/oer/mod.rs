@@ -0,0 +1,90 @@
+//! Octet Encoding Rules (OER) encoders and decoders
+//!
+//! OER is defined in [[X.696]]. Like PER, encodings are not self-describing and depend on the
+//! OER-visible [`Constraints`](crate::Constraints) of the type, but all fields are octet-aligned,
+//! which makes encoding and decoding simpler and faster.
+//!
+//! Two variants are defined:
+//! - BASIC-OER
+//! - CANONICAL-OER (COER), used for ex. by IEEE 1609.2 and ETSI ITS security messages
+//!
+//! Encoders always produce canonical encodings, so the output of [`ToOer`] is valid for both
+//! variants. When decoding, the variant is a property of the [`OerReader`] object: the COER reader
+//! rejects encodings which are not canonical.
+//!
+//! Note that extensible constraints are not OER-visible: they are ignored when encoding and
+//! decoding.
+//!
+//! Types built with the `Sequence`, `Choice`, `Enumerated` and `Alias` custom derive attributes can
+//! implement these traits by adding `OER` to the `parse` and `encode` items of the `asn1` attribute.
+//! Constraints are described using the `value` and `size` items of the `asn1` attribute on fields.
+//! See [doc::derive](crate::doc::derive) for details.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use asn1_rs::oer::{OerParser, OerWriter, ToOer};
+//! use asn1_rs::Constraints;
+//!
+//! // INTEGER (0..65535) is encoded as 2 octets
+//! let c = Constraints::NONE.with_value(Some(0), Some(65535), false);
+//!
+//! let mut writer = OerWriter::new();
+//! 1234u32.oer_encode(&mut writer, &c).expect("serialization failed");
+//! assert_eq!(&writer.into_bytes(), &[0x04, 0xd2]);
+//!
+//! // unconstrained values
+//! let v = 1234u32.to_oer_vec().expect("serialization failed");
+//! assert_eq!(&v, &[0x02, 0x04, 0xd2]);
+//! let value = u32::parse_coer(&v).expect("parsing failed");
+//! assert_eq!(value, 1234);
+//!
+//! // not canonical: BOOLEAN TRUE must be encoded as 0xff
+//! assert!(bool::parse_oer(&[0x01]).expect("parsing failed"));
+//! assert!(bool::parse_coer(&[0x01]).is_err());
+//! ```
+//!
+//! # Limitations
+//!
+//! - `REAL` and time types are not supported
+//! - extension additions of `SEQUENCE` types are skipped when decoding, and extension additions of
+//!   `CHOICE` and `ENUMERATED` types are reported as [`OerError::UnknownExtension`](crate::OerError)
+//! - `SET` and `SET OF` are not supported
+//!
+//! [X.696]: http://www.itu.int/rec/T-REC-X.696/en "Recommendation ITU-T X.696"
+
+mod integer;
+mod reader;
+mod strings;
+mod traits;
+mod types;
+mod writer;
+
+pub use reader::*;
+pub use traits::*;
+pub use writer::*;
+
+/// Returns the number of octets required to encode `value` as a non-negative binary integer
+///
+/// The result is at least 1.
+#[inline]
+pub(crate) const fn min_unsigned_octets(value: u128) -> usize {
+    let bits = (u128::BITS - value.leading_zeros()) as usize;
+    if bits == 0 {
+        1
+    } else {
+        (bits + 7) / 8
+    }
+}
+
+/// Returns the number of octets required to encode `value` as a 2's-complement binary integer
+#[inline]
+pub(crate) const fn min_signed_octets(value: i128) -> usize {
+    // number of significant bits, plus the sign bit
+    let bits = if value < 0 {
+        i128::BITS - value.leading_ones() + 1
+    } else {
+        i128::BITS - value.leading_zeros() + 1
+    } as usize;
+    (bits + 7) / 8
+}
@@ -0,0 +1,342 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::{Class, OerError, OerResult, Tag};
+
+use super::{min_signed_octets, min_unsigned_octets};
+
+/// Reader for OER encodings
+///
+/// The reader holds the variant (BASIC-OER or CANONICAL-OER) of the encoding. If the canonical
+/// variant is used, encodings which are not canonical are rejected with
+/// [`OerError::NonCanonical`].
+///
+/// Procedures defined in [[X.696]] section 8 are provided as methods, and are used to implement
+/// [`OerParser`](super::OerParser).
+///
+/// [X.696]: http://www.itu.int/rec/T-REC-X.696/en "Recommendation ITU-T X.696"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OerReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    canonical: bool,
+}
+
+impl<'a> OerReader<'a> {
+    /// Build a new reader. If `canonical` is `true`, the canonical variant (COER) is used.
+    pub const fn new(data: &'a [u8], canonical: bool) -> Self {
+        OerReader {
+            data,
+            pos: 0,
+            canonical,
+        }
+    }
+
+    /// Build a new reader for the basic variant (BASIC-OER)
+    #[inline]
+    pub const fn oer(data: &'a [u8]) -> Self {
+        Self::new(data, false)
+    }
+
+    /// Build a new reader for the canonical variant (COER)
+    #[inline]
+    pub const fn coer(data: &'a [u8]) -> Self {
+        Self::new(data, true)
+    }
+
+    /// Returns `true` if the reader uses the canonical variant
+    #[inline]
+    pub const fn is_canonical(&self) -> bool {
+        self.canonical
+    }
+
+    /// Returns the current position (in octets)
+    #[inline]
+    pub const fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the input octets following the current position
+    #[inline]
+    pub fn remaining_bytes(&self) -> &'a [u8] {
+        &self.data[self.pos..]
+    }
+
+    /// Returns an error if the reader is canonical and `valid` is `false`
+    #[inline]
+    pub fn check_canonical(&self, valid: bool) -> OerResult<()> {
+        if self.canonical && !valid {
+            return Err(OerError::NonCanonical);
+        }
+        Ok(())
+    }
+
+    /// Read a single octet
+    pub fn read_u8(&mut self) -> OerResult<u8> {
+        let b = *self
+            .data
+            .get(self.pos)
+            .ok_or(OerError::Incomplete(nom::Needed::new(1)))?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    /// Read `len` octets
+    pub fn read_bytes(&mut self, len: usize) -> OerResult<&'a [u8]> {
+        let remaining = self.data.len() - self.pos;
+        if len > remaining {
+            return Err(OerError::Incomplete(nom::Needed::new(len - remaining)));
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    /// Decode a length determinant (X.696: 8.6)
+    pub fn read_length(&mut self) -> OerResult<usize> {
+        let b = self.read_u8()?;
+        if b & 0x80 == 0 {
+            return Ok(usize::from(b));
+        }
+        let n = usize::from(b & 0x7f);
+        let bytes = self.read_bytes(n)?;
+        let len = bytes_to_u128(bytes)?;
+        // long form must use the minimum number of octets, and only for lengths >= 128
+        self.check_canonical(len >= 128 && n == min_unsigned_octets(len))?;
+        usize::try_from(len).map_err(|_| OerError::InvalidEncoding)
+    }
+
+    /// Decode an unsigned integer encoded using exactly `noctets` octets (X.696: 10.3)
+    pub fn read_unsigned(&mut self, noctets: usize) -> OerResult<u128> {
+        let bytes = self.read_bytes(noctets)?;
+        bytes_to_u128(bytes)
+    }
+
+    /// Decode a 2's-complement integer encoded using exactly `noctets` octets (X.696: 10.4)
+    pub fn read_signed(&mut self, noctets: usize) -> OerResult<i128> {
+        let bytes = self.read_bytes(noctets)?;
+        bytes_to_i128(bytes)
+    }
+
+    /// Decode an unsigned integer, prefixed with a length determinant (X.696: 10.6)
+    pub fn read_unsigned_with_length(&mut self) -> OerResult<u128> {
+        let len = self.read_length()?;
+        if len == 0 {
+            return Err(OerError::InvalidEncoding);
+        }
+        let value = self.read_unsigned(len)?;
+        self.check_canonical(len == min_unsigned_octets(value))?;
+        Ok(value)
+    }
+
+    /// Decode a 2's-complement integer, prefixed with a length determinant (X.696: 10.7)
+    pub fn read_signed_with_length(&mut self) -> OerResult<i128> {
+        let len = self.read_length()?;
+        if len == 0 {
+            return Err(OerError::InvalidEncoding);
+        }
+        let value = self.read_signed(len)?;
+        self.check_canonical(len == min_signed_octets(value))?;
+        Ok(value)
+    }
+
+    /// Decode the number of items of a `SEQUENCE OF` value (X.696: 21.1)
+    pub fn read_quantity(&mut self) -> OerResult<usize> {
+        let value = self.read_unsigned_with_length()?;
+        usize::try_from(value).map_err(|_| OerError::InvalidEncoding)
+    }
+
+    /// Decode a tag (class and number), as used for `CHOICE` values (X.696: 8.7)
+    pub fn read_tag(&mut self) -> OerResult<(Class, Tag)> {
+        let b = self.read_u8()?;
+        let class = Class::try_from(b >> 6).map_err(|_| OerError::InvalidEncoding)?;
+        if b & 0x3f != 0x3f {
+            return Ok((class, Tag(u32::from(b & 0x3f))));
+        }
+        let mut tag = 0u32;
+        for i in 0.. {
+            let b = self.read_u8()?;
+            // first octet must not be 0x80 (no leading zero bits)
+            if i == 0 && b == 0x80 {
+                return Err(OerError::InvalidEncoding);
+            }
+            if tag >> 25 != 0 {
+                return Err(OerError::InvalidEncoding);
+            }
+            tag = (tag << 7) | u32::from(b & 0x7f);
+            if b & 0x80 == 0 {
+                break;
+            }
+        }
+        self.check_canonical(tag >= 63)?;
+        Ok((class, Tag(tag)))
+    }
+
+    /// Decode octets, prefixed with a length determinant
+    pub fn read_octets_with_length(&mut self) -> OerResult<&'a [u8]> {
+        let len = self.read_length()?;
+        self.read_bytes(len)
+    }
+
+    /// Decode an open type field (X.696: 30), and return the encoding of the value
+    #[inline]
+    pub fn read_open_type(&mut self) -> OerResult<&'a [u8]> {
+        self.read_octets_with_length()
+    }
+
+    /// Decode the value of an `ENUMERATED` type (X.696: 11)
+    pub fn read_enumerated(&mut self) -> OerResult<i128> {
+        let b = self.read_u8()?;
+        if b & 0x80 == 0 {
+            return Ok(i128::from(b));
+        }
+        let n = usize::from(b & 0x7f);
+        if n == 0 {
+            return Err(OerError::InvalidEncoding);
+        }
+        let value = self.read_signed(n)?;
+        // long form is used only for values outside 0..=127, with the minimum number of octets
+        self.check_canonical(!(0..128).contains(&value) && n == min_signed_octets(value))?;
+        Ok(value)
+    }
+
+    /// Decode a preamble of `nbits` bits, padded to an octet boundary (X.696: 16.2)
+    pub fn read_preamble(&mut self, nbits: usize) -> OerResult<Vec<bool>> {
+        let bytes = self.read_bytes((nbits + 7) / 8)?;
+        let bits: Vec<bool> = (0..nbits)
+            .map(|i| bytes[i / 8] & (0x80 >> (i % 8)) != 0)
+            .collect();
+        if nbits % 8 != 0 {
+            let padding = bytes[bytes.len() - 1] & (0xff >> (nbits % 8));
+            self.check_canonical(padding == 0)?;
+        }
+        Ok(bits)
+    }
+
+    /// Skip the extension additions of a `SEQUENCE` value (X.696: 16.4)
+    ///
+    /// This function must be called after decoding root components, if the extension bit was set.
+    /// Returns the number of extension additions present.
+    pub fn skip_sequence_extensions(&mut self) -> OerResult<usize> {
+        // extension presence bit-map, as a BIT STRING
+        let bitmap = self.read_octets_with_length()?;
+        let (&unused, bits) = bitmap.split_first().ok_or(OerError::InvalidEncoding)?;
+        if unused > 7 || (bits.is_empty() && unused != 0) {
+            return Err(OerError::InvalidEncoding);
+        }
+        let count = bits.iter().map(|b| b.count_ones() as usize).sum();
+        for _ in 0..count {
+            self.read_open_type()?;
+        }
+        Ok(count)
+    }
+}
+
+fn bytes_to_u128(bytes: &[u8]) -> OerResult<u128> {
+    // ignore leading zeroes
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    let bytes = &bytes[start..];
+    if bytes.len() > 16 {
+        return Err(OerError::IntegerTooLarge);
+    }
+    Ok(bytes.iter().fold(0, |acc, &b| (acc << 8) | u128::from(b)))
+}
+
+fn bytes_to_i128(bytes: &[u8]) -> OerResult<i128> {
+    let Some(&first) = bytes.first() else {
+        return Err(OerError::InvalidEncoding);
+    };
+    let fill = if first & 0x80 != 0 { 0xff } else { 0x00 };
+    // remove redundant leading octets
+    let start = bytes
+        .windows(2)
+        .position(|w| !(w[0] == fill && (w[1] & 0x80) == (fill & 0x80)))
+        .unwrap_or(bytes.len() - 1);
+    let bytes = &bytes[start..];
+    if bytes.len() > 16 {
+        return Err(OerError::IntegerTooLarge);
+    }
+    let mut buf = [fill; 16];
+    buf[16 - bytes.len()..].copy_from_slice(bytes);
+    Ok(i128::from_be_bytes(buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use hex_literal::hex;
+
+    use crate::oer::OerReader;
+    use crate::{Class, OerError, Tag};
+
+    #[test]
+    fn oer_reader_length() {
+        let mut r = OerReader::coer(&hex!("05 7f 81 80 82 12 34"));
+        assert_eq!(r.read_length(), Ok(5));
+        assert_eq!(r.read_length(), Ok(127));
+        assert_eq!(r.read_length(), Ok(128));
+        assert_eq!(r.read_length(), Ok(0x1234));
+        assert!(r.remaining_bytes().is_empty());
+
+        // long form for a short length
+        let input = &hex!("81 05");
+        assert_eq!(OerReader::oer(input).read_length(), Ok(5));
+        assert_eq!(
+            OerReader::coer(input).read_length(),
+            Err(OerError::NonCanonical)
+        );
+        // long form with leading zeroes
+        let input = &hex!("82 00 80");
+        assert_eq!(OerReader::oer(input).read_length(), Ok(128));
+        assert_eq!(
+            OerReader::coer(input).read_length(),
+            Err(OerError::NonCanonical)
+        );
+    }
+
+    #[test]
+    fn oer_reader_integers() {
+        let mut r = OerReader::coer(&hex!("12 34 fe 01 00 02 00 80 02 ff 7f"));
+        assert_eq!(r.read_unsigned(2), Ok(0x1234));
+        assert_eq!(r.read_signed(1), Ok(-2));
+        assert_eq!(r.read_unsigned_with_length(), Ok(0));
+        assert_eq!(r.read_signed_with_length(), Ok(128));
+        assert_eq!(r.read_signed_with_length(), Ok(-129));
+
+        let input = &hex!("02 00 01");
+        assert_eq!(OerReader::oer(input).read_signed_with_length(), Ok(1));
+        assert_eq!(
+            OerReader::coer(input).read_signed_with_length(),
+            Err(OerError::NonCanonical)
+        );
+
+        let mut r = OerReader::oer(&hex!("04 00 00"));
+        assert!(matches!(
+            r.read_unsigned_with_length(),
+            Err(OerError::Incomplete(_))
+        ));
+    }
+
+    #[test]
+    fn oer_reader_misc() {
+        let mut r = OerReader::coer(&hex!("82 7f 81 48 05 82 00 c8 81 ff a0"));
+        assert_eq!(r.read_tag(), Ok((Class::ContextSpecific, Tag(2))));
+        assert_eq!(r.read_tag(), Ok((Class::Application, Tag(200))));
+        assert_eq!(r.read_enumerated(), Ok(5));
+        assert_eq!(r.read_enumerated(), Ok(200));
+        assert_eq!(r.read_enumerated(), Ok(-1));
+        assert_eq!(r.read_preamble(3), Ok(vec![true, false, true]));
+
+        // padding bits must be zero in COER
+        assert_eq!(
+            OerReader::coer(&hex!("a1")).read_preamble(3),
+            Err(OerError::NonCanonical)
+        );
+
+        // extensions: bit-map with 2 extensions present, then 2 open types
+        let mut r = OerReader::oer(&hex!("02 06 c0 01 ff 02 12 34 aa"));
+        assert_eq!(r.skip_sequence_extensions(), Ok(2));
+        assert_eq!(r.remaining_bytes(), &hex!("aa"));
+    }
+}
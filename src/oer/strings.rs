@@ -0,0 +1,244 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{
+    BmpString, Constraints, GeneralString, GraphicString, Ia5String, NumericString, OerError,
+    OerResult, PrintableString, TeletexString, TestValidCharset, UniversalString, Utf8String,
+    VideotexString, VisibleString,
+};
+
+use super::{OerParser, OerReader, OerWriter, ToOer};
+
+/// Encode the octets of a known-multiplier character string (X.696: 27.2)
+///
+/// The length determinant is omitted if the size constraint allows only one value. `nchars` is
+/// the number of characters, used to check the size constraint.
+fn oer_encode_known_multiplier(
+    writer: &mut OerWriter,
+    bytes: &[u8],
+    nchars: usize,
+    constraints: &Constraints,
+) -> OerResult<()> {
+    let size = &constraints.size;
+    if size.extensible {
+        writer.write_octets_with_length(bytes);
+        return Ok(());
+    }
+    if !size.contains(nchars) {
+        return Err(OerError::ConstraintViolation);
+    }
+    if size.fixed().is_none() {
+        writer.write_length(bytes.len());
+    }
+    writer.write_bytes(bytes);
+    Ok(())
+}
+
+/// Decode the octets of a known-multiplier character string (X.696: 27.2)
+fn oer_decode_known_multiplier<'a>(
+    reader: &mut OerReader<'a>,
+    octets_per_char: usize,
+    constraints: &Constraints,
+) -> OerResult<&'a [u8]> {
+    let size = &constraints.size;
+    let bytes = match size.fixed() {
+        Some(n) if !size.extensible => {
+            let len = n
+                .checked_mul(octets_per_char)
+                .ok_or(OerError::InvalidEncoding)?;
+            reader.read_bytes(len)?
+        }
+        _ => reader.read_octets_with_length()?,
+    };
+    if bytes.len() % octets_per_char != 0 {
+        return Err(OerError::InvalidEncoding);
+    }
+    if !size.extensible && !size.contains(bytes.len() / octets_per_char) {
+        return Err(OerError::ConstraintViolation);
+    }
+    Ok(bytes)
+}
+
+/// Strings using one octet per character
+macro_rules! impl_oer_single_octet_string {
+    ($($name:ident)*) => {
+        $(
+            impl ToOer for $name<'_> {
+                fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+                    let bytes = self.data.as_bytes();
+                    <$name>::test_valid_charset(bytes).map_err(|_| OerError::StringInvalidCharset)?;
+                    oer_encode_known_multiplier(writer, bytes, bytes.len(), constraints)
+                }
+            }
+
+            impl<'a> OerParser<'a> for $name<'a> {
+                fn oer_decode(reader: &mut OerReader<'a>, constraints: &Constraints) -> OerResult<Self> {
+                    let bytes = oer_decode_known_multiplier(reader, 1, constraints)?;
+                    <$name>::test_valid_charset(bytes).map_err(|_| OerError::StringInvalidCharset)?;
+                    let s = core::str::from_utf8(bytes).map_err(|_| OerError::StringInvalidCharset)?;
+                    Ok($name::new(s))
+                }
+            }
+        )*
+    };
+}
+
+impl_oer_single_octet_string!(Ia5String PrintableString VisibleString NumericString);
+
+impl ToOer for BmpString<'_> {
+    fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+        let values = self.data.encode_utf16().collect::<Vec<_>>();
+        // characters outside the BMP would be encoded as surrogate pairs
+        if values.len() != self.data.chars().count() {
+            return Err(OerError::StringInvalidCharset);
+        }
+        let bytes = values
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect::<Vec<_>>();
+        oer_encode_known_multiplier(writer, &bytes, values.len(), constraints)
+    }
+}
+
+impl OerParser<'_> for BmpString<'_> {
+    fn oer_decode(reader: &mut OerReader, constraints: &Constraints) -> OerResult<Self> {
+        let bytes = oer_decode_known_multiplier(reader, 2, constraints)?;
+        let s = bytes
+            .chunks_exact(2)
+            .map(|c| char::from_u32(u32::from(u16::from_be_bytes([c[0], c[1]]))))
+            .collect::<Option<String>>()
+            .ok_or(OerError::StringInvalidCharset)?;
+        Ok(BmpString::from(s))
+    }
+}
+
+impl ToOer for UniversalString<'_> {
+    fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+        let bytes = self
+            .data
+            .chars()
+            .flat_map(|c| (c as u32).to_be_bytes())
+            .collect::<Vec<_>>();
+        oer_encode_known_multiplier(writer, &bytes, bytes.len() / 4, constraints)
+    }
+}
+
+impl OerParser<'_> for UniversalString<'_> {
+    fn oer_decode(reader: &mut OerReader, constraints: &Constraints) -> OerResult<Self> {
+        let bytes = oer_decode_known_multiplier(reader, 4, constraints)?;
+        let s = bytes
+            .chunks_exact(4)
+            .map(|c| char::from_u32(u32::from_be_bytes([c[0], c[1], c[2], c[3]])))
+            .collect::<Option<String>>()
+            .ok_or(OerError::StringInvalidCharset)?;
+        Ok(UniversalString::from(s))
+    }
+}
+
+/// Decode the octets of a character string which is not a known-multiplier type (X.696: 27.3)
+fn oer_decode_utf8<'a>(reader: &mut OerReader<'a>) -> OerResult<&'a str> {
+    let bytes = reader.read_octets_with_length()?;
+    core::str::from_utf8(bytes).map_err(|_| OerError::StringInvalidCharset)
+}
+
+/// Strings which are not known-multiplier types are encoded as octets prefixed with a length
+/// determinant. Size constraints are not OER-visible for these types.
+impl ToOer for str {
+    fn oer_encode(&self, writer: &mut OerWriter, _constraints: &Constraints) -> OerResult<()> {
+        writer.write_octets_with_length(self.as_bytes());
+        Ok(())
+    }
+}
+
+impl ToOer for String {
+    fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+        self.as_str().oer_encode(writer, constraints)
+    }
+}
+
+impl OerParser<'_> for String {
+    fn oer_decode(reader: &mut OerReader, _constraints: &Constraints) -> OerResult<Self> {
+        oer_decode_utf8(reader).map(String::from)
+    }
+}
+
+macro_rules! impl_oer_octet_string_type {
+    ($($name:ident)*) => {
+        $(
+            impl ToOer for $name<'_> {
+                fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+                    self.data.oer_encode(writer, constraints)
+                }
+            }
+
+            impl<'a> OerParser<'a> for $name<'a> {
+                fn oer_decode(reader: &mut OerReader<'a>, _constraints: &Constraints) -> OerResult<Self> {
+                    oer_decode_utf8(reader).map(|s| $name { data: Cow::Borrowed(s) })
+                }
+            }
+        )*
+    };
+}
+
+impl_oer_octet_string_type!(Utf8String GeneralString GraphicString TeletexString VideotexString);
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use hex_literal::hex;
+
+    use crate::oer::{OerParser, OerReader, OerWriter, ToOer};
+    use crate::{
+        BmpString, Constraints, Ia5String, NumericString, PrintableString, UniversalString,
+        Utf8String,
+    };
+
+    #[test]
+    fn oer_known_multiplier_strings() {
+        // unconstrained IA5String
+        let s = Ia5String::new("abc");
+        let v = s.to_oer_vec().unwrap();
+        assert_eq!(&v, &hex!("03 61 62 63"));
+        assert_eq!(Ia5String::parse_coer(&v).unwrap(), s);
+
+        // fixed size: no length determinant
+        let c = Constraints::NONE.with_size(2, Some(2), false);
+        let mut w = OerWriter::new();
+        BmpString::new("ab").oer_encode(&mut w, &c).unwrap();
+        let v = w.into_bytes();
+        assert_eq!(&v, &hex!("00 61 00 62"));
+        let mut r = OerReader::coer(&v);
+        assert_eq!(BmpString::oer_decode(&mut r, &c).unwrap().as_ref(), "ab");
+
+        // length is a number of octets
+        let s = UniversalString::new("é");
+        let v = s.to_oer_vec().unwrap();
+        assert_eq!(&v, &hex!("04 00 00 00 e9"));
+        assert_eq!(UniversalString::parse_oer(&v).unwrap(), s);
+        UniversalString::parse_oer(&hex!("03 00 00 00")).unwrap_err();
+
+        // invalid characters
+        PrintableString::new("a@b").to_oer_vec().unwrap_err();
+        BmpString::new("\u{1f600}").to_oer_vec().unwrap_err();
+        NumericString::parse_oer(&hex!("01 41")).unwrap_err();
+
+        // size constraint violation
+        let mut w = OerWriter::new();
+        Ia5String::new("abc")
+            .oer_encode(&mut w, &Constraints::NONE.with_size(1, Some(2), false))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn oer_octet_strings_types() {
+        let s = Utf8String::from("é");
+        let v = s.to_oer_vec().unwrap();
+        assert_eq!(&v, &hex!("02 c3 a9"));
+        assert_eq!(Utf8String::parse_coer(&v).unwrap(), s);
+        assert_eq!(String::parse_oer(&v).unwrap(), "é");
+
+        Utf8String::parse_oer(&hex!("01 ff")).unwrap_err();
+    }
+}
@@ -0,0 +1,81 @@
+use alloc::vec::Vec;
+
+use crate::{Constraints, OerResult};
+
+use super::{OerReader, OerWriter};
+
+/// Common trait for OER encoding functions
+///
+/// The encoding of a value depends on its OER-visible constraints, which are provided by the
+/// caller (usually, the code for the enclosing type). Functions encoding a value at top-level
+/// (like [`to_oer_vec`](ToOer::to_oer_vec)) use no constraints.
+///
+/// Encodings are always canonical, and can be decoded using both BASIC-OER and COER.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::oer::{OerWriter, ToOer};
+/// use asn1_rs::Constraints;
+///
+/// // OCTET STRING (SIZE(2))
+/// let c = Constraints::NONE.with_size(2, Some(2), false);
+///
+/// let mut writer = OerWriter::new();
+/// [0x12u8, 0x34][..].oer_encode(&mut writer, &c).expect("serialization failed");
+/// assert_eq!(&writer.into_bytes(), &[0x12, 0x34]);
+/// ```
+pub trait ToOer {
+    /// Encode the value to the writer `writer`, using the OER-visible constraints `constraints`
+    fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()>;
+
+    /// Encode the value to a newly allocated `Vec<u8>`
+    fn to_oer_vec(&self) -> OerResult<Vec<u8>> {
+        let mut writer = OerWriter::new();
+        self.oer_encode(&mut writer, &Constraints::NONE)?;
+        Ok(writer.into_bytes())
+    }
+}
+
+impl<T> ToOer for &'_ T
+where
+    T: ToOer + ?Sized,
+{
+    fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+        (*self).oer_encode(writer, constraints)
+    }
+}
+
+/// Base trait for OER decoding functions
+///
+/// See [`ToOer`] for the use of constraints.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::oer::{OerParser, OerReader};
+/// use asn1_rs::{Constraints, OctetString};
+///
+/// // OCTET STRING (SIZE(2))
+/// let c = Constraints::NONE.with_size(2, Some(2), false);
+///
+/// let mut reader = OerReader::coer(&[0x12, 0x34]);
+/// let s = OctetString::oer_decode(&mut reader, &c).expect("parsing failed");
+/// assert_eq!(s.as_ref(), &[0x12, 0x34]);
+/// ```
+pub trait OerParser<'a>: Sized {
+    /// Decode a value from the reader `reader`, using the OER-visible constraints `constraints`
+    fn oer_decode(reader: &mut OerReader<'a>, constraints: &Constraints) -> OerResult<Self>;
+
+    /// Decode a value from a complete encoding, using the basic variant (BASIC-OER)
+    fn parse_oer(bytes: &'a [u8]) -> OerResult<Self> {
+        let mut reader = OerReader::oer(bytes);
+        Self::oer_decode(&mut reader, &Constraints::NONE)
+    }
+
+    /// Decode a value from a complete encoding, using the canonical variant (COER)
+    fn parse_coer(bytes: &'a [u8]) -> OerResult<Self> {
+        let mut reader = OerReader::coer(bytes);
+        Self::oer_decode(&mut reader, &Constraints::NONE)
+    }
+}
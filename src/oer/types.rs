@@ -0,0 +1,372 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::{
+    BitString, Boolean, Constraints, Null, OctetString, OerError, OerResult, Oid, SequenceOf,
+};
+
+use super::{OerParser, OerReader, OerWriter, ToOer};
+
+//---- BOOLEAN (X.696: 9)
+
+impl ToOer for bool {
+    fn oer_encode(&self, writer: &mut OerWriter, _constraints: &Constraints) -> OerResult<()> {
+        writer.write_u8(if *self { 0xff } else { 0x00 });
+        Ok(())
+    }
+}
+
+impl OerParser<'_> for bool {
+    fn oer_decode(reader: &mut OerReader, _constraints: &Constraints) -> OerResult<Self> {
+        let b = reader.read_u8()?;
+        reader.check_canonical(b == 0x00 || b == 0xff)?;
+        Ok(b != 0)
+    }
+}
+
+impl ToOer for Boolean {
+    fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+        self.bool().oer_encode(writer, constraints)
+    }
+}
+
+impl OerParser<'_> for Boolean {
+    fn oer_decode(reader: &mut OerReader, constraints: &Constraints) -> OerResult<Self> {
+        let b = bool::oer_decode(reader, constraints)?;
+        Ok(if b { Boolean::TRUE } else { Boolean::FALSE })
+    }
+}
+
+//---- NULL (X.696: 24)
+
+impl ToOer for Null {
+    fn oer_encode(&self, _writer: &mut OerWriter, _constraints: &Constraints) -> OerResult<()> {
+        Ok(())
+    }
+}
+
+impl OerParser<'_> for Null {
+    fn oer_decode(_reader: &mut OerReader, _constraints: &Constraints) -> OerResult<Self> {
+        Ok(Null::new())
+    }
+}
+
+impl ToOer for () {
+    fn oer_encode(&self, _writer: &mut OerWriter, _constraints: &Constraints) -> OerResult<()> {
+        Ok(())
+    }
+}
+
+impl OerParser<'_> for () {
+    fn oer_decode(_reader: &mut OerReader, _constraints: &Constraints) -> OerResult<Self> {
+        Ok(())
+    }
+}
+
+//---- BIT STRING (X.696: 12)
+
+impl ToOer for BitString {
+    fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+        let size = &constraints.size;
+        let nbits = self.len();
+        if !size.extensible && !size.contains(nbits) {
+            return Err(OerError::ConstraintViolation);
+        }
+        // unused bits must be zero
+        let mut bytes = self.as_raw_slice()[..(nbits + 7) / 8].to_vec();
+        let unused_bits = (8 * bytes.len() - nbits) as u8;
+        if let Some(last) = bytes.last_mut() {
+            *last &= 0xff << unused_bits;
+        }
+        match size.fixed() {
+            Some(_) if !size.extensible => writer.write_bytes(&bytes),
+            _ => {
+                writer.write_length(bytes.len() + 1);
+                writer.write_u8(unused_bits);
+                writer.write_bytes(&bytes);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl OerParser<'_> for BitString {
+    fn oer_decode(reader: &mut OerReader, constraints: &Constraints) -> OerResult<Self> {
+        let size = &constraints.size;
+        let (unused_bits, bytes) = match size.fixed() {
+            Some(n) if !size.extensible => {
+                let bytes = reader.read_bytes((n + 7) / 8)?;
+                ((8 * bytes.len() - n) as u8, bytes)
+            }
+            _ => {
+                let data = reader.read_octets_with_length()?;
+                let (&unused_bits, bytes) = data.split_first().ok_or(OerError::InvalidEncoding)?;
+                if unused_bits > 7 || (bytes.is_empty() && unused_bits != 0) {
+                    return Err(OerError::InvalidEncoding);
+                }
+                (unused_bits, bytes)
+            }
+        };
+        let Some(&last) = bytes.last() else {
+            return Ok(BitString::default());
+        };
+        reader.check_canonical(last & !(0xff << unused_bits) == 0)?;
+        let b = BitString::new(unused_bits, bytes);
+        if !size.extensible && !size.contains(b.len()) {
+            return Err(OerError::ConstraintViolation);
+        }
+        Ok(b)
+    }
+}
+
+//---- OCTET STRING (X.696: 13)
+
+impl ToOer for [u8] {
+    fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+        let size = &constraints.size;
+        if size.extensible {
+            writer.write_octets_with_length(self);
+            return Ok(());
+        }
+        if !size.contains(self.len()) {
+            return Err(OerError::ConstraintViolation);
+        }
+        match size.fixed() {
+            Some(_) => writer.write_bytes(self),
+            None => writer.write_octets_with_length(self),
+        }
+        Ok(())
+    }
+}
+
+impl ToOer for OctetString<'_> {
+    fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+        self.as_ref().oer_encode(writer, constraints)
+    }
+}
+
+impl<'a> OerParser<'a> for OctetString<'a> {
+    fn oer_decode(reader: &mut OerReader<'a>, constraints: &Constraints) -> OerResult<Self> {
+        let size = &constraints.size;
+        let data = match size.fixed() {
+            Some(n) if !size.extensible => reader.read_bytes(n)?,
+            _ => reader.read_octets_with_length()?,
+        };
+        if !size.extensible && !size.contains(data.len()) {
+            return Err(OerError::ConstraintViolation);
+        }
+        Ok(OctetString::new(data))
+    }
+}
+
+//---- OBJECT IDENTIFIER (X.696: 25)
+
+impl ToOer for Oid<'_> {
+    fn oer_encode(&self, writer: &mut OerWriter, _constraints: &Constraints) -> OerResult<()> {
+        writer.write_octets_with_length(self.as_bytes());
+        Ok(())
+    }
+}
+
+impl<'a> OerParser<'a> for Oid<'a> {
+    fn oer_decode(reader: &mut OerReader<'a>, _constraints: &Constraints) -> OerResult<Self> {
+        let data = reader.read_octets_with_length()?;
+        // the last subidentifier must be terminated
+        match data.last() {
+            Some(b) if b & 0x80 == 0 => Ok(Oid::new(Cow::Borrowed(data))),
+            _ => Err(OerError::InvalidEncoding),
+        }
+    }
+}
+
+//---- OPTIONAL
+
+/// The presence of optional values is encoded by the enclosing type (in the preamble of a
+/// `SEQUENCE`), so encoding `None` writes nothing.
+impl<T> ToOer for Option<T>
+where
+    T: ToOer,
+{
+    fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+        match self {
+            Some(t) => t.oer_encode(writer, constraints),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The presence of optional values is encoded by the enclosing type, so this function always
+/// decodes a value and returns `Some`. The caller must check presence before calling it.
+impl<'a, T> OerParser<'a> for Option<T>
+where
+    T: OerParser<'a>,
+{
+    fn oer_decode(reader: &mut OerReader<'a>, constraints: &Constraints) -> OerResult<Self> {
+        T::oer_decode(reader, constraints).map(Some)
+    }
+}
+
+//---- SEQUENCE OF (X.696: 21)
+
+/// The number of items is always encoded (size constraints are not OER-visible for `SEQUENCE OF`),
+/// but it is checked against the size constraint. Items are encoded with no constraints.
+impl<T> ToOer for Vec<T>
+where
+    T: ToOer,
+{
+    fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+        let size = &constraints.size;
+        if !size.extensible && !size.contains(self.len()) {
+            return Err(OerError::ConstraintViolation);
+        }
+        writer.write_quantity(self.len());
+        for item in self {
+            item.oer_encode(writer, &Constraints::NONE)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T> OerParser<'a> for Vec<T>
+where
+    T: OerParser<'a>,
+{
+    fn oer_decode(reader: &mut OerReader<'a>, constraints: &Constraints) -> OerResult<Self> {
+        let size = &constraints.size;
+        let count = reader.read_quantity()?;
+        if !size.extensible && !size.contains(count) {
+            return Err(OerError::ConstraintViolation);
+        }
+        // do not trust count for allocation: it is not bounded by the input length
+        let mut items = Vec::with_capacity(count.min(reader.remaining_bytes().len()));
+        for _ in 0..count {
+            items.push(T::oer_decode(reader, &Constraints::NONE)?);
+        }
+        Ok(items)
+    }
+}
+
+impl<T> ToOer for SequenceOf<T>
+where
+    T: ToOer,
+{
+    fn oer_encode(&self, writer: &mut OerWriter, constraints: &Constraints) -> OerResult<()> {
+        self.items.oer_encode(writer, constraints)
+    }
+}
+
+impl<'a, T> OerParser<'a> for SequenceOf<T>
+where
+    T: OerParser<'a>,
+{
+    fn oer_decode(reader: &mut OerReader<'a>, constraints: &Constraints) -> OerResult<Self> {
+        Vec::oer_decode(reader, constraints).map(SequenceOf::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use hex_literal::hex;
+
+    use crate::oer::{OerParser, OerReader, OerWriter, ToOer};
+    use crate::{oid, BitString, Constraints, OctetString, OerError, Oid, SequenceOf};
+
+    #[test]
+    fn oer_boolean() {
+        assert_eq!(&true.to_oer_vec().unwrap(), &hex!("ff"));
+        assert_eq!(&false.to_oer_vec().unwrap(), &hex!("00"));
+        assert!(bool::parse_coer(&hex!("ff")).unwrap());
+        assert!(bool::parse_oer(&hex!("01")).unwrap());
+        assert_eq!(bool::parse_coer(&hex!("01")), Err(OerError::NonCanonical));
+    }
+
+    #[test]
+    fn oer_octetstring() {
+        // unconstrained
+        let v = hex!("01 02 03")[..].to_oer_vec().unwrap();
+        assert_eq!(&v, &hex!("03 01 02 03"));
+        let s = OctetString::parse_coer(&v).unwrap();
+        assert_eq!(s.as_ref(), &hex!("01 02 03"));
+
+        // fixed size
+        let c = Constraints::NONE.with_size(3, Some(3), false);
+        let mut w = OerWriter::new();
+        hex!("01 02 03")[..].oer_encode(&mut w, &c).unwrap();
+        let v = w.into_bytes();
+        assert_eq!(&v, &hex!("01 02 03"));
+        let mut r = OerReader::coer(&v);
+        assert_eq!(
+            OctetString::oer_decode(&mut r, &c).unwrap().as_ref(),
+            &hex!("01 02 03")
+        );
+
+        // size constraint violation
+        let c = Constraints::NONE.with_size(1, Some(2), false);
+        let mut w = OerWriter::new();
+        hex!("01 02 03")[..].oer_encode(&mut w, &c).unwrap_err();
+        let mut r = OerReader::oer(&hex!("03 01 02 03"));
+        OctetString::oer_decode(&mut r, &c).unwrap_err();
+    }
+
+    #[test]
+    fn oer_bitstring() {
+        let b = BitString::new(4, &hex!("a5 f0"));
+        let v = b.to_oer_vec().unwrap();
+        assert_eq!(&v, &hex!("03 04 a5 f0"));
+        assert_eq!(BitString::parse_coer(&v).unwrap(), b);
+
+        // unused bits must be zero in COER
+        let input = &hex!("03 04 a5 f1");
+        assert_eq!(BitString::parse_oer(input).unwrap(), b);
+        assert_eq!(BitString::parse_coer(input), Err(OerError::NonCanonical));
+
+        // fixed size: no length and no unused bits octet
+        let c = Constraints::NONE.with_size(4, Some(4), false);
+        let b = BitString::new(4, &hex!("a0"));
+        let mut w = OerWriter::new();
+        b.oer_encode(&mut w, &c).unwrap();
+        let v = w.into_bytes();
+        assert_eq!(&v, &hex!("a0"));
+        let mut r = OerReader::coer(&v);
+        assert_eq!(BitString::oer_decode(&mut r, &c).unwrap(), b);
+
+        let v = BitString::default().to_oer_vec().unwrap();
+        assert_eq!(&v, &hex!("01 00"));
+        assert!(BitString::parse_coer(&v).unwrap().is_empty());
+    }
+
+    #[test]
+    fn oer_oid_and_options() {
+        let oid = oid!(1.2.840 .113549);
+        let v = oid.to_oer_vec().unwrap();
+        assert_eq!(&v, &hex!("06 2a 86 48 86 f7 0d"));
+        assert_eq!(Oid::parse_coer(&v).unwrap(), oid);
+        Oid::parse_oer(&hex!("01 86")).unwrap_err();
+
+        let v = Some(true).to_oer_vec().unwrap();
+        assert_eq!(&v, &hex!("ff"));
+        assert_eq!(Option::<bool>::parse_coer(&v).unwrap(), Some(true));
+    }
+
+    #[test]
+    fn oer_sequence_of() {
+        let c = Constraints::NONE
+            .with_size(1, Some(4), false)
+            .with_value(Some(0), Some(7), false);
+        let items = SequenceOf::new(vec![1u8, 2, 3]);
+        let mut w = OerWriter::new();
+        items.oer_encode(&mut w, &c).unwrap();
+        let v = w.into_bytes();
+        // quantity is always encoded, and items are not constrained
+        assert_eq!(&v, &hex!("01 03 01 01 01 02 01 03"));
+        let mut r = OerReader::coer(&v);
+        let res = SequenceOf::<u8>::oer_decode(&mut r, &c).unwrap();
+        assert_eq!(res, items);
+
+        let mut w = OerWriter::new();
+        Vec::<u8>::new().oer_encode(&mut w, &c).unwrap_err();
+    }
+}
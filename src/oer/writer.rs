@@ -0,0 +1,211 @@
+use alloc::vec::Vec;
+
+use crate::{Class, Constraints, OerError, OerResult, Tag};
+
+use super::{min_signed_octets, min_unsigned_octets, ToOer};
+
+/// Writer for OER encodings
+///
+/// Procedures defined in [[X.696]] section 8 are provided as methods, and are used to implement
+/// [`ToOer`].
+///
+/// The writer always produces canonical encodings, so its output is valid for both BASIC-OER and
+/// CANONICAL-OER (COER).
+///
+/// [X.696]: http://www.itu.int/rec/T-REC-X.696/en "Recommendation ITU-T X.696"
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OerWriter {
+    data: Vec<u8>,
+}
+
+impl OerWriter {
+    /// Build a new writer
+    pub const fn new() -> Self {
+        OerWriter { data: Vec::new() }
+    }
+
+    /// Returns the number of octets written
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if no octet was written
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the complete encoding
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Write a single octet
+    #[inline]
+    pub fn write_u8(&mut self, b: u8) {
+        self.data.push(b);
+    }
+
+    /// Write octets
+    #[inline]
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    /// Encode a length determinant (X.696: 8.6)
+    pub fn write_length(&mut self, len: usize) {
+        if len < 128 {
+            self.write_u8(len as u8);
+        } else {
+            let bytes = (len as u128).to_be_bytes();
+            let n = min_unsigned_octets(len as u128);
+            self.write_u8(0x80 | n as u8);
+            self.write_bytes(&bytes[bytes.len() - n..]);
+        }
+    }
+
+    /// Encode `value` as an unsigned integer using exactly `noctets` octets (X.696: 10.3)
+    pub fn write_unsigned(&mut self, value: u128, noctets: usize) -> OerResult<()> {
+        if noctets < 16 && value >> (8 * noctets) != 0 {
+            return Err(OerError::IntegerTooLarge);
+        }
+        let bytes = value.to_be_bytes();
+        self.write_bytes(&bytes[bytes.len() - noctets..]);
+        Ok(())
+    }
+
+    /// Encode `value` as a 2's-complement integer using exactly `noctets` octets (X.696: 10.4)
+    pub fn write_signed(&mut self, value: i128, noctets: usize) -> OerResult<()> {
+        if min_signed_octets(value) > noctets {
+            return Err(OerError::IntegerTooLarge);
+        }
+        let bytes = value.to_be_bytes();
+        self.write_bytes(&bytes[bytes.len() - noctets..]);
+        Ok(())
+    }
+
+    /// Encode `value` as an unsigned integer, prefixed with a length determinant (X.696: 10.6)
+    pub fn write_unsigned_with_length(&mut self, value: u128) {
+        let bytes = value.to_be_bytes();
+        let n = min_unsigned_octets(value);
+        self.write_length(n);
+        self.write_bytes(&bytes[bytes.len() - n..]);
+    }
+
+    /// Encode `value` as a 2's-complement integer, prefixed with a length determinant (X.696: 10.7)
+    pub fn write_signed_with_length(&mut self, value: i128) {
+        let bytes = value.to_be_bytes();
+        let n = min_signed_octets(value);
+        self.write_length(n);
+        self.write_bytes(&bytes[bytes.len() - n..]);
+    }
+
+    /// Encode the number of items of a `SEQUENCE OF` value (X.696: 21.1)
+    pub fn write_quantity(&mut self, count: usize) {
+        self.write_unsigned_with_length(count as u128);
+    }
+
+    /// Encode a tag (class and number), as used for `CHOICE` values (X.696: 8.7)
+    pub fn write_tag(&mut self, class: Class, tag: Tag) {
+        let class = (class as u8) << 6;
+        if tag.0 < 63 {
+            self.write_u8(class | tag.0 as u8);
+            return;
+        }
+        self.write_u8(class | 0x3f);
+        // tag number, in base 128 with continuation bit
+        let n = (32 - tag.0.leading_zeros() as usize + 6) / 7;
+        for i in (0..n).rev() {
+            let b = ((tag.0 >> (7 * i)) & 0x7f) as u8;
+            self.write_u8(if i > 0 { b | 0x80 } else { b });
+        }
+    }
+
+    /// Encode octets, prefixed with a length determinant
+    pub fn write_octets_with_length(&mut self, bytes: &[u8]) {
+        self.write_length(bytes.len());
+        self.write_bytes(bytes);
+    }
+
+    /// Encode `value` as an open type (X.696: 30)
+    pub fn write_open_type<T>(&mut self, value: &T, constraints: &Constraints) -> OerResult<()>
+    where
+        T: ToOer + ?Sized,
+    {
+        let mut writer = OerWriter::new();
+        value.oer_encode(&mut writer, constraints)?;
+        self.write_octets_with_length(&writer.into_bytes());
+        Ok(())
+    }
+
+    /// Encode the value of an `ENUMERATED` type (X.696: 11)
+    pub fn write_enumerated(&mut self, value: i128) {
+        if (0..128).contains(&value) {
+            self.write_u8(value as u8);
+        } else {
+            let bytes = value.to_be_bytes();
+            let n = min_signed_octets(value);
+            self.write_u8(0x80 | n as u8);
+            self.write_bytes(&bytes[bytes.len() - n..]);
+        }
+    }
+
+    /// Encode a preamble (bit-map of presence bits), padded to an octet boundary (X.696: 16.2)
+    ///
+    /// If `bits` is empty, nothing is written.
+    pub fn write_preamble(&mut self, bits: &[bool]) {
+        for chunk in bits.chunks(8) {
+            let b = chunk.iter().enumerate().fold(
+                0u8,
+                |acc, (i, &bit)| if bit { acc | (0x80 >> i) } else { acc },
+            );
+            self.write_u8(b);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::oer::OerWriter;
+    use crate::{Class, Tag};
+
+    #[test]
+    fn oer_writer_length() {
+        let mut w = OerWriter::new();
+        w.write_length(5);
+        w.write_length(127);
+        w.write_length(128);
+        w.write_length(0x1234);
+        assert_eq!(&w.into_bytes(), &hex!("05 7f 81 80 82 12 34"));
+    }
+
+    #[test]
+    fn oer_writer_integers() {
+        let mut w = OerWriter::new();
+        w.write_unsigned(0x1234, 2).unwrap();
+        w.write_signed(-2, 1).unwrap();
+        w.write_unsigned_with_length(0);
+        w.write_signed_with_length(128);
+        w.write_signed_with_length(-129);
+        assert_eq!(&w.into_bytes(), &hex!("12 34 fe 01 00 02 00 80 02 ff 7f"));
+
+        let mut w = OerWriter::new();
+        w.write_unsigned(256, 1).unwrap_err();
+        w.write_signed(128, 1).unwrap_err();
+    }
+
+    #[test]
+    fn oer_writer_misc() {
+        let mut w = OerWriter::new();
+        w.write_tag(Class::ContextSpecific, Tag(2));
+        w.write_tag(Class::Application, Tag(200));
+        w.write_enumerated(5);
+        w.write_enumerated(200);
+        w.write_enumerated(-1);
+        w.write_preamble(&[true, false, true]);
+        assert_eq!(&w.into_bytes(), &hex!("82 7f 81 48 05 82 00 c8 81 ff a0"));
+    }
+}
@@ -0,0 +1,214 @@
+use core::convert::TryFrom;
+
+use crate::{Constraints, Enumerated, Integer, PerError, PerResult};
+
+use super::{PerParser, PerReader, PerWriter, ToPer};
+
+/// Encode an `INTEGER` value (X.691: 13)
+fn per_encode_integer(
+    writer: &mut PerWriter,
+    value: i128,
+    constraints: &Constraints,
+) -> PerResult<()> {
+    let vc = &constraints.value;
+    let in_root = vc.contains(value);
+    if vc.extensible {
+        writer.write_bit(!in_root);
+        if !in_root {
+            return writer.write_unconstrained_whole_number(value);
+        }
+    } else if !in_root {
+        return Err(PerError::ConstraintViolation);
+    }
+    match (vc.lower, vc.upper) {
+        (Some(lower), Some(upper)) => writer.write_constrained_whole_number(value, lower, upper),
+        (Some(lower), None) => writer.write_semi_constrained_whole_number(value, lower),
+        _ => writer.write_unconstrained_whole_number(value),
+    }
+}
+
+/// Decode an `INTEGER` value (X.691: 13)
+fn per_decode_integer(reader: &mut PerReader, constraints: &Constraints) -> PerResult<i128> {
+    let vc = &constraints.value;
+    if vc.extensible && reader.read_bit()? {
+        return reader.read_unconstrained_whole_number();
+    }
+    let value = match (vc.lower, vc.upper) {
+        (Some(lower), Some(upper)) => reader.read_constrained_whole_number(lower, upper)?,
+        (Some(lower), None) => reader.read_semi_constrained_whole_number(lower)?,
+        _ => reader.read_unconstrained_whole_number()?,
+    };
+    if !vc.contains(value) {
+        return Err(PerError::ConstraintViolation);
+    }
+    Ok(value)
+}
+
+macro_rules! impl_per_int {
+    ($($ty:ty)*) => {
+        $(
+            impl ToPer for $ty {
+                fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()> {
+                    per_encode_integer(writer, i128::from(*self), constraints)
+                }
+            }
+
+            impl PerParser<'_> for $ty {
+                fn per_decode(reader: &mut PerReader, constraints: &Constraints) -> PerResult<Self> {
+                    let value = per_decode_integer(reader, constraints)?;
+                    <$ty>::try_from(value).map_err(|_| PerError::IntegerTooLarge)
+                }
+            }
+        )*
+    };
+}
+
+impl_per_int!(i8 i16 i32 i64 i128 u8 u16 u32 u64);
+
+impl ToPer for Integer<'_> {
+    fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()> {
+        let vc = &constraints.value;
+        if vc.lower.is_none() && !vc.extensible {
+            // unconstrained: content is already a 2's-complement binary integer
+            return writer.write_octets_with_length(&self.data);
+        }
+        let value = self.as_i128().map_err(|_| PerError::IntegerTooLarge)?;
+        per_encode_integer(writer, value, constraints)
+    }
+}
+
+impl<'a> PerParser<'a> for Integer<'a> {
+    fn per_decode(reader: &mut PerReader<'a>, constraints: &Constraints) -> PerResult<Self> {
+        let vc = &constraints.value;
+        if vc.lower.is_none() && !vc.extensible {
+            let data = reader.read_octets_with_length()?;
+            if data.is_empty() {
+                return Err(PerError::InvalidEncoding);
+            }
+            return Ok(Integer { data });
+        }
+        let value = per_decode_integer(reader, constraints)?;
+        Ok(Integer::from_i128(value))
+    }
+}
+
+fn enumerated_root_count(constraints: &Constraints) -> PerResult<usize> {
+    let upper = constraints.value.upper.ok_or(PerError::MissingConstraint)?;
+    upper
+        .checked_add(1)
+        .and_then(|n| usize::try_from(n).ok())
+        .ok_or(PerError::ConstraintViolation)
+}
+
+/// `ENUMERATED` values are encoded using the value constraint: the upper bound is the last
+/// value of the root (values must be numbered `0..=upper`).
+impl ToPer for Enumerated {
+    fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()> {
+        let vc = &constraints.value;
+        let root_count = enumerated_root_count(constraints)?;
+        writer.write_enumerated(self.0 as usize, root_count, vc.extensible)
+    }
+}
+
+impl PerParser<'_> for Enumerated {
+    fn per_decode(reader: &mut PerReader, constraints: &Constraints) -> PerResult<Self> {
+        let vc = &constraints.value;
+        let root_count = enumerated_root_count(constraints)?;
+        let index = reader.read_enumerated(root_count, vc.extensible)?;
+        let value = u32::try_from(index).map_err(|_| PerError::IntegerTooLarge)?;
+        Ok(Enumerated(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use hex_literal::hex;
+
+    use crate::per::{PerParser, PerReader, PerWriter, ToPer};
+    use crate::{Constraints, Enumerated, Integer};
+
+    fn aper<T: ToPer>(value: T, c: &Constraints) -> Vec<u8> {
+        let mut w = PerWriter::aper();
+        value.per_encode(&mut w, c).unwrap();
+        w.into_bytes()
+    }
+
+    fn uper<T: ToPer>(value: T, c: &Constraints) -> Vec<u8> {
+        let mut w = PerWriter::uper();
+        value.per_encode(&mut w, c).unwrap();
+        w.into_bytes()
+    }
+
+    #[test]
+    fn per_integer_constrained() {
+        let c = Constraints::NONE.with_value(Some(3), Some(6), false);
+        assert_eq!(&aper(5u8, &c), &hex!("80"));
+        assert_eq!(&uper(5u8, &c), &hex!("80"));
+        let mut r = PerReader::aper(&hex!("80"));
+        assert_eq!(u8::per_decode(&mut r, &c).unwrap(), 5);
+
+        // value outside root
+        let mut w = PerWriter::aper();
+        7u8.per_encode(&mut w, &c).unwrap_err();
+
+        // extensible constraint, value outside root
+        let c = Constraints::NONE.with_value(Some(3), Some(6), true);
+        assert_eq!(&uper(5u8, &c), &hex!("40"));
+        assert_eq!(&uper(7u8, &c), &hex!("80 83 80"));
+        let mut r = PerReader::uper(&hex!("80 83 80"));
+        assert_eq!(u8::per_decode(&mut r, &c).unwrap(), 7);
+
+        // negative values
+        let c = Constraints::NONE.with_value(Some(-100), Some(100), false);
+        let v = aper(-100i32, &c);
+        assert_eq!(&v, &hex!("00"));
+        let mut r = PerReader::aper(&v);
+        assert_eq!(i32::per_decode(&mut r, &c).unwrap(), -100);
+    }
+
+    #[test]
+    fn per_integer_semi_constrained() {
+        let c = Constraints::NONE.with_value(Some(-1), None, false);
+        let v = aper(255i64, &c);
+        assert_eq!(&v, &hex!("02 01 00"));
+        let mut r = PerReader::aper(&v);
+        assert_eq!(i64::per_decode(&mut r, &c).unwrap(), 255);
+    }
+
+    #[test]
+    fn per_integer_unconstrained() {
+        let v = (-129i16).to_uper_vec().unwrap();
+        assert_eq!(&v, &hex!("02 ff 7f"));
+        assert_eq!(i16::parse_uper(&v).unwrap(), -129);
+
+        // too large for requested type
+        let v = 256u16.to_aper_vec().unwrap();
+        u8::parse_aper(&v).unwrap_err();
+
+        let i = Integer::from_u64(0x8000_0000_0000_0000);
+        let v = i.to_aper_vec().unwrap();
+        assert_eq!(&v, &hex!("09 00 80 00 00 00 00 00 00 00"));
+        let res = Integer::parse_aper(&v).unwrap();
+        assert_eq!(res, i);
+    }
+
+    #[test]
+    fn per_enumerated() {
+        let c = Constraints::NONE.with_value(Some(0), Some(3), false);
+        assert_eq!(&uper(Enumerated(2), &c), &hex!("80"));
+        let mut r = PerReader::uper(&hex!("80"));
+        assert_eq!(Enumerated::per_decode(&mut r, &c).unwrap(), Enumerated(2));
+
+        // extension value
+        let c = Constraints::NONE.with_value(Some(0), Some(3), true);
+        let v = uper(Enumerated(5), &c);
+        assert_eq!(&v, &hex!("81"));
+        let mut r = PerReader::uper(&v);
+        assert_eq!(Enumerated::per_decode(&mut r, &c).unwrap(), Enumerated(5));
+
+        // constraint is required
+        Enumerated(2).to_uper_vec().unwrap_err();
+    }
+}
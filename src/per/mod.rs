@@ -0,0 +1,88 @@
+//! Packed Encoding Rules (PER) encoders and decoders
+//!
+//! PER is defined in [[X.691]]. Unlike BER and DER, encodings are not self-describing: tags and
+//! lengths are not encoded when they can be deduced from the type definition, and the encoding
+//! of values depends on the PER-visible [`Constraints`](crate::Constraints) of the type.
+//!
+//! Two variants are supported:
+//! - the aligned variant (APER), where some fields are padded to octet boundaries
+//! - the unaligned variant (UPER), where fields are written as bit-fields with no padding
+//!
+//! Both variants use the same traits, [`ToPer`] and [`PerParser`]. The variant is a property of
+//! the [`PerWriter`] or [`PerReader`] object.
+//!
+//! Types built with the `Sequence`, `Choice` and `Enumerated` custom derive attributes can implement
+//! these traits by adding `PER` to the `parse` and `encode` items of the `asn1` attribute. Constraints
+//! are described using the `value` and `size` items of the `asn1` attribute on fields. See
+//! [doc::derive](crate::doc::derive) for details.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use asn1_rs::per::{PerParser, PerWriter, ToPer};
+//! use asn1_rs::Constraints;
+//!
+//! // INTEGER (0..255) is encoded as one aligned octet in APER, and as 8 bits in UPER
+//! let c = Constraints::NONE.with_value(Some(0), Some(255), false);
+//!
+//! let mut writer = PerWriter::aper();
+//! writer.write_bit(true);
+//! 42u8.per_encode(&mut writer, &c).expect("serialization failed");
+//! assert_eq!(&writer.into_bytes(), &[0x80, 0x2a]);
+//!
+//! let mut writer = PerWriter::uper();
+//! writer.write_bit(true);
+//! 42u8.per_encode(&mut writer, &c).expect("serialization failed");
+//! assert_eq!(&writer.into_bytes(), &[0x95, 0x00]);
+//!
+//! // unconstrained values
+//! let v = 1234u32.to_uper_vec().expect("serialization failed");
+//! assert_eq!(&v, &[0x02, 0x04, 0xd2]);
+//! let value = u32::parse_uper(&v).expect("parsing failed");
+//! assert_eq!(value, 1234);
+//! ```
+//!
+//! # Limitations
+//!
+//! - permitted alphabet constraints on restricted character strings are not supported
+//! - extension additions of `SEQUENCE` types are skipped when decoding, and extension additions of
+//!   `CHOICE` and `ENUMERATED` types are reported as [`PerError::UnknownExtension`](crate::PerError)
+//! - `SET` and `SET OF` are not supported
+//!
+//! [X.691]: http://www.itu.int/rec/T-REC-X.691/en "Recommendation ITU-T X.691"
+
+mod integer;
+mod reader;
+mod strings;
+mod traits;
+mod types;
+mod writer;
+
+pub use reader::*;
+pub use traits::*;
+pub use writer::*;
+
+/// 16K: number of items in a fragment of a length determinant (X.691: 11.9.3.8)
+pub(crate) const PER_FRAGMENT_SIZE: usize = 16384;
+
+/// 64K: bound used in length determinants and fixed-size items (X.691: 11.9.3.3)
+pub(crate) const PER_64K: usize = 65536;
+
+/// Returns the number of bits required to encode `value` as a non-negative binary integer
+#[inline]
+pub(crate) const fn num_bits(value: u128) -> usize {
+    (u128::BITS - value.leading_zeros()) as usize
+}
+
+/// Returns the number of octets required to encode `value` as a non-negative binary integer
+///
+/// The result is at least 1.
+#[inline]
+pub(crate) const fn num_octets(value: u128) -> usize {
+    let n = (num_bits(value) + 7) / 8;
+    if n == 0 {
+        1
+    } else {
+        n
+    }
+}
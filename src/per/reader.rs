@@ -0,0 +1,446 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::{PerError, PerResult, SizeConstraint};
+
+use super::{num_bits, num_octets, PER_64K, PER_FRAGMENT_SIZE};
+
+/// Bit-level reader for PER encodings
+///
+/// The reader holds the variant (aligned or unaligned) of the encoding. Procedures defined in
+/// [[X.691]] section 11 are provided as methods, and are used to implement [`PerParser`](super::PerParser).
+///
+/// [X.691]: http://www.itu.int/rec/T-REC-X.691/en "Recommendation ITU-T X.691"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerReader<'a> {
+    data: &'a [u8],
+    bit_pos: usize,
+    aligned: bool,
+}
+
+impl<'a> PerReader<'a> {
+    /// Build a new reader. If `aligned` is `true`, the aligned variant (APER) is used.
+    pub const fn new(data: &'a [u8], aligned: bool) -> Self {
+        PerReader {
+            data,
+            bit_pos: 0,
+            aligned,
+        }
+    }
+
+    /// Build a new reader for the aligned variant (APER)
+    #[inline]
+    pub const fn aper(data: &'a [u8]) -> Self {
+        Self::new(data, true)
+    }
+
+    /// Build a new reader for the unaligned variant (UPER)
+    #[inline]
+    pub const fn uper(data: &'a [u8]) -> Self {
+        Self::new(data, false)
+    }
+
+    /// Returns `true` if the reader uses the aligned variant
+    #[inline]
+    pub const fn is_aligned(&self) -> bool {
+        self.aligned
+    }
+
+    /// Returns the current position (in bits)
+    #[inline]
+    pub const fn bit_pos(&self) -> usize {
+        self.bit_pos
+    }
+
+    /// Returns the number of bits remaining in input
+    #[inline]
+    pub const fn remaining_bits(&self) -> usize {
+        8 * self.data.len() - self.bit_pos
+    }
+
+    /// Returns the input octets following the current position
+    ///
+    /// If the reader is not at an octet boundary, the current (partially read) octet is skipped.
+    pub fn remaining_bytes(&self) -> &'a [u8] {
+        let idx = (self.bit_pos + 7) / 8;
+        &self.data[idx..]
+    }
+
+    fn check_remaining(&self, nbits: usize) -> PerResult<()> {
+        let remaining = self.remaining_bits();
+        if nbits > remaining {
+            let missing = (nbits - remaining + 7) / 8;
+            return Err(PerError::Incomplete(nom::Needed::new(missing)));
+        }
+        Ok(())
+    }
+
+    /// Read a single bit
+    pub fn read_bit(&mut self) -> PerResult<bool> {
+        self.check_remaining(1)?;
+        let b = self.data[self.bit_pos / 8] & (0x80 >> (self.bit_pos % 8));
+        self.bit_pos += 1;
+        Ok(b != 0)
+    }
+
+    /// Read `nbits` bits as an unsigned integer, most significant bit first
+    pub fn read_bits(&mut self, nbits: usize) -> PerResult<u128> {
+        debug_assert!(nbits <= 128);
+        self.check_remaining(nbits)?;
+        let mut value = 0u128;
+        for _ in 0..nbits {
+            value = (value << 1) | u128::from(self.read_bit()?);
+        }
+        Ok(value)
+    }
+
+    /// Read `len` octets. This function does not align the reader.
+    ///
+    /// If the reader is at an octet boundary, the returned data is borrowed from input.
+    pub fn read_bytes(&mut self, len: usize) -> PerResult<Cow<'a, [u8]>> {
+        let nbits = len.checked_mul(8).ok_or(PerError::InvalidEncoding)?;
+        self.check_remaining(nbits)?;
+        if self.bit_pos % 8 == 0 {
+            let start = self.bit_pos / 8;
+            self.bit_pos += nbits;
+            return Ok(Cow::Borrowed(&self.data[start..start + len]));
+        }
+        let mut v = Vec::with_capacity(len);
+        for _ in 0..len {
+            v.push(self.read_bits(8)? as u8);
+        }
+        Ok(Cow::Owned(v))
+    }
+
+    /// Read `nbits` bits, and return them packed in octets (most significant bit first)
+    ///
+    /// The unused bits of the last octet are set to zero.
+    pub fn read_bits_to_vec(&mut self, nbits: usize) -> PerResult<Vec<u8>> {
+        self.check_remaining(nbits)?;
+        let mut v = Vec::with_capacity((nbits + 7) / 8);
+        let full = nbits / 8;
+        for _ in 0..full {
+            v.push(self.read_bits(8)? as u8);
+        }
+        let rem = nbits % 8;
+        if rem > 0 {
+            let b = self.read_bits(rem)? as u8;
+            v.push(b << (8 - rem));
+        }
+        Ok(v)
+    }
+
+    /// Skip padding bits up to the next octet boundary (aligned variant only)
+    pub fn align(&mut self) {
+        if self.aligned {
+            self.bit_pos = (self.bit_pos + 7) / 8 * 8;
+        }
+    }
+
+    /// Decode a constrained whole number, in range `lower..=upper` (X.691: 11.5.7)
+    pub fn read_constrained_whole_number(&mut self, lower: i128, upper: i128) -> PerResult<i128> {
+        if upper < lower {
+            return Err(PerError::ConstraintViolation);
+        }
+        let max = upper.wrapping_sub(lower) as u128;
+        if max == 0 {
+            return Ok(lower);
+        }
+        let n = if !self.aligned {
+            self.read_bits(num_bits(max))?
+        } else {
+            match max + 1 {
+                0..=255 => self.read_bits(num_bits(max))?,
+                256 => {
+                    self.align();
+                    self.read_bits(8)?
+                }
+                257..=65536 => {
+                    self.align();
+                    self.read_bits(16)?
+                }
+                _ => {
+                    let len = self.read_constrained_whole_number(1, num_octets(max) as i128)?;
+                    self.align();
+                    self.read_bits(8 * len as usize)?
+                }
+            }
+        };
+        if n > max {
+            return Err(PerError::ConstraintViolation);
+        }
+        Ok(lower.wrapping_add(n as i128))
+    }
+
+    /// Decode a semi-constrained whole number, with lower bound `lower` (X.691: 11.7)
+    pub fn read_semi_constrained_whole_number(&mut self, lower: i128) -> PerResult<i128> {
+        let bytes = self.read_octets_with_length()?;
+        if bytes.is_empty() {
+            return Err(PerError::InvalidEncoding);
+        }
+        if bytes.len() > 16 {
+            return Err(PerError::IntegerTooLarge);
+        }
+        let n = bytes
+            .iter()
+            .fold(0u128, |acc, &b| (acc << 8) | u128::from(b));
+        let n = i128::try_from(n).map_err(|_| PerError::IntegerTooLarge)?;
+        lower.checked_add(n).ok_or(PerError::IntegerTooLarge)
+    }
+
+    /// Decode an unconstrained whole number (X.691: 11.8)
+    pub fn read_unconstrained_whole_number(&mut self) -> PerResult<i128> {
+        let bytes = self.read_octets_with_length()?;
+        if bytes.is_empty() {
+            return Err(PerError::InvalidEncoding);
+        }
+        if bytes.len() > 16 {
+            return Err(PerError::IntegerTooLarge);
+        }
+        // sign extension
+        let init = if bytes[0] & 0x80 != 0 { -1 } else { 0 };
+        let value = bytes
+            .iter()
+            .fold(init, |acc: i128, &b| (acc << 8) | i128::from(b));
+        Ok(value)
+    }
+
+    /// Decode a normally small non-negative whole number (X.691: 11.6)
+    pub fn read_normally_small_number(&mut self) -> PerResult<u64> {
+        if !self.read_bit()? {
+            Ok(self.read_bits(6)? as u64)
+        } else {
+            let n = self.read_semi_constrained_whole_number(0)?;
+            u64::try_from(n).map_err(|_| PerError::IntegerTooLarge)
+        }
+    }
+
+    /// Decode a normally small length (X.691: 11.9.3.4)
+    pub fn read_normally_small_length(&mut self) -> PerResult<usize> {
+        if !self.read_bit()? {
+            Ok(self.read_bits(6)? as usize + 1)
+        } else {
+            let mut len = 0;
+            self.read_length_with(&SizeConstraint::NONE, |_, count| {
+                len += count;
+                Ok(())
+            })?;
+            Ok(len)
+        }
+    }
+
+    /// Read the extension bit for a size constraint, if it is extensible
+    ///
+    /// Returns the constraint to use for the decoding of the length: the root of `size` if the
+    /// extension bit is not set, or no constraint otherwise.
+    pub fn read_size_extension(&mut self, size: &SizeConstraint) -> PerResult<SizeConstraint> {
+        if size.extensible && self.read_bit()? {
+            Ok(SizeConstraint::NONE)
+        } else {
+            Ok(SizeConstraint::new(size.lower, size.upper, false))
+        }
+    }
+
+    /// Decode a length determinant (X.691: 11.9), and call `f` to read the items
+    ///
+    /// `f` receives the reader, and the number of items to read. If the encoding is fragmented,
+    /// `f` is called once per fragment.
+    ///
+    /// Returns the total number of items.
+    ///
+    /// The extension marker of `size` is ignored, see [`Self::read_size_extension`].
+    pub fn read_length_with<F>(&mut self, size: &SizeConstraint, mut f: F) -> PerResult<usize>
+    where
+        F: FnMut(&mut Self, usize) -> PerResult<()>,
+    {
+        if let Some(upper) = size.upper {
+            if upper < PER_64K {
+                let count =
+                    self.read_constrained_whole_number(size.lower as i128, upper as i128)? as usize;
+                f(self, count)?;
+                return Ok(count);
+            }
+        }
+        // unconstrained length, possibly fragmented
+        let mut total = 0usize;
+        loop {
+            self.align();
+            let b = self.read_bits(8)? as usize;
+            let count = if b & 0x80 == 0 {
+                b
+            } else if b & 0xc0 == 0x80 {
+                ((b & 0x3f) << 8) | self.read_bits(8)? as usize
+            } else {
+                let m = b & 0x3f;
+                if !(1..=4).contains(&m) {
+                    return Err(PerError::InvalidEncoding);
+                }
+                let count = m * PER_FRAGMENT_SIZE;
+                f(self, count)?;
+                total = total.checked_add(count).ok_or(PerError::InvalidEncoding)?;
+                continue;
+            };
+            f(self, count)?;
+            total = total.checked_add(count).ok_or(PerError::InvalidEncoding)?;
+            break;
+        }
+        if !size.contains(total) {
+            return Err(PerError::ConstraintViolation);
+        }
+        Ok(total)
+    }
+
+    /// Decode octets, prefixed with an unconstrained length determinant
+    ///
+    /// If the encoding is not fragmented and the octets are aligned, the returned data is borrowed
+    /// from input.
+    pub fn read_octets_with_length(&mut self) -> PerResult<Cow<'a, [u8]>> {
+        self.read_octets_with_size(&SizeConstraint::NONE)
+    }
+
+    /// Decode octets, prefixed with a length determinant using the size constraint `size`
+    ///
+    /// If the encoding is not fragmented and the octets are aligned, the returned data is borrowed
+    /// from input.
+    pub fn read_octets_with_size(&mut self, size: &SizeConstraint) -> PerResult<Cow<'a, [u8]>> {
+        let mut result: Option<Cow<'a, [u8]>> = None;
+        self.read_length_with(size, |r, count| {
+            r.align();
+            let bytes = r.read_bytes(count)?;
+            result = match result.take() {
+                None => Some(bytes),
+                Some(prev) => {
+                    let mut v = prev.into_owned();
+                    v.extend_from_slice(&bytes);
+                    Some(Cow::Owned(v))
+                }
+            };
+            Ok(())
+        })?;
+        Ok(result.unwrap_or(Cow::Borrowed(&[])))
+    }
+
+    /// Read an open type field (X.691: 11.2), and return the encoded value
+    ///
+    /// The returned data is a complete encoding, which can be decoded using a new [`PerReader`].
+    pub fn read_open_type(&mut self) -> PerResult<Cow<'a, [u8]>> {
+        self.read_octets_with_length()
+    }
+
+    /// Decode the index of an `ENUMERATED` value (X.691: 14)
+    ///
+    /// If the value is an extension, the returned index is `root_count` plus the extension index.
+    pub fn read_enumerated(&mut self, root_count: usize, extensible: bool) -> PerResult<usize> {
+        if extensible && self.read_bit()? {
+            let n = self.read_normally_small_number()?;
+            let n = usize::try_from(n).map_err(|_| PerError::IntegerTooLarge)?;
+            return root_count.checked_add(n).ok_or(PerError::IntegerTooLarge);
+        }
+        if root_count == 0 {
+            return Err(PerError::InvalidEncoding);
+        }
+        let index = self.read_constrained_whole_number(0, root_count as i128 - 1)?;
+        Ok(index as usize)
+    }
+
+    /// Decode the index of a `CHOICE` alternative (X.691: 23)
+    ///
+    /// If the alternative is an extension, the open type is skipped and
+    /// [`PerError::UnknownExtension`] is returned.
+    pub fn read_choice_index(&mut self, root_count: usize, extensible: bool) -> PerResult<usize> {
+        if extensible && self.read_bit()? {
+            let n = self.read_normally_small_number()?;
+            let _ = self.read_open_type()?;
+            return Err(PerError::UnknownExtension(n));
+        }
+        if root_count == 0 {
+            return Err(PerError::InvalidEncoding);
+        }
+        let index = self.read_constrained_whole_number(0, root_count as i128 - 1)?;
+        Ok(index as usize)
+    }
+
+    /// Skip the extension additions of a `SEQUENCE` (X.691: 19.7)
+    ///
+    /// This function must be called after decoding root components, if the extension bit was set.
+    /// Returns the number of extension additions that were present.
+    pub fn skip_sequence_extensions(&mut self) -> PerResult<usize> {
+        let len = self.read_normally_small_length()?;
+        let mut present = 0;
+        for _ in 0..len {
+            if self.read_bit()? {
+                present += 1;
+            }
+        }
+        for _ in 0..present {
+            let _ = self.read_open_type()?;
+        }
+        Ok(present)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use hex_literal::hex;
+
+    use super::PerReader;
+    use crate::per::PerWriter;
+    use crate::{PerError, SizeConstraint};
+
+    #[test]
+    fn per_reader_bits() {
+        let input = &hex!("af f8");
+        let mut r = PerReader::uper(input);
+        assert!(r.read_bit().unwrap());
+        assert_eq!(r.read_bits(4).unwrap(), 0b0101);
+        assert_eq!(r.read_bytes(1).unwrap().as_ref(), &[0xff]);
+        assert_eq!(r.remaining_bits(), 3);
+        let e = r.read_bits(4).unwrap_err();
+        assert_eq!(e, PerError::Incomplete(nom::Needed::new(1)));
+    }
+
+    #[test]
+    fn per_reader_whole_numbers() {
+        let input = &hex!("60 12 34");
+        let mut r = PerReader::aper(input);
+        assert_eq!(r.read_constrained_whole_number(0, 7).unwrap(), 3);
+        assert_eq!(r.read_constrained_whole_number(0, 65535).unwrap(), 0x1234);
+
+        let input = &hex!("40 01 00");
+        let mut r = PerReader::aper(input);
+        assert_eq!(r.read_constrained_whole_number(0, 1 << 24).unwrap(), 256);
+
+        let input = &hex!("01 ff 02 00 80 02 01 00");
+        let mut r = PerReader::aper(input);
+        assert_eq!(r.read_unconstrained_whole_number().unwrap(), -1);
+        assert_eq!(r.read_unconstrained_whole_number().unwrap(), 128);
+        assert_eq!(r.read_semi_constrained_whole_number(0).unwrap(), 256);
+
+        // value out of range
+        let input = &hex!("f0");
+        let mut r = PerReader::uper(input);
+        r.read_constrained_whole_number(0, 10).unwrap_err();
+    }
+
+    #[test]
+    fn per_reader_length() {
+        let mut r = PerReader::uper(&[0x40]);
+        let size = SizeConstraint::new(1, Some(8), false);
+        let count = r.read_length_with(&size, |_, _| Ok(())).unwrap();
+        assert_eq!(count, 3);
+
+        // fragmented octets
+        for len in [0, 127, 128, 16384, 16384 + 10, 4 * 16384 + 1] {
+            let data = vec![0x55u8; len];
+            let mut w = PerWriter::aper();
+            w.write_octets_with_length(&data).unwrap();
+            let v = w.into_bytes();
+            let mut r = PerReader::aper(&v);
+            let res = r.read_octets_with_length().unwrap();
+            assert_eq!(res.as_ref(), data.as_slice());
+        }
+    }
+}
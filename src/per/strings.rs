@@ -0,0 +1,328 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::{
+    BmpString, Constraints, GeneralString, GraphicString, Ia5String, NumericString, PerError,
+    PerResult, PrintableString, SizeConstraint, TeletexString, UniversalString, Utf8String,
+    VideotexString, VisibleString,
+};
+
+use super::{PerParser, PerReader, PerWriter, ToPer, PER_64K};
+
+/// Description of a known-multiplier character string type (X.691: 30)
+struct KnownMultiplier {
+    /// Number of bits per character in the unaligned variant
+    uper_bits: usize,
+    /// Number of bits per character in the aligned variant
+    aper_bits: usize,
+    /// Value used to encode a character, or `None` if the character is not in the alphabet
+    encode: fn(char) -> Option<u32>,
+    /// Character corresponding to an encoded value
+    decode: fn(u32) -> Option<char>,
+}
+
+impl KnownMultiplier {
+    const fn bits(&self, aligned: bool) -> usize {
+        if aligned {
+            self.aper_bits
+        } else {
+            self.uper_bits
+        }
+    }
+}
+
+fn encode_direct_ascii(c: char, is_valid: fn(u8) -> bool) -> Option<u32> {
+    if c.is_ascii() && is_valid(c as u8) {
+        Some(c as u32)
+    } else {
+        None
+    }
+}
+
+fn decode_direct_ascii(v: u32, is_valid: fn(u8) -> bool) -> Option<char> {
+    match u8::try_from(v) {
+        Ok(b) if b.is_ascii() && is_valid(b) => Some(char::from(b)),
+        _ => None,
+    }
+}
+
+const fn is_printable(b: u8) -> bool {
+    matches!(b,
+        b'a'..=b'z'
+        | b'A'..=b'Z'
+        | b'0'..=b'9'
+        | b' '
+        | b'\''
+        | b'('
+        | b')'
+        | b'+'
+        | b','
+        | b'-'
+        | b'.'
+        | b'/'
+        | b':'
+        | b'='
+        | b'?')
+}
+
+const NUMERIC_ALPHABET: &[u8] = b" 0123456789";
+
+const IA5: KnownMultiplier = KnownMultiplier {
+    uper_bits: 7,
+    aper_bits: 8,
+    encode: |c| encode_direct_ascii(c, |_| true),
+    decode: |v| decode_direct_ascii(v, |_| true),
+};
+
+const PRINTABLE: KnownMultiplier = KnownMultiplier {
+    uper_bits: 7,
+    aper_bits: 8,
+    encode: |c| encode_direct_ascii(c, is_printable),
+    decode: |v| decode_direct_ascii(v, is_printable),
+};
+
+const VISIBLE: KnownMultiplier = KnownMultiplier {
+    uper_bits: 7,
+    aper_bits: 8,
+    encode: |c| encode_direct_ascii(c, |b| (0x20..=0x7e).contains(&b)),
+    decode: |v| decode_direct_ascii(v, |b| (0x20..=0x7e).contains(&b)),
+};
+
+// the alphabet has less characters than the largest value, so characters are encoded using
+// their index (X.691: 30.5.4)
+const NUMERIC: KnownMultiplier = KnownMultiplier {
+    uper_bits: 4,
+    aper_bits: 4,
+    encode: |c| {
+        NUMERIC_ALPHABET
+            .iter()
+            .position(|&b| c == char::from(b))
+            .map(|idx| idx as u32)
+    },
+    decode: |v| NUMERIC_ALPHABET.get(v as usize).map(|&b| char::from(b)),
+};
+
+const BMP: KnownMultiplier = KnownMultiplier {
+    uper_bits: 16,
+    aper_bits: 16,
+    encode: |c| Some(c as u32).filter(|&v| v <= 0xffff),
+    decode: char::from_u32,
+};
+
+const UNIVERSAL: KnownMultiplier = KnownMultiplier {
+    uper_bits: 32,
+    aper_bits: 32,
+    encode: |c| Some(c as u32),
+    decode: char::from_u32,
+};
+
+/// Returns `true` if the string cannot be longer than 16 bits. In that case, characters are never
+/// aligned (X.691: 30.5.7).
+fn is_short_string(size: &SizeConstraint, bits_per_char: usize) -> bool {
+    size.upper
+        .is_some_and(|ub| ub.saturating_mul(bits_per_char) <= 16)
+}
+
+/// Encode a known-multiplier character string (X.691: 30.5)
+fn per_encode_known_multiplier(
+    writer: &mut PerWriter,
+    s: &str,
+    km: &KnownMultiplier,
+    constraints: &Constraints,
+) -> PerResult<()> {
+    let values = s
+        .chars()
+        .map(km.encode)
+        .collect::<Option<Vec<_>>>()
+        .ok_or(PerError::StringInvalidCharset)?;
+    let size = writer.write_size_extension(values.len(), &constraints.size)?;
+    let b = km.bits(writer.is_aligned());
+    let short = is_short_string(&size, b);
+    let write_chars = |w: &mut PerWriter, start: usize, end: usize| {
+        if !short {
+            w.align();
+        }
+        for &v in &values[start..end] {
+            w.write_bits(u128::from(v), b);
+        }
+        Ok(())
+    };
+    match size.fixed() {
+        Some(n) if n < PER_64K => write_chars(writer, 0, n),
+        _ => writer.write_length_with(values.len(), &size, write_chars),
+    }
+}
+
+/// Decode a known-multiplier character string (X.691: 30.5)
+fn per_decode_known_multiplier(
+    reader: &mut PerReader,
+    km: &KnownMultiplier,
+    constraints: &Constraints,
+) -> PerResult<String> {
+    let size = reader.read_size_extension(&constraints.size)?;
+    let b = km.bits(reader.is_aligned());
+    let short = is_short_string(&size, b);
+    let mut s = String::new();
+    let mut read_chars = |r: &mut PerReader, count: usize| {
+        if !short {
+            r.align();
+        }
+        for _ in 0..count {
+            let v = r.read_bits(b)? as u32;
+            s.push((km.decode)(v).ok_or(PerError::StringInvalidCharset)?);
+        }
+        Ok(())
+    };
+    match size.fixed() {
+        Some(n) if n < PER_64K => read_chars(reader, n)?,
+        _ => {
+            reader.read_length_with(&size, read_chars)?;
+        }
+    }
+    Ok(s)
+}
+
+macro_rules! impl_per_known_multiplier {
+    ($($name:ident => $km:expr),* $(,)?) => {
+        $(
+            impl ToPer for $name<'_> {
+                fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()> {
+                    per_encode_known_multiplier(writer, &self.data, &$km, constraints)
+                }
+            }
+
+            impl PerParser<'_> for $name<'_> {
+                fn per_decode(reader: &mut PerReader, constraints: &Constraints) -> PerResult<Self> {
+                    per_decode_known_multiplier(reader, &$km, constraints).map(Self::from)
+                }
+            }
+        )*
+    };
+}
+
+impl_per_known_multiplier! {
+    Ia5String => IA5,
+    PrintableString => PRINTABLE,
+    VisibleString => VISIBLE,
+    NumericString => NUMERIC,
+    BmpString => BMP,
+    UniversalString => UNIVERSAL,
+}
+
+/// Decode the octets of a character string which is not a known-multiplier type (X.691: 30.6)
+fn per_decode_utf8<'a>(reader: &mut PerReader<'a>) -> PerResult<Cow<'a, str>> {
+    match reader.read_octets_with_length()? {
+        Cow::Borrowed(b) => core::str::from_utf8(b)
+            .map(Cow::Borrowed)
+            .map_err(|_| PerError::StringInvalidCharset),
+        Cow::Owned(v) => String::from_utf8(v)
+            .map(Cow::Owned)
+            .map_err(|_| PerError::StringInvalidCharset),
+    }
+}
+
+/// Strings which are not known-multiplier types are encoded as octets with an unconstrained length.
+/// Size constraints are not PER-visible for these types.
+impl ToPer for str {
+    fn per_encode(&self, writer: &mut PerWriter, _constraints: &Constraints) -> PerResult<()> {
+        writer.write_octets_with_length(self.as_bytes())
+    }
+}
+
+impl ToPer for String {
+    fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()> {
+        self.as_str().per_encode(writer, constraints)
+    }
+}
+
+impl PerParser<'_> for String {
+    fn per_decode(reader: &mut PerReader, _constraints: &Constraints) -> PerResult<Self> {
+        per_decode_utf8(reader).map(Cow::into_owned)
+    }
+}
+
+macro_rules! impl_per_octet_string_type {
+    ($($name:ident)*) => {
+        $(
+            impl ToPer for $name<'_> {
+                fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()> {
+                    self.data.per_encode(writer, constraints)
+                }
+            }
+
+            impl<'a> PerParser<'a> for $name<'a> {
+                fn per_decode(reader: &mut PerReader<'a>, _constraints: &Constraints) -> PerResult<Self> {
+                    per_decode_utf8(reader).map(|data| $name { data })
+                }
+            }
+        )*
+    };
+}
+
+impl_per_octet_string_type!(Utf8String GeneralString GraphicString TeletexString VideotexString);
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use hex_literal::hex;
+
+    use crate::per::{PerParser, PerReader, PerWriter, ToPer};
+    use crate::{BmpString, Constraints, Ia5String, NumericString, PrintableString, Utf8String};
+
+    #[test]
+    fn per_known_multiplier_strings() {
+        // unconstrained IA5String
+        let s = Ia5String::new("abc");
+        assert_eq!(&s.to_aper_vec().unwrap(), &hex!("03 61 62 63"));
+        assert_eq!(&s.to_uper_vec().unwrap(), &hex!("03 c3 8b 18"));
+        assert_eq!(Ia5String::parse_uper(&hex!("03 c3 8b 18")).unwrap(), s);
+
+        // fixed size, at most 16 bits: no alignment
+        let c = Constraints::NONE.with_size(2, Some(2), false);
+        let mut w = PerWriter::aper();
+        w.write_bit(true);
+        Ia5String::new("ab").per_encode(&mut w, &c).unwrap();
+        let v = w.into_bytes();
+        assert_eq!(&v, &hex!("b0 b1 00"));
+        let mut r = PerReader::aper(&v);
+        assert!(r.read_bit().unwrap());
+        assert_eq!(Ia5String::per_decode(&mut r, &c).unwrap().as_ref(), "ab");
+
+        // NumericString uses indexes
+        let c = Constraints::NONE.with_size(1, Some(8), false);
+        let mut w = PerWriter::uper();
+        NumericString::new("1 9").per_encode(&mut w, &c).unwrap();
+        let v = w.into_bytes();
+        assert_eq!(&v, &hex!("44 14"));
+        let mut r = PerReader::uper(&v);
+        assert_eq!(
+            NumericString::per_decode(&mut r, &c).unwrap().as_ref(),
+            "1 9"
+        );
+
+        // invalid characters
+        PrintableString::new("a@b").to_uper_vec().unwrap_err();
+        BmpString::new("\u{1f600}").to_uper_vec().unwrap_err();
+        NumericString::parse_uper(&hex!("01 f0")).unwrap_err();
+
+        // size constraint violation
+        let mut w = PerWriter::uper();
+        Ia5String::new("abc")
+            .per_encode(&mut w, &Constraints::NONE.with_size(1, Some(2), false))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn per_octet_strings_types() {
+        let s = Utf8String::from("é");
+        let v = s.to_uper_vec().unwrap();
+        assert_eq!(&v, &hex!("02 c3 a9"));
+        assert_eq!(Utf8String::parse_uper(&v).unwrap(), s);
+        assert_eq!(String::parse_aper(&v).unwrap(), "é");
+
+        Utf8String::parse_aper(&hex!("01 ff")).unwrap_err();
+    }
+}
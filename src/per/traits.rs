@@ -0,0 +1,86 @@
+use alloc::vec::Vec;
+
+use crate::{Constraints, PerResult};
+
+use super::{PerReader, PerWriter};
+
+/// Common trait for PER encoding functions
+///
+/// The encoding of a value depends on its PER-visible constraints, which are provided by the
+/// caller (usually, the code for the enclosing type). Functions encoding a value at top-level
+/// (like [`to_aper_vec`](ToPer::to_aper_vec)) use no constraints.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::per::{PerWriter, ToPer};
+/// use asn1_rs::Constraints;
+///
+/// // OCTET STRING (SIZE(1..4))
+/// let c = Constraints::NONE.with_size(1, Some(4), false);
+///
+/// let mut writer = PerWriter::uper();
+/// [0x12u8, 0x34][..].per_encode(&mut writer, &c).expect("serialization failed");
+/// assert_eq!(&writer.into_bytes(), &[0x44, 0x8d, 0x00]);
+/// ```
+pub trait ToPer {
+    /// Encode the value to the writer `writer`, using the PER-visible constraints `constraints`
+    fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()>;
+
+    /// Encode the value using the aligned variant (APER) to a newly allocated `Vec<u8>`
+    fn to_aper_vec(&self) -> PerResult<Vec<u8>> {
+        let mut writer = PerWriter::aper();
+        self.per_encode(&mut writer, &Constraints::NONE)?;
+        Ok(writer.into_bytes())
+    }
+
+    /// Encode the value using the unaligned variant (UPER) to a newly allocated `Vec<u8>`
+    fn to_uper_vec(&self) -> PerResult<Vec<u8>> {
+        let mut writer = PerWriter::uper();
+        self.per_encode(&mut writer, &Constraints::NONE)?;
+        Ok(writer.into_bytes())
+    }
+}
+
+impl<T> ToPer for &'_ T
+where
+    T: ToPer + ?Sized,
+{
+    fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()> {
+        (*self).per_encode(writer, constraints)
+    }
+}
+
+/// Base trait for PER decoding functions
+///
+/// See [`ToPer`] for the use of constraints.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::per::{PerParser, PerReader};
+/// use asn1_rs::{Constraints, OctetString};
+///
+/// // OCTET STRING (SIZE(1..4))
+/// let c = Constraints::NONE.with_size(1, Some(4), false);
+///
+/// let mut reader = PerReader::uper(&[0x44, 0x8d, 0x00]);
+/// let s = OctetString::per_decode(&mut reader, &c).expect("parsing failed");
+/// assert_eq!(s.as_ref(), &[0x12, 0x34]);
+/// ```
+pub trait PerParser<'a>: Sized {
+    /// Decode a value from the reader `reader`, using the PER-visible constraints `constraints`
+    fn per_decode(reader: &mut PerReader<'a>, constraints: &Constraints) -> PerResult<Self>;
+
+    /// Decode a value from a complete encoding, using the aligned variant (APER)
+    fn parse_aper(bytes: &'a [u8]) -> PerResult<Self> {
+        let mut reader = PerReader::aper(bytes);
+        Self::per_decode(&mut reader, &Constraints::NONE)
+    }
+
+    /// Decode a value from a complete encoding, using the unaligned variant (UPER)
+    fn parse_uper(bytes: &'a [u8]) -> PerResult<Self> {
+        let mut reader = PerReader::uper(bytes);
+        Self::per_decode(&mut reader, &Constraints::NONE)
+    }
+}
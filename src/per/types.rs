@@ -0,0 +1,361 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::{
+    BitString, Boolean, Constraints, Null, OctetString, Oid, PerError, PerResult, SequenceOf,
+};
+
+use super::{PerParser, PerReader, PerWriter, ToPer, PER_64K};
+
+//---- BOOLEAN (X.691: 12)
+
+impl ToPer for bool {
+    fn per_encode(&self, writer: &mut PerWriter, _constraints: &Constraints) -> PerResult<()> {
+        writer.write_bit(*self);
+        Ok(())
+    }
+}
+
+impl PerParser<'_> for bool {
+    fn per_decode(reader: &mut PerReader, _constraints: &Constraints) -> PerResult<Self> {
+        reader.read_bit()
+    }
+}
+
+impl ToPer for Boolean {
+    fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()> {
+        self.bool().per_encode(writer, constraints)
+    }
+}
+
+impl PerParser<'_> for Boolean {
+    fn per_decode(reader: &mut PerReader, constraints: &Constraints) -> PerResult<Self> {
+        let b = bool::per_decode(reader, constraints)?;
+        Ok(if b { Boolean::TRUE } else { Boolean::FALSE })
+    }
+}
+
+//---- NULL (X.691: 24)
+
+impl ToPer for Null {
+    fn per_encode(&self, _writer: &mut PerWriter, _constraints: &Constraints) -> PerResult<()> {
+        Ok(())
+    }
+}
+
+impl PerParser<'_> for Null {
+    fn per_decode(_reader: &mut PerReader, _constraints: &Constraints) -> PerResult<Self> {
+        Ok(Null::new())
+    }
+}
+
+impl ToPer for () {
+    fn per_encode(&self, _writer: &mut PerWriter, _constraints: &Constraints) -> PerResult<()> {
+        Ok(())
+    }
+}
+
+impl PerParser<'_> for () {
+    fn per_decode(_reader: &mut PerReader, _constraints: &Constraints) -> PerResult<Self> {
+        Ok(())
+    }
+}
+
+//---- BIT STRING (X.691: 16)
+
+impl ToPer for BitString {
+    fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()> {
+        let bits = self.as_bitslice();
+        let size = writer.write_size_extension(bits.len(), &constraints.size)?;
+        let write_bits = |w: &mut PerWriter, start: usize, end: usize| {
+            for bit in bits[start..end].iter() {
+                w.write_bit(*bit);
+            }
+            Ok(())
+        };
+        match size.fixed() {
+            Some(n) if n <= 16 => write_bits(writer, 0, n),
+            Some(n) if n < PER_64K => {
+                writer.align();
+                write_bits(writer, 0, n)
+            }
+            _ => writer.write_length_with(bits.len(), &size, |w, start, end| {
+                w.align();
+                write_bits(w, start, end)
+            }),
+        }
+    }
+}
+
+impl PerParser<'_> for BitString {
+    fn per_decode(reader: &mut PerReader, constraints: &Constraints) -> PerResult<Self> {
+        let size = reader.read_size_extension(&constraints.size)?;
+        let (bytes, nbits) = match size.fixed() {
+            Some(n) if n <= 16 => (reader.read_bits_to_vec(n)?, n),
+            Some(n) if n < PER_64K => {
+                reader.align();
+                (reader.read_bits_to_vec(n)?, n)
+            }
+            _ => {
+                // fragments always contain a multiple of 8 bits, so octets can be appended
+                let mut bytes = Vec::new();
+                let nbits = reader.read_length_with(&size, |r, count| {
+                    r.align();
+                    bytes.extend_from_slice(&r.read_bits_to_vec(count)?);
+                    Ok(())
+                })?;
+                (bytes, nbits)
+            }
+        };
+        if nbits == 0 {
+            return Ok(BitString::default());
+        }
+        let unused_bits = (8 * bytes.len() - nbits) as u8;
+        Ok(BitString::new(unused_bits, &bytes))
+    }
+}
+
+//---- OCTET STRING (X.691: 17)
+
+impl ToPer for [u8] {
+    fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()> {
+        let size = writer.write_size_extension(self.len(), &constraints.size)?;
+        match size.fixed() {
+            Some(n) if n <= 2 => {
+                writer.write_bytes(self);
+                Ok(())
+            }
+            Some(n) if n < PER_64K => {
+                writer.align();
+                writer.write_bytes(self);
+                Ok(())
+            }
+            _ => writer.write_octets_with_size(self, &size),
+        }
+    }
+}
+
+impl ToPer for OctetString<'_> {
+    fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()> {
+        self.as_ref().per_encode(writer, constraints)
+    }
+}
+
+impl<'a> PerParser<'a> for OctetString<'a> {
+    fn per_decode(reader: &mut PerReader<'a>, constraints: &Constraints) -> PerResult<Self> {
+        let size = reader.read_size_extension(&constraints.size)?;
+        let data = match size.fixed() {
+            Some(n) if n <= 2 => reader.read_bytes(n)?,
+            Some(n) if n < PER_64K => {
+                reader.align();
+                reader.read_bytes(n)?
+            }
+            _ => reader.read_octets_with_size(&size)?,
+        };
+        Ok(OctetString::from(data))
+    }
+}
+
+//---- OBJECT IDENTIFIER (X.691: 25)
+
+impl ToPer for Oid<'_> {
+    fn per_encode(&self, writer: &mut PerWriter, _constraints: &Constraints) -> PerResult<()> {
+        writer.write_octets_with_length(self.as_bytes())
+    }
+}
+
+impl<'a> PerParser<'a> for Oid<'a> {
+    fn per_decode(reader: &mut PerReader<'a>, _constraints: &Constraints) -> PerResult<Self> {
+        let data: Cow<'a, [u8]> = reader.read_octets_with_length()?;
+        // the last subidentifier must be terminated
+        match data.last() {
+            Some(b) if b & 0x80 == 0 => Ok(Oid::new(data)),
+            _ => Err(PerError::InvalidEncoding),
+        }
+    }
+}
+
+//---- OPTIONAL
+
+/// The presence of optional values is encoded by the enclosing type (for ex. in the preamble of
+/// a `SEQUENCE`), so encoding `None` writes nothing.
+impl<T> ToPer for Option<T>
+where
+    T: ToPer,
+{
+    fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()> {
+        match self {
+            Some(t) => t.per_encode(writer, constraints),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The presence of optional values is encoded by the enclosing type, so this function always
+/// decodes a value and returns `Some`. The caller must check presence before calling it.
+impl<'a, T> PerParser<'a> for Option<T>
+where
+    T: PerParser<'a>,
+{
+    fn per_decode(reader: &mut PerReader<'a>, constraints: &Constraints) -> PerResult<Self> {
+        T::per_decode(reader, constraints).map(Some)
+    }
+}
+
+//---- SEQUENCE OF (X.691: 20)
+
+/// The size constraint applies to the number of items. Items are encoded with no constraints.
+impl<T> ToPer for Vec<T>
+where
+    T: ToPer,
+{
+    fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()> {
+        let size = writer.write_size_extension(self.len(), &constraints.size)?;
+        writer.write_length_with(self.len(), &size, |w, start, end| {
+            for item in &self[start..end] {
+                item.per_encode(w, &Constraints::NONE)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl<'a, T> PerParser<'a> for Vec<T>
+where
+    T: PerParser<'a>,
+{
+    fn per_decode(reader: &mut PerReader<'a>, constraints: &Constraints) -> PerResult<Self> {
+        let size = reader.read_size_extension(&constraints.size)?;
+        let mut items = Vec::new();
+        reader.read_length_with(&size, |r, count| {
+            for _ in 0..count {
+                items.push(T::per_decode(r, &Constraints::NONE)?);
+            }
+            Ok(())
+        })?;
+        Ok(items)
+    }
+}
+
+impl<T> ToPer for SequenceOf<T>
+where
+    T: ToPer,
+{
+    fn per_encode(&self, writer: &mut PerWriter, constraints: &Constraints) -> PerResult<()> {
+        self.items.per_encode(writer, constraints)
+    }
+}
+
+impl<'a, T> PerParser<'a> for SequenceOf<T>
+where
+    T: PerParser<'a>,
+{
+    fn per_decode(reader: &mut PerReader<'a>, constraints: &Constraints) -> PerResult<Self> {
+        Vec::per_decode(reader, constraints).map(SequenceOf::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use hex_literal::hex;
+
+    use crate::per::{PerParser, PerReader, PerWriter, ToPer};
+    use crate::{oid, BitString, Constraints, OctetString, Oid, SequenceOf};
+
+    #[test]
+    fn per_octetstring() {
+        // unconstrained
+        let v = hex!("01 02 03")[..].to_aper_vec().unwrap();
+        assert_eq!(&v, &hex!("03 01 02 03"));
+        let s = OctetString::parse_aper(&v).unwrap();
+        assert_eq!(s.as_ref(), &hex!("01 02 03"));
+
+        // fixed size, at most 2 octets: not aligned
+        let c = Constraints::NONE.with_size(2, Some(2), false);
+        let mut w = PerWriter::aper();
+        w.write_bit(true);
+        hex!("ff 00")[..].per_encode(&mut w, &c).unwrap();
+        let v = w.into_bytes();
+        assert_eq!(&v, &hex!("ff 80 00"));
+        let mut r = PerReader::aper(&v);
+        assert!(r.read_bit().unwrap());
+        assert_eq!(
+            OctetString::per_decode(&mut r, &c).unwrap().as_ref(),
+            &hex!("ff 00")
+        );
+
+        // fixed size, aligned
+        let c = Constraints::NONE.with_size(3, Some(3), false);
+        let mut w = PerWriter::aper();
+        w.write_bit(true);
+        hex!("01 02 03")[..].per_encode(&mut w, &c).unwrap();
+        assert_eq!(&w.into_bytes(), &hex!("80 01 02 03"));
+
+        // fragmented
+        let data = vec![0xa5u8; 20000];
+        let v = data[..].to_uper_vec().unwrap();
+        assert_eq!(v.len(), 20000 + 3);
+        assert_eq!(&v[..2], &hex!("c1 a5"));
+        let s = OctetString::parse_uper(&v).unwrap();
+        assert_eq!(s.as_ref(), &data[..]);
+    }
+
+    #[test]
+    fn per_bitstring() {
+        let b = BitString::new(4, &hex!("a5 f0"));
+        let v = b.to_uper_vec().unwrap();
+        assert_eq!(&v, &hex!("0c a5 f0"));
+        assert_eq!(BitString::parse_uper(&v).unwrap(), b);
+
+        // fixed size, at most 16 bits
+        let c = Constraints::NONE.with_size(4, Some(4), false);
+        let b = BitString::new(4, &hex!("a0"));
+        let mut w = PerWriter::aper();
+        w.write_bit(true);
+        b.per_encode(&mut w, &c).unwrap();
+        let v = w.into_bytes();
+        assert_eq!(&v, &hex!("d0"));
+        let mut r = PerReader::aper(&v);
+        assert!(r.read_bit().unwrap());
+        assert_eq!(BitString::per_decode(&mut r, &c).unwrap(), b);
+
+        let v = BitString::default().to_aper_vec().unwrap();
+        assert_eq!(&v, &hex!("00"));
+        assert!(BitString::parse_aper(&v).unwrap().is_empty());
+    }
+
+    #[test]
+    fn per_oid_and_options() {
+        let oid = oid!(1.2.840 .113549);
+        let v = oid.to_aper_vec().unwrap();
+        assert_eq!(&v, &hex!("06 2a 86 48 86 f7 0d"));
+        assert_eq!(Oid::parse_aper(&v).unwrap(), oid);
+        Oid::parse_aper(&hex!("01 86")).unwrap_err();
+
+        let v = Some(true).to_uper_vec().unwrap();
+        assert_eq!(&v, &hex!("80"));
+        assert_eq!(Option::<bool>::parse_uper(&v).unwrap(), Some(true));
+    }
+
+    #[test]
+    fn per_sequence_of() {
+        let c = Constraints::NONE
+            .with_size(1, Some(4), false)
+            .with_value(Some(0), Some(7), false);
+        let items = SequenceOf::new(vec![1u8, 2, 3]);
+        let mut w = PerWriter::uper();
+        items.per_encode(&mut w, &c).unwrap();
+        let v = w.into_bytes();
+        // items are not constrained
+        assert_eq!(&v, &hex!("80 40 40 40 80 40 c0"));
+        let mut r = PerReader::uper(&v);
+        let res = SequenceOf::<u8>::per_decode(&mut r, &c).unwrap();
+        assert_eq!(res, items);
+
+        let mut w = PerWriter::uper();
+        Vec::<u8>::new().per_encode(&mut w, &c).unwrap_err();
+    }
+}
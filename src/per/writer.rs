@@ -0,0 +1,449 @@
+use alloc::vec::Vec;
+
+use crate::{PerError, PerResult, SizeConstraint};
+
+use super::{num_bits, num_octets, ToPer, PER_64K, PER_FRAGMENT_SIZE};
+
+/// Bit-level writer for PER encodings
+///
+/// The writer holds the variant (aligned or unaligned) of the encoding. Procedures defined in
+/// [[X.691]] section 11 are provided as methods, and are used to implement [`ToPer`].
+///
+/// [X.691]: http://www.itu.int/rec/T-REC-X.691/en "Recommendation ITU-T X.691"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerWriter {
+    data: Vec<u8>,
+    bit_len: usize,
+    aligned: bool,
+}
+
+impl PerWriter {
+    /// Build a new writer. If `aligned` is `true`, the aligned variant (APER) is used.
+    pub const fn new(aligned: bool) -> Self {
+        PerWriter {
+            data: Vec::new(),
+            bit_len: 0,
+            aligned,
+        }
+    }
+
+    /// Build a new writer for the aligned variant (APER)
+    #[inline]
+    pub const fn aper() -> Self {
+        Self::new(true)
+    }
+
+    /// Build a new writer for the unaligned variant (UPER)
+    #[inline]
+    pub const fn uper() -> Self {
+        Self::new(false)
+    }
+
+    /// Returns `true` if the writer uses the aligned variant
+    #[inline]
+    pub const fn is_aligned(&self) -> bool {
+        self.aligned
+    }
+
+    /// Returns the number of bits written
+    #[inline]
+    pub const fn bit_len(&self) -> usize {
+        self.bit_len
+    }
+
+    /// Returns the complete encoding, padded with zero bits to an octet boundary
+    ///
+    /// If no bit was written, the complete encoding is a single zero octet (X.691: 11.1.3).
+    pub fn into_bytes(self) -> Vec<u8> {
+        let mut data = self.data;
+        if data.is_empty() {
+            data.push(0);
+        }
+        data
+    }
+
+    /// Write a single bit
+    pub fn write_bit(&mut self, bit: bool) {
+        let offset = self.bit_len % 8;
+        if offset == 0 {
+            self.data.push(0);
+        }
+        if bit {
+            if let Some(last) = self.data.last_mut() {
+                *last |= 0x80 >> offset;
+            }
+        }
+        self.bit_len += 1;
+    }
+
+    /// Write the `nbits` lowest bits of `value`, most significant bit first
+    pub fn write_bits(&mut self, value: u128, nbits: usize) {
+        debug_assert!(nbits <= 128);
+        for i in (0..nbits).rev() {
+            self.write_bit((value >> i) & 1 == 1);
+        }
+    }
+
+    /// Write octets. This function does not align the writer.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        if self.bit_len % 8 == 0 {
+            self.data.extend_from_slice(bytes);
+            self.bit_len += 8 * bytes.len();
+        } else {
+            for &b in bytes {
+                self.write_bits(u128::from(b), 8);
+            }
+        }
+    }
+
+    /// Add padding bits up to the next octet boundary (aligned variant only)
+    pub fn align(&mut self) {
+        if self.aligned {
+            self.bit_len = 8 * self.data.len();
+        }
+    }
+
+    /// Encode a constrained whole number `value`, in range `lower..=upper` (X.691: 11.5.7)
+    pub fn write_constrained_whole_number(
+        &mut self,
+        value: i128,
+        lower: i128,
+        upper: i128,
+    ) -> PerResult<()> {
+        if value < lower || value > upper {
+            return Err(PerError::ConstraintViolation);
+        }
+        let n = value.wrapping_sub(lower) as u128;
+        let max = upper.wrapping_sub(lower) as u128;
+        if max == 0 {
+            // range is 1, nothing to encode
+            return Ok(());
+        }
+        if !self.aligned {
+            self.write_bits(n, num_bits(max));
+            return Ok(());
+        }
+        match max + 1 {
+            // bit-field case
+            0..=255 => self.write_bits(n, num_bits(max)),
+            // one-octet case
+            256 => {
+                self.align();
+                self.write_bits(n, 8);
+            }
+            // two-octet case
+            257..=65536 => {
+                self.align();
+                self.write_bits(n, 16);
+            }
+            // indefinite length case
+            _ => {
+                let len = num_octets(n);
+                self.write_constrained_whole_number(len as i128, 1, num_octets(max) as i128)?;
+                self.align();
+                self.write_bits(n, 8 * len);
+            }
+        }
+        Ok(())
+    }
+
+    /// Encode a semi-constrained whole number `value`, with lower bound `lower` (X.691: 11.7)
+    pub fn write_semi_constrained_whole_number(
+        &mut self,
+        value: i128,
+        lower: i128,
+    ) -> PerResult<()> {
+        if value < lower {
+            return Err(PerError::ConstraintViolation);
+        }
+        let n = value.wrapping_sub(lower) as u128;
+        let bytes = n.to_be_bytes();
+        self.write_octets_with_length(&bytes[bytes.len() - num_octets(n)..])
+    }
+
+    /// Encode an unconstrained whole number `value` (X.691: 11.8)
+    pub fn write_unconstrained_whole_number(&mut self, value: i128) -> PerResult<()> {
+        let bytes = value.to_be_bytes();
+        // remove redundant leading octets of the 2's-complement representation
+        let mut idx = 0;
+        while idx < bytes.len() - 1 {
+            let next_high_bit = bytes[idx + 1] & 0x80 != 0;
+            match bytes[idx] {
+                0x00 if !next_high_bit => idx += 1,
+                0xff if next_high_bit => idx += 1,
+                _ => break,
+            }
+        }
+        self.write_octets_with_length(&bytes[idx..])
+    }
+
+    /// Encode a normally small non-negative whole number (X.691: 11.6)
+    pub fn write_normally_small_number(&mut self, value: u64) -> PerResult<()> {
+        if value < 64 {
+            self.write_bit(false);
+            self.write_bits(u128::from(value), 6);
+            Ok(())
+        } else {
+            self.write_bit(true);
+            self.write_semi_constrained_whole_number(i128::from(value), 0)
+        }
+    }
+
+    /// Encode a normally small length `len` (X.691: 11.9.3.4)
+    ///
+    /// `len` must be greater than 0.
+    pub fn write_normally_small_length(&mut self, len: usize) -> PerResult<()> {
+        if len == 0 {
+            return Err(PerError::ConstraintViolation);
+        }
+        if len <= 64 {
+            self.write_bit(false);
+            self.write_bits((len - 1) as u128, 6);
+            Ok(())
+        } else {
+            self.write_bit(true);
+            self.write_length_with(len, &SizeConstraint::NONE, |_, _, _| Ok(()))
+        }
+    }
+
+    /// Write the extension bit for a size constraint, if it is extensible
+    ///
+    /// Returns the constraint to use for the encoding of the length: the root of `size` if `count`
+    /// is in the root, or no constraint otherwise.
+    ///
+    /// If the constraint is not extensible and `count` is outside the root, an error is returned.
+    pub fn write_size_extension(
+        &mut self,
+        count: usize,
+        size: &SizeConstraint,
+    ) -> PerResult<SizeConstraint> {
+        let in_root = size.contains(count);
+        if size.extensible {
+            self.write_bit(!in_root);
+        } else if !in_root {
+            return Err(PerError::ConstraintViolation);
+        }
+        if in_root {
+            Ok(SizeConstraint::new(size.lower, size.upper, false))
+        } else {
+            Ok(SizeConstraint::NONE)
+        }
+    }
+
+    /// Encode a length determinant for `count` items (X.691: 11.9), and call `f` to write the items
+    ///
+    /// `f` receives the writer, and the range `start..end` of the items to write. If `count` is
+    /// large, the encoding is fragmented and `f` is called once per fragment.
+    ///
+    /// The extension marker of `size` is ignored, see [`Self::write_size_extension`].
+    pub fn write_length_with<F>(
+        &mut self,
+        count: usize,
+        size: &SizeConstraint,
+        mut f: F,
+    ) -> PerResult<()>
+    where
+        F: FnMut(&mut Self, usize, usize) -> PerResult<()>,
+    {
+        if let Some(upper) = size.upper {
+            if upper < PER_64K {
+                self.write_constrained_whole_number(
+                    count as i128,
+                    size.lower as i128,
+                    upper as i128,
+                )?;
+                return f(self, 0, count);
+            }
+        }
+        // unconstrained length, fragmented if required
+        let mut start = 0;
+        loop {
+            self.align();
+            let remaining = count - start;
+            if remaining < 128 {
+                self.write_bits(remaining as u128, 8);
+                return f(self, start, count);
+            } else if remaining < PER_FRAGMENT_SIZE {
+                self.write_bits(0x8000 | remaining as u128, 16);
+                return f(self, start, count);
+            }
+            let m = (remaining / PER_FRAGMENT_SIZE).min(4);
+            self.write_bits(0xc0 | m as u128, 8);
+            f(self, start, start + m * PER_FRAGMENT_SIZE)?;
+            start += m * PER_FRAGMENT_SIZE;
+        }
+    }
+
+    /// Encode octets, prefixed with an unconstrained length determinant
+    ///
+    /// In the aligned variant, octets are aligned.
+    pub fn write_octets_with_length(&mut self, bytes: &[u8]) -> PerResult<()> {
+        self.write_octets_with_size(bytes, &SizeConstraint::NONE)
+    }
+
+    /// Encode octets, prefixed with a length determinant using the size constraint `size`
+    ///
+    /// In the aligned variant, octets are aligned.
+    pub fn write_octets_with_size(&mut self, bytes: &[u8], size: &SizeConstraint) -> PerResult<()> {
+        self.write_length_with(bytes.len(), size, |w, start, end| {
+            w.align();
+            w.write_bytes(&bytes[start..end]);
+            Ok(())
+        })
+    }
+
+    /// Encode `value` as an open type field (X.691: 11.2)
+    ///
+    /// The value is encoded as a complete encoding, using the same variant as `self`.
+    pub fn write_open_type<T>(
+        &mut self,
+        value: &T,
+        constraints: &crate::Constraints,
+    ) -> PerResult<()>
+    where
+        T: ToPer + ?Sized,
+    {
+        let mut writer = PerWriter::new(self.aligned);
+        value.per_encode(&mut writer, constraints)?;
+        self.write_octets_with_length(&writer.into_bytes())
+    }
+
+    /// Encode the index of an `ENUMERATED` value (X.691: 14)
+    ///
+    /// `root_count` is the number of items in the root. If `index` is greater or equal, the value is
+    /// encoded as an extension (only if `extensible` is `true`).
+    pub fn write_enumerated(
+        &mut self,
+        index: usize,
+        root_count: usize,
+        extensible: bool,
+    ) -> PerResult<()> {
+        let in_root = index < root_count;
+        if extensible {
+            self.write_bit(!in_root);
+        }
+        if in_root {
+            self.write_constrained_whole_number(index as i128, 0, root_count as i128 - 1)
+        } else if extensible {
+            self.write_normally_small_number((index - root_count) as u64)
+        } else {
+            Err(PerError::ConstraintViolation)
+        }
+    }
+
+    /// Encode the index of a `CHOICE` root alternative (X.691: 23)
+    ///
+    /// `index` is the position of the alternative in the canonical order of tags.
+    /// The value of the alternative must be written after this function.
+    pub fn write_choice_index(
+        &mut self,
+        index: usize,
+        root_count: usize,
+        extensible: bool,
+    ) -> PerResult<()> {
+        if index >= root_count {
+            return Err(PerError::ConstraintViolation);
+        }
+        if extensible {
+            self.write_bit(false);
+        }
+        self.write_constrained_whole_number(index as i128, 0, root_count as i128 - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use hex_literal::hex;
+
+    use super::PerWriter;
+    use crate::SizeConstraint;
+
+    #[test]
+    fn per_writer_bits() {
+        let mut w = PerWriter::uper();
+        w.write_bit(true);
+        w.write_bits(0b0101, 4);
+        w.write_bytes(&[0xff]);
+        assert_eq!(w.bit_len(), 13);
+        assert_eq!(&w.into_bytes(), &hex!("af f8"));
+
+        // aligned variant adds padding
+        let mut w = PerWriter::aper();
+        w.write_bit(true);
+        w.align();
+        w.write_bytes(&[0xff]);
+        assert_eq!(&w.into_bytes(), &hex!("80 ff"));
+
+        // empty encoding
+        assert_eq!(&PerWriter::aper().into_bytes(), &hex!("00"));
+    }
+
+    #[test]
+    fn per_writer_whole_numbers() {
+        // X.691 examples: constrained whole numbers
+        let mut w = PerWriter::aper();
+        w.write_constrained_whole_number(3, 0, 7).unwrap();
+        w.write_constrained_whole_number(0x1234, 0, 65535).unwrap();
+        assert_eq!(&w.into_bytes(), &hex!("60 12 34"));
+
+        let mut w = PerWriter::uper();
+        w.write_constrained_whole_number(3, 0, 7).unwrap();
+        w.write_constrained_whole_number(0x1234, 0, 65535).unwrap();
+        assert_eq!(&w.into_bytes(), &hex!("62 46 80"));
+
+        // indefinite length case
+        let mut w = PerWriter::aper();
+        w.write_constrained_whole_number(256, 0, 1 << 24).unwrap();
+        assert_eq!(&w.into_bytes(), &hex!("40 01 00"));
+
+        // out of range
+        let mut w = PerWriter::aper();
+        w.write_constrained_whole_number(8, 0, 7).unwrap_err();
+
+        let mut w = PerWriter::aper();
+        w.write_unconstrained_whole_number(-1).unwrap();
+        w.write_unconstrained_whole_number(128).unwrap();
+        w.write_semi_constrained_whole_number(256, 0).unwrap();
+        assert_eq!(&w.into_bytes(), &hex!("01 ff 02 00 80 02 01 00"));
+
+        let mut w = PerWriter::uper();
+        w.write_normally_small_number(5).unwrap();
+        assert_eq!(&w.into_bytes(), &hex!("0a"));
+    }
+
+    #[test]
+    fn per_writer_length() {
+        // constrained length
+        let mut w = PerWriter::uper();
+        let size = SizeConstraint::new(1, Some(8), false);
+        w.write_length_with(3, &size, |_, _, _| Ok(())).unwrap();
+        assert_eq!(&w.into_bytes(), &hex!("40"));
+
+        // unconstrained length, 1 and 2 octets
+        let mut w = PerWriter::aper();
+        w.write_length_with(127, &SizeConstraint::NONE, |_, _, _| Ok(()))
+            .unwrap();
+        w.write_length_with(128, &SizeConstraint::NONE, |_, _, _| Ok(()))
+            .unwrap();
+        assert_eq!(&w.into_bytes(), &hex!("7f 80 80"));
+
+        // fragmented length
+        let data = vec![0x55u8; 16384 + 10];
+        let mut w = PerWriter::aper();
+        w.write_octets_with_length(&data).unwrap();
+        let v = w.into_bytes();
+        assert_eq!(v[0], 0xc1);
+        assert_eq!(v[16385], 0x0a);
+        assert_eq!(v.len(), 16384 + 10 + 2);
+
+        // exactly one fragment: a final zero length is added
+        let data = vec![0x55u8; 16384];
+        let mut w = PerWriter::aper();
+        w.write_octets_with_length(&data).unwrap();
+        let v = w.into_bytes();
+        assert_eq!(v.len(), 16384 + 2);
+        assert_eq!(v[16385], 0x00);
+    }
+}
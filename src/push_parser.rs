@@ -0,0 +1,364 @@
+use alloc::vec::Vec;
+
+use crate::ber::MAX_RECURSION;
+use crate::header::raw_header_len;
+use crate::{Any, BerParser, DerParser, Error, Header, Input, Length, Result};
+
+/// Event returned by [`PushParser::next_event`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PushEvent<'a> {
+    /// Start of a constructed object
+    ///
+    /// Events for sub-objects follow, until the matching [`PushEvent::End`].
+    Constructed(Header<'a>),
+    /// A complete primitive object
+    Primitive(Any<'a>),
+    /// End of the innermost constructed object
+    ///
+    /// For objects with indefinite length, this is emitted when End-Of-Content is read.
+    End,
+}
+
+/// Constructed object being parsed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Frame {
+    /// Position of the end of the object, if length is definite
+    end: Option<u64>,
+    /// Position of the end of the innermost object with a definite length
+    limit: Option<u64>,
+}
+
+/// Resumable (sans-IO) BER/DER push parser
+///
+/// Data is provided in chunks of any size using [`PushParser::feed`], and parsed into a sequence
+/// of events using [`PushParser::next_event`]: start of constructed objects, complete primitive
+/// objects, and end of constructed objects. When more data is required, `next_event` returns
+/// `Ok(None)`, and parsing resumes from the same state after the next call to `feed`.
+///
+/// Contrary to nom parsers, which return `Incomplete` and parse the input again when more data
+/// arrives, each header is parsed only once: parsing a large message received in small chunks
+/// is linear. The parser does not perform any I/O.
+///
+/// The stack of constructed objects is kept by the parser. Lengths of sub-objects are checked
+/// against the length of their parent objects. Only the unparsed data is kept: the content of
+/// primitive objects is buffered until complete, so memory usage depends on the size of the
+/// largest primitive object, not on the size of the message.
+///
+/// Several top-level objects can be parsed one after the other. Use
+/// [`PushParser::is_complete`] after the last chunk to check that no object is truncated.
+///
+/// Events borrow the parser, so they must be dropped before calling `feed` again.
+///
+/// If an error occurs, the parser cannot be used anymore: calls to `next_event` will return
+/// the same error.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{PushEvent, PushParser, Tag};
+///
+/// let mut parser = PushParser::ber();
+///
+/// // a SEQUENCE with indefinite length, received in two chunks
+/// parser.feed(&[0x30, 0x80, 0x02, 0x01]);
+/// assert!(matches!(parser.next_event(), Ok(Some(PushEvent::Constructed(h))) if h.tag() == Tag::Sequence));
+/// assert_eq!(parser.next_event(), Ok(None));
+///
+/// parser.feed(&[0x05, 0x00, 0x00]);
+/// let Ok(Some(PushEvent::Primitive(any))) = parser.next_event() else {
+///     panic!("expected a primitive object");
+/// };
+/// assert_eq!(any.as_u32(), Ok(5));
+/// assert_eq!(parser.depth(), 1);
+/// assert_eq!(parser.next_event(), Ok(Some(PushEvent::End)));
+/// assert!(parser.is_complete());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PushParser {
+    /// Received data (unparsed data starts at `state.pos`)
+    buffer: Vec<u8>,
+    state: ParserState,
+    error: Option<Error>,
+}
+
+#[derive(Debug, Clone)]
+struct ParserState {
+    der: bool,
+    max_depth: usize,
+    /// Offset of unparsed data in buffer
+    pos: usize,
+    /// Position of unparsed data in the stream
+    position: u64,
+    stack: Vec<Frame>,
+}
+
+impl PushParser {
+    fn new(der: bool) -> Self {
+        PushParser {
+            buffer: Vec::new(),
+            state: ParserState {
+                der,
+                max_depth: MAX_RECURSION,
+                pos: 0,
+                position: 0,
+                stack: Vec::new(),
+            },
+            error: None,
+        }
+    }
+
+    /// Build a new parser for BER data
+    pub fn ber() -> Self {
+        Self::new(false)
+    }
+
+    /// Build a new parser for DER data (indefinite lengths are rejected)
+    pub fn der() -> Self {
+        Self::new(true)
+    }
+
+    /// Set the maximum nesting depth of constructed objects (default: [`MAX_RECURSION`])
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.state.max_depth = max_depth;
+        self
+    }
+
+    /// Provide more data to the parser
+    pub fn feed(&mut self, data: &[u8]) {
+        // drop data already parsed
+        if self.state.pos > 0 {
+            self.buffer.drain(..self.state.pos);
+            self.state.pos = 0;
+        }
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Return the number of octets parsed (position of the next event in the stream)
+    #[inline]
+    pub const fn position(&self) -> u64 {
+        self.state.position
+    }
+
+    /// Return the number of constructed objects not terminated
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.state.stack.len()
+    }
+
+    /// Return the number of octets received, but not parsed yet
+    #[inline]
+    pub fn buffered(&self) -> usize {
+        self.buffer.len() - self.state.pos
+    }
+
+    /// Return `true` if all received data was parsed, and no constructed object is pending
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none() && self.state.stack.is_empty() && self.buffered() == 0
+    }
+
+    /// Parse the next event
+    ///
+    /// Return `Ok(None)` if more data is required.
+    pub fn next_event(&mut self) -> Result<Option<PushEvent<'_>>> {
+        if let Some(e) = &self.error {
+            return Err(e.clone());
+        }
+        let r = self.state.parse_next(&self.buffer);
+        if let Err(e) = &r {
+            self.error = Some(e.clone());
+        }
+        r
+    }
+}
+
+impl ParserState {
+    fn parse_next<'a>(&mut self, buffer: &'a [u8]) -> Result<Option<PushEvent<'a>>> {
+        let top = self.stack.last().copied();
+        if let Some(frame) = top {
+            if frame.end == Some(self.position) {
+                self.stack.pop();
+                return Ok(Some(PushEvent::End));
+            }
+        }
+        let limit = top.and_then(|f| f.limit);
+        if limit == Some(self.position) {
+            // object with indefinite length, not terminated before end of parent object
+            return Err(Error::InvalidLength);
+        }
+        let bytes = &buffer[self.pos..];
+        let Some(header_len) = raw_header_len(bytes) else {
+            return Ok(None);
+        };
+        let header_end = self.position + header_len as u64;
+        if matches!(limit, Some(l) if header_end > l) {
+            return Err(Error::InvalidLength);
+        }
+        // End-Of-Content
+        if matches!(top, Some(f) if f.end.is_none()) && bytes[..header_len] == [0, 0] {
+            self.advance(header_len);
+            self.stack.pop();
+            return Ok(Some(PushEvent::End));
+        }
+        let header = parse_header(self.der, &bytes[..header_len])?;
+        match header.length() {
+            Length::Definite(len) => {
+                let end = header_end
+                    .checked_add(len as u64)
+                    .ok_or(Error::InvalidLength)?;
+                if matches!(limit, Some(l) if end > l) {
+                    return Err(Error::InvalidLength);
+                }
+                if header.is_constructed() {
+                    self.push(Frame {
+                        end: Some(end),
+                        limit: Some(end),
+                    })?;
+                    self.advance(header_len);
+                    Ok(Some(PushEvent::Constructed(header)))
+                } else {
+                    let Some(data) = bytes.get(header_len..header_len + len) else {
+                        return Ok(None);
+                    };
+                    self.advance(header_len + len);
+                    let any = Any::new(header, Input::from_slice(data));
+                    Ok(Some(PushEvent::Primitive(any)))
+                }
+            }
+            Length::Indefinite => {
+                self.push(Frame { end: None, limit })?;
+                self.advance(header_len);
+                Ok(Some(PushEvent::Constructed(header)))
+            }
+        }
+    }
+
+    fn push(&mut self, frame: Frame) -> Result<()> {
+        if self.stack.len() >= self.max_depth {
+            return Err(Error::BerMaxDepth);
+        }
+        self.stack.push(frame);
+        Ok(())
+    }
+
+    fn advance(&mut self, n: usize) {
+        self.pos += n;
+        self.position += n as u64;
+    }
+}
+
+fn parse_header(der: bool, raw: &[u8]) -> Result<Header<'_>> {
+    let input = Input::from_slice(raw);
+    let r = if der {
+        Header::parse_der(input)
+    } else {
+        Header::parse_ber(input)
+    };
+    let (_, header) = r.map_err(Error::from_nom_berr)?;
+    Ok(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use hex_literal::hex;
+
+    use crate::{Error, PushEvent, PushParser};
+
+    /// Feed `data` in chunks of `chunk_size` octets, and return a description of events
+    fn events(
+        mut parser: PushParser,
+        data: &[u8],
+        chunk_size: usize,
+    ) -> Result<Vec<String>, Error> {
+        let mut v = Vec::new();
+        for chunk in data.chunks(chunk_size) {
+            parser.feed(chunk);
+            while let Some(event) = parser.next_event()? {
+                v.push(match event {
+                    PushEvent::Constructed(h) => format!("C{}", h.tag().0),
+                    PushEvent::Primitive(any) => {
+                        format!("P{}:{:02x?}", any.tag().0, any.data.as_bytes2())
+                    }
+                    PushEvent::End => "E".into(),
+                });
+            }
+        }
+        assert!(parser.is_complete());
+        assert_eq!(parser.position(), data.len() as u64);
+        Ok(v)
+    }
+
+    #[test]
+    fn push_parser_chunks() {
+        // definite and indefinite lengths, nested, and two top-level objects
+        let data = hex!("30 0b 02 01 01 30 80 04 02 aa bb 00 00 24 80 24 03 04 01 cc 00 00 05 00");
+        let expected = [
+            "C16",
+            "P2:[01]",
+            "C16",
+            "P4:[aa, bb]",
+            "E",
+            "E",
+            "C4",
+            "C4",
+            "P4:[cc]",
+            "E",
+            "E",
+            "P5:[]",
+        ];
+        for chunk_size in [1, 2, 3, 7, data.len()] {
+            let v = events(PushParser::ber(), &data, chunk_size).expect("parsing failed");
+            assert_eq!(v, expected, "chunk size {}", chunk_size);
+        }
+
+        // large primitive object, long tag
+        let mut data = hex!("9f 81 00 82 01 00").to_vec();
+        data.extend_from_slice(&[0x42; 256]);
+        let v = events(PushParser::der(), &data, 10).expect("parsing failed");
+        assert_eq!(v.len(), 1);
+        assert!(v[0].starts_with("P128:[42, "));
+    }
+
+    #[test]
+    fn push_parser_partial() {
+        let mut parser = PushParser::ber();
+        parser.feed(&hex!("30 80 02"));
+        assert!(matches!(
+            parser.next_event(),
+            Ok(Some(PushEvent::Constructed(_)))
+        ));
+        assert_eq!(parser.next_event(), Ok(None));
+        assert_eq!(parser.depth(), 1);
+        assert_eq!(parser.buffered(), 1);
+        assert!(!parser.is_complete());
+    }
+
+    #[test]
+    fn push_parser_errors() {
+        // DER: indefinite length
+        let r = events(PushParser::der(), &hex!("30 80 00 00"), 1);
+        assert!(matches!(r, Err(Error::DerConstraintFailed(_))));
+        // sub-object larger than parent
+        let r = events(PushParser::ber(), &hex!("30 03 04 02 aa bb"), 1);
+        assert_eq!(r, Err(Error::InvalidLength));
+        // indefinite length not terminated in parent
+        let r = events(PushParser::ber(), &hex!("30 05 30 80 02 01 01 00 00"), 1);
+        assert_eq!(r, Err(Error::InvalidLength));
+        // maximum depth
+        let r = events(
+            PushParser::ber().with_max_depth(2),
+            &hex!("30 80 30 80 30 80 00 00 00 00 00 00"),
+            1,
+        );
+        assert_eq!(r, Err(Error::BerMaxDepth));
+
+        // errors are kept
+        let mut parser = PushParser::ber();
+        parser.feed(&hex!("1f 80 80 80 80 80 80 01 00"));
+        assert!(parser.next_event().is_err());
+        parser.feed(&hex!("05 00"));
+        assert!(parser.next_event().is_err());
+    }
+}
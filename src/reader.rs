@@ -0,0 +1,570 @@
+use alloc::vec::Vec;
+use core::cmp;
+use core::marker::PhantomData;
+use std::io::{self, Read};
+
+use crate::ber::MAX_RECURSION;
+use crate::{
+    ASN1Mode, Any, BerMode, BerParser, DerMode, DerParser, Error, Header, Input, Length, ReadError,
+    ToStatic,
+};
+
+/// Size of the buffer used to read or skip content
+const BUFFER_SIZE: usize = 8192;
+
+/// Encoding of End-Of-Content
+const EOC: [u8; 2] = [0, 0];
+
+/// Owned version of [`Any`], returned by stream readers
+///
+/// Since `Any` can only borrow its content, this type holds a copy of the object content. Use
+/// [`OwnedAny::as_any`] to parse it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OwnedAny {
+    /// The object header
+    pub header: Header<'static>,
+    /// The object contents
+    ///
+    /// Note: if length is indefinite, the terminating End-Of-Content is NOT included
+    pub data: Vec<u8>,
+}
+
+impl OwnedAny {
+    /// Create a new `OwnedAny` from BER/DER header and content
+    #[inline]
+    pub const fn new(header: Header<'static>, data: Vec<u8>) -> Self {
+        OwnedAny { header, data }
+    }
+
+    /// Return a borrowed `Any` object, referencing the content of `self`
+    pub fn as_any(&self) -> Any<'_> {
+        Any::new(self.header.clone(), Input::from_slice(&self.data))
+    }
+}
+
+impl From<&Any<'_>> for OwnedAny {
+    fn from(any: &Any<'_>) -> Self {
+        OwnedAny::new(any.header.to_static(), any.data.as_bytes2().to_vec())
+    }
+}
+
+/// Remaining content of an object, or of one of its sub-objects
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Frame {
+    Definite(usize),
+    Indefinite,
+}
+
+/// Blocking reader of BER/DER objects, from a stream implementing [`Read`]
+///
+/// Contrary to parsers, which require the full input in memory, this reader reads one object
+/// header at a time, so it can be used on very large streams (for ex. archives of logs). After
+/// reading a header, the caller can either:
+/// - read the complete object, using [`AnyReader::read_any`], which returns an [`OwnedAny`]
+/// - read the object content in chunks, using the bounded reader returned by
+///   [`AnyReader::content`] or [`AnyReader::read_object`]. Since this reader also implements
+///   `Read`, it can be used to build another `AnyReader` iterating on sub-objects.
+///
+/// Unread content is skipped when the next header is read.
+///
+/// Content is not parsed, except (for BER) to find the End-Of-Content of objects with an
+/// indefinite length. The End-Of-Content terminating the object is not part of the content.
+///
+/// This type is usually used through the [`BerReader`] and [`DerReader`] aliases. The DER
+/// reader rejects indefinite lengths.
+///
+/// Headers are read octet by octet: if the underlying stream is not buffered (for ex. a
+/// `File`), it should be wrapped in a [`std::io::BufReader`].
+///
+/// `AnyReader` also implements `Iterator`, returning each object as an `OwnedAny`. Calling
+/// `.next()` after an error happened will return `None` (ending iteration).
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{BerReader, Tag};
+/// use hex_literal::hex;
+/// use std::io::Cursor;
+///
+/// // a SEQUENCE with indefinite length, followed by an INTEGER
+/// let data = hex!("30 80 02 01 01 02 01 02 00 00 02 01 03");
+/// let mut reader = BerReader::new(Cursor::new(&data));
+///
+/// // read the SEQUENCE header, and iterate on sub-objects
+/// let (header, content) = reader.read_object()?.expect("no object");
+/// assert_eq!(header.tag(), Tag::Sequence);
+/// for obj in BerReader::new(content) {
+///     assert_eq!(obj?.header.tag(), Tag::Integer);
+/// }
+///
+/// // read the next object
+/// let obj = reader.read_any()?.expect("no object");
+/// assert_eq!(obj.as_any().as_u32(), Ok(3));
+/// assert!(reader.read_any()?.is_none());
+/// # Ok::<(), asn1_rs::ReadError>(())
+/// ```
+#[derive(Debug)]
+pub struct AnyReader<R, Mode>
+where
+    Mode: ASN1Mode,
+{
+    reader: R,
+    der: bool,
+    max_depth: usize,
+    max_object_size: usize,
+    position: u64,
+    /// Remaining content of the current object (and of sub-objects, if length is indefinite)
+    frames: Vec<Frame>,
+    /// Octets read from stream, not yet returned as content
+    pending: Vec<u8>,
+    pending_pos: usize,
+    has_error: bool,
+    _mode: PhantomData<fn() -> Mode>,
+}
+
+/// Blocking reader of BER objects (see [`AnyReader`])
+pub type BerReader<R> = AnyReader<R, BerMode>;
+
+/// Blocking reader of DER objects (see [`AnyReader`])
+pub type DerReader<R> = AnyReader<R, DerMode>;
+
+impl<R: Read> AnyReader<R, BerMode> {
+    /// Build a new reader of BER objects
+    pub fn new(reader: R) -> Self {
+        Self::with_rules(reader, false)
+    }
+}
+
+impl<R: Read> AnyReader<R, DerMode> {
+    /// Build a new reader of DER objects
+    pub fn new(reader: R) -> Self {
+        Self::with_rules(reader, true)
+    }
+}
+
+impl<R, Mode> AnyReader<R, Mode>
+where
+    R: Read,
+    Mode: ASN1Mode,
+{
+    fn with_rules(reader: R, der: bool) -> Self {
+        AnyReader {
+            reader,
+            der,
+            max_depth: MAX_RECURSION,
+            max_object_size: usize::MAX,
+            position: 0,
+            frames: Vec::new(),
+            pending: Vec::new(),
+            pending_pos: 0,
+            has_error: false,
+            _mode: PhantomData,
+        }
+    }
+
+    /// Set the maximum nesting depth of objects with indefinite length (default:
+    /// [`MAX_RECURSION`])
+    pub fn with_max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    /// Set the maximum size of objects returned by [`AnyReader::read_any`] (default: no limit)
+    ///
+    /// This does not apply to content read using [`AnyReader::content`].
+    pub fn with_max_object_size(self, max_object_size: usize) -> Self {
+        Self {
+            max_object_size,
+            ..self
+        }
+    }
+
+    /// Return the number of octets read from the underlying stream
+    #[inline]
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Return a reference to the underlying stream
+    #[inline]
+    pub const fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Return the underlying stream
+    ///
+    /// Unread content of the current object is not skipped.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Read the header of the next object, skipping unread content of the current object
+    ///
+    /// Return `Ok(None)` if the end of stream is reached before the next object.
+    pub fn read_header(&mut self) -> Result<Option<Header<'static>>, ReadError> {
+        self.skip_content()?;
+        let mut raw = Vec::new();
+        if !self.read_raw_header(&mut raw)? {
+            return Ok(None);
+        }
+        let header = self.parse_raw_header(&raw)?;
+        self.frames.push(match header.length() {
+            Length::Definite(l) => Frame::Definite(l),
+            Length::Indefinite => Frame::Indefinite,
+        });
+        Ok(Some(header))
+    }
+
+    /// Return a reader for the content of the current object (from the last header read)
+    pub fn content(&mut self) -> ContentReader<'_, R, Mode> {
+        ContentReader { reader: self }
+    }
+
+    /// Read the header of the next object, and return it with a reader for its content
+    ///
+    /// Return `Ok(None)` if the end of stream is reached before the next object.
+    #[allow(clippy::type_complexity)]
+    pub fn read_object(
+        &mut self,
+    ) -> Result<Option<(Header<'static>, ContentReader<'_, R, Mode>)>, ReadError> {
+        match self.read_header()? {
+            Some(header) => Ok(Some((header, self.content()))),
+            None => Ok(None),
+        }
+    }
+
+    /// Read the next complete object
+    ///
+    /// Return `Ok(None)` if the end of stream is reached before the next object.
+    pub fn read_any(&mut self) -> Result<Option<OwnedAny>, ReadError> {
+        let Some(header) = self.read_header()? else {
+            return Ok(None);
+        };
+        let capacity = match header.length() {
+            Length::Definite(l) if l > self.max_object_size => {
+                return Err(ReadError::ObjectTooLarge)
+            }
+            // do not trust length for allocation
+            Length::Definite(l) => cmp::min(l, BUFFER_SIZE),
+            Length::Indefinite => BUFFER_SIZE,
+        };
+        let mut data = Vec::with_capacity(capacity);
+        let mut buf = [0u8; BUFFER_SIZE];
+        loop {
+            let n = self.read_content(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            if data.len() + n > self.max_object_size {
+                return Err(ReadError::ObjectTooLarge);
+            }
+            data.extend_from_slice(&buf[..n]);
+        }
+        Ok(Some(OwnedAny::new(header, data)))
+    }
+
+    fn skip_content(&mut self) -> Result<(), ReadError> {
+        let mut buf = [0u8; BUFFER_SIZE];
+        while self.read_content(&mut buf)? > 0 {}
+        Ok(())
+    }
+
+    /// Read content of the current object
+    fn read_content(&mut self, buf: &mut [u8]) -> Result<usize, ReadError> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            if self.pending_pos < self.pending.len() {
+                let n = cmp::min(buf.len(), self.pending.len() - self.pending_pos);
+                buf[..n].copy_from_slice(&self.pending[self.pending_pos..self.pending_pos + n]);
+                self.pending_pos += n;
+                return Ok(n);
+            }
+            match self.frames.last_mut() {
+                None => return Ok(0),
+                Some(Frame::Definite(0)) => {
+                    self.frames.pop();
+                }
+                Some(Frame::Definite(remaining)) => {
+                    let len = cmp::min(*remaining, buf.len());
+                    let n = read_some(&mut self.reader, &mut buf[..len])?;
+                    *remaining -= n;
+                    self.position += n as u64;
+                    return Ok(n);
+                }
+                Some(Frame::Indefinite) => {
+                    // parse header of sub-object, to find End-Of-Content
+                    let mut raw = core::mem::take(&mut self.pending);
+                    if !self.read_raw_header(&mut raw)? {
+                        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+                    }
+                    if raw == EOC {
+                        self.frames.pop();
+                        if self.frames.is_empty() {
+                            // End-Of-Content of the current object is not part of content
+                            raw.clear();
+                        }
+                    } else {
+                        let header = self.parse_raw_header(&raw)?;
+                        if self.frames.len() >= self.max_depth {
+                            return Err(Error::BerMaxDepth.into());
+                        }
+                        self.frames.push(match header.length() {
+                            Length::Definite(l) => Frame::Definite(l),
+                            Length::Indefinite => Frame::Indefinite,
+                        });
+                    }
+                    self.pending = raw;
+                    self.pending_pos = 0;
+                }
+            }
+        }
+    }
+
+    /// Read the octets of a header (identifier and length)
+    ///
+    /// Return `false` if the end of stream is reached before the first octet.
+    fn read_raw_header(&mut self, raw: &mut Vec<u8>) -> Result<bool, ReadError> {
+        raw.clear();
+        let Some(b0) = self.read_u8()? else {
+            return Ok(false);
+        };
+        raw.push(b0);
+        // high tag number form (X.690: 8.1.2.4)
+        if b0 & 0x1f == 0x1f {
+            loop {
+                let b = self.read_u8_required()?;
+                raw.push(b);
+                // longer tags are rejected by the parser
+                if b & 0x80 == 0 || raw.len() > 6 {
+                    break;
+                }
+            }
+        }
+        // long form of length (X.690: 8.1.3.5)
+        let len_b0 = self.read_u8_required()?;
+        raw.push(len_b0);
+        if len_b0 > 0x80 && len_b0 != 0xff {
+            for _ in 0..(len_b0 & 0x7f) {
+                let b = self.read_u8_required()?;
+                raw.push(b);
+            }
+        }
+        Ok(true)
+    }
+
+    fn parse_raw_header(&self, raw: &[u8]) -> Result<Header<'static>, ReadError> {
+        let input = Input::from_slice(raw);
+        let r = if self.der {
+            Header::parse_der(input)
+        } else {
+            Header::parse_ber(input)
+        };
+        let (_, header) = r.map_err(Error::from_nom_berr)?;
+        Ok(header.to_static())
+    }
+
+    fn read_u8(&mut self) -> io::Result<Option<u8>> {
+        let mut b = [0u8];
+        loop {
+            match self.reader.read(&mut b) {
+                Ok(0) => return Ok(None),
+                Ok(_) => {
+                    self.position += 1;
+                    return Ok(Some(b[0]));
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn read_u8_required(&mut self) -> io::Result<u8> {
+        self.read_u8()?
+            .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))
+    }
+}
+
+/// Read at least one octet, or return an error
+fn read_some<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    loop {
+        match reader.read(buf) {
+            Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+            Ok(n) => return Ok(n),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+impl<R, Mode> Iterator for AnyReader<R, Mode>
+where
+    R: Read,
+    Mode: ASN1Mode,
+{
+    type Item = Result<OwnedAny, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.has_error {
+            return None;
+        }
+        match self.read_any() {
+            Ok(obj) => obj.map(Ok),
+            Err(e) => {
+                self.has_error = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Bounded reader for the content of the current object of an [`AnyReader`]
+///
+/// The reader returns end of stream after the last octet of content. ASN.1 errors (for ex. an
+/// invalid header in an object with indefinite length) are returned as I/O errors of kind
+/// [`InvalidData`](io::ErrorKind::InvalidData).
+#[derive(Debug)]
+pub struct ContentReader<'r, R, Mode>
+where
+    Mode: ASN1Mode,
+{
+    reader: &'r mut AnyReader<R, Mode>,
+}
+
+impl<R, Mode> Read for ContentReader<'_, R, Mode>
+where
+    R: Read,
+    Mode: ASN1Mode,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reader.read_content(buf).map_err(io::Error::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+    use std::io::{Cursor, ErrorKind, Read};
+
+    use crate::*;
+
+    #[test]
+    fn reader_definite() {
+        let data = hex!("02 01 01 30 06 02 01 02 02 01 03 04 00");
+        let mut reader = BerReader::new(Cursor::new(&data));
+        let obj = reader.read_any().expect("read failed").expect("no object");
+        assert_eq!(obj.header.tag(), Tag::Integer);
+        assert_eq!(obj.data, [1]);
+        assert_eq!(reader.position(), 3);
+        // partially read content, then skip the rest
+        let (header, mut content) = reader.read_object().unwrap().expect("no object");
+        assert_eq!(header.tag(), Tag::Sequence);
+        let mut buf = [0u8; 2];
+        content.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [2, 1]);
+        let obj = reader.read_any().unwrap().expect("no object");
+        assert_eq!(obj.header.tag(), Tag::OctetString);
+        assert!(obj.data.is_empty());
+        assert!(reader.read_any().unwrap().is_none());
+        assert_eq!(reader.position(), data.len() as u64);
+
+        // same objects, with DER and iterator
+        let tags: Vec<_> = DerReader::new(Cursor::new(&data))
+            .map(|r| r.map(|obj| obj.header.tag()))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(tags, [Tag::Integer, Tag::Sequence, Tag::OctetString]);
+    }
+
+    #[test]
+    fn reader_indefinite() {
+        // nested SEQUENCE with indefinite lengths, then NULL
+        let data = hex!("30 80 30 80 02 01 01 00 00 04 02 aa bb 00 00 05 00");
+        let mut reader = BerReader::new(Cursor::new(&data));
+        let obj = reader.read_any().unwrap().expect("no object");
+        assert_eq!(obj.header.length(), Length::Indefinite);
+        assert_eq!(obj.data, hex!("30 80 02 01 01 00 00 04 02 aa bb"));
+        // owned object can be parsed as Any
+        let any = obj.as_any();
+        let tags: Vec<_> = any
+            .iter_elements::<BerMode>()
+            .map(|r| r.map(|(_, obj)| obj.tag()))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(tags, [Tag::Sequence, Tag::OctetString]);
+        let obj = reader.read_any().unwrap().expect("no object");
+        assert_eq!(obj.header.tag(), Tag::Null);
+        assert!(reader.read_any().unwrap().is_none());
+
+        // iterate on sub-objects using content reader
+        let mut reader = BerReader::new(Cursor::new(&data));
+        let (_, content) = reader.read_object().unwrap().expect("no object");
+        let mut sub_reader = BerReader::new(content);
+        let (header, content) = sub_reader.read_object().unwrap().expect("no object");
+        assert_eq!(header.tag(), Tag::Sequence);
+        let items: Vec<_> = BerReader::new(content).collect::<Result<_, _>>().unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].as_any().as_u32(), Ok(1));
+        let obj = sub_reader.read_any().unwrap().expect("no object");
+        assert_eq!(obj.data, [0xaa, 0xbb]);
+        assert!(sub_reader.read_any().unwrap().is_none());
+        // content of first object was fully read
+        let obj = reader.read_any().unwrap().expect("no object");
+        assert_eq!(obj.header.tag(), Tag::Null);
+    }
+
+    #[test]
+    fn reader_errors() {
+        // DER: indefinite length
+        let data = hex!("30 80 02 01 01 00 00");
+        let mut reader = DerReader::new(Cursor::new(&data));
+        let r = reader.next().expect("no result");
+        assert!(matches!(
+            r,
+            Err(ReadError::ASN1Error(Error::DerConstraintFailed(
+                DerConstraint::IndefiniteLength
+            )))
+        ));
+        assert!(reader.next().is_none());
+
+        // truncated content
+        let data = hex!("04 05 01 02");
+        let r = BerReader::new(Cursor::new(&data)).read_any();
+        assert!(matches!(r, Err(ReadError::IOError(e)) if e.kind() == ErrorKind::UnexpectedEof));
+
+        // truncated header
+        let data = hex!("04 82 01");
+        let r = BerReader::new(Cursor::new(&data)).read_any();
+        assert!(matches!(r, Err(ReadError::IOError(e)) if e.kind() == ErrorKind::UnexpectedEof));
+
+        // missing End-Of-Content
+        let data = hex!("30 80 02 01 01");
+        let r = BerReader::new(Cursor::new(&data)).read_any();
+        assert!(matches!(r, Err(ReadError::IOError(e)) if e.kind() == ErrorKind::UnexpectedEof));
+
+        // limits
+        let data = hex!("04 05 01 02 03 04 05");
+        let r = BerReader::new(Cursor::new(&data))
+            .with_max_object_size(4)
+            .read_any();
+        assert!(matches!(r, Err(ReadError::ObjectTooLarge)));
+        let data = hex!("30 80 30 80 30 80 00 00 00 00 00 00");
+        let r = BerReader::new(Cursor::new(&data))
+            .with_max_object_size(4)
+            .read_any();
+        assert!(matches!(r, Err(ReadError::ObjectTooLarge)));
+        let r = BerReader::new(Cursor::new(&data))
+            .with_max_depth(2)
+            .read_any();
+        assert!(matches!(r, Err(ReadError::ASN1Error(Error::BerMaxDepth))));
+
+        // errors in content reader are returned as I/O errors
+        let data = hex!("30 80 02 80 00 00");
+        let mut reader = BerReader::new(Cursor::new(&data));
+        let (_, mut content) = reader.read_object().unwrap().expect("no object");
+        let e = content
+            .read_to_end(&mut Vec::new())
+            .expect_err("read should fail");
+        assert_eq!(e.kind(), ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,477 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::{max, min};
+use core::fmt;
+use core::fmt::Write as _;
+use core::ops::Range;
+
+use nom::Input as _;
+use thiserror::Error;
+
+use crate::ber::{GetObjectContent, MAX_RECURSION};
+use crate::{BerError, BerMode, BerParser, ErrorPath, Header, InnerError, Input, Length, ToStatic};
+
+/// Number of bytes in a row of the hex dump
+const ROW_LEN: usize = 16;
+/// Maximum number of rows with marked bytes in the hex dump
+const MAX_MARKED_ROWS: usize = 4;
+/// Length of the offset prefix of a row (`00000010:`), before the bytes
+const ROW_PREFIX_LEN: usize = 9;
+
+/// Detailed report of a parsing error, with its location in the original input
+///
+/// The report contains the absolute offset of the error, the chain of enclosing objects (from the
+/// outermost), and a hex dump of the input around the error, where the bad bytes are marked.
+/// It is built from the original buffer and the error using [`ErrorReport::new`] or
+/// [`BerError::report`].
+///
+/// The `Display` implementation writes a one-line summary. Use the alternate flag (`{:#}`) to
+/// write the complete report.
+///
+/// With feature `miette`, this type implements `miette::Diagnostic`: the hex dump is the source
+/// code, and the bad bytes are labeled.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::{BerParser, Input};
+/// use hex_literal::hex;
+///
+/// // SEQUENCE { INTEGER 1, INTEGER 2 } with a BOOLEAN instead of the second INTEGER
+/// let data = hex!("30 06 02 01 01 01 01 ff");
+/// let e = <(u32, u32)>::parse_ber(Input::from(&data)).expect_err("parsing should fail");
+/// let nom::Err::Error(e) = e else { panic!() };
+///
+/// let report = e.report(&data);
+/// assert_eq!(report.span(), &(5..8));
+/// assert_eq!(report.frames().len(), 2);
+/// assert_eq!(
+///     format!("{:#}", report),
+///     "\
+/// Unexpected Tag (expected: Some(Tag(2)), actual: Tag(1)) at offset 5 (0x5)
+/// enclosing objects:
+///   [UNIVERSAL 16] at offset 0 (header 2 bytes, length 6)
+///   [UNIVERSAL 1] at offset 5 (header 2 bytes, length 1)
+/// 00000000: 30 06 02 01 01 01 01 ff
+///                          ^^^^^^^^
+/// "
+/// );
+/// ```
+#[derive(Debug, Error)]
+pub struct ErrorReport {
+    error: InnerError,
+    path: ErrorPath,
+    span: Range<usize>,
+    frames: Vec<ReportFrame>,
+    dump: String,
+    /// Marked columns, for each line of the dump
+    marks: Vec<(usize, Range<usize>)>,
+}
+
+/// Object enclosing the location of an error (see [`ErrorReport`])
+#[derive(Clone, Debug)]
+pub struct ReportFrame {
+    offset: usize,
+    header_len: usize,
+    header: Header<'static>,
+}
+
+impl ReportFrame {
+    /// Return the absolute offset of the object
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Return the length of the object header, in bytes
+    #[inline]
+    pub const fn header_len(&self) -> usize {
+        self.header_len
+    }
+
+    /// Return the object header
+    #[inline]
+    pub const fn header(&self) -> &Header<'static> {
+        &self.header
+    }
+}
+
+impl fmt::Display for ReportFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{} {}] at offset {} (header {} bytes, ",
+            self.header.class(),
+            self.header.tag().0,
+            self.offset,
+            self.header_len
+        )?;
+        match self.header.length() {
+            Length::Definite(l) => write!(f, "length {})", l),
+            Length::Indefinite => f.write_str("indefinite length)"),
+        }
+    }
+}
+
+impl ErrorReport {
+    /// Build the report of `error`, using `data` (the buffer used to create the parser input)
+    ///
+    /// Offsets of [`Input`] are relative to the buffer used to create the input, so `data` must
+    /// be this buffer (and not a sub-slice).
+    pub fn new(data: &[u8], error: &BerError<Input<'_>>) -> Self {
+        let mut report = Self::with_span(data, error.input().span().clone(), error.inner().clone());
+        report.path = error.path().clone();
+        report
+    }
+
+    /// Build the report of a `nom` error, using `data` (the buffer used to create the parser input)
+    ///
+    /// `Incomplete` errors are located at the end of `data`.
+    pub fn from_nom(data: &[u8], error: &nom::Err<BerError<Input<'_>>>) -> Self {
+        match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => Self::new(data, e),
+            nom::Err::Incomplete(needed) => Self::with_span(
+                data,
+                data.len()..data.len(),
+                InnerError::Incomplete(*needed),
+            ),
+        }
+    }
+
+    fn with_span(data: &[u8], span: Range<usize>, error: InnerError) -> Self {
+        let frames = enclosing_frames(data, span.start);
+        let (dump, marks) = hex_dump(data, &span);
+        ErrorReport {
+            error,
+            path: ErrorPath::new(),
+            span,
+            frames,
+            dump,
+            marks,
+        }
+    }
+
+    /// Return the error kind
+    #[inline]
+    pub const fn error(&self) -> &InnerError {
+        &self.error
+    }
+
+    /// Return the location of the error in the parsed structure (see [`BerError::path`])
+    #[inline]
+    pub const fn path(&self) -> &ErrorPath {
+        &self.path
+    }
+
+    /// Return the absolute location (start and end offsets) of the error in the input
+    #[inline]
+    pub const fn span(&self) -> &Range<usize> {
+        &self.span
+    }
+
+    /// Return the objects enclosing the error location, starting from the outermost
+    ///
+    /// Objects are found by parsing headers from the start of the input, so the list can be
+    /// incomplete if the input is invalid before the error location.
+    #[inline]
+    pub fn frames(&self) -> &[ReportFrame] {
+        &self.frames
+    }
+
+    /// Return the hex dump of the input around the error location (without marks)
+    #[inline]
+    pub fn hex_dump(&self) -> &str {
+        &self.dump
+    }
+}
+
+impl fmt::Display for ErrorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at offset {} ({:#x})",
+            self.error, self.span.start, self.span.start
+        )?;
+        if !f.alternate() {
+            return Ok(());
+        }
+        f.write_char('\n')?;
+        if !self.path.is_empty() {
+            writeln!(f, "path: {}", self.path)?;
+        }
+        if !self.frames.is_empty() {
+            writeln!(f, "enclosing objects:")?;
+            for frame in &self.frames {
+                writeln!(f, "  {}", frame)?;
+            }
+        }
+        for (index, line) in self.dump.lines().enumerate() {
+            writeln!(f, "{}", line)?;
+            for (_, columns) in self.marks.iter().filter(|(l, _)| *l == index) {
+                let width = max(columns.len(), 1);
+                writeln!(f, "{:start$}{:^<width$}", "", "", start = columns.start)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for ErrorReport {
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let mut help = String::new();
+        if !self.path.is_empty() {
+            let _ = write!(help, "path: {}", self.path);
+        }
+        if let Some(frame) = self.frames.last() {
+            if !help.is_empty() {
+                help.push('\n');
+            }
+            let _ = write!(help, "in object {}", frame);
+        }
+        if help.is_empty() {
+            None
+        } else {
+            Some(Box::new(help))
+        }
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.dump)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let mut line_starts = Vec::new();
+        let mut offset = 0;
+        for line in self.dump.split('\n') {
+            line_starts.push((offset, line.len()));
+            offset += line.len() + 1;
+        }
+        let labels = self.marks.iter().filter_map(move |(line, columns)| {
+            let (start, len) = *line_starts.get(*line)?;
+            // marks past the end of the input are shown at the end of the line
+            let first = min(columns.start, len);
+            let last = min(columns.end, len);
+            Some(miette::LabeledSpan::new(
+                Some(String::from("here")),
+                start + first,
+                last - first,
+            ))
+        });
+        Some(Box::new(labels))
+    }
+}
+
+impl<'i> BerError<Input<'i>> {
+    /// Build a detailed report of this error, using `data` (the buffer used to create the parser
+    /// input)
+    ///
+    /// See [`ErrorReport`].
+    pub fn report(&self, data: &[u8]) -> ErrorReport {
+        ErrorReport::new(data, self)
+    }
+}
+
+/// Return the chain of objects containing `offset`, by parsing headers from the start of `data`
+fn enclosing_frames(data: &[u8], offset: usize) -> Vec<ReportFrame> {
+    let mut frames = Vec::new();
+    let mut input = Input::from_slice(data);
+    'outer: while frames.len() < MAX_RECURSION {
+        while !input.is_empty() {
+            let start = input.start();
+            let Ok((rem, header)) = Header::parse_ber(input.clone()) else {
+                break 'outer;
+            };
+            let header_len = rem.start() - start;
+            // end of object (declared end, if truncated), and content
+            let (end, content) = match header.length() {
+                Length::Definite(l) => {
+                    let end = rem.start().saturating_add(l);
+                    (end, rem.take(min(l, rem.input_len())))
+                }
+                Length::Indefinite => {
+                    match BerMode::get_object_content(&header, rem.clone(), MAX_RECURSION) {
+                        Ok((after, content)) => (after.start(), content),
+                        Err(_) => (rem.end(), rem.clone()),
+                    }
+                }
+            };
+            if offset < start {
+                break 'outer;
+            }
+            if offset < end || offset == start {
+                let contains_content = offset >= content.start();
+                frames.push(ReportFrame {
+                    offset: start,
+                    header_len,
+                    header: header.to_static(),
+                });
+                if !header.is_constructed() || !contains_content {
+                    break 'outer;
+                }
+                input = content;
+                continue 'outer;
+            }
+            input = input.take_from(min(end - start, input.input_len()));
+        }
+        break;
+    }
+    frames
+}
+
+/// Build the hex dump of `data` around `span`, and return it with the marked columns of each line
+fn hex_dump(data: &[u8], span: &Range<usize>) -> (String, Vec<(usize, Range<usize>)>) {
+    let start = min(span.start, data.len());
+    let end = min(max(span.end, start), data.len());
+    // empty spans mark the byte at `start` (which can be past the end of data)
+    let mark_end = max(end, start + 1);
+
+    let first_row = start / ROW_LEN;
+    let mut last_row = (mark_end - 1) / ROW_LEN;
+    let truncated = last_row >= first_row + MAX_MARKED_ROWS;
+    if truncated {
+        last_row = first_row + MAX_MARKED_ROWS - 1;
+    }
+    let num_rows = max((data.len() + ROW_LEN - 1) / ROW_LEN, first_row + 1);
+    let from_row = first_row.saturating_sub(1);
+    let to_row = if truncated {
+        last_row
+    } else {
+        min(last_row + 1, num_rows - 1)
+    };
+
+    let mut dump = String::new();
+    let mut marks = Vec::new();
+    for (line, row) in (from_row..=to_row).enumerate() {
+        let row_start = row * ROW_LEN;
+        let row_end = min(row_start + ROW_LEN, data.len());
+        let _ = write!(dump, "{:08x}:", row_start);
+        for b in data.get(row_start..row_end).unwrap_or_default() {
+            let _ = write!(dump, " {:02x}", b);
+        }
+        dump.push('\n');
+        let marked_start = max(start, row_start);
+        let marked_end = min(mark_end, row_start + ROW_LEN);
+        if marked_start < marked_end {
+            let column = |i: usize| ROW_PREFIX_LEN + 1 + 3 * (i - row_start);
+            marks.push((line, column(marked_start)..column(marked_end - 1) + 2));
+        }
+    }
+    if truncated {
+        dump.push_str("...\n");
+    }
+    (dump, marks)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::vec::Vec;
+    use hex_literal::hex;
+
+    use crate::{BerParser, DerParser, ErrorReport, InnerError, Input, Length, Tag};
+
+    #[test]
+    fn report_nested() {
+        // SEQUENCE { SEQUENCE { INTEGER 1, [0] { OCTET STRING } }, BOOLEAN with wrong length }
+        let data = hex!("30 10 30 09 02 01 01 a0 04 04 02 aa bb 01 03 ff ff ff");
+        let e = <(Vec<crate::Any>, bool)>::parse_der(Input::from(&data))
+            .expect_err("parsing should fail");
+        let report = ErrorReport::from_nom(&data, &e);
+        assert_eq!(report.span(), &(15..18));
+        let tags: Vec<_> = report.frames().iter().map(|f| f.header().tag()).collect();
+        assert_eq!(tags, [Tag::Sequence, Tag::Boolean]);
+        assert_eq!(report.frames()[1].offset(), 13);
+        assert_eq!(report.frames()[1].header_len(), 2);
+
+        // error inside a deeper object
+        let data = hex!("30 0d 30 09 02 01 01 a0 04 04 02 aa bb 05 00");
+        let e = <((u32, u32), ())>::parse_ber(Input::from(&data)).expect_err("should fail");
+        let report = ErrorReport::from_nom(&data, &e);
+        assert_eq!(report.span().start, 7);
+        let tags: Vec<_> = report.frames().iter().map(|f| f.header().tag()).collect();
+        assert_eq!(tags, [Tag::Sequence, Tag::Sequence, Tag(0)]);
+        assert_eq!(report.frames()[2].header().length(), Length::Definite(4));
+        assert!(format!("{:#}", report).contains("  [CONTEXT-SPECIFIC 0] at offset 7"));
+    }
+
+    #[test]
+    fn report_hex_dump() {
+        // error on the second row, with context rows
+        let mut data = Vec::new();
+        data.extend_from_slice(&hex!("30 2d"));
+        for _ in 0..15 {
+            data.extend_from_slice(&hex!("01 01 ff"));
+        }
+        data[2 + 3 * 6] = 0x02;
+        let e = <Vec<bool>>::parse_der(Input::from(&data)).expect_err("should fail");
+        let report = ErrorReport::from_nom(&data, &e);
+        assert_eq!(report.span(), &(22..23));
+        let s = format!("{:#}", report);
+        let lines: Vec<_> = s.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Unexpected Tag (expected: Some(Tag(1)), actual: Tag(2)) at offset 22 (0x16)"
+        );
+        assert_eq!(lines[1], "path: [6]");
+        assert_eq!(
+            lines[4],
+            "  [UNIVERSAL 2] at offset 20 (header 2 bytes, length 1)"
+        );
+        assert_eq!(
+            &lines[lines.len() - 4..],
+            [
+                "00000000: 30 2d 01 01 ff 01 01 ff 01 01 ff 01 01 ff 01 01",
+                "00000010: ff 01 01 ff 02 01 ff 01 01 ff 01 01 ff 01 01 ff",
+                "                            ^^",
+                "00000020: 01 01 ff 01 01 ff 01 01 ff 01 01 ff 01 01 ff",
+            ]
+        );
+        assert_eq!(report.hex_dump().lines().count(), 3);
+
+        // one-line summary
+        assert_eq!(
+            format!("{}", report),
+            "Unexpected Tag (expected: Some(Tag(1)), actual: Tag(2)) at offset 22 (0x16)"
+        );
+    }
+
+    #[test]
+    fn report_incomplete() {
+        let data = hex!("30 06 02 01 01 02");
+        let e = <(u32, u32)>::parse_ber(Input::from(&data)).expect_err("should fail");
+        let report = ErrorReport::from_nom(&data, &e);
+        assert!(matches!(report.error(), InnerError::Incomplete(_)));
+        assert_eq!(report.span(), &(6..6));
+        // the truncated SEQUENCE is reported, marker is after the last byte
+        assert_eq!(report.frames().len(), 1);
+        let s = format!("{:#}", report);
+        assert!(s.ends_with("00000000: 30 06 02 01 01 02\n                            ^^\n"));
+
+        // empty input
+        let report = ErrorReport::from_nom(&[], &e);
+        assert!(report.frames().is_empty());
+        assert_eq!(report.hex_dump(), "00000000:\n");
+    }
+
+    #[cfg(feature = "miette")]
+    #[test]
+    fn report_miette() {
+        use miette::Diagnostic;
+
+        let data = hex!("30 06 02 01 01 01 01 ff");
+        let e = <(u32, u32)>::parse_ber(Input::from(&data)).expect_err("should fail");
+        let report = ErrorReport::from_nom(&data, &e);
+        let labels: Vec<_> = report.labels().expect("no labels").collect();
+        assert_eq!(labels.len(), 1);
+        let dump = report.hex_dump();
+        let label = &dump[labels[0].offset()..labels[0].offset() + labels[0].len()];
+        assert_eq!(label, "01 01 ff");
+        assert!(report.source_code().is_some());
+        let help = report.help().expect("no help").to_string();
+        assert_eq!(
+            help,
+            "in object [UNIVERSAL 1] at offset 5 (header 2 bytes, length 1)"
+        );
+    }
+}
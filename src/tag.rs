@@ -126,6 +126,14 @@ pub trait DynTagged {
     /// - type `Any` (accepts all tag numbers)
     /// - ASN.1 type `CHOICE` (accept multiple tags)
     fn accept_tag(tag: Tag) -> bool;
+
+    /// Return the expected tag number, if unique
+    ///
+    /// This is used to report the expected tag in errors. Types accepting several tag numbers
+    /// (see [`DynTagged::accept_tag`]) return `None`.
+    fn expected_tag() -> Option<Tag> {
+        None
+    }
 }
 
 impl<T> DynTagged for T
@@ -147,4 +155,8 @@ where
     fn accept_tag(tag: Tag) -> bool {
         tag == T::TAG
     }
+
+    fn expected_tag() -> Option<Tag> {
+        Some(T::TAG)
+    }
 }
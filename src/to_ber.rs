@@ -1,8 +1,7 @@
-#![cfg(feature = "std")]
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
-use std::io::Write;
-
-use crate::{Class, Header, InnerError, Length, SerializeResult, Tag};
+use crate::{Class, Header, InnerError, Length, SerializeResult, Tag, Writer};
 
 mod constructed;
 mod constructed_indefinite;
@@ -41,12 +40,12 @@ pub trait ToBer {
     /// Encode and write the content of the object to the writer `target`
     ///
     /// Returns the number of bytes written
-    fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize>;
+    fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize>;
 
     /// Encode and write the header of the object to the writer `target`
     ///
     /// Returns the number of bytes written
-    fn ber_write_header<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+    fn ber_write_header<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
         let mut encoder = Self::Encoder::new();
 
         let mut sz = 0;
@@ -63,7 +62,7 @@ pub trait ToBer {
     /// Encode and write the object (header + content) to the writer `target`
     ///
     /// Returns the number of bytes written
-    fn ber_encode<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+    fn ber_encode<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
         let sz = self.ber_write_header(target)? + self.ber_write_content(target)?;
 
         Ok(sz)
@@ -74,7 +73,7 @@ pub trait ToBer {
     /// Usually, `class` is `Class::ContextSpecific`.
     ///
     /// Returns the number of bytes written
-    fn ber_encode_tagged_explicit<W: Write>(
+    fn ber_encode_tagged_explicit<W: Writer>(
         &self,
         class: Class,
         tag_number: u32,
@@ -94,7 +93,7 @@ pub trait ToBer {
     /// Usually, `class` is `Class::ContextSpecific`.
     ///
     /// Returns the number of bytes written
-    fn ber_encode_tagged_implicit<W: Write>(
+    fn ber_encode_tagged_implicit<W: Writer>(
         &self,
         class: Class,
         tag_number: u32,
@@ -118,7 +117,7 @@ pub trait ToBer {
     /// Encode in BER and write the object (header + content) to the writer `target`
     ///
     /// Returns the number of bytes written
-    fn write_ber<W: Write>(&self, writer: &mut W) -> SerializeResult<usize> {
+    fn write_ber<W: Writer>(&self, writer: &mut W) -> SerializeResult<usize> {
         self.ber_encode(writer)
     }
 }
@@ -140,7 +139,7 @@ where
         (*self).ber_tag_info()
     }
 
-    fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+    fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
         (*self).ber_write_content(target)
     }
 }
@@ -1,7 +1,4 @@
-use std::io;
-use std::io::Write;
-
-use crate::{BerEncoder, Class, Tag};
+use crate::{BerEncoder, Class, SerializeResult, Tag, Writer};
 
 /// Encoder for constructed objects, with *Definite* length
 #[allow(missing_debug_implementations)]
@@ -24,19 +21,22 @@ impl BerEncoder for Constructed {
         Constructed::new()
     }
 
-    fn write_tag_info<W: Write>(
+    fn write_tag_info<W: Writer>(
         &mut self,
         class: Class,
         _constructed: bool,
         tag: Tag,
         target: &mut W,
-    ) -> Result<usize, io::Error> {
+    ) -> SerializeResult<usize> {
         self.write_tag_generic(class, true, tag, target)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
     use hex_literal::hex;
     // use nom::HexDisplay;
 
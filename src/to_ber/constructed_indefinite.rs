@@ -1,7 +1,7 @@
-use std::io;
-use std::io::Write;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
-use crate::{Class, DynTagged, Length, SerializeError, SerializeResult, Tag};
+use crate::{Class, DynTagged, Length, SerializeError, SerializeResult, Tag, Writer};
 
 use super::{BerEncoder, ToBer};
 
@@ -26,13 +26,13 @@ impl BerEncoder for ConstructedIndefinite {
         ConstructedIndefinite::new()
     }
 
-    fn write_tag_info<W: Write>(
+    fn write_tag_info<W: Writer>(
         &mut self,
         class: Class,
         _constructed: bool,
         tag: Tag,
         target: &mut W,
-    ) -> Result<usize, io::Error> {
+    ) -> SerializeResult<usize> {
         self.write_tag_generic(class, true, tag, target)
     }
 }
@@ -65,7 +65,7 @@ where
         Length::Indefinite
     }
 
-    fn ber_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+    fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
         let sz = self.0.iter().try_fold(0, |acc, t| {
             let sz = t.ber_encode(target)?;
             Ok::<_, SerializeError>(acc + sz)
@@ -82,6 +82,9 @@ where
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
     use hex_literal::hex;
     // use nom::HexDisplay;
 
@@ -1,6 +1,4 @@
-use std::io::{self, Write};
-
-use crate::{Class, Length, Tag};
+use crate::{Class, Length, SerializeError, SerializeResult, Tag, Writer};
 
 /// Common trait for BER encoders
 ///
@@ -12,26 +10,26 @@ pub trait BerEncoder {
 
     /// Write tag, constructed bit, and class to `target`
     // NOTE: mut is here to allow keeping state
-    fn write_tag_info<W: Write>(
+    fn write_tag_info<W: Writer>(
         &mut self,
         class: Class,
         constructed: bool,
         tag: Tag,
         target: &mut W,
-    ) -> Result<usize, io::Error> {
+    ) -> SerializeResult<usize> {
         self.write_tag_generic(class, constructed, tag, target)
     }
 
     /// This functions writes a full tag (Class, Constructed and Number) to `target`
     ///
     /// Note: this function should not be reimplemented unless implementer has very good reasons!
-    fn write_tag_generic<W: Write>(
+    fn write_tag_generic<W: Writer>(
         &mut self,
         class: Class,
         constructed: bool,
         tag: Tag,
         target: &mut W,
-    ) -> Result<usize, io::Error> {
+    ) -> SerializeResult<usize> {
         let class = class as u8;
 
         const CONSTRUCTED_BIT: u8 = 0b0010_0000;
@@ -65,8 +63,7 @@ pub trait BerEncoder {
             while val > 0 {
                 current_index -= 1;
                 if current_index == 0 {
-                    // return Err(SerializeError::InvalidLength);
-                    return Err(io::Error::new(io::ErrorKind::InvalidData, "tag too long"));
+                    return Err(SerializeError::InvalidLength);
                 }
                 buffer[current_index] = (val & 0x7f) as u8 | 0x80;
                 val >>= 7;
@@ -80,11 +77,11 @@ pub trait BerEncoder {
     }
 
     /// Write the length of the encoded object content (without header) to `target`
-    fn write_length<W: Write>(
+    fn write_length<W: Writer>(
         &mut self,
         length: Length,
         target: &mut W,
-    ) -> Result<usize, io::Error> {
+    ) -> SerializeResult<usize> {
         const INDEFINITE: u8 = 0b1000_0000;
         match length {
             Length::Indefinite => {
@@ -121,6 +118,8 @@ pub trait BerEncoder {
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec::Vec;
+
     use hex_literal::hex;
 
     use crate::{BerEncoder, Length, Primitive};
@@ -1,7 +1,4 @@
-use std::io;
-use std::io::Write;
-
-use crate::{Class, Tag};
+use crate::{Class, SerializeResult, Tag, Writer};
 
 use super::BerEncoder;
 
@@ -25,13 +22,13 @@ impl<const TAG: u32> BerEncoder for Primitive<TAG> {
         Primitive::new()
     }
 
-    fn write_tag_info<W: Write>(
+    fn write_tag_info<W: Writer>(
         &mut self,
         _class: Class,
         _constructed: bool,
         _tag: Tag,
         target: &mut W,
-    ) -> Result<usize, io::Error> {
+    ) -> SerializeResult<usize> {
         self.write_tag_generic(Class::Universal, false, Tag(TAG), target)
     }
 }
@@ -0,0 +1,317 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::to_ber::*;
+use crate::{Class, Header, Length, SerializeResult, Tag, Writer, CER_SEGMENT_SIZE};
+
+/// Common trait for CER encoding functions
+///
+/// The `Encoder` type allows specifying common encoders for objects with similar headers
+/// (for ex. primitive objects) easily.
+///
+/// CER requires that constructed objects are encoded using the indefinite length form.
+/// By convention, implementations for constructed objects return [`Length::Indefinite`] in
+/// [`cer_content_len`](ToCer::cer_content_len), and write the terminating End-Of-Content
+/// in [`cer_write_content`](ToCer::cer_write_content).
+///
+/// # Examples
+///
+/// ```
+/// use asn1_rs::ToCer;
+///
+/// let mut writer = Vec::new();
+/// let sz = vec![4u32].cer_encode(&mut writer).expect("serialization failed");
+///
+/// assert_eq!(&writer, &[0x30, 0x80, 0x02, 0x01, 0x04, 0x00, 0x00]);
+/// # assert_eq!(sz, 7);
+/// ```
+pub trait ToCer {
+    type Encoder: BerEncoder;
+
+    /// Returns the length of the encoded content of the object
+    ///
+    /// The length describes the _content_ only, not the header.
+    /// For constructed objects, this is always `Length::Indefinite`.
+    fn cer_content_len(&self) -> Length;
+
+    /// Returns the total length (including header) of the encoded content of the object
+    fn cer_total_len(&self) -> Length {
+        let (_, _, tag) = self.cer_tag_info();
+        let content_length = self.cer_content_len();
+        ber_total_length(tag, content_length)
+    }
+
+    /// Return the tag information to be encoded in header
+    fn cer_tag_info(&self) -> (Class, bool, Tag);
+
+    /// Encode and write the content of the object to the writer `target`
+    ///
+    /// If length is indefinite, this includes the End-Of-Content.
+    ///
+    /// Returns the number of bytes written
+    fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize>;
+
+    /// Encode and write the header of the object to the writer `target`
+    ///
+    /// Returns the number of bytes written
+    fn cer_write_header<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+        let mut encoder = Self::Encoder::new();
+
+        let mut sz = 0;
+        let (class, constructed, tag) = self.cer_tag_info();
+        sz += encoder.write_tag_info(class, constructed, tag, target)?;
+
+        // write length
+        let length = self.cer_content_len();
+        sz += encoder.write_length(length, target)?;
+
+        Ok(sz)
+    }
+
+    /// Encode and write the object (header + content) to the writer `target`
+    ///
+    /// Returns the number of bytes written
+    fn cer_encode<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+        let sz = self.cer_write_header(target)? + self.cer_write_content(target)?;
+
+        Ok(sz)
+    }
+
+    /// Encode and write the object (header + content) as TAGGED EXPLICIT and write it to the writer `target`
+    ///
+    /// Since the tagged object is constructed, it uses the indefinite length form.
+    ///
+    /// Usually, `class` is `Class::ContextSpecific`.
+    ///
+    /// Returns the number of bytes written
+    fn cer_encode_tagged_explicit<W: Writer>(
+        &self,
+        class: Class,
+        tag_number: u32,
+        target: &mut W,
+    ) -> SerializeResult<usize> {
+        let tagged_header = Header::new(class, true, Tag(tag_number), Length::Indefinite);
+        let sz = tagged_header.cer_write_header(target)?
+            + self.cer_write_header(target)?
+            + self.cer_write_content(target)?
+            + cer_write_eoc(target)?;
+
+        Ok(sz)
+    }
+
+    /// Encode and write the object (header + content) as TAGGED IMPLICIT and write it to the writer `target`
+    ///
+    /// Usually, `class` is `Class::ContextSpecific`.
+    ///
+    /// Returns the number of bytes written
+    fn cer_encode_tagged_implicit<W: Writer>(
+        &self,
+        class: Class,
+        tag_number: u32,
+        target: &mut W,
+    ) -> SerializeResult<usize> {
+        let length = self.cer_content_len();
+        let (_, constructed, _) = self.cer_tag_info();
+        let tagged_header = Header::new(class, constructed, Tag(tag_number), length);
+        let sz = tagged_header.cer_write_header(target)? + self.cer_write_content(target)?;
+
+        Ok(sz)
+    }
+
+    /// Write the CER encoded representation to a newly allocated `Vec<u8>`
+    fn to_cer_vec(&self) -> SerializeResult<Vec<u8>> {
+        let mut v = Vec::new();
+        self.cer_encode(&mut v)?;
+        Ok(v)
+    }
+
+    /// Encode in CER and write the object (header + content) to the writer `target`
+    ///
+    /// Returns the number of bytes written
+    fn write_cer<W: Writer>(&self, writer: &mut W) -> SerializeResult<usize> {
+        self.cer_encode(writer)
+    }
+}
+
+//--- blanket impls
+
+impl<E, T: ToCer> ToCer for &'_ T
+where
+    T: ToCer<Encoder = E>,
+    E: BerEncoder,
+{
+    type Encoder = <T as ToCer>::Encoder;
+
+    fn cer_content_len(&self) -> Length {
+        (*self).cer_content_len()
+    }
+
+    fn cer_tag_info(&self) -> (Class, bool, Tag) {
+        (*self).cer_tag_info()
+    }
+
+    fn cer_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+        (*self).cer_write_content(target)
+    }
+}
+
+//--- Macros
+
+/// Helper macro to implement [`ToCer`] for types where implementation is the same as [`ToDer`](crate::ToDer)
+///
+/// This is the case for most types using a primitive encoding (except strings).
+#[macro_export]
+macro_rules! impl_tocer_from_toder {
+    (TY $ty:ty) => {
+        impl $crate::ToCer for $ty
+        where
+            $ty: $crate::ToDer,
+        {
+            type Encoder = <$ty as $crate::ToDer>::Encoder;
+
+            fn cer_content_len(&self) -> $crate::Length {
+                <$ty as $crate::ToDer>::der_content_len(self)
+            }
+
+            fn cer_tag_info(&self) -> ($crate::Class, bool, $crate::Tag) {
+                <$ty as $crate::ToDer>::der_tag_info(self)
+            }
+
+            fn cer_write_content<W: $crate::Writer>(
+                &self,
+                target: &mut W,
+            ) -> $crate::SerializeResult<usize> {
+                <$ty as $crate::ToDer>::der_write_content(self, target)
+            }
+        }
+    };
+    (LFT $lft:lifetime, $ty:ty) => {
+        impl<$lft> $crate::ToCer for $ty
+        where
+            $ty: $crate::ToDer,
+        {
+            type Encoder = <$ty as $crate::ToDer>::Encoder;
+
+            fn cer_content_len(&self) -> $crate::Length {
+                <$ty as $crate::ToDer>::der_content_len(self)
+            }
+
+            fn cer_tag_info(&self) -> ($crate::Class, bool, $crate::Tag) {
+                <$ty as $crate::ToDer>::der_tag_info(self)
+            }
+
+            fn cer_write_content<W: $crate::Writer>(
+                &self,
+                target: &mut W,
+            ) -> $crate::SerializeResult<usize> {
+                <$ty as $crate::ToDer>::der_write_content(self, target)
+            }
+        }
+    };
+}
+
+//--- Helper functions
+
+/// Write an End-Of-Content marker (`00 00`) to the writer `target`
+///
+/// Returns the number of bytes written
+pub fn cer_write_eoc<W: Writer>(target: &mut W) -> SerializeResult<usize> {
+    target.write_all(&[0, 0])?;
+    Ok(2)
+}
+
+/// Returns `true` if a string with content length `len` must use the constructed form in CER
+///
+/// See X.690 section 9.2
+#[inline]
+pub const fn cer_string_is_segmented(len: usize) -> bool {
+    len > CER_SEGMENT_SIZE
+}
+
+/// Returns the length of the content of an octet string (or restricted character string) of
+/// `len` bytes, when encoded in CER
+#[inline]
+pub const fn cer_string_content_len(len: usize) -> Length {
+    if cer_string_is_segmented(len) {
+        Length::Indefinite
+    } else {
+        Length::Definite(len)
+    }
+}
+
+/// Write the content of an octet string (or restricted character string) to the writer `target`
+///
+/// If `data` is longer than [`CER_SEGMENT_SIZE`], content is written as a sequence of `OCTET STRING`
+/// segments followed by End-Of-Content (X.690: 9.2).
+///
+/// Returns the number of bytes written
+pub fn cer_write_string_content<W: Writer>(data: &[u8], target: &mut W) -> SerializeResult<usize> {
+    if !cer_string_is_segmented(data.len()) {
+        target.write_all(data)?;
+        return Ok(data.len());
+    }
+    let mut sz = 0;
+    for segment in data.chunks(CER_SEGMENT_SIZE) {
+        let header = Header::new(
+            Class::Universal,
+            false,
+            Tag::OctetString,
+            Length::Definite(segment.len()),
+        );
+        sz += header.cer_write_header(target)?;
+        target.write_all(segment)?;
+        sz += segment.len();
+    }
+    sz += cer_write_eoc(target)?;
+    Ok(sz)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use hex_literal::hex;
+
+    use crate::{Length, ToCer};
+
+    use super::{cer_string_content_len, cer_write_string_content};
+
+    #[test]
+    fn tocer_string_segments() {
+        assert_eq!(cer_string_content_len(1000), Length::Definite(1000));
+        assert_eq!(cer_string_content_len(1001), Length::Indefinite);
+
+        // short strings are written as is
+        let mut v: Vec<u8> = Vec::new();
+        let sz = cer_write_string_content(b"abc", &mut v).expect("serialization failed");
+        assert_eq!(sz, 3);
+        assert_eq!(&v, b"abc");
+
+        // long strings are segmented
+        v.clear();
+        let data = vec![0x55u8; 2001];
+        let sz = cer_write_string_content(&data, &mut v).expect("serialization failed");
+        assert_eq!(sz, v.len());
+        assert_eq!(&v[..4], &hex!("04 82 03 e8"));
+        assert_eq!(&v[1004..1008], &hex!("04 82 03 e8"));
+        assert_eq!(&v[2008..2011], &hex!("04 01 55"));
+        assert_eq!(&v[2011..], &hex!("00 00"));
+    }
+
+    #[test]
+    fn tocer_tagged() {
+        let mut v: Vec<u8> = Vec::new();
+        let value = vec![true];
+        value
+            .cer_encode_tagged_explicit(crate::Class::ContextSpecific, 1, &mut v)
+            .expect("serialization failed");
+        assert_eq!(&v, &hex!("a1 80 30 80 0101ff 0000 0000"));
+
+        v.clear();
+        value
+            .cer_encode_tagged_implicit(crate::Class::ContextSpecific, 1, &mut v)
+            .expect("serialization failed");
+        assert_eq!(&v, &hex!("a1 80 0101ff 0000"));
+    }
+}
@@ -1,9 +1,14 @@
-#![cfg(feature = "std")]
-
-use std::io::Write;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
 use crate::to_ber::*;
-use crate::{Class, Header, Length, SerializeResult, Tag};
+use crate::{Class, Header, Length, SerializeResult, Tag, Writer};
+
+mod length_cache;
+mod reverse_writer;
+
+pub use length_cache::*;
+pub use reverse_writer::*;
 
 /// Common trait for DER encoding functions
 ///
@@ -57,12 +62,12 @@ pub trait ToDer {
     /// Encode and write the content of the object to the writer `target`
     ///
     /// Returns the number of bytes written
-    fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize>;
+    fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize>;
 
     /// Encode and write the header of the object to the writer `target`
     ///
     /// Returns the number of bytes written
-    fn der_write_header<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+    fn der_write_header<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
         let mut encoder = Self::Encoder::new();
 
         let mut sz = 0;
@@ -79,7 +84,7 @@ pub trait ToDer {
     /// Encode and write the object (header + content) to the writer `target`
     ///
     /// Returns the number of bytes written
-    fn der_encode<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+    fn der_encode<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
         let sz = self.der_write_header(target)? + self.der_write_content(target)?;
 
         Ok(sz)
@@ -90,7 +95,7 @@ pub trait ToDer {
     /// Usually, `class` is `Class::ContextSpecific`.
     ///
     /// Returns the number of bytes written
-    fn der_encode_tagged_explicit<W: Write>(
+    fn der_encode_tagged_explicit<W: Writer>(
         &self,
         class: Class,
         tag_number: u32,
@@ -110,7 +115,7 @@ pub trait ToDer {
     /// Usually, `class` is `Class::ContextSpecific`.
     ///
     /// Returns the number of bytes written
-    fn der_encode_tagged_implicit<W: Write>(
+    fn der_encode_tagged_implicit<W: Writer>(
         &self,
         class: Class,
         tag_number: u32,
@@ -124,6 +129,211 @@ pub trait ToDer {
         Ok(sz)
     }
 
+    /// Returns the length of the encoded content of the object, and record the lengths of
+    /// sub-objects in `cache`
+    ///
+    /// This is the first pass of two-pass encoding (see [`LengthCache`]). The default
+    /// implementation does not record anything and returns `der_content_len()`, which is enough
+    /// for primitive objects. Constructed objects should implement this method and
+    /// [`ToDer::der_write_content_cached`] together, so that lengths of sub-objects are computed
+    /// only once.
+    fn der_content_len_cached(&self, cache: &mut LengthCache) -> Length {
+        let _ = cache;
+        self.der_content_len()
+    }
+
+    /// Returns the total length (including header) of the encoded object, and record the lengths
+    /// of the object and its sub-objects in `cache`
+    fn der_total_len_cached(&self, cache: &mut LengthCache) -> Length {
+        let index = cache.reserve();
+        let content_length = self.der_content_len_cached(cache);
+        cache.set(index, content_length);
+        let (_, _, tag) = self.der_tag_info();
+        ber_total_length(tag, content_length)
+    }
+
+    /// Returns the total length of the encoded object as TAGGED EXPLICIT, and record the lengths
+    /// of the object and its sub-objects in `cache`
+    fn der_total_len_tagged_explicit_cached(
+        &self,
+        tag_number: u32,
+        cache: &mut LengthCache,
+    ) -> Length {
+        let length = self.der_total_len_cached(cache);
+        ber_total_length(Tag(tag_number), length)
+    }
+
+    /// Returns the total length of the encoded object as TAGGED IMPLICIT, and record the lengths
+    /// of the object and its sub-objects in `cache`
+    fn der_total_len_tagged_implicit_cached(
+        &self,
+        tag_number: u32,
+        cache: &mut LengthCache,
+    ) -> Length {
+        let index = cache.reserve();
+        let content_length = self.der_content_len_cached(cache);
+        cache.set(index, content_length);
+        ber_total_length(Tag(tag_number), content_length)
+    }
+
+    /// Encode and write the content of the object to the writer `target`, using the lengths of
+    /// sub-objects from `cache`
+    ///
+    /// This is the second pass of two-pass encoding (see [`LengthCache`]). The default
+    /// implementation calls `der_write_content()`.
+    ///
+    /// Returns the number of bytes written
+    fn der_write_content_cached<W: Writer>(
+        &self,
+        cache: &mut LengthCache,
+        target: &mut W,
+    ) -> SerializeResult<usize> {
+        let _ = cache;
+        self.der_write_content(target)
+    }
+
+    /// Encode and write the object (header + content) to the writer `target`, using the lengths
+    /// recorded in `cache` by [`ToDer::der_total_len_cached`]
+    ///
+    /// Returns the number of bytes written
+    fn der_encode_cached<W: Writer>(
+        &self,
+        cache: &mut LengthCache,
+        target: &mut W,
+    ) -> SerializeResult<usize> {
+        let length = cache.next_length()?;
+        let mut encoder = Self::Encoder::new();
+        let (class, constructed, tag) = self.der_tag_info();
+        let sz = encoder.write_tag_info(class, constructed, tag, target)?
+            + encoder.write_length(length, target)?;
+
+        Ok(sz + self.der_write_content_cached(cache, target)?)
+    }
+
+    /// Encode and write the object (header + content) as TAGGED EXPLICIT to the writer `target`,
+    /// using the lengths recorded in `cache` by [`ToDer::der_total_len_tagged_explicit_cached`]
+    ///
+    /// Returns the number of bytes written
+    fn der_encode_tagged_explicit_cached<W: Writer>(
+        &self,
+        class: Class,
+        tag_number: u32,
+        cache: &mut LengthCache,
+        target: &mut W,
+    ) -> SerializeResult<usize> {
+        let (_, _, tag) = self.der_tag_info();
+        let length = ber_total_length(tag, cache.peek_length()?);
+        let tagged_header = Header::new(class, true, Tag(tag_number), length);
+        let sz = tagged_header.der_write_header(target)? + self.der_encode_cached(cache, target)?;
+
+        Ok(sz)
+    }
+
+    /// Encode and write the object (header + content) as TAGGED IMPLICIT to the writer `target`,
+    /// using the lengths recorded in `cache` by [`ToDer::der_total_len_tagged_implicit_cached`]
+    ///
+    /// Returns the number of bytes written
+    fn der_encode_tagged_implicit_cached<W: Writer>(
+        &self,
+        class: Class,
+        tag_number: u32,
+        cache: &mut LengthCache,
+        target: &mut W,
+    ) -> SerializeResult<usize> {
+        let length = cache.next_length()?;
+        let (_, constructed, _) = self.der_tag_info();
+        let tagged_header = Header::new(class, constructed, Tag(tag_number), length);
+        let sz = tagged_header.der_write_header(target)?
+            + self.der_write_content_cached(cache, target)?;
+
+        Ok(sz)
+    }
+
+    /// Encode and write the object (header + content) to the writer `target`, in two passes
+    ///
+    /// The lengths of the object and all its sub-objects are first computed once and stored in
+    /// a [`LengthCache`], then the object is written. This costs linear time for deeply nested
+    /// objects, while `der_encode()` computes lengths of sub-objects again for each level.
+    ///
+    /// Returns the number of bytes written
+    fn der_encode_with_cache<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+        let mut cache = LengthCache::new();
+        self.der_total_len_cached(&mut cache);
+        self.der_encode_cached(&mut cache, target)
+    }
+
+    /// Encode and write the content of the object to the reverse writer `target`, before all
+    /// written bytes
+    ///
+    /// The default implementation writes `der_write_content()` to a temporary buffer, which is
+    /// enough for primitive objects. Constructed objects should implement this method by writing
+    /// their sub-objects in reverse order, using [`ToDer::der_encode_reverse`].
+    ///
+    /// Returns the number of bytes written
+    fn der_write_content_reverse(&self, target: &mut ReverseWriter) -> SerializeResult<usize> {
+        target.prepend_with(|v| self.der_write_content(v))
+    }
+
+    /// Encode and write the object (header + content) to the reverse writer `target`, before all
+    /// written bytes
+    ///
+    /// The content is written first, so its length is known when writing the header, and
+    /// `der_content_len()` is not called (see [`ReverseWriter`]).
+    ///
+    /// Returns the number of bytes written
+    fn der_encode_reverse(&self, target: &mut ReverseWriter) -> SerializeResult<usize> {
+        let length = self.der_write_content_reverse(target)?;
+        let mut encoder = Self::Encoder::new();
+        let (class, constructed, tag) = self.der_tag_info();
+        let sz = target.prepend_with(|v| {
+            Ok(encoder.write_tag_info(class, constructed, tag, v)?
+                + encoder.write_length(Length::Definite(length), v)?)
+        })?;
+
+        Ok(length + sz)
+    }
+
+    /// Encode and write the object (header + content) as TAGGED EXPLICIT to the reverse writer
+    /// `target`, before all written bytes
+    ///
+    /// Returns the number of bytes written
+    fn der_encode_tagged_explicit_reverse(
+        &self,
+        class: Class,
+        tag_number: u32,
+        target: &mut ReverseWriter,
+    ) -> SerializeResult<usize> {
+        let length = self.der_encode_reverse(target)?;
+        let sz = target.prepend_header(class, true, Tag(tag_number), length)?;
+
+        Ok(length + sz)
+    }
+
+    /// Encode and write the object (header + content) as TAGGED IMPLICIT to the reverse writer
+    /// `target`, before all written bytes
+    ///
+    /// Returns the number of bytes written
+    fn der_encode_tagged_implicit_reverse(
+        &self,
+        class: Class,
+        tag_number: u32,
+        target: &mut ReverseWriter,
+    ) -> SerializeResult<usize> {
+        let length = self.der_write_content_reverse(target)?;
+        let (_, constructed, _) = self.der_tag_info();
+        let sz = target.prepend_header(class, constructed, Tag(tag_number), length)?;
+
+        Ok(length + sz)
+    }
+
+    /// Write the DER encoded representation to a newly allocated `Vec<u8>`, using a
+    /// [`ReverseWriter`]
+    fn to_der_vec_reverse(&self) -> SerializeResult<Vec<u8>> {
+        let mut writer = ReverseWriter::new();
+        self.der_encode_reverse(&mut writer)?;
+        Ok(writer.into_vec())
+    }
+
     /// Write the DER encoded representation to a newly allocated `Vec<u8>`
     fn to_der_vec(&self) -> SerializeResult<Vec<u8>> {
         let mut v = Vec::new();
@@ -134,7 +344,7 @@ pub trait ToDer {
     /// Encode in DER and write the object (header + content) to the writer `target`
     ///
     /// Returns the number of bytes written
-    fn write_der<W: Write>(&self, writer: &mut W) -> SerializeResult<usize> {
+    fn write_der<W: Writer>(&self, writer: &mut W) -> SerializeResult<usize> {
         self.der_encode(writer)
     }
 
@@ -174,9 +384,103 @@ where
         (*self).der_tag_info()
     }
 
-    fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+    fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
         (*self).der_write_content(target)
     }
+
+    fn der_total_len(&self) -> Length {
+        (*self).der_total_len()
+    }
+
+    fn der_encode<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+        (*self).der_encode(target)
+    }
+
+    fn der_content_len_cached(&self, cache: &mut LengthCache) -> Length {
+        (*self).der_content_len_cached(cache)
+    }
+
+    fn der_total_len_cached(&self, cache: &mut LengthCache) -> Length {
+        (*self).der_total_len_cached(cache)
+    }
+
+    fn der_total_len_tagged_explicit_cached(
+        &self,
+        tag_number: u32,
+        cache: &mut LengthCache,
+    ) -> Length {
+        (*self).der_total_len_tagged_explicit_cached(tag_number, cache)
+    }
+
+    fn der_total_len_tagged_implicit_cached(
+        &self,
+        tag_number: u32,
+        cache: &mut LengthCache,
+    ) -> Length {
+        (*self).der_total_len_tagged_implicit_cached(tag_number, cache)
+    }
+
+    fn der_write_content_cached<W: Writer>(
+        &self,
+        cache: &mut LengthCache,
+        target: &mut W,
+    ) -> SerializeResult<usize> {
+        (*self).der_write_content_cached(cache, target)
+    }
+
+    fn der_encode_cached<W: Writer>(
+        &self,
+        cache: &mut LengthCache,
+        target: &mut W,
+    ) -> SerializeResult<usize> {
+        (*self).der_encode_cached(cache, target)
+    }
+
+    fn der_encode_tagged_explicit_cached<W: Writer>(
+        &self,
+        class: Class,
+        tag_number: u32,
+        cache: &mut LengthCache,
+        target: &mut W,
+    ) -> SerializeResult<usize> {
+        (*self).der_encode_tagged_explicit_cached(class, tag_number, cache, target)
+    }
+
+    fn der_encode_tagged_implicit_cached<W: Writer>(
+        &self,
+        class: Class,
+        tag_number: u32,
+        cache: &mut LengthCache,
+        target: &mut W,
+    ) -> SerializeResult<usize> {
+        (*self).der_encode_tagged_implicit_cached(class, tag_number, cache, target)
+    }
+
+    fn der_write_content_reverse(&self, target: &mut ReverseWriter) -> SerializeResult<usize> {
+        (*self).der_write_content_reverse(target)
+    }
+
+    fn der_encode_reverse(&self, target: &mut ReverseWriter) -> SerializeResult<usize> {
+        (*self).der_encode_reverse(target)
+    }
+
+    fn der_encode_tagged_explicit_reverse(
+        &self,
+        class: Class,
+        tag_number: u32,
+        target: &mut ReverseWriter,
+    ) -> SerializeResult<usize> {
+        (*self).der_encode_tagged_explicit_reverse(class, tag_number, target)
+    }
+
+    fn der_encode_tagged_implicit_reverse(
+        &self,
+        class: Class,
+        tag_number: u32,
+        target: &mut ReverseWriter,
+    ) -> SerializeResult<usize> {
+        (*self).der_encode_tagged_implicit_reverse(class, tag_number, target)
+    }
 }
 
 //--- Macros
@@ -199,7 +503,10 @@ macro_rules! impl_toder_from_tober {
                 <$ty as ToBer>::ber_tag_info(self)
             }
 
-            fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+            fn der_write_content<W: $crate::Writer>(
+                &self,
+                target: &mut W,
+            ) -> SerializeResult<usize> {
                 <$ty as ToBer>::ber_write_content(self, target)
             }
         }
@@ -219,7 +526,10 @@ macro_rules! impl_toder_from_tober {
                 <$ty as ToBer>::ber_tag_info(self)
             }
 
-            fn der_write_content<W: Write>(&self, target: &mut W) -> SerializeResult<usize> {
+            fn der_write_content<W: $crate::Writer>(
+                &self,
+                target: &mut W,
+            ) -> SerializeResult<usize> {
                 <$ty as ToBer>::ber_write_content(self, target)
             }
         }
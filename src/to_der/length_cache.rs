@@ -0,0 +1,103 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{Length, SerializeError, SerializeResult};
+
+/// Lengths of encoded objects, computed once for two-pass DER encoding
+///
+/// The header of an object contains the length of its content, so it requires computing the
+/// lengths of all sub-objects. When headers are written recursively, these lengths are computed
+/// again for each level, which costs O(N²) for objects nested N levels deep.
+///
+/// Two-pass encoding uses a `LengthCache` to compute each length only once:
+/// - the first pass ([`ToDer::der_total_len_cached`](crate::ToDer::der_total_len_cached))
+///   records the content lengths of the object and its sub-objects, in pre-order
+/// - the second pass ([`ToDer::der_encode_cached`](crate::ToDer::der_encode_cached)) reads
+///   them in the same order when writing headers
+///
+/// Usually, this object is not used directly: see
+/// [`ToDer::der_encode_with_cache`](crate::ToDer::der_encode_with_cache) (used by the custom
+/// derive for types with constructed fields, since it requires an allocation).
+///
+/// # Examples
+///
+/// ```
+/// use asn1_rs::{LengthCache, ToDer};
+///
+/// let value = vec![vec![1u32, 2], vec![3]];
+/// let mut cache = LengthCache::new();
+/// let length = value.der_total_len_cached(&mut cache);
+///
+/// let mut writer = Vec::new();
+/// let sz = value.der_encode_cached(&mut cache, &mut writer).expect("serialization failed");
+/// assert_eq!(length, asn1_rs::Length::Definite(sz));
+/// assert_eq!(writer, value.to_der_vec().unwrap());
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LengthCache {
+    lengths: Vec<Length>,
+    position: usize,
+}
+
+impl LengthCache {
+    /// Build a new empty `LengthCache`
+    pub const fn new() -> Self {
+        LengthCache {
+            lengths: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Return the number of recorded lengths
+    pub fn len(&self) -> usize {
+        self.lengths.len()
+    }
+
+    /// Return `true` if no length was recorded
+    pub fn is_empty(&self) -> bool {
+        self.lengths.is_empty()
+    }
+
+    /// Remove all recorded lengths, and reset the read position
+    pub fn clear(&mut self) {
+        self.lengths.clear();
+        self.position = 0;
+    }
+
+    /// Reserve an entry for the length of an object (first pass), and return its index
+    ///
+    /// The entry must be reserved before computing lengths of sub-objects, and set using
+    /// [`LengthCache::set`] when the length is known.
+    pub fn reserve(&mut self) -> usize {
+        self.lengths.push(Length::Definite(0));
+        self.lengths.len() - 1
+    }
+
+    /// Set the length of the entry at `index`, reserved using [`LengthCache::reserve`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` was not reserved
+    pub fn set(&mut self, index: usize, length: Length) {
+        self.lengths[index] = length;
+    }
+
+    /// Return the next length (second pass), without consuming it
+    ///
+    /// Returns [`SerializeError::InvalidLength`] if all lengths were consumed.
+    pub fn peek_length(&self) -> SerializeResult<Length> {
+        self.lengths
+            .get(self.position)
+            .copied()
+            .ok_or(SerializeError::InvalidLength)
+    }
+
+    /// Return and consume the next length (second pass)
+    ///
+    /// Returns [`SerializeError::InvalidLength`] if all lengths were consumed.
+    pub fn next_length(&mut self) -> SerializeResult<Length> {
+        let length = self.peek_length()?;
+        self.position += 1;
+        Ok(length)
+    }
+}
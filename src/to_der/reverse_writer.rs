@@ -0,0 +1,218 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{Class, Header, Length, SerializeResult, Tag, ToDer};
+
+/// Buffer for single-pass DER encoding, filled from the end
+///
+/// The header of an object contains the length of its content, so a writer filling a buffer from
+/// the start must compute the lengths of all sub-objects before writing anything (see
+/// [`LengthCache`](crate::LengthCache)).
+///
+/// A `ReverseWriter` avoids this pre-pass: objects are encoded back-to-front, starting with the
+/// last sub-object, and the content of each object is written before its header. When the header
+/// is written, the length of the content is known.
+///
+/// Objects are written using [`ToDer::der_encode_reverse`] (or
+/// [`ToDer::to_der_vec_reverse`] to get the encoded bytes directly). The buffer grows as needed,
+/// and can be reused with [`ReverseWriter::clear`] to avoid allocations when encoding many
+/// objects.
+///
+/// # Examples
+///
+/// ```
+/// use asn1_rs::{ReverseWriter, ToDer};
+///
+/// let value = vec![vec![1u32, 2], vec![3]];
+/// let mut writer = ReverseWriter::new();
+/// let sz = value.der_encode_reverse(&mut writer).expect("serialization failed");
+///
+/// assert_eq!(sz, writer.len());
+/// assert_eq!(writer.as_slice(), &value.to_der_vec().unwrap()[..]);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ReverseWriter {
+    // written bytes are stored in `buffer[start..]`
+    buffer: Vec<u8>,
+    start: usize,
+    // temporary buffer for objects written from the start (headers, primitive content)
+    scratch: Vec<u8>,
+}
+
+impl ReverseWriter {
+    /// Build a new empty `ReverseWriter`
+    pub const fn new() -> Self {
+        ReverseWriter {
+            buffer: Vec::new(),
+            start: 0,
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Build a new empty `ReverseWriter`, with space for at least `capacity` bytes
+    pub fn with_capacity(capacity: usize) -> Self {
+        ReverseWriter {
+            buffer: alloc::vec![0; capacity],
+            start: capacity,
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Return the number of written bytes
+    pub fn len(&self) -> usize {
+        self.buffer.len() - self.start
+    }
+
+    /// Return `true` if no byte was written
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the written bytes
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buffer[self.start..]
+    }
+
+    /// Remove all written bytes, keeping the allocated buffer
+    pub fn clear(&mut self) {
+        self.start = self.buffer.len();
+    }
+
+    /// Consume the writer, and return the written bytes
+    pub fn into_vec(mut self) -> Vec<u8> {
+        self.buffer.drain(..self.start);
+        self.buffer
+    }
+
+    /// Write `buf` before all written bytes
+    pub fn prepend(&mut self, buf: &[u8]) {
+        if buf.len() > self.start {
+            self.grow(buf.len());
+        }
+        let start = self.start - buf.len();
+        self.buffer[start..self.start].copy_from_slice(buf);
+        self.start = start;
+    }
+
+    /// Write bytes produced by `f` before all written bytes
+    ///
+    /// `f` writes to a temporary buffer, from the start. This is used to write objects which are
+    /// not written back-to-front, for ex. headers or content of primitive objects.
+    ///
+    /// Returns the number of bytes written
+    pub fn prepend_with<F>(&mut self, f: F) -> SerializeResult<usize>
+    where
+        F: FnOnce(&mut Vec<u8>) -> SerializeResult<usize>,
+    {
+        let mut scratch = core::mem::take(&mut self.scratch);
+        scratch.clear();
+        let res = f(&mut scratch);
+        if res.is_ok() {
+            self.prepend(&scratch);
+        }
+        let sz = scratch.len();
+        self.scratch = scratch;
+        res.map(|_| sz)
+    }
+
+    /// Write a DER header before all written bytes, for content of `length` bytes
+    ///
+    /// Returns the number of bytes written
+    pub fn prepend_header(
+        &mut self,
+        class: Class,
+        constructed: bool,
+        tag: Tag,
+        length: usize,
+    ) -> SerializeResult<usize> {
+        let header = Header::new(class, constructed, tag, Length::Definite(length));
+        self.prepend_with(|v| header.der_write_header(v))
+    }
+
+    fn grow(&mut self, additional: usize) {
+        let len = self.len();
+        let capacity = (2 * self.buffer.len()).max(len + additional).max(64);
+        let mut buffer = alloc::vec![0; capacity];
+        buffer[capacity - len..].copy_from_slice(self.as_slice());
+        self.buffer = buffer;
+        self.start = capacity - len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use hex_literal::hex;
+
+    use crate::*;
+
+    #[test]
+    fn reverse_writer_prepend() {
+        let mut writer = ReverseWriter::new();
+        assert!(writer.is_empty());
+        writer.prepend(&hex!("0304"));
+        writer.prepend(&hex!("0102"));
+        assert_eq!(writer.as_slice(), &hex!("01020304"));
+
+        // grow buffer, keeping written bytes
+        writer.prepend(&[0xff; 100]);
+        assert_eq!(writer.len(), 104);
+        assert_eq!(&writer.as_slice()[100..], &hex!("01020304"));
+
+        writer.clear();
+        assert!(writer.is_empty());
+        let sz = writer
+            .prepend_header(Class::Universal, true, Tag::Sequence, 3)
+            .expect("serialization failed");
+        assert_eq!(sz, 2);
+        assert_eq!(writer.into_vec(), hex!("3003"));
+
+        // with_capacity: no growth needed
+        let mut writer = ReverseWriter::with_capacity(4);
+        writer.prepend(&hex!("0304"));
+        writer.prepend(&hex!("0102"));
+        assert_eq!(writer.into_vec(), hex!("01020304"));
+    }
+
+    #[test]
+    fn reverse_writer_error() {
+        let mut writer = ReverseWriter::new();
+        writer.prepend(&hex!("0101ff"));
+        let e = writer
+            .prepend_with(|v| {
+                v.extend_from_slice(&hex!("00"));
+                Err(SerializeError::InvalidLength)
+            })
+            .expect_err("error");
+        assert!(matches!(e, SerializeError::InvalidLength));
+        // nothing is written on error
+        assert_eq!(writer.as_slice(), &hex!("0101ff"));
+    }
+
+    #[test]
+    fn reverse_writer_encode() {
+        // primitives
+        assert_eq!(4u32.to_der_vec_reverse().unwrap(), hex!("020104"));
+        assert_eq!(true.to_der_vec_reverse().unwrap(), hex!("0101ff"));
+        assert_eq!("abc".to_der_vec_reverse().unwrap(), hex!("0c03616263"));
+
+        // constructed and tagged values
+        let value = vec![
+            TaggedExplicit::<u32, Error, 0>::explicit(1),
+            TaggedExplicit::<u32, Error, 0>::explicit(2),
+        ];
+        let der = value.to_der_vec_reverse().unwrap();
+        assert_eq!(&der, &hex!("300a a003020101 a003020102"));
+        assert_eq!(der, value.to_der_vec().unwrap());
+
+        let value = TaggedImplicit::<u32, Error, 1>::implicit(3);
+        assert_eq!(value.to_der_vec_reverse().unwrap(), hex!("810103"));
+
+        // content longer than 127 bytes (long form of length)
+        let value = vec![1u32; 100];
+        let der = value.to_der_vec_reverse().unwrap();
+        assert_eq!(&der[..4], &hex!("30 82 012c"));
+        assert_eq!(der, value.to_der_vec().unwrap());
+    }
+}
@@ -14,11 +14,17 @@ pub enum BerMode {}
 #[derive(Debug)]
 pub enum DerMode {}
 
+/// Phantom type representing a CER parser
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum CerMode {}
+
 #[doc(hidden)]
 pub trait ASN1Mode {}
 
 impl ASN1Mode for BerMode {}
 impl ASN1Mode for DerMode {}
+impl ASN1Mode for CerMode {}
 
 /// Helper trait for creating tagged EXPLICIT values
 ///
@@ -0,0 +1,235 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::ber::MAX_RECURSION;
+use crate::{Any, AnyIterator, BerMode, Class, Tag};
+
+use super::types::write_hstring;
+use super::{ComponentsWriter, ToValueNotation};
+
+/// Write an `Any` object, without schema information
+///
+/// The type of the object is guessed from its tag:
+/// - `BOOLEAN`, `INTEGER`, `BIT STRING`, `OCTET STRING`, `NULL`, `OBJECT IDENTIFIER` and
+///   `UTF8String` values are written as usual
+/// - other universal types are written using the open type notation `Type : value` (for ex.
+///   `PrintableString : "FR"` or `ENUMERATED : 2`), since the type cannot be deduced from the
+///   value
+/// - `SEQUENCE` values are written as a list of unnamed components (for ex. `{ 1, TRUE }`), and
+///   `SET` values are prefixed with `SET :`
+/// - other objects are written with their tag. If the content is a single object, the tag is
+///   considered to be explicit (for ex. `[0] 2`). Otherwise, the tag is considered to be implicit,
+///   and the content is written as a list of components (if constructed, for ex.
+///   `[APPLICATION 1] IMPLICIT { 1, 2 }`) or as a `hstring` (if primitive, for ex.
+///   `[1] IMPLICIT '0102'H`)
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::value_notation::ToValueNotation;
+/// use asn1_rs::{Any, FromDer};
+/// use hex_literal::hex;
+///
+/// let input = &hex!("30 0c a0 03 02 01 02 02 01 2a 13 02 46 52");
+/// let (_, any) = Any::from_der(input).expect("parsing failed");
+/// assert_eq!(any.to_value_notation(), r#"{ [0] 2, 42, PrintableString : "FR" }"#);
+/// ```
+impl ToValueNotation for Any<'_> {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write_any(self, f, MAX_RECURSION)
+    }
+}
+
+/// An `Any` object, with the remaining recursion depth
+struct AnyValue<'a, 'b>(&'b Any<'a>, usize);
+
+impl ToValueNotation for AnyValue<'_, '_> {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write_any(self.0, f, self.1)
+    }
+}
+
+fn write_any(any: &Any, f: &mut dyn fmt::Write, max_depth: usize) -> fmt::Result {
+    if any.class() == Class::Universal {
+        if let Some(r) = write_universal(any, f, max_depth) {
+            return r;
+        }
+    }
+    write_tagged(any, f, max_depth)
+}
+
+/// Returns the sub-objects of a constructed object, or `None` if the content is not valid
+fn elements<'a>(any: &'a Any<'a>, max_depth: usize) -> Option<Vec<Any<'a>>> {
+    if !any.header.is_constructed() || max_depth == 0 {
+        return None;
+    }
+    let (_, items) = AnyIterator::<BerMode>::new(any.data.clone())
+        .try_collect::<Vec<_>>()
+        .ok()?;
+    Some(items)
+}
+
+fn write_elements(items: &[Any], f: &mut dyn fmt::Write, max_depth: usize) -> fmt::Result {
+    let mut writer = ComponentsWriter::new(f);
+    for item in items {
+        writer.component(None, &AnyValue(item, max_depth - 1))?;
+    }
+    writer.finish()
+}
+
+/// Write a universal object, or return `None` if the object type is not supported or if its
+/// content is not valid
+fn write_universal(any: &Any, f: &mut dyn fmt::Write, max_depth: usize) -> Option<fmt::Result> {
+    fn typed<T: ToValueNotation, E>(
+        f: &mut dyn fmt::Write,
+        type_name: Option<&str>,
+        value: Result<T, E>,
+    ) -> Option<fmt::Result> {
+        let value = value.ok()?;
+        let r = match type_name {
+            Some(name) => write!(f, "{name} : ").and_then(|_| value.write_value(f)),
+            None => value.write_value(f),
+        };
+        Some(r)
+    }
+
+    match any.tag() {
+        Tag::Sequence => {
+            let items = elements(any, max_depth)?;
+            return Some(write_elements(&items, f, max_depth));
+        }
+        Tag::Set => {
+            let items = elements(any, max_depth)?;
+            return Some(
+                f.write_str("SET : ")
+                    .and_then(|_| write_elements(&items, f, max_depth)),
+            );
+        }
+        _ => (),
+    }
+    if any.header.is_constructed() {
+        return None;
+    }
+    let a = any.clone();
+    match any.tag() {
+        Tag::Boolean => typed(f, None, a.bool()),
+        Tag::Integer => typed(f, None, a.integer()),
+        Tag::BitString => typed(f, None, a.bitstring()),
+        Tag::OctetString => typed(f, None, a.octetstring()),
+        Tag::Null => typed(f, None, a.null()),
+        Tag::Oid => typed(f, None, a.oid()),
+        Tag::Utf8String => typed(f, None, a.utf8string()),
+        Tag::RelativeOid => typed(f, Some("RELATIVE-OID"), a.relative_oid()),
+        Tag::RealType => typed(f, Some("REAL"), a.real()),
+        Tag::Enumerated => typed(f, Some("ENUMERATED"), a.enumerated()),
+        Tag::UtcTime => typed(f, Some("UTCTime"), a.utctime()),
+        Tag::GeneralizedTime => typed(f, Some("GeneralizedTime"), a.generalizedtime()),
+        Tag::NumericString => typed(f, Some("NumericString"), a.numericstring()),
+        Tag::PrintableString => typed(f, Some("PrintableString"), a.printablestring()),
+        Tag::TeletexString => typed(f, Some("TeletexString"), a.teletexstring()),
+        Tag::VideotexString => typed(f, Some("VideotexString"), a.videotexstring()),
+        Tag::Ia5String => typed(f, Some("IA5String"), a.ia5string()),
+        Tag::GraphicString => typed(f, Some("GraphicString"), a.graphicstring()),
+        Tag::VisibleString => typed(f, Some("VisibleString"), a.visiblestring()),
+        Tag::GeneralString => typed(f, Some("GeneralString"), a.generalstring()),
+        Tag::UniversalString => typed(f, Some("UniversalString"), a.universalstring()),
+        Tag::BmpString => typed(f, Some("BMPString"), a.bmpstring()),
+        Tag::ObjectDescriptor => typed(f, Some("ObjectDescriptor"), a.objectdescriptor()),
+        _ => None,
+    }
+}
+
+/// Write an object with its tag (for ex. `[0] 2` or `[APPLICATION 1] IMPLICIT '01'H`)
+fn write_tagged(any: &Any, f: &mut dyn fmt::Write, max_depth: usize) -> fmt::Result {
+    let tag = any.tag().0;
+    match any.class() {
+        Class::Universal => write!(f, "[UNIVERSAL {tag}]")?,
+        Class::Application => write!(f, "[APPLICATION {tag}]")?,
+        Class::ContextSpecific => write!(f, "[{tag}]")?,
+        Class::Private => write!(f, "[PRIVATE {tag}]")?,
+    }
+    match elements(any, max_depth) {
+        Some(items) if items.len() == 1 => {
+            f.write_char(' ')?;
+            write_any(&items[0], f, max_depth - 1)
+        }
+        Some(items) => {
+            f.write_str(" IMPLICIT ")?;
+            write_elements(&items, f, max_depth)
+        }
+        None => {
+            f.write_str(" IMPLICIT ")?;
+            write_hstring(f, any.data.as_bytes2())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use hex_literal::hex;
+
+    use crate::value_notation::ToValueNotation;
+    use crate::{Any, FromBer, FromDer};
+
+    fn to_value_notation(bytes: &[u8]) -> String {
+        let (rem, any) = Any::from_ber(bytes).expect("parsing failed");
+        assert!(rem.is_empty());
+        any.to_value_notation()
+    }
+
+    #[test]
+    fn value_notation_any() {
+        // AlgorithmIdentifier
+        let (_, any) = Any::from_der(&hex!("30 0d 06 09 2a 86 48 86 f7 0d 01 01 0b 05 00"))
+            .expect("parsing failed");
+        assert_eq!(
+            any.to_value_notation(),
+            "{ { 1 2 840 113549 1 1 11 }, NULL }"
+        );
+        assert_eq!(to_value_notation(&hex!("30 00")), "{}");
+        assert_eq!(to_value_notation(&hex!("31 03 01 01 ff")), "SET : { TRUE }");
+        assert_eq!(to_value_notation(&hex!("03 02 04 50")), "'0101'B");
+        assert_eq!(to_value_notation(&hex!("04 02 0a 0b")), "'0A0B'H");
+        assert_eq!(to_value_notation(&hex!("0a 01 02")), "ENUMERATED : 2");
+        assert_eq!(
+            to_value_notation(&hex!("17 0d 30 32 31 32 31 33 31 34 32 39 32 33 5a")),
+            r#"UTCTime : "021213142923Z""#
+        );
+        assert_eq!(to_value_notation(&hex!("0c 03 61 22 62")), r#""a""b""#);
+        assert_eq!(
+            to_value_notation(&hex!("16 02 46 52")),
+            r#"IA5String : "FR""#
+        );
+    }
+
+    #[test]
+    fn value_notation_any_tagged() {
+        // explicit tag
+        assert_eq!(to_value_notation(&hex!("a0 03 02 01 02")), "[0] 2");
+        assert_eq!(
+            to_value_notation(&hex!("61 03 02 01 02")),
+            "[APPLICATION 1] 2"
+        );
+        // implicit tags
+        assert_eq!(
+            to_value_notation(&hex!("81 02 01 02")),
+            "[1] IMPLICIT '0102'H"
+        );
+        assert_eq!(
+            to_value_notation(&hex!("e2 06 02 01 01 02 01 02")),
+            "[PRIVATE 2] IMPLICIT { 1, 2 }"
+        );
+        assert_eq!(to_value_notation(&hex!("a3 00")), "[3] IMPLICIT {}");
+        // unsupported universal type, and invalid content
+        assert_eq!(
+            to_value_notation(&hex!("0e 01 00")),
+            "[UNIVERSAL 14] IMPLICIT '00'H"
+        );
+        assert_eq!(
+            to_value_notation(&hex!("0c 01 ff")),
+            "[UNIVERSAL 12] IMPLICIT 'FF'H"
+        );
+    }
+}
@@ -0,0 +1,42 @@
+//! ASN.1 value notation
+//!
+//! The value notation is defined in [[X.680]]. It is the textual representation of values used in
+//! specifications, for example `{ version 2, serialNumber 42, algorithm { 1 2 840 113549 1 1 11 } }`.
+//!
+//! The [`ToValueNotation`] trait is implemented for native types, and for [`Any`](crate::Any)
+//! objects. The [`parse_value_notation`] function parses values (without schema) back to objects
+//! which can be encoded as DER. Types built with the `Sequence`, `Set`, `Choice`, `Enumerated` and `Alias` custom
+//! derive attributes can implement it by adding `value_notation` to the `asn1` attribute. Field
+//! names are used as component identifiers. See [doc::derive](crate::doc::derive) for details.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use asn1_rs::value_notation::ToValueNotation;
+//! use asn1_rs::{oid, BitString, OctetString};
+//!
+//! assert_eq!(42.to_value_notation(), "42");
+//! assert_eq!(OctetString::new(&[0x0a, 0x0b]).to_value_notation(), "'0A0B'H");
+//! assert_eq!(BitString::new(4, &[0x50]).to_value_notation(), "'0101'B");
+//! assert_eq!(oid!(1.2.840 .113549).to_value_notation(), "{ 1 2 840 113549 }");
+//! assert_eq!(vec![1, 2].to_value_notation(), "{ 1, 2 }");
+//! ```
+//!
+//! # Limitations
+//!
+//! - values are written on a single line
+//! - parsing requires the `std` feature. Values are parsed without schema: component identifiers
+//!   are ignored, and `CHOICE` alternatives and `ENUMERATED` identifiers are not supported
+//! - without the `bigint` feature, object identifiers with arcs larger than `u64` are not supported
+//!
+//! [X.680]: http://www.itu.int/rec/T-REC-X.680/en "Recommendation ITU-T X.680"
+
+mod any;
+mod parser;
+mod strings;
+mod traits;
+mod types;
+
+#[cfg(feature = "std")]
+pub use parser::*;
+pub use traits::*;
@@ -0,0 +1,845 @@
+#![cfg(feature = "std")]
+
+use core::convert::TryFrom;
+
+use crate::ber::MAX_RECURSION;
+use crate::{
+    impl_toder_from_tober, Any, BerGenericEncoder, Class, GeneralString, GeneralizedTime,
+    GraphicString, Header, Ia5String, Input, Length, NumericString, ObjectDescriptor, Oid,
+    PrintableString, Real, SerializeResult, Tag, TeletexString, TestValidCharset, ToBer, ToDer,
+    UtcTime, ValueNotationError, ValueNotationResult, VideotexString, VisibleString, Writer,
+};
+
+/// A value parsed from the ASN.1 value notation
+///
+/// This object owns the encoded content of the value. It can be accessed as an [`Any`] object
+/// using [`ParsedValue::any`], or encoded using [`ToDer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParsedValue {
+    class: Class,
+    constructed: bool,
+    tag: Tag,
+    content: Vec<u8>,
+}
+
+impl ParsedValue {
+    /// Return the `Class` of this object
+    #[inline]
+    pub const fn class(&self) -> Class {
+        self.class
+    }
+
+    /// Return the `Tag` of this object
+    #[inline]
+    pub const fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    /// Return `true` if this object is constructed
+    #[inline]
+    pub const fn constructed(&self) -> bool {
+        self.constructed
+    }
+
+    /// Return the DER-encoded content of this object
+    #[inline]
+    pub fn content(&self) -> &[u8] {
+        &self.content
+    }
+
+    /// Creates a borrowed `Any` for this object
+    pub fn any(&self) -> Any<'_> {
+        let header = Header::new(
+            self.class,
+            self.constructed,
+            self.tag,
+            Length::Definite(self.content.len()),
+        );
+        Any::new(header, Input::from_slice(&self.content))
+    }
+
+    fn universal(tag: Tag, constructed: bool, content: Vec<u8>) -> Self {
+        ParsedValue {
+            class: Class::Universal,
+            constructed,
+            tag,
+            content,
+        }
+    }
+
+    fn is_universal(&self, tag: Tag) -> bool {
+        self.class == Class::Universal && self.tag == tag
+    }
+}
+
+impl ToBer for ParsedValue {
+    type Encoder = BerGenericEncoder;
+
+    fn ber_content_len(&self) -> Length {
+        Length::Definite(self.content.len())
+    }
+
+    fn ber_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+        target.write_all(&self.content)?;
+        Ok(self.content.len())
+    }
+
+    fn ber_tag_info(&self) -> (Class, bool, Tag) {
+        (self.class, self.constructed, self.tag)
+    }
+}
+
+impl_toder_from_tober!(TY ParsedValue);
+
+/// Parse a value written in the ASN.1 value notation
+///
+/// The accepted syntax is the one produced by the [`ToValueNotation`](super::ToValueNotation)
+/// implementation of [`Any`]:
+/// - `TRUE`, `FALSE` and `NULL`
+/// - numbers, as `INTEGER` (for ex. `-5`), or as `REAL` if they have a fractional part or an
+///   exponent (for ex. `1.5` or `1e300`). `PLUS-INFINITY`, `MINUS-INFINITY` and `NOT-A-NUMBER` are
+///   also `REAL` values
+/// - `bstring` values as `BIT STRING` (for ex. `'0101'B`), and `hstring` values as `OCTET STRING`
+///   (for ex. `'0A0B'H`)
+/// - `cstring` values as `UTF8String` (for ex. `"abc"`)
+/// - lists of numbers as `OBJECT IDENTIFIER`, optionally in the name and number form (for ex.
+///   `{ 1 2 840 113549 }` or `{ iso(1) member-body(2) us(840) }`)
+/// - lists of components, separated by commas, as `SEQUENCE` (for ex. `{ 1, TRUE }`). Component
+///   identifiers are allowed and ignored (for ex. `{ version 2, serialNumber 42 }`)
+/// - tagged values (for ex. `[0] 2`, `[APPLICATION 1] IMPLICIT { 1, 2 }` or
+///   `[PRIVATE 3] EXPLICIT TRUE`). Tags are explicit by default
+/// - values of other universal types, using the open type notation `Type : value` (for ex.
+///   `PrintableString : "FR"`, `ENUMERATED : 2`, `SET : { 1, 2 }`, `RELATIVE-OID : { 1 2 }` or
+///   `UTCTime : "021213142923Z"`)
+///
+/// Comments (starting with `--`) are ignored.
+///
+/// `SET` and `SET OF` components are encoded in the given order.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::value_notation::parse_value_notation;
+/// use asn1_rs::ToDer;
+///
+/// let value = parse_value_notation(r#"
+///     {
+///         version [0] 2,    -- explicit tag
+///         { 1 2 840 113549 1 1 11 },
+///         PrintableString : "FR"
+///     }
+/// "#).expect("parsing failed");
+/// let der = value.to_der_vec().expect("serialization failed");
+/// assert_eq!(
+///     &der,
+///     &[0x30, 0x14,
+///         0xa0, 0x03, 0x02, 0x01, 0x02,
+///         0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b,
+///         0x13, 0x02, 0x46, 0x52]
+/// );
+/// ```
+pub fn parse_value_notation(s: &str) -> ValueNotationResult<ParsedValue> {
+    let mut parser = ValueParser {
+        input: s.as_bytes(),
+        pos: 0,
+    };
+    let value = parser.parse_value(MAX_RECURSION)?;
+    parser.skip_whitespace()?;
+    if parser.pos != parser.input.len() {
+        return Err(parser.syntax_error());
+    }
+    Ok(value)
+}
+
+struct ValueParser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl ValueParser<'_> {
+    fn syntax_error(&self) -> ValueNotationError {
+        if self.pos >= self.input.len() {
+            ValueNotationError::UnexpectedEnd
+        } else {
+            ValueNotationError::Syntax(self.pos)
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn starts_with(&self, s: &[u8]) -> bool {
+        self.input[self.pos..].starts_with(s)
+    }
+
+    fn expect(&mut self, c: u8) -> ValueNotationResult<()> {
+        self.skip_whitespace()?;
+        if self.peek() != Some(c) {
+            return Err(self.syntax_error());
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Skip whitespace and comments
+    ///
+    /// A comment starts with `--`, and ends with the next `--` or at the end of the line.
+    fn skip_whitespace(&mut self) -> ValueNotationResult<()> {
+        loop {
+            match self.peek() {
+                Some(b' ' | b'\t' | b'\n' | b'\r' | 0x0b | 0x0c) => self.pos += 1,
+                Some(b'-') if self.starts_with(b"--") => {
+                    self.pos += 2;
+                    while let Some(b) = self.peek() {
+                        if b == b'\n' || b == b'\r' {
+                            break;
+                        }
+                        if self.starts_with(b"--") {
+                            self.pos += 2;
+                            break;
+                        }
+                        self.pos += 1;
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Parse a word (identifier or reserved word), which can contain letters, digits and hyphens
+    fn parse_word(&mut self) -> ValueNotationResult<String> {
+        let start = self.pos;
+        if !matches!(self.peek(), Some(b) if b.is_ascii_alphabetic()) {
+            return Err(self.syntax_error());
+        }
+        while let Some(b) = self.peek() {
+            let hyphen = b == b'-' && !self.starts_with(b"--");
+            if !(b.is_ascii_alphanumeric() || hyphen) {
+                break;
+            }
+            self.pos += 1;
+        }
+        // the slice contains only ASCII characters
+        Ok(String::from_utf8_lossy(&self.input[start..self.pos]).into_owned())
+    }
+
+    /// Returns `true` if the next word starts with a lowercase letter (identifier)
+    fn next_is_identifier(&self) -> bool {
+        matches!(self.peek(), Some(b) if b.is_ascii_lowercase())
+    }
+
+    /// Parse an unsigned decimal number
+    fn parse_u64(&mut self) -> ValueNotationResult<u64> {
+        self.skip_whitespace()?;
+        let start = self.pos;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+        let digits = std::str::from_utf8(&self.input[start..self.pos])
+            .map_err(|_| ValueNotationError::Syntax(start))?;
+        if digits.is_empty() {
+            return Err(self.syntax_error());
+        }
+        digits
+            .parse()
+            .map_err(|_| ValueNotationError::InvalidValue(start))
+    }
+
+    fn parse_value(&mut self, max_depth: usize) -> ValueNotationResult<ParsedValue> {
+        if max_depth == 0 {
+            return Err(ValueNotationError::NestingTooDeep);
+        }
+        self.skip_whitespace()?;
+        match self.peek() {
+            Some(b'{') => self.parse_braces(max_depth),
+            Some(b'[') => self.parse_tagged(max_depth),
+            Some(b'\'') => self.parse_bh_string(),
+            Some(b'"') => {
+                let s = self.parse_cstring()?;
+                Ok(ParsedValue::universal(
+                    Tag::Utf8String,
+                    false,
+                    s.into_bytes(),
+                ))
+            }
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(b) if b.is_ascii_alphabetic() => {
+                let start = self.pos;
+                let word = self.parse_word()?;
+                match word.as_str() {
+                    "TRUE" => Ok(ParsedValue::universal(Tag::Boolean, false, vec![0xff])),
+                    "FALSE" => Ok(ParsedValue::universal(Tag::Boolean, false, vec![0x00])),
+                    "NULL" => Ok(ParsedValue::universal(Tag::Null, false, Vec::new())),
+                    "PLUS-INFINITY" | "MINUS-INFINITY" | "NOT-A-NUMBER" => {
+                        self.pos = start;
+                        self.parse_real()
+                    }
+                    _ => {
+                        self.pos = start;
+                        self.parse_typed_value(max_depth)
+                    }
+                }
+            }
+            _ => Err(self.syntax_error()),
+        }
+    }
+
+    /// Parse an `INTEGER` or a `REAL` value
+    fn parse_number(&mut self) -> ValueNotationResult<ParsedValue> {
+        let start = self.pos;
+        let negative = self.peek() == Some(b'-');
+        if negative {
+            self.pos += 1;
+        }
+        let digits_start = self.pos;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+        let digits_end = self.pos;
+        if digits_start == digits_end {
+            return Err(self.syntax_error());
+        }
+        if matches!(self.peek(), Some(b'.' | b'e' | b'E')) {
+            self.pos = start;
+            return self.parse_real();
+        }
+        let content = encode_integer(&self.input[digits_start..digits_end], negative);
+        Ok(ParsedValue::universal(Tag::Integer, false, content))
+    }
+
+    /// Parse a `REAL` value: a decimal number, or one of the special values
+    fn parse_real(&mut self) -> ValueNotationResult<ParsedValue> {
+        self.skip_whitespace()?;
+        let start = self.pos;
+        let real = if matches!(self.peek(), Some(b) if b.is_ascii_alphabetic()) {
+            match self.parse_word()?.as_str() {
+                "PLUS-INFINITY" => Real::Infinity,
+                "MINUS-INFINITY" => Real::NegInfinity,
+                "NOT-A-NUMBER" => Real::NaN,
+                _ => return Err(ValueNotationError::InvalidValue(start)),
+            }
+        } else {
+            while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') = self.peek() {
+                if self.starts_with(b"--") {
+                    break;
+                }
+                self.pos += 1;
+            }
+            let s = std::str::from_utf8(&self.input[start..self.pos])
+                .map_err(|_| ValueNotationError::Syntax(start))?;
+            let f = s
+                .parse::<f64>()
+                .map_err(|_| ValueNotationError::InvalidValue(start))?;
+            Real::new(f)
+        };
+        let mut content = Vec::new();
+        real.der_write_content(&mut content)
+            .map_err(|_| ValueNotationError::InvalidValue(start))?;
+        Ok(ParsedValue::universal(Tag::RealType, false, content))
+    }
+
+    /// Parse a `bstring` (`BIT STRING`) or a `hstring` (`OCTET STRING`)
+    fn parse_bh_string(&mut self) -> ValueNotationResult<ParsedValue> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut chars = Vec::new();
+        loop {
+            match self.peek() {
+                Some(b'\'') => break,
+                // whitespace is allowed in bstring and hstring
+                Some(b) if b.is_ascii_whitespace() => (),
+                Some(b) => chars.push(b),
+                None => return Err(ValueNotationError::UnexpectedEnd),
+            }
+            self.pos += 1;
+        }
+        self.pos += 1;
+        match self.peek() {
+            Some(b'B') => {
+                self.pos += 1;
+                let mut bytes = vec![0u8; (chars.len() + 7) / 8];
+                for (i, c) in chars.iter().enumerate() {
+                    match c {
+                        b'0' => (),
+                        b'1' => bytes[i / 8] |= 0x80 >> (i % 8),
+                        _ => return Err(ValueNotationError::InvalidValue(start)),
+                    }
+                }
+                let unused_bits = (bytes.len() * 8 - chars.len()) as u8;
+                let mut content = vec![unused_bits];
+                content.extend_from_slice(&bytes);
+                Ok(ParsedValue::universal(Tag::BitString, false, content))
+            }
+            Some(b'H') => {
+                self.pos += 1;
+                if chars.len() % 2 != 0 {
+                    return Err(ValueNotationError::InvalidValue(start));
+                }
+                let content = chars
+                    .chunks(2)
+                    .map(|pair| {
+                        let hex = std::str::from_utf8(pair).ok()?;
+                        u8::from_str_radix(hex, 16).ok()
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or(ValueNotationError::InvalidValue(start))?;
+                Ok(ParsedValue::universal(Tag::OctetString, false, content))
+            }
+            _ => Err(self.syntax_error()),
+        }
+    }
+
+    /// Parse a `cstring`, where quotation marks are doubled
+    fn parse_cstring(&mut self) -> ValueNotationResult<String> {
+        self.skip_whitespace()?;
+        if self.peek() != Some(b'"') {
+            return Err(self.syntax_error());
+        }
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            match self.peek() {
+                Some(b'"') if self.starts_with(b"\"\"") => {
+                    bytes.push(b'"');
+                    self.pos += 2;
+                }
+                Some(b'"') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(b) => {
+                    bytes.push(b);
+                    self.pos += 1;
+                }
+                None => return Err(ValueNotationError::UnexpectedEnd),
+            }
+        }
+        // input is a str, and the string boundaries are ASCII characters
+        String::from_utf8(bytes).map_err(|_| self.syntax_error())
+    }
+
+    /// Parse an object identifier component list, or return `None` if the input is not an object
+    /// identifier (the position is not modified in that case)
+    fn try_parse_oid_arcs(&mut self) -> ValueNotationResult<Option<Vec<u64>>> {
+        let start = self.pos;
+        let arcs = self.parse_oid_arcs();
+        match arcs {
+            Ok(arcs) => Ok(Some(arcs)),
+            Err(ValueNotationError::InvalidValue(pos)) => {
+                Err(ValueNotationError::InvalidValue(pos))
+            }
+            Err(_) => {
+                self.pos = start;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Parse `{ arc arc ... }`, where each arc is a number or an identifier followed by a number
+    /// in parentheses
+    fn parse_oid_arcs(&mut self) -> ValueNotationResult<Vec<u64>> {
+        self.expect(b'{')?;
+        let mut arcs = Vec::new();
+        loop {
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(arcs);
+                }
+                Some(b'0'..=b'9') => {
+                    let arc = self.parse_u64()?;
+                    arcs.push(arc);
+                }
+                Some(_) if self.next_is_identifier() => {
+                    self.parse_word()?;
+                    self.expect(b'(')?;
+                    let arc = self.parse_u64()?;
+                    self.expect(b')')?;
+                    arcs.push(arc);
+                }
+                _ => return Err(self.syntax_error()),
+            }
+        }
+    }
+
+    fn parse_oid(&mut self, relative: bool) -> ValueNotationResult<ParsedValue> {
+        self.skip_whitespace()?;
+        let start = self.pos;
+        let arcs = self.parse_oid_arcs()?;
+        let (oid, tag) = if relative {
+            (Oid::from_relative(&arcs), Tag::RelativeOid)
+        } else {
+            (Oid::from(&arcs), Tag::Oid)
+        };
+        let oid = oid.map_err(|_| ValueNotationError::InvalidValue(start))?;
+        Ok(ParsedValue::universal(tag, false, oid.as_bytes().to_vec()))
+    }
+
+    /// Parse an object identifier (if the list contains only arcs, and at least 2 arcs) or a
+    /// `SEQUENCE` value
+    fn parse_braces(&mut self, max_depth: usize) -> ValueNotationResult<ParsedValue> {
+        let start = self.pos;
+        if let Some(arcs) = self.try_parse_oid_arcs()? {
+            if arcs.len() >= 2 {
+                let oid = Oid::from(&arcs).map_err(|_| ValueNotationError::InvalidValue(start))?;
+                return Ok(ParsedValue::universal(
+                    Tag::Oid,
+                    false,
+                    oid.as_bytes().to_vec(),
+                ));
+            }
+            self.pos = start;
+        }
+        let items = self.parse_components(max_depth)?;
+        let mut content = Vec::new();
+        for item in &items {
+            item.der_encode(&mut content)
+                .map_err(|_| ValueNotationError::InvalidValue(start))?;
+        }
+        Ok(ParsedValue::universal(Tag::Sequence, true, content))
+    }
+
+    /// Parse `{ [identifier] value, ... }`
+    fn parse_components(&mut self, max_depth: usize) -> ValueNotationResult<Vec<ParsedValue>> {
+        self.expect(b'{')?;
+        let mut items = Vec::new();
+        self.skip_whitespace()?;
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(items);
+        }
+        loop {
+            self.skip_whitespace()?;
+            // component identifiers are ignored
+            if self.next_is_identifier() {
+                self.parse_word()?;
+            }
+            items.push(self.parse_value(max_depth - 1)?);
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(items);
+                }
+                _ => return Err(self.syntax_error()),
+            }
+        }
+    }
+
+    /// Parse a tagged value (for ex. `[APPLICATION 2] IMPLICIT value`)
+    fn parse_tagged(&mut self, max_depth: usize) -> ValueNotationResult<ParsedValue> {
+        let start = self.pos;
+        self.expect(b'[')?;
+        self.skip_whitespace()?;
+        let class = if matches!(self.peek(), Some(b) if b.is_ascii_alphabetic()) {
+            match self.parse_word()?.as_str() {
+                "UNIVERSAL" => Class::Universal,
+                "APPLICATION" => Class::Application,
+                "PRIVATE" => Class::Private,
+                _ => return Err(ValueNotationError::Syntax(start)),
+            }
+        } else {
+            Class::ContextSpecific
+        };
+        let tag = self.parse_u64()?;
+        let tag = u32::try_from(tag).map_err(|_| ValueNotationError::InvalidValue(start))?;
+        self.expect(b']')?;
+        self.skip_whitespace()?;
+        let mut implicit = false;
+        if matches!(self.peek(), Some(b'I' | b'E')) {
+            let pos = self.pos;
+            match self.parse_word()?.as_str() {
+                "IMPLICIT" => implicit = true,
+                "EXPLICIT" => (),
+                // this is a value
+                _ => self.pos = pos,
+            }
+        }
+        let inner = self.parse_value(max_depth - 1)?;
+        if implicit {
+            return Ok(ParsedValue {
+                class,
+                tag: Tag(tag),
+                ..inner
+            });
+        }
+        let mut content = Vec::new();
+        inner
+            .der_encode(&mut content)
+            .map_err(|_| ValueNotationError::InvalidValue(start))?;
+        Ok(ParsedValue {
+            class,
+            constructed: true,
+            tag: Tag(tag),
+            content,
+        })
+    }
+
+    /// Parse a value using the open type notation (`Type : value`)
+    fn parse_typed_value(&mut self, max_depth: usize) -> ValueNotationResult<ParsedValue> {
+        let start = self.pos;
+        let mut type_name = self.parse_word()?;
+        // type names with two words
+        if matches!(type_name.as_str(), "BIT" | "OCTET" | "OBJECT") {
+            self.skip_whitespace()?;
+            let second = self.parse_word()?;
+            type_name = format!("{type_name} {second}");
+        }
+        self.expect(b':')?;
+        self.skip_whitespace()?;
+        let value_start = self.pos;
+
+        let expect_tag = |value: ParsedValue, tag: Tag| {
+            if value.is_universal(tag) {
+                Ok(value)
+            } else {
+                Err(ValueNotationError::InvalidValue(value_start))
+            }
+        };
+
+        match type_name.as_str() {
+            "BOOLEAN" => expect_tag(self.parse_value(max_depth)?, Tag::Boolean),
+            "INTEGER" => expect_tag(self.parse_value(max_depth)?, Tag::Integer),
+            "BIT STRING" => expect_tag(self.parse_value(max_depth)?, Tag::BitString),
+            "OCTET STRING" => expect_tag(self.parse_value(max_depth)?, Tag::OctetString),
+            "NULL" => expect_tag(self.parse_value(max_depth)?, Tag::Null),
+            "OBJECT IDENTIFIER" => self.parse_oid(false),
+            "RELATIVE-OID" => self.parse_oid(true),
+            "REAL" => self.parse_real(),
+            "ENUMERATED" => {
+                let value = expect_tag(self.parse_value(max_depth)?, Tag::Integer)?;
+                Ok(ParsedValue {
+                    tag: Tag::Enumerated,
+                    ..value
+                })
+            }
+            "SEQUENCE" => expect_tag(self.parse_value(max_depth)?, Tag::Sequence),
+            "SET" => {
+                let value = expect_tag(self.parse_value(max_depth)?, Tag::Sequence)?;
+                Ok(ParsedValue {
+                    tag: Tag::Set,
+                    ..value
+                })
+            }
+            "UTF8String" => expect_tag(self.parse_value(max_depth)?, Tag::Utf8String),
+            "UTCTime" => {
+                let s = self.parse_cstring()?;
+                UtcTime::from_bytes(s.as_bytes())
+                    .map_err(|_| ValueNotationError::InvalidValue(value_start))?;
+                Ok(ParsedValue::universal(Tag::UtcTime, false, s.into_bytes()))
+            }
+            "GeneralizedTime" => {
+                let s = self.parse_cstring()?;
+                GeneralizedTime::from_bytes(s.as_bytes())
+                    .map_err(|_| ValueNotationError::InvalidValue(value_start))?;
+                Ok(ParsedValue::universal(
+                    Tag::GeneralizedTime,
+                    false,
+                    s.into_bytes(),
+                ))
+            }
+            "NumericString" => self.parse_string::<NumericString>(Tag::NumericString),
+            "PrintableString" => self.parse_string::<PrintableString>(Tag::PrintableString),
+            "TeletexString" | "T61String" => self.parse_string::<TeletexString>(Tag::TeletexString),
+            "VideotexString" => self.parse_string::<VideotexString>(Tag::VideotexString),
+            "IA5String" => self.parse_string::<Ia5String>(Tag::Ia5String),
+            "GraphicString" => self.parse_string::<GraphicString>(Tag::GraphicString),
+            "VisibleString" | "ISO646String" => {
+                self.parse_string::<VisibleString>(Tag::VisibleString)
+            }
+            "GeneralString" => self.parse_string::<GeneralString>(Tag::GeneralString),
+            "ObjectDescriptor" => self.parse_string::<ObjectDescriptor>(Tag::ObjectDescriptor),
+            "BMPString" => {
+                let s = self.parse_cstring()?;
+                let mut content = Vec::new();
+                for c in s.chars() {
+                    let c = u16::try_from(u32::from(c))
+                        .map_err(|_| ValueNotationError::StringInvalidCharset(value_start))?;
+                    content.extend_from_slice(&c.to_be_bytes());
+                }
+                Ok(ParsedValue::universal(Tag::BmpString, false, content))
+            }
+            "UniversalString" => {
+                let s = self.parse_cstring()?;
+                let content = s.chars().flat_map(|c| u32::from(c).to_be_bytes()).collect();
+                Ok(ParsedValue::universal(Tag::UniversalString, false, content))
+            }
+            _ => Err(ValueNotationError::UnknownType(
+                String::from_utf8_lossy(&self.input[start..value_start])
+                    .trim_end_matches(|c: char| c == ':' || c.is_whitespace())
+                    .to_string(),
+            )),
+        }
+    }
+
+    /// Parse a `cstring` for a string type with a restricted character set
+    fn parse_string<T>(&mut self, tag: Tag) -> ValueNotationResult<ParsedValue>
+    where
+        T: TestValidCharset,
+    {
+        self.skip_whitespace()?;
+        let start = self.pos;
+        let s = self.parse_cstring()?;
+        T::test_valid_charset(s.as_bytes())
+            .map_err(|_| ValueNotationError::StringInvalidCharset(start))?;
+        Ok(ParsedValue::universal(tag, false, s.into_bytes()))
+    }
+}
+
+/// Encode a decimal number as the content of an `INTEGER` (minimal two's complement)
+fn encode_integer(digits: &[u8], negative: bool) -> Vec<u8> {
+    // absolute value, big-endian, with a leading zero byte
+    let mut bytes = vec![0u8];
+    for d in digits {
+        let mut carry = u16::from(d - b'0');
+        for b in bytes.iter_mut().rev() {
+            let v = u16::from(*b) * 10 + carry;
+            *b = v as u8;
+            carry = v >> 8;
+        }
+        if carry > 0 {
+            bytes.insert(0, carry as u8);
+        }
+        if bytes[0] != 0 {
+            bytes.insert(0, 0);
+        }
+    }
+    if negative {
+        // two's complement
+        let mut carry = true;
+        for b in bytes.iter_mut().rev() {
+            *b = !*b;
+            if carry {
+                let (v, overflow) = b.overflowing_add(1);
+                *b = v;
+                carry = overflow;
+            }
+        }
+    }
+    // remove redundant leading bytes
+    let redundant = |bytes: &[u8]| {
+        bytes.len() > 1
+            && ((bytes[0] == 0x00 && bytes[1] & 0x80 == 0)
+                || (bytes[0] == 0xff && bytes[1] & 0x80 != 0))
+    };
+    let mut start = 0;
+    while redundant(&bytes[start..]) {
+        start += 1;
+    }
+    bytes.split_off(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::value_notation::{parse_value_notation, ToValueNotation};
+    use crate::{Any, FromBer, Tag, ToDer, ValueNotationError};
+
+    fn to_der(s: &str) -> Vec<u8> {
+        let value = parse_value_notation(s).expect("parsing failed");
+        value.to_der_vec().expect("serialization failed")
+    }
+
+    #[test]
+    fn parse_value_notation_primitive_types() {
+        assert_eq!(to_der("TRUE"), hex!("01 01 ff"));
+        assert_eq!(to_der("NULL"), hex!("05 00"));
+        assert_eq!(to_der("0"), hex!("02 01 00"));
+        assert_eq!(to_der("-0"), hex!("02 01 00"));
+        assert_eq!(to_der("127"), hex!("02 01 7f"));
+        assert_eq!(to_der("128"), hex!("02 02 00 80"));
+        assert_eq!(to_der("-128"), hex!("02 01 80"));
+        assert_eq!(to_der("-129"), hex!("02 02 ff 7f"));
+        assert_eq!(to_der("65537"), hex!("02 03 01 00 01"));
+        assert_eq!(
+            to_der("-340282366920938463463374607431768211456"),
+            hex!("02 11 ff 00000000 00000000 00000000 00000000")
+        );
+        assert_eq!(to_der("ENUMERATED : 2"), hex!("0a 01 02"));
+        assert_eq!(to_der("REAL : 0"), hex!("09 00"));
+        assert_eq!(to_der("PLUS-INFINITY"), hex!("09 01 40"));
+        assert_eq!(to_der("'0101'B"), hex!("03 02 04 50"));
+        assert_eq!(to_der("''B"), hex!("03 01 00"));
+        assert_eq!(to_der("'0A 0b'H"), hex!("04 02 0a 0b"));
+        assert_eq!(to_der(r#""a""b""#), hex!("0c 03 61 22 62"));
+        assert_eq!(to_der(r#"IA5String : "FR""#), hex!("16 02 46 52"));
+        assert_eq!(to_der(r#"BMPString : "é""#), hex!("1e 02 00 e9"));
+        assert_eq!(
+            to_der(r#"UTCTime : "021213142923Z""#),
+            hex!("17 0d 30 32 31 32 31 33 31 34 32 39 32 33 5a")
+        );
+        assert_eq!(
+            to_der("RELATIVE-OID : { 8571 3 2 }"),
+            hex!("0d 04 c2 7b 03 02")
+        );
+    }
+
+    #[test]
+    fn parse_value_notation_constructed() {
+        assert_eq!(
+            to_der("{ iso(1) member-body(2) us(840) 113549 1 1 11 }"),
+            hex!("06 09 2a 86 48 86 f7 0d 01 01 0b")
+        );
+        assert_eq!(to_der("{}"), hex!("30 00"));
+        assert_eq!(to_der("{ 1 }"), hex!("30 03 02 01 01"));
+        assert_eq!(
+            to_der("{ a 1, b { TRUE } }"),
+            hex!("30 08 02 01 01 30 03 01 01 ff")
+        );
+        assert_eq!(to_der("SET : { TRUE }"), hex!("31 03 01 01 ff"));
+        assert_eq!(to_der("[0] 2"), hex!("a0 03 02 01 02"));
+        assert_eq!(to_der("[APPLICATION 1] EXPLICIT 2"), hex!("61 03 02 01 02"));
+        assert_eq!(to_der("[1] IMPLICIT '0102'H"), hex!("81 02 01 02"));
+        assert_eq!(
+            to_der("[PRIVATE 2] IMPLICIT { 1, 2 }"),
+            hex!("e2 06 02 01 01 02 01 02")
+        );
+        assert_eq!(to_der("[UNIVERSAL 14] IMPLICIT '00'H"), hex!("0e 01 00"));
+        assert_eq!(
+            to_der("{ -- comment\n 1, -- another comment -- 2 }"),
+            hex!("30 06 02 01 01 02 01 02")
+        );
+    }
+
+    #[test]
+    fn parse_value_notation_errors() {
+        let err = parse_value_notation("{ 1, 2").expect_err("unexpected end");
+        assert_eq!(err, ValueNotationError::UnexpectedEnd);
+        let err = parse_value_notation("TRUE FALSE").expect_err("trailing data");
+        assert_eq!(err, ValueNotationError::Syntax(5));
+        let err = parse_value_notation("Foo : 1").expect_err("unknown type");
+        assert_eq!(err, ValueNotationError::UnknownType("Foo".into()));
+        let err = parse_value_notation(r#"PrintableString : "a@b""#).expect_err("charset");
+        assert_eq!(err, ValueNotationError::StringInvalidCharset(18));
+        let err = parse_value_notation("'012'H").expect_err("odd hstring");
+        assert_eq!(err, ValueNotationError::InvalidValue(0));
+        let err = parse_value_notation("INTEGER : TRUE").expect_err("type mismatch");
+        assert_eq!(err, ValueNotationError::InvalidValue(10));
+        let deep = "{ ".repeat(100) + &"}".repeat(100);
+        let err = parse_value_notation(&deep).expect_err("too deep");
+        assert_eq!(err, ValueNotationError::NestingTooDeep);
+    }
+
+    #[test]
+    fn parse_value_notation_roundtrip() {
+        let input = &hex!(
+            "30 2b a0 03 02 01 02 02 01 2a 30 0d 06 09 2a 86 48 86 f7 0d 01 01 0b 05 00
+             31 0b 30 09 06 03 55 04 06 13 02 46 52 81 02 01 02 0a 01 01"
+        );
+        let (rem, any) = Any::from_ber(input).expect("parsing failed");
+        assert!(rem.is_empty());
+        let s = any.to_value_notation();
+        assert_eq!(
+            s,
+            r#"{ [0] 2, 42, { { 1 2 840 113549 1 1 11 }, NULL }, SET : { { { 2 5 4 6 }, PrintableString : "FR" } }, [1] IMPLICIT '0102'H, ENUMERATED : 1 }"#
+        );
+        let value = parse_value_notation(&s).expect("parsing failed");
+        assert_eq!(value.tag(), Tag::Sequence);
+        assert_eq!(value.any().data.as_bytes2(), any.data.as_bytes2());
+        assert_eq!(value.to_der_vec().expect("serialization failed"), input);
+    }
+}
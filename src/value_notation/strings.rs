@@ -0,0 +1,60 @@
+use alloc::string::String;
+use core::fmt;
+
+use crate::{
+    BmpString, GeneralString, GraphicString, Ia5String, NumericString, ObjectDescriptor,
+    PrintableString, TeletexString, UniversalString, Utf8String, VideotexString, VisibleString,
+};
+
+use super::ToValueNotation;
+
+/// Character strings are written as a `cstring` (X.680: 12.14), where quotation marks are doubled
+impl ToValueNotation for str {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        f.write_char('"')?;
+        for (i, part) in self.split('"').enumerate() {
+            if i > 0 {
+                f.write_str("\"\"")?;
+            }
+            f.write_str(part)?;
+        }
+        f.write_char('"')
+    }
+}
+
+impl ToValueNotation for String {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.as_str().write_value(f)
+    }
+}
+
+macro_rules! impl_value_notation_string {
+    ($($name:ident)*) => {
+        $(
+            impl ToValueNotation for $name<'_> {
+                fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+                    self.data.write_value(f)
+                }
+            }
+        )*
+    };
+}
+
+impl_value_notation_string!(
+    BmpString GeneralString GraphicString Ia5String NumericString ObjectDescriptor PrintableString
+    TeletexString UniversalString Utf8String VideotexString VisibleString
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::value_notation::ToValueNotation;
+    use crate::*;
+
+    #[test]
+    fn value_notation_strings() {
+        assert_eq!("abc".to_value_notation(), r#""abc""#);
+        assert_eq!(r#""a"b""#.to_value_notation(), r#""""a""b""""#);
+        assert_eq!(PrintableString::from("FR").to_value_notation(), r#""FR""#);
+        assert_eq!(BmpString::from("é").to_value_notation(), r#""é""#);
+    }
+}
@@ -0,0 +1,88 @@
+use alloc::string::String;
+use core::fmt;
+
+/// Common trait for writing values using the ASN.1 value notation
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::value_notation::ToValueNotation;
+/// use asn1_rs::Utf8String;
+///
+/// let s = Utf8String::from("a \"quoted\" word");
+/// assert_eq!(s.to_value_notation(), r#""a ""quoted"" word""#);
+///
+/// // `value_notation` can be used in format strings
+/// let v = vec![true, false];
+/// assert_eq!(format!("v = {}", v.value_notation()), "v = { TRUE, FALSE }");
+/// ```
+pub trait ToValueNotation {
+    /// Write the value notation of this value to `f`
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// Returns the value notation of this value
+    fn to_value_notation(&self) -> String {
+        let mut s = String::new();
+        // writing to a `String` cannot fail
+        let _ = self.write_value(&mut s);
+        s
+    }
+
+    /// Returns an object implementing `Display` using the value notation of this value
+    fn value_notation(&self) -> ValueNotation<'_, Self>
+    where
+        Self: Sized,
+    {
+        ValueNotation(self)
+    }
+}
+
+/// Helper to display a value using the ASN.1 value notation (see [`ToValueNotation`])
+#[derive(Debug)]
+pub struct ValueNotation<'a, T: ?Sized>(&'a T);
+
+impl<T: ToValueNotation + ?Sized> fmt::Display for ValueNotation<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write_value(f)
+    }
+}
+
+/// Writer for the components of `SEQUENCE`, `SET`, `SEQUENCE OF` and `SET OF` values
+///
+/// Components are separated by commas, and enclosed in braces (for ex. `{ a 1, b TRUE }`). An
+/// empty list is written as `{}`.
+pub struct ComponentsWriter<'w> {
+    f: &'w mut dyn fmt::Write,
+    empty: bool,
+}
+
+impl<'w> ComponentsWriter<'w> {
+    /// Create a new writer for a list of components
+    pub fn new(f: &'w mut dyn fmt::Write) -> Self {
+        ComponentsWriter { f, empty: true }
+    }
+
+    /// Write a component, preceded by its identifier if `name` is not `None`
+    pub fn component(&mut self, name: Option<&str>, value: &dyn ToValueNotation) -> fmt::Result {
+        self.f.write_str(if self.empty { "{ " } else { ", " })?;
+        self.empty = false;
+        if let Some(name) = name {
+            self.f.write_str(name)?;
+            self.f.write_char(' ')?;
+        }
+        value.write_value(self.f)
+    }
+
+    /// Write the end of the list
+    pub fn finish(self) -> fmt::Result {
+        self.f.write_str(if self.empty { "{}" } else { " }" })
+    }
+}
+
+impl fmt::Debug for ComponentsWriter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComponentsWriter")
+            .field("empty", &self.empty)
+            .finish_non_exhaustive()
+    }
+}
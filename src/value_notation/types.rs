@@ -0,0 +1,308 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{
+    BitString, Boolean, Enumerated, GeneralizedTime, Integer, Null, OctetString, Oid, Real,
+    SequenceOf, SetOf, TaggedValue, UtcTime,
+};
+
+use super::{ComponentsWriter, ToValueNotation};
+
+/// Write a string of bytes as a `hstring` (for ex. `'0A0B'H`)
+pub(crate) fn write_hstring(f: &mut dyn fmt::Write, bytes: &[u8]) -> fmt::Result {
+    f.write_char('\'')?;
+    for b in bytes {
+        write!(f, "{b:02X}")?;
+    }
+    f.write_str("'H")
+}
+
+/// Write a signed big-endian integer of any size in decimal
+fn write_integer_bytes(f: &mut dyn fmt::Write, bytes: &[u8]) -> fmt::Result {
+    let negative = matches!(bytes.first(), Some(b) if b & 0x80 != 0);
+    // compute the absolute value (two's complement)
+    let mut magnitude = bytes.to_vec();
+    if negative {
+        let mut carry = true;
+        for b in magnitude.iter_mut().rev() {
+            *b = !*b;
+            if carry {
+                let (v, overflow) = b.overflowing_add(1);
+                *b = v;
+                carry = overflow;
+            }
+        }
+    }
+    // repeated divisions by 10
+    let mut digits = Vec::new();
+    while magnitude.iter().any(|&b| b != 0) {
+        let mut remainder = 0u16;
+        for b in magnitude.iter_mut() {
+            let v = (remainder << 8) | u16::from(*b);
+            *b = (v / 10) as u8;
+            remainder = v % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+    }
+    if digits.is_empty() {
+        return f.write_char('0');
+    }
+    if negative {
+        f.write_char('-')?;
+    }
+    for d in digits.iter().rev() {
+        f.write_char(char::from(*d))?;
+    }
+    Ok(())
+}
+
+//---- BOOLEAN (X.680: 18.3)
+
+impl ToValueNotation for bool {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        f.write_str(if *self { "TRUE" } else { "FALSE" })
+    }
+}
+
+impl ToValueNotation for Boolean {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.bool().write_value(f)
+    }
+}
+
+//---- NULL (X.680: 24.3)
+
+impl ToValueNotation for Null {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        f.write_str("NULL")
+    }
+}
+
+impl ToValueNotation for () {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        f.write_str("NULL")
+    }
+}
+
+//---- INTEGER (X.680: 19.9) and ENUMERATED (X.680: 20.8)
+
+macro_rules! impl_value_notation_int {
+    ($($ty:ty)*) => {
+        $(
+            impl ToValueNotation for $ty {
+                fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+                    write!(f, "{self}")
+                }
+            }
+        )*
+    };
+}
+
+impl_value_notation_int!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+impl ToValueNotation for Integer<'_> {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write_integer_bytes(f, &self.data)
+    }
+}
+
+/// Without the definition of the type, values are written as numbers
+impl ToValueNotation for Enumerated {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.0.write_value(f)
+    }
+}
+
+//---- REAL (X.680: 21.6)
+
+/// Values are written as decimal numbers (for ex. `1.5` or `1e300`), or using the special values
+/// `PLUS-INFINITY`, `MINUS-INFINITY` and `NOT-A-NUMBER`
+impl ToValueNotation for Real {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        match self {
+            Real::Zero => f.write_char('0'),
+            Real::Infinity => f.write_str("PLUS-INFINITY"),
+            Real::NegInfinity => f.write_str("MINUS-INFINITY"),
+            Real::NaN => f.write_str("NOT-A-NUMBER"),
+            Real::Binary { .. } => write!(f, "{:?}", self.f64()),
+        }
+    }
+}
+
+//---- BIT STRING (X.680: 22.9)
+
+/// Values are written as a `bstring` (for ex. `'0101'B`)
+impl ToValueNotation for BitString {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        f.write_char('\'')?;
+        for i in 0..self.len() {
+            f.write_char(if self.is_set(i) { '1' } else { '0' })?;
+        }
+        f.write_str("'B")
+    }
+}
+
+//---- OCTET STRING (X.680: 23.3)
+
+/// Values are written as a `hstring` (for ex. `'0A0B'H`)
+impl ToValueNotation for OctetString<'_> {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write_hstring(f, self.as_ref())
+    }
+}
+
+//---- OBJECT IDENTIFIER (X.680: 32.3) and RELATIVE-OID (X.680: 33.3)
+
+/// Values are written as a list of numbers (for ex. `{ 1 2 840 113549 }`)
+impl ToValueNotation for Oid<'_> {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        f.write_str("{ ")?;
+        match self.iter() {
+            Some(arcs) => {
+                for arc in arcs {
+                    write!(f, "{arc} ")?;
+                }
+            }
+            None => {
+                for arc in self.to_id_string().split('.') {
+                    write!(f, "{arc} ")?;
+                }
+            }
+        }
+        f.write_char('}')
+    }
+}
+
+//---- UTCTime and GeneralizedTime (X.680: 46, 47)
+
+/// Values are written as strings, containing the characters of their BER encoding
+impl ToValueNotation for UtcTime {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.to_value_string().as_str().write_value(f)
+    }
+}
+
+impl ToValueNotation for GeneralizedTime {
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.to_value_string().as_str().write_value(f)
+    }
+}
+
+//---- SEQUENCE OF (X.680: 25.3) and SET OF (X.680: 28.3)
+
+impl<T> ToValueNotation for [T]
+where
+    T: ToValueNotation,
+{
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        let mut writer = ComponentsWriter::new(f);
+        for item in self {
+            writer.component(None, item)?;
+        }
+        writer.finish()
+    }
+}
+
+impl<T> ToValueNotation for Vec<T>
+where
+    T: ToValueNotation,
+{
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.as_slice().write_value(f)
+    }
+}
+
+impl<T> ToValueNotation for SequenceOf<T>
+where
+    T: ToValueNotation,
+{
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.as_ref().write_value(f)
+    }
+}
+
+impl<T> ToValueNotation for SetOf<T>
+where
+    T: ToValueNotation,
+{
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.as_ref().write_value(f)
+    }
+}
+
+//---- Tagged values
+
+/// Tags are not part of the value notation: the inner value is written
+impl<T, E, TagKind, const CLASS: u8, const TAG: u32> ToValueNotation
+    for TaggedValue<T, E, TagKind, CLASS, TAG>
+where
+    T: ToValueNotation,
+{
+    fn write_value(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.inner.write_value(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use hex_literal::hex;
+
+    use crate::value_notation::ToValueNotation;
+    use crate::*;
+
+    #[test]
+    fn value_notation_primitive_types() {
+        assert_eq!(true.to_value_notation(), "TRUE");
+        assert_eq!(Boolean::FALSE.to_value_notation(), "FALSE");
+        assert_eq!(Null::new().to_value_notation(), "NULL");
+        assert_eq!((-5i8).to_value_notation(), "-5");
+        assert_eq!(Integer::from(65537).to_value_notation(), "65537");
+        assert_eq!(Integer::new(&[]).to_value_notation(), "0");
+        assert_eq!(Integer::from(-129).to_value_notation(), "-129");
+        // integers larger than 128 bits
+        let i = Integer::new(&hex!("01 00000000 00000000 00000000 00000000"));
+        assert_eq!(
+            i.to_value_notation(),
+            "340282366920938463463374607431768211456"
+        );
+        let i = Integer::new(&hex!("ff 00000000 00000000 00000000 00000000"));
+        assert_eq!(
+            i.to_value_notation(),
+            "-340282366920938463463374607431768211456"
+        );
+        assert_eq!(Enumerated::new(3).to_value_notation(), "3");
+        assert_eq!(Real::new(1.5).to_value_notation(), "1.5");
+        assert_eq!(Real::Zero.to_value_notation(), "0");
+        assert_eq!(Real::NegInfinity.to_value_notation(), "MINUS-INFINITY");
+    }
+
+    #[test]
+    fn value_notation_strings_of_bits_and_octets() {
+        assert_eq!(BitString::new(4, &[0x50]).to_value_notation(), "'0101'B");
+        assert_eq!(BitString::default().to_value_notation(), "''B");
+        assert_eq!(
+            OctetString::new(&[0x0a, 0x0b]).to_value_notation(),
+            "'0A0B'H"
+        );
+        assert_eq!(OctetString::new(&[]).to_value_notation(), "''H");
+    }
+
+    #[test]
+    fn value_notation_constructed() {
+        assert_eq!(
+            oid!(1.2.840 .113549 .1 .1 .11).to_value_notation(),
+            "{ 1 2 840 113549 1 1 11 }"
+        );
+        assert_eq!(vec![1u8, 2, 3].to_value_notation(), "{ 1, 2, 3 }");
+        assert_eq!(Vec::<u8>::new().to_value_notation(), "{}");
+        let s = SetOf::new(vec![vec![true], vec![]]);
+        assert_eq!(s.to_value_notation(), "{ { TRUE }, {} }");
+        let t = TaggedExplicit::<u32, Error, 0>::explicit(4);
+        assert_eq!(t.to_value_notation(), "4");
+        let t = UtcTime::from_bytes(b"021213142923Z").expect("invalid time");
+        assert_eq!(t.to_value_notation(), r#""021213142923Z""#);
+    }
+}
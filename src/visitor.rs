@@ -0,0 +1,262 @@
+use nom::{Err, IResult};
+
+use crate::ber::GetObjectContent;
+use crate::{BerError, BerMode, BerParser, DerMode, DerParser, Header, InnerError, Input};
+
+/// Value returned by [`Visitor`] callbacks, to control the walk
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Visit {
+    /// Continue walking
+    Continue,
+    /// Do not visit the content of the current constructed object
+    ///
+    /// This is only meaningful for [`Visitor::start_constructed`] (it is the same as `Continue`
+    /// for other callbacks). `end_constructed` is not called for this object.
+    Skip,
+    /// Stop walking
+    Stop,
+}
+
+/// Callbacks called when walking BER/DER objects (see [`visit_ber`] and [`visit_der`])
+///
+/// All methods have a default implementation returning [`Visit::Continue`], so visitors only
+/// implement the callbacks they need.
+///
+/// Objects are visited in order, without building `Any` objects or collections: content of
+/// primitive objects is a reference to the input.
+pub trait Visitor<'i> {
+    /// Called at the start of a constructed object, before its sub-objects
+    fn start_constructed(&mut self, _header: &Header<'i>) -> Visit {
+        Visit::Continue
+    }
+
+    /// Called for a primitive object, with its content
+    fn primitive(&mut self, _header: &Header<'i>, _content: Input<'i>) -> Visit {
+        Visit::Continue
+    }
+
+    /// Called at the end of a constructed object, after its sub-objects
+    fn end_constructed(&mut self, _header: &Header<'i>) -> Visit {
+        Visit::Continue
+    }
+}
+
+/// Walk the next BER object, calling `visitor` for each object and sub-object
+///
+/// Return the remaining input (after the object), and [`Visit::Stop`] if the visitor stopped
+/// the walk, or [`Visit::Continue`].
+///
+/// Sub-objects are walked recursively, up to the maximum depth of the input
+/// [`Limits`](crate::Limits) (default: [`MAX_RECURSION`](crate::MAX_RECURSION) levels). Only
+/// headers are parsed: this function does not allocate.
+///
+/// # Examples
+///
+/// Collect the values of all INTEGER objects in a structure:
+///
+/// ```rust
+/// use asn1_rs::{visit_ber, Header, Input, Tag, Visit, Visitor};
+/// use hex_literal::hex;
+///
+/// struct Integers(Vec<u8>);
+///
+/// impl<'i> Visitor<'i> for Integers {
+///     fn start_constructed(&mut self, header: &Header<'i>) -> Visit {
+///         // do not look into SET objects
+///         if header.tag() == Tag::Set {
+///             Visit::Skip
+///         } else {
+///             Visit::Continue
+///         }
+///     }
+///
+///     fn primitive(&mut self, header: &Header<'i>, content: Input<'i>) -> Visit {
+///         if header.tag() == Tag::Integer {
+///             self.0.extend_from_slice(content.as_bytes2());
+///         }
+///         Visit::Continue
+///     }
+/// }
+///
+/// let input = Input::from_slice(&hex!("30 0f 02 01 01 30 03 02 01 02 31 80 02 01 03 00 00"));
+/// let mut visitor = Integers(Vec::new());
+/// let (rem, _) = visit_ber(input, &mut visitor).expect("parsing failed");
+/// assert!(rem.is_empty());
+/// assert_eq!(visitor.0, [1, 2]);
+/// ```
+pub fn visit_ber<'i, V>(
+    input: Input<'i>,
+    visitor: &mut V,
+) -> IResult<Input<'i>, Visit, BerError<Input<'i>>>
+where
+    V: Visitor<'i>,
+{
+    let max_depth = input.limits().max_depth();
+    visit_object::<BerMode, V>(input, visitor, max_depth)
+}
+
+/// Walk the next DER object, calling `visitor` for each object and sub-object
+///
+/// See [`visit_ber`]. Objects with indefinite length are rejected.
+pub fn visit_der<'i, V>(
+    input: Input<'i>,
+    visitor: &mut V,
+) -> IResult<Input<'i>, Visit, BerError<Input<'i>>>
+where
+    V: Visitor<'i>,
+{
+    let max_depth = input.limits().max_depth();
+    visit_object::<DerMode, V>(input, visitor, max_depth)
+}
+
+/// Parser of headers for a given encoding
+trait VisitMode: GetObjectContent {
+    fn parse_header(input: Input<'_>) -> IResult<Input<'_>, Header<'_>, BerError<Input<'_>>>;
+}
+
+impl VisitMode for BerMode {
+    fn parse_header(input: Input<'_>) -> IResult<Input<'_>, Header<'_>, BerError<Input<'_>>> {
+        Header::parse_ber(input)
+    }
+}
+
+impl VisitMode for DerMode {
+    fn parse_header(input: Input<'_>) -> IResult<Input<'_>, Header<'_>, BerError<Input<'_>>> {
+        Header::parse_der(input)
+    }
+}
+
+fn visit_object<'i, M, V>(
+    input: Input<'i>,
+    visitor: &mut V,
+    max_depth: usize,
+) -> IResult<Input<'i>, Visit, BerError<Input<'i>>>
+where
+    M: VisitMode,
+    V: Visitor<'i>,
+{
+    if max_depth == 0 {
+        return Err(Err::Error(BerError::new(input, InnerError::BerMaxDepth)));
+    }
+    let (rem, header) = M::parse_header(input)?;
+    let (rem, mut content) = M::get_object_content(&header, rem, max_depth)?;
+    if !header.is_constructed() {
+        let visit = match visitor.primitive(&header, content) {
+            Visit::Stop => Visit::Stop,
+            _ => Visit::Continue,
+        };
+        return Ok((rem, visit));
+    }
+    match visitor.start_constructed(&header) {
+        Visit::Continue => (),
+        Visit::Skip => return Ok((rem, Visit::Continue)),
+        Visit::Stop => return Ok((rem, Visit::Stop)),
+    }
+    while !content.is_empty() {
+        let (i, visit) = visit_object::<M, V>(content, visitor, max_depth - 1)?;
+        if visit == Visit::Stop {
+            return Ok((rem, Visit::Stop));
+        }
+        content = i;
+    }
+    let visit = match visitor.end_constructed(&header) {
+        Visit::Stop => Visit::Stop,
+        _ => Visit::Continue,
+    };
+    Ok((rem, visit))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use hex_literal::hex;
+
+    use crate::{visit_ber, visit_der, Header, InnerError, Input, Tag, Visit, Visitor};
+
+    #[derive(Default)]
+    struct Events {
+        events: Vec<String>,
+        skip: Option<Tag>,
+        stop: Option<Tag>,
+    }
+
+    impl<'i> Visitor<'i> for Events {
+        fn start_constructed(&mut self, header: &Header<'i>) -> Visit {
+            self.events.push(format!("C{}", header.tag().0));
+            if self.skip == Some(header.tag()) {
+                return Visit::Skip;
+            }
+            Visit::Continue
+        }
+
+        fn primitive(&mut self, header: &Header<'i>, content: Input<'i>) -> Visit {
+            self.events
+                .push(format!("P{}:{:02x?}", header.tag().0, content.as_bytes2()));
+            if self.stop == Some(header.tag()) {
+                return Visit::Stop;
+            }
+            Visit::Continue
+        }
+
+        fn end_constructed(&mut self, header: &Header<'i>) -> Visit {
+            self.events.push(format!("E{}", header.tag().0));
+            Visit::Continue
+        }
+    }
+
+    #[test]
+    fn visit_events() {
+        let data = hex!("30 80 02 01 01 31 06 04 01 aa 04 01 bb 24 80 04 01 cc 00 00 00 00 05 00");
+        let mut visitor = Events::default();
+        let (rem, visit) = visit_ber(Input::from(&data), &mut visitor).expect("parsing failed");
+        assert_eq!(visit, Visit::Continue);
+        assert_eq!(rem.as_bytes2(), &hex!("05 00"));
+        assert_eq!(
+            visitor.events,
+            ["C16", "P2:[01]", "C17", "P4:[aa]", "P4:[bb]", "E17", "C4", "P4:[cc]", "E4", "E16"]
+        );
+
+        // skip content of SET
+        let mut visitor = Events {
+            skip: Some(Tag::Set),
+            ..Default::default()
+        };
+        let (rem, _) = visit_ber(Input::from(&data), &mut visitor).expect("parsing failed");
+        assert_eq!(rem.as_bytes2(), &hex!("05 00"));
+        assert_eq!(
+            visitor.events,
+            ["C16", "P2:[01]", "C17", "C4", "P4:[cc]", "E4", "E16"]
+        );
+
+        // stop at first OCTET STRING, remaining input is after the top-level object
+        let mut visitor = Events {
+            stop: Some(Tag::OctetString),
+            ..Default::default()
+        };
+        let (rem, visit) = visit_ber(Input::from(&data), &mut visitor).expect("parsing failed");
+        assert_eq!(visit, Visit::Stop);
+        assert_eq!(rem.as_bytes2(), &hex!("05 00"));
+        assert_eq!(visitor.events, ["C16", "P2:[01]", "C17", "P4:[aa]"]);
+    }
+
+    #[test]
+    fn visit_errors() {
+        let mut visitor = Events::default();
+        // DER: indefinite length
+        let r = visit_der(Input::from(&hex!("30 80 00 00")), &mut visitor);
+        assert!(r.is_err());
+        // sub-object larger than parent
+        let r = visit_der(Input::from(&hex!("30 03 04 02 aa bb")), &mut visitor);
+        assert!(matches!(r, Err(nom::Err::Incomplete(_))));
+        // maximum depth
+        let mut data = Vec::new();
+        for _ in 0..100 {
+            data.extend_from_slice(&[0x30, 0x80]);
+        }
+        data.extend_from_slice(&[0; 200]);
+        let r = visit_ber(Input::from(&data), &mut visitor);
+        assert!(matches!(r, Err(nom::Err::Error(e)) if *e.inner() == InnerError::BerMaxDepth));
+    }
+}
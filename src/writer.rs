@@ -0,0 +1,210 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{SerializeError, SerializeResult};
+
+/// Common trait for objects receiving encoded bytes
+///
+/// This trait is used as target by all encoders ([`ToBer`](crate::ToBer),
+/// [`ToDer`](crate::ToDer), [`BerEncoder`](crate::BerEncoder), etc.). Unlike `std::io::Write`,
+/// it is available in `no_std` environments.
+///
+/// It is implemented for:
+/// - `Vec<u8>`: encoded bytes are appended
+/// - `&mut [u8]`: encoded bytes are written to the start of the slice, which is then advanced
+///   past the written bytes (so it contains the unused part of the buffer). If the slice is too
+///   small, [`SerializeError::BufferOverflow`] is returned and nothing is written
+/// - [`IoWriter`] (with feature `std`): adaptor for objects implementing `std::io::Write`
+///
+/// # Examples
+///
+/// Encoding to a fixed buffer (without allocation):
+///
+/// ```
+/// use asn1_rs::ToDer;
+///
+/// let mut buffer = [0u8; 16];
+/// let mut target = &mut buffer[..];
+/// let sz = 4.der_encode(&mut target).expect("serialization failed");
+/// assert_eq!(target.len(), 13);
+/// assert_eq!(&buffer[..sz], &[0x02, 0x01, 0x04]);
+/// ```
+pub trait Writer {
+    /// Write all bytes from `buf` to this writer
+    fn write_all(&mut self, buf: &[u8]) -> SerializeResult<()>;
+
+    /// Write formatted string to this writer
+    ///
+    /// This method is used by the `write!` macro.
+    ///
+    /// Errors returned by the writer are forwarded. If formatting fails for another reason (a
+    /// `Display` or `Debug` implementation returning an error), [`SerializeError::FormatError`]
+    /// is returned.
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> SerializeResult<()> {
+        // adaptor to forward `fmt::Write` to `Writer`, keeping the first error
+        struct Adapter<'a, W: ?Sized> {
+            inner: &'a mut W,
+            error: SerializeResult<()>,
+        }
+
+        impl<W: Writer + ?Sized> fmt::Write for Adapter<'_, W> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.inner.write_all(s.as_bytes()).map_err(|e| {
+                    self.error = Err(e);
+                    fmt::Error
+                })
+            }
+        }
+
+        let mut adapter = Adapter {
+            inner: self,
+            error: Ok(()),
+        };
+        match fmt::write(&mut adapter, args) {
+            Ok(()) => Ok(()),
+            // error raised by a formatting trait implementation, not by the writer
+            Err(_) => adapter.error.and(Err(SerializeError::FormatError)),
+        }
+    }
+}
+
+impl<W: Writer + ?Sized> Writer for &mut W {
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> SerializeResult<()> {
+        (**self).write_all(buf)
+    }
+}
+
+impl Writer for Vec<u8> {
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> SerializeResult<()> {
+        self.extend_from_slice(buf);
+        Ok(())
+    }
+}
+
+impl Writer for &mut [u8] {
+    fn write_all(&mut self, buf: &[u8]) -> SerializeResult<()> {
+        if buf.len() > self.len() {
+            return Err(SerializeError::BufferOverflow);
+        }
+        let (head, tail) = core::mem::take(self).split_at_mut(buf.len());
+        head.copy_from_slice(buf);
+        *self = tail;
+        Ok(())
+    }
+}
+
+/// Adaptor to use an object implementing `std::io::Write` as a [`Writer`]
+///
+/// I/O errors are returned as [`SerializeError::IOError`].
+///
+/// # Examples
+///
+/// ```
+/// use asn1_rs::{IoWriter, ToDer};
+///
+/// let mut writer = IoWriter::new(std::io::Cursor::new(Vec::new()));
+/// let sz = 4.der_encode(&mut writer).expect("serialization failed");
+/// assert_eq!(writer.into_inner().into_inner(), &[0x02, 0x01, 0x04]);
+/// # assert_eq!(sz, 3);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct IoWriter<W>(W);
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> IoWriter<W> {
+    /// Build a new `IoWriter`, writing to `inner`
+    pub const fn new(inner: W) -> Self {
+        IoWriter(inner)
+    }
+
+    /// Get a reference to the underlying writer
+    pub fn get_ref(&self) -> &W {
+        &self.0
+    }
+
+    /// Get a mutable reference to the underlying writer
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.0
+    }
+
+    /// Consume the adaptor, and return the underlying writer
+    pub fn into_inner(self) -> W {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Writer for IoWriter<W> {
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> SerializeResult<()> {
+        Ok(self.0.write_all(buf)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use crate::*;
+
+    #[test]
+    fn writer_slice() {
+        let mut buffer = [0u8; 8];
+        let mut target = &mut buffer[..];
+        target.write_all(&hex!("0101")).expect("write failed");
+        target.write_all(&hex!("ff")).expect("write failed");
+        assert_eq!(target.len(), 5);
+        let e = target.write_all(&[0; 6]).expect_err("overflow");
+        assert!(matches!(e, SerializeError::BufferOverflow));
+        assert_eq!(target.len(), 5);
+        assert_eq!(&buffer[..3], &hex!("0101ff"));
+
+        // encoding larger than buffer
+        let mut buffer = [0u8; 4];
+        let e = "abcd"
+            .der_encode(&mut &mut buffer[..])
+            .expect_err("overflow");
+        assert!(matches!(e, SerializeError::BufferOverflow));
+    }
+
+    #[test]
+    fn writer_fmt() {
+        struct Failing;
+
+        impl core::fmt::Display for Failing {
+            fn fmt(&self, _f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                Err(core::fmt::Error)
+            }
+        }
+
+        let mut buffer = [0u8; 4];
+        let mut target = &mut buffer[..];
+        write!(target, "{}", 12).expect("write failed");
+        assert_eq!(&buffer[..2], b"12");
+
+        // writer error is forwarded
+        let mut target = &mut buffer[..];
+        let e = write!(target, "{}", 12345).expect_err("overflow");
+        assert!(matches!(e, SerializeError::BufferOverflow));
+
+        // formatting error
+        let mut target = &mut buffer[..];
+        let e = write!(target, "{}", Failing).expect_err("formatting error");
+        assert!(matches!(e, SerializeError::FormatError));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn writer_io() {
+        let mut writer = IoWriter::new(std::io::Cursor::new([0u8; 2]));
+        true.der_encode(&mut writer).expect_err("I/O error");
+        let mut writer = IoWriter::new(Vec::new());
+        let sz = true.der_encode(&mut writer).expect("serialization failed");
+        assert_eq!(sz, 3);
+        assert_eq!(writer.get_ref(), &hex!("0101ff"));
+    }
+}
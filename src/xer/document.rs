@@ -0,0 +1,377 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{XerError, XerResult};
+
+/// Maximum nesting level of elements accepted by [`XmlElement::parse`]
+const MAX_DEPTH: usize = 128;
+
+/// Names of the empty elements used for control characters in XML value notation (X.680: 12.15.5)
+pub(crate) const CONTROL_CHARACTER_NAMES: [&str; 32] = [
+    "nul", "soh", "stx", "etx", "eot", "enq", "ack", "bel", "bs", "ht", "lf", "vt", "ff", "cr",
+    "so", "si", "dle", "dc1", "dc2", "dc3", "dc4", "nak", "syn", "etb", "can", "em", "sub", "esc",
+    "is4", "is3", "is2", "is1",
+];
+
+/// A node of an XML element content
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum XmlNode {
+    Element(XmlElement),
+    /// Character data, with references and entities already replaced
+    Text(String),
+}
+
+/// An XML element
+///
+/// This is a minimal representation of XML documents, used as the source of XER decoders.
+/// Attributes, comments and processing instructions are ignored when parsing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XmlElement {
+    pub name: String,
+    pub content: Vec<XmlNode>,
+}
+
+impl XmlElement {
+    /// Parse a complete XML document, and return its root element
+    ///
+    /// The document can start with an XML declaration. Any trailing data other than whitespace,
+    /// comments or processing instructions is an error.
+    pub fn parse(s: &str) -> XerResult<Self> {
+        let mut parser = XmlParser {
+            input: s.as_bytes(),
+            pos: 0,
+        };
+        parser.skip_misc()?;
+        let root = parser.parse_element(0)?;
+        parser.skip_misc()?;
+        if parser.pos != parser.input.len() {
+            return Err(parser.error());
+        }
+        Ok(root)
+    }
+}
+
+/// Returns `true` if the node is text containing only whitespace
+pub(crate) fn is_whitespace(node: &XmlNode) -> bool {
+    matches!(node, XmlNode::Text(t) if t.bytes().all(|b| b.is_ascii_whitespace()))
+}
+
+/// Returns the child elements of `content`
+///
+/// Whitespace between elements is ignored. Any other text is an error.
+pub fn child_elements(content: &[XmlNode]) -> XerResult<Vec<&XmlElement>> {
+    content
+        .iter()
+        .filter(|node| !is_whitespace(node))
+        .map(|node| match node {
+            XmlNode::Element(e) => Ok(e),
+            XmlNode::Text(_) => Err(XerError::UnexpectedContent),
+        })
+        .collect()
+}
+
+/// Returns the first child element of `content` with the given name
+pub fn find_element<'a>(content: &'a [XmlNode], name: &str) -> Option<&'a XmlElement> {
+    content.iter().find_map(|node| match node {
+        XmlNode::Element(e) if e.name == name => Some(e),
+        _ => None,
+    })
+}
+
+/// Returns the first child element of `content` with the given name, or an error if not found
+pub fn get_element<'a>(content: &'a [XmlNode], name: &str) -> XerResult<&'a XmlElement> {
+    find_element(content, name).ok_or_else(|| XerError::MissingElement(name.into()))
+}
+
+/// Returns the single child element of `content`
+///
+/// Whitespace around the element is ignored.
+pub fn single_element(content: &[XmlNode]) -> XerResult<&XmlElement> {
+    match child_elements(content)?.as_slice() {
+        [e] => Ok(e),
+        _ => Err(XerError::UnexpectedContent),
+    }
+}
+
+/// Returns the text of `content`
+///
+/// Control characters are decoded from their empty element form (for ex. `<nul/>`). Any other
+/// element is an error.
+pub fn text_content(content: &[XmlNode]) -> XerResult<String> {
+    let mut s = String::new();
+    for node in content {
+        match node {
+            XmlNode::Text(t) => s.push_str(t),
+            XmlNode::Element(e) if e.content.is_empty() => {
+                let index = CONTROL_CHARACTER_NAMES
+                    .iter()
+                    .position(|name| *name == e.name)
+                    .ok_or(XerError::UnexpectedContent)?;
+                s.push(char::from(index as u8));
+            }
+            XmlNode::Element(_) => return Err(XerError::UnexpectedContent),
+        }
+    }
+    Ok(s)
+}
+
+struct XmlParser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl XmlParser<'_> {
+    fn error(&self) -> XerError {
+        XerError::InvalidXml(self.pos)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn starts_with(&self, s: &[u8]) -> bool {
+        self.input[self.pos..].starts_with(s)
+    }
+
+    fn expect(&mut self, s: &[u8]) -> XerResult<()> {
+        if !self.starts_with(s) {
+            return Err(self.error());
+        }
+        self.pos += s.len();
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    /// Skip input until after `end`
+    fn skip_until(&mut self, end: &[u8]) -> XerResult<()> {
+        while !self.starts_with(end) {
+            if self.pos >= self.input.len() {
+                return Err(self.error());
+            }
+            self.pos += 1;
+        }
+        self.pos += end.len();
+        Ok(())
+    }
+
+    /// Skip whitespace, comments and processing instructions (including the XML declaration)
+    fn skip_misc(&mut self) -> XerResult<()> {
+        loop {
+            self.skip_whitespace();
+            if self.starts_with(b"<!--") {
+                self.skip_until(b"-->")?;
+            } else if self.starts_with(b"<?") {
+                self.skip_until(b"?>")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> XerResult<String> {
+        let start = self.pos;
+        while let Some(b) = self.peek() {
+            if !(b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':') || b >= 0x80) {
+                break;
+            }
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(self.error());
+        }
+        // input is a str, and the slice boundaries are ASCII characters
+        let name = core::str::from_utf8(&self.input[start..self.pos]).map_err(|_| self.error())?;
+        Ok(name.into())
+    }
+
+    /// Skip the attributes of a start tag
+    fn skip_attributes(&mut self) -> XerResult<()> {
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b'/' | b'>') => return Ok(()),
+                Some(_) => {
+                    self.parse_name()?;
+                    self.skip_whitespace();
+                    self.expect(b"=")?;
+                    self.skip_whitespace();
+                    let quote = self.peek().ok_or_else(|| self.error())?;
+                    if quote != b'"' && quote != b'\'' {
+                        return Err(self.error());
+                    }
+                    self.pos += 1;
+                    self.skip_until(&[quote])?;
+                }
+                None => return Err(self.error()),
+            }
+        }
+    }
+
+    fn parse_element(&mut self, depth: usize) -> XerResult<XmlElement> {
+        if depth > MAX_DEPTH {
+            return Err(self.error());
+        }
+        self.expect(b"<")?;
+        let name = self.parse_name()?;
+        self.skip_attributes()?;
+        if self.starts_with(b"/>") {
+            self.pos += 2;
+            return Ok(XmlElement {
+                name,
+                content: Vec::new(),
+            });
+        }
+        self.expect(b">")?;
+        let mut content = Vec::new();
+        let mut text = String::new();
+        loop {
+            if self.starts_with(b"</") {
+                self.pos += 2;
+                if self.parse_name()? != name {
+                    return Err(self.error());
+                }
+                self.skip_whitespace();
+                self.expect(b">")?;
+                break;
+            } else if self.starts_with(b"<!--") {
+                self.skip_until(b"-->")?;
+            } else if self.starts_with(b"<?") {
+                self.skip_until(b"?>")?;
+            } else if self.starts_with(b"<![CDATA[") {
+                self.pos += 9;
+                let start = self.pos;
+                self.skip_until(b"]]>")?;
+                let data = core::str::from_utf8(&self.input[start..self.pos - 3])
+                    .map_err(|_| self.error())?;
+                text.push_str(data);
+            } else if self.starts_with(b"<") {
+                if !text.is_empty() {
+                    content.push(XmlNode::Text(core::mem::take(&mut text)));
+                }
+                let element = self.parse_element(depth + 1)?;
+                content.push(XmlNode::Element(element));
+            } else {
+                self.parse_char_data(&mut text)?;
+            }
+        }
+        if !text.is_empty() {
+            content.push(XmlNode::Text(text));
+        }
+        Ok(XmlElement { name, content })
+    }
+
+    /// Parse character data until the next markup, and append it to `text`
+    fn parse_char_data(&mut self, text: &mut String) -> XerResult<()> {
+        loop {
+            let start = self.pos;
+            while let Some(b) = self.peek() {
+                if b == b'<' || b == b'&' {
+                    break;
+                }
+                self.pos += 1;
+            }
+            // input is a str, and the slice boundaries are ASCII characters
+            let chunk =
+                core::str::from_utf8(&self.input[start..self.pos]).map_err(|_| self.error())?;
+            text.push_str(chunk);
+            match self.peek() {
+                Some(b'&') => text.push(self.parse_reference()?),
+                Some(_) => return Ok(()),
+                None => return Err(self.error()),
+            }
+        }
+    }
+
+    /// Parse a character or entity reference (for ex. `&amp;` or `&#x41;`)
+    fn parse_reference(&mut self) -> XerResult<char> {
+        self.expect(b"&")?;
+        let c = if self.starts_with(b"#") {
+            self.pos += 1;
+            let radix = if self.peek() == Some(b'x') {
+                self.pos += 1;
+                16
+            } else {
+                10
+            };
+            let start = self.pos;
+            while let Some(b) = self.peek() {
+                if !b.is_ascii_hexdigit() {
+                    break;
+                }
+                self.pos += 1;
+            }
+            let digits =
+                core::str::from_utf8(&self.input[start..self.pos]).map_err(|_| self.error())?;
+            let v = u32::from_str_radix(digits, radix).map_err(|_| self.error())?;
+            char::from_u32(v).ok_or_else(|| self.error())?
+        } else {
+            match self.parse_name()?.as_str() {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                _ => return Err(self.error()),
+            }
+        };
+        self.expect(b";")?;
+        Ok(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::xer::{child_elements, text_content, XmlElement, XmlNode};
+    use crate::XerError;
+
+    #[test]
+    fn xml_parse() {
+        let doc = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- comment -->
+<A xmlns="urn:test">
+  <b>1 &lt; 2 &amp;&#x41;&#66;</b>
+  <c/><d x='1'><![CDATA[<e>]]></d>
+</A>
+"#;
+        let root = XmlElement::parse(doc).unwrap();
+        assert_eq!(root.name, "A");
+        let children = child_elements(&root.content).unwrap();
+        assert_eq!(children.len(), 3);
+        assert_eq!(text_content(&children[0].content).unwrap(), "1 < 2 &AB");
+        assert!(children[1].content.is_empty());
+        assert_eq!(children[2].content, vec![XmlNode::Text("<e>".into())]);
+
+        // text with control characters
+        let root = XmlElement::parse("<s>a<nul/>b<cr/></s>").unwrap();
+        assert_eq!(text_content(&root.content).unwrap(), "a\u{0}b\r");
+        let root = XmlElement::parse("<s>a<x/></s>").unwrap();
+        assert_eq!(
+            text_content(&root.content),
+            Err(XerError::UnexpectedContent)
+        );
+        let root = XmlElement::parse("<s>a<b/></s>").unwrap();
+        assert_eq!(
+            child_elements(&root.content),
+            Err(XerError::UnexpectedContent)
+        );
+    }
+
+    #[test]
+    fn xml_parse_errors() {
+        XmlElement::parse("").unwrap_err();
+        XmlElement::parse("<a>").unwrap_err();
+        XmlElement::parse("<a></b>").unwrap_err();
+        XmlElement::parse("<a/><b/>").unwrap_err();
+        XmlElement::parse("<a>&unknown;</a>").unwrap_err();
+        XmlElement::parse("<a x=1/>").unwrap_err();
+        let deep = "<a>".repeat(200) + &"</a>".repeat(200);
+        XmlElement::parse(&deep).unwrap_err();
+    }
+}
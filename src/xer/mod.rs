@@ -0,0 +1,62 @@
+//! XML Encoding Rules (XER) encoders and decoders
+//!
+//! XER is defined in [[X.693]]. It represents ASN.1 values as XML documents, using the XML value
+//! notation of [[X.680]].
+//!
+//! Two variants are supported:
+//! - BASIC-XER: elements containing other elements are indented, to make documents readable
+//! - CANONICAL-XER (CXER): there is exactly one encoding for each value, without any whitespace
+//!   outside of values. This variant is suitable for digital signatures
+//!
+//! The variant is a property of the [`XerWriter`] object (see also [`ToXer::to_xer_string`] and
+//! [`ToXer::to_cxer_string`]). The same decoder is used for both variants. XML documents are
+//! parsed using a minimal parser ([`XmlElement::parse`]), so no external XML library is required.
+//!
+//! Types built with the `Sequence`, `Set`, `Choice`, `Enumerated` and `Alias` custom derive
+//! attributes can implement these traits by adding `XER` to the `parse` and `encode` items of
+//! the `asn1` attribute. Field names are used as element names, and the name of the type is used
+//! as XML type name. See [doc::derive](crate::doc::derive) for details.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use asn1_rs::xer::{ToXer, XerParser};
+//! use asn1_rs::{BitString, SequenceOf};
+//!
+//! let s = 1234u32.to_cxer_string().expect("serialization failed");
+//! assert_eq!(s, "<INTEGER>1234</INTEGER>");
+//!
+//! let b = BitString::new(4, &[0xa5, 0xf0]);
+//! let s = b.to_cxer_string().expect("serialization failed");
+//! assert_eq!(s, "<BIT_STRING>101001011111</BIT_STRING>");
+//! assert_eq!(BitString::parse_xer(&s).expect("parsing failed"), b);
+//!
+//! // BASIC-XER is indented
+//! let v = SequenceOf::new(vec![true, false]);
+//! let s = v.to_xer_string().expect("serialization failed");
+//! assert_eq!(s, "<SEQUENCE_OF>\n  <true/>\n  <false/>\n</SEQUENCE_OF>");
+//! let v2 = SequenceOf::<bool>::parse_xer(&s).expect("parsing failed");
+//! assert_eq!(v2, v);
+//! ```
+//!
+//! # Limitations
+//!
+//! - constraints are not checked, and encoding instructions (EXTENDED-XER) are not supported
+//! - `ENUMERATED` values are only supported for derived types (the [`Enumerated`](struct@crate::Enumerated)
+//!   type has no identifiers), and `Any` is not supported
+//! - `RELATIVE-OID` values are decoded as [`Oid`](crate::Oid) objects, which are not relative
+//! - with CXER, components of derived `SET` types are encoded in the order of declaration
+//! - decoded values always own their data
+//!
+//! [X.680]: http://www.itu.int/rec/T-REC-X.680/en "Recommendation ITU-T X.680"
+//! [X.693]: http://www.itu.int/rec/T-REC-X.693/en "Recommendation ITU-T X.693"
+
+mod document;
+mod strings;
+mod traits;
+mod types;
+mod writer;
+
+pub use document::*;
+pub use traits::*;
+pub use writer::*;
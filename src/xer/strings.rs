@@ -0,0 +1,179 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+use crate::{
+    BmpString, GeneralString, GraphicString, Ia5String, NumericString, PrintableString,
+    TeletexString, TestValidCharset, UniversalString, Utf8String, VideotexString, VisibleString,
+    XerError, XerResult,
+};
+
+use super::{text_content, ToXer, XerParser, XerWriter, XmlNode, XmlTypeName};
+
+impl XmlTypeName for str {
+    const XML_TYPE_NAME: &'static str = "UTF8String";
+}
+
+/// Character strings are encoded as text. Control characters are encoded as empty elements (for
+/// ex. `<nul/>`).
+impl ToXer for str {
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        writer.write_text(self);
+        Ok(())
+    }
+}
+
+impl XmlTypeName for String {
+    const XML_TYPE_NAME: &'static str = "UTF8String";
+}
+
+impl ToXer for String {
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        self.as_str().xer_encode(writer)
+    }
+}
+
+impl XerParser for String {
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        text_content(content)
+    }
+}
+
+/// Strings with a restricted character set, checked when encoding and decoding
+macro_rules! impl_xer_restricted_string {
+    ($($name:ident => $xml_name:expr),*) => {
+        $(
+            impl XmlTypeName for $name<'_> {
+                const XML_TYPE_NAME: &'static str = $xml_name;
+            }
+
+            impl ToXer for $name<'_> {
+                fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+                    <$name>::test_valid_charset(self.data.as_bytes())
+                        .map_err(|_| XerError::StringInvalidCharset)?;
+                    self.data.xer_encode(writer)
+                }
+            }
+
+            impl XerParser for $name<'_> {
+                fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+                    let s = text_content(content)?;
+                    <$name>::test_valid_charset(s.as_bytes())
+                        .map_err(|_| XerError::StringInvalidCharset)?;
+                    Ok($name { data: Cow::Owned(s) })
+                }
+            }
+        )*
+    };
+}
+
+impl_xer_restricted_string!(
+    Ia5String => "IA5String",
+    PrintableString => "PrintableString",
+    VisibleString => "VisibleString",
+    NumericString => "NumericString"
+);
+
+/// Strings where any Unicode character is valid
+macro_rules! impl_xer_string {
+    ($($name:ident => $xml_name:expr),*) => {
+        $(
+            impl XmlTypeName for $name<'_> {
+                const XML_TYPE_NAME: &'static str = $xml_name;
+            }
+
+            impl ToXer for $name<'_> {
+                fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+                    self.data.xer_encode(writer)
+                }
+            }
+
+            impl XerParser for $name<'_> {
+                fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+                    let s = text_content(content)?;
+                    Ok($name { data: Cow::Owned(s) })
+                }
+            }
+        )*
+    };
+}
+
+impl_xer_string!(
+    Utf8String => "UTF8String",
+    GeneralString => "GeneralString",
+    GraphicString => "GraphicString",
+    TeletexString => "TeletexString",
+    VideotexString => "VideotexString",
+    UniversalString => "UniversalString"
+);
+
+/// Returns an error if the string contains characters outside the Basic Multilingual Plane
+fn check_bmp(s: &str) -> XerResult<()> {
+    if s.chars().any(|c| c as u32 > 0xffff) {
+        return Err(XerError::StringInvalidCharset);
+    }
+    Ok(())
+}
+
+impl XmlTypeName for BmpString<'_> {
+    const XML_TYPE_NAME: &'static str = "BMPString";
+}
+
+impl ToXer for BmpString<'_> {
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        check_bmp(&self.data)?;
+        self.data.xer_encode(writer)
+    }
+}
+
+impl XerParser for BmpString<'_> {
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        let s = text_content(content)?;
+        check_bmp(&s)?;
+        Ok(BmpString::from(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xer::{ToXer, XerParser};
+    use crate::{BmpString, Ia5String, NumericString, Utf8String, XerError};
+
+    #[test]
+    fn xer_strings() {
+        let s = Utf8String::from("a<b & c\u{0}");
+        let v = s.to_cxer_string().unwrap();
+        assert_eq!(v, "<UTF8String>a&lt;b &amp; c<nul/></UTF8String>");
+        assert_eq!(Utf8String::parse_xer(&v).unwrap(), s);
+
+        // whitespace is significant
+        let s = Utf8String::from(" x ");
+        let v = s.to_xer_string().unwrap();
+        assert_eq!(v, "<UTF8String> x </UTF8String>");
+        assert_eq!(Utf8String::parse_xer(&v).unwrap(), s);
+
+        assert_eq!(
+            Utf8String::from("").to_cxer_string().unwrap(),
+            "<UTF8String/>"
+        );
+
+        assert_eq!(
+            Ia5String::parse_xer("<IA5String>abc</IA5String>").unwrap(),
+            Ia5String::new("abc")
+        );
+        assert_eq!(
+            Ia5String::parse_xer("<IA5String>café</IA5String>"),
+            Err(XerError::StringInvalidCharset)
+        );
+        assert_eq!(
+            NumericString::new("12a").to_xer_string(),
+            Err(XerError::StringInvalidCharset)
+        );
+        assert_eq!(
+            Ia5String::parse_xer("<IA5String><x/></IA5String>"),
+            Err(XerError::UnexpectedContent)
+        );
+
+        BmpString::parse_xer("<BMPString>😀</BMPString>").unwrap_err();
+        BmpString::parse_xer("<BMPString>€</BMPString>").unwrap();
+    }
+}
@@ -0,0 +1,85 @@
+use alloc::string::String;
+
+use crate::{XerError, XerResult};
+
+use super::{XerWriter, XmlElement, XmlNode};
+
+/// Name of the XML element used for values of a type, when no identifier is given
+///
+/// This name is used for the root element of a document, and for the items of `SEQUENCE OF` and
+/// `SET OF` values.
+pub trait XmlTypeName {
+    /// The XML type name (for ex. `INTEGER`, `BIT_STRING`, or the name of a derived type)
+    const XML_TYPE_NAME: &'static str;
+
+    /// `true` if items of this type are written without enclosing element in `SEQUENCE OF` and
+    /// `SET OF` values (this is the case for `BOOLEAN`, `ENUMERATED` and `CHOICE` types)
+    const XML_VALUE_LIST: bool = false;
+}
+
+/// Common trait for XER encoding functions
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::xer::ToXer;
+/// use asn1_rs::oid;
+///
+/// let oid = oid!(1.2.840 .113549);
+/// let s = oid.to_cxer_string().expect("serialization failed");
+/// assert_eq!(s, "<OBJECT_IDENTIFIER>1.2.840.113549</OBJECT_IDENTIFIER>");
+/// ```
+pub trait ToXer: XmlTypeName {
+    /// Encode the content of the value (without enclosing element)
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()>;
+
+    /// Encode the value in an element with the given name
+    fn xer_encode_element(&self, writer: &mut XerWriter, name: &str) -> XerResult<()> {
+        writer.start_element(name);
+        self.xer_encode(writer)?;
+        writer.end_element();
+        Ok(())
+    }
+
+    /// Encode the value as a BASIC-XER document
+    fn to_xer_string(&self) -> XerResult<String> {
+        let mut writer = XerWriter::new();
+        self.xer_encode_element(&mut writer, Self::XML_TYPE_NAME)?;
+        Ok(writer.into_string())
+    }
+
+    /// Encode the value as a CANONICAL-XER document
+    fn to_cxer_string(&self) -> XerResult<String> {
+        let mut writer = XerWriter::canonical();
+        self.xer_encode_element(&mut writer, Self::XML_TYPE_NAME)?;
+        Ok(writer.into_string())
+    }
+}
+
+/// Base trait for XER decoding functions
+///
+/// The same decoder is used for BASIC-XER and CANONICAL-XER. Decoded values own their data.
+///
+/// # Examples
+///
+/// ```rust
+/// use asn1_rs::xer::XerParser;
+///
+/// let b = bool::parse_xer("<BOOLEAN><true/></BOOLEAN>").expect("parsing failed");
+/// assert!(b);
+/// ```
+pub trait XerParser: XmlTypeName + Sized {
+    /// Decode a value from the content of its enclosing element
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self>;
+
+    /// Decode a value from an XML document
+    ///
+    /// The name of the root element must be the XML type name.
+    fn parse_xer(s: &str) -> XerResult<Self> {
+        let root = XmlElement::parse(s)?;
+        if root.name != Self::XML_TYPE_NAME {
+            return Err(XerError::UnexpectedElement(root.name));
+        }
+        Self::from_xer(&root.content)
+    }
+}
@@ -0,0 +1,649 @@
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::{
+    BitString, Boolean, GeneralizedTime, Integer, Null, OctetString, Oid, Real, SequenceOf, SetOf,
+    UtcTime, XerError, XerResult,
+};
+
+use super::{
+    is_whitespace, single_element, text_content, ToXer, XerParser, XerWriter, XmlNode, XmlTypeName,
+};
+
+/// Returns the text of `content`, without leading and trailing whitespace
+fn trimmed_text(content: &[XmlNode]) -> XerResult<String> {
+    let s = text_content(content)?;
+    Ok(s.trim().into())
+}
+
+//---- BOOLEAN (X.693: 8.3, X.680: 18.3)
+
+impl XmlTypeName for bool {
+    const XML_TYPE_NAME: &'static str = "BOOLEAN";
+    const XML_VALUE_LIST: bool = true;
+}
+
+/// Values are encoded as empty elements `<true/>` or `<false/>`
+impl ToXer for bool {
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        writer.write_empty_element(if *self { "true" } else { "false" });
+        Ok(())
+    }
+}
+
+impl XerParser for bool {
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        let e = single_element(content)?;
+        if !e.content.is_empty() {
+            return Err(XerError::UnexpectedContent);
+        }
+        match e.name.as_str() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Err(XerError::UnexpectedElement(e.name.clone())),
+        }
+    }
+}
+
+impl XmlTypeName for Boolean {
+    const XML_TYPE_NAME: &'static str = "BOOLEAN";
+    const XML_VALUE_LIST: bool = true;
+}
+
+impl ToXer for Boolean {
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        self.bool().xer_encode(writer)
+    }
+}
+
+impl XerParser for Boolean {
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        let b = bool::from_xer(content)?;
+        Ok(if b { Boolean::TRUE } else { Boolean::FALSE })
+    }
+}
+
+//---- NULL
+
+impl XmlTypeName for Null {
+    const XML_TYPE_NAME: &'static str = "NULL";
+}
+
+/// Values have no content (for ex. `<NULL/>`)
+impl ToXer for Null {
+    fn xer_encode(&self, _writer: &mut XerWriter) -> XerResult<()> {
+        Ok(())
+    }
+}
+
+impl XerParser for Null {
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        if !trimmed_text(content)?.is_empty() {
+            return Err(XerError::UnexpectedContent);
+        }
+        Ok(Null::new())
+    }
+}
+
+impl XmlTypeName for () {
+    const XML_TYPE_NAME: &'static str = "NULL";
+}
+
+impl ToXer for () {
+    fn xer_encode(&self, _writer: &mut XerWriter) -> XerResult<()> {
+        Ok(())
+    }
+}
+
+impl XerParser for () {
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        Null::from_xer(content).map(|_| ())
+    }
+}
+
+//---- INTEGER
+
+/// Returns the text of an integer value
+fn integer_text(content: &[XmlNode]) -> XerResult<String> {
+    let s = trimmed_text(content)?;
+    let digits = s.strip_prefix('-').unwrap_or(&s);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(XerError::InvalidValue);
+    }
+    Ok(s)
+}
+
+macro_rules! impl_xer_int {
+    ($($ty:ty)*) => {
+        $(
+            impl XmlTypeName for $ty {
+                const XML_TYPE_NAME: &'static str = "INTEGER";
+            }
+
+            impl ToXer for $ty {
+                fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+                    writer.write_text(&self.to_string());
+                    Ok(())
+                }
+            }
+
+            impl XerParser for $ty {
+                fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+                    let s = integer_text(content)?;
+                    s.parse::<$ty>().map_err(|_| XerError::IntegerTooLarge)
+                }
+            }
+        )*
+    };
+}
+
+impl_xer_int!(i8 i16 i32 i64 i128 u8 u16 u32 u64 u128);
+
+impl XmlTypeName for Integer<'_> {
+    const XML_TYPE_NAME: &'static str = "INTEGER";
+}
+
+impl ToXer for Integer<'_> {
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        if let Ok(i) = self.as_i128() {
+            writer.write_text(&i.to_string());
+            return Ok(());
+        }
+        #[cfg(feature = "bigint")]
+        {
+            writer.write_text(&self.as_bigint().to_string());
+            Ok(())
+        }
+        #[cfg(not(feature = "bigint"))]
+        {
+            Err(XerError::IntegerTooLarge)
+        }
+    }
+}
+
+impl XerParser for Integer<'_> {
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        let s = integer_text(content)?;
+        if let Ok(i) = s.parse::<i128>() {
+            return Ok(Integer::from_i128(i));
+        }
+        #[cfg(feature = "bigint")]
+        {
+            let i = s
+                .parse::<crate::BigInt>()
+                .map_err(|_| XerError::InvalidValue)?;
+            Ok(Integer {
+                data: Cow::Owned(i.to_signed_bytes_be()),
+            })
+        }
+        #[cfg(not(feature = "bigint"))]
+        {
+            Err(XerError::IntegerTooLarge)
+        }
+    }
+}
+
+//---- REAL
+
+impl XmlTypeName for Real {
+    const XML_TYPE_NAME: &'static str = "REAL";
+}
+
+/// Special values are encoded as empty elements (`<PLUS-INFINITY/>`, `<MINUS-INFINITY/>` and
+/// `<NOT-A-NUMBER/>`). Other values are encoded with a mantissa containing a single digit before
+/// the decimal point and no trailing zeroes, and an exponent (for ex. `1.5E3`), which is the
+/// same for both variants. Zero is encoded as `0`.
+impl ToXer for Real {
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        match self {
+            Real::Zero => writer.write_text("0"),
+            Real::Infinity => writer.write_empty_element("PLUS-INFINITY"),
+            Real::NegInfinity => writer.write_empty_element("MINUS-INFINITY"),
+            Real::NaN => writer.write_empty_element("NOT-A-NUMBER"),
+            Real::Binary { .. } => {
+                let f = self.f64();
+                if f == 0.0 {
+                    writer.write_text("0");
+                } else {
+                    writer.write_text(&format!("{f:E}"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl XerParser for Real {
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        if let Ok(e) = single_element(content) {
+            if !e.content.is_empty() {
+                return Err(XerError::UnexpectedContent);
+            }
+            return match e.name.as_str() {
+                "PLUS-INFINITY" => Ok(Real::Infinity),
+                "MINUS-INFINITY" => Ok(Real::NegInfinity),
+                "NOT-A-NUMBER" => Ok(Real::NaN),
+                _ => Err(XerError::UnexpectedElement(e.name.clone())),
+            };
+        }
+        let s = trimmed_text(content)?;
+        // reject values accepted by Rust but not by X.680 (for ex. "inf")
+        if !s
+            .bytes()
+            .all(|b| b.is_ascii_digit() || matches!(b, b'-' | b'+' | b'.' | b'e' | b'E'))
+        {
+            return Err(XerError::InvalidValue);
+        }
+        let f = s.parse::<f64>().map_err(|_| XerError::InvalidValue)?;
+        Ok(Real::new(f))
+    }
+}
+
+//---- BIT STRING
+
+impl XmlTypeName for BitString {
+    const XML_TYPE_NAME: &'static str = "BIT_STRING";
+}
+
+/// Values are encoded as a string of `0` and `1` characters (for ex. `<BIT_STRING>1010</BIT_STRING>`)
+impl ToXer for BitString {
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        let s = (0..self.len())
+            .map(|i| if self.is_set(i) { '1' } else { '0' })
+            .collect::<String>();
+        writer.write_text(&s);
+        Ok(())
+    }
+}
+
+impl XerParser for BitString {
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        let s = text_content(content)?;
+        let mut bytes = Vec::new();
+        let mut nbits = 0;
+        // whitespace is allowed between bits
+        for b in s.bytes().filter(|b| !b.is_ascii_whitespace()) {
+            if nbits % 8 == 0 {
+                bytes.push(0);
+            }
+            match b {
+                b'0' => (),
+                b'1' => bytes[nbits / 8] |= 0x80 >> (nbits % 8),
+                _ => return Err(XerError::InvalidValue),
+            }
+            nbits += 1;
+        }
+        if nbits == 0 {
+            return Ok(BitString::default());
+        }
+        Ok(BitString::new((8 * bytes.len() - nbits) as u8, &bytes))
+    }
+}
+
+//---- OCTET STRING
+
+impl XmlTypeName for OctetString<'_> {
+    const XML_TYPE_NAME: &'static str = "OCTET_STRING";
+}
+
+/// Values are encoded as hexadecimal strings, using uppercase digits
+impl ToXer for OctetString<'_> {
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+        let s = self
+            .as_ref()
+            .iter()
+            .flat_map(|b| [DIGITS[usize::from(b >> 4)], DIGITS[usize::from(b & 0xf)]])
+            .map(char::from)
+            .collect::<String>();
+        writer.write_text(&s);
+        Ok(())
+    }
+}
+
+impl XerParser for OctetString<'_> {
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        let s = text_content(content)?;
+        // whitespace is allowed between digits
+        let digits = s
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace())
+            .map(|c| c.to_digit(16).map(|d| d as u8))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(XerError::InvalidValue)?;
+        if digits.len() % 2 != 0 {
+            return Err(XerError::InvalidValue);
+        }
+        let bytes = digits.chunks_exact(2).map(|d| (d[0] << 4) | d[1]).collect();
+        Ok(OctetString::from(Cow::Owned(bytes)))
+    }
+}
+
+//---- OBJECT IDENTIFIER
+
+impl XmlTypeName for Oid<'_> {
+    const XML_TYPE_NAME: &'static str = "OBJECT_IDENTIFIER";
+}
+
+/// Values are encoded using the dotted notation (for ex. `1.2.840.113549`)
+impl ToXer for Oid<'_> {
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        writer.write_text(&self.to_id_string());
+        Ok(())
+    }
+}
+
+/// Relative object identifiers cannot be distinguished from absolute ones, so this function always
+/// returns an absolute object identifier
+impl XerParser for Oid<'_> {
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        let s = trimmed_text(content)?;
+        let arcs = s
+            .split('.')
+            .map(|arc| arc.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| XerError::InvalidValue)?;
+        Oid::from(&arcs).map_err(|_| XerError::InvalidValue)
+    }
+}
+
+//---- UTCTime and GeneralizedTime
+
+impl XmlTypeName for UtcTime {
+    const XML_TYPE_NAME: &'static str = "UTCTime";
+}
+
+/// Time values are encoded using the characters of their BER encoding
+impl ToXer for UtcTime {
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        writer.write_text(&self.to_value_string());
+        Ok(())
+    }
+}
+
+impl XerParser for UtcTime {
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        let s = trimmed_text(content)?;
+        UtcTime::from_bytes(s.as_bytes()).map_err(|_| XerError::InvalidValue)
+    }
+}
+
+impl XmlTypeName for GeneralizedTime {
+    const XML_TYPE_NAME: &'static str = "GeneralizedTime";
+}
+
+impl ToXer for GeneralizedTime {
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        writer.write_text(&self.to_value_string());
+        Ok(())
+    }
+}
+
+impl XerParser for GeneralizedTime {
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        let s = trimmed_text(content)?;
+        GeneralizedTime::from_bytes(s.as_bytes()).map_err(|_| XerError::InvalidValue)
+    }
+}
+
+//---- SEQUENCE OF and SET OF
+
+/// Encode one item of a `SEQUENCE OF` or `SET OF` value
+fn xer_encode_item<T: ToXer>(item: &T, writer: &mut XerWriter) -> XerResult<()> {
+    if T::XML_VALUE_LIST {
+        item.xer_encode(writer)
+    } else {
+        item.xer_encode_element(writer, T::XML_TYPE_NAME)
+    }
+}
+
+/// Decode the items of a `SEQUENCE OF` or `SET OF` value
+fn xer_decode_items<T: XerParser>(content: &[XmlNode]) -> XerResult<Vec<T>> {
+    content
+        .iter()
+        .filter(|node| !is_whitespace(node))
+        .map(|node| match node {
+            XmlNode::Element(_) if T::XML_VALUE_LIST => T::from_xer(core::slice::from_ref(node)),
+            XmlNode::Element(e) if e.name == T::XML_TYPE_NAME => T::from_xer(&e.content),
+            XmlNode::Element(e) => Err(XerError::UnexpectedElement(e.name.clone())),
+            XmlNode::Text(_) => Err(XerError::UnexpectedContent),
+        })
+        .collect()
+}
+
+impl<T> XmlTypeName for Vec<T> {
+    const XML_TYPE_NAME: &'static str = "SEQUENCE_OF";
+}
+
+/// Items are encoded in elements named after their XML type name, or without enclosing element
+/// for `BOOLEAN`, `ENUMERATED` and `CHOICE` types
+impl<T> ToXer for Vec<T>
+where
+    T: ToXer,
+{
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        self.iter()
+            .try_for_each(|item| xer_encode_item(item, writer))
+    }
+}
+
+impl<T> XerParser for Vec<T>
+where
+    T: XerParser,
+{
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        xer_decode_items(content)
+    }
+}
+
+impl<T> XmlTypeName for SequenceOf<T> {
+    const XML_TYPE_NAME: &'static str = "SEQUENCE_OF";
+}
+
+impl<T> ToXer for SequenceOf<T>
+where
+    T: ToXer,
+{
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        self.iter()
+            .try_for_each(|item| xer_encode_item(item, writer))
+    }
+}
+
+impl<T> XerParser for SequenceOf<T>
+where
+    T: XerParser,
+{
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        xer_decode_items(content).map(SequenceOf::new)
+    }
+}
+
+impl<T> XmlTypeName for SetOf<T> {
+    const XML_TYPE_NAME: &'static str = "SET_OF";
+}
+
+/// With CANONICAL-XER, items are sorted using their canonical encoding
+impl<T> ToXer for SetOf<T>
+where
+    T: ToXer,
+{
+    fn xer_encode(&self, writer: &mut XerWriter) -> XerResult<()> {
+        if !writer.is_canonical() {
+            return self
+                .iter()
+                .try_for_each(|item| xer_encode_item(item, writer));
+        }
+        let mut items = self
+            .iter()
+            .map(|item| {
+                let mut w = XerWriter::canonical();
+                xer_encode_item(item, &mut w)?;
+                Ok(w.into_string())
+            })
+            .collect::<XerResult<Vec<_>>>()?;
+        items.sort();
+        for item in &items {
+            writer.write_encoded_element(item);
+        }
+        Ok(())
+    }
+}
+
+impl<T> XerParser for SetOf<T>
+where
+    T: XerParser,
+{
+    fn from_xer(content: &[XmlNode]) -> XerResult<Self> {
+        xer_decode_items(content).map(SetOf::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use hex_literal::hex;
+
+    use crate::xer::{ToXer, XerParser};
+    use crate::{
+        oid, BitString, GeneralizedTime, Integer, Null, OctetString, Oid, Real, SequenceOf, SetOf,
+        UtcTime, XerError,
+    };
+
+    #[test]
+    fn xer_primitive_types() {
+        assert_eq!(true.to_cxer_string().unwrap(), "<BOOLEAN><true/></BOOLEAN>");
+        assert!(!bool::parse_xer("<BOOLEAN>\n  <false/>\n</BOOLEAN>").unwrap());
+        bool::parse_xer("<BOOLEAN>true</BOOLEAN>").unwrap_err();
+        assert_eq!(Null::new().to_cxer_string().unwrap(), "<NULL/>");
+        Null::parse_xer("<NULL></NULL>").unwrap();
+
+        assert_eq!(
+            (-129i16).to_cxer_string().unwrap(),
+            "<INTEGER>-129</INTEGER>"
+        );
+        assert_eq!(i16::parse_xer("<INTEGER> -129 </INTEGER>").unwrap(), -129);
+        assert_eq!(
+            u8::parse_xer("<INTEGER>256</INTEGER>"),
+            Err(XerError::IntegerTooLarge)
+        );
+        assert_eq!(
+            u8::parse_xer("<INTEGER>+1</INTEGER>"),
+            Err(XerError::InvalidValue)
+        );
+        assert_eq!(
+            u8::parse_xer("<REAL>1</REAL>"),
+            Err(XerError::UnexpectedElement("REAL".into()))
+        );
+
+        let i = Integer::from_u64(0x8000_0000_0000_0000);
+        let s = i.to_cxer_string().unwrap();
+        assert_eq!(s, "<INTEGER>9223372036854775808</INTEGER>");
+        assert_eq!(Integer::parse_xer(&s).unwrap(), i);
+    }
+
+    #[test]
+    fn xer_real() {
+        assert_eq!(
+            Real::new(1500.0).to_cxer_string().unwrap(),
+            "<REAL>1.5E3</REAL>"
+        );
+        assert_eq!(
+            Real::new(-0.25).to_cxer_string().unwrap(),
+            "<REAL>-2.5E-1</REAL>"
+        );
+        assert_eq!(Real::Zero.to_cxer_string().unwrap(), "<REAL>0</REAL>");
+        assert_eq!(
+            Real::NegInfinity.to_cxer_string().unwrap(),
+            "<REAL><MINUS-INFINITY/></REAL>"
+        );
+        assert_eq!(Real::parse_xer("<REAL>1.5E3</REAL>").unwrap().f64(), 1500.0);
+        assert_eq!(Real::parse_xer("<REAL>0.5</REAL>").unwrap().f64(), 0.5);
+        assert_eq!(
+            Real::parse_xer("<REAL><NOT-A-NUMBER/></REAL>").unwrap(),
+            Real::NaN
+        );
+        Real::parse_xer("<REAL>inf</REAL>").unwrap_err();
+    }
+
+    #[test]
+    fn xer_strings_of_bits_and_octets() {
+        let b = BitString::new(4, &hex!("a5 f0"));
+        let s = b.to_cxer_string().unwrap();
+        assert_eq!(s, "<BIT_STRING>101001011111</BIT_STRING>");
+        assert_eq!(BitString::parse_xer(&s).unwrap(), b);
+        assert_eq!(
+            BitString::parse_xer("<BIT_STRING>1010 0101 1111</BIT_STRING>").unwrap(),
+            b
+        );
+        assert_eq!(
+            BitString::parse_xer("<BIT_STRING/>").unwrap(),
+            BitString::default()
+        );
+        BitString::parse_xer("<BIT_STRING>102</BIT_STRING>").unwrap_err();
+
+        let s = OctetString::new(&hex!("01 af")).to_cxer_string().unwrap();
+        assert_eq!(s, "<OCTET_STRING>01AF</OCTET_STRING>");
+        assert_eq!(
+            OctetString::parse_xer("<OCTET_STRING>01 af</OCTET_STRING>")
+                .unwrap()
+                .as_ref(),
+            &hex!("01 af")
+        );
+        OctetString::parse_xer("<OCTET_STRING>1af</OCTET_STRING>").unwrap_err();
+    }
+
+    #[test]
+    fn xer_oid_and_times() {
+        let oid = oid!(1.2.840 .113549);
+        let s = oid.to_cxer_string().unwrap();
+        assert_eq!(Oid::parse_xer(&s).unwrap(), oid);
+
+        let t = UtcTime::from_bytes(b"021213142923Z").unwrap();
+        let s = t.to_cxer_string().unwrap();
+        assert_eq!(s, "<UTCTime>021213142923Z</UTCTime>");
+        assert_eq!(UtcTime::parse_xer(&s).unwrap(), t);
+
+        let t = GeneralizedTime::from_bytes(b"20021213142923.5Z").unwrap();
+        let s = t.to_cxer_string().unwrap();
+        assert_eq!(s, "<GeneralizedTime>20021213142923.5Z</GeneralizedTime>");
+        assert_eq!(GeneralizedTime::parse_xer(&s).unwrap(), t);
+    }
+
+    #[test]
+    fn xer_sequence_of_and_set_of() {
+        let v = SequenceOf::new(vec![1u8, 2]);
+        let s = v.to_cxer_string().unwrap();
+        assert_eq!(
+            s,
+            "<SEQUENCE_OF><INTEGER>1</INTEGER><INTEGER>2</INTEGER></SEQUENCE_OF>"
+        );
+        assert_eq!(SequenceOf::<u8>::parse_xer(&s).unwrap(), v);
+        let s = v.to_xer_string().unwrap();
+        assert_eq!(
+            s,
+            "<SEQUENCE_OF>\n  <INTEGER>1</INTEGER>\n  <INTEGER>2</INTEGER>\n</SEQUENCE_OF>"
+        );
+        assert_eq!(SequenceOf::<u8>::parse_xer(&s).unwrap(), v);
+
+        // BOOLEAN items have no enclosing element
+        let v = vec![true, false];
+        let s = v.to_cxer_string().unwrap();
+        assert_eq!(s, "<SEQUENCE_OF><true/><false/></SEQUENCE_OF>");
+        assert_eq!(Vec::<bool>::parse_xer(&s).unwrap(), v);
+
+        // canonical encoding sorts SET OF items (using the octets of their encodings)
+        let v = SetOf::new(vec![10u8, 2, 1]);
+        let s = v.to_cxer_string().unwrap();
+        assert_eq!(
+            s,
+            "<SET_OF><INTEGER>10</INTEGER><INTEGER>1</INTEGER><INTEGER>2</INTEGER></SET_OF>"
+        );
+        let s = v.to_xer_string().unwrap();
+        assert!(s.starts_with("<SET_OF>\n  <INTEGER>10</INTEGER>"));
+    }
+}
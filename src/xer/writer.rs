@@ -0,0 +1,206 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::CONTROL_CHARACTER_NAMES;
+
+/// Content written so far in an open element
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ContentState {
+    /// No content (the start tag is not closed yet)
+    Empty,
+    /// Only child elements
+    Elements,
+    /// Character data
+    Text,
+}
+
+/// XER writer
+///
+/// The writer produces either a BASIC-XER document, where elements containing other elements are
+/// indented, or a CANONICAL-XER (CXER) document without any whitespace outside of values.
+///
+/// In both variants, elements without content are written as empty-element tags (for ex.
+/// `<NULL/>`).
+#[derive(Debug)]
+pub struct XerWriter {
+    buf: String,
+    canonical: bool,
+    /// Names and content state of open elements
+    stack: Vec<(String, ContentState)>,
+}
+
+impl XerWriter {
+    /// Create a new BASIC-XER writer
+    pub const fn new() -> Self {
+        XerWriter {
+            buf: String::new(),
+            canonical: false,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Create a new CANONICAL-XER writer
+    pub const fn canonical() -> Self {
+        XerWriter {
+            buf: String::new(),
+            canonical: true,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if this writer produces CANONICAL-XER
+    #[inline]
+    pub const fn is_canonical(&self) -> bool {
+        self.canonical
+    }
+
+    /// Returns the XML text written so far
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+
+    /// Consume the writer and return the XML text
+    ///
+    /// All elements should have been closed.
+    pub fn into_string(self) -> String {
+        self.buf
+    }
+
+    fn write_indent(&mut self, depth: usize) {
+        self.buf.push('\n');
+        for _ in 0..depth {
+            self.buf.push_str("  ");
+        }
+    }
+
+    /// Update the state of the current element before writing a child element
+    fn open_child(&mut self) {
+        let depth = self.stack.len();
+        let canonical = self.canonical;
+        if let Some((_, state)) = self.stack.last_mut() {
+            if *state == ContentState::Empty {
+                self.buf.push('>');
+            }
+            // whitespace is not allowed in text
+            if *state != ContentState::Text {
+                *state = ContentState::Elements;
+                if !canonical {
+                    self.write_indent(depth);
+                }
+            }
+        }
+    }
+
+    /// Write the start tag of an element
+    pub fn start_element(&mut self, name: &str) {
+        self.open_child();
+        self.buf.push('<');
+        self.buf.push_str(name);
+        self.stack.push((name.into(), ContentState::Empty));
+    }
+
+    /// Write the end tag of the last open element
+    ///
+    /// If the element has no content, it is written as an empty-element tag. Does nothing if there
+    /// is no open element.
+    pub fn end_element(&mut self) {
+        let Some((name, state)) = self.stack.pop() else {
+            return;
+        };
+        match state {
+            ContentState::Empty => self.buf.push_str("/>"),
+            ContentState::Elements | ContentState::Text => {
+                if state == ContentState::Elements && !self.canonical {
+                    self.write_indent(self.stack.len());
+                }
+                self.buf.push_str("</");
+                self.buf.push_str(&name);
+                self.buf.push('>');
+            }
+        }
+    }
+
+    /// Write an empty element (for ex. `<true/>`)
+    pub fn write_empty_element(&mut self, name: &str) {
+        self.start_element(name);
+        self.end_element();
+    }
+
+    /// Write character data in the current element
+    ///
+    /// The characters `&`, `<` and `>` are escaped, and control characters are written as empty
+    /// elements (for ex. `<nul/>`).
+    pub fn write_text(&mut self, s: &str) {
+        if s.is_empty() {
+            return;
+        }
+        if let Some((_, state)) = self.stack.last_mut() {
+            if *state == ContentState::Empty {
+                self.buf.push('>');
+            }
+            *state = ContentState::Text;
+        }
+        for c in s.chars() {
+            match c {
+                '&' => self.buf.push_str("&amp;"),
+                '<' => self.buf.push_str("&lt;"),
+                '>' => self.buf.push_str("&gt;"),
+                c if (c as u32) < 0x20 => {
+                    self.buf.push('<');
+                    self.buf.push_str(CONTROL_CHARACTER_NAMES[c as usize]);
+                    self.buf.push_str("/>");
+                }
+                c => self.buf.push(c),
+            }
+        }
+    }
+
+    /// Write an element already encoded by another writer
+    pub(crate) fn write_encoded_element(&mut self, xml: &str) {
+        self.open_child();
+        self.buf.push_str(xml);
+    }
+}
+
+impl Default for XerWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::xer::XerWriter;
+
+    fn write_sample(w: &mut XerWriter) {
+        w.start_element("A");
+        w.start_element("b");
+        w.write_text("1 < 2\n");
+        w.end_element();
+        w.start_element("c");
+        w.write_empty_element("true");
+        w.end_element();
+        w.start_element("d");
+        w.write_text("");
+        w.end_element();
+        w.end_element();
+    }
+
+    #[test]
+    fn xer_writer() {
+        let mut w = XerWriter::canonical();
+        write_sample(&mut w);
+        assert_eq!(
+            w.into_string(),
+            "<A><b>1 &lt; 2<lf/></b><c><true/></c><d/></A>"
+        );
+
+        let mut w = XerWriter::new();
+        write_sample(&mut w);
+        assert_eq!(
+            w.into_string(),
+            "<A>\n  <b>1 &lt; 2<lf/></b>\n  <c>\n    <true/>\n  </c>\n  <d/>\n</A>"
+        );
+    }
+}
@@ -62,7 +62,10 @@ fn assert_traits_berparser() {
 
     // test traits that should require BerParser
     #[allow(dead_code)]
-    fn compound_wrapper<'a, T: BerParser<'a>>(_: T) {
+    fn compound_wrapper<'a, T: BerParser<'a>>(_: T)
+    where
+        T::Error: ErrorContext,
+    {
         test_assert!(Option<T>);
 
         test_assert!(Vec<T>, SequenceOf<T>);
@@ -76,7 +79,10 @@ fn assert_traits_berparser() {
 
         #[cfg(feature = "std")]
         #[allow(dead_code)]
-        fn compound_wrapper_requiring_ord<'a, T: BerParser<'a> + Ord>(_: T) {
+        fn compound_wrapper_requiring_ord<'a, T: BerParser<'a> + Ord>(_: T)
+        where
+            T::Error: ErrorContext,
+        {
             use std::collections::BTreeSet;
             test_assert!(BTreeSet<T>);
 
@@ -85,7 +91,10 @@ fn assert_traits_berparser() {
 
         #[cfg(feature = "std")]
         #[allow(dead_code)]
-        fn compound_wrapper_requiring_hash_eq<'a, T: BerParser<'a> + std::hash::Hash + Eq>(_: T) {
+        fn compound_wrapper_requiring_hash_eq<'a, T: BerParser<'a> + std::hash::Hash + Eq>(_: T)
+        where
+            T::Error: ErrorContext,
+        {
             use std::collections::HashSet;
             test_assert!(HashSet<T>);
         }
@@ -157,7 +166,10 @@ fn assert_traits_derparser() {
 
     // test traits that should require BerParser
     #[allow(dead_code)]
-    fn compound_wrapper<'a, T: DerParser<'a>>(_: T) {
+    fn compound_wrapper<'a, T: DerParser<'a>>(_: T)
+    where
+        T::Error: ErrorContext,
+    {
         test_assert!(Option<T>);
 
         test_assert!(Vec<T>, SequenceOf<T>);
@@ -169,6 +181,7 @@ fn assert_traits_derparser() {
         fn compound_wrapper_requiring_ord<'a, T>(_: T)
         where
             T: DerParser<'a> + Ord,
+            T::Error: ErrorContext,
         {
             use std::collections::BTreeSet;
             test_assert!(BTreeSet<T>);
@@ -181,6 +194,7 @@ fn assert_traits_derparser() {
         fn compound_wrapper_requiring_hash_eq<'a, T>(_: T)
         where
             T: DerParser<'a> + std::hash::Hash + Eq,
+            T::Error: ErrorContext,
         {
             use std::collections::HashSet;
             test_assert!(HashSet<T>);
@@ -0,0 +1,184 @@
+#![cfg(feature = "std")]
+
+use asn1_rs::*;
+use hex_literal::hex;
+use std::iter::FromIterator;
+
+#[test]
+fn cer_header_length() {
+    // Ok: constructed, indefinite length
+    let input = Input::from_slice(&hex!("30 80"));
+    let (_, header) = Header::parse_cer(input).expect("parsing failed");
+    assert_eq!(header.length(), Length::Indefinite);
+
+    // Fail: constructed, definite length
+    let input = Input::from_slice(&hex!("30 00"));
+    let _ = Header::parse_cer(input).expect_err("definite length");
+
+    // Fail: primitive, indefinite length
+    let input = Input::from_slice(&hex!("04 80"));
+    let _ = Header::parse_cer(input).expect_err("indefinite length");
+}
+
+#[test]
+fn cer_primitive() {
+    let (rem, res) =
+        u32::parse_cer(Input::from_slice(&hex!("0203010001"))).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, 65537);
+    assert_eq!(
+        &65537u32.to_cer_vec().expect("serialization failed"),
+        &hex!("0203010001")
+    );
+
+    // Fail: same constraints as DER
+    let _ = bool::parse_cer(Input::from_slice(&hex!("010101"))).expect_err("invalid boolean");
+    let _ = u32::parse_cer(Input::from_slice(&hex!("0202007f"))).expect_err("leading zeroes");
+}
+
+#[test]
+fn cer_octetstring_segments() {
+    // short strings use the primitive form
+    let data = vec![0x55u8; 1000];
+    let s = OctetString::from(data.as_slice());
+    let v = s.to_cer_vec().expect("serialization failed");
+    assert_eq!(&v[..4], &hex!("04 82 03 e8"));
+    assert_eq!(v.len(), 1004);
+    let (rem, res) = OctetString::parse_cer(Input::from_slice(&v)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res.as_ref(), data.as_slice());
+    let (_, res) = <&[u8]>::parse_cer(Input::from_slice(&v)).expect("parsing failed");
+    assert_eq!(res, data.as_slice());
+
+    // long strings use the constructed form, with segments of 1000 octets
+    let data = vec![0x55u8; 2500];
+    let s = OctetString::from(data.as_slice());
+    let v = s.to_cer_vec().expect("serialization failed");
+    assert_eq!(&v[..6], &hex!("24 80 04 82 03 e8"));
+    assert_eq!(&v[2010..2014], &hex!("04 82 01 f4"));
+    assert_eq!(&v[v.len() - 2..], &hex!("00 00"));
+    let (rem, res) = OctetString::parse_cer(Input::from_slice(&v)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res.as_ref(), data.as_slice());
+
+    // Fail: constructed form cannot be parsed as shared slice
+    let _ = <&[u8]>::parse_cer(Input::from_slice(&v)).expect_err("constructed");
+
+    // Fail: primitive form for a long string
+    let der = s.to_der_vec().expect("serialization failed");
+    let _ = OctetString::parse_cer(Input::from_slice(&der)).expect_err("primitive too long");
+
+    // Fail: constructed form for a short string
+    let input = Input::from_slice(&hex!("24 80 0403 414243 0000"));
+    let _ = OctetString::parse_cer(input).expect_err("constructed too short");
+
+    // Fail: segments with invalid size
+    let mut input = hex!("24 80 04 82 03 e7").to_vec();
+    input.extend_from_slice(&[0x55; 999]);
+    input.extend_from_slice(&hex!("0402 5555 0000"));
+    let _ = OctetString::parse_cer(Input::from_slice(&input)).expect_err("segment size");
+}
+
+#[test]
+fn cer_strings_segments() {
+    let s = "a".repeat(1500);
+    let v = s.as_str().to_cer_vec().expect("serialization failed");
+    assert_eq!(&v[..6], &hex!("2c 80 04 82 03 e8"));
+    let (rem, res) = String::parse_cer(Input::from_slice(&v)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, s);
+
+    let p = PrintableString::from(s.as_str());
+    let v = p.to_cer_vec().expect("serialization failed");
+    assert_eq!(&v[..2], &hex!("33 80"));
+    let (rem, res) = PrintableString::parse_cer(Input::from_slice(&v)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res.as_ref(), s);
+
+    // Fail: invalid charset in one of the segments
+    let mut input = v.clone();
+    input[1010] = b'*';
+    let _ = PrintableString::parse_cer(Input::from_slice(&input)).expect_err("invalid charset");
+}
+
+#[test]
+fn cer_bitstring_segments() {
+    let data = vec![0xaau8; 1500];
+    let b = BitString::new(0, &data);
+    let v = b.to_cer_vec().expect("serialization failed");
+    // segments contain 1000 octets, including the unused bits octet
+    assert_eq!(&v[..7], &hex!("23 80 03 82 03 e8 00"));
+    let (rem, res) = BitString::parse_cer(Input::from_slice(&v)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, b);
+}
+
+#[test]
+fn cer_sequence_of() {
+    let v = vec![1u32, 2];
+    let encoded = v.to_cer_vec().expect("serialization failed");
+    assert_eq!(&encoded, &hex!("30 80 020101 020102 0000"));
+    let (rem, res) = <Vec<u32>>::parse_cer(Input::from_slice(&encoded)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, v);
+
+    // nested constructed objects
+    let v = vec![vec![true]];
+    let encoded = v.to_cer_vec().expect("serialization failed");
+    assert_eq!(&encoded, &hex!("30 80 30 80 0101ff 0000 0000"));
+    let (rem, res) =
+        <Vec<Vec<bool>>>::parse_cer(Input::from_slice(&encoded)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, v);
+
+    // Fail: nested object with definite length
+    let input = Input::from_slice(&hex!("30 80 30 03 0101ff 0000"));
+    let _ = <Vec<Vec<bool>>>::parse_cer(input).expect_err("definite length");
+}
+
+#[test]
+fn cer_set_of() {
+    // components are sorted when encoding
+    let s = SetOf::from_iter([0x0102u32, 0x01, 0x02]);
+    let encoded = s.to_cer_vec().expect("serialization failed");
+    assert_eq!(&encoded, &hex!("31 80 020101 020102 02020102 0000"));
+    let (rem, res) = <SetOf<u32>>::parse_cer(Input::from_slice(&encoded)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res.as_ref(), &[1, 2, 0x0102]);
+
+    // Fail: components not sorted
+    let input = Input::from_slice(&hex!("31 80 020102 020101 0000"));
+    let _ = <SetOf<u32>>::parse_cer(input).expect_err("not sorted");
+}
+
+#[test]
+fn cer_tagged() {
+    let (rem, res) = <TaggedExplicit<u32, BerError<Input>, 0>>::parse_cer(Input::from_slice(
+        &hex!("a0 80 020101 0000"),
+    ))
+    .expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res.into_inner(), 1);
+
+    let _ = <TaggedExplicit<u32, BerError<Input>, 0>>::parse_cer(Input::from_slice(&hex!(
+        "a0 03 020101"
+    )))
+    .expect_err("definite length");
+
+    let (rem, res) =
+        <TaggedImplicit<u32, BerError<Input>, 0>>::parse_cer(Input::from_slice(&hex!("80 01 01")))
+            .expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res.into_inner(), 1);
+}
+
+#[test]
+fn cer_any() {
+    let input = &hex!("30 80 020101 0000");
+    let (rem, any) = Any::parse_cer(Input::from_slice(input)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(any.data.as_bytes2(), &hex!("020101"));
+    // encoding writes End-Of-Content again
+    let v = any.to_cer_vec().expect("serialization failed");
+    assert_eq!(&v, input);
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "codec")]
+
+use asn1_rs::*;
+use futures::{SinkExt, StreamExt};
+use hex_literal::hex;
+use tokio::io::AsyncWriteExt;
+use tokio_util::codec::{FramedRead, FramedWrite};
+
+#[tokio::test]
+async fn codec_partial_reads() {
+    let pdus: [&[u8]; 4] = [
+        // definite length, long form
+        &hex!("04 81 03 010203"),
+        // indefinite length, with nested objects
+        &hex!("30 80 30 80 02 01 01 00 00 24 80 04 01 aa 00 00 00 00"),
+        // high tag number
+        &hex!("bf 82 01 03 02 01 05"),
+        // empty
+        &hex!("05 00"),
+    ];
+    let (client, server) = tokio::io::duplex(4);
+    let data: Vec<u8> = pdus.concat();
+    let writer = tokio::spawn(async move {
+        let mut client = client;
+        // write octet by octet, to split headers and content
+        for b in data {
+            client.write_all(&[b]).await.expect("write failed");
+        }
+    });
+    let mut framed = FramedRead::new(server, BerCodec::new());
+    for pdu in pdus {
+        let frame = framed
+            .next()
+            .await
+            .expect("no frame")
+            .expect("decoding failed");
+        assert_eq!(&frame[..], pdu);
+        let (rem, _) = Any::parse_ber(Input::from(&frame[..])).expect("parsing failed");
+        assert!(rem.is_empty());
+    }
+    writer.await.expect("writer failed");
+    assert!(framed.next().await.is_none());
+}
+
+#[tokio::test]
+async fn codec_encode() {
+    let (client, server) = tokio::io::duplex(64);
+    let mut sink = FramedWrite::new(client, DerCodec::new());
+    sink.send(Integer::from(1234_u32))
+        .await
+        .expect("encoding failed");
+    sink.send(&vec![Utf8String::new("abc"), Utf8String::new("de")])
+        .await
+        .expect("encoding failed");
+    drop(sink);
+
+    let mut framed = FramedRead::new(server, DerCodec::new());
+    let frame = framed.next().await.expect("no frame").unwrap();
+    assert_eq!(&frame[..], &hex!("02 02 04d2"));
+    let frame = framed.next().await.expect("no frame").unwrap();
+    let (_, v) = <Vec<Utf8String>>::parse_der(Input::from(&frame[..])).expect("parsing failed");
+    assert_eq!(v, [Utf8String::new("abc"), Utf8String::new("de")]);
+    assert!(framed.next().await.is_none());
+}
+
+#[tokio::test]
+async fn codec_errors() {
+    use tokio_util::codec::Decoder;
+
+    let mut buf = bytes::BytesMut::from(&hex!("30 80 02 01 01 00 00")[..]);
+    let r = DerCodec::new().decode(&mut buf);
+    assert!(matches!(
+        r,
+        Err(ReadError::ASN1Error(Error::DerConstraintFailed(
+            DerConstraint::IndefiniteLength
+        )))
+    ));
+
+    // maximum size is checked before content is received
+    let mut codec = BerCodec::new().with_max_pdu_size(16);
+    let mut buf = bytes::BytesMut::from(&hex!("04 11 00")[..]);
+    assert!(matches!(
+        codec.decode(&mut buf),
+        Err(ReadError::ObjectTooLarge)
+    ));
+    let mut buf = bytes::BytesMut::from(&hex!("30 80 04 0d 00")[..]);
+    assert!(matches!(
+        codec.decode(&mut buf),
+        Err(ReadError::ObjectTooLarge)
+    ));
+    // codec can be reused after an error
+    let mut buf = bytes::BytesMut::from(&hex!("05 00")[..]);
+    assert!(matches!(codec.decode(&mut buf), Ok(Some(_))));
+
+    let mut buf = bytes::BytesMut::from(&hex!("30 80 30 80 30 80 00 00 00 00 00 00")[..]);
+    let r = BerCodec::new().with_max_depth(2).decode(&mut buf);
+    assert!(matches!(r, Err(ReadError::ASN1Error(Error::BerMaxDepth))));
+
+    // stream closed in the middle of a PDU
+    let (mut client, server) = tokio::io::duplex(64);
+    client.write_all(&hex!("30 05 02 01")).await.unwrap();
+    drop(client);
+    let mut framed = FramedRead::new(server, BerCodec::new());
+    let r = framed.next().await.expect("no result");
+    assert!(matches!(r, Err(ReadError::IOError(_))));
+}
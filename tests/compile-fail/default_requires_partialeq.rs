@@ -0,0 +1,23 @@
+fn test_default_partialeq() {
+    use asn1_rs::*;
+
+    #[derive(Debug, Clone, Sequence)]
+    pub struct Inner {
+        a: u16,
+    }
+
+    const DEFAULT_INNER: Inner = Inner { a: 0 };
+
+    /// Should not compile: DER/CER compare fields to their DEFAULT value
+    #[derive(Debug, Sequence)]
+    // #[debug_derive]
+    pub struct T0 {
+        #[optional]
+        #[default(DEFAULT_INNER)]
+        a: Inner,
+    }
+}
+
+fn main() {
+    test_default_partialeq();
+}
@@ -39,7 +39,45 @@ fn compile_pass_enumerated() {
 #[test]
 fn compile_pass_sequence() {
     let t = trybuild::TestCases::new();
-    t.pass("tests/run-pass/sequence*.rs");
+    t.pass("tests/run-pass/sequence.rs");
+    t.pass("tests/run-pass/sequence_cer.rs");
+    t.pass("tests/run-pass/sequence_custom_error.rs");
+    t.pass("tests/run-pass/sequence_tagged.rs");
+}
+
+#[cfg(feature = "per")]
+#[test]
+fn compile_pass_sequence_per() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/run-pass/sequence_per.rs");
+}
+
+#[cfg(feature = "oer")]
+#[test]
+fn compile_pass_sequence_oer() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/run-pass/sequence_oer.rs");
+}
+
+#[cfg(feature = "jer")]
+#[test]
+fn compile_pass_sequence_jer() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/run-pass/sequence_jer.rs");
+}
+
+#[cfg(feature = "xer")]
+#[test]
+fn compile_pass_sequence_xer() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/run-pass/sequence_xer.rs");
+}
+
+#[cfg(feature = "value-notation")]
+#[test]
+fn compile_pass_sequence_value_notation() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/run-pass/sequence_value_notation.rs");
 }
 
 #[test]
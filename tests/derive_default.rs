@@ -0,0 +1,119 @@
+#![cfg(feature = "std")]
+
+use asn1_rs::*;
+use hex_literal::hex;
+
+// Extension ::= SEQUENCE {
+//     extnID      OBJECT IDENTIFIER,
+//     critical    BOOLEAN DEFAULT FALSE,
+//     extnValue   OCTET STRING }
+#[derive(Debug, PartialEq, Sequence)]
+#[asn1(parse = "BER,CER,DER", encode = "BER,CER,DER")]
+pub struct Extension<'a> {
+    pub extn_id: Oid<'a>,
+    #[default(false)]
+    pub critical: bool,
+    pub extn_value: &'a [u8],
+}
+
+#[derive(Debug, PartialEq, Sequence)]
+pub struct Tagged {
+    #[tag_explicit(0)]
+    #[default(1)]
+    pub version: u32,
+    pub serial: u32,
+}
+
+// legacy derive attributes
+#[derive(Debug, PartialEq, DerSequence)]
+pub struct LegacyDefault {
+    #[default(false)]
+    pub critical: bool,
+    pub value: u32,
+}
+
+#[test]
+fn default_omitted_when_encoding() {
+    // basicConstraints, not critical
+    let ext = Extension {
+        extn_id: Oid::from(&[2, 5, 29, 19]).unwrap(),
+        critical: false,
+        extn_value: &hex!("3000"),
+    };
+    let der = ext.to_der_vec().expect("DER serialization failed");
+    assert_eq!(&der, &hex!("30 09 0603551d13 04023000"));
+    assert_eq!(ext.der_total_len(), Length::Definite(der.len()));
+    let cer = ext.to_cer_vec().expect("CER serialization failed");
+    assert_eq!(&cer, &hex!("30 80 0603551d13 04023000 0000"));
+    // BER: default value is encoded
+    let ber = ext.to_ber_vec().expect("BER serialization failed");
+    assert_eq!(&ber, &hex!("30 0c 0603551d13 010100 04023000"));
+
+    let (rem, res) = Extension::parse_der(Input::from(&der)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, ext);
+
+    // value different from default is encoded
+    let ext = Extension {
+        critical: true,
+        ..ext
+    };
+    let der = ext.to_der_vec().expect("DER serialization failed");
+    assert_eq!(&der, &hex!("30 0c 0603551d13 0101ff 04023000"));
+    assert_eq!(ext.der_total_len(), Length::Definite(der.len()));
+
+    // tagged field
+    let t = Tagged {
+        version: 1,
+        serial: 2,
+    };
+    let der = t.to_der_vec().expect("DER serialization failed");
+    assert_eq!(&der, &hex!("30 03 020102"));
+    let t = Tagged {
+        version: 3,
+        serial: 2,
+    };
+    let der = t.to_der_vec().expect("DER serialization failed");
+    assert_eq!(&der, &hex!("30 08 a003020103 020102"));
+}
+
+#[test]
+fn default_encoded_rejected() {
+    let input = hex!("30 0c 0603551d13 010100 04023000");
+    // BER: accepted
+    let (_, ext) = Extension::parse_ber(Input::from(&input)).expect("parsing failed");
+    assert!(!ext.critical);
+    // DER: rejected
+    let e = Extension::parse_der(Input::from(&input)).expect_err("default value encoded");
+    match e {
+        nom::Err::Error(e) => {
+            assert_eq!(
+                e.inner(),
+                &InnerError::DerConstraintFailed(DerConstraint::DefaultValueEncoded)
+            );
+            assert_eq!(e.path().to_string(), "Extension.critical");
+        }
+        _ => panic!("parsing should fail with an error"),
+    }
+
+    let input = hex!("30 08 a003020101 020102");
+    let _ = Tagged::parse_ber(Input::from(&input)).expect("parsing failed");
+    let _ = Tagged::parse_der(Input::from(&input)).expect_err("default value encoded");
+
+    // legacy derive attributes
+    let (_, res) = LegacyDefault::from_der(&hex!("30 03 020101")).expect("parsing failed");
+    assert_eq!(
+        res,
+        LegacyDefault {
+            critical: false,
+            value: 1
+        }
+    );
+    let e = LegacyDefault::from_der(&hex!("30 06 010100 020101")).expect_err("default value");
+    assert_eq!(
+        e,
+        nom::Err::Error(Error::DerConstraintFailed(
+            DerConstraint::DefaultValueEncoded
+        ))
+    );
+}
@@ -0,0 +1,105 @@
+use asn1_rs::*;
+use hex_literal::hex;
+
+#[derive(Debug, PartialEq, Sequence)]
+pub struct Extension {
+    pub id: u32,
+    pub critical: bool,
+}
+
+#[derive(Debug, PartialEq, Sequence)]
+pub struct Tbs {
+    pub version: u32,
+    pub extensions: Vec<Extension>,
+}
+
+#[derive(Debug, PartialEq, Sequence)]
+pub struct Certificate {
+    pub tbs: Tbs,
+    pub serial: u32,
+}
+
+fn parse_error<'a, T>(r: IResult<Input<'a>, T, BerError<Input<'a>>>) -> BerError<Input<'a>> {
+    match r {
+        Err(nom::Err::Error(e)) => e,
+        _ => panic!("parsing should fail with an error"),
+    }
+}
+
+#[test]
+fn error_path_nested() {
+    // Certificate { tbs: { 2, [ {1, TRUE}, {2, INTEGER 0 (should be BOOLEAN)} ] }, 5 }
+    let data =
+        hex!("30 1a 30 15 02 01 02 30 10 30 06 02 01 01 01 01 ff 30 06 02 01 02 02 01 00 02 01 05");
+    let e = parse_error(Certificate::parse_ber(Input::from(&data)));
+    assert_eq!(
+        e.path().to_string(),
+        "Certificate.tbs.extensions[1].critical"
+    );
+    assert_eq!(e.path().type_name(), Some("Certificate"));
+    let segments: Vec<_> = e.path().segments().copied().collect();
+    assert_eq!(
+        segments,
+        [
+            ErrorPathSegment::Field("tbs"),
+            ErrorPathSegment::Field("extensions"),
+            ErrorPathSegment::Index(1),
+            ErrorPathSegment::Field("critical"),
+        ]
+    );
+    // expected tag is reported
+    assert_eq!(
+        *e.inner(),
+        InnerError::UnexpectedTag {
+            expected: Some(Tag::Boolean),
+            actual: Tag::Integer
+        }
+    );
+    assert!(e
+        .to_string()
+        .ends_with(" path=Certificate.tbs.extensions[1].critical"));
+
+    // same path with DER
+    let e = parse_error(Certificate::parse_der(Input::from(&data)));
+    assert_eq!(
+        e.path().to_string(),
+        "Certificate.tbs.extensions[1].critical"
+    );
+
+    // path of an inner type starts with that type
+    let e = parse_error(Tbs::parse_der(Input::from(&data[2..])));
+    assert_eq!(e.path().to_string(), "Tbs.extensions[1].critical");
+
+    // in a collection, the type name of elements is not displayed
+    let data = hex!("30 08 30 06 02 01 02 02 01 00");
+    let e = parse_error(<Vec<Extension>>::parse_ber(Input::from(&data)));
+    assert_eq!(e.path().to_string(), "[0].critical");
+    assert_eq!(e.path().type_name(), None);
+}
+
+#[test]
+fn error_path_collections() {
+    // SEQUENCE OF BOOLEAN, third element is an INTEGER
+    let data = hex!("30 09 01 01 00 01 01 ff 02 01 00");
+    let e = parse_error(<Vec<bool>>::parse_der(Input::from(&data)));
+    assert_eq!(e.path().to_string(), "[2]");
+    assert_eq!(e.path().type_name(), None);
+
+    // SET OF BOOLEAN
+    let data = hex!("31 06 01 01 00 02 01 00");
+    let e = parse_error(<SetOf<bool>>::parse_ber(Input::from(&data)));
+    assert_eq!(e.path().to_string(), "[1]");
+}
+
+#[test]
+fn error_path_empty() {
+    // errors outside structures have no path, and the path is ignored when comparing errors
+    let e = parse_error(u32::parse_der(Input::from(&hex!("01 01 00"))));
+    assert!(e.path().is_empty());
+    assert!(!e.to_string().contains("path="));
+
+    let mut e2 = BerError::new(e.input().clone(), e.inner().clone());
+    e2.push_field("T", "a");
+    assert_eq!(e2.path().to_string(), "T.a");
+    assert_eq!(e, e2);
+}
@@ -0,0 +1,195 @@
+#![cfg(feature = "std")]
+
+use std::collections::{BTreeSet, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use asn1_rs::*;
+use hex_literal::hex;
+
+/// Number of calls to `Leaf::der_content_len`
+static LEAF_LENGTHS: AtomicUsize = AtomicUsize::new(0);
+
+/// Lock held by tests counting calls in `LEAF_LENGTHS` (tests run in parallel)
+static LEAF_LENGTHS_LOCK: Mutex<()> = Mutex::new(());
+
+/// An `INTEGER`, counting how many times its length is computed
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Leaf(u32);
+
+impl Tagged for Leaf {
+    const TAG: Tag = Tag::Integer;
+}
+
+impl<'i> BerParser<'i> for Leaf {
+    type Error = BerError<Input<'i>>;
+
+    fn from_ber_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        let (rem, v) = u32::from_ber_content(header, input)?;
+        Ok((rem, Leaf(v)))
+    }
+}
+
+impl ToDer for Leaf {
+    type Encoder = Primitive<{ Tag::Integer.0 }>;
+
+    fn der_content_len(&self) -> Length {
+        LEAF_LENGTHS.fetch_add(1, Ordering::Relaxed);
+        self.0.der_content_len()
+    }
+
+    fn der_tag_info(&self) -> (Class, bool, Tag) {
+        self.0.der_tag_info()
+    }
+
+    fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+        self.0.der_write_content(target)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Sequence)]
+#[asn1(parse = "BER", encode = "DER")]
+pub struct Inner {
+    pub a: Leaf,
+    #[tag_explicit(2)]
+    pub b: Leaf,
+}
+
+#[derive(Debug, PartialEq, Sequence)]
+#[asn1(parse = "BER", encode = "DER")]
+pub struct Middle {
+    pub items: Vec<Inner>,
+    #[tag_implicit(1)]
+    pub c: Leaf,
+}
+
+#[derive(Debug, PartialEq, Sequence)]
+#[asn1(parse = "BER", encode = "DER")]
+pub struct Outer {
+    #[tag_explicit(0)]
+    pub middle: Middle,
+    #[optional]
+    pub opt: Option<Middle>,
+    #[tag_implicit(3)]
+    #[optional]
+    pub absent: Option<Leaf>,
+}
+
+#[derive(Debug, PartialEq, Sequence)]
+#[asn1(parse = "BER", encode = "DER")]
+pub struct Sets {
+    pub a: SetOf<Inner>,
+    pub b: BTreeSet<Inner>,
+    pub c: HashSet<Inner>,
+}
+
+fn inner(i: u32) -> Inner {
+    Inner {
+        a: Leaf(i),
+        b: Leaf(i + 1),
+    }
+}
+
+fn middle(n: u32) -> Middle {
+    Middle {
+        items: (0..n)
+            .map(|i| Inner {
+                a: Leaf(i),
+                b: Leaf(i + 1),
+            })
+            .collect(),
+        c: Leaf(n),
+    }
+}
+
+#[test]
+fn derive_length_cache() {
+    let outer = Outer {
+        middle: middle(2),
+        opt: Some(middle(1)),
+        absent: None,
+    };
+    let num_leaves = 2 * 2 + 1 + 2 + 1;
+
+    // derived `der_encode` computes the length of each object only once
+    let _lock = LEAF_LENGTHS_LOCK.lock().unwrap();
+    LEAF_LENGTHS.store(0, Ordering::Relaxed);
+    let der = outer.to_der_vec().expect("serialization failed");
+    assert_eq!(LEAF_LENGTHS.load(Ordering::Relaxed), num_leaves);
+    assert_eq!(
+        &der,
+        &hex!(
+            "30 2e"
+            "a0 1b 30 19 30 14 3008 020100 a203020101 3008 020101 a203020102 810102"
+            "30 0f 30 0a 3008 020100 a203020101 810101"
+        )[..]
+    );
+    assert_eq!(outer.der_total_len(), Length::Definite(der.len()));
+
+    // same encoding when writing header and content separately
+    let mut v = Vec::new();
+    outer
+        .der_write_header(&mut v)
+        .expect("serialization failed");
+    outer
+        .der_write_content(&mut v)
+        .expect("serialization failed");
+    assert_eq!(v, der);
+
+    // same encoding in a collection
+    let v = vec![outer].to_der_vec().expect("serialization failed");
+    assert_eq!(&v[2..], &der[..]);
+
+    let (rem, res) = Outer::parse_ber(Input::from(&der)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res.middle, middle(2));
+}
+
+#[test]
+fn length_cache_explicit() {
+    let value = vec![middle(3), middle(0)];
+    let mut cache = LengthCache::new();
+    let length = value.der_total_len_cached(&mut cache);
+    assert_eq!(length, value.der_total_len());
+
+    let mut v = Vec::new();
+    let sz = value
+        .der_encode_cached(&mut cache, &mut v)
+        .expect("serialization failed");
+    assert_eq!(Length::Definite(sz), length);
+    assert_eq!(v, value.to_der_vec().expect("serialization failed"));
+
+    // all lengths were consumed
+    cache.next_length().expect_err("cache is exhausted");
+}
+
+#[test]
+fn length_cache_set_of() {
+    // components are sorted when encoding
+    let sets = Sets {
+        a: vec![inner(1), inner(0)].into_iter().collect(),
+        b: vec![inner(1), inner(0)].into_iter().collect(),
+        c: vec![inner(1), inner(0)].into_iter().collect(),
+    };
+    let expected_set = hex!("31 14 3008 020100 a203020101 3008 020101 a203020102");
+
+    let _lock = LEAF_LENGTHS_LOCK.lock().unwrap();
+    LEAF_LENGTHS.store(0, Ordering::Relaxed);
+    let der = sets.to_der_vec().expect("serialization failed");
+    assert_eq!(LEAF_LENGTHS.load(Ordering::Relaxed), 3 * 2 * 2);
+    assert_eq!(&der[..2], &hex!("30 42"));
+    for set in der[2..].chunks(expected_set.len()) {
+        assert_eq!(set, &expected_set[..]);
+    }
+    assert_eq!(sets.der_total_len(), Length::Definite(der.len()));
+
+    // same encoding without cache
+    let mut v = Vec::new();
+    sets.der_write_header(&mut v).expect("serialization failed");
+    sets.der_write_content(&mut v)
+        .expect("serialization failed");
+    assert_eq!(v, der);
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "std")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use asn1_rs::*;
+use hex_literal::hex;
+
+// number of calls to `Counted::der_content_len`
+static CONTENT_LEN_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// INTEGER, counting the computations of its content length
+#[derive(Debug, PartialEq)]
+pub struct Counted(u32);
+
+impl Tagged for Counted {
+    const TAG: Tag = Tag::Integer;
+}
+
+impl<'i> BerParser<'i> for Counted {
+    type Error = BerError<Input<'i>>;
+
+    fn from_ber_content(
+        header: &'_ Header<'i>,
+        input: Input<'i>,
+    ) -> IResult<Input<'i>, Self, Self::Error> {
+        let (rem, v) = u32::from_ber_content(header, input)?;
+        Ok((rem, Counted(v)))
+    }
+}
+
+impl ToDer for Counted {
+    type Encoder = Primitive<{ Tag::Integer.0 }>;
+
+    fn der_content_len(&self) -> Length {
+        CONTENT_LEN_CALLS.fetch_add(1, Ordering::SeqCst);
+        self.0.der_content_len()
+    }
+
+    fn der_tag_info(&self) -> (Class, bool, Tag) {
+        self.0.der_tag_info()
+    }
+
+    fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
+        self.0.der_write_content(target)
+    }
+}
+
+#[derive(Debug, PartialEq, Sequence)]
+#[asn1(parse = "BER", encode = "DER")]
+pub struct Inner {
+    pub a: Counted,
+    #[tag_explicit(2)]
+    pub b: Counted,
+    #[tag_implicit(APPLICATION 1)]
+    pub c: Counted,
+    #[default(0)]
+    pub d: u32,
+}
+
+#[derive(Debug, PartialEq, Sequence)]
+#[asn1(parse = "BER", encode = "DER")]
+pub struct Outer {
+    #[tag_explicit(0)]
+    pub inner: Inner,
+    pub items: Vec<Inner>,
+    #[tag_implicit(1)]
+    #[optional]
+    pub opt: Option<Counted>,
+    #[tag_explicit(3)]
+    #[optional]
+    pub absent: Option<Counted>,
+    pub attributes: Attributes,
+    pub choice: Vec<Selection>,
+    pub alias: Version,
+}
+
+// fields are not declared in canonical tag order
+#[derive(Debug, PartialEq, Set)]
+#[asn1(parse = "BER", encode = "DER")]
+pub struct Attributes {
+    #[tag_explicit(1)]
+    pub x: Counted,
+    pub y: bool,
+    #[tag_implicit(0)]
+    pub z: Counted,
+}
+
+#[derive(Debug, PartialEq, Choice)]
+#[asn1(parse = "BER", encode = "DER")]
+pub enum Selection {
+    Number(Counted),
+    Flag(bool),
+}
+
+#[derive(Debug, PartialEq, Alias)]
+#[asn1(parse = "BER", encode = "DER")]
+pub struct Version(Counted);
+
+#[test]
+fn derive_reverse_writer() {
+    let inner = |v| Inner {
+        a: Counted(v),
+        b: Counted(v + 1),
+        c: Counted(v + 2),
+        d: v,
+    };
+    let value = Outer {
+        inner: inner(0),
+        items: vec![inner(1), inner(2)],
+        opt: Some(Counted(4)),
+        absent: None,
+        attributes: Attributes {
+            x: Counted(5),
+            y: true,
+            z: Counted(6),
+        },
+        choice: vec![Selection::Number(Counted(7)), Selection::Flag(false)],
+        alias: Version(Counted(8)),
+    };
+
+    // no content length is computed
+    CONTENT_LEN_CALLS.store(0, Ordering::SeqCst);
+    let mut writer = ReverseWriter::new();
+    let sz = value
+        .der_encode_reverse(&mut writer)
+        .expect("serialization failed");
+    assert_eq!(CONTENT_LEN_CALLS.load(Ordering::SeqCst), 0);
+    assert_eq!(sz, writer.len());
+
+    let der = writer.into_vec();
+    assert_eq!(
+        &der,
+        &hex!(
+            "30 4c"
+            "a0 0d 300b 020100 a203020101 410102"
+            "30 20 300e 020101 a203020102 410103 020101"
+            "      300e 020102 a203020103 410104 020102"
+            "810104"
+            "31 0b 0101ff 800106 a103020105"
+            "30 06 020107 010100"
+            "020108"
+        )
+    );
+    // same encoding as two-pass encoding
+    assert_eq!(der, value.to_der_vec().expect("serialization failed"));
+
+    let (rem, res) = Outer::parse_ber(Input::from(&der)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, value);
+
+    // writer can be reused
+    let mut writer = ReverseWriter::with_capacity(der.len());
+    value
+        .der_encode_reverse(&mut writer)
+        .expect("serialization failed");
+    writer.clear();
+    inner(1)
+        .der_encode_reverse(&mut writer)
+        .expect("serialization failed");
+    assert_eq!(
+        writer.as_slice(),
+        &hex!("300e 020101 a203020102 410103 020101")
+    );
+}
@@ -0,0 +1,242 @@
+#![cfg(feature = "std")]
+
+use asn1_rs::*;
+use hex_literal::hex;
+
+// fields are not declared in canonical tag order
+#[derive(Debug, PartialEq, Set)]
+#[asn1(parse = "BER,CER,DER", encode = "BER,CER,DER")]
+pub struct Attributes {
+    #[tag_explicit(1)]
+    pub a: u32,
+    pub b: bool,
+    #[tag_implicit(0)]
+    pub c: u32,
+    #[tag_explicit(APPLICATION 2)]
+    pub d: u32,
+    #[optional]
+    pub e: Option<u32>,
+}
+
+// extension marker: unknown components are ignored
+#[derive(Debug, PartialEq, Set)]
+#[asn1(parse = "BER,CER,DER", extensible)]
+pub struct ExtensibleSet {
+    pub a: u32,
+    #[tag_explicit(0)]
+    pub b: bool,
+}
+
+// legacy derive attributes
+#[derive(Debug, PartialEq, DerSet, ToDerSet)]
+pub struct LegacySet {
+    #[tag_explicit(0)]
+    pub a: u32,
+    pub b: u32,
+}
+
+#[derive(Debug, PartialEq, DerSet)]
+#[asn1(extensible)]
+pub struct LegacyExtensibleSet {
+    #[tag_explicit(0)]
+    pub a: u32,
+    pub b: u32,
+}
+
+fn parse_error<'a, T>(r: IResult<Input<'a>, T, BerError<Input<'a>>>) -> BerError<Input<'a>> {
+    match r {
+        Err(nom::Err::Error(e)) => e,
+        _ => panic!("parsing should fail with an error"),
+    }
+}
+
+#[test]
+fn set_encode_sorted() {
+    let item = Attributes {
+        a: 1,
+        b: true,
+        c: 3,
+        d: 5,
+        e: Some(7),
+    };
+
+    // DER and CER: universal, application, then context-specific tags (sorted by number)
+    let der = item.to_der_vec().expect("DER serialization failed");
+    assert_eq!(
+        &der,
+        &hex!("31 13 0101ff 020107 6203020105 800103 a103020101")
+    );
+    let cer = item.to_cer_vec().expect("CER serialization failed");
+    assert_eq!(
+        &cer,
+        &hex!("31 80 0101ff 020107 6280 020105 0000 800103 a180 020101 0000 0000")
+    );
+    // BER: declaration order
+    let ber = item.to_ber_vec().expect("BER serialization failed");
+    assert_eq!(
+        &ber,
+        &hex!("31 13 a103020101 0101ff 800103 6203020105 020107")
+    );
+
+    // absent OPTIONAL field
+    let item = Attributes { e: None, ..item };
+    let der = item.to_der_vec().expect("DER serialization failed");
+    assert_eq!(&der, &hex!("31 10 0101ff 6203020105 800103 a103020101"));
+    let (rem, res) = Attributes::parse_der(Input::from(&der)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, item);
+}
+
+#[test]
+fn set_parse_order() {
+    let expected = Attributes {
+        a: 1,
+        b: true,
+        c: 3,
+        d: 5,
+        e: Some(7),
+    };
+    let der = hex!("31 13 0101ff 020107 6203020105 800103 a103020101");
+    let ber = hex!("31 13 a103020101 0101ff 800103 6203020105 020107");
+
+    let (rem, res) = Attributes::parse_der(Input::from(&der)).expect("parsing DER failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, expected);
+    let (_, res) = Attributes::parse_ber(Input::from(&der)).expect("parsing BER failed");
+    assert_eq!(res, expected);
+
+    // BER: any order is accepted
+    let (rem, res) = Attributes::parse_ber(Input::from(&ber)).expect("parsing BER failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, expected);
+
+    // DER and CER: components must be sorted by tag
+    let e = parse_error(Attributes::parse_der(Input::from(&ber)));
+    assert_eq!(
+        e.inner(),
+        &InnerError::DerConstraintFailed(DerConstraint::SetUnsorted)
+    );
+    let cer = hex!("31 80 a180 020101 0000 0101ff 800103 6280 020105 0000 020107 0000");
+    let _ = Attributes::parse_cer(Input::from(&cer)).expect_err("not sorted");
+
+    // missing required field
+    let input = hex!("31 08 0101ff a103020101");
+    let _ = Attributes::parse_der(Input::from(&input)).expect_err("missing field");
+
+    // unknown components: rejected in DER and CER, ignored in BER
+    let input = hex!("31 13 0101ff 6203020105 800103 a103020101 850100");
+    let e = parse_error(Attributes::parse_der(Input::from(&input)));
+    assert_eq!(
+        e.inner(),
+        &InnerError::UnexpectedTag {
+            expected: None,
+            actual: Tag(5)
+        }
+    );
+    let cer = hex!("31 80 0101ff 6280 020105 0000 800103 a180 020101 0000 850100 0000");
+    let _ = Attributes::parse_cer(Input::from(&cer)).expect_err("unknown component");
+    let (_, res) = Attributes::parse_ber(Input::from(&input)).expect("parsing failed");
+    assert_eq!(
+        res,
+        Attributes {
+            e: None,
+            ..expected
+        }
+    );
+
+    // invalid field
+    let input = hex!("31 10 0101ff 6203020105 800103 a103040101");
+    let e = parse_error(Attributes::parse_der(Input::from(&input)));
+    assert_eq!(e.path().to_string(), "Attributes.a");
+}
+
+#[test]
+fn set_parse_extensible() {
+    let expected = ExtensibleSet { a: 1, b: true };
+    let input = hex!("31 0b 020101 a0030101ff 850100");
+    let (rem, res) = ExtensibleSet::parse_der(Input::from(&input)).expect("parsing DER failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, expected);
+    let cer = hex!("31 80 020101 a080 0101ff 0000 850100 0000");
+    let (rem, res) = ExtensibleSet::parse_cer(Input::from(&cer)).expect("parsing CER failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn set_parse_duplicate() {
+    // duplicated components are rejected, including in BER (X.680: 27.3)
+    let input = hex!("31 16 0101ff 0101ff 020107 6203020105 800103 a103020101");
+    for e in [
+        parse_error(Attributes::parse_ber(Input::from(&input))),
+        parse_error(Attributes::parse_der(Input::from(&input))),
+    ] {
+        assert_eq!(
+            e.inner(),
+            &InnerError::invalid_value(Tag::Boolean, "Duplicate SET component")
+        );
+    }
+    let input = hex!("31 16 0101ff 020107 6203020105 800103 800104 a103020101");
+    let e = parse_error(Attributes::parse_ber(Input::from(&input)));
+    assert_eq!(
+        e.inner(),
+        &InnerError::invalid_value(Tag(0), "Duplicate SET component")
+    );
+
+    // extensible: a duplicated component is not an extension
+    let input = hex!("31 0b 020101 020102 a0030101ff");
+    let e = parse_error(ExtensibleSet::parse_der(Input::from(&input)));
+    assert_eq!(
+        e.inner(),
+        &InnerError::invalid_value(Tag::Integer, "Duplicate SET component")
+    );
+
+    // legacy derive
+    let input = hex!("31 0b 020102 020103 a003020101");
+    let e = LegacySet::from_der(&input).expect_err("duplicate component");
+    assert_eq!(
+        e,
+        nom::Err::Error(Error::invalid_value(
+            Tag::Integer,
+            "Duplicate SET component".into()
+        ))
+    );
+}
+
+#[test]
+fn set_legacy_order() {
+    let item = LegacySet { a: 1, b: 2 };
+    let der = item.to_der_vec().expect("DER serialization failed");
+    assert_eq!(&der, &hex!("31 08 020102 a003020101"));
+
+    let (rem, res) = LegacySet::from_der(&der).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, item);
+
+    // components not sorted by tag
+    let input = hex!("31 08 a003020101 020102");
+    let e = LegacySet::from_der(&input).expect_err("not sorted");
+    assert_eq!(
+        e,
+        nom::Err::Error(Error::DerConstraintFailed(DerConstraint::SetUnsorted))
+    );
+    let (_, any) = Any::from_ber(&input).expect("parsing failed");
+    assert_eq!(
+        LegacySet::check_constraints(&any),
+        Err(Error::DerConstraintFailed(DerConstraint::SetUnsorted))
+    );
+
+    // unknown components
+    let input = hex!("31 0b 020102 a003020101 850100");
+    let e = LegacySet::from_der(&input).expect_err("unknown component");
+    assert_eq!(
+        e,
+        nom::Err::Error(Error::UnexpectedTag {
+            expected: None,
+            actual: Tag(5)
+        })
+    );
+    let (rem, res) = LegacyExtensibleSet::from_der(&input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, LegacyExtensibleSet { a: 1, b: 2 });
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "std")]
+
+use asn1_rs::*;
+use hex_literal::hex;
+
+#[derive(Debug, PartialEq, Sequence)]
+#[asn1(parse = "BER,DER", encode = "BER,CER,DER")]
+pub struct TaggedOptional {
+    #[tag_explicit(0)]
+    #[optional]
+    pub a: Option<u32>,
+    #[tag_implicit(1)]
+    #[optional]
+    pub b: Option<u32>,
+    pub c: u32,
+}
+
+#[derive(Debug, PartialEq, Sequence)]
+#[asn1(parse = "BER,DER", encode = "BER,DER")]
+pub struct TaggedImplicit {
+    #[tag_implicit(1)]
+    pub a: String,
+}
+
+#[test]
+fn tagged_optional_absent() {
+    let value = TaggedOptional {
+        a: None,
+        b: None,
+        c: 1,
+    };
+    // absent tagged OPTIONAL values are not encoded
+    let der = value.to_der_vec().expect("serialization failed");
+    assert_eq!(&der, &hex!("30 03 020101"));
+    assert_eq!(value.der_total_len(), Length::Definite(der.len()));
+    let ber = value.to_ber_vec().expect("serialization failed");
+    assert_eq!(&ber, &hex!("30 03 020101"));
+    assert_eq!(value.ber_total_len(), Length::Definite(ber.len()));
+    let cer = value.to_cer_vec().expect("serialization failed");
+    assert_eq!(&cer, &hex!("30 80 020101 0000"));
+
+    let value = TaggedOptional {
+        a: Some(2),
+        b: Some(3),
+        c: 1,
+    };
+    let der = value.to_der_vec().expect("serialization failed");
+    assert_eq!(&der, &hex!("30 0b a003020102 810103 020101"));
+    assert_eq!(value.der_total_len(), Length::Definite(der.len()));
+    let (_, res) = TaggedOptional::parse_der(Input::from(&der)).expect("parsing failed");
+    assert_eq!(res, value);
+}
+
+#[test]
+fn tagged_implicit_length() {
+    // the header of the implicitly tagged field is computed from its content length (126),
+    // not from its total length (128, which would require a long form length)
+    let value = TaggedImplicit { a: "a".repeat(126) };
+    let der = value.to_der_vec().expect("serialization failed");
+    assert_eq!(&der[..5], &hex!("30 81 80 81 7e"));
+    assert_eq!(value.der_total_len(), Length::Definite(der.len()));
+    let ber = value.to_ber_vec().expect("serialization failed");
+    assert_eq!(&ber, &der);
+    assert_eq!(value.ber_total_len(), Length::Definite(ber.len()));
+}
@@ -65,7 +65,7 @@ impl ToDer for PrincipalName {
         sz1 + sz2
     }
 
-    fn der_write_content<W: std::io::Write>(&self, target: &mut W) -> SerializeResult<usize> {
+    fn der_write_content<W: Writer>(&self, target: &mut W) -> SerializeResult<usize> {
         // build DER sequence content
         let sz1 = self
             .name_type
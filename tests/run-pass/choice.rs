@@ -59,6 +59,28 @@ fn derive_choice_untagged_lifetime() {
     assert_eq!(r1_der, expected);
 }
 
+fn derive_choice_untagged_tagged_variants() {
+    #[derive(Debug, PartialEq)]
+    //
+    #[derive(Choice)]
+    // #[debug_derive]
+    pub enum UntaggedChoice<'a> {
+        Val0(TaggedImplicit<u8, Error, 0>),
+        Val1(TaggedExplicit<&'a [u8], Error, 1>),
+    }
+
+    //--- variant 1
+    // Ok: valid content
+    let ber1 = &hex!("a1 05 04 03 010203");
+    let (_, r1_ber) = UntaggedChoice::parse_ber(Input::from(ber1)).expect("parsing BER failed");
+    let (_, r1_der) = UntaggedChoice::parse_der(Input::from(ber1)).expect("parsing DER failed");
+    let expected = UntaggedChoice::Val1(TaggedValue::explicit(&[0x01, 0x02, 0x03]));
+    assert_eq!(r1_ber, expected);
+    assert_eq!(r1_der, expected);
+    assert_eq!(r1_ber.class(), Class::ContextSpecific);
+    assert_eq!(r1_ber.tag(), Tag(1));
+}
+
 fn derive_choice_tagged_explicit() {
     #[derive(Debug, PartialEq)]
     //
@@ -382,6 +404,7 @@ mod with_std {
 fn main() {
     derive_choice_untagged();
     derive_choice_untagged_lifetime();
+    derive_choice_untagged_tagged_variants();
     derive_choice_tagged_explicit();
     derive_choice_attribute_tag_explicit();
     derive_choice_tagged_implicit();
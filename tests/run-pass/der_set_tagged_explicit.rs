@@ -40,25 +40,29 @@ fn test_tag_explicit_optional() {
     }
 
     // optional value present
-    let input0 = &hex!("3108 a003020103 020103");
+    let input0 = &hex!("3108 020103 a003020103");
     let (rem, t0) = T0::from_der(input0).expect("parsing failed");
     assert!(rem.is_empty());
     assert_eq!(t0, T0 { a: Some(3), b: 3 });
 
+    // components not sorted by tag (X.690: 10.3)
+    let input_unsorted = &hex!("3108 a003020103 020103");
+    T0::from_der(input_unsorted).expect_err("parsing should fail");
+
     // optional value absent
     let input1 = &hex!("3103 020103");
     let (rem, t0) = T0::from_der(input1).expect("parsing failed");
     assert!(rem.is_empty());
     assert_eq!(t0, T0 { a: None, b: 3 });
 
-    // optional value present but wrong tag
-    let input_wrong_tag = &hex!("3108 a103020103 020103");
+    // optional value present but wrong tag (and required value absent)
+    let input_wrong_tag = &hex!("3105 a103020103");
     T0::from_der(input_wrong_tag).expect_err("parsing should fail");
 
     // optional value present but invalid length
-    let input_wrong_len0 = &hex!("3108 a002020103 020103");
+    let input_wrong_len0 = &hex!("3108 020103 a002020103");
     T0::from_der(input_wrong_len0).expect_err("parsing should fail");
-    let input_wrong_len1 = &hex!("3108 a003020403 020103");
+    let input_wrong_len1 = &hex!("3108 020103 a003020403");
     T0::from_der(input_wrong_len1).expect_err("parsing should fail");
 
     // test empty input
@@ -21,12 +21,9 @@ fn derive_derparser_default() {
     assert!(rem.is_empty());
     assert_eq!(res, AADefault { a: 0 });
 
-    // Ok: value absent, remaining bytes
+    // Fail: value absent, unknown component (rejected in DER)
     let input = Input::from_slice(&hex!("31 04 040200aa"));
-    let (rem, res) = AADefault::parse_der(input).expect("parsing failed");
-    // NOTE: rem is empty, because there are remaining bytes in _content_ (ignored by default)
-    assert!(rem.is_empty());
-    assert_eq!(res, AADefault { a: 0 });
+    let _e = AADefault::parse_der(input).expect_err("unknown component");
 }
 
 fn main() {
@@ -103,11 +103,9 @@ fn derive_derparser_opttagged_default() {
     assert!(rem.is_empty());
     assert_eq!(res, AATagEx2 { a: 0 });
 
-    // Ok: value absent (different tag), remaining bytes
+    // Fail: value absent, unknown component (different tag, rejected in DER)
     let input = Input::from_slice(&hex!("31 06 a1 04 020200aa"));
-    let (rem, res) = AATagEx2::parse_der(input).expect("parsing failed");
-    assert!(rem.is_empty());
-    assert_eq!(res, AATagEx2 { a: 0 });
+    let _e = AATagEx2::parse_der(input).expect_err("unknown component");
 
     // Fail: value present, but inner type different
     let input = Input::from_slice(&hex!("31 06 a0 04 040200aa"));
@@ -0,0 +1,74 @@
+use asn1_rs::*;
+use hex_literal::hex;
+
+fn derive_sequence_cer() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Sequence)]
+    #[asn1(parse = "BER,CER,DER", encode = "BER,CER,DER")]
+    // #[debug_derive]
+    pub struct AA {
+        a: u32,
+        #[tag_explicit(0)]
+        b: bool,
+        #[tag_implicit(1)]
+        c: u16,
+        #[optional]
+        d: Option<String>,
+    }
+
+    let value = AA {
+        a: 0xaa,
+        b: true,
+        c: 2,
+        d: Some(String::from("abc")),
+    };
+    let expected = &hex!("30 80 020200aa a0 80 0101ff 0000 810102 0c03616263 0000");
+
+    // Ok: encode with indefinite lengths
+    let v = value.to_cer_vec().expect("serialization failed");
+    assert_eq!(&v, expected);
+
+    // Ok: parse encoded value
+    let (rem, res) = AA::parse_cer(Input::from_slice(&v)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, value);
+
+    // Ok: absent optional field
+    let input = Input::from_slice(&hex!("30 80 020200aa a0 80 0101ff 0000 810102 0000"));
+    let (rem, res) = AA::parse_cer(input).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res.d, None);
+
+    // Fail: definite length (DER encoding)
+    let input = Input::from_slice(&hex!("30 0f 020200aa a0 03 0101ff 810102 0c03616263"));
+    let _ = AA::parse_cer(input).expect_err("definite length");
+}
+
+fn derive_choice_cer() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Choice)]
+    #[tagged_explicit]
+    #[asn1(parse = "CER", encode = "CER")]
+    // #[debug_derive]
+    pub enum C {
+        Val0(u8),
+        Val1(String),
+    }
+
+    let value = C::Val1(String::from("abc"));
+    let expected = &hex!("a1 80 0c03616263 0000");
+
+    let v = value.to_cer_vec().expect("serialization failed");
+    assert_eq!(&v, expected);
+
+    let (rem, res) = C::parse_cer(Input::from_slice(&v)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res, value);
+}
+
+fn main() {
+    derive_sequence_cer();
+    derive_choice_cer();
+}
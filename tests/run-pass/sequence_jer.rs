@@ -0,0 +1,139 @@
+use asn1_rs::jer::{JerParser, ToJer};
+use asn1_rs::*;
+use hex_literal::hex;
+
+fn derive_sequence_jer() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Sequence)]
+    #[asn1(parse = "DER,JER", encode = "DER,JER")]
+    // #[debug_derive]
+    pub struct AA<'a> {
+        a: u8,
+        #[optional]
+        b: Option<bool>,
+        #[default(5)]
+        c: u16,
+        d: OctetString<'a>,
+        r#type: Oid<'a>,
+    }
+
+    let value = AA {
+        a: 5,
+        b: Some(true),
+        c: 200,
+        d: OctetString::new(&hex!("12 34")),
+        r#type: oid!(1.2.3),
+    };
+
+    // Ok: members are named after fields
+    let s = value.to_jer_string().expect("serialization failed");
+    assert_eq!(s, r#"{"a":5,"b":true,"c":200,"d":"1234","type":"1.2.3"}"#);
+    let res = AA::parse_jer(&s).expect("parsing failed");
+    assert_eq!(res, value);
+
+    // Ok: absent OPTIONAL fields are omitted
+    let value = AA { b: None, ..value };
+    let s = value.to_jer_string().expect("serialization failed");
+    assert_eq!(s, r#"{"a":5,"c":200,"d":"1234","type":"1.2.3"}"#);
+
+    // Ok: absent OPTIONAL and DEFAULT fields, members in any order
+    let res = AA::parse_jer(r#"{"type":"1.2.3","d":"","a":1}"#).expect("parsing failed");
+    assert_eq!(res.b, None);
+    assert_eq!(res.c, 5);
+
+    // Fail: missing member
+    let err = AA::parse_jer(r#"{"a":1}"#).expect_err("missing member");
+    assert_eq!(err, JerError::MissingMember("d".into()));
+
+    // Fail: not an object
+    let err = AA::parse_jer("[1]").expect_err("unexpected type");
+    assert_eq!(err, JerError::UnexpectedType);
+}
+
+fn derive_set_jer() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Set)]
+    #[asn1(parse = "DER,JER", encode = "DER,JER")]
+    pub struct S<'a> {
+        name: Utf8String<'a>,
+        values: Vec<u32>,
+    }
+
+    let value = S {
+        name: Utf8String::from("x"),
+        values: vec![1, 2],
+    };
+    let s = value.to_jer_string().expect("serialization failed");
+    assert_eq!(s, r#"{"name":"x","values":[1,2]}"#);
+    assert_eq!(S::parse_jer(&s).expect("parsing failed"), value);
+}
+
+fn derive_choice_jer() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Choice)]
+    #[tagged_explicit]
+    #[asn1(parse = "BER,JER", encode = "BER,JER")]
+    pub enum C {
+        #[tag(1)]
+        Val1(u8),
+        #[tag(0)]
+        Val0(bool),
+    }
+
+    // Ok: object with one member, named after the variant
+    let value = C::Val1(2);
+    let s = value.to_jer_string().expect("serialization failed");
+    assert_eq!(s, r#"{"Val1":2}"#);
+    assert_eq!(C::parse_jer(&s).expect("parsing failed"), value);
+
+    // Fail: unknown alternative
+    let err = C::parse_jer(r#"{"Val2":true}"#).expect_err("unknown alternative");
+    assert_eq!(err, JerError::UnknownAlternative("Val2".into()));
+
+    // Fail: more than one member
+    let _ = C::parse_jer(r#"{"Val0":true,"Val1":2}"#).expect_err("too many members");
+}
+
+fn derive_enumerated_jer() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    //
+    #[derive(Enumerated)]
+    #[asn1(parse = "BER,JER", encode = "BER,JER")]
+    pub enum E {
+        A = 0,
+        B = 5,
+    }
+
+    // Ok: values are encoded using identifiers
+    let s = E::B.to_jer_string().expect("serialization failed");
+    assert_eq!(s, r#""B""#);
+    assert_eq!(E::parse_jer(&s).expect("parsing failed"), E::B);
+
+    // Fail: unknown identifier, or numerical value
+    let _ = E::parse_jer(r#""C""#).expect_err("unknown value");
+    let _ = E::parse_jer("5").expect_err("unexpected type");
+}
+
+fn derive_alias_jer() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Alias)]
+    #[asn1(parse = "DER,JER", encode = "DER,JER")]
+    pub struct Id(u32);
+
+    let value = Id(1234);
+    let s = value.to_jer_string().expect("serialization failed");
+    assert_eq!(s, "1234");
+    assert_eq!(Id::parse_jer(&s).expect("parsing failed"), value);
+}
+
+fn main() {
+    derive_sequence_jer();
+    derive_set_jer();
+    derive_choice_jer();
+    derive_enumerated_jer();
+    derive_alias_jer();
+}
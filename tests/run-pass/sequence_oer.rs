@@ -0,0 +1,154 @@
+use asn1_rs::oer::{OerParser, ToOer};
+use asn1_rs::*;
+use hex_literal::hex;
+
+fn derive_sequence_oer() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Sequence)]
+    #[asn1(parse = "BER,DER,OER", encode = "BER,DER,OER", extensible)]
+    // #[debug_derive]
+    pub struct AA<'a> {
+        #[asn1(value(0..=7))]
+        a: u8,
+        #[optional]
+        b: Option<bool>,
+        #[default(5)]
+        #[asn1(value(0..=255))]
+        c: u16,
+        #[asn1(size(1..=4))]
+        d: OctetString<'a>,
+    }
+
+    let value = AA {
+        a: 5,
+        b: Some(true),
+        c: 200,
+        d: OctetString::new(&hex!("12 34")),
+    };
+
+    // Ok: preamble, then fields
+    let v = value.to_oer_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("60 05 ff c8 02 12 34"));
+    let res = AA::parse_coer(&v).expect("parsing failed");
+    assert_eq!(res, value);
+
+    // Ok: absent OPTIONAL and DEFAULT fields
+    let res = AA::parse_coer(&hex!("00 05 02 12 34")).expect("parsing failed");
+    assert_eq!(res.b, None);
+    assert_eq!(res.c, 5);
+
+    // DEFAULT values are omitted by encoder
+    let value = AA { c: 5, ..value };
+    let v = value.to_oer_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("40 05 ff 02 12 34"));
+
+    // Fail (COER only): DEFAULT value is present
+    let input = &hex!("20 05 05 02 12 34");
+    let res = AA::parse_oer(input).expect("parsing failed");
+    assert_eq!(res.c, 5);
+    let err = AA::parse_coer(input).expect_err("non-canonical");
+    assert_eq!(err, OerError::NonCanonical);
+
+    // Ok: extension additions are skipped
+    let res = AA::parse_oer(&hex!("80 05 02 12 34 02 07 80 01 ff")).expect("parsing failed");
+    assert_eq!(res.a, 5);
+
+    // Fail: value outside constraint
+    let value = AA { a: 8, ..value };
+    let _ = value.to_oer_vec().expect_err("constraint violation");
+}
+
+fn derive_choice_oer() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Choice)]
+    #[tagged_explicit]
+    #[asn1(parse = "BER,OER", encode = "BER,OER", extensible)]
+    // #[debug_derive]
+    pub enum C {
+        #[tag(1)]
+        #[asn1(value(0..=3))]
+        Val1(u8),
+        #[tag(0)]
+        Val0(bool),
+    }
+
+    // tag is context-specific
+    let value = C::Val1(2);
+    let v = value.to_oer_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("81 02"));
+    assert_eq!(C::parse_coer(&v).expect("parsing failed"), value);
+
+    let value = C::Val0(true);
+    let v = value.to_oer_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("80 ff"));
+    assert_eq!(C::parse_coer(&v).expect("parsing failed"), value);
+
+    // Fail: extension addition
+    let err = C::parse_oer(&hex!("82 01 00")).expect_err("unknown extension");
+    assert_eq!(err, OerError::UnknownExtension(2));
+
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Choice)]
+    #[asn1(parse = "BER,OER", encode = "BER,OER")]
+    pub enum U {
+        Int(u32),
+        Flag(bool),
+    }
+
+    // tag is the universal tag of the variant
+    let value = U::Int(5);
+    let v = value.to_oer_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("02 01 05"));
+    assert_eq!(U::parse_coer(&v).expect("parsing failed"), value);
+
+    let err = U::parse_oer(&hex!("04 00")).expect_err("unknown alternative");
+    assert_eq!(err, OerError::InvalidEncoding);
+}
+
+fn derive_enumerated_oer() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    //
+    #[derive(Enumerated)]
+    #[asn1(parse = "BER,OER", encode = "BER,OER", extensible)]
+    pub enum E {
+        A = 0,
+        B = 5,
+        C = 200,
+    }
+
+    // values are encoded directly
+    let v = E::B.to_oer_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("05"));
+    assert_eq!(E::parse_coer(&v).expect("parsing failed"), E::B);
+
+    let v = E::C.to_oer_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("82 00 c8"));
+    assert_eq!(E::parse_coer(&v).expect("parsing failed"), E::C);
+
+    // Fail: extension addition
+    let err = E::parse_oer(&hex!("07")).expect_err("unknown extension");
+    assert_eq!(err, OerError::UnknownExtension(7));
+}
+
+fn derive_alias_oer() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Alias)]
+    #[asn1(parse = "DER,OER", encode = "DER,OER")]
+    pub struct Id(u32);
+
+    let value = Id(1234);
+    let v = value.to_oer_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("02 04 d2"));
+    assert_eq!(Id::parse_coer(&v).expect("parsing failed"), value);
+}
+
+fn main() {
+    derive_sequence_oer();
+    derive_choice_oer();
+    derive_enumerated_oer();
+    derive_alias_oer();
+}
@@ -0,0 +1,118 @@
+use asn1_rs::per::{PerParser, ToPer};
+use asn1_rs::*;
+use hex_literal::hex;
+
+fn derive_sequence_per() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Sequence)]
+    #[asn1(parse = "BER,DER,PER", encode = "BER,DER,PER", extensible)]
+    // #[debug_derive]
+    pub struct AA<'a> {
+        #[asn1(value(0..=7))]
+        a: u8,
+        #[optional]
+        b: Option<bool>,
+        #[default(5)]
+        #[asn1(value(0..=255))]
+        c: u16,
+        #[asn1(size(1..=4))]
+        d: OctetString<'a>,
+    }
+
+    let value = AA {
+        a: 5,
+        b: Some(true),
+        c: 200,
+        d: OctetString::new(&hex!("12 34")),
+    };
+
+    // Ok: aligned variant
+    let v = value.to_aper_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("76 c8 40 12 34"));
+    let res = AA::parse_aper(&v).expect("parsing failed");
+    assert_eq!(res, value);
+
+    // Ok: unaligned variant
+    let v = value.to_uper_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("77 90 89 1a 00"));
+    let res = AA::parse_uper(&v).expect("parsing failed");
+    assert_eq!(res, value);
+
+    // Ok: absent OPTIONAL and DEFAULT fields
+    let res = AA::parse_uper(&hex!("15 12 34")).expect("parsing failed");
+    assert_eq!(res.b, None);
+    assert_eq!(res.c, 5);
+
+    // Fail: value outside constraint
+    let value = AA { a: 8, ..value };
+    let _ = value.to_uper_vec().expect_err("constraint violation");
+}
+
+fn derive_choice_per() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Choice)]
+    #[tagged_explicit]
+    #[asn1(parse = "BER,PER", encode = "BER,PER")]
+    // #[debug_derive]
+    pub enum C {
+        #[tag(1)]
+        #[asn1(value(0..=3))]
+        Val1(u8),
+        #[tag(0)]
+        Val0(bool),
+    }
+
+    // index uses the canonical order of tags
+    let value = C::Val1(2);
+    let v = value.to_uper_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("c0"));
+    assert_eq!(C::parse_uper(&v).expect("parsing failed"), value);
+
+    let value = C::Val0(true);
+    let v = value.to_aper_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("40"));
+    assert_eq!(C::parse_aper(&v).expect("parsing failed"), value);
+}
+
+fn derive_enumerated_per() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    //
+    #[derive(Enumerated)]
+    #[asn1(parse = "BER,PER", encode = "BER,PER", extensible)]
+    pub enum E {
+        A = 0,
+        B = 5,
+        C = 2,
+    }
+
+    // index uses the order of values
+    let v = E::B.to_uper_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("40"));
+    assert_eq!(E::parse_uper(&v).expect("parsing failed"), E::B);
+
+    // Fail: extension addition
+    let err = E::parse_uper(&hex!("80")).expect_err("unknown extension");
+    assert_eq!(err, PerError::UnknownExtension(0));
+}
+
+fn derive_alias_per() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Alias)]
+    #[asn1(parse = "DER,PER", encode = "DER,PER")]
+    pub struct Id(u32);
+
+    let value = Id(1234);
+    let v = value.to_uper_vec().expect("serialization failed");
+    assert_eq!(&v, &hex!("02 04 d2"));
+    assert_eq!(Id::parse_uper(&v).expect("parsing failed"), value);
+}
+
+fn main() {
+    derive_sequence_per();
+    derive_choice_per();
+    derive_enumerated_per();
+    derive_alias_per();
+}
@@ -0,0 +1,125 @@
+use asn1_rs::value_notation::ToValueNotation;
+use asn1_rs::*;
+use hex_literal::hex;
+
+fn derive_sequence_value_notation() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Sequence)]
+    #[asn1(value_notation)]
+    // #[debug_derive]
+    pub struct AlgorithmIdentifier<'a> {
+        algorithm: Oid<'a>,
+        #[optional]
+        parameters: Option<Any<'a>>,
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Sequence)]
+    #[asn1(value_notation)]
+    pub struct AA<'a> {
+        version: u8,
+        #[tag_explicit(0)]
+        #[optional]
+        extra: Option<bool>,
+        #[default(5)]
+        c: u16,
+        r#type: OctetString<'a>,
+        algorithm: AlgorithmIdentifier<'a>,
+    }
+
+    let value = AA {
+        version: 2,
+        extra: None,
+        c: 5,
+        r#type: OctetString::new(&hex!("0a 0b")),
+        algorithm: AlgorithmIdentifier {
+            algorithm: oid!(1.2.840 .113549 .1 .1 .11),
+            parameters: None,
+        },
+    };
+
+    // absent OPTIONAL fields are omitted, identifiers are named after fields
+    assert_eq!(
+        value.to_value_notation(),
+        "{ version 2, c 5, type '0A0B'H, algorithm { algorithm { 1 2 840 113549 1 1 11 } } }"
+    );
+
+    let value = AA {
+        extra: Some(true),
+        ..value
+    };
+    assert_eq!(
+        format!("{}", value.value_notation()),
+        "{ version 2, extra TRUE, c 5, type '0A0B'H, algorithm { algorithm { 1 2 840 113549 1 1 11 } } }"
+    );
+}
+
+fn derive_set_value_notation() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Set)]
+    #[asn1(value_notation)]
+    pub struct S<'a> {
+        name: Utf8String<'a>,
+        values: Vec<u32>,
+    }
+
+    let value = S {
+        name: Utf8String::from("x"),
+        values: vec![1, 2],
+    };
+    assert_eq!(value.to_value_notation(), r#"{ name "x", values { 1, 2 } }"#);
+}
+
+fn derive_choice_value_notation() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Choice)]
+    #[tagged_explicit]
+    #[asn1(value_notation)]
+    pub enum C {
+        #[tag(1)]
+        Val1(u8),
+        #[tag(0)]
+        Val0(bool),
+    }
+
+    assert_eq!(C::Val1(2).to_value_notation(), "Val1 : 2");
+    assert_eq!(
+        vec![C::Val0(true), C::Val1(3)].to_value_notation(),
+        "{ Val0 : TRUE, Val1 : 3 }"
+    );
+}
+
+fn derive_enumerated_value_notation() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    //
+    #[derive(Enumerated)]
+    #[asn1(value_notation)]
+    pub enum E {
+        A = 0,
+        B = 5,
+    }
+
+    assert_eq!(E::B.to_value_notation(), "B");
+}
+
+fn derive_alias_value_notation() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Alias)]
+    #[asn1(value_notation)]
+    pub struct Id(u32);
+
+    assert_eq!(Id(1234).to_value_notation(), "1234");
+}
+
+fn main() {
+    derive_sequence_value_notation();
+    derive_set_value_notation();
+    derive_choice_value_notation();
+    derive_enumerated_value_notation();
+    derive_alias_value_notation();
+}
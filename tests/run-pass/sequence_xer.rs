@@ -0,0 +1,166 @@
+use asn1_rs::xer::{ToXer, XerParser};
+use asn1_rs::*;
+use hex_literal::hex;
+
+fn derive_sequence_xer() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Sequence)]
+    #[asn1(parse = "DER,XER", encode = "DER,XER")]
+    // #[debug_derive]
+    pub struct AA<'a> {
+        a: u8,
+        #[optional]
+        b: Option<bool>,
+        #[default(5)]
+        c: u16,
+        d: OctetString<'a>,
+        r#type: Oid<'a>,
+    }
+
+    let value = AA {
+        a: 5,
+        b: Some(true),
+        c: 200,
+        d: OctetString::new(&hex!("12 ab")),
+        r#type: oid!(1.2.3),
+    };
+
+    // Ok: elements are named after fields
+    let s = value.to_cxer_string().expect("serialization failed");
+    assert_eq!(
+        s,
+        "<AA><a>5</a><b><true/></b><c>200</c><d>12AB</d><type>1.2.3</type></AA>"
+    );
+    let res = AA::parse_xer(&s).expect("parsing failed");
+    assert_eq!(res, value);
+
+    // Ok: BASIC-XER is indented, and round-trips
+    let s = value.to_xer_string().expect("serialization failed");
+    assert_eq!(
+        s,
+        "<AA>\n  <a>5</a>\n  <b>\n    <true/>\n  </b>\n  <c>200</c>\n  <d>12AB</d>\n  <type>1.2.3</type>\n</AA>"
+    );
+    let res = AA::parse_xer(&s).expect("parsing failed");
+    assert_eq!(res, value);
+
+    // Ok: absent OPTIONAL fields, and DEFAULT fields with the default value, are omitted
+    let value = AA {
+        b: None,
+        c: 5,
+        ..value
+    };
+    let s = value.to_cxer_string().expect("serialization failed");
+    assert_eq!(s, "<AA><a>5</a><d>12AB</d><type>1.2.3</type></AA>");
+    let res = AA::parse_xer(&s).expect("parsing failed");
+    assert_eq!(res, value);
+
+    // Fail: missing element
+    let err = AA::parse_xer("<AA><a>1</a></AA>").expect_err("missing element");
+    assert_eq!(err, XerError::MissingElement("d".into()));
+
+    // Fail: wrong root element
+    let err = AA::parse_xer("<BB/>").expect_err("unexpected element");
+    assert_eq!(err, XerError::UnexpectedElement("BB".into()));
+}
+
+fn derive_set_xer() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Set)]
+    #[asn1(parse = "DER,XER", encode = "DER,XER")]
+    pub struct S<'a> {
+        name: Utf8String<'a>,
+        values: Vec<u32>,
+    }
+
+    let value = S {
+        name: Utf8String::from("a&b"),
+        values: vec![1, 2],
+    };
+    let s = value.to_cxer_string().expect("serialization failed");
+    assert_eq!(
+        s,
+        "<S><name>a&amp;b</name><values><INTEGER>1</INTEGER><INTEGER>2</INTEGER></values></S>"
+    );
+    assert_eq!(S::parse_xer(&s).expect("parsing failed"), value);
+}
+
+fn derive_choice_xer() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Choice)]
+    #[tagged_explicit]
+    #[asn1(parse = "BER,XER", encode = "BER,XER")]
+    pub enum C {
+        #[tag(1)]
+        Val1(u8),
+        #[tag(0)]
+        Val0(bool),
+    }
+
+    // Ok: element named after the variant
+    let value = C::Val1(2);
+    let s = value.to_cxer_string().expect("serialization failed");
+    assert_eq!(s, "<C><Val1>2</Val1></C>");
+    assert_eq!(C::parse_xer(&s).expect("parsing failed"), value);
+
+    // Ok: CHOICE items of SEQUENCE OF are written without enclosing element
+    let values = vec![C::Val0(false), C::Val1(3)];
+    let s = values.to_cxer_string().expect("serialization failed");
+    assert_eq!(
+        s,
+        "<SEQUENCE_OF><Val0><false/></Val0><Val1>3</Val1></SEQUENCE_OF>"
+    );
+    assert_eq!(Vec::<C>::parse_xer(&s).expect("parsing failed"), values);
+
+    // Fail: unknown alternative
+    let err = C::parse_xer("<C><Val2><true/></Val2></C>").expect_err("unknown alternative");
+    assert_eq!(err, XerError::UnknownAlternative("Val2".into()));
+
+    // Fail: more than one element
+    let _ = C::parse_xer("<C><Val0><true/></Val0><Val1>2</Val1></C>")
+        .expect_err("too many elements");
+}
+
+fn derive_enumerated_xer() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    //
+    #[derive(Enumerated)]
+    #[asn1(parse = "BER,XER", encode = "BER,XER")]
+    pub enum E {
+        A = 0,
+        B = 5,
+    }
+
+    // Ok: values are encoded as empty elements named after identifiers
+    let s = E::B.to_cxer_string().expect("serialization failed");
+    assert_eq!(s, "<E><B/></E>");
+    assert_eq!(E::parse_xer(&s).expect("parsing failed"), E::B);
+
+    // Fail: unknown identifier, or numerical value
+    let _ = E::parse_xer("<E><C/></E>").expect_err("unknown value");
+    let _ = E::parse_xer("<E>5</E>").expect_err("unexpected content");
+}
+
+fn derive_alias_xer() {
+    #[derive(Debug, PartialEq, Eq)]
+    //
+    #[derive(Alias)]
+    #[asn1(parse = "DER,XER", encode = "DER,XER")]
+    pub struct Id(u32);
+
+    // Ok: root element is named after the alias
+    let value = Id(1234);
+    let s = value.to_cxer_string().expect("serialization failed");
+    assert_eq!(s, "<Id>1234</Id>");
+    assert_eq!(Id::parse_xer(&s).expect("parsing failed"), value);
+}
+
+fn main() {
+    derive_sequence_xer();
+    derive_set_xer();
+    derive_choice_xer();
+    derive_enumerated_xer();
+    derive_alias_xer();
+}
@@ -3,7 +3,7 @@
 use asn1_rs::*;
 use hex_literal::hex;
 // use nom::HexDisplay;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashSet};
 use std::convert::{TryFrom, TryInto};
 use std::iter::FromIterator;
 
@@ -427,6 +427,37 @@ fn to_der_set_of() {
     assert_eq!(seq, seq2);
 }
 
+#[test]
+fn to_der_set_of_sorted() {
+    // components are sorted by encoding, not by insertion or `Ord` order
+    let expected = hex!("31 0d 020101 020102 0201ff 02020102");
+    let s = SetOf::from_iter([0x0102i32, -1, 0x01, 0x02]);
+    let v = s.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &expected);
+    let set = BTreeSet::from_iter([0x0102i32, -1, 0x01, 0x02]);
+    let v = set.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &expected);
+    let set = HashSet::<i32>::from_iter([0x0102i32, -1, 0x01, 0x02]);
+    let v = set.to_der_vec().expect("serialization failed");
+    assert_eq!(&v, &expected);
+
+    let (rem, res) = <SetOf<i32>>::parse_der(Input::from_slice(&v)).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(res.as_ref(), &[1, 2, -1, 0x0102]);
+
+    // Fail: components not sorted
+    let input = &hex!("31 06 020102 020101");
+    let _ = <SetOf<u32>>::parse_der(Input::from_slice(input)).expect_err("not sorted");
+    let _ = <BTreeSet<u32>>::parse_der(Input::from_slice(input)).expect_err("not sorted");
+    let _ = <HashSet<u32>>::parse_der(Input::from_slice(input)).expect_err("not sorted");
+    let _ = <SetOf<u32>>::from_der(input).expect_err("not sorted");
+    let (_, any) = Any::from_ber(input).expect("parsing failed");
+    assert_eq!(
+        <SetOf<u32>>::check_constraints(&any),
+        Err(Error::DerConstraintFailed(DerConstraint::SetOfUnsorted))
+    );
+}
+
 #[test]
 fn to_der_str() {
     let s = "abcdef";
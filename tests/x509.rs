@@ -154,3 +154,45 @@ fn x509_decode_dn() {
     // dbg!(&dn);
     assert_eq!(dn.rdn_sequence.len(), 3);
 }
+
+#[cfg(all(feature = "std", feature = "value-notation"))]
+#[test]
+fn x509_decode_dn_value_notation() {
+    use asn1_rs::value_notation::{parse_value_notation, ToValueNotation};
+    use asn1_rs::ToDer;
+
+    // same DN as in `x509_decode_dn`
+    let value = parse_value_notation(
+        r#"
+{
+    SET : { { { 2 5 4 6 }, PrintableString : "FR" } },   -- countryName
+    SET : { { { 2 5 4 8 }, "Some-State" } },              -- stateOrProvinceName
+    SET : { { { 2 5 4 10 }, "Internet Widgits Pty Ltd" } } -- organizationName
+}
+"#,
+    )
+    .expect("parsing failed");
+    let dn_bytes = value.to_der_vec().expect("serialization failed");
+    assert_eq!(
+        &dn_bytes,
+        &hex!(
+            "
+30 45 31 0b 30 09 06 03 55 04 06 13 02 46 52
+31 13 30 11 06 03 55 04 08 0c 0a 53 6f 6d 65
+2d 53 74 61 74 65 31 21 30 1f 06 03 55 04 0a
+0c 18 49 6e 74 65 72 6e 65 74 20 57 69 64 67
+69 74 73 20 50 74 79 20 4c 74 64
+"
+        )
+    );
+
+    let (rem, dn) = Name::from_der(&dn_bytes).expect("parsing failed");
+    assert!(rem.is_empty());
+    assert_eq!(dn.rdn_sequence.len(), 3);
+
+    let (_, any) = Any::from_der(&dn_bytes).expect("parsing failed");
+    assert_eq!(
+        any.to_value_notation(),
+        r#"{ SET : { { { 2 5 4 6 }, PrintableString : "FR" } }, SET : { { { 2 5 4 8 }, "Some-State" } }, SET : { { { 2 5 4 10 }, "Internet Widgits Pty Ltd" } } }"#
+    );
+}